@@ -0,0 +1,47 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use aphrodite_api::schemas::request::RenderRequest;
+use aphrodite_api::services::ChartService;
+use serde_json::json;
+use std::cell::Cell;
+
+/// Natal and three transit layers; `minute` varies the birth time so
+/// every iteration misses the response cache
+fn multi_layer_request(minute: u32) -> RenderRequest {
+    serde_json::from_value(json!({
+        "subjects": [{
+            "id": "person",
+            "label": "Person",
+            "birthDateTime": format!("1990-01-01T12:{:02}:00Z", minute % 60),
+            "location": { "lat": 40.7128, "lon": -74.0060 }
+        }],
+        "settings": {
+            "zodiacType": "tropical",
+            "houseSystem": "placidus",
+            "includeObjects": ["sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn", "uranus", "neptune", "pluto"]
+        },
+        "layer_config": {
+            "natal": { "kind": "natal", "subjectId": "person" },
+            "transit_now": { "kind": "transit", "explicitDateTime": "2024-03-18T12:00:00Z" },
+            "transit_next": { "kind": "transit", "explicitDateTime": "2025-03-18T12:00:00Z" },
+            "transit_later": { "kind": "transit", "explicitDateTime": "2026-03-18T12:00:00Z" }
+        }
+    }))
+    .unwrap()
+}
+
+fn bench_multi_layer_positions(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let mut service = ChartService::new(None, 1, None).expect("Swiss Ephemeris files are required");
+    let minute = Cell::new(0);
+
+    c.bench_function("get_positions_four_layers", |b| {
+        b.iter(|| {
+            minute.set(minute.get() + 1);
+            let request = multi_layer_request(minute.get());
+            runtime.block_on(service.get_positions(&request)).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, bench_multi_layer_positions);
+criterion_main!(benches);
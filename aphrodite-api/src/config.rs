@@ -1,49 +1,493 @@
+//! Layered application configuration.
+//!
+//! Values are resolved in order: built-in defaults, then a TOML file
+//! (`APHRODITE_CONFIG`, or `aphrodite.toml` in the working directory when present),
+//! then environment variables. Invalid values fail startup instead of falling back.
+
+use crate::middleware::rate_limit::RateLimits;
+use serde::{Deserialize, Serialize};
 use std::env;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Config file read when `APHRODITE_CONFIG` is not set
+pub const DEFAULT_CONFIG_FILE: &str = "aphrodite.toml";
+
+/// Upper bound on pooled services; each holds an ephemeris adapter
+const MAX_SERVICE_POOL_SIZE: usize = 256;
+
+/// Where missing Swiss Ephemeris files are downloaded from
+pub const DEFAULT_EPHEMERIS_DOWNLOAD_URL: &str = "https://raw.githubusercontent.com/aloistr/swisseph/master/ephe";
+
+/// Configuration errors
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read config file {path}: {message}")]
+    Io { path: String, message: String },
+    #[error("Invalid config file {path}: {message}")]
+    Parse { path: String, message: String },
+    #[error("Invalid value for {var} ('{value}'): {message}")]
+    InvalidEnv { var: String, value: String, message: String },
+    #[error("Invalid configuration: {0}")]
+    Invalid(String),
+}
 
 /// Application configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Config {
     pub host: String,
     pub port: u16,
     pub cors_origins: Vec<String>,
     pub swiss_ephemeris_path: Option<String>,
+    /// Base URL the admin endpoint downloads missing ephemeris files from
+    pub ephemeris_download_url: String,
     pub log_level: String,
     pub service_pool_size: usize,
     pub cache_size: usize,
+    pub cache_backend: String,
+    pub cache_url: Option<String>,
+    pub cache_namespace: String,
+    pub cache_ttl_seconds: Option<u64>,
     pub default_wheel_json_path: Option<String>,
+    /// Directory of interpretation content packs (`.toml` and `.json`)
+    pub interpretations_path: Option<String>,
+    pub storage_backend: String,
+    pub storage_url: Option<String>,
+    pub record_requests_path: Option<String>,
+    /// Bearer token for admin endpoints; admin endpoints are disabled when unset
+    pub admin_token: Option<String>,
+    /// Address the admin listener binds to
+    pub admin_host: String,
+    /// Serve metrics and the admin endpoints on this port instead of the public API port
+    pub admin_port: Option<u16>,
+    /// OTLP collector base URL; span export is disabled when unset
+    pub otlp_endpoint: Option<String>,
+    pub otel_service_name: String,
+    /// Seconds to wait for in-flight requests after SIGTERM/SIGINT
+    pub shutdown_timeout_secs: u64,
+    /// Reject unknown request fields and settings_override keys; when false they are ignored
+    pub strict_requests: bool,
+    /// Include the merged chart settings in render responses
+    pub echo_settings: bool,
+    /// Serve the in-development `/api/v2` routes
+    pub api_v2_enabled: bool,
+    /// Sunset date (YYYY-MM-DD) for `/api/v1`; when set, v1 responses carry deprecation headers
+    pub api_v1_sunset: Option<String>,
+    /// Requests per minute per client IP on each rate limited endpoint
+    pub rate_limits: RateLimits,
 }
 
-impl Config {
-    /// Load configuration from environment variables
-    pub fn from_env() -> Self {
+/// Values accepted in the TOML file; every key is optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileConfig {
+    host: Option<String>,
+    port: Option<u16>,
+    cors_origins: Option<Vec<String>>,
+    swiss_ephemeris_path: Option<String>,
+    ephemeris_download_url: Option<String>,
+    log_level: Option<String>,
+    service_pool_size: Option<usize>,
+    cache_size: Option<usize>,
+    cache_backend: Option<String>,
+    cache_url: Option<String>,
+    cache_namespace: Option<String>,
+    cache_ttl_seconds: Option<u64>,
+    default_wheel_json_path: Option<String>,
+    interpretations_path: Option<String>,
+    storage_backend: Option<String>,
+    storage_url: Option<String>,
+    record_requests_path: Option<String>,
+    admin_token: Option<String>,
+    admin_host: Option<String>,
+    admin_port: Option<u16>,
+    otlp_endpoint: Option<String>,
+    otel_service_name: Option<String>,
+    shutdown_timeout_secs: Option<u64>,
+    strict_requests: Option<bool>,
+    echo_settings: Option<bool>,
+    api_v2_enabled: Option<bool>,
+    api_v1_sunset: Option<String>,
+    /// Partial tables keep the defaults for endpoints left out
+    rate_limits: Option<RateLimits>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
         Self {
-            host: env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
-            port: env::var("PORT")
-                .unwrap_or_else(|_| "8000".to_string())
-                .parse()
-                .unwrap_or(8000),
-            cors_origins: env::var("CORS_ORIGINS")
-                .unwrap_or_else(|_| "http://localhost:3000,http://localhost:5173".to_string())
-                .split(',')
-                .map(|s| s.trim().to_string())
-                .collect(),
-            swiss_ephemeris_path: env::var("SWISS_EPHEMERIS_PATH").ok(),
-            log_level: env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
-            service_pool_size: env::var("SERVICE_POOL_SIZE")
-                .unwrap_or_else(|_| "4".to_string())
-                .parse()
-                .unwrap_or(4),
-            cache_size: env::var("CACHE_SIZE")
-                .unwrap_or_else(|_| "1000".to_string())
-                .parse()
-                .unwrap_or(1000),
-            default_wheel_json_path: env::var("DEFAULT_WHEEL_JSON_PATH")
-                .ok()
-                .or_else(|| {
-                    // Default to wheels/default.json relative to the executable or current directory
-                    Some("wheels/default.json".to_string())
-                }),
+            host: "0.0.0.0".to_string(),
+            port: 8000,
+            cors_origins: vec!["http://localhost:3000".to_string(), "http://localhost:5173".to_string()],
+            swiss_ephemeris_path: None,
+            ephemeris_download_url: DEFAULT_EPHEMERIS_DOWNLOAD_URL.to_string(),
+            log_level: "info".to_string(),
+            service_pool_size: 4,
+            cache_size: 1000,
+            cache_backend: "memory".to_string(),
+            cache_url: None,
+            cache_namespace: "aphrodite".to_string(),
+            cache_ttl_seconds: None,
+            // Default to wheels/default.json relative to the current directory
+            default_wheel_json_path: Some("wheels/default.json".to_string()),
+            interpretations_path: Some("interpretations".to_string()),
+            storage_backend: "memory".to_string(),
+            storage_url: None,
+            record_requests_path: None,
+            admin_token: None,
+            admin_host: "127.0.0.1".to_string(),
+            admin_port: None,
+            otlp_endpoint: None,
+            otel_service_name: "aphrodite-api".to_string(),
+            shutdown_timeout_secs: 30,
+            strict_requests: true,
+            echo_settings: true,
+            api_v2_enabled: false,
+            api_v1_sunset: None,
+            rate_limits: RateLimits::default(),
         }
     }
 }
 
+impl Config {
+    /// Load defaults, the config file and environment overrides, then validate
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        match env::var("APHRODITE_CONFIG").ok().filter(|p| !p.is_empty()) {
+            Some(path) => config.apply_file(Path::new(&path))?,
+            None if Path::new(DEFAULT_CONFIG_FILE).exists() => config.apply_file(Path::new(DEFAULT_CONFIG_FILE))?,
+            None => {}
+        }
+        config.apply_env()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse a TOML document over the defaults, without env overrides or validation
+    pub fn from_toml_str(contents: &str) -> Result<Self, ConfigError> {
+        let mut config = Self::default();
+        config.apply_toml(contents, "<inline>")?;
+        Ok(config)
+    }
+
+    fn apply_file(&mut self, path: &Path) -> Result<(), ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|e| ConfigError::Io {
+            path: path.display().to_string(),
+            message: e.to_string(),
+        })?;
+        self.apply_toml(&contents, &path.display().to_string())
+    }
+
+    fn apply_toml(&mut self, contents: &str, path: &str) -> Result<(), ConfigError> {
+        let file: FileConfig = toml::from_str(contents).map_err(|e| ConfigError::Parse {
+            path: path.to_string(),
+            message: e.to_string(),
+        })?;
+
+        macro_rules! set {
+            ($($field:ident),* $(,)?) => {
+                $(if let Some(value) = file.$field { self.$field = value; })*
+            };
+        }
+        macro_rules! set_optional {
+            ($($field:ident),* $(,)?) => {
+                $(if let Some(value) = file.$field { self.$field = Some(value); })*
+            };
+        }
+        set!(
+            host, port, cors_origins, ephemeris_download_url, log_level, service_pool_size, cache_size, cache_backend,
+            cache_namespace, storage_backend, otel_service_name, shutdown_timeout_secs, strict_requests,
+            echo_settings, api_v2_enabled, rate_limits, admin_host,
+        );
+        set_optional!(
+            swiss_ephemeris_path, cache_url, cache_ttl_seconds, default_wheel_json_path, interpretations_path,
+            storage_url, record_requests_path, admin_token, admin_port, otlp_endpoint, api_v1_sunset,
+        );
+        Ok(())
+    }
+
+    fn apply_env(&mut self) -> Result<(), ConfigError> {
+        if let Some(host) = env_string("HOST") {
+            self.host = host;
+        }
+        env_parse("PORT", &mut self.port)?;
+        if let Some(origins) = env_string("CORS_ORIGINS") {
+            self.cors_origins = origins.split(',').map(|s| s.trim().to_string()).collect();
+        }
+        if let Some(path) = env_string("SWISS_EPHEMERIS_PATH") {
+            self.swiss_ephemeris_path = Some(path);
+        }
+        if let Some(url) = env_string("EPHEMERIS_DOWNLOAD_URL") {
+            self.ephemeris_download_url = url;
+        }
+        if let Some(level) = env_string("RUST_LOG") {
+            self.log_level = level;
+        }
+        env_parse("SERVICE_POOL_SIZE", &mut self.service_pool_size)?;
+        env_parse("CACHE_SIZE", &mut self.cache_size)?;
+        if let Some(backend) = env_string("CACHE_BACKEND") {
+            self.cache_backend = backend;
+        }
+        if let Some(url) = env_string("CACHE_URL").or_else(|| env_string("REDIS_URL")) {
+            self.cache_url = Some(url);
+        }
+        if let Some(namespace) = env_string("CACHE_NAMESPACE") {
+            self.cache_namespace = namespace;
+        }
+        let mut ttl = self.cache_ttl_seconds.unwrap_or(0);
+        env_parse("CACHE_TTL_SECONDS", &mut ttl)?;
+        // 0 keeps entries until evicted
+        self.cache_ttl_seconds = Some(ttl).filter(|ttl| *ttl > 0);
+        if let Some(path) = env_string("DEFAULT_WHEEL_JSON_PATH") {
+            self.default_wheel_json_path = Some(path);
+        }
+        if let Some(path) = env_string("INTERPRETATIONS_PATH") {
+            self.interpretations_path = Some(path);
+        }
+        if let Some(backend) = env_string("STORAGE_BACKEND") {
+            self.storage_backend = backend;
+        }
+        if let Some(url) = env_string("STORAGE_URL").or_else(|| env_string("DATABASE_URL")) {
+            self.storage_url = Some(url);
+        }
+        if let Some(path) = env_string("RECORD_REQUESTS_PATH") {
+            self.record_requests_path = Some(path);
+        }
+        if let Some(token) = env_string("ADMIN_TOKEN") {
+            self.admin_token = Some(token);
+        }
+        if let Some(host) = env_string("ADMIN_HOST") {
+            self.admin_host = host;
+        }
+        let mut admin_port = self.admin_port.unwrap_or(0);
+        env_parse("ADMIN_PORT", &mut admin_port)?;
+        // 0 serves the admin endpoints on the public port
+        self.admin_port = Some(admin_port).filter(|port| *port > 0);
+        if let Some(endpoint) = env_string("OTEL_EXPORTER_OTLP_ENDPOINT") {
+            self.otlp_endpoint = Some(endpoint);
+        }
+        if let Some(name) = env_string("OTEL_SERVICE_NAME") {
+            self.otel_service_name = name;
+        }
+        env_parse("SHUTDOWN_TIMEOUT_SECONDS", &mut self.shutdown_timeout_secs)?;
+        env_parse("STRICT_REQUESTS", &mut self.strict_requests)?;
+        env_parse("ECHO_SETTINGS", &mut self.echo_settings)?;
+        env_parse("API_V2_ENABLED", &mut self.api_v2_enabled)?;
+        if let Some(date) = env_string("API_V1_SUNSET") {
+            self.api_v1_sunset = Some(date);
+        }
+        for (endpoint, limit) in self.rate_limits.entries_mut() {
+            env_parse(&format!("RATE_LIMIT_{}", endpoint.to_uppercase()), limit)?;
+        }
+        Ok(())
+    }
+
+    /// Check value ranges and backend names
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.port == 0 {
+            return Err(ConfigError::Invalid("port must be between 1 and 65535".to_string()));
+        }
+        if self.host.trim().is_empty() {
+            return Err(ConfigError::Invalid("host must not be empty".to_string()));
+        }
+        if let Some(admin_port) = self.admin_port {
+            if admin_port == 0 || admin_port == self.port {
+                return Err(ConfigError::Invalid(format!(
+                    "admin_port must be between 1 and 65535 and differ from port ({})",
+                    self.port
+                )));
+            }
+            if self.admin_host.parse::<std::net::IpAddr>().is_err() {
+                return Err(ConfigError::Invalid(format!("admin_host '{}' must be an IP address", self.admin_host)));
+            }
+        }
+        if !(1..=MAX_SERVICE_POOL_SIZE).contains(&self.service_pool_size) {
+            return Err(ConfigError::Invalid(format!(
+                "service_pool_size must be between 1 and {}",
+                MAX_SERVICE_POOL_SIZE
+            )));
+        }
+        if self.cache_size == 0 {
+            return Err(ConfigError::Invalid("cache_size must be at least 1".to_string()));
+        }
+        if self.cache_namespace.is_empty() {
+            return Err(ConfigError::Invalid("cache_namespace must not be empty".to_string()));
+        }
+        let storage_backends = crate::storage::available_backends();
+        if !storage_backends.contains(&self.storage_backend.as_str()) {
+            return Err(ConfigError::Invalid(format!(
+                "unsupported storage_backend '{}' (available: {})",
+                self.storage_backend,
+                storage_backends.join(", ")
+            )));
+        }
+        let cache_backends = crate::services::cache::available_backends();
+        if !cache_backends.contains(&self.cache_backend.as_str()) {
+            return Err(ConfigError::Invalid(format!(
+                "unsupported cache_backend '{}' (available: {})",
+                self.cache_backend,
+                cache_backends.join(", ")
+            )));
+        }
+        if self.cache_backend == "redis" && self.cache_url.is_none() {
+            return Err(ConfigError::Invalid("cache_url is required for the redis cache backend".to_string()));
+        }
+        if let Some(path) = &self.swiss_ephemeris_path {
+            if !PathBuf::from(path).exists() {
+                return Err(ConfigError::Invalid(format!("swiss_ephemeris_path '{}' does not exist", path)));
+            }
+        }
+        if !["http://", "https://"].iter().any(|scheme| self.ephemeris_download_url.starts_with(scheme)) {
+            return Err(ConfigError::Invalid(format!(
+                "ephemeris_download_url '{}' must be an http(s) URL",
+                self.ephemeris_download_url
+            )));
+        }
+        let mut rate_limits = self.rate_limits.clone();
+        if let Some((endpoint, _)) = rate_limits.entries_mut().into_iter().find(|(_, limit)| **limit == 0) {
+            return Err(ConfigError::Invalid(format!("rate_limits.{} must be at least 1 request per minute", endpoint)));
+        }
+        if let Some(date) = &self.api_v1_sunset {
+            if chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").is_err() {
+                return Err(ConfigError::Invalid(format!("api_v1_sunset '{}' must be a YYYY-MM-DD date", date)));
+            }
+        }
+        Ok(())
+    }
+
+    /// Parsed `api_v1_sunset`; `None` when unset or invalid
+    pub fn api_v1_sunset_date(&self) -> Option<chrono::NaiveDate> {
+        self.api_v1_sunset
+            .as_deref()
+            .and_then(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+    }
+
+    /// Render the effective configuration as TOML with secrets redacted
+    pub fn to_redacted_toml(&self) -> String {
+        let mut redacted = self.clone();
+        for secret in [&mut redacted.admin_token, &mut redacted.storage_url, &mut redacted.cache_url] {
+            if secret.is_some() {
+                *secret = Some("<redacted>".to_string());
+            }
+        }
+        toml::to_string_pretty(&redacted).unwrap_or_else(|e| format!("# failed to render config: {}\n", e))
+    }
+}
+
+/// Non-empty environment variable
+fn env_string(var: &str) -> Option<String> {
+    env::var(var).ok().filter(|value| !value.is_empty())
+}
+
+/// Parse an environment variable into `target` when set
+fn env_parse<T>(var: &str, target: &mut T) -> Result<(), ConfigError>
+where
+    T: FromStr,
+    T::Err: std::fmt::Display,
+{
+    if let Some(value) = env_string(var) {
+        *target = value.trim().parse().map_err(|e: T::Err| ConfigError::InvalidEnv {
+            var: var.to_string(),
+            value: value.clone(),
+            message: e.to_string(),
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toml_overrides_defaults() {
+        let config = Config::from_toml_str(
+            r#"
+            port = 9000
+            service_pool_size = 8
+            cache_ttl_seconds = 600
+            cors_origins = ["https://example.com"]
+            echo_settings = false
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.port, 9000);
+        assert_eq!(config.service_pool_size, 8);
+        assert_eq!(config.cache_ttl_seconds, Some(600));
+        assert_eq!(config.cors_origins, vec!["https://example.com"]);
+        assert!(!config.echo_settings);
+        assert_eq!(config.cache_size, 1000);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_invalid_values_fail() {
+        assert!(matches!(Config::from_toml_str("port = 70000"), Err(ConfigError::Parse { .. })));
+        assert!(matches!(Config::from_toml_str("pool_size = 4"), Err(ConfigError::Parse { .. })));
+
+        let config = Config::from_toml_str("port = 0").unwrap();
+        assert!(config.validate().is_err());
+        let config = Config::from_toml_str("service_pool_size = 0").unwrap();
+        assert!(config.validate().is_err());
+        let config = Config::from_toml_str("storage_backend = \"oracle\"").unwrap();
+        assert!(config.validate().is_err());
+        let config = Config::from_toml_str("ephemeris_download_url = \"ftp://example.com/ephe\"").unwrap();
+        assert!(config.validate().is_err());
+        let config = Config::from_toml_str("api_v1_sunset = \"next year\"").unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_rate_limits() {
+        let config = Config::from_toml_str("[rate_limits]\nrender = 200\nrectification = 2").unwrap();
+        assert_eq!(config.rate_limits.render, 200);
+        assert_eq!(config.rate_limits.rectification, 2);
+        assert_eq!(config.rate_limits.chartspec, RateLimits::default().chartspec);
+        assert!(config.validate().is_ok());
+        assert!(config.to_redacted_toml().contains("[rate_limits]"));
+
+        let config = Config::from_toml_str("[rate_limits]\nmuhurta = 0").unwrap();
+        assert!(config.validate().unwrap_err().to_string().contains("rate_limits.muhurta"));
+        assert!(matches!(Config::from_toml_str("[rate_limits]\nhealth = 10"), Err(ConfigError::Parse { .. })));
+    }
+
+    #[test]
+    fn test_admin_listener() {
+        let config = Config::from_toml_str("admin_port = 9100").unwrap();
+        assert_eq!(config.admin_port, Some(9100));
+        assert_eq!(config.admin_host, "127.0.0.1");
+        assert!(config.validate().is_ok());
+
+        let config = Config::from_toml_str("admin_port = 8000").unwrap();
+        assert!(config.validate().unwrap_err().to_string().contains("admin_port"));
+        let config = Config::from_toml_str("admin_port = 9100\nadmin_host = \"localhost\"").unwrap();
+        assert!(config.validate().unwrap_err().to_string().contains("admin_host"));
+    }
+
+    #[test]
+    fn test_api_v1_sunset_date() {
+        let config = Config::from_toml_str("api_v1_sunset = \"2027-06-30\"").unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.api_v1_sunset_date(), chrono::NaiveDate::from_ymd_opt(2027, 6, 30));
+        assert_eq!(Config::default().api_v1_sunset_date(), None);
+    }
+
+    #[test]
+    fn test_env_parse_rejects_garbage() {
+        let mut port: u16 = 8000;
+        env::set_var("APHRODITE_TEST_PORT", "eighty");
+        let result = env_parse("APHRODITE_TEST_PORT", &mut port);
+        env::remove_var("APHRODITE_TEST_PORT");
+        assert!(matches!(result, Err(ConfigError::InvalidEnv { .. })));
+        assert_eq!(port, 8000);
+    }
+
+    #[test]
+    fn test_redacted_toml_hides_secrets() {
+        let config = Config::from_toml_str("admin_token = \"hunter2\"\nstorage_url = \"postgres://u:p@db/aphrodite\"").unwrap();
+        let rendered = config.to_redacted_toml();
+        assert!(!rendered.contains("hunter2"));
+        assert!(!rendered.contains("u:p@db"));
+        assert!(rendered.contains("port = 8000"));
+    }
+}
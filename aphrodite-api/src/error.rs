@@ -1,46 +1,117 @@
+use aphrodite_core::ErrorCategory;
 use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
     Json,
 };
+use serde::Serialize;
 use serde_json::json;
 use thiserror::Error;
 use uuid::Uuid;
 
+/// Machine-readable reason for a field validation failure
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum FieldErrorCode {
+    /// A required value is missing
+    Required,
+    /// The value cannot be parsed (e.g. a malformed datetime)
+    InvalidFormat,
+    /// A number or date is outside the accepted range
+    OutOfRange,
+    /// The value is not one of the accepted options
+    InvalidValue,
+    /// The value repeats another entry that must be unique
+    Duplicate,
+    /// The value refers to something not present in the request
+    UnknownReference,
+    /// The value conflicts with another field
+    Conflict,
+    /// The field is not part of the request schema (strict mode)
+    UnknownField,
+}
+
+/// A validation failure tied to a request field
+#[derive(Debug, Clone, Serialize)]
+pub struct FieldError {
+    /// Path to the field in the request JSON, e.g. `subjects[0].location.lat`
+    pub field: String,
+    pub code: FieldErrorCode,
+    pub message: String,
+}
+
 /// API error types
 #[derive(Error, Debug)]
 pub enum ApiError {
     #[error("Validation error: {0}")]
     ValidationError(String),
+    #[error("Validation error: {}", join_messages(.0))]
+    InvalidFields(Vec<FieldError>),
     #[error("Calculation error: {0}")]
     CalculationError(String),
     #[error("Not found: {0}")]
     NotFound(String),
-    #[error("Rate limit exceeded")]
-    RateLimitExceeded,
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    #[error("Rate limit exceeded; retry in {retry_after}s")]
+    RateLimitExceeded {
+        /// Seconds until the next request is allowed
+        retry_after: u64,
+    },
     #[error("Internal server error: {0}")]
     InternalError(String),
+    /// An error from the calculation engine, reported by its category with the engine's code
+    #[error("{}", core_message(.0))]
+    Core(aphrodite_core::Error),
 }
 
 impl ApiError {
     fn status_code(&self) -> StatusCode {
         match self {
-            ApiError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            ApiError::ValidationError(_) | ApiError::InvalidFields(_) => StatusCode::BAD_REQUEST,
             ApiError::CalculationError(_) => StatusCode::BAD_REQUEST,
             ApiError::NotFound(_) => StatusCode::NOT_FOUND,
-            ApiError::RateLimitExceeded => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            ApiError::RateLimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
             ApiError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ApiError::Core(err) => match err.category() {
+                ErrorCategory::InvalidInput | ErrorCategory::Calculation => StatusCode::BAD_REQUEST,
+                ErrorCategory::EphemerisData => StatusCode::INTERNAL_SERVER_ERROR,
+            },
         }
     }
 
     fn error_code(&self) -> &'static str {
         match self {
-            ApiError::ValidationError(_) => "VALIDATION_ERROR",
+            ApiError::ValidationError(_) | ApiError::InvalidFields(_) => "VALIDATION_ERROR",
             ApiError::CalculationError(_) => "CALCULATION_ERROR",
             ApiError::NotFound(_) => "NOT_FOUND",
-            ApiError::RateLimitExceeded => "RATE_LIMIT_EXCEEDED",
+            ApiError::Unauthorized(_) => "UNAUTHORIZED",
+            ApiError::RateLimitExceeded { .. } => "RATE_LIMIT_EXCEEDED",
             ApiError::InternalError(_) => "INTERNAL_ERROR",
+            ApiError::Core(err) => match err.category() {
+                ErrorCategory::InvalidInput => "VALIDATION_ERROR",
+                ErrorCategory::EphemerisData => "INTERNAL_ERROR",
+                ErrorCategory::Calculation => "CALCULATION_ERROR",
+            },
+        }
+    }
+}
+
+impl ApiError {
+    /// The `error` object of an error response: code, message and any field details
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut error = json!({
+            "code": self.error_code(),
+            "message": self.to_string(),
+        });
+        match self {
+            ApiError::InvalidFields(details) => error["details"] = json!(details),
+            ApiError::RateLimitExceeded { retry_after } => error["retry_after"] = json!(retry_after),
+            ApiError::Core(err) => error["reason"] = json!(err.code()),
+            _ => {}
         }
+        error
     }
 }
 
@@ -48,13 +119,9 @@ impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         let correlation_id = Uuid::new_v4().to_string();
         let status = self.status_code();
-        let error_response = json!({
-            "error": {
-                "code": self.error_code(),
-                "message": self.to_string(),
-                "correlation_id": correlation_id,
-            }
-        });
+        let mut error = self.to_json();
+        error["correlation_id"] = json!(correlation_id);
+        let error_response = json!({ "error": error });
 
         tracing::error!(
             error = %self,
@@ -66,56 +133,76 @@ impl IntoResponse for ApiError {
     }
 }
 
+fn join_messages(errors: &[FieldError]) -> String {
+    errors.iter().map(|e| e.message.as_str()).collect::<Vec<_>>().join("; ")
+}
+
+/// Core errors read like the API error their category maps to
+fn core_message(err: &aphrodite_core::Error) -> String {
+    match err.category() {
+        ErrorCategory::InvalidInput => format!("Validation error: {}", err),
+        ErrorCategory::EphemerisData => format!("Internal server error: {}", err),
+        ErrorCategory::Calculation => format!("Calculation error: {}", err),
+    }
+}
+
 /// Convert core library errors to API errors
-impl From<aphrodite_core::ephemeris::adapter::EphemerisError> for ApiError {
-    fn from(err: aphrodite_core::ephemeris::adapter::EphemerisError) -> Self {
-        match err {
-            aphrodite_core::ephemeris::adapter::EphemerisError::FileNotFound { path, message } => {
-                ApiError::InternalError(format!("Ephemeris file not found at {}: {}", path, message))
-            }
-            aphrodite_core::ephemeris::adapter::EphemerisError::InvalidHouseSystem { system, valid } => {
-                ApiError::ValidationError(format!(
-                    "Invalid house system: {}. Valid systems: {:?}",
-                    system, valid
-                ))
-            }
-            aphrodite_core::ephemeris::adapter::EphemerisError::InvalidAyanamsa { ayanamsa, valid } => {
-                ApiError::ValidationError(format!(
-                    "Invalid ayanamsa: {}. Valid ayanamsas: {:?}",
-                    ayanamsa, valid
-                ))
-            }
-            aphrodite_core::ephemeris::adapter::EphemerisError::CalculationFailed {
-                planet_id,
-                datetime,
-                message,
-            } => ApiError::CalculationError(format!(
-                "Failed to calculate position for {} at {}: {}",
-                planet_id, datetime, message
-            )),
-            aphrodite_core::ephemeris::adapter::EphemerisError::HouseCalculationFailed { message } => {
-                ApiError::CalculationError(format!("House calculation failed: {}", message))
-            }
-        }
+impl From<aphrodite_core::Error> for ApiError {
+    fn from(err: aphrodite_core::Error) -> Self {
+        ApiError::Core(err)
+    }
+}
+
+impl From<aphrodite_core::ephemeris::EphemerisError> for ApiError {
+    fn from(err: aphrodite_core::ephemeris::EphemerisError) -> Self {
+        aphrodite_core::Error::from(err).into()
     }
 }
 
 impl From<aphrodite_core::layout::WheelDefinitionError> for ApiError {
     fn from(err: aphrodite_core::layout::WheelDefinitionError) -> Self {
+        aphrodite_core::Error::from(err).into()
+    }
+}
+
+impl From<aphrodite_core::interpretations::InterpretationError> for ApiError {
+    fn from(err: aphrodite_core::interpretations::InterpretationError) -> Self {
         match err {
-            aphrodite_core::layout::WheelDefinitionError::InvalidJson(msg) => {
-                ApiError::ValidationError(format!("Invalid wheel definition JSON: {}", msg))
-            }
-            aphrodite_core::layout::WheelDefinitionError::ValidationError(msg) => {
-                ApiError::ValidationError(format!("Wheel definition validation error: {}", msg))
-            }
-            aphrodite_core::layout::WheelDefinitionError::MissingField(field) => {
-                ApiError::ValidationError(format!("Missing required field in wheel definition: {}", field))
-            }
-            aphrodite_core::layout::WheelDefinitionError::InvalidFieldValue(msg) => {
-                ApiError::ValidationError(format!("Invalid field value in wheel definition: {}", msg))
+            aphrodite_core::interpretations::InterpretationError::UnknownPack { .. } => ApiError::NotFound(err.to_string()),
+            other => ApiError::InternalError(other.to_string()),
+        }
+    }
+}
+
+impl From<crate::storage::StorageError> for ApiError {
+    fn from(err: crate::storage::StorageError) -> Self {
+        match err {
+            crate::storage::StorageError::NotFound { collection, id } => {
+                ApiError::NotFound(format!("{}/{}", collection, id))
             }
+            other => ApiError::InternalError(other.to_string()),
         }
     }
 }
 
+impl From<crate::services::cache::CacheError> for ApiError {
+    fn from(err: crate::services::cache::CacheError) -> Self {
+        ApiError::InternalError(err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aphrodite_core::ephemeris::EphemerisError;
+
+    #[test]
+    fn test_core_error_keeps_its_code() {
+        let error = ApiError::from(EphemerisError::InvalidHouseSystem { system: "x".to_string(), valid: vec![] });
+        assert_eq!(error.status_code(), StatusCode::BAD_REQUEST);
+        let json = error.to_json();
+        assert_eq!(json["code"], "VALIDATION_ERROR");
+        assert_eq!(json["reason"], "INVALID_HOUSE_SYSTEM");
+        assert_eq!(json["message"], "Validation error: Invalid house system: x. Valid systems: []");
+    }
+}
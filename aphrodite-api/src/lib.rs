@@ -1,11 +1,15 @@
 pub mod config;
 pub mod error;
 pub mod middleware;
+pub mod projection;
+pub mod replay;
 pub mod routes;
 pub mod schemas;
 pub mod services;
+pub mod storage;
+pub mod telemetry;
 pub mod validation;
 
 pub use error::ApiError;
-pub use validation::RequestValidator;
+pub use validation::{RequestValidator, ValidatedJson};
 
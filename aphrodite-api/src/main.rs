@@ -1,26 +1,47 @@
 use aphrodite_api::config::Config;
 use aphrodite_api::routes;
-use std::net::SocketAddr;
+use aphrodite_core::layout::{validate_wheel_definition, WHEEL_DEFINITION_SCHEMA};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
 use tower::ServiceBuilder;
 use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
-use tracing_subscriber::EnvFilter;
 
 #[tokio::main]
 async fn main() {
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            EnvFilter::try_from_default_env()
-                .unwrap_or_else(|_| EnvFilter::new("aphrodite_api=info,tower_http=debug")),
-        )
-        .init();
+    // Wheel authoring helpers; they need no configuration
+    let args: Vec<String> = std::env::args().collect();
+    if args.iter().any(|arg| arg == "--print-wheel-schema") {
+        print!("{}", WHEEL_DEFINITION_SCHEMA);
+        return;
+    }
+    if let Some(i) = args.iter().position(|arg| arg == "--lint-wheel") {
+        let Some(path) = args.get(i + 1) else {
+            eprintln!("Usage: --lint-wheel <wheel.json>");
+            std::process::exit(2);
+        };
+        std::process::exit(lint_wheel_file(path));
+    }
 
-    // Load configuration
-    let config = Config::from_env();
+    // Load configuration; invalid values stop startup
+    let config = Config::load().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+
+    if args.iter().any(|arg| arg == "--print-config") {
+        print!("{}", config.to_redacted_toml());
+        return;
+    }
+
+    // Initialize tracing (and OTLP export when configured)
+    let _telemetry = aphrodite_api::telemetry::init(&config);
 
     // Build application with middleware
-    let app = routes::create_router()
+    let routers = routes::create_routers_with_config(config.clone());
+    let app = routers.api
         .layer(
             ServiceBuilder::new()
                 .layer(TraceLayer::new_for_http())
@@ -32,23 +53,120 @@ async fn main() {
     let addr = SocketAddr::from(([0, 0, 0, 0], config.port));
     tracing::info!("Starting Aphrodite API server on {}", addr);
 
-    let listener = tokio::net::TcpListener::bind(addr).await
-        .unwrap_or_else(|e| {
-            eprintln!("Failed to bind to {}: {}", addr, e);
-            eprintln!("Error: {}", e);
-            if e.kind() == std::io::ErrorKind::AddrInUse {
-                eprintln!("Port {} is already in use. Please stop the existing server or use a different port.", config.port);
-            }
-            std::process::exit(1);
-        });
+    let listener = bind(addr).await;
 
     tracing::info!("Server listening on {}", addr);
 
-    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
-        .await
-        .unwrap_or_else(|e| {
-            eprintln!("Server error: {}", e);
-            std::process::exit(1);
+    // Metrics and admin endpoints on their own port, without CORS; the address was
+    // checked by Config::validate
+    if let (Some(admin), Some(admin_port)) = (routers.admin, config.admin_port) {
+        let admin_host: IpAddr = config.admin_host.parse().expect("admin_host is validated");
+        let admin_addr = SocketAddr::new(admin_host, admin_port);
+        let admin_listener = bind(admin_addr).await;
+        tracing::info!("Admin endpoints listening on {}", admin_addr);
+        tokio::spawn(async move {
+            let admin = admin.layer(TraceLayer::new_for_http());
+            if let Err(e) = axum::serve(admin_listener, admin).with_graceful_shutdown(shutdown_signal()).await {
+                tracing::error!(error = %e, "Admin server error");
+            }
         });
+    }
+
+    // Stop accepting connections on SIGTERM/SIGINT and let in-flight requests finish,
+    // up to the configured drain timeout
+    let draining = Arc::new(Notify::new());
+    let drain_timeout = Duration::from_secs(config.shutdown_timeout_secs);
+    let server = axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown({
+            let draining = draining.clone();
+            async move {
+                shutdown_signal().await;
+                tracing::info!(timeout_secs = drain_timeout.as_secs(), "Shutdown signal received, draining in-flight requests");
+                draining.notify_one();
+            }
+        });
+
+    tokio::select! {
+        result = server => {
+            if let Err(e) = result {
+                eprintln!("Server error: {}", e);
+                std::process::exit(1);
+            }
+            tracing::info!("Server stopped");
+        }
+        _ = async {
+            draining.notified().await;
+            tokio::time::sleep(drain_timeout).await;
+        } => {
+            tracing::warn!("Drain timeout elapsed, abandoning in-flight requests");
+        }
+    }
+}
+
+/// Bind a TCP listener, exiting with a message when the address is unavailable
+async fn bind(addr: SocketAddr) -> tokio::net::TcpListener {
+    tokio::net::TcpListener::bind(addr).await.unwrap_or_else(|e| {
+        eprintln!("Failed to bind to {}: {}", addr, e);
+        eprintln!("Error: {}", e);
+        if e.kind() == std::io::ErrorKind::AddrInUse {
+            eprintln!("Port {} is already in use. Please stop the existing server or use a different port.", addr.port());
+        }
+        std::process::exit(1);
+    })
 }
 
+/// Resolve when the process receives SIGINT (Ctrl+C) or, on Unix, SIGTERM
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            tracing::error!(error = %e, "Failed to listen for Ctrl+C");
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to listen for SIGTERM");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+
+/// Print lint warnings for a wheel file. Exit code 1 when the wheel does not load.
+fn lint_wheel_file(path: &str) -> i32 {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("Failed to read {}: {}", path, e);
+            return 1;
+        }
+    };
+    match validate_wheel_definition(&json, None) {
+        Ok(warnings) => {
+            for warning in &warnings {
+                println!("{}: {} {}", warning.path, warning.code.as_str(), warning.message);
+            }
+            println!("{}: {} warning(s)", path, warnings.len());
+            0
+        }
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            1
+        }
+    }
+}
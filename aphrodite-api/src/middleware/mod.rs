@@ -1,4 +1,7 @@
 pub mod rate_limit;
+pub mod recorder;
+pub mod versioning;
 
 pub use rate_limit::rate_limit_layer;
-
+pub use recorder::record_exchange;
+pub use versioning::{version_headers, ApiVersion, Deprecation};
@@ -1,5 +1,11 @@
+use crate::error::ApiError;
+use axum::body::Body;
+use axum::http::{header::RETRY_AFTER, Response};
+use axum::response::IntoResponse;
+use governor::middleware::StateInformationMiddleware;
+use serde::{Deserialize, Serialize};
 use tower_governor::governor::{GovernorConfigBuilder, GovernorConfig};
-use tower_governor::GovernorLayer;
+use tower_governor::{GovernorError, GovernorLayer};
 use tower_governor::key_extractor::PeerIpKeyExtractor;
 use std::sync::Arc;
 
@@ -30,8 +36,9 @@ pub fn rate_limit_config(config: RateLimitConfig) -> Arc<GovernorConfig<PeerIpKe
     )
 }
 
-/// Create a rate limit layer for an endpoint
-pub fn rate_limit_layer(config: RateLimitConfig) -> GovernorLayer<'static, PeerIpKeyExtractor, governor::middleware::NoOpMiddleware> {
+/// Create a rate limit layer for an endpoint. Responses carry `x-ratelimit-limit` and
+/// `x-ratelimit-remaining`, plus `x-ratelimit-after` once the limit is hit.
+pub fn rate_limit_layer(config: RateLimitConfig) -> GovernorLayer<'static, PeerIpKeyExtractor, StateInformationMiddleware> {
     // Calculate per_second, ensuring it's at least 1
     let per_second = ((config.requests_per_minute as f64) / 60.0).ceil().max(1.0) as u64;
     
@@ -40,6 +47,8 @@ pub fn rate_limit_layer(config: RateLimitConfig) -> GovernorLayer<'static, PeerI
         GovernorConfigBuilder::default()
             .per_second(per_second)
             .burst_size(config.requests_per_minute)
+            .use_headers()
+            .error_handler(error_response)
             .finish()
             .expect("Failed to create rate limit layer: invalid configuration")
     ));
@@ -49,20 +58,83 @@ pub fn rate_limit_layer(config: RateLimitConfig) -> GovernorLayer<'static, PeerI
     }
 }
 
-/// Default rate limits
-pub mod limits {
-    use super::RateLimitConfig;
-
-    pub fn render() -> RateLimitConfig {
-        RateLimitConfig::new(50) // 50 requests per minute
+/// Rate limiter rejections in the ApiError envelope, keeping the rate limit headers
+fn error_response(error: GovernorError) -> Response<Body> {
+    match error {
+        GovernorError::TooManyRequests { wait_time, headers } => {
+            let mut response = ApiError::RateLimitExceeded { retry_after: wait_time }.into_response();
+            response.headers_mut().extend(headers.unwrap_or_default());
+            response.headers_mut().insert(RETRY_AFTER, wait_time.into());
+            response
+        }
+        GovernorError::UnableToExtractKey => {
+            ApiError::InternalError("Could not determine the client address for rate limiting".to_string())
+                .into_response()
+        }
+        GovernorError::Other { msg, headers, .. } => {
+            let mut response = ApiError::InternalError(msg.unwrap_or_else(|| "Rate limiter failed".to_string()))
+                .into_response();
+            response.headers_mut().extend(headers.unwrap_or_default());
+            response
+        }
     }
+}
 
-    pub fn chartspec() -> RateLimitConfig {
-        RateLimitConfig::new(50) // 50 requests per minute
-    }
+/// Requests per minute allowed per client IP on each rate limited endpoint, set in the
+/// `[rate_limits]` config table or `RATE_LIMIT_<ENDPOINT>` environment variables
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimits {
+    pub render: u32,
+    pub chartspec: u32,
+    /// Each request samples a date range
+    pub ephemeris: u32,
+    /// A single moment, no layers
+    pub positions: u32,
+    pub synastry: u32,
+    /// Each request samples a date range
+    pub muhurta: u32,
+    /// Each request samples a date range
+    pub electional: u32,
+    /// Each request scores hundreds of charts
+    pub rectification: u32,
+    /// Each request samples a date range
+    pub transits: u32,
+    /// One chart per request
+    pub interpretations: u32,
+}
 
-    pub fn health() -> RateLimitConfig {
-        RateLimitConfig::new(100) // 100 requests per minute
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            render: 50,
+            chartspec: 50,
+            ephemeris: 20,
+            positions: 120,
+            synastry: 50,
+            muhurta: 20,
+            electional: 20,
+            rectification: 10,
+            transits: 20,
+            interpretations: 50,
+        }
     }
 }
 
+impl RateLimits {
+    /// Every limit keyed by endpoint name, for env overrides and validation
+    pub fn entries_mut(&mut self) -> [(&'static str, &mut u32); 10] {
+        [
+            ("render", &mut self.render),
+            ("chartspec", &mut self.chartspec),
+            ("ephemeris", &mut self.ephemeris),
+            ("positions", &mut self.positions),
+            ("synastry", &mut self.synastry),
+            ("muhurta", &mut self.muhurta),
+            ("electional", &mut self.electional),
+            ("rectification", &mut self.rectification),
+            ("transits", &mut self.transits),
+            ("interpretations", &mut self.interpretations),
+        ]
+    }
+}
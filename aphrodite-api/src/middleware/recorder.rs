@@ -0,0 +1,97 @@
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{header, Method};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::Utc;
+use std::sync::Arc;
+use tokio_stream::StreamExt;
+
+use crate::replay::{anonymize, RecordedExchange, Recorder, MAX_RECORDED_BODY_BYTES};
+
+/// Record anonymized API exchanges for later replay.
+///
+/// Only JSON POST requests under `/api/` are captured; bodies are buffered so the
+/// request and response pass through unchanged. Event streams and bodies over
+/// [`MAX_RECORDED_BODY_BYTES`] are passed through without being recorded.
+pub async fn record_exchange(
+    State(recorder): State<Arc<Recorder>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::POST || !request.uri().path().starts_with("/api/") {
+        return next.run(request).await;
+    }
+
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let (parts, body) = request.into_parts();
+    let request_bytes = match buffer_body(body).await {
+        Ok(bytes) => bytes,
+        Err(body) => return next.run(Request::from_parts(parts, body)).await,
+    };
+    let request = Request::from_parts(parts, Body::from(request_bytes.clone()));
+
+    let response = next.run(request).await;
+    if is_event_stream(&response) {
+        return response;
+    }
+    let (parts, body) = response.into_parts();
+    let response_bytes = match buffer_body(body).await {
+        Ok(bytes) => bytes,
+        Err(body) => return Response::from_parts(parts, body),
+    };
+
+    // Non-JSON exchanges (e.g. rejected bodies) are not replayable
+    if let (Ok(mut request_body), Ok(response_body)) = (
+        serde_json::from_slice(&request_bytes),
+        serde_json::from_slice(&response_bytes),
+    ) {
+        anonymize(&mut request_body);
+        let exchange = RecordedExchange {
+            method,
+            path,
+            request_body,
+            status: parts.status.as_u16(),
+            response_body,
+            recorded_at: Utc::now(),
+        };
+        if let Err(e) = recorder.record(&exchange).await {
+            tracing::warn!(error = %e, path = %recorder.path().display(), "Failed to record exchange");
+        }
+    }
+
+    Response::from_parts(parts, Body::from(response_bytes))
+}
+
+/// Streamed responses never end on their own, so they can't be buffered
+fn is_event_stream(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/event-stream"))
+}
+
+/// Buffer a body of at most [`MAX_RECORDED_BODY_BYTES`].
+///
+/// A larger (or failing) body is handed back whole, with the bytes read so far
+/// replayed ahead of the rest of the stream, so it can still be forwarded.
+async fn buffer_body(body: Body) -> Result<Bytes, Body> {
+    let mut stream = body.into_data_stream();
+    let mut buffered = Vec::new();
+
+    while let Some(chunk) = stream.next().await {
+        match chunk {
+            Ok(chunk) if buffered.len() + chunk.len() <= MAX_RECORDED_BODY_BYTES => {
+                buffered.extend_from_slice(&chunk);
+            }
+            chunk => {
+                let head = tokio_stream::iter([Ok(Bytes::from(buffered)), chunk]);
+                return Err(Body::from_stream(head.chain(stream)));
+            }
+        }
+    }
+
+    Ok(Bytes::from(buffered))
+}
@@ -0,0 +1,51 @@
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderValue};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::NaiveDate;
+use std::sync::Arc;
+
+/// Version metadata attached to every response of an API version
+#[derive(Debug, Clone)]
+pub struct ApiVersion {
+    /// Major version, sent in the `API-Version` header
+    pub version: u32,
+    /// Set once the version is deprecated
+    pub deprecation: Option<Deprecation>,
+}
+
+/// Deprecation notice, sent as `Deprecation`, `Sunset` (RFC 8594) and `Link` headers
+#[derive(Debug, Clone)]
+pub struct Deprecation {
+    /// Date after which the version may be removed
+    pub sunset: Option<NaiveDate>,
+    /// Path prefix of the version replacing this one, e.g. `/api/v2`
+    pub successor: Option<String>,
+}
+
+/// Add version and deprecation headers to responses
+pub async fn version_headers(
+    State(version): State<Arc<ApiVersion>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert("api-version", HeaderValue::from(version.version));
+
+    if let Some(deprecation) = &version.deprecation {
+        headers.insert("deprecation", HeaderValue::from_static("true"));
+        if let Some(sunset) = deprecation.sunset {
+            let date = sunset.and_time(chrono::NaiveTime::MIN).and_utc();
+            if let Ok(value) = HeaderValue::from_str(&date.format("%a, %d %b %Y %H:%M:%S GMT").to_string()) {
+                headers.insert("sunset", value);
+            }
+        }
+        if let Some(successor) = &deprecation.successor {
+            if let Ok(value) = HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", successor)) {
+                headers.insert(header::LINK, value);
+            }
+        }
+    }
+    response
+}
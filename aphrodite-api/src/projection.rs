@@ -0,0 +1,198 @@
+//! Response field selection.
+//!
+//! Render responses accept `?fields=` and `?exclude=` with comma-separated dotted paths
+//! into the response JSON, e.g. `fields=layers.natal.positions.planets,western`. A path
+//! keeps or drops the whole subtree it names; `*` matches any key, and paths continue
+//! through arrays into each element. `fields` is applied first, then `exclude`. Paths
+//! that match nothing are ignored, since layer ids and object ids vary per request.
+
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Matches any key in a path
+const WILDCARD: &str = "*";
+
+/// Query string of the render endpoints
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldSelection {
+    /// Comma-separated paths to keep; everything is kept when absent
+    pub fields: Option<String>,
+    /// Comma-separated paths to drop
+    pub exclude: Option<String>,
+}
+
+impl FieldSelection {
+    /// Paths of `fields`, split into segments
+    pub fn field_paths(&self) -> Vec<Vec<&str>> {
+        split_paths(self.fields.as_deref())
+    }
+
+    /// Paths of `exclude`, split into segments
+    pub fn exclude_paths(&self) -> Vec<Vec<&str>> {
+        split_paths(self.exclude.as_deref())
+    }
+
+    /// Also drop `path`
+    pub fn excluding(mut self, path: &str) -> Self {
+        self.exclude = Some(match self.exclude.take() {
+            Some(exclude) => format!("{},{}", exclude, path),
+            None => path.to_string(),
+        });
+        self
+    }
+
+    /// Project a serialized response
+    pub fn apply(&self, value: Value) -> Value {
+        let fields = self.field_paths();
+        let mut value = if fields.is_empty() {
+            value
+        } else {
+            let paths: Vec<&[&str]> = fields.iter().map(Vec::as_slice).collect();
+            select(&value, &paths).unwrap_or_else(|| Value::Object(Map::new()))
+        };
+        for path in self.exclude_paths() {
+            remove(&mut value, &path);
+        }
+        value
+    }
+
+    fn is_empty(&self) -> bool {
+        self.field_paths().is_empty() && self.exclude_paths().is_empty()
+    }
+}
+
+/// JSON response projected with a [`FieldSelection`]
+pub struct Projected<T>(pub T, pub FieldSelection);
+
+impl<T: Serialize> IntoResponse for Projected<T> {
+    fn into_response(self) -> Response {
+        let Projected(response, selection) = self;
+        if selection.is_empty() {
+            return Json(response).into_response();
+        }
+        match serde_json::to_value(&response) {
+            Ok(value) => Json(selection.apply(value)).into_response(),
+            Err(e) => crate::error::ApiError::InternalError(format!("Failed to serialize response: {}", e)).into_response(),
+        }
+    }
+}
+
+fn split_paths(list: Option<&str>) -> Vec<Vec<&str>> {
+    list.into_iter()
+        .flat_map(|list| list.split(','))
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .map(|path| path.split('.').collect())
+        .collect()
+}
+
+/// Copy of `value` with only the subtrees named by `paths`; `None` when nothing matched
+fn select(value: &Value, paths: &[&[&str]]) -> Option<Value> {
+    if paths.iter().any(|path| path.is_empty()) {
+        return Some(value.clone());
+    }
+    match value {
+        Value::Object(map) => {
+            let selected: Map<String, Value> = map
+                .iter()
+                .filter_map(|(key, child)| {
+                    let rest: Vec<&[&str]> = paths
+                        .iter()
+                        .filter(|path| path[0] == key || path[0] == WILDCARD)
+                        .map(|path| &path[1..])
+                        .collect();
+                    if rest.is_empty() {
+                        return None;
+                    }
+                    select(child, &rest).map(|child| (key.clone(), child))
+                })
+                .collect();
+            (!selected.is_empty()).then_some(Value::Object(selected))
+        }
+        Value::Array(items) => {
+            let selected: Vec<Option<Value>> = items.iter().map(|item| select(item, paths)).collect();
+            selected
+                .iter()
+                .any(Option::is_some)
+                .then(|| Value::Array(selected.into_iter().map(|item| item.unwrap_or(Value::Null)).collect()))
+        }
+        _ => None,
+    }
+}
+
+/// Drop the subtrees named by `path`
+fn remove(value: &mut Value, path: &[&str]) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    match value {
+        Value::Object(map) if rest.is_empty() => {
+            if *first == WILDCARD {
+                map.clear();
+            } else {
+                map.remove(*first);
+            }
+        }
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if key == first || *first == WILDCARD {
+                    remove(child, rest);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| remove(item, path)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn selection(fields: Option<&str>, exclude: Option<&str>) -> FieldSelection {
+        FieldSelection {
+            fields: fields.map(str::to_string),
+            exclude: exclude.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_field_selection() {
+        let response = json!({
+            "layers": {
+                "natal": { "id": "natal", "positions": { "planets": { "sun": { "lon": 1.0 } }, "houses": {} } },
+                "transit": { "id": "transit", "positions": { "planets": {} } }
+            },
+            "settings": { "zodiacType": "tropical" },
+            "western": { "natal": { "dignities": [{ "planet": "sun", "score": 5 }] } }
+        });
+
+        let projected = selection(Some("layers.natal.positions.planets, western"), None).apply(response.clone());
+        assert_eq!(
+            projected,
+            json!({
+                "layers": { "natal": { "positions": { "planets": { "sun": { "lon": 1.0 } } } } },
+                "western": { "natal": { "dignities": [{ "planet": "sun", "score": 5 }] } }
+            })
+        );
+
+        let projected = selection(Some("layers.*.id,western.natal.dignities.planet"), Some("layers.transit")).apply(response.clone());
+        assert_eq!(
+            projected,
+            json!({
+                "layers": { "natal": { "id": "natal" } },
+                "western": { "natal": { "dignities": [{ "planet": "sun" }] } }
+            })
+        );
+
+        let projected = selection(None, Some("layers.*.positions.houses")).excluding("settings").apply(response.clone());
+        assert!(projected["layers"]["natal"]["positions"].get("houses").is_none());
+        assert!(projected.get("settings").is_none());
+        assert_eq!(projected["western"], response["western"]);
+
+        assert_eq!(selection(Some("vedic"), None).apply(response), json!({}));
+    }
+}
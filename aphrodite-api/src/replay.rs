@@ -0,0 +1,320 @@
+//! Request recording and replay.
+//!
+//! When `RECORD_REQUESTS_PATH` is set, API exchanges are anonymized and appended to a
+//! JSON-lines file. The recordings can later be replayed against a new build with
+//! [`replay_exchanges`], which compares responses structurally (keys, value types and
+//! array lengths) so refactors can be checked against real traffic shapes.
+
+use axum::body::{to_bytes, Body};
+use axum::extract::ConnectInfo;
+use axum::http::Request;
+use axum::Router;
+use chrono::{DateTime, Datelike, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+use tower::ServiceExt;
+
+/// Maximum body size captured or replayed
+pub const MAX_RECORDED_BODY_BYTES: usize = 1024 * 1024;
+
+/// Decimal places kept for recorded coordinates (~1 km)
+const COORDINATE_PRECISION: i32 = 2;
+
+/// A recorded request/response pair
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub method: String,
+    pub path: String,
+    #[serde(rename = "requestBody")]
+    pub request_body: Value,
+    pub status: u16,
+    #[serde(rename = "responseBody")]
+    pub response_body: Value,
+    #[serde(rename = "recordedAt")]
+    pub recorded_at: DateTime<Utc>,
+}
+
+/// Appends anonymized exchanges to a JSON-lines file
+pub struct Recorder {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl Recorder {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append an exchange to the recording file
+    pub async fn record(&self, exchange: &RecordedExchange) -> std::io::Result<()> {
+        let mut line = serde_json::to_string(exchange)?;
+        line.push('\n');
+
+        let _guard = self.lock.lock().await;
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        file.write_all(line.as_bytes()).await?;
+        file.flush().await
+    }
+}
+
+/// Strip identifying details from a request body.
+///
+/// Subject labels are replaced with "Subject N", birth times are blurred to noon UTC
+/// on January 1st of the birth year, location names are removed and coordinates are
+/// rounded. Ids are kept because layers reference them.
+pub fn anonymize(body: &mut Value) {
+    if let Some(subjects) = body.get_mut("subjects").and_then(Value::as_array_mut) {
+        for (idx, subject) in subjects.iter_mut().enumerate() {
+            if let Some(obj) = subject.as_object_mut() {
+                obj.remove("name");
+                obj.insert("label".to_string(), Value::String(format!("Subject {}", idx + 1)));
+                blur_birth_date_time(obj);
+            }
+        }
+    }
+    anonymize_locations(body);
+}
+
+/// Keep only the birth year so replays still calculate a chart; drop anything unparseable
+fn blur_birth_date_time(subject: &mut serde_json::Map<String, Value>) {
+    let year = subject
+        .get("birthDateTime")
+        .and_then(Value::as_str)
+        .and_then(|dt| DateTime::parse_from_rfc3339(dt).ok())
+        .map(|dt| dt.year());
+    match year {
+        Some(year) => {
+            subject.insert(
+                "birthDateTime".to_string(),
+                Value::String(format!("{:04}-01-01T12:00:00Z", year)),
+            );
+        }
+        None => {
+            subject.remove("birthDateTime");
+        }
+    }
+    subject.remove("birthTimezone");
+}
+
+fn anonymize_locations(value: &mut Value) {
+    match value {
+        Value::Object(obj) => {
+            if let Some(Value::Object(location)) = obj.get_mut("location") {
+                location.remove("name");
+                for key in ["lat", "lon"] {
+                    if let Some(coord) = location.get(key).and_then(Value::as_f64) {
+                        location.insert(key.to_string(), Value::from(round_coordinate(coord)));
+                    }
+                }
+            }
+            for child in obj.values_mut() {
+                anonymize_locations(child);
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(anonymize_locations),
+        _ => {}
+    }
+}
+
+fn round_coordinate(value: f64) -> f64 {
+    let factor = 10f64.powi(COORDINATE_PRECISION);
+    (value * factor).round() / factor
+}
+
+/// Read recorded exchanges from a JSON-lines file
+pub fn read_recordings(path: &Path) -> Result<Vec<RecordedExchange>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read recordings '{}': {}", path.display(), e))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line).map_err(|e| format!("Invalid recording on line {}: {}", idx + 1, e))
+        })
+        .collect()
+}
+
+/// Compare two JSON documents structurally.
+///
+/// Object keys, value types and array lengths must match; scalar values are not
+/// compared. Returns a list of differences keyed by JSON path.
+pub fn compare_structure(expected: &Value, actual: &Value) -> Vec<String> {
+    let mut differences = Vec::new();
+    compare_at("$", expected, actual, &mut differences);
+    differences
+}
+
+fn compare_at(path: &str, expected: &Value, actual: &Value, differences: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(e), Value::Object(a)) => {
+            for (key, e_value) in e {
+                let child = format!("{}.{}", path, key);
+                match a.get(key) {
+                    Some(a_value) => compare_at(&child, e_value, a_value, differences),
+                    None => differences.push(format!("{}: missing", child)),
+                }
+            }
+            for key in a.keys().filter(|key| !e.contains_key(*key)) {
+                differences.push(format!("{}.{}: unexpected", path, key));
+            }
+        }
+        (Value::Array(e), Value::Array(a)) => {
+            if e.len() != a.len() {
+                differences.push(format!("{}: expected {} items, got {}", path, e.len(), a.len()));
+            }
+            for (idx, (e_item, a_item)) in e.iter().zip(a.iter()).enumerate() {
+                compare_at(&format!("{}[{}]", path, idx), e_item, a_item, differences);
+            }
+        }
+        _ => {
+            let (e_type, a_type) = (type_name(expected), type_name(actual));
+            if e_type != a_type {
+                differences.push(format!("{}: expected {}, got {}", path, e_type, a_type));
+            }
+        }
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Result of replaying a single exchange
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayOutcome {
+    pub index: usize,
+    pub path: String,
+    #[serde(rename = "expectedStatus")]
+    pub expected_status: u16,
+    #[serde(rename = "actualStatus")]
+    pub actual_status: u16,
+    pub differences: Vec<String>,
+}
+
+impl ReplayOutcome {
+    pub fn passed(&self) -> bool {
+        self.expected_status == self.actual_status && self.differences.is_empty()
+    }
+}
+
+/// Replay recorded exchanges against a router and compare the responses.
+///
+/// Requests are sent in-process with a loopback peer address so per-IP rate limiting works.
+pub async fn replay_exchanges(router: Router, exchanges: &[RecordedExchange]) -> Vec<ReplayOutcome> {
+    let mut outcomes = Vec::with_capacity(exchanges.len());
+
+    for (index, exchange) in exchanges.iter().enumerate() {
+        let mut request = Request::builder()
+            .method(exchange.method.as_str())
+            .uri(exchange.path.as_str())
+            .header("content-type", "application/json")
+            .body(Body::from(exchange.request_body.to_string()))
+            .expect("recorded method and path form a valid request");
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 0))));
+
+        let response = match router.clone().oneshot(request).await {
+            Ok(response) => response,
+            Err(never) => match never {},
+        };
+        let actual_status = response.status().as_u16();
+        let body = to_bytes(response.into_body(), MAX_RECORDED_BODY_BYTES)
+            .await
+            .unwrap_or_default();
+        let actual_body: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+
+        // Scalar values (positions, correlation ids) vary between builds and recordings
+        let differences = compare_structure(&exchange.response_body, &actual_body);
+
+        outcomes.push(ReplayOutcome {
+            index,
+            path: exchange.path.clone(),
+            expected_status: exchange.status,
+            actual_status,
+            differences,
+        });
+    }
+
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_anonymize() {
+        let mut body = json!({
+            "subjects": [{
+                "id": "p1",
+                "label": "Jane Doe",
+                "birthDateTime": "1990-06-15T08:30:00Z",
+                "location": { "name": "Springfield", "lat": 40.712776, "lon": -74.005974 }
+            }, {
+                "id": "p2",
+                "birthDateTime": "1984-12-31T23:30:00-05:00"
+            }, {
+                "id": "p3",
+                "birthDateTime": "last tuesday"
+            }],
+            "layer_config": {
+                "transit": { "kind": "transit", "location": { "name": "Home", "lat": 1.23456, "lon": 2.0 } }
+            }
+        });
+        anonymize(&mut body);
+
+        assert_eq!(body["subjects"][0]["id"], "p1");
+        assert_eq!(body["subjects"][0]["label"], "Subject 1");
+        assert_eq!(body["subjects"][0]["birthDateTime"], "1990-01-01T12:00:00Z");
+        assert_eq!(body["subjects"][1]["birthDateTime"], "1984-01-01T12:00:00Z");
+        assert!(body["subjects"][2].get("birthDateTime").is_none());
+        assert!(body["subjects"][0]["location"].get("name").is_none());
+        assert_eq!(body["subjects"][0]["location"]["lat"], 40.71);
+        assert_eq!(body["subjects"][0]["location"]["lon"], -74.01);
+        assert!(body["layer_config"]["transit"]["location"].get("name").is_none());
+        assert_eq!(body["layer_config"]["transit"]["location"]["lat"], 1.23);
+    }
+
+    #[test]
+    fn test_compare_structure_ignores_scalar_values() {
+        let expected = json!({ "a": 1.0, "b": ["x", "y"], "c": { "d": true } });
+        let actual = json!({ "a": 2.5, "b": ["z", "w"], "c": { "d": false } });
+        assert!(compare_structure(&expected, &actual).is_empty());
+    }
+
+    #[test]
+    fn test_compare_structure_reports_differences() {
+        let expected = json!({ "a": 1, "b": [1, 2], "c": "s" });
+        let actual = json!({ "b": [1], "c": 3, "extra": null });
+        let differences = compare_structure(&expected, &actual);
+        assert!(differences.contains(&"$.a: missing".to_string()));
+        assert!(differences.contains(&"$.b: expected 2 items, got 1".to_string()));
+        assert!(differences.contains(&"$.c: expected string, got number".to_string()));
+        assert!(differences.contains(&"$.extra: unexpected".to_string()));
+    }
+}
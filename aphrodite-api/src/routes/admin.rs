@@ -0,0 +1,122 @@
+use axum::{
+    extract::State,
+    http::{header, HeaderMap},
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use crate::error::ApiError;
+use crate::routes::cache::{self, require_admin};
+use crate::routes::AppState;
+use crate::schemas::request::EphemerisDownloadRequest;
+use crate::schemas::response::{EphemerisDownloadResponse, EphemerisFilesResponse, WheelReloadResponse};
+use crate::services::ephemeris_files;
+use crate::validation::ValidatedJson;
+use std::fmt::Write;
+use std::path::PathBuf;
+
+/// Cache and admin routes, shared by `/api/v1` and the admin listener
+pub(super) fn router() -> Router<AppState> {
+    Router::new()
+        .route("/cache/stats", get(cache::cache_stats))
+        .route("/cache/clear", post(cache::clear_cache))
+        .route("/admin/ephemeris", get(ephemeris_files))
+        .route("/admin/ephemeris/download", post(download_ephemeris_files))
+        .route("/admin/wheel/reload", post(reload_default_wheel))
+}
+
+/// Routes served on `admin_port`: the admin routes plus metrics and the config dump
+pub(super) fn listener_router() -> Router<AppState> {
+    router()
+        .route("/metrics", get(metrics))
+        .route("/admin/config", get(config_dump))
+}
+
+/// Prometheus text exposition of the response cache and service pool
+pub async fn metrics(State(state): State<AppState>) -> Result<impl IntoResponse, ApiError> {
+    let stats = state.service_pool.cache().stats().await?;
+    let mut body = String::new();
+    let cache = format!("{{backend=\"{}\"}}", stats.backend);
+    let mut metric = |name: &str, kind: &str, help: &str, labels: &str, value: u64| {
+        let _ = writeln!(body, "# HELP {} {}", name, help);
+        let _ = writeln!(body, "# TYPE {} {}", name, kind);
+        let _ = writeln!(body, "{}{} {}", name, labels, value);
+    };
+    metric("aphrodite_cache_hits_total", "counter", "Response cache hits since startup", &cache, stats.hits);
+    metric("aphrodite_cache_misses_total", "counter", "Response cache misses since startup", &cache, stats.misses);
+    metric("aphrodite_cache_entries", "gauge", "Responses currently cached", &cache, stats.entries);
+    if let Some(capacity) = stats.capacity {
+        metric("aphrodite_cache_capacity", "gauge", "Maximum number of cached responses", &cache, capacity);
+    }
+    if let Some(evictions) = stats.evictions {
+        metric("aphrodite_cache_evictions_total", "counter", "Responses dropped for capacity or expiry", &cache, evictions);
+    }
+    metric("aphrodite_service_pool_size", "gauge", "Chart services in the pool", "", state.service_pool.size() as u64);
+    Ok(([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], body))
+}
+
+/// Effective configuration as TOML with secrets redacted (admin only)
+pub async fn config_dump(State(state): State<AppState>, headers: HeaderMap) -> Result<impl IntoResponse, ApiError> {
+    require_admin(&state, &headers)?;
+    Ok(([(header::CONTENT_TYPE, "application/toml")], state.config.to_redacted_toml()))
+}
+
+/// Installed ephemeris files and the years they cover (admin only)
+pub async fn ephemeris_files(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<EphemerisFilesResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+    let dir = ephemeris_dir(&state).await;
+    Ok(Json(file_status(dir).await?))
+}
+
+/// Download ephemeris files, by default the missing standard ones (admin only)
+pub async fn download_ephemeris_files(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    ValidatedJson(request): ValidatedJson<EphemerisDownloadRequest>,
+) -> Result<Json<EphemerisDownloadResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+    let dir = ephemeris_dir(&state).await;
+    let names = match request.files {
+        Some(files) => files,
+        None => file_status(dir.clone()).await?.missing_standard_files,
+    };
+    let response = ephemeris_files::download_files(&dir, &state.ephemeris_download_url, names).await?;
+
+    // Cached responses may have been calculated with the Moshier fallback or without asteroids,
+    // and the Swiss Ephemeris only looks for the new files once it reopens the directory
+    if !response.downloaded.is_empty() {
+        aphrodite_core::ephemeris::reload_ephemeris_files();
+        state.service_pool.cache().clear().await?;
+    }
+    Ok(Json(response))
+}
+
+/// List the ephemeris directory off the async runtime
+async fn file_status(dir: PathBuf) -> Result<EphemerisFilesResponse, ApiError> {
+    tokio::task::spawn_blocking(move || ephemeris_files::file_status(&dir))
+        .await
+        .map_err(|e| ApiError::InternalError(format!("File listing task failed: {}", e)))?
+}
+
+/// Re-read the default wheel file, keeping the current wheel if the new one is invalid (admin only)
+pub async fn reload_default_wheel(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<WheelReloadResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+    let default_wheel = state.service_pool.default_wheel().clone();
+    let response = tokio::task::spawn_blocking(move || default_wheel.reload())
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Wheel reload task failed: {}", e)))??;
+    Ok(Json(response))
+}
+
+/// Directory the pooled services read ephemeris files from
+async fn ephemeris_dir(state: &AppState) -> PathBuf {
+    let service = state.service_pool.get_service();
+    let service = service.lock().await;
+    service.ephemeris_path().to_path_buf()
+}
@@ -0,0 +1,47 @@
+use axum::{extract::State, http::HeaderMap, Json};
+use crate::error::ApiError;
+use crate::routes::AppState;
+use crate::schemas::response::CacheClearResponse;
+use crate::services::CacheStats;
+
+/// Response cache statistics endpoint
+pub async fn cache_stats(State(state): State<AppState>) -> Result<Json<CacheStats>, ApiError> {
+    let stats = state.service_pool.cache().stats().await?;
+    Ok(Json(stats))
+}
+
+/// Clear the response cache (admin only)
+pub async fn clear_cache(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<CacheClearResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let cache = state.service_pool.cache();
+    let cleared = cache.stats().await?.entries;
+    cache.clear().await?;
+    tracing::info!(cleared, backend = cache.backend(), "Response cache cleared");
+    Ok(Json(CacheClearResponse { cleared }))
+}
+
+/// Check the `Authorization: Bearer <token>` header against the configured admin token
+pub(super) fn require_admin(state: &AppState, headers: &HeaderMap) -> Result<(), ApiError> {
+    let expected = state
+        .admin_token
+        .as_deref()
+        .ok_or_else(|| ApiError::Unauthorized("admin endpoints are disabled (ADMIN_TOKEN is not set)".to_string()))?;
+    let provided = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .ok_or_else(|| ApiError::Unauthorized("missing bearer token".to_string()))?;
+
+    if !constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+        return Err(ApiError::Unauthorized("invalid admin token".to_string()));
+    }
+    Ok(())
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,20 @@
+use axum::{extract::State, Json};
+use crate::error::ApiError;
+use crate::routes::AppState;
+use crate::schemas::request::ElectionalSearchRequest;
+use crate::schemas::response::ElectionalSearchResponse;
+use crate::validation::ValidatedJson;
+
+/// Electional search endpoint
+pub async fn search_electional(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<ElectionalSearchRequest>,
+) -> Result<Json<ElectionalSearchResponse>, ApiError> {
+    // The search runs on its own adapter, so the pooled service is only held while resolving it
+    let search = {
+        let service = state.service_pool.get_service();
+        let service = service.lock().await;
+        service.electional_search(&request)?
+    };
+    Ok(Json(search.run().await?))
+}
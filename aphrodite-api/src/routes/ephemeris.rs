@@ -0,0 +1,21 @@
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Query, State};
+use axum::Json;
+use crate::error::ApiError;
+use crate::routes::AppState;
+use crate::schemas::request::EphemerisTableQuery;
+use crate::schemas::response::EphemerisTableResponse;
+use crate::validation::Validate;
+
+/// Ephemeris table endpoint: object positions at regular steps
+pub async fn ephemeris_table(
+    State(state): State<AppState>,
+    query: Result<Query<EphemerisTableQuery>, QueryRejection>,
+) -> Result<Json<EphemerisTableResponse>, ApiError> {
+    let Query(query) = query.map_err(|rejection| ApiError::ValidationError(rejection.body_text()))?;
+    query.validate()?;
+    let service = state.service_pool.get_service();
+    let service = service.lock().await;
+    let response = service.get_ephemeris_table(&query).await?;
+    Ok(Json(response))
+}
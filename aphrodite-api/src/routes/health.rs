@@ -1,16 +1,24 @@
-use axum::Json;
-use crate::schemas::response::{ApiInfoResponse, HealthResponse};
+use axum::{extract::State, http::StatusCode, Json};
+use std::time::Duration;
+use crate::routes::AppState;
+use crate::schemas::response::{
+    ApiInfoResponse, EphemerisReadiness, HealthResponse, PoolReadiness, ReadinessResponse,
+};
+
+/// How long the readiness probe waits for a pooled service
+const READINESS_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// API info endpoint
-pub async fn api_info() -> Json<ApiInfoResponse> {
+pub async fn api_info(State(state): State<AppState>) -> Json<ApiInfoResponse> {
     Json(ApiInfoResponse {
         name: "Aphrodite API".to_string(),
         version: "0.1.0".to_string(),
         description: "Rust-based astrology charting API".to_string(),
+        rate_limits: state.rate_limits.clone(),
     })
 }
 
-/// Health check endpoint
+/// Health check endpoint (liveness)
 pub async fn health_check() -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "ok".to_string(),
@@ -18,3 +26,38 @@ pub async fn health_check() -> Json<HealthResponse> {
     })
 }
 
+/// Readiness endpoint: runs a trivial calculation through a pooled service
+pub async fn readiness_check(State(state): State<AppState>) -> (StatusCode, Json<ReadinessResponse>) {
+    let service = state.service_pool.get_service();
+    let (available, backend, path, check) = match tokio::time::timeout(READINESS_TIMEOUT, service.lock()).await {
+        Ok(service) => {
+            let backend = service.ephemeris_backend().to_string();
+            let path = service.ephemeris_path().display().to_string();
+            (true, Some(backend), Some(path), service.check_ephemeris().await)
+        }
+        Err(_) => (false, None, None, Err(crate::error::ApiError::InternalError(
+            "timed out waiting for a pooled service".to_string(),
+        ))),
+    };
+
+    let ready = check.is_ok();
+    let response = ReadinessResponse {
+        status: if ready { "ready" } else { "not_ready" }.to_string(),
+        version: "0.1.0".to_string(),
+        ephemeris: EphemerisReadiness {
+            backend,
+            path,
+            ok: ready,
+            error: check.err().map(|e| e.to_string()),
+        },
+        pool: PoolReadiness {
+            size: state.service_pool.size(),
+            available,
+        },
+        storage_backend: state.storage.backend().to_string(),
+        cache_backend: state.service_pool.cache().backend().to_string(),
+    };
+
+    let status = if ready { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(response))
+}
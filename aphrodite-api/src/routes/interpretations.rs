@@ -0,0 +1,17 @@
+use axum::{extract::State, Json};
+use crate::error::ApiError;
+use crate::routes::AppState;
+use crate::schemas::request::InterpretationRequest;
+use crate::schemas::response::InterpretationResponse;
+use crate::validation::ValidatedJson;
+
+/// Interpretation texts for a natal chart from a content pack
+pub async fn interpret_chart(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<InterpretationRequest>,
+) -> Result<Json<InterpretationResponse>, ApiError> {
+    let service = state.service_pool.get_service();
+    let mut service = service.lock().await;
+    let response = service.get_interpretations(&request, &state.interpretations).await?;
+    Ok(Json(response))
+}
@@ -0,0 +1,127 @@
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::State;
+use axum::response::Response;
+use chrono::Utc;
+use std::time::Duration;
+use tokio::time::{interval, Interval, MissedTickBehavior};
+
+use crate::error::ApiError;
+use crate::routes::AppState;
+use crate::schemas::request::{LiveClientMessage, LiveSubscription};
+use crate::schemas::response::LiveMessage;
+use crate::services::LiveChart;
+use crate::validation::parse_request;
+
+/// Live chart WebSocket endpoint.
+///
+/// Clients send `{"type": "subscribe", ...}` with a natal subject; the server then pushes
+/// transit positions and newly exact transit-to-natal aspects every `intervalSeconds`.
+pub async fn live_chart(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+    ws.on_upgrade(move |socket| run_session(socket, state))
+}
+
+async fn run_session(mut socket: WebSocket, state: AppState) {
+    let mut chart: Option<LiveChart> = None;
+    let mut ticker = update_interval(Duration::from_secs(60));
+
+    loop {
+        tokio::select! {
+            message = socket.recv() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                    // Pings are answered by axum; binary frames are ignored
+                    Some(Ok(_)) => continue,
+                };
+                let reply = match handle_message(&text, &state).await {
+                    Ok(Some(subscribed)) => {
+                        ticker = update_interval(subscribed.period);
+                        chart = Some(subscribed.chart);
+                        subscribed.reply
+                    }
+                    Ok(None) => {
+                        chart = None;
+                        continue;
+                    }
+                    Err(e) => error_message(&e),
+                };
+                if !send(&mut socket, &reply).await {
+                    break;
+                }
+            }
+            _ = ticker.tick(), if chart.is_some() => {
+                let Some(mut current) = chart.take() else { continue };
+                let update = tokio::task::spawn_blocking(move || {
+                    let update = current.update(Utc::now());
+                    (current, update)
+                })
+                .await;
+                let reply = match update {
+                    Ok((current, update)) => {
+                        chart = Some(current);
+                        update.map(LiveMessage::Update).unwrap_or_else(|e| error_message(&e))
+                    }
+                    Err(e) => error_message(&ApiError::InternalError(format!("Task join error: {}", e))),
+                };
+                if !send(&mut socket, &reply).await {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// A newly accepted subscription
+struct Subscribed {
+    chart: LiveChart,
+    period: Duration,
+    reply: LiveMessage,
+}
+
+/// Apply a client message; `None` means the client unsubscribed
+async fn handle_message(text: &str, state: &AppState) -> Result<Option<Subscribed>, ApiError> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| ApiError::ValidationError(format!("Invalid message: {}", e)))?;
+    let subscription: LiveSubscription = match parse_request(value, state.validation)? {
+        LiveClientMessage::Subscribe(subscription) => *subscription,
+        LiveClientMessage::Unsubscribe => return Ok(None),
+    };
+
+    let ephemeris_path = {
+        let service = state.service_pool.get_service();
+        let service = service.lock().await;
+        service.ephemeris_path().to_path_buf()
+    };
+    let reply = LiveMessage::Subscribed {
+        subject_id: subscription.subject.id.clone(),
+        interval_seconds: subscription.interval_seconds,
+    };
+    let period = Duration::from_secs(subscription.interval_seconds);
+    let chart = tokio::task::spawn_blocking(move || LiveChart::new(Some(ephemeris_path), &subscription))
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))??;
+    Ok(Some(Subscribed { chart, period, reply }))
+}
+
+/// Ticker whose first tick fires immediately, so subscribers get positions right away
+fn update_interval(period: Duration) -> Interval {
+    let mut ticker = interval(period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    ticker
+}
+
+fn error_message(error: &ApiError) -> LiveMessage {
+    tracing::warn!(error = %error, "Live chart error");
+    LiveMessage::Error { error: error.to_json() }
+}
+
+/// Send a message; false once the client is gone
+async fn send(socket: &mut WebSocket, message: &LiveMessage) -> bool {
+    match serde_json::to_string(message) {
+        Ok(json) => socket.send(Message::Text(json)).await.is_ok(),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to serialize live chart message");
+            false
+        }
+    }
+}
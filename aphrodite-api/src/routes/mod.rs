@@ -1,43 +1,150 @@
-use axum::{
-    routing::{get, post},
-    Router,
-};
+use axum::{extract::FromRef, routing::get, Router};
 use std::sync::Arc;
 
-use crate::middleware::rate_limit::{rate_limit_layer, limits};
+use crate::middleware::rate_limit::RateLimits;
+use crate::middleware::{record_exchange, version_headers, ApiVersion, Deprecation};
 use crate::services::ChartServicePool;
+use aphrodite_core::interpretations::InterpretationLibrary;
+use crate::storage::Storage;
+use crate::validation::ValidationOptions;
 
+mod admin;
+mod cache;
+mod electional;
+mod ephemeris;
 mod health;
+mod interpretations;
+mod live;
+mod positions;
+mod rectification;
 mod render;
+mod synastry;
+mod transits;
+mod validate;
+mod wheels;
+pub mod v1;
+pub mod v2;
+mod vedic;
 
 /// Application state
 #[derive(Clone)]
 pub struct AppState {
     pub service_pool: Arc<ChartServicePool>,
+    pub storage: Arc<dyn Storage>,
+    pub admin_token: Option<String>,
+    /// Base URL missing ephemeris files are downloaded from
+    pub ephemeris_download_url: String,
+    /// Content packs for `POST /interpretations`
+    pub interpretations: Arc<InterpretationLibrary>,
+    /// Active per-endpoint limits, reported by `GET /`
+    pub rate_limits: RateLimits,
+    pub validation: ValidationOptions,
+    /// Include the merged `settings` in render responses
+    pub echo_settings: bool,
+    /// Effective configuration, for `GET /admin/config` on the admin listener
+    pub config: Arc<crate::config::Config>,
 }
 
-/// Create the main router with all required state
+/// Routers for the public API port and, when `admin_port` is set, the admin listener
+pub struct Routers {
+    pub api: Router,
+    pub admin: Option<Router>,
+}
+
+impl FromRef<AppState> for ValidationOptions {
+    fn from_ref(state: &AppState) -> Self {
+        state.validation
+    }
+}
+
+/// Create the main router, loading configuration from the config file and environment
 pub fn create_router() -> Router {
+    let config = crate::config::Config::load().expect("Invalid configuration");
+    create_router_with_config(config)
+}
+
+/// Create the main router with all required state. With `admin_port` set the cache and
+/// admin routes are left out; use `create_routers_with_config` to serve them.
+pub fn create_router_with_config(config: crate::config::Config) -> Router {
+    create_routers_with_config(config).api
+}
+
+/// Create the public router and, when `admin_port` is set, the admin router sharing its state
+pub fn create_routers_with_config(config: crate::config::Config) -> Routers {
+    let v1_version = Arc::new(ApiVersion {
+        version: 1,
+        deprecation: config.api_v1_sunset_date().map(|sunset| Deprecation {
+            sunset: Some(sunset),
+            successor: config.api_v2_enabled.then(|| "/api/v2".to_string()),
+        }),
+    });
+
     // Initialize service pool
-    let config = crate::config::Config::from_env();
+    let storage = crate::storage::from_config(&config).expect("Failed to create storage backend");
+    let cache = crate::services::cache::from_config(&config).expect("Failed to create response cache");
     let service_pool = ChartServicePool::new(
         config.service_pool_size,
-        config.swiss_ephemeris_path.map(std::path::PathBuf::from),
-        config.cache_size,
-        config.default_wheel_json_path,
+        config.swiss_ephemeris_path.clone().map(std::path::PathBuf::from),
+        cache,
+        config.default_wheel_json_path.clone(),
     )
     .expect("Failed to create service pool");
 
+    let interpretations = crate::services::interpretations::load_library(config.interpretations_path.as_deref())
+        .expect("Failed to load interpretation packs");
+
+    let recorder = config
+        .record_requests_path
+        .clone()
+        .map(|path| Arc::new(crate::replay::Recorder::new(path)));
+
     let state = AppState {
         service_pool: Arc::new(service_pool),
+        storage,
+        admin_token: config.admin_token.clone(),
+        ephemeris_download_url: config.ephemeris_download_url.clone(),
+        interpretations: Arc::new(interpretations),
+        rate_limits: config.rate_limits.clone(),
+        validation: ValidationOptions {
+            strict: config.strict_requests,
+        },
+        echo_settings: config.echo_settings,
+        config: Arc::new(config.clone()),
     };
 
-    Router::new()
+    // Operational routes move off the public port when the admin listener is enabled
+    let separate_admin = config.admin_port.is_some();
+    let mut v1_routes = v1::router(&config.rate_limits);
+    if !separate_admin {
+        v1_routes = v1_routes.merge(admin::router());
+    }
+
+    let mut router = Router::new()
         .route("/", get(health::api_info))
         .route("/health", get(health::health_check))
-        // API v1 routes with rate limiting
-        .route("/api/v1/render", post(render::render_ephemeris).layer(rate_limit_layer(limits::render())))
-        .route("/api/v1/render/chartspec", post(render::render_chartspec).layer(rate_limit_layer(limits::chartspec())))
-        .with_state(state)
+        .route("/health/ready", get(health::readiness_check))
+        .nest(
+            "/api/v1",
+            v1_routes.layer(axum::middleware::from_fn_with_state(v1_version, version_headers)),
+        );
+    if config.api_v2_enabled {
+        let v2_version = Arc::new(ApiVersion { version: 2, deprecation: None });
+        router = router.nest(
+            "/api/v2",
+            v2::router(&config.rate_limits).layer(axum::middleware::from_fn_with_state(v2_version, version_headers)),
+        );
+    }
+    let admin = separate_admin.then(|| admin::listener_router().with_state(state.clone()));
+    let router = router.with_state(state);
+
+    // Opt-in request recording for replay testing
+    let api = match recorder {
+        Some(recorder) => {
+            tracing::info!(path = %recorder.path().display(), "Recording anonymized API requests");
+            router.layer(axum::middleware::from_fn_with_state(recorder, record_exchange))
+        }
+        None => router,
+    };
+    Routers { api, admin }
 }
 
@@ -0,0 +1,21 @@
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Query, State};
+use axum::Json;
+use crate::error::ApiError;
+use crate::routes::AppState;
+use crate::schemas::request::PositionsQuery;
+use crate::schemas::response::PositionsResponse;
+use crate::validation::Validate;
+
+/// Longitudes at one moment, for ephemeris tables and widgets
+pub async fn get_positions(
+    State(state): State<AppState>,
+    query: Result<Query<PositionsQuery>, QueryRejection>,
+) -> Result<Json<PositionsResponse>, ApiError> {
+    let Query(query) = query.map_err(|rejection| ApiError::ValidationError(rejection.body_text()))?;
+    query.validate()?;
+    let service = state.service_pool.get_service();
+    let service = service.lock().await;
+    let response = service.get_raw_positions(&query).await?;
+    Ok(Json(response))
+}
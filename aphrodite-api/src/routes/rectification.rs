@@ -0,0 +1,17 @@
+use axum::{extract::State, Json};
+use crate::error::ApiError;
+use crate::routes::AppState;
+use crate::schemas::request::RectificationRequest;
+use crate::schemas::response::RectificationResponse;
+use crate::validation::ValidatedJson;
+
+/// Rectification endpoint
+pub async fn rectify_birth_time(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<RectificationRequest>,
+) -> Result<Json<RectificationResponse>, ApiError> {
+    let service = state.service_pool.get_service();
+    let service = service.lock().await;
+    let response = service.get_rectification(&request).await?;
+    Ok(Json(response))
+}
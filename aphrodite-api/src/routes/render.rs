@@ -1,39 +1,47 @@
-use axum::{extract::State, Json};
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Query, State};
 use crate::error::ApiError;
+use crate::projection::{FieldSelection, Projected};
 use crate::routes::AppState;
 use crate::schemas::request::RenderRequest;
 use crate::schemas::response::{ChartSpecResponse, EphemerisResponse};
-use crate::validation::RequestValidator;
+use crate::validation::{Validate, ValidatedJson};
 
 /// Render ephemeris positions endpoint
 pub async fn render_ephemeris(
     State(state): State<AppState>,
-    Json(request): Json<RenderRequest>,
-) -> Result<Json<EphemerisResponse>, ApiError> {
-    // Validate request
-    RequestValidator::validate_request(&request)?;
-    
+    selection: Result<Query<FieldSelection>, QueryRejection>,
+    ValidatedJson(request): ValidatedJson<RenderRequest>,
+) -> Result<Projected<EphemerisResponse>, ApiError> {
+    let selection = field_selection(selection)?;
+    let selection = if state.echo_settings { selection } else { selection.excluding("settings") };
     let service = state.service_pool.get_service();
     let mut service = service.lock().await;
     let response = service.get_positions(&request).await?;
-    Ok(Json(response))
+    Ok(Projected(response, selection))
 }
 
 /// Render ChartSpec endpoint
 pub async fn render_chartspec(
     State(state): State<AppState>,
-    Json(request): Json<RenderRequest>,
-) -> Result<Json<ChartSpecResponse>, ApiError> {
-    // Validate request
-    RequestValidator::validate_request(&request)?;
-    
+    selection: Result<Query<FieldSelection>, QueryRejection>,
+    ValidatedJson(request): ValidatedJson<RenderRequest>,
+) -> Result<Projected<ChartSpecResponse>, ApiError> {
+    let selection = field_selection(selection)?;
+    let selection = if state.echo_settings { selection } else { selection.excluding("ephemeris.settings") };
     let service = state.service_pool.get_service();
     let mut service = service.lock().await;
     let (spec, ephemeris) = service.get_chartspec(&request, None).await?;
     
-    Ok(Json(ChartSpecResponse {
+    Ok(Projected(ChartSpecResponse {
         spec,
         ephemeris,
-    }))
+    }, selection))
 }
 
+/// `?fields=` / `?exclude=` of a render request, validated before a service is acquired
+pub(super) fn field_selection(query: Result<Query<FieldSelection>, QueryRejection>) -> Result<FieldSelection, ApiError> {
+    let Query(selection) = query.map_err(|rejection| ApiError::ValidationError(rejection.body_text()))?;
+    selection.validate()?;
+    Ok(selection)
+}
@@ -0,0 +1,20 @@
+use axum::extract::State;
+use crate::error::ApiError;
+use crate::projection::{FieldSelection, Projected};
+use crate::routes::AppState;
+use crate::schemas::request::SynastryRequest;
+use crate::schemas::response::SynastryResponse;
+use crate::validation::ValidatedJson;
+
+/// Synastry report endpoint
+pub async fn render_synastry(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<SynastryRequest>,
+) -> Result<Projected<SynastryResponse>, ApiError> {
+    let service = state.service_pool.get_service();
+    let mut service = service.lock().await;
+    let response = service.get_synastry(&request).await?;
+    let selection = FieldSelection::default();
+    let selection = if state.echo_settings { selection } else { selection.excluding("ephemeris.settings") };
+    Ok(Projected(response, selection))
+}
@@ -0,0 +1,48 @@
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::{extract::State, Json};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
+use crate::error::ApiError;
+use crate::routes::AppState;
+use crate::schemas::request::TransitTimelineRequest;
+use crate::schemas::response::TransitTimelineResponse;
+use crate::services::chart::TransitTimelineEvent;
+use crate::validation::ValidatedJson;
+
+/// Transit-to-natal aspect timeline endpoint
+pub async fn transit_timeline(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<TransitTimelineRequest>,
+) -> Result<Json<TransitTimelineResponse>, ApiError> {
+    let service = state.service_pool.get_service();
+    let service = service.lock().await;
+    let response = service.get_transit_timeline(&request).await?;
+    Ok(Json(response))
+}
+
+/// Transit timeline streamed as Server-Sent Events.
+///
+/// Emits a `day` event per sampled day, then `done` (or `error`).
+pub async fn stream_transit_timeline(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<TransitTimelineRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    // The scan runs on its own adapter, so the pooled service is only held while starting it
+    let events = {
+        let service = state.service_pool.get_service();
+        let service = service.lock().await;
+        service.stream_transit_timeline(&request)?
+    };
+
+    let subject_id = request.subject.id;
+    let stream = ReceiverStream::new(events).map(move |event| match event {
+        TransitTimelineEvent::Day(day) => Event::default().event("day").json_data(day),
+        TransitTimelineEvent::Done { days } => Event::default().event("done").json_data(serde_json::json!({
+            "subjectId": subject_id,
+            "days": days,
+        })),
+        TransitTimelineEvent::Error(message) => Event::default()
+            .event("error")
+            .json_data(serde_json::json!({ "code": "CALCULATION_ERROR", "message": message })),
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
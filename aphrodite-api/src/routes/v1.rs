@@ -0,0 +1,32 @@
+//! `/api/v1` routes. The v1 schema is frozen: changes here must stay backward compatible.
+
+use axum::{
+    routing::{get, post},
+    Router,
+};
+
+use super::{electional, ephemeris, interpretations, live, positions, rectification, render, synastry, transits, validate, vedic, wheels, AppState};
+use crate::middleware::rate_limit::{rate_limit_layer, RateLimitConfig, RateLimits};
+
+/// Routes served under `/api/v1`; the cache and admin routes are added by
+/// `create_routers_with_config` unless they are served on `admin_port`
+pub fn router(limits: &RateLimits) -> Router<AppState> {
+    let limit = |requests_per_minute| rate_limit_layer(RateLimitConfig::new(requests_per_minute));
+    Router::new()
+        .route("/render", post(render::render_ephemeris).layer(limit(limits.render)))
+        .route("/render/chartspec", post(render::render_chartspec).layer(limit(limits.chartspec)))
+        .route("/ephemeris", get(ephemeris::ephemeris_table).layer(limit(limits.ephemeris)))
+        .route("/positions", get(positions::get_positions).layer(limit(limits.positions)))
+        .route("/synastry", post(synastry::render_synastry).layer(limit(limits.synastry)))
+        .route("/transits/timeline", post(transits::transit_timeline).layer(limit(limits.transits)))
+        .route("/transits/timeline/stream", post(transits::stream_transit_timeline).layer(limit(limits.transits)))
+        .route("/electional/search", post(electional::search_electional).layer(limit(limits.electional)))
+        .route("/rectification", post(rectification::rectify_birth_time).layer(limit(limits.rectification)))
+        .route("/interpretations", post(interpretations::interpret_chart).layer(limit(limits.interpretations)))
+        .route("/validate", post(validate::validate_request))
+        .route("/wheels/schema", get(wheels::wheel_schema))
+        .route("/wheels/validate", post(wheels::validate_wheel))
+        .route("/vedic/muhurta", post(vedic::search_muhurta).layer(limit(limits.muhurta)))
+        .route("/vedic/muhurta/stream", post(vedic::stream_muhurta).layer(limit(limits.muhurta)))
+        .route("/ws", get(live::live_chart))
+}
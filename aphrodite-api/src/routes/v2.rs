@@ -0,0 +1,55 @@
+//! `/api/v2` routes (under development, opt-in via `api_v2_enabled`).
+//!
+//! v2 reuses the v1 services and request types and only differs in response shape; see
+//! `schemas::v2`. Endpoints without a v2 schema change are not served here yet.
+
+use axum::extract::rejection::QueryRejection;
+use axum::extract::{Query, State};
+use axum::{routing::post, Router};
+
+use super::{render::field_selection, AppState};
+use crate::error::ApiError;
+use crate::middleware::rate_limit::{rate_limit_layer, RateLimitConfig, RateLimits};
+use crate::projection::{FieldSelection, Projected};
+use crate::schemas::request::RenderRequest;
+use crate::schemas::v2::{ChartSpecResponse, EphemerisResponse};
+use crate::validation::ValidatedJson;
+
+/// Routes served under `/api/v2`
+pub fn router(limits: &RateLimits) -> Router<AppState> {
+    let limit = |requests_per_minute| rate_limit_layer(RateLimitConfig::new(requests_per_minute));
+    Router::new()
+        .route("/render", post(render_ephemeris).layer(limit(limits.render)))
+        .route("/render/chartspec", post(render_chartspec).layer(limit(limits.chartspec)))
+}
+
+/// Render ephemeris positions with per-layer Vedic and Western results
+pub async fn render_ephemeris(
+    State(state): State<AppState>,
+    selection: Result<Query<FieldSelection>, QueryRejection>,
+    ValidatedJson(request): ValidatedJson<RenderRequest>,
+) -> Result<Projected<EphemerisResponse>, ApiError> {
+    let selection = field_selection(selection)?;
+    let selection = if state.echo_settings { selection } else { selection.excluding("settings") };
+    let service = state.service_pool.get_service();
+    let mut service = service.lock().await;
+    let response = service.get_positions(&request).await?;
+    Ok(Projected(response.into(), selection))
+}
+
+/// Render ChartSpec with the v2 ephemeris payload
+pub async fn render_chartspec(
+    State(state): State<AppState>,
+    selection: Result<Query<FieldSelection>, QueryRejection>,
+    ValidatedJson(request): ValidatedJson<RenderRequest>,
+) -> Result<Projected<ChartSpecResponse>, ApiError> {
+    let selection = field_selection(selection)?;
+    let selection = if state.echo_settings { selection } else { selection.excluding("ephemeris.settings") };
+    let service = state.service_pool.get_service();
+    let mut service = service.lock().await;
+    let (spec, ephemeris) = service.get_chartspec(&request, None).await?;
+    Ok(Projected(ChartSpecResponse {
+        spec,
+        ephemeris: ephemeris.into(),
+    }, selection))
+}
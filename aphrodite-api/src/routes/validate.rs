@@ -0,0 +1,53 @@
+use axum::{extract::State, Json};
+use crate::error::{ApiError, FieldError, FieldErrorCode};
+use crate::routes::AppState;
+use crate::schemas::request::RenderRequest;
+use crate::schemas::response::RequestValidateResponse;
+use crate::services::ChartService;
+use crate::validation::parse_request;
+
+/// Check a render request without calculating it: field validation, then settings
+/// overrides and layer resolution. Bodies that are not a render request at all are
+/// rejected like `/render` would reject them.
+pub async fn validate_request(
+    State(state): State<AppState>,
+    Json(body): Json<serde_json::Value>,
+) -> Result<Json<RequestValidateResponse>, ApiError> {
+    let issues = match parse_request::<RenderRequest>(body, state.validation) {
+        Ok(request) => resolution_issues(&request),
+        Err(ApiError::InvalidFields(issues)) => issues,
+        Err(e) => return Err(e),
+    };
+    Ok(Json(RequestValidateResponse {
+        valid: issues.is_empty(),
+        issues,
+    }))
+}
+
+/// Problems the calculation would hit after validation passed
+fn resolution_issues(request: &RenderRequest) -> Vec<FieldError> {
+    let issue = |field: String, error: ApiError| FieldError {
+        field,
+        code: FieldErrorCode::InvalidValue,
+        message: match error {
+            ApiError::ValidationError(message) => message,
+            other => other.to_string(),
+        },
+    };
+
+    let mut settings = request.settings.clone();
+    if let Err(e) = ChartService::merge_settings_override(&mut settings, &request.settings_override) {
+        return vec![issue("settings_override".to_string(), e)];
+    }
+
+    let mut layers: Vec<_> = request.layer_config.iter().collect();
+    layers.sort_by_key(|(layer_id, _)| *layer_id);
+    layers
+        .into_iter()
+        .filter_map(|(layer_id, config)| {
+            ChartService::resolve_layer_context(layer_id, config, &request.subjects, &settings)
+                .err()
+                .map(|e| issue(format!("layer_config.{}", layer_id), e))
+        })
+        .collect()
+}
@@ -0,0 +1,50 @@
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::{extract::State, Json};
+use tokio_stream::{wrappers::ReceiverStream, Stream, StreamExt};
+use crate::error::ApiError;
+use crate::routes::AppState;
+use crate::schemas::request::MuhurtaRequest;
+use crate::schemas::response::MuhurtaResponse;
+use crate::services::chart::MuhurtaEvent;
+use crate::validation::ValidatedJson;
+
+/// Muhurta window search endpoint
+pub async fn search_muhurta(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<MuhurtaRequest>,
+) -> Result<Json<MuhurtaResponse>, ApiError> {
+    let service = state.service_pool.get_service();
+    let service = service.lock().await;
+    let response = service.get_muhurta(&request).await?;
+    Ok(Json(response))
+}
+
+/// Muhurta window search streamed as Server-Sent Events.
+///
+/// Emits a `window` event per window as the scan finds it, then `done` (or `error`).
+pub async fn stream_muhurta(
+    State(state): State<AppState>,
+    ValidatedJson(request): ValidatedJson<MuhurtaRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ApiError> {
+    // The scan runs on its own adapter, so the pooled service is only held while starting it
+    let events = {
+        let service = state.service_pool.get_service();
+        let service = service.lock().await;
+        service.stream_muhurta(&request)?
+    };
+
+    let step_minutes = request.step_minutes;
+    let ayanamsa = request.ayanamsa;
+    let stream = ReceiverStream::new(events).map(move |event| match event {
+        MuhurtaEvent::Window(window) => Event::default().event("window").json_data(window),
+        MuhurtaEvent::Done { windows } => Event::default().event("done").json_data(serde_json::json!({
+            "windows": windows,
+            "stepMinutes": step_minutes,
+            "ayanamsa": ayanamsa,
+        })),
+        MuhurtaEvent::Error(message) => Event::default()
+            .event("error")
+            .json_data(serde_json::json!({ "code": "CALCULATION_ERROR", "message": message })),
+    });
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
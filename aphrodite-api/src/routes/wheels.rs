@@ -0,0 +1,33 @@
+use axum::Json;
+use crate::schemas::request::WheelValidateRequest;
+use crate::schemas::response::WheelValidateResponse;
+use crate::validation::ValidatedJson;
+use aphrodite_core::layout::{lint_wheel, load_wheel_definition_from_json, wheel_definition_schema};
+
+/// JSON Schema of the wheel definition format
+pub async fn wheel_schema() -> Json<serde_json::Value> {
+    Json(wheel_definition_schema())
+}
+
+/// Check a wheel definition: whether it loads, and lint warnings when it does
+pub async fn validate_wheel(
+    ValidatedJson(request): ValidatedJson<WheelValidateRequest>,
+) -> Json<WheelValidateResponse> {
+    let layer_ids: Option<Vec<&str>> = request
+        .layer_ids
+        .as_ref()
+        .map(|ids| ids.iter().map(String::as_str).collect());
+    let response = match load_wheel_definition_from_json(&request.wheel.to_string()) {
+        Ok(definition) => WheelValidateResponse {
+            valid: true,
+            error: None,
+            warnings: lint_wheel(&definition.wheel, layer_ids.as_deref()),
+        },
+        Err(e) => WheelValidateResponse {
+            valid: false,
+            error: Some(e.to_string()),
+            warnings: Vec::new(),
+        },
+    };
+    Json(response)
+}
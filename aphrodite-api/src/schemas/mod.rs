@@ -1,6 +1,9 @@
+//! Request and response types. `request` and `response` are the v1 wire schema, which v2
+//! shares except where `v2` defines its own types.
+
 pub mod request;
 pub mod response;
+pub mod v2;
 
 pub use request::*;
 pub use response::*;
-
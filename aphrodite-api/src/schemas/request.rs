@@ -1,3 +1,7 @@
+use aphrodite_core::format::DegreeFormat;
+use aphrodite_core::layout::CustomPoint;
+use aphrodite_core::ephemeris::{LilithType, MoonPosition, NodeType, PolarFallback, ProgressionOptions};
+use aphrodite_core::vedic::MuhurtaConstraints;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -65,12 +69,51 @@ impl Default for OrbSettings {
     }
 }
 
+/// Aspect filter DTO: which aspects and object pairs to compute
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AspectFilterSettings {
+    /// Major aspects only; false adds quincunx, semisextile, semisquare and sesquiquadrate
+    #[serde(rename = "onlyMajor", default = "default_only_major")]
+    pub only_major: bool,
+    /// Aspect types to compute, overriding `onlyMajor`
+    #[serde(rename = "aspectTypes", default, skip_serializing_if = "Option::is_none")]
+    pub aspect_types: Option<Vec<String>>,
+    /// When set, only these object pairs are compared
+    #[serde(rename = "includePairs", default, skip_serializing_if = "Option::is_none")]
+    pub include_pairs: Option<Vec<[String; 2]>>,
+    /// Object pairs never compared, e.g. `["north_node", "south_node"]`
+    #[serde(rename = "excludePairs", default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_pairs: Vec<[String; 2]>,
+    /// Also aspect the Ascendant and Midheaven
+    #[serde(rename = "includeAngles", default)]
+    pub include_angles: bool,
+}
+
+fn default_only_major() -> bool {
+    true
+}
+
+impl Default for AspectFilterSettings {
+    fn default() -> Self {
+        Self {
+            only_major: true,
+            aspect_types: None,
+            include_pairs: None,
+            exclude_pairs: Vec::new(),
+            include_angles: false,
+        }
+    }
+}
+
 /// Chart settings DTO
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChartSettings {
     #[serde(rename = "zodiacType", default = "default_zodiac_type")]
     pub zodiac_type: String,
     pub ayanamsa: Option<String>,
+    /// Ayanamsa in degrees at J2000 when `ayanamsa` is "custom"
+    #[serde(rename = "customAyanamsa", skip_serializing_if = "Option::is_none")]
+    pub custom_ayanamsa: Option<f64>,
     #[serde(rename = "houseSystem", default = "default_house_system")]
     pub house_system: String,
     #[serde(rename = "orbSettings", default)]
@@ -79,6 +122,52 @@ pub struct ChartSettings {
     pub include_objects: Vec<String>,
     #[serde(rename = "vedicConfig", skip_serializing_if = "Option::is_none")]
     pub vedic_config: Option<VedicConfig>,
+    #[serde(rename = "decanSystem", default = "default_decan_system")]
+    pub decan_system: String, // "triplicity" or "chaldean"
+    #[serde(rename = "termSystem", default = "default_term_system")]
+    pub term_system: String, // "egyptian" or "ptolemaic"
+    #[serde(rename = "includeAspects", default)]
+    pub include_aspects: bool,
+    /// Drop aspects whose planets' signs don't match the aspect, e.g. a trine from Aries to Virgo
+    #[serde(rename = "excludeOutOfSign", default)]
+    pub exclude_out_of_sign: bool,
+    /// Orb per object; a pair's orb is the mean of both objects' orbs, using the aspect's orb
+    /// for objects not listed
+    #[serde(rename = "objectOrbs", default, skip_serializing_if = "HashMap::is_empty")]
+    pub object_orbs: HashMap<String, f64>,
+    #[serde(rename = "aspectFilter", default)]
+    pub aspect_filter: AspectFilterSettings,
+    /// Add each planet's phase, elongation, apparent diameter and magnitude to the layers
+    #[serde(rename = "includePhenomena", default)]
+    pub include_phenomena: bool,
+    /// True (osculating) or mean lunar node for `north_node` and `south_node`
+    #[serde(rename = "nodeType", default)]
+    pub node_type: NodeType,
+    /// Mean or true (osculating) lunar apogee for `lilith`
+    #[serde(rename = "lilithType", default)]
+    pub lilith_type: LilithType,
+    /// Moon from the Earth's centre or from the layer's location, which can differ by over a
+    /// degree; layers without a location stay geocentric
+    #[serde(rename = "moonPosition", default)]
+    pub moon_position: MoonPosition,
+    /// House system used instead of Placidus or Koch for locations inside the polar circles
+    #[serde(rename = "polarFallback", default)]
+    pub polar_fallback: PolarFallback,
+    /// Whether partial results (skipped objects, missing or fallback houses) come back with
+    /// warnings or fail the request
+    #[serde(rename = "calculationMode", default)]
+    pub calculation_mode: CalculationMode,
+}
+
+/// How a layer that can only be partly calculated is reported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalculationMode {
+    /// Return what could be calculated, with a warning on the layer for the rest
+    #[default]
+    Lenient,
+    /// Fail the request instead
+    Strict,
 }
 
 fn default_zodiac_type() -> String {
@@ -87,16 +176,35 @@ fn default_zodiac_type() -> String {
 fn default_house_system() -> String {
     "placidus".to_string()
 }
+fn default_decan_system() -> String {
+    "triplicity".to_string()
+}
+fn default_term_system() -> String {
+    "egyptian".to_string()
+}
 
 impl Default for ChartSettings {
     fn default() -> Self {
         Self {
             zodiac_type: "tropical".to_string(),
             ayanamsa: None,
+            custom_ayanamsa: None,
             house_system: "placidus".to_string(),
             orb_settings: OrbSettings::default(),
             include_objects: vec![],
             vedic_config: None,
+            decan_system: "triplicity".to_string(),
+            term_system: "egyptian".to_string(),
+            include_aspects: false,
+            exclude_out_of_sign: false,
+            object_orbs: HashMap::new(),
+            aspect_filter: AspectFilterSettings::default(),
+            include_phenomena: false,
+            node_type: NodeType::default(),
+            lilith_type: LilithType::default(),
+            moon_position: MoonPosition::default(),
+            polar_fallback: PolarFallback::default(),
+            calculation_mode: CalculationMode::default(),
         }
     }
 }
@@ -117,8 +225,28 @@ pub struct VedicConfig {
     pub dasha_systems: Vec<String>,
     #[serde(default = "default_dashas_depth")]
     pub dashas_depth: String,
+    /// Only return dasha periods ending after this moment (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dashas_from: Option<String>,
+    /// Only return dasha periods starting before this moment (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dashas_to: Option<String>,
+    /// Lunar node used for dashas; the chart's `nodeType` when omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dasha_node_type: Option<NodeType>,
     #[serde(default)]
     pub include_yogas: bool,
+    #[serde(default)]
+    pub include_tarabala: bool,
+    #[serde(default)]
+    pub include_ashtakavarga: bool,
+    #[serde(default)]
+    pub include_bhava_chalit: bool,
+    #[serde(default)]
+    pub include_avasthas: bool,
+    /// Bhava madhya method: "sripati" or "house_cusps"
+    #[serde(default = "default_bhava_method")]
+    pub bhava_method: String,
 }
 
 fn default_true() -> bool {
@@ -130,6 +258,9 @@ fn default_vimshottari() -> Vec<String> {
 fn default_dashas_depth() -> String {
     "pratyantardasha".to_string()
 }
+fn default_bhava_method() -> String {
+    "sripati".to_string()
+}
 
 /// Layer configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -141,6 +272,9 @@ pub struct LayerConfig {
     pub explicit_date_time: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<Location>,
+    /// How a progressed layer moves its angles, and whether it progresses backwards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub progression: Option<ProgressionOptions>,
 }
 
 /// Render request payload
@@ -152,5 +286,306 @@ pub struct RenderRequest {
     pub layer_config: HashMap<String, LayerConfig>,
     #[serde(rename = "settings_override", default, skip_serializing_if = "HashMap::is_empty")]
     pub settings_override: HashMap<String, serde_json::Value>,
+    /// Canvas size, margin and orientation of the generated ChartSpec
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<ChartLayout>,
+    /// Point set id -> labeled longitudes for `custom_points` rings
+    #[serde(rename = "customPoints", default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom_points: HashMap<String, Vec<CustomPoint>>,
+    /// Wheel preset for the ChartSpec, e.g. "natal_transit"; the server's default wheel when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wheel: Option<String>,
+}
+
+/// Canvas size, margin and orientation of a generated ChartSpec
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartLayout {
+    #[serde(default = "default_chart_size")]
+    pub width: f32,
+    #[serde(default = "default_chart_size")]
+    pub height: f32,
+    /// Padding between the outermost ring and the canvas edge
+    #[serde(default = "default_chart_margin")]
+    pub margin: f32,
+    #[serde(default)]
+    pub rotate: ChartRotation,
+}
+
+impl Default for ChartLayout {
+    fn default() -> Self {
+        Self {
+            width: default_chart_size(),
+            height: default_chart_size(),
+            margin: default_chart_margin(),
+            rotate: ChartRotation::default(),
+        }
+    }
+}
+
+/// What stays fixed on the wheel
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChartRotation {
+    /// 0° Aries at the 3 o'clock position
+    #[default]
+    Aries,
+    /// The Ascendant on the left, at the 9 o'clock position
+    Ascendant,
+}
+
+
+/// Synastry request payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SynastryRequest {
+    /// Exactly two subjects; their ids are used as layer ids in the report
+    pub subjects: Vec<Subject>,
+    #[serde(default)]
+    pub settings: ChartSettings,
+    #[serde(rename = "includeChartSpec", default)]
+    pub include_chart_spec: bool,
+}
+
+/// Muhurta search request payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MuhurtaRequest {
+    /// Start of the search range (RFC 3339)
+    pub start: String,
+    /// End of the search range (RFC 3339)
+    pub end: String,
+    pub location: Location,
+    #[serde(rename = "stepMinutes", default = "default_muhurta_step")]
+    pub step_minutes: u32,
+    #[serde(default = "default_muhurta_ayanamsa")]
+    pub ayanamsa: String,
+    #[serde(rename = "customAyanamsa", default)]
+    pub custom_ayanamsa: Option<f64>,
+    #[serde(default)]
+    pub constraints: MuhurtaConstraints,
+    /// Natal subject, required for tarabala and chandrabala constraints
+    #[serde(default)]
+    pub subject: Option<Subject>,
+}
+
+/// Transit timeline request payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitTimelineRequest {
+    /// Natal subject the transits are measured against
+    pub subject: Subject,
+    /// First day of the timeline (RFC 3339); transits are sampled at this time of day
+    pub start: String,
+    /// End of the timeline (RFC 3339), exclusive
+    pub end: String,
+    #[serde(default)]
+    pub settings: ChartSettings,
+}
+
+/// Interpretation request payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterpretationRequest {
+    pub subject: Subject,
+    #[serde(default)]
+    pub settings: ChartSettings,
+    /// Content pack id
+    pub pack: String,
+    /// Pack locale; a regional locale such as "pt-BR" falls back to "pt"
+    #[serde(default = "default_interpretation_locale")]
+    pub locale: String,
+    /// Moment the running dashas are read at (RFC 3339); now when absent. Dashas are only
+    /// included when `settings.vedicConfig.include_dashas` is set.
+    #[serde(default)]
+    pub at: Option<String>,
+}
+
+fn default_interpretation_locale() -> String {
+    "en".to_string()
 }
 
+/// Body of `POST /admin/ephemeris/download`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EphemerisDownloadRequest {
+    /// File names such as `sepl_18.se1`; the missing standard files when absent
+    pub files: Option<Vec<String>>,
+}
+
+/// Body of `POST /wheels/validate`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WheelValidateRequest {
+    /// Wheel definition to check
+    pub wheel: serde_json::Value,
+    /// Layers the wheel will be drawn with; when absent, the layers its rings draw
+    #[serde(rename = "layerIds", default)]
+    pub layer_ids: Option<Vec<String>>,
+}
+
+/// Query string of `GET /positions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionsQuery {
+    /// Moment to calculate (RFC 3339)
+    pub datetime: String,
+    /// Latitude and longitude, given together, add the angles
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+    /// Comma-separated object ids; the default objects when absent
+    pub objects: Option<String>,
+    #[serde(rename = "zodiacType", default = "default_zodiac_type")]
+    pub zodiac_type: String,
+    pub ayanamsa: Option<String>,
+    /// Ayanamsa in degrees at J2000 when `ayanamsa` is "custom"
+    #[serde(rename = "customAyanamsa")]
+    pub custom_ayanamsa: Option<f64>,
+    /// Also write each longitude out in this format
+    pub format: Option<DegreeFormat>,
+}
+
+impl PositionsQuery {
+    /// Requested object ids, empty when `objects` is absent
+    pub fn object_ids(&self) -> Vec<String> {
+        split_object_ids(self.objects.as_deref())
+    }
+}
+
+/// Query string of `GET /ephemeris`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisTableQuery {
+    /// First row (RFC 3339)
+    pub from: String,
+    /// End of the table (RFC 3339), exclusive
+    pub to: String,
+    /// Time between rows: a number of days, hours or minutes such as `1d`, `6h` or `30m`
+    #[serde(default = "default_ephemeris_step")]
+    pub step: String,
+    /// Comma-separated object ids; the default objects when absent
+    pub objects: Option<String>,
+    /// Add each object's declination
+    #[serde(default)]
+    pub declination: bool,
+    /// Add each object's speed in longitude
+    #[serde(default)]
+    pub speed: bool,
+}
+
+fn default_ephemeris_step() -> String {
+    "1d".to_string()
+}
+
+impl EphemerisTableQuery {
+    /// Requested object ids, empty when `objects` is absent
+    pub fn object_ids(&self) -> Vec<String> {
+        split_object_ids(self.objects.as_deref())
+    }
+
+    /// `step` as a duration; None when it isn't a positive `<n>d`, `<n>h` or `<n>m`
+    pub fn step_duration(&self) -> Option<chrono::Duration> {
+        let step = self.step.trim();
+        let unit = step.chars().last()?;
+        let count: i64 = step[..step.len() - unit.len_utf8()].parse().ok().filter(|count| *count > 0)?;
+        match unit {
+            'd' => chrono::Duration::try_days(count),
+            'h' => chrono::Duration::try_hours(count),
+            'm' => chrono::Duration::try_minutes(count),
+            _ => None,
+        }
+    }
+}
+
+/// Ids of a comma-separated `objects` query parameter
+fn split_object_ids(objects: Option<&str>) -> Vec<String> {
+    objects
+        .into_iter()
+        .flat_map(|objects| objects.split(','))
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// Electional search request payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElectionalSearchRequest {
+    /// Start of the search range (RFC 3339)
+    pub start: String,
+    /// End of the search range (RFC 3339)
+    pub end: String,
+    pub location: Location,
+    #[serde(rename = "stepMinutes", default = "default_electional_step")]
+    pub step_minutes: u32,
+    /// Constraint phrases that must all hold, e.g. "moon not void"
+    pub constraints: Vec<String>,
+    #[serde(default)]
+    pub settings: ChartSettings,
+}
+
+/// A dated life event used to rectify a birth time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LifeEvent {
+    /// When the event happened (RFC 3339)
+    pub date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+/// Rectification request payload
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RectificationRequest {
+    /// Earliest candidate birth time (RFC 3339)
+    #[serde(rename = "windowStart")]
+    pub window_start: String,
+    /// End of the candidate birth times (RFC 3339, exclusive)
+    #[serde(rename = "windowEnd")]
+    pub window_end: String,
+    /// Birth place
+    pub location: Location,
+    #[serde(rename = "stepMinutes", default = "default_rectification_step")]
+    pub step_minutes: u32,
+    pub events: Vec<LifeEvent>,
+    /// Number of candidates to return
+    #[serde(default = "default_rectification_limit")]
+    pub limit: usize,
+    #[serde(default)]
+    pub settings: ChartSettings,
+}
+
+/// Natal chart to follow on the live chart WebSocket
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiveSubscription {
+    pub subject: Subject,
+    #[serde(default)]
+    pub settings: ChartSettings,
+    /// Seconds between position updates
+    #[serde(rename = "intervalSeconds", default = "default_live_interval")]
+    pub interval_seconds: u64,
+}
+
+/// Messages accepted on the live chart WebSocket
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveClientMessage {
+    /// Start (or replace) the subscription
+    Subscribe(Box<LiveSubscription>),
+    /// Stop sending updates, keeping the connection open
+    Unsubscribe,
+}
+
+fn default_chart_size() -> f32 {
+    800.0
+}
+fn default_chart_margin() -> f32 {
+    aphrodite_core::rendering::DEFAULT_MARGIN
+}
+fn default_live_interval() -> u64 {
+    60
+}
+fn default_muhurta_step() -> u32 {
+    15
+}
+fn default_electional_step() -> u32 {
+    30
+}
+fn default_rectification_step() -> u32 {
+    4
+}
+fn default_rectification_limit() -> usize {
+    5
+}
+fn default_muhurta_ayanamsa() -> String {
+    "lahiri".to_string()
+}
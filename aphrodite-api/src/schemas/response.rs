@@ -1,6 +1,16 @@
+use aphrodite_core::aspects::{AspectPair, AspectSet};
+use aphrodite_core::electional::{ElectionalConstraint, ElectionalWindow};
+use aphrodite_core::interpretations::Interpretation;
+use aphrodite_core::layout::WheelWarning;
+use aphrodite_core::ephemeris::{InstalledFile, PlanetPhenomena, PositionWarning, YearRange};
+use aphrodite_core::rectification::RectificationCandidate;
 use aphrodite_core::rendering::ChartSpec;
-use aphrodite_core::vedic::VedicPayload;
+use aphrodite_core::synastry::SynastryReport;
+use aphrodite_core::transits::TransitTimeline;
+use aphrodite_core::vedic::{MuhurtaWindow, VedicPayload};
 use aphrodite_core::western::WesternLayerData;
+use crate::error::FieldError;
+use crate::middleware::rate_limit::RateLimits;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -34,6 +44,33 @@ pub struct LayerPositions {
     pub houses: Option<HousePositions>,
 }
 
+impl From<&aphrodite_core::ephemeris::LayerPositions> for LayerPositions {
+    fn from(positions: &aphrodite_core::ephemeris::LayerPositions) -> Self {
+        Self {
+            planets: positions
+                .planets
+                .iter()
+                .map(|(id, p)| {
+                    (
+                        id.clone(),
+                        PlanetPosition {
+                            lon: p.lon,
+                            lat: p.lat,
+                            speed_lon: Some(p.speed_lon),
+                            retrograde: Some(p.retrograde),
+                        },
+                    )
+                })
+                .collect(),
+            houses: positions.houses.as_ref().map(|h| HousePositions {
+                system: h.system.clone(),
+                cusps: h.cusps.clone(),
+                angles: h.angles.clone(),
+            }),
+        }
+    }
+}
+
 /// Layer response with positions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LayerResponse {
@@ -44,6 +81,34 @@ pub struct LayerResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<crate::schemas::request::Location>,
     pub positions: LayerPositions,
+    /// Ayanamsa applied at the layer moment; only for the sidereal zodiac
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ayanamsa: Option<AppliedAyanamsa>,
+    /// Planet visibility, when `includePhenomena` is set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phenomena: Option<HashMap<String, PlanetPhenomena>>,
+    /// Set when the house system is undefined at the layer's latitude and `polarFallback`
+    /// was used in its place
+    #[serde(rename = "houseSystemFallback", default, skip_serializing_if = "Option::is_none")]
+    pub house_system_fallback: Option<HouseSystemFallback>,
+    /// Included objects left out of `positions` because their calculation failed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<PositionWarning>,
+}
+
+/// House system substituted for one that has no cusps at the layer's latitude
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseSystemFallback {
+    pub requested: String,
+    pub used: String,
+}
+
+/// Ayanamsa applied to sidereal positions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppliedAyanamsa {
+    pub name: String,
+    /// Ayanamsa in degrees at the layer moment
+    pub value: f64,
 }
 
 /// Ephemeris response - only positions and settings
@@ -56,6 +121,30 @@ pub struct EphemerisResponse {
     pub vedic: Option<VedicPayload>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub western: Option<HashMap<String, WesternLayerData>>,
+    /// Aspect sets keyed by layer id (intra-layer) or "a:b" (inter-layer)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aspects: Option<HashMap<String, AspectSet>>,
+    /// How this response was produced; set per request and never cached
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<ResponseMeta>,
+}
+
+/// Details of a render response for debugging results
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseMeta {
+    /// Version of `aphrodite-core`
+    pub engine_version: String,
+    /// `swiss_ephemeris`, or `moshier` when no planet files are installed
+    pub ephemeris_backend: String,
+    /// Time spent answering the request, including cache lookups
+    pub computation_ms: u64,
+    pub cache_hit: bool,
+    /// Ayanamsa applied to sidereal positions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ayanamsa: Option<String>,
+    /// Layer warnings and house system fallbacks, as `<layerId>: <message>`
+    pub warnings: Vec<String>,
 }
 
 /// ChartSpec response - complete chart specification
@@ -65,6 +154,172 @@ pub struct ChartSpecResponse {
     pub ephemeris: EphemerisResponse, // For backward compatibility
 }
 
+/// Synastry response - report plus the positions it was computed from
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SynastryResponse {
+    pub report: SynastryReport,
+    pub ephemeris: EphemerisResponse,
+    /// Biwheel ChartSpec, when requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spec: Option<ChartSpec>,
+}
+
+/// Muhurta search response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MuhurtaResponse {
+    pub windows: Vec<MuhurtaWindow>,
+    #[serde(rename = "stepMinutes")]
+    pub step_minutes: u32,
+    /// Ayanamsa used for the sidereal positions
+    pub ayanamsa: String,
+}
+
+/// Electional search response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ElectionalSearchResponse {
+    pub windows: Vec<ElectionalWindow>,
+    /// The constraints as parsed, in request order
+    pub constraints: Vec<ElectionalConstraint>,
+    #[serde(rename = "stepMinutes")]
+    pub step_minutes: u32,
+}
+
+/// Rectification response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RectificationResponse {
+    /// Best candidate birth times, highest score first
+    pub candidates: Vec<RectificationCandidate>,
+    /// Number of birth times scored across the window
+    #[serde(rename = "candidatesScored")]
+    pub candidates_scored: usize,
+    #[serde(rename = "stepMinutes")]
+    pub step_minutes: u32,
+}
+
+/// Transit timeline response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitTimelineResponse {
+    #[serde(rename = "subjectId")]
+    pub subject_id: String,
+    pub timeline: TransitTimeline,
+}
+
+/// Longitudes at one moment, from `GET /positions`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionsResponse {
+    #[serde(rename = "dateTime")]
+    pub date_time: chrono::DateTime<chrono::Utc>,
+    /// Object id -> tropical longitude in degrees
+    pub longitudes: HashMap<String, f64>,
+    /// Ascendant, Midheaven, IC and Descendant; only with a location
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub angles: Option<HashMap<String, f64>>,
+    /// Object or angle id -> longitude written in the requested `format`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formatted: Option<HashMap<String, String>>,
+}
+
+/// Tabular time series from `GET /ephemeris`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisTableResponse {
+    /// Object ids, in the order of every row's columns
+    pub objects: Vec<String>,
+    pub rows: Vec<EphemerisTableRow>,
+}
+
+/// One moment of an ephemeris table; `null` marks an object that couldn't be calculated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisTableRow {
+    #[serde(rename = "dateTime")]
+    pub date_time: chrono::DateTime<chrono::Utc>,
+    pub longitudes: Vec<Option<f64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub declinations: Option<Vec<Option<f64>>>,
+    /// Degrees per day
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speeds: Option<Vec<Option<f64>>>,
+}
+
+/// Cache clear response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheClearResponse {
+    /// Entries removed
+    pub cleared: u64,
+}
+
+/// Installed Swiss Ephemeris files (admin)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisFilesResponse {
+    /// Directory the service reads ephemeris files from
+    pub path: String,
+    pub files: Vec<InstalledFile>,
+    pub coverage: EphemerisCoverage,
+    /// Standard files not installed; calculations outside the coverage fall back to Moshier
+    #[serde(rename = "missingStandardFiles")]
+    pub missing_standard_files: Vec<String>,
+    /// Whether this build can download missing files
+    #[serde(rename = "downloadEnabled")]
+    pub download_enabled: bool,
+}
+
+/// Years covered by the installed files of each kind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisCoverage {
+    pub planets: Vec<YearRange>,
+    pub moon: Vec<YearRange>,
+    pub asteroids: Vec<YearRange>,
+}
+
+/// Ephemeris file download response (admin)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisDownloadResponse {
+    pub downloaded: Vec<InstalledFile>,
+    /// Requested files that were already installed and left alone
+    #[serde(rename = "alreadyPresent")]
+    pub already_present: Vec<String>,
+}
+
+/// Default wheel reload response (admin)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WheelReloadResponse {
+    /// File the wheel was read from
+    pub path: String,
+    /// Name of the wheel now in use
+    pub name: String,
+    /// Number of rings
+    pub rings: usize,
+}
+
+/// Interpretation texts for a chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InterpretationResponse {
+    pub pack: String,
+    /// Locale of the pack the texts came from
+    pub locale: String,
+    /// Texts for the factors the pack covers: sign and house placements, aspects, then dashas
+    pub interpretations: Vec<Interpretation>,
+}
+
+/// Wheel definition check result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WheelValidateResponse {
+    /// Whether the wheel loads; warnings do not make it invalid
+    pub valid: bool,
+    /// Why the wheel does not load
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub warnings: Vec<WheelWarning>,
+}
+
+/// Dry-run check of a render request
+#[derive(Debug, Clone, Serialize)]
+pub struct RequestValidateResponse {
+    /// Whether the request would be accepted by `/render`
+    pub valid: bool,
+    /// Every problem found, in the format of `INVALID_FIELDS` error details
+    pub issues: Vec<FieldError>,
+}
+
 /// Health check response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HealthResponse {
@@ -72,12 +327,46 @@ pub struct HealthResponse {
     pub version: String,
 }
 
+/// Readiness probe response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    /// "ready" or "not_ready"
+    pub status: String,
+    pub version: String,
+    pub ephemeris: EphemerisReadiness,
+    pub pool: PoolReadiness,
+    #[serde(rename = "storageBackend")]
+    pub storage_backend: String,
+    #[serde(rename = "cacheBackend")]
+    pub cache_backend: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisReadiness {
+    /// `swiss_ephemeris` or `moshier`, as reported by the pooled service
+    pub backend: Option<String>,
+    pub path: Option<String>,
+    pub ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolReadiness {
+    pub size: usize,
+    /// Whether a pooled service could be acquired within the probe timeout
+    pub available: bool,
+}
+
 /// API info response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ApiInfoResponse {
     pub name: String,
     pub version: String,
     pub description: String,
+    /// Requests per minute per client IP, by endpoint
+    #[serde(rename = "rateLimits")]
+    pub rate_limits: RateLimits,
 }
 
 // Re-export Vedic types for convenience (only types not already imported above)
@@ -90,3 +379,30 @@ pub use aphrodite_core::western::{
     DignityType, ExactExaltation,
 };
 
+
+/// Transit positions pushed to a live chart subscriber
+#[derive(Debug, Clone, Serialize)]
+pub struct LiveUpdate {
+    #[serde(rename = "dateTime")]
+    pub date_time: chrono::DateTime<chrono::Utc>,
+    pub transits: LayerPositions,
+    /// Transit-to-natal aspects within orb
+    pub aspects: Vec<AspectPair>,
+    /// Aspects that perfected since the previous update
+    #[serde(rename = "newlyExact")]
+    pub newly_exact: Vec<AspectPair>,
+}
+
+/// Messages sent on the live chart WebSocket
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveMessage {
+    Subscribed {
+        #[serde(rename = "subjectId")]
+        subject_id: String,
+        #[serde(rename = "intervalSeconds")]
+        interval_seconds: u64,
+    },
+    Update(LiveUpdate),
+    Error { error: serde_json::Value },
+}
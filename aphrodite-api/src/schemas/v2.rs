@@ -0,0 +1,102 @@
+//! API v2 response schema (under development).
+//!
+//! v2 shares request types and services with v1; only the types here differ. Vedic and
+//! Western results sit inside each layer instead of in top-level maps keyed by layer id.
+
+use aphrodite_core::aspects::AspectSet;
+use aphrodite_core::rendering::ChartSpec;
+use aphrodite_core::vedic::{Ashtakavarga, VedicLayerData, VimshottariResponse};
+use aphrodite_core::western::WesternLayerData;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::schemas::request::ChartSettings;
+use crate::schemas::response as v1;
+
+/// Layer with its positions and the per-layer Vedic and Western results
+#[derive(Debug, Clone, Serialize)]
+pub struct LayerResponse {
+    #[serde(flatten)]
+    pub layer: v1::LayerResponse,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vedic: Option<VedicLayerData>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub western: Option<WesternLayerData>,
+}
+
+/// Vedic results that describe the chart as a whole
+#[derive(Debug, Clone, Serialize)]
+pub struct VedicChartData {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dashas: Option<VimshottariResponse>,
+    /// Ashtakavarga of the natal layer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ashtakavarga: Option<Ashtakavarga>,
+}
+
+/// Ephemeris response
+#[derive(Debug, Clone, Serialize)]
+pub struct EphemerisResponse {
+    pub layers: HashMap<String, LayerResponse>,
+    pub settings: ChartSettings,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vedic: Option<VedicChartData>,
+    /// Aspect sets keyed by layer id (intra-layer) or "a:b" (inter-layer)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aspects: Option<HashMap<String, AspectSet>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<v1::ResponseMeta>,
+}
+
+/// ChartSpec response
+#[derive(Debug, Clone, Serialize)]
+pub struct ChartSpecResponse {
+    pub spec: ChartSpec,
+    pub ephemeris: EphemerisResponse,
+}
+
+impl From<v1::EphemerisResponse> for EphemerisResponse {
+    fn from(response: v1::EphemerisResponse) -> Self {
+        let (mut vedic_layers, vedic) = match response.vedic {
+            Some(payload) => (
+                payload.layers,
+                Some(VedicChartData {
+                    dashas: payload.dashas,
+                    ashtakavarga: payload.ashtakavarga,
+                }),
+            ),
+            None => (HashMap::new(), None),
+        };
+        let mut western = response.western.unwrap_or_default();
+
+        let layers = response
+            .layers
+            .into_iter()
+            .map(|(id, layer)| {
+                let layer = LayerResponse {
+                    vedic: vedic_layers.remove(&id),
+                    western: western.remove(&id),
+                    layer,
+                };
+                (id, layer)
+            })
+            .collect();
+
+        Self {
+            layers,
+            settings: response.settings,
+            vedic,
+            aspects: response.aspects,
+            meta: response.meta,
+        }
+    }
+}
+
+impl From<v1::ChartSpecResponse> for ChartSpecResponse {
+    fn from(response: v1::ChartSpecResponse) -> Self {
+        Self {
+            spec: response.spec,
+            ephemeris: response.ephemeris.into(),
+        }
+    }
+}
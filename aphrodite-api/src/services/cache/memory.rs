@@ -0,0 +1,139 @@
+use async_trait::async_trait;
+use moka::sync::Cache;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{CacheBackend, CacheError};
+use crate::schemas::response::EphemerisResponse;
+
+/// In-process concurrent cache, used by default
+pub struct MemoryCache {
+    entries: Cache<String, EphemerisResponse>,
+    capacity: u64,
+    evictions: Arc<AtomicU64>,
+}
+
+impl MemoryCache {
+    /// Create a cache holding up to `capacity` responses, each kept for at most `ttl`
+    pub fn new(capacity: usize, ttl: Option<Duration>) -> Self {
+        let capacity = capacity.max(1) as u64;
+        let evictions = Arc::new(AtomicU64::new(0));
+        let counter = evictions.clone();
+        let mut builder = Cache::builder()
+            .max_capacity(capacity)
+            .eviction_listener(move |_key, _value, cause| {
+                // Explicit clears and replacements are not evictions
+                if cause.was_evicted() {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        if let Some(ttl) = ttl {
+            builder = builder.time_to_live(ttl);
+        }
+        Self {
+            entries: builder.build(),
+            capacity,
+            evictions,
+        }
+    }
+}
+
+#[async_trait]
+impl CacheBackend for MemoryCache {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<EphemerisResponse>, CacheError> {
+        Ok(self.entries.get(key))
+    }
+
+    async fn insert(&self, key: &str, response: &EphemerisResponse) -> Result<(), CacheError> {
+        self.entries.insert(key.to_string(), response.clone());
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), CacheError> {
+        self.entries.invalidate_all();
+        self.entries.run_pending_tasks();
+        Ok(())
+    }
+
+    async fn entry_count(&self) -> Result<u64, CacheError> {
+        self.entries.run_pending_tasks();
+        Ok(self.entries.entry_count())
+    }
+
+    fn capacity(&self) -> Option<u64> {
+        Some(self.capacity)
+    }
+
+    fn evictions(&self) -> Option<u64> {
+        self.entries.run_pending_tasks();
+        Some(self.evictions.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::cache::ResponseCache;
+
+    fn response() -> EphemerisResponse {
+        serde_json::from_value(serde_json::json!({
+            "layers": {},
+            "settings": {
+                "zodiacType": "tropical",
+                "houseSystem": "placidus",
+                "includeObjects": ["sun"]
+            }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_hit_miss_counters() {
+        let cache = ResponseCache::in_memory(4);
+        assert!(cache.get("a").await.is_none());
+        cache.insert("a", &response()).await;
+        assert!(cache.get("a").await.is_some());
+        assert!(cache.get("a").await.is_some());
+
+        let stats = cache.stats().await.unwrap();
+        assert_eq!(stats.backend, "memory");
+        assert_eq!(stats.hits, 2);
+        assert_eq!(stats.misses, 1);
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.capacity, Some(4));
+
+        cache.clear().await.unwrap();
+        assert_eq!(cache.stats().await.unwrap().entries, 0);
+        assert!(cache.get("a").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expiry() {
+        let cache = MemoryCache::new(4, Some(Duration::from_millis(50)));
+        cache.insert("a", &response()).await.unwrap();
+        assert!(cache.get("a").await.unwrap().is_some());
+        tokio::time::sleep(Duration::from_millis(80)).await;
+        assert!(cache.get("a").await.unwrap().is_none());
+        assert_eq!(cache.evictions(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_capacity_evictions() {
+        let cache = MemoryCache::new(2, None);
+        for key in ["a", "b", "c", "d"] {
+            cache.insert(key, &response()).await.unwrap();
+            cache.entries.run_pending_tasks();
+        }
+        assert_eq!(cache.entry_count().await.unwrap(), 2);
+        assert_eq!(cache.evictions(), Some(2));
+
+        // Clearing is not an eviction
+        cache.clear().await.unwrap();
+        assert_eq!(cache.evictions(), Some(2));
+    }
+}
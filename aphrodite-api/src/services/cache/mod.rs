@@ -0,0 +1,161 @@
+//! Pluggable response cache for ephemeris results.
+//!
+//! The backend (in-process or Redis) is chosen from configuration. Every service in
+//! the pool shares one `ResponseCache`, and with Redis so do all API replicas. Backend
+//! failures are logged and treated as misses so a cache outage never fails a request.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+use crate::config::Config;
+use crate::schemas::response::EphemerisResponse;
+
+pub mod memory;
+#[cfg(feature = "redis")]
+pub mod redis;
+
+pub use memory::MemoryCache;
+#[cfg(feature = "redis")]
+pub use self::redis::RedisCache;
+
+/// Cache errors
+#[derive(Error, Debug)]
+pub enum CacheError {
+    #[error("Unsupported cache backend: {0}")]
+    UnsupportedBackend(String),
+    #[error("Cache configuration error: {0}")]
+    Configuration(String),
+    #[error("Cache backend error: {0}")]
+    Backend(String),
+}
+
+/// Storage for cached ephemeris responses
+#[async_trait]
+pub trait CacheBackend: Send + Sync {
+    /// Backend name ("memory", "redis")
+    fn backend(&self) -> &'static str;
+
+    async fn get(&self, key: &str) -> Result<Option<EphemerisResponse>, CacheError>;
+
+    async fn insert(&self, key: &str, response: &EphemerisResponse) -> Result<(), CacheError>;
+
+    /// Remove every entry owned by this cache
+    async fn clear(&self) -> Result<(), CacheError>;
+
+    /// Number of cached entries
+    async fn entry_count(&self) -> Result<u64, CacheError>;
+
+    /// Maximum number of entries, when bounded
+    fn capacity(&self) -> Option<u64>;
+
+    /// Entries dropped for capacity or expiry, when the backend tracks them
+    fn evictions(&self) -> Option<u64>;
+}
+
+/// Snapshot of response cache counters
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    pub backend: &'static str,
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub capacity: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub evictions: Option<u64>,
+}
+
+/// Response cache shared by every service in the pool
+pub struct ResponseCache {
+    backend: Arc<dyn CacheBackend>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ResponseCache {
+    pub fn new(backend: Arc<dyn CacheBackend>) -> Self {
+        Self {
+            backend,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// In-process cache holding up to `capacity` responses without expiry
+    pub fn in_memory(capacity: usize) -> Self {
+        Self::new(Arc::new(MemoryCache::new(capacity, None)))
+    }
+
+    pub fn backend(&self) -> &'static str {
+        self.backend.backend()
+    }
+
+    /// Look up a response, recording a hit or miss
+    pub async fn get(&self, key: &str) -> Option<EphemerisResponse> {
+        let cached = self.backend.get(key).await.unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Response cache lookup failed");
+            None
+        });
+        let counter = if cached.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    pub async fn insert(&self, key: &str, response: &EphemerisResponse) {
+        if let Err(e) = self.backend.insert(key, response).await {
+            tracing::warn!(error = %e, "Response cache insert failed");
+        }
+    }
+
+    /// Drop all entries; counters are kept
+    pub async fn clear(&self) -> Result<(), CacheError> {
+        self.backend.clear().await
+    }
+
+    pub async fn stats(&self) -> Result<CacheStats, CacheError> {
+        Ok(CacheStats {
+            backend: self.backend.backend(),
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.backend.entry_count().await?,
+            capacity: self.backend.capacity(),
+            evictions: self.backend.evictions(),
+        })
+    }
+}
+
+/// Create the configured response cache
+pub fn from_config(config: &Config) -> Result<ResponseCache, CacheError> {
+    let ttl = config.cache_ttl_seconds.map(Duration::from_secs);
+    let backend: Arc<dyn CacheBackend> = match config.cache_backend.as_str() {
+        "memory" => Arc::new(MemoryCache::new(config.cache_size, ttl)),
+        #[cfg(feature = "redis")]
+        "redis" => {
+            let url = config.cache_url.as_deref().ok_or_else(|| {
+                CacheError::Configuration("CACHE_URL is required for the redis backend".to_string())
+            })?;
+            Arc::new(RedisCache::new(url, &config.cache_namespace, ttl)?)
+        }
+        other => {
+            return Err(CacheError::UnsupportedBackend(format!(
+                "{} (available: {})",
+                other,
+                available_backends().join(", ")
+            )))
+        }
+    };
+    Ok(ResponseCache::new(backend))
+}
+
+/// Cache backends compiled into this build
+pub fn available_backends() -> Vec<&'static str> {
+    #[allow(unused_mut)]
+    let mut backends = vec!["memory"];
+    #[cfg(feature = "redis")]
+    backends.push("redis");
+    backends
+}
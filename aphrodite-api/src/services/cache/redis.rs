@@ -0,0 +1,122 @@
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::{AsyncCommands, Client};
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use super::{CacheBackend, CacheError};
+use crate::schemas::response::EphemerisResponse;
+
+/// Keys fetched per SCAN round trip
+const SCAN_BATCH: usize = 500;
+
+/// Redis-backed cache shared between API replicas
+pub struct RedisCache {
+    client: Client,
+    connection: OnceCell<ConnectionManager>,
+    prefix: String,
+    ttl: Option<Duration>,
+}
+
+impl RedisCache {
+    /// Create a cache handle without connecting yet; keys live under `namespace`
+    pub fn new(url: &str, namespace: &str, ttl: Option<Duration>) -> Result<Self, CacheError> {
+        let client = Client::open(url)
+            .map_err(|e| CacheError::Configuration(format!("Invalid Redis URL '{}': {}", url, e)))?;
+        Ok(Self {
+            client,
+            connection: OnceCell::new(),
+            prefix: format!("{}:ephemeris:", namespace),
+            ttl,
+        })
+    }
+
+    async fn connection(&self) -> Result<ConnectionManager, CacheError> {
+        self.connection
+            .get_or_try_init(|| self.client.get_connection_manager())
+            .await
+            .cloned()
+            .map_err(backend_error)
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}{}", self.prefix, key)
+    }
+
+    /// All keys under this cache's namespace
+    async fn keys(&self, conn: &mut ConnectionManager) -> Result<Vec<String>, CacheError> {
+        let pattern = format!("{}*", self.prefix);
+        let mut keys = Vec::new();
+        let mut cursor: u64 = 0;
+        loop {
+            let (next, batch): (u64, Vec<String>) = redis::cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(SCAN_BATCH)
+                .query_async(conn)
+                .await
+                .map_err(backend_error)?;
+            keys.extend(batch);
+            if next == 0 {
+                return Ok(keys);
+            }
+            cursor = next;
+        }
+    }
+}
+
+#[async_trait]
+impl CacheBackend for RedisCache {
+    fn backend(&self) -> &'static str {
+        "redis"
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<EphemerisResponse>, CacheError> {
+        let mut conn = self.connection().await?;
+        let value: Option<String> = conn.get(self.key(key)).await.map_err(backend_error)?;
+        value
+            .map(|json| serde_json::from_str(&json))
+            .transpose()
+            .map_err(|e| CacheError::Backend(format!("Invalid cached response: {}", e)))
+    }
+
+    async fn insert(&self, key: &str, response: &EphemerisResponse) -> Result<(), CacheError> {
+        let json = serde_json::to_string(response)
+            .map_err(|e| CacheError::Backend(format!("Failed to serialize response: {}", e)))?;
+        let mut conn = self.connection().await?;
+        match self.ttl {
+            Some(ttl) => conn.set_ex(self.key(key), json, ttl.as_secs().max(1)).await,
+            None => conn.set(self.key(key), json).await,
+        }
+        .map_err(backend_error)
+    }
+
+    async fn clear(&self) -> Result<(), CacheError> {
+        let mut conn = self.connection().await?;
+        let keys = self.keys(&mut conn).await?;
+        for chunk in keys.chunks(SCAN_BATCH) {
+            conn.del::<_, ()>(chunk).await.map_err(backend_error)?;
+        }
+        Ok(())
+    }
+
+    async fn entry_count(&self) -> Result<u64, CacheError> {
+        let mut conn = self.connection().await?;
+        Ok(self.keys(&mut conn).await?.len() as u64)
+    }
+
+    fn capacity(&self) -> Option<u64> {
+        None
+    }
+
+    /// Redis expires and evicts keys server-side without reporting them per namespace
+    fn evictions(&self) -> Option<u64> {
+        None
+    }
+}
+
+fn backend_error(e: redis::RedisError) -> CacheError {
+    CacheError::Backend(e.to_string())
+}
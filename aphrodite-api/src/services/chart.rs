@@ -1,167 +1,304 @@
 use crate::error::ApiError;
-use crate::schemas::request::{ChartSettings, LayerConfig, RenderRequest, Subject, VedicConfig};
+use crate::services::cache::ResponseCache;
+use crate::services::wheel::DefaultWheel;
+use crate::schemas::request::{
+    AspectFilterSettings, CalculationMode, ChartRotation, ChartSettings, ElectionalSearchRequest, EphemerisTableQuery, InterpretationRequest, LayerConfig, MuhurtaRequest, PositionsQuery, RectificationRequest, RenderRequest,
+    Subject, SynastryRequest, TransitTimelineRequest, VedicConfig,
+};
 use crate::schemas::response::{
-    EphemerisResponse, HousePositions, LayerPositions, LayerResponse, PlanetPosition,
+    AppliedAyanamsa, ElectionalSearchResponse, EphemerisResponse, EphemerisTableResponse, EphemerisTableRow, HouseSystemFallback, InterpretationResponse, LayerPositions,
+    LayerResponse, MuhurtaResponse, PositionsResponse, ResponseMeta, SynastryResponse, RectificationResponse, TransitTimelineResponse,
 };
 use aphrodite_core::vedic::{VedicPayload, VedicLayerData, NakshatraLayer};
+use aphrodite_core::interpretations::{chart_factors, dasha_factors, InterpretationError, InterpretationLibrary};
 use aphrodite_core::western::WesternLayerData;
-use aphrodite_core::aspects::{AspectCalculator, AspectSettings};
+use aphrodite_core::aspects::{AspectCalculator, AspectFilter, AspectSet, AspectSettings};
+use aphrodite_core::electional::{parse_constraint, search_elections, ElectionalConstraint};
+use aphrodite_core::ephemeris::adapter::DEFAULT_AYANAMSA;
+use aphrodite_core::format::format_longitude;
+use aphrodite_core::ephemeris::progressions::{angle_arc, progressed_datetime, years_of_life};
+use aphrodite_core::ephemeris::coordinates::{ecliptic_to_equatorial, true_obliquity, Ecliptic};
 use aphrodite_core::ephemeris::{
-    EphemerisSettings, GeoLocation, LayerContext, SwissEphemerisAdapter,
+    fixed_star_longitude, lock_swiss_state, EphemerisSettings, GeoLocation, LayerContext, LilithType, MoonPosition,
+    NodeType, PolarFallback, PositionWarning, Progression, ProgressionOptions, SwissEphemerisAdapter, ANGLE_IDS,
+    DEFAULT_OBJECTS, FIXED_STARS,
+};
+use aphrodite_core::layout::{
+    load_wheel_definition_from_json, CustomPoint, WheelAssembler, WheelPoints, WheelPresets,
 };
-use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler};
+use aphrodite_core::rectification::{rank_birth_times, EventSky};
 use aphrodite_core::rendering::ChartSpecGenerator;
+use aphrodite_core::synastry::compute_synastry;
+use aphrodite_core::transits::{compute_transit_timeline, compute_transit_timeline_each, TransitTimelineDay};
 use aphrodite_core::vedic::{
-    annotate_layer_nakshatras, build_varga_layers, identify_yogas,
-    compute_vimshottari_dasha, compute_yogini_dasha, compute_ashtottari_dasha, compute_kalachakra_dasha,
-    DashaLevel, VimshottariResponse,
+    annotate_layer_nakshatras, build_varga_layers, compute_ashtakavarga, compute_avasthas, compute_bhava_chalit, identify_yogas, compute_daily_strength,
+    compute_dasha, filter_dasha_periods, search_muhurta_each, DashaLevel, MuhurtaConstraints, MuhurtaWindow, VimshottariResponse,
 };
 use aphrodite_core::western::{
-    DignitiesService, get_decan_info_from_longitude,
+    DecanSystem, DignitiesService, EssentialDignityScore, TermSystem, compute_analysis, compute_balance, compute_conditions, compute_interceptions, get_decan_info_from_longitude_with_system,
 };
 use chrono::{DateTime, Utc};
-use lru::LruCache;
+use rayon::prelude::*;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::num::NonZeroUsize;
 use std::path::PathBuf;
-use std::sync::Mutex;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+/// Version of the cache key layout; bump when cached responses change shape
+const CACHE_KEY_VERSION: u32 = 2;
+
+/// Events buffered between a streaming muhurta scan and its client
+const MUHURTA_STREAM_BUFFER: usize = 16;
+
+/// Progress of a streaming muhurta search
+#[derive(Debug)]
+pub enum MuhurtaEvent {
+    /// A window satisfying every constraint
+    Window(MuhurtaWindow),
+    /// The scan covered the whole range
+    Done { windows: usize },
+    /// The scan failed; no further events follow
+    Error(String),
+}
+
+/// Events buffered between a streaming transit timeline and its client
+const TRANSIT_STREAM_BUFFER: usize = 16;
+
+/// Progress of a streaming transit timeline
+#[derive(Debug)]
+pub enum TransitTimelineEvent {
+    /// The contacts on one sampled day
+    Day(TransitTimelineDay),
+    /// The scan covered the whole range
+    Done { days: usize },
+    /// The scan failed; no further events follow
+    Error(String),
+}
+
+/// A resolved transit timeline, ready to run on a blocking thread
+struct TransitScan {
+    natal_datetime: DateTime<Utc>,
+    location: Option<GeoLocation>,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    settings: EphemerisSettings,
+    aspect_settings: AspectSettings,
+    ephemeris_path: Option<PathBuf>,
+}
+
+impl TransitScan {
+    /// Open an adapter for the scan and compute the natal positions it compares against
+    fn prepare(&self) -> Result<(SwissEphemerisAdapter, aphrodite_core::ephemeris::LayerPositions), ApiError> {
+        let mut adapter = SwissEphemerisAdapter::new(self.ephemeris_path.clone())
+            .map_err(|e| ApiError::InternalError(format!("Failed to create temp adapter: {}", e)))?;
+        let natal = adapter.calc_positions(self.natal_datetime, self.location.clone(), &self.settings)?;
+        Ok((adapter, natal))
+    }
+}
+
+/// A resolved electional search, ready to run on a blocking thread
+pub struct ElectionalSearch {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    step_minutes: u32,
+    location: GeoLocation,
+    constraints: Vec<ElectionalConstraint>,
+    settings: EphemerisSettings,
+    orb_settings: HashMap<String, f64>,
+    ephemeris_path: Option<PathBuf>,
+}
+
+impl ElectionalSearch {
+    /// Search the range for moments that meet every constraint, on its own adapter
+    pub async fn run(self) -> Result<ElectionalSearchResponse, ApiError> {
+        let Self { start, end, step_minutes, location, constraints, settings, orb_settings, ephemeris_path } = self;
+        let step = chrono::Duration::minutes(step_minutes as i64);
+        let search_constraints = constraints.clone();
+
+        // Sampling the range is CPU-bound
+        let windows = tokio::task::spawn_blocking(move || {
+            let mut adapter = SwissEphemerisAdapter::new(ephemeris_path)
+                .map_err(|e| ApiError::InternalError(format!("Failed to create temp adapter: {}", e)))?;
+            let samples = adapter.calc_positions_range(start, end, step, Some(location), &settings)?;
+            search_elections(&samples, end, &search_constraints, &orb_settings)
+                .map_err(|e| ApiError::CalculationError(format!("Electional search error: {}", e)))
+        })
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))??;
+
+        Ok(ElectionalSearchResponse {
+            windows,
+            constraints,
+            step_minutes,
+        })
+    }
+}
+
+/// A resolved muhurta search, ready to run on a blocking thread
+struct MuhurtaScan {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    step: chrono::Duration,
+    location: GeoLocation,
+    constraints: MuhurtaConstraints,
+    natal_datetime: Option<DateTime<Utc>>,
+    settings: EphemerisSettings,
+    ephemeris_path: Option<PathBuf>,
+}
+
+impl MuhurtaScan {
+    /// Sample the range, handing windows to `on_window`; stops with an error once `cancelled` returns true
+    fn run(self, cancelled: impl Fn() -> bool, on_window: impl FnMut(MuhurtaWindow)) -> Result<(), ApiError> {
+        let mut adapter = SwissEphemerisAdapter::new(self.ephemeris_path)
+            .map_err(|e| ApiError::InternalError(format!("Failed to create temp adapter: {}", e)))?;
+        let natal_moon_lon = match self.natal_datetime {
+            Some(dt) => {
+                let natal = adapter.calc_positions(dt, None, &self.settings)?;
+                let moon = natal.planets.get("moon").ok_or_else(|| {
+                    ApiError::CalculationError("Moon position not found for the natal subject".to_string())
+                })?;
+                Some(moon.lon)
+            }
+            None => None,
+        };
+        let location = self.location;
+        let settings = self.settings;
+        search_muhurta_each(
+            self.start,
+            self.end,
+            self.step,
+            &location,
+            &self.constraints,
+            natal_moon_lon,
+            |moment| {
+                if cancelled() {
+                    return Err("search cancelled".to_string());
+                }
+                adapter
+                    .calc_positions(moment, Some(location.clone()), &settings)
+                    .map_err(|e| e.to_string())
+            },
+            on_window,
+        )
+        .map_err(|e| ApiError::CalculationError(format!("Muhurta error: {}", e)))
+    }
+}
+
+/// Request inputs that determine an ephemeris response
+#[derive(Serialize)]
+struct CacheKeyInputs<'a> {
+    subjects: &'a [Subject],
+    layer_config: &'a HashMap<String, LayerConfig>,
+    settings: &'a ChartSettings,
+}
+
+/// Rebuild a JSON value with object keys in sorted order
+fn canonical_json(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(entries.into_iter().map(|(k, v)| (k, canonical_json(v))).collect())
+        }
+        serde_json::Value::Array(items) => serde_json::Value::Array(items.into_iter().map(canonical_json).collect()),
+        other => other,
+    }
+}
 
 /// Chart calculation service
 pub struct ChartService {
-    _adapter: SwissEphemerisAdapter,
+    adapter: SwissEphemerisAdapter,
     ephemeris_path: Option<PathBuf>,
-    cache: Mutex<LruCache<String, EphemerisResponse>>,
-    default_wheel_json: String,
+    cache: Arc<ResponseCache>,
+    default_wheel: Arc<DefaultWheel>,
 }
 
 impl ChartService {
-    /// Create a new chart service
+    /// Create a new chart service with its own response cache
     pub fn new(ephemeris_path: Option<PathBuf>, cache_size: usize, default_wheel_json_path: Option<String>) -> Result<Self, ApiError> {
+        Self::with_cache(
+            ephemeris_path,
+            Arc::new(ResponseCache::in_memory(cache_size)),
+            Arc::new(DefaultWheel::load(default_wheel_json_path)),
+        )
+    }
+
+    /// Create a new chart service backed by a shared response cache and default wheel
+    pub fn with_cache(ephemeris_path: Option<PathBuf>, cache: Arc<ResponseCache>, default_wheel: Arc<DefaultWheel>) -> Result<Self, ApiError> {
         let path_for_adapter = ephemeris_path.clone();
         let adapter = SwissEphemerisAdapter::new(path_for_adapter)
             .map_err(|e| ApiError::InternalError(format!("Failed to create adapter: {}", e)))?; // Keep manual conversion here as it's a creation error
-        let cache = Mutex::new(LruCache::new(
-            NonZeroUsize::new(cache_size.max(1)).unwrap()
-        ));
-        
-        // Load default wheel JSON from file or use embedded fallback
-        let default_wheel_json = if let Some(path) = default_wheel_json_path {
-            std::fs::read_to_string(&path)
-                .unwrap_or_else(|_| {
-                    // Fallback to embedded default if file not found
-                    Self::embedded_default_wheel_json()
-                })
-        } else {
-            Self::embedded_default_wheel_json()
-        };
-        
+
         Ok(Self { 
-            _adapter: adapter,
+            adapter,
             ephemeris_path,
             cache,
-            default_wheel_json,
+            default_wheel,
         })
     }
     
-    /// Get embedded default wheel JSON (fallback)
-    fn embedded_default_wheel_json() -> String {
-        r#"
-        {
-          "name": "Standard Natal Wheel",
-          "rings": [
-            {
-              "slug": "ring_signs",
-              "type": "signs",
-              "label": "Zodiac Signs",
-              "orderIndex": 0,
-              "radiusInner": 0.85,
-              "radiusOuter": 1.0,
-              "dataSource": { "kind": "static_zodiac" }
-            },
-            {
-              "slug": "ring_houses",
-              "type": "houses",
-              "label": "Houses",
-              "orderIndex": 1,
-              "radiusInner": 0.75,
-              "radiusOuter": 0.85,
-              "dataSource": { "kind": "layer_houses", "layerId": "natal" }
-            },
-            {
-              "slug": "ring_planets",
-              "type": "planets",
-              "label": "Natal Planets",
-              "orderIndex": 2,
-              "radiusInner": 0.55,
-              "radiusOuter": 0.75,
-              "dataSource": { "kind": "layer_planets", "layerId": "natal" }
-            }
-          ]
-        }
-        "#.to_string()
-    }
-
-    /// Generate a cache key from request parameters
-    fn generate_cache_key(&self, request: &RenderRequest, settings: &ChartSettings) -> String {
-        use std::hash::{Hash, Hasher};
-        use std::collections::hash_map::DefaultHasher;
-        
-        let mut hasher = DefaultHasher::new();
-        
-        // Hash subjects
-        for subject in &request.subjects {
-            subject.id.hash(&mut hasher);
-            if let Some(dt) = &subject.birth_date_time {
-                dt.hash(&mut hasher);
-            }
-            if let Some(loc) = &subject.location {
-                loc.lat.to_bits().hash(&mut hasher);
-                loc.lon.to_bits().hash(&mut hasher);
-            }
-        }
-        
-        // Hash layer config
-        for (key, value) in &request.layer_config {
-            key.hash(&mut hasher);
-            value.kind.hash(&mut hasher);
-            if let Some(subject_id) = &value.subject_id {
-                subject_id.hash(&mut hasher);
-            }
-            if let Some(dt) = &value.explicit_date_time {
-                dt.hash(&mut hasher);
-            }
-            if let Some(loc) = &value.location {
-                loc.lat.to_bits().hash(&mut hasher);
-                loc.lon.to_bits().hash(&mut hasher);
-            }
-        }
-        
-        // Hash settings
-        settings.zodiac_type.hash(&mut hasher);
-        settings.house_system.hash(&mut hasher);
-        if let Some(ayanamsa) = &settings.ayanamsa {
-            ayanamsa.hash(&mut hasher);
-        }
-        settings.include_objects.hash(&mut hasher);
-        
-        // Hash settings_override (merged settings)
-        for (key, value) in &request.settings_override {
-            key.hash(&mut hasher);
-            // Hash the JSON value as string for simplicity
-            if let Some(s) = value.as_str() {
-                s.hash(&mut hasher);
-            } else if let Some(n) = value.as_f64() {
-                n.to_bits().hash(&mut hasher);
-            } else if let Some(b) = value.as_bool() {
-                b.hash(&mut hasher);
+    /// Directory the service reads ephemeris files from
+    pub fn ephemeris_path(&self) -> &std::path::Path {
+        self.adapter.ephemeris_path()
+    }
+
+    /// `swiss_ephemeris` when planet files are installed, otherwise `moshier`
+    pub fn ephemeris_backend(&self) -> &'static str {
+        self.adapter.backend()
+    }
+
+    /// Calculate the Sun at J2000 to confirm the ephemeris files are readable
+    pub async fn check_ephemeris(&self) -> Result<(), ApiError> {
+        let ephemeris_path = self.ephemeris_path.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut adapter = SwissEphemerisAdapter::new(ephemeris_path)?;
+            let settings = EphemerisSettings {
+                zodiac_type: "tropical".to_string(),
+                ayanamsa: None,
+                custom_ayanamsa: None,
+                house_system: "whole_sign".to_string(),
+                include_objects: vec!["sun".to_string()],
+                node_type: NodeType::default(),
+                lilith_type: LilithType::default(),
+                moon_position: MoonPosition::default(),
+                polar_fallback: PolarFallback::default(),
+            };
+            let j2000 = DateTime::parse_from_rfc3339("2000-01-01T12:00:00Z")
+                .map(|dt| dt.with_timezone(&Utc))
+                .map_err(|e| ApiError::InternalError(e.to_string()))?;
+            let positions = adapter.calc_positions(j2000, None, &settings)?;
+            if positions.planets.contains_key("sun") {
+                Ok(())
+            } else {
+                Err(ApiError::CalculationError("Sun position missing from ephemeris check".to_string()))
             }
-        }
-        
-        format!("ephemeris:{}", hasher.finish())
+        })
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))?
+    }
+
+    /// Generate a canonical cache key covering every request input.
+    ///
+    /// Subjects, layer config and the merged settings are serialized to JSON with
+    /// object keys sorted, then hashed with SHA-256, so the key is stable across runs
+    /// and replicas and independent of map iteration order.
+    fn generate_cache_key(request: &RenderRequest, settings: &ChartSettings) -> Result<String, ApiError> {
+        let inputs = serde_json::to_value(CacheKeyInputs {
+            subjects: &request.subjects,
+            layer_config: &request.layer_config,
+            settings,
+        })
+        .map_err(|e| ApiError::InternalError(format!("Failed to serialize cache key: {}", e)))?;
+        let digest = Sha256::digest(canonical_json(inputs).to_string().as_bytes());
+        let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+        Ok(format!("ephemeris:v{}:{}", CACHE_KEY_VERSION, hex))
     }
 
     /// Merge settings_override into settings
-    fn merge_settings_override(
+    pub(crate) fn merge_settings_override(
         settings: &mut ChartSettings,
         settings_override: &HashMap<String, serde_json::Value>,
     ) -> Result<(), ApiError> {
@@ -196,6 +333,17 @@ impl ChartService {
                         ));
                     }
                 }
+                "customAyanamsa" => {
+                    if value.is_null() {
+                        settings.custom_ayanamsa = None;
+                    } else if let Some(custom_ayanamsa) = value.as_f64() {
+                        settings.custom_ayanamsa = Some(custom_ayanamsa);
+                    } else {
+                        return Err(ApiError::ValidationError(
+                            format!("customAyanamsa must be a number or null, got: {:?}", value)
+                        ));
+                    }
+                }
                 "orbSettings" => {
                     if let Some(_obj) = value.as_object() {
                         if let Some(v) = _obj.get("conjunction") {
@@ -241,6 +389,97 @@ impl ChartService {
                         ));
                     }
                 }
+                "decanSystem" => {
+                    if let Some(decan_system) = value.as_str() {
+                        settings.decan_system = decan_system.to_string();
+                    } else {
+                        return Err(ApiError::ValidationError(
+                            format!("decanSystem must be a string, got: {:?}", value)
+                        ));
+                    }
+                }
+                "termSystem" => {
+                    if let Some(term_system) = value.as_str() {
+                        settings.term_system = term_system.to_string();
+                    } else {
+                        return Err(ApiError::ValidationError(
+                            format!("termSystem must be a string, got: {:?}", value)
+                        ));
+                    }
+                }
+                "includeAspects" => {
+                    if let Some(include_aspects) = value.as_bool() {
+                        settings.include_aspects = include_aspects;
+                    } else {
+                        return Err(ApiError::ValidationError(
+                            format!("includeAspects must be a boolean, got: {:?}", value)
+                        ));
+                    }
+                }
+                "objectOrbs" => {
+                    let orbs = value.as_object().and_then(|obj| {
+                        obj.iter().map(|(id, orb)| orb.as_f64().map(|orb| (id.clone(), orb))).collect::<Option<HashMap<_, _>>>()
+                    });
+                    if let Some(orbs) = orbs {
+                        settings.object_orbs = orbs;
+                    } else {
+                        return Err(ApiError::ValidationError(
+                            format!("objectOrbs must be an object of numbers, got: {:?}", value)
+                        ));
+                    }
+                }
+                "nodeType" => match serde_json::from_value::<NodeType>(value.clone()) {
+                    Ok(node_type) => settings.node_type = node_type,
+                    Err(e) => {
+                        return Err(ApiError::ValidationError(format!("Invalid nodeType: {}", e)));
+                    }
+                },
+                "lilithType" => match serde_json::from_value::<LilithType>(value.clone()) {
+                    Ok(lilith_type) => settings.lilith_type = lilith_type,
+                    Err(e) => {
+                        return Err(ApiError::ValidationError(format!("Invalid lilithType: {}", e)));
+                    }
+                },
+                "moonPosition" => match serde_json::from_value::<MoonPosition>(value.clone()) {
+                    Ok(moon_position) => settings.moon_position = moon_position,
+                    Err(e) => {
+                        return Err(ApiError::ValidationError(format!("Invalid moonPosition: {}", e)));
+                    }
+                },
+                "polarFallback" => match serde_json::from_value::<PolarFallback>(value.clone()) {
+                    Ok(polar_fallback) => settings.polar_fallback = polar_fallback,
+                    Err(e) => {
+                        return Err(ApiError::ValidationError(format!("Invalid polarFallback: {}", e)));
+                    }
+                },
+                "aspectFilter" => match serde_json::from_value::<AspectFilterSettings>(value.clone()) {
+                    Ok(aspect_filter) => settings.aspect_filter = aspect_filter,
+                    Err(e) => {
+                        return Err(ApiError::ValidationError(format!("Invalid aspectFilter: {}", e)));
+                    }
+                },
+                "includePhenomena" => {
+                    if let Some(include_phenomena) = value.as_bool() {
+                        settings.include_phenomena = include_phenomena;
+                    } else {
+                        return Err(ApiError::ValidationError(
+                            format!("includePhenomena must be a boolean, got: {:?}", value)
+                        ));
+                    }
+                }
+                "calculationMode" => {
+                    settings.calculation_mode = serde_json::from_value::<CalculationMode>(value.clone())
+                        .map_err(|e| ApiError::ValidationError(format!("Invalid calculationMode: {}", e)))?;
+                }
+                "excludeOutOfSign" => {
+                    if let Some(exclude_out_of_sign) = value.as_bool() {
+                        settings.exclude_out_of_sign = exclude_out_of_sign;
+                    } else {
+                        return Err(ApiError::ValidationError(
+                            format!("excludeOutOfSign must be a boolean, got: {:?}", value)
+                        ));
+                    }
+                }
                 "vedicConfig" => {
                     if value.is_null() {
                         settings.vedic_config = None;
@@ -263,8 +502,7 @@ impl ChartService {
                     }
                 }
                 _ => {
-                    // Unknown key - ignore or return error?
-                    // For now, we'll ignore unknown keys to allow future extensions
+                    // Unknown keys are rejected during validation in strict mode
                 }
             }
         }
@@ -272,39 +510,103 @@ impl ChartService {
     }
 
     /// Get ephemeris positions for a render request
+    #[tracing::instrument(name = "get_positions", skip_all, fields(layers = request.layer_config.len(), cache_hit))]
     pub async fn get_positions(
         &mut self,
         request: &RenderRequest,
     ) -> Result<EphemerisResponse, ApiError> {
+        let started = Instant::now();
+
         // Merge settings
         let mut settings = request.settings.clone();
         ChartService::merge_settings_override(&mut settings, &request.settings_override)?;
 
         // Check cache
-        let cache_key = self.generate_cache_key(request, &settings);
-        if let Ok(mut cache) = self.cache.lock() {
-            if let Some(cached_response) = cache.get(&cache_key) {
-                return Ok(cached_response.clone());
-            }
+        let cache_key = Self::generate_cache_key(request, &settings)?;
+        let cached = self.cache.get(&cache_key).await;
+        tracing::Span::current().record("cache_hit", cached.is_some());
+        if let Some(mut cached_response) = cached {
+            cached_response.meta = Some(self.response_meta(&cached_response, true, started));
+            return Ok(cached_response);
         }
 
         // Resolve layer contexts
-        let layer_contexts = self.resolve_layer_contexts(&request.subjects, &request.layer_config, &settings)?;
+        let layer_contexts = Self::resolve_layer_contexts(&request.subjects, &request.layer_config, &settings)?;
 
         // Calculate positions - wrap CPU-bound work in spawn_blocking
-        // Create a temporary adapter in the blocking task to avoid moving &mut self.adapter
+        // Layers are computed in parallel, each with its own temporary adapter. Swiss
+        // Ephemeris state is thread-local on Linux, but process-global on Apple and Windows,
+        // where lock_swiss_state() makes the layers take turns
         let layer_contexts_for_blocking = layer_contexts.clone();
         let ephemeris_path = self.ephemeris_path.clone();
-        let positions_by_layer = tokio::task::spawn_blocking(move || {
-            let mut temp_adapter = SwissEphemerisAdapter::new(ephemeris_path)
-                .map_err(|e| ApiError::InternalError(format!("Failed to create temp adapter: {}", e)))?; // Keep manual conversion here
+        let include_phenomena = settings.include_phenomena;
+        let strict = settings.calculation_mode == CalculationMode::Strict;
+        let span = tracing::info_span!("calculate_positions", layers = layer_contexts.len());
+        let (positions_by_layer, ayanamsa_by_layer, mut phenomena_by_layer, mut warnings_by_layer) = tokio::task::spawn_blocking(move || {
+            let _entered = span.enter();
+            let layers = layer_contexts_for_blocking
+                .par_iter()
+                .map(|ctx| {
+                    let _entered = tracing::info_span!(parent: &span, "calculate_layer", layer_id = %ctx.layer_id).entered();
+                    let _swiss_state = lock_swiss_state();
+                    let mut temp_adapter = SwissEphemerisAdapter::new(ephemeris_path.clone())
+                        .map_err(|e| ApiError::InternalError(format!("Failed to create temp adapter: {}", e)))?; // Keep manual conversion here
+                    let ((positions, warnings), moment) = match &ctx.progression {
+                        Some(progression) => {
+                            let positions = temp_adapter.calc_progressed_positions_with_warnings(
+                                progression.birth,
+                                ctx.datetime,
+                                ctx.location.clone(),
+                                &ctx.settings,
+                                &progression.options,
+                            )?;
+                            let moment =
+                                progressed_datetime(progression.birth, ctx.datetime, progression.options.converse);
+                            (positions, moment)
+                        }
+                        None => (
+                            temp_adapter.calc_positions_with_warnings(ctx.datetime, ctx.location.clone(), &ctx.settings)?, // Use From trait
+                            ctx.datetime,
+                        ),
+                    };
+                    let mut warnings = warnings;
+                    warnings.extend(Self::house_warnings(ctx, &positions));
+                    if strict && !warnings.is_empty() {
+                        let skipped: Vec<String> =
+                            warnings.iter().map(|w| format!("{} ({})", w.object_id, w.message)).collect();
+                        let message = format!("Could not calculate {} for layer {}", skipped.join(", "), ctx.layer_id);
+                        // A missing location is the caller's to fix; anything else is the ephemeris
+                        return Err(if ctx.location.is_none() {
+                            ApiError::ValidationError(message)
+                        } else {
+                            ApiError::CalculationError(message)
+                        });
+                    }
+                    let ayanamsa = temp_adapter.calc_ayanamsa(moment, &ctx.settings)?;
+                    let phenomena = if include_phenomena {
+                        Some(temp_adapter.calc_phenomena(moment, &ctx.settings)?)
+                    } else {
+                        None
+                    };
+                    Ok::<_, ApiError>((ctx.layer_id.clone(), positions, ayanamsa, phenomena, warnings))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
             let mut positions_by_layer = HashMap::new();
-            for ctx in &layer_contexts_for_blocking {
-                let positions = temp_adapter
-                    .calc_positions(ctx.datetime, ctx.location.clone(), &ctx.settings)?; // Use From trait
-                positions_by_layer.insert(ctx.layer_id.clone(), positions);
+            let mut ayanamsa_by_layer = HashMap::new();
+            let mut phenomena_by_layer = HashMap::new();
+            let mut warnings_by_layer = HashMap::new();
+            for (layer_id, positions, ayanamsa, phenomena, warnings) in layers {
+                warnings_by_layer.insert(layer_id.clone(), warnings);
+                if let Some(ayanamsa) = ayanamsa {
+                    ayanamsa_by_layer.insert(layer_id.clone(), ayanamsa);
+                }
+                if let Some(phenomena) = phenomena {
+                    phenomena_by_layer.insert(layer_id.clone(), phenomena);
+                }
+                positions_by_layer.insert(layer_id, positions);
             }
-            Ok::<HashMap<String, aphrodite_core::ephemeris::LayerPositions>, ApiError>(positions_by_layer)
+            Ok::<_, ApiError>((positions_by_layer, ayanamsa_by_layer, phenomena_by_layer, warnings_by_layer))
         })
         .await
         .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))??;
@@ -314,28 +616,6 @@ impl ChartService {
         let layer_contexts_for_response = layer_contexts.clone();
         for ctx in layer_contexts {
             if let Some(positions) = positions_by_layer.get(&ctx.layer_id) {
-                let planets: HashMap<String, PlanetPosition> = positions
-                    .planets
-                    .iter()
-                    .map(|(k, v)| {
-                        (
-                            k.clone(),
-                            PlanetPosition {
-                                lon: v.lon,
-                                lat: v.lat,
-                                speed_lon: Some(v.speed_lon),
-                                retrograde: Some(v.retrograde),
-                            },
-                        )
-                    })
-                    .collect();
-
-                let houses = positions.houses.as_ref().map(|h| HousePositions {
-                    system: h.system.clone(),
-                    cusps: h.cusps.clone(),
-                    angles: h.angles.clone(),
-                });
-
                 layers_response.insert(
                     ctx.layer_id.clone(),
                     LayerResponse {
@@ -347,10 +627,21 @@ impl ChartService {
                             lat: loc.lat,
                             lon: loc.lon,
                         }),
-                        positions: LayerPositions {
-                            planets,
-                            houses,
-                        },
+                        positions: LayerPositions::from(positions),
+                        ayanamsa: ayanamsa_by_layer.get(&ctx.layer_id).map(|value| AppliedAyanamsa {
+                            name: ctx.settings.ayanamsa.clone().unwrap_or_else(|| DEFAULT_AYANAMSA.to_string()),
+                            value: *value,
+                        }),
+                        phenomena: phenomena_by_layer.remove(&ctx.layer_id),
+                        house_system_fallback: positions
+                            .houses
+                            .as_ref()
+                            .filter(|houses| !houses.system.eq_ignore_ascii_case(&ctx.settings.house_system))
+                            .map(|houses| HouseSystemFallback {
+                                requested: ctx.settings.house_system.clone(),
+                                used: houses.system.clone(),
+                            }),
+                        warnings: warnings_by_layer.remove(&ctx.layer_id).unwrap_or_default(),
                     },
                 );
             }
@@ -369,23 +660,61 @@ impl ChartService {
         };
 
         // Calculate Western data (dignities and decans)
-        let western = self.calculate_western_data(&positions_by_layer)?;
+        let western = self.calculate_western_data(
+            &positions_by_layer,
+            &settings.decan_system,
+            &settings.term_system,
+        )?;
 
-        let response = EphemerisResponse {
+        // Calculate aspects if requested
+        let aspects = if settings.include_aspects {
+            Some(Self::calculate_aspect_sets(&positions_by_layer, &settings))
+        } else {
+            None
+        };
+
+        let mut response = EphemerisResponse {
             layers: layers_response,
             settings: settings.clone(),
             vedic,
             western: if western.is_empty() { None } else { Some(western) },
+            aspects,
+            meta: None,
         };
 
         // Insert into cache
-        if let Ok(mut cache) = self.cache.lock() {
-            cache.put(cache_key, response.clone());
-        }
+        self.cache.insert(&cache_key, &response).await;
 
+        response.meta = Some(self.response_meta(&response, false, started));
         Ok(response)
     }
 
+    /// Debugging details for a render response
+    fn response_meta(&self, response: &EphemerisResponse, cache_hit: bool, started: Instant) -> ResponseMeta {
+        let mut layers: Vec<_> = response.layers.values().collect();
+        layers.sort_by(|a, b| a.id.cmp(&b.id));
+        let mut warnings = Vec::new();
+        for layer in &layers {
+            if let Some(fallback) = &layer.house_system_fallback {
+                warnings.push(format!(
+                    "{}: {} houses are undefined at this latitude; used {}",
+                    layer.id, fallback.requested, fallback.used
+                ));
+            }
+            for warning in &layer.warnings {
+                warnings.push(format!("{}: {}: {}", layer.id, warning.object_id, warning.message));
+            }
+        }
+        ResponseMeta {
+            engine_version: aphrodite_core::VERSION.to_string(),
+            ephemeris_backend: self.adapter.backend().to_string(),
+            computation_ms: started.elapsed().as_millis() as u64,
+            cache_hit,
+            ayanamsa: layers.iter().find_map(|layer| layer.ayanamsa.as_ref()).map(|ayanamsa| ayanamsa.name.clone()),
+            warnings,
+        }
+    }
+
     /// Get ChartSpec for a render request
     /// Returns both the ChartSpec and the EphemerisResponse to avoid duplicate calculations
     pub async fn get_chartspec(
@@ -396,10 +725,155 @@ impl ChartService {
         // Get ephemeris positions first
         let ephemeris_response = self.get_positions(request).await?;
 
-        // Get settings from request
-        let _settings = &request.settings;
-
         // Convert to core types for aspect calculation
+        let positions_by_layer = Self::core_positions_by_layer(&ephemeris_response);
+
+        // Get settings from ephemeris response
+        let settings = &ephemeris_response.settings;
+
+        // Reuse the aspect sets already in the response; they use the same orb settings
+        let aspect_sets = match &ephemeris_response.aspects {
+            Some(aspects) => aspects.clone(),
+            None => Self::calculate_aspect_sets(&positions_by_layer, settings),
+        };
+
+        // Load wheel definition
+        // Use provided wheel_json, then the requested preset, then the configured default
+        let default_wheel_json = self.default_wheel.json();
+        let preset_json = request.wheel.as_deref().and_then(WheelPresets::get).map(|preset| preset.json);
+        let wheel_json_str = wheel_json.or(preset_json).unwrap_or(&default_wheel_json);
+
+        let wheel_def_with_presets = load_wheel_definition_from_json(wheel_json_str)?; // Use From trait
+
+        // Assemble wheel
+        let _entered = tracing::info_span!("generate_chartspec").entered();
+        let points = WheelPoints {
+            point_sets: request.custom_points.clone(),
+            fixed_stars: Self::fixed_star_points(&ephemeris_response),
+        };
+        let wheel = WheelAssembler::build_wheel_with_points(
+            &wheel_def_with_presets.wheel,
+            &positions_by_layer,
+            &aspect_sets,
+            if settings.include_objects.is_empty() {
+                None
+            } else {
+                Some(&settings.include_objects)
+            },
+            &points,
+        );
+
+        // Generate ChartSpec
+        let layout = request.layout.clone().unwrap_or_default();
+        let rotation = match layout.rotate {
+            ChartRotation::Aries => 0.0,
+            ChartRotation::Ascendant => {
+                let asc = Self::ascendant(&positions_by_layer, &request.layer_config).ok_or_else(|| {
+                    ApiError::ValidationError(
+                        "layout.rotate 'ascendant' requires a layer with house positions".to_string(),
+                    )
+                })?;
+                ChartSpecGenerator::rotation_for_left(wheel.project_longitude(asc))
+            }
+        };
+        let generator = ChartSpecGenerator::new().with_margin(layout.margin).with_rotation(rotation);
+        let spec = generator.generate(&wheel, &aspect_sets, layout.width, layout.height);
+
+        Ok((spec, ephemeris_response))
+    }
+
+    /// Catalogued fixed stars at each layer's moment, in the layer's zodiac
+    fn fixed_star_points(ephemeris: &EphemerisResponse) -> HashMap<String, Vec<CustomPoint>> {
+        ephemeris
+            .layers
+            .iter()
+            .map(|(layer_id, layer)| {
+                let ayanamsa = layer.ayanamsa.as_ref().map_or(0.0, |ayanamsa| ayanamsa.value);
+                let stars = FIXED_STARS
+                    .iter()
+                    .map(|star| CustomPoint {
+                        id: star.id.to_string(),
+                        label: star.name.to_string(),
+                        lon: (fixed_star_longitude(star, layer.date_time) - ayanamsa).rem_euclid(360.0),
+                    })
+                    .collect();
+                (layer_id.clone(), stars)
+            })
+            .collect()
+    }
+
+    /// Ascendant of the natal layer, or of the first layer (by id) with houses
+    fn ascendant(
+        positions_by_layer: &HashMap<String, aphrodite_core::ephemeris::LayerPositions>,
+        layer_config: &HashMap<String, LayerConfig>,
+    ) -> Option<f64> {
+        let mut layer_ids: Vec<&String> = positions_by_layer.keys().collect();
+        layer_ids.sort_by_key(|id| (layer_config.get(*id).map(|c| c.kind.as_str()) != Some("natal"), *id));
+        layer_ids
+            .into_iter()
+            .find_map(|id| positions_by_layer[id].houses.as_ref()?.angles.get("asc").copied())
+    }
+
+    /// Calculate intra- and inter-layer aspect sets using the chart's orb settings
+    fn calculate_aspect_sets(
+        positions_by_layer: &HashMap<String, aphrodite_core::ephemeris::LayerPositions>,
+        settings: &ChartSettings,
+    ) -> HashMap<String, AspectSet> {
+        let _entered = tracing::info_span!("calculate_aspects", layers = positions_by_layer.len()).entered();
+        let calculator = AspectCalculator::new();
+        calculator.compute_all_aspect_sets(positions_by_layer, &Self::aspect_settings(settings))
+    }
+
+    /// The warning for a layer whose houses are missing or fell back to another system
+    fn house_warnings(
+        ctx: &LayerContext,
+        positions: &aphrodite_core::ephemeris::LayerPositions,
+    ) -> Option<PositionWarning> {
+        let message = match &positions.houses {
+            None if ctx.location.is_none() => "No location, so houses and angles were not calculated".to_string(),
+            None => return None,
+            Some(houses) if houses.system.eq_ignore_ascii_case(&ctx.settings.house_system) => return None,
+            Some(houses) => format!(
+                "{} houses are undefined at this latitude; used {}",
+                ctx.settings.house_system, houses.system
+            ),
+        };
+        Some(PositionWarning { object_id: "houses".to_string(), message })
+    }
+
+    /// Build core aspect settings from chart settings
+    pub(crate) fn aspect_settings(settings: &ChartSettings) -> AspectSettings {
+        let orb_settings: HashMap<String, f64> = [
+            ("conjunction".to_string(), settings.orb_settings.conjunction),
+            ("opposition".to_string(), settings.orb_settings.opposition),
+            ("trine".to_string(), settings.orb_settings.trine),
+            ("square".to_string(), settings.orb_settings.square),
+            ("sextile".to_string(), settings.orb_settings.sextile),
+        ]
+        .into_iter()
+        .collect();
+        let filter = &settings.aspect_filter;
+        let pair = |[a, b]: &[String; 2]| (a.clone(), b.clone());
+
+        AspectSettings {
+            orb_settings,
+            include_objects: settings.include_objects.clone(),
+            only_major: Some(filter.only_major),
+            exclude_out_of_sign: settings.exclude_out_of_sign,
+            object_orbs: settings.object_orbs.clone(),
+            filter: AspectFilter {
+                aspect_types: filter.aspect_types.clone(),
+                include_pairs: filter.include_pairs.as_ref().map(|pairs| pairs.iter().map(pair).collect()),
+                exclude_pairs: filter.exclude_pairs.iter().map(pair).collect(),
+                include_angles: filter.include_angles,
+            },
+        }
+    }
+
+    /// Convert response layers back to core positions
+    fn core_positions_by_layer(
+        ephemeris_response: &EphemerisResponse,
+    ) -> HashMap<String, aphrodite_core::ephemeris::LayerPositions> {
         let mut positions_by_layer = HashMap::new();
         for (layer_id, layer) in &ephemeris_response.layers {
             let mut planets = HashMap::new();
@@ -428,53 +902,653 @@ impl ChartService {
                 aphrodite_core::ephemeris::LayerPositions { planets, houses },
             );
         }
+        positions_by_layer
+    }
 
-        // Get settings from ephemeris response
-        let settings = &ephemeris_response.settings;
+    /// Get a synastry report between two subjects
+    pub async fn get_synastry(
+        &mut self,
+        request: &SynastryRequest,
+    ) -> Result<SynastryResponse, ApiError> {
+        let (subject_a, subject_b) = match request.subjects.as_slice() {
+            [a, b] => (a, b),
+            _ => {
+                return Err(ApiError::ValidationError(
+                    "Synastry requires exactly two subjects".to_string(),
+                ))
+            }
+        };
 
-        // Calculate aspects
-        let calculator = AspectCalculator::new();
-        let orb_settings: HashMap<String, f64> = [
-            ("conjunction".to_string(), settings.orb_settings.conjunction),
-            ("opposition".to_string(), settings.orb_settings.opposition),
-            ("trine".to_string(), settings.orb_settings.trine),
-            ("square".to_string(), settings.orb_settings.square),
-            ("sextile".to_string(), settings.orb_settings.sextile),
-        ]
-        .into_iter()
-        .collect();
+        // Each subject becomes a natal layer keyed by its id
+        let layer_config = [subject_a, subject_b]
+            .iter()
+            .map(|subject| {
+                (
+                    subject.id.clone(),
+                    LayerConfig {
+                        kind: "natal".to_string(),
+                        subject_id: Some(subject.id.clone()),
+                        explicit_date_time: None,
+                        location: None,
+                        progression: None,
+                    },
+                )
+            })
+            .collect();
+        let render_request = RenderRequest {
+            subjects: request.subjects.clone(),
+            settings: request.settings.clone(),
+            layer_config,
+            settings_override: HashMap::new(),
+            layout: None,
+            custom_points: HashMap::new(),
+            wheel: None,
+        };
 
-        let aspect_settings = AspectSettings {
-            orb_settings,
-            include_objects: settings.include_objects.clone(),
-            only_major: None,
+        let ephemeris = self.get_positions(&render_request).await?;
+        let positions_by_layer = Self::core_positions_by_layer(&ephemeris);
+        let (positions_a, positions_b) = match (
+            positions_by_layer.get(&subject_a.id),
+            positions_by_layer.get(&subject_b.id),
+        ) {
+            (Some(a), Some(b)) => (a, b),
+            _ => {
+                return Err(ApiError::InternalError(
+                    "Synastry positions missing for a subject".to_string(),
+                ))
+            }
         };
 
-        let aspect_sets = calculator.compute_all_aspect_sets(&positions_by_layer, &aspect_settings);
+        let report = compute_synastry(
+            &subject_a.id,
+            &subject_b.id,
+            positions_a,
+            positions_b,
+            &Self::aspect_settings(&ephemeris.settings),
+        );
 
-        // Load wheel definition
-        // Use provided wheel_json, or fall back to configured default
-        let wheel_json_str = wheel_json.unwrap_or(&self.default_wheel_json);
+        let spec = if request.include_chart_spec {
+            let wheel_json = Self::biwheel_json(&subject_a.id, &subject_b.id, &report.aspects.id);
+            let wheel_def_with_presets = load_wheel_definition_from_json(&wheel_json)?;
+            let aspect_sets: HashMap<String, AspectSet> =
+                [(report.aspects.id.clone(), report.aspects.clone())].into_iter().collect();
+            let settings = &ephemeris.settings;
+            let wheel = WheelAssembler::build_wheel(
+                &wheel_def_with_presets.wheel,
+                &positions_by_layer,
+                &aspect_sets,
+                if settings.include_objects.is_empty() {
+                    None
+                } else {
+                    Some(&settings.include_objects)
+                },
+            );
+            let generator = ChartSpecGenerator::new();
+            Some(generator.generate(&wheel, &aspect_sets, 800.0, 800.0))
+        } else {
+            None
+        };
 
-        let wheel_def_with_presets = load_wheel_definition_from_json(wheel_json_str)?; // Use From trait
+        Ok(SynastryResponse {
+            report,
+            ephemeris,
+            spec,
+        })
+    }
 
-        // Assemble wheel
-        let wheel = WheelAssembler::build_wheel(
-            &wheel_def_with_presets.wheel,
-            &positions_by_layer,
-            &aspect_sets,
-            if settings.include_objects.is_empty() {
-                None
+    /// Interpretation texts for a subject's natal chart and, when dashas are requested, the
+    /// dasha periods running at `request.at`
+    pub async fn get_interpretations(
+        &mut self,
+        request: &InterpretationRequest,
+        library: &InterpretationLibrary,
+    ) -> Result<InterpretationResponse, ApiError> {
+        // Fail before calculating anything when the pack is not installed
+        let locale = library
+            .get(&request.pack, &request.locale)
+            .ok_or_else(|| InterpretationError::UnknownPack { id: request.pack.clone(), locale: request.locale.clone() })?
+            .locale
+            .clone();
+        let at = match &request.at {
+            Some(at) => parse_datetime(at, None)?,
+            None => Utc::now(),
+        };
+
+        let subject = &request.subject;
+        let render_request = RenderRequest {
+            subjects: vec![subject.clone()],
+            settings: request.settings.clone(),
+            layer_config: HashMap::from([(
+                subject.id.clone(),
+                LayerConfig {
+                    kind: "natal".to_string(),
+                    subject_id: Some(subject.id.clone()),
+                    explicit_date_time: None,
+                    location: None,
+                    progression: None,
+                },
+            )]),
+            settings_override: HashMap::new(),
+            layout: None,
+            custom_points: HashMap::new(),
+            wheel: None,
+        };
+        let ephemeris = self.get_positions(&render_request).await?;
+        let positions = Self::core_positions_by_layer(&ephemeris).remove(&subject.id).ok_or_else(|| {
+            ApiError::InternalError("Interpretation positions missing for the subject".to_string())
+        })?;
+
+        let aspects = ephemeris.aspects.as_ref().and_then(|sets| sets.get(&subject.id));
+        let mut factors = chart_factors(&positions, aspects);
+        if let Some(dashas) = ephemeris.vedic.as_ref().and_then(|vedic| vedic.dashas.as_ref()) {
+            factors.extend(dasha_factors(&dashas.periods, at));
+        }
+
+        Ok(InterpretationResponse {
+            pack: request.pack.clone(),
+            interpretations: library.interpret(&request.pack, &locale, &factors)?,
+            locale,
+        })
+    }
+
+    /// Search a date range for muhurta windows
+    pub async fn get_muhurta(&self, request: &MuhurtaRequest) -> Result<MuhurtaResponse, ApiError> {
+        let scan = self.muhurta_scan(request)?;
+
+        // Sampling the range is CPU-bound
+        let windows = tokio::task::spawn_blocking(move || {
+            let mut windows = Vec::new();
+            scan.run(|| false, |window| windows.push(window))?;
+            Ok::<_, ApiError>(windows)
+        })
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))??;
+
+        Ok(MuhurtaResponse {
+            windows,
+            step_minutes: request.step_minutes,
+            ayanamsa: request.ayanamsa.clone(),
+        })
+    }
+
+    /// Start a muhurta search that reports each window as soon as it is found.
+    ///
+    /// The scan runs on a blocking thread and ends with a `Done` or `Error` event; it
+    /// stops early once the receiver is dropped.
+    pub fn stream_muhurta(&self, request: &MuhurtaRequest) -> Result<mpsc::Receiver<MuhurtaEvent>, ApiError> {
+        let scan = self.muhurta_scan(request)?;
+        let (tx, rx) = mpsc::channel(MUHURTA_STREAM_BUFFER);
+
+        tokio::task::spawn_blocking(move || {
+            let mut count = 0;
+            let result = scan.run(
+                || tx.is_closed(),
+                |window| {
+                    count += 1;
+                    let _ = tx.blocking_send(MuhurtaEvent::Window(window));
+                },
+            );
+            let last = match result {
+                Ok(()) => MuhurtaEvent::Done { windows: count },
+                Err(e) => MuhurtaEvent::Error(e.to_string()),
+            };
+            let _ = tx.blocking_send(last);
+        });
+
+        Ok(rx)
+    }
+
+    /// Resolve an electional search so it can run without holding the service; see
+    /// [`ElectionalSearch::run`]
+    pub fn electional_search(&self, request: &ElectionalSearchRequest) -> Result<ElectionalSearch, ApiError> {
+        let constraints = request
+            .constraints
+            .iter()
+            .map(|text| parse_constraint(text).map_err(ApiError::ValidationError))
+            .collect::<Result<Vec<_>, _>>()?;
+        let start = parse_datetime(&request.start, None)?;
+        let end = parse_datetime(&request.end, None)?;
+        let location = GeoLocation {
+            lat: request.location.lat,
+            lon: request.location.lon,
+        };
+
+        // Every object a constraint names is calculated, on top of the requested ones
+        let settings = &request.settings;
+        let mut include_objects: Vec<String> = if settings.include_objects.is_empty() {
+            DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect()
+        } else {
+            settings.include_objects.clone()
+        };
+        for id in constraints.iter().flat_map(|constraint| constraint.objects()) {
+            if !include_objects.iter().any(|included| included == id) {
+                include_objects.push(id.to_string());
+            }
+        }
+        let ephemeris_settings = EphemerisSettings {
+            zodiac_type: settings.zodiac_type.clone(),
+            ayanamsa: settings.ayanamsa.clone(),
+            custom_ayanamsa: settings.custom_ayanamsa,
+            house_system: settings.house_system.clone(),
+            include_objects,
+            node_type: settings.node_type,
+            lilith_type: settings.lilith_type,
+            moon_position: settings.moon_position,
+            polar_fallback: settings.polar_fallback,
+        };
+
+        Ok(ElectionalSearch {
+            start,
+            end,
+            step_minutes: request.step_minutes,
+            location,
+            constraints,
+            settings: ephemeris_settings,
+            orb_settings: Self::aspect_settings(settings).orb_settings,
+            ephemeris_path: self.ephemeris_path.clone(),
+        })
+    }
+
+    /// Rank candidate birth times by how the events hit their angles
+    pub async fn get_rectification(&self, request: &RectificationRequest) -> Result<RectificationResponse, ApiError> {
+        let start = parse_datetime(&request.window_start, None)?;
+        let end = parse_datetime(&request.window_end, None)?;
+        let step = chrono::Duration::minutes(request.step_minutes as i64);
+        let event_dates = request
+            .events
+            .iter()
+            .map(|event| parse_datetime(&event.date, None))
+            .collect::<Result<Vec<_>, _>>()?;
+        let location = GeoLocation {
+            lat: request.location.lat,
+            lon: request.location.lon,
+        };
+        let settings = &request.settings;
+        let ephemeris_settings = EphemerisSettings {
+            zodiac_type: settings.zodiac_type.clone(),
+            ayanamsa: settings.ayanamsa.clone(),
+            custom_ayanamsa: settings.custom_ayanamsa,
+            house_system: settings.house_system.clone(),
+            include_objects: if settings.include_objects.is_empty() {
+                DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect()
             } else {
-                Some(&settings.include_objects)
+                settings.include_objects.clone()
             },
-        );
+            node_type: settings.node_type,
+            lilith_type: settings.lilith_type,
+            moon_position: settings.moon_position,
+            polar_fallback: settings.polar_fallback,
+        };
+        let limit = request.limit;
+        let ephemeris_path = self.ephemeris_path.clone();
 
-        // Generate ChartSpec
-        let generator = ChartSpecGenerator::new();
-        let spec = generator.generate(&wheel, &aspect_sets, 800.0, 800.0);
+        // Sampling the window is CPU-bound
+        let (candidates, candidates_scored) = tokio::task::spawn_blocking(move || {
+            let mut adapter = SwissEphemerisAdapter::new(ephemeris_path)
+                .map_err(|e| ApiError::InternalError(format!("Failed to create temp adapter: {}", e)))?;
+            let candidates = adapter.calc_positions_range(start, end, step, Some(location), &ephemeris_settings)?;
 
-        Ok((spec, ephemeris_response))
+            // The solar arc barely changes across the window, so it is taken from its middle
+            let middle = start + (end - start) / 2;
+            let sun_settings = EphemerisSettings {
+                include_objects: vec!["sun".to_string()],
+                ..ephemeris_settings.clone()
+            };
+            let mut sun_at = |moment| {
+                adapter
+                    .calc_positions(moment, None, &sun_settings)?
+                    .planets
+                    .get("sun")
+                    .map(|sun| sun.lon)
+                    .ok_or_else(|| ApiError::CalculationError("Sun position unavailable".to_string()))
+            };
+            let natal_sun = sun_at(middle)?;
+            let mut arcs = Vec::with_capacity(event_dates.len());
+            for date in &event_dates {
+                let progressed_sun = sun_at(progressed_datetime(middle, *date, false))?;
+                let options = ProgressionOptions::default();
+                arcs.push(angle_arc(&options, years_of_life(middle, *date), natal_sun, progressed_sun));
+            }
+            let events = event_dates
+                .into_iter()
+                .zip(arcs)
+                .map(|(date, solar_arc)| {
+                    let transits = adapter.calc_positions(date, None, &ephemeris_settings)?;
+                    Ok(EventSky { date, transits, solar_arc })
+                })
+                .collect::<Result<Vec<_>, ApiError>>()?;
+
+            Ok::<_, ApiError>((rank_birth_times(&candidates, &events, limit), candidates.len()))
+        })
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))??;
+
+        Ok(RectificationResponse {
+            candidates,
+            candidates_scored,
+            step_minutes: request.step_minutes,
+        })
+    }
+
+    /// Longitudes at one moment with the default settings; no layers and no response cache
+    pub async fn get_raw_positions(&self, query: &PositionsQuery) -> Result<PositionsResponse, ApiError> {
+        let datetime = parse_datetime(&query.datetime, None)?;
+        let location = query.lat.zip(query.lon).map(|(lat, lon)| GeoLocation { lat, lon });
+        let object_ids = query.object_ids();
+        let settings = EphemerisSettings {
+            zodiac_type: query.zodiac_type.clone(),
+            ayanamsa: query.ayanamsa.clone(),
+            custom_ayanamsa: query.custom_ayanamsa,
+            house_system: "placidus".to_string(),
+            include_objects: if object_ids.is_empty() {
+                DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect()
+            } else {
+                object_ids
+            },
+            node_type: NodeType::default(),
+            lilith_type: LilithType::default(),
+            moon_position: MoonPosition::default(),
+            polar_fallback: PolarFallback::default(),
+        };
+        let ephemeris_path = self.ephemeris_path.clone();
+        let format = query.format;
+
+        tokio::task::spawn_blocking(move || {
+            let mut adapter = SwissEphemerisAdapter::new(ephemeris_path)
+                .map_err(|e| ApiError::InternalError(format!("Failed to create temp adapter: {}", e)))?;
+            let positions = adapter.calc_positions(datetime, location, &settings)?;
+            let longitudes: HashMap<String, f64> =
+                positions.planets.into_iter().map(|(id, position)| (id, position.lon)).collect();
+            let angles: Option<HashMap<String, f64>> = positions.houses.map(|houses| {
+                houses
+                    .angles
+                    .into_iter()
+                    .filter(|(id, _)| ANGLE_IDS.contains(&id.as_str()))
+                    .collect()
+            });
+            let formatted = format.map(|format| {
+                longitudes
+                    .iter()
+                    .chain(angles.iter().flatten())
+                    .map(|(id, lon)| (id.clone(), format_longitude(*lon, format)))
+                    .collect()
+            });
+            Ok::<_, ApiError>(PositionsResponse {
+                date_time: datetime,
+                longitudes,
+                angles,
+                formatted,
+            })
+        })
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))?
+    }
+
+    /// Longitudes, and optionally declinations and speeds, of objects at regular steps
+    pub async fn get_ephemeris_table(&self, query: &EphemerisTableQuery) -> Result<EphemerisTableResponse, ApiError> {
+        let from = parse_datetime(&query.from, None)?;
+        let to = parse_datetime(&query.to, None)?;
+        let step = query
+            .step_duration()
+            .ok_or_else(|| ApiError::ValidationError(format!("Invalid step: {}", query.step)))?;
+        let object_ids = query.object_ids();
+        let objects: Vec<String> = if object_ids.is_empty() {
+            DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect()
+        } else {
+            object_ids
+        };
+        let settings = EphemerisSettings {
+            zodiac_type: "tropical".to_string(),
+            ayanamsa: None,
+            custom_ayanamsa: None,
+            house_system: "placidus".to_string(),
+            include_objects: objects.clone(),
+            node_type: NodeType::default(),
+            lilith_type: LilithType::default(),
+            moon_position: MoonPosition::default(),
+            polar_fallback: PolarFallback::default(),
+        };
+        let (include_declination, include_speed) = (query.declination, query.speed);
+        let ephemeris_path = self.ephemeris_path.clone();
+
+        // Sampling the range is CPU-bound
+        tokio::task::spawn_blocking(move || {
+            let mut adapter = SwissEphemerisAdapter::new(ephemeris_path)
+                .map_err(|e| ApiError::InternalError(format!("Failed to create temp adapter: {}", e)))?;
+            let rows = adapter
+                .calc_positions_range(from, to, step, None, &settings)?
+                .into_iter()
+                .map(|(moment, positions)| {
+                    let column = |value: &dyn Fn(&aphrodite_core::ephemeris::PlanetPosition) -> f64| {
+                        objects.iter().map(|id| positions.planets.get(id).map(value)).collect::<Vec<_>>()
+                    };
+                    let obliquity = true_obliquity(moment);
+                    EphemerisTableRow {
+                        date_time: moment,
+                        longitudes: column(&|position| position.lon),
+                        declinations: include_declination.then(|| {
+                            column(&|position| {
+                                ecliptic_to_equatorial(Ecliptic { lon: position.lon, lat: position.lat }, obliquity).dec
+                            })
+                        }),
+                        speeds: include_speed.then(|| column(&|position| position.speed_lon)),
+                    }
+                })
+                .collect();
+            Ok::<_, ApiError>(EphemerisTableResponse { objects, rows })
+        })
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))?
+    }
+
+    /// Day-by-day transit aspects to a subject's natal chart
+    pub async fn get_transit_timeline(
+        &self,
+        request: &TransitTimelineRequest,
+    ) -> Result<TransitTimelineResponse, ApiError> {
+        let scan = self.transit_scan(request)?;
+
+        // Sampling the range is CPU-bound
+        let timeline = tokio::task::spawn_blocking(move || {
+            let (mut adapter, natal) = scan.prepare()?;
+            compute_transit_timeline(&natal, scan.start, scan.end, &scan.aspect_settings, |moment| {
+                adapter
+                    .calc_positions(moment, None, &scan.settings)
+                    .map_err(|e| e.to_string())
+            })
+            .map_err(|e| ApiError::CalculationError(format!("Transit timeline error: {}", e)))
+        })
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Task join error: {}", e)))??;
+
+        Ok(TransitTimelineResponse {
+            subject_id: request.subject.id.clone(),
+            timeline,
+        })
+    }
+
+    /// Start a transit timeline that reports each day as soon as it is sampled.
+    ///
+    /// The scan runs on a blocking thread and ends with a `Done` or `Error` event; it
+    /// stops early once the receiver is dropped.
+    pub fn stream_transit_timeline(
+        &self,
+        request: &TransitTimelineRequest,
+    ) -> Result<mpsc::Receiver<TransitTimelineEvent>, ApiError> {
+        let scan = self.transit_scan(request)?;
+        let (tx, rx) = mpsc::channel(TRANSIT_STREAM_BUFFER);
+
+        tokio::task::spawn_blocking(move || {
+            let mut count = 0;
+            let result = scan.prepare().and_then(|(mut adapter, natal)| {
+                compute_transit_timeline_each(
+                    &natal,
+                    scan.start,
+                    scan.end,
+                    &scan.aspect_settings,
+                    |moment| {
+                        if tx.is_closed() {
+                            return Err("timeline cancelled".to_string());
+                        }
+                        adapter
+                            .calc_positions(moment, None, &scan.settings)
+                            .map_err(|e| e.to_string())
+                    },
+                    |day| {
+                        count += 1;
+                        let _ = tx.blocking_send(TransitTimelineEvent::Day(day));
+                    },
+                )
+                .map_err(|e| ApiError::CalculationError(format!("Transit timeline error: {}", e)))
+            });
+            let last = match result {
+                Ok(()) => TransitTimelineEvent::Done { days: count },
+                Err(e) => TransitTimelineEvent::Error(e.to_string()),
+            };
+            let _ = tx.blocking_send(last);
+        });
+
+        Ok(rx)
+    }
+
+    /// Resolve a transit timeline request into a scan that can run off the async runtime
+    fn transit_scan(&self, request: &TransitTimelineRequest) -> Result<TransitScan, ApiError> {
+        let subject = &request.subject;
+        let birth_dt = subject.birth_date_time.as_deref().ok_or_else(|| {
+            ApiError::ValidationError(format!("Subject '{}' missing 'birthDateTime'", subject.id))
+        })?;
+        let natal_datetime = parse_datetime(birth_dt, subject.birth_timezone.as_deref())?;
+        let start = parse_datetime(&request.start, None)?;
+        let end = parse_datetime(&request.end, None)?;
+        let location = subject.location.as_ref().map(|loc| GeoLocation {
+            lat: loc.lat,
+            lon: loc.lon,
+        });
+        let settings = &request.settings;
+        let ephemeris_settings = EphemerisSettings {
+            zodiac_type: settings.zodiac_type.clone(),
+            ayanamsa: settings.ayanamsa.clone(),
+            custom_ayanamsa: settings.custom_ayanamsa,
+            house_system: settings.house_system.clone(),
+            include_objects: if settings.include_objects.is_empty() {
+                DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect()
+            } else {
+                settings.include_objects.clone()
+            },
+            node_type: settings.node_type,
+            lilith_type: settings.lilith_type,
+            moon_position: settings.moon_position,
+            polar_fallback: settings.polar_fallback,
+        };
+
+        Ok(TransitScan {
+            natal_datetime,
+            location,
+            start,
+            end,
+            settings: ephemeris_settings,
+            aspect_settings: Self::aspect_settings(settings),
+            ephemeris_path: self.ephemeris_path.clone(),
+        })
+    }
+
+    /// Resolve a muhurta request into a scan that can run off the async runtime
+    fn muhurta_scan(&self, request: &MuhurtaRequest) -> Result<MuhurtaScan, ApiError> {
+        let start = parse_datetime(&request.start, None)?;
+        let end = parse_datetime(&request.end, None)?;
+        let location = GeoLocation {
+            lat: request.location.lat,
+            lon: request.location.lon,
+        };
+
+        // The natal Moon is only needed for tarabala/chandrabala constraints
+        let natal_datetime = if request.constraints.needs_natal_moon() {
+            let subject = request.subject.as_ref().ok_or_else(|| {
+                ApiError::ValidationError("Tarabala and chandrabala constraints require a subject".to_string())
+            })?;
+            let birth_dt = subject.birth_date_time.as_deref().ok_or_else(|| {
+                ApiError::ValidationError(format!("Subject '{}' missing 'birthDateTime'", subject.id))
+            })?;
+            Some(parse_datetime(birth_dt, subject.birth_timezone.as_deref())?)
+        } else {
+            None
+        };
+
+        Ok(MuhurtaScan {
+            start,
+            end,
+            step: chrono::Duration::minutes(request.step_minutes as i64),
+            location,
+            constraints: request.constraints.clone(),
+            natal_datetime,
+            settings: EphemerisSettings {
+                zodiac_type: "sidereal".to_string(),
+                ayanamsa: Some(request.ayanamsa.clone()),
+                custom_ayanamsa: request.custom_ayanamsa,
+                house_system: "whole_sign".to_string(),
+                include_objects: vec!["sun".to_string(), "moon".to_string()],
+                node_type: NodeType::default(),
+                lilith_type: LilithType::default(),
+                moon_position: MoonPosition::default(),
+                polar_fallback: PolarFallback::default(),
+            },
+            ephemeris_path: self.ephemeris_path.clone(),
+        })
+    }
+
+    /// Biwheel definition: first subject inside with houses, second subject outside
+    fn biwheel_json(layer_id_a: &str, layer_id_b: &str, aspect_set_id: &str) -> String {
+        serde_json::json!({
+            "name": "Synastry Biwheel",
+            "rings": [
+                {
+                    "slug": "ring_signs",
+                    "type": "signs",
+                    "label": "Zodiac Signs",
+                    "orderIndex": 0,
+                    "radiusInner": 0.88,
+                    "radiusOuter": 1.0,
+                    "dataSource": { "kind": "static_zodiac" }
+                },
+                {
+                    "slug": "ring_planets_outer",
+                    "type": "planets",
+                    "label": format!("{} Planets", layer_id_b),
+                    "orderIndex": 1,
+                    "radiusInner": 0.76,
+                    "radiusOuter": 0.88,
+                    "dataSource": { "kind": "layer_planets", "layerId": layer_id_b }
+                },
+                {
+                    "slug": "ring_houses",
+                    "type": "houses",
+                    "label": "Houses",
+                    "orderIndex": 2,
+                    "radiusInner": 0.68,
+                    "radiusOuter": 0.76,
+                    "dataSource": { "kind": "layer_houses", "layerId": layer_id_a }
+                },
+                {
+                    "slug": "ring_planets_inner",
+                    "type": "planets",
+                    "label": format!("{} Planets", layer_id_a),
+                    "orderIndex": 3,
+                    "radiusInner": 0.5,
+                    "radiusOuter": 0.68,
+                    "dataSource": { "kind": "layer_planets", "layerId": layer_id_a }
+                },
+                {
+                    "slug": "ring_aspects",
+                    "type": "aspects",
+                    "label": "Synastry Aspects",
+                    "orderIndex": 4,
+                    "radiusInner": 0.0,
+                    "radiusOuter": 0.5,
+                    "dataSource": { "kind": "aspect_set", "aspectSetId": aspect_set_id }
+                }
+            ]
+        })
+        .to_string()
     }
 
     /// Calculate Vedic data (nakshatras, vargas, yogas, dashas)
@@ -486,6 +1560,9 @@ impl ChartService {
     ) -> Result<VedicPayload, ApiError> {
         let mut vedic_layers: HashMap<String, VedicLayerData> = HashMap::new();
 
+        // Natal layer is the reference for tarabala/chandrabala
+        let natal_reference = layer_contexts.iter().find(|ctx| ctx.kind == "natal");
+
         for ctx in layer_contexts {
             if let Some(positions) = positions_by_layer.get(&ctx.layer_id) {
                 let mut layer_data = VedicLayerData {
@@ -493,6 +1570,9 @@ impl ChartService {
                     nakshatras: None,
                     vargas: HashMap::new(),
                     yogas: vec![],
+                    daily_strength: None,
+                    bhava_chalit: None,
+                    avasthas: None,
                 };
 
                 // Calculate nakshatras if requested
@@ -523,6 +1603,33 @@ impl ChartService {
                     layer_data.yogas = identify_yogas(positions);
                 }
 
+                // Calculate bhava chalit placements if requested
+                if vedic_config.include_bhava_chalit {
+                    layer_data.bhava_chalit =
+                        compute_bhava_chalit(&ctx.layer_id, positions, &vedic_config.bhava_method);
+                }
+
+                // Calculate avasthas if requested
+                if vedic_config.include_avasthas {
+                    layer_data.avasthas = Some(compute_avasthas(positions));
+                }
+
+                // Calculate tarabala/chandrabala for non-natal layers if requested
+                if vedic_config.include_tarabala && ctx.kind != "natal" {
+                    if let Some(natal_ctx) = natal_reference {
+                        if let Some(natal_positions) = positions_by_layer.get(&natal_ctx.layer_id) {
+                            let strength = compute_daily_strength(
+                                &ctx.layer_id,
+                                &natal_ctx.layer_id,
+                                natal_positions,
+                                positions,
+                            )
+                            .map_err(|e| ApiError::CalculationError(format!("Tarabala error: {}", e)))?;
+                            layer_data.daily_strength = Some(strength);
+                        }
+                    }
+                }
+
                 vedic_layers.insert(ctx.layer_id.clone(), layer_data);
             }
         }
@@ -539,6 +1646,18 @@ impl ChartService {
                     .find(|ctx| ctx.kind == "natal")
                     .ok_or_else(|| ApiError::ValidationError("Natal layer required for dasha calculation".to_string()))?;
 
+                // Chara dasha needs Rahu and Ketu even when the chart leaves the nodes out
+                let node_type = vedic_config.dasha_node_type.unwrap_or(natal_context.settings.node_type);
+                let recalculated;
+                let natal_positions = if node_type == natal_context.settings.node_type
+                    && natal_positions.planets.contains_key("north_node")
+                {
+                    natal_positions
+                } else {
+                    recalculated = self.with_node_type(natal_context, natal_positions, node_type)?;
+                    &recalculated
+                };
+
                 let depth = match vedic_config.dashas_depth.as_str() {
                     "mahadasha" => DashaLevel::Mahadasha,
                     "antardasha" => DashaLevel::Antardasha,
@@ -550,22 +1669,16 @@ impl ChartService {
                 let dasha_system = vedic_config.dasha_systems.first()
                     .ok_or_else(|| ApiError::ValidationError("No dasha system specified".to_string()))?;
 
-                let periods = match dasha_system.as_str() {
-                    "vimshottari" => compute_vimshottari_dasha(natal_context.datetime, natal_positions, depth)
-                        .map_err(|e| ApiError::CalculationError(format!("Vimshottari dasha error: {}", e)))?,
-                    "yogini" => compute_yogini_dasha(natal_context.datetime, natal_positions, depth)
-                        .map_err(|e| ApiError::CalculationError(format!("Yogini dasha error: {}", e)))?,
-                    "ashtottari" => compute_ashtottari_dasha(natal_context.datetime, natal_positions, depth)
-                        .map_err(|e| ApiError::CalculationError(format!("Ashtottari dasha error: {}", e)))?,
-                    "kalachakra" => compute_kalachakra_dasha(natal_context.datetime, natal_positions, depth)
-                        .map_err(|e| ApiError::CalculationError(format!("Kalachakra dasha error: {}", e)))?,
-                    _ => return Err(ApiError::ValidationError(format!("Unknown dasha system: {}", dasha_system))),
-                };
+                let periods = compute_dasha(dasha_system, natal_context.datetime, natal_positions, depth)?;
+                let from = vedic_config.dashas_from.as_deref().map(|from| parse_datetime(from, None)).transpose()?;
+                let to = vedic_config.dashas_to.as_deref().map(|to| parse_datetime(to, None)).transpose()?;
+                let periods = filter_dasha_periods(periods, from, to);
 
                 Some(VimshottariResponse {
                     system: dasha_system.clone(),
                     depth,
                     birth_date_time: natal_context.datetime,
+                    node_type: Some(node_type),
                     periods,
                 })
             } else {
@@ -575,24 +1688,70 @@ impl ChartService {
             None
         };
 
+        // Calculate ashtakavarga for the natal layer if requested
+        let ashtakavarga = if vedic_config.include_ashtakavarga {
+            let natal_context = natal_reference.ok_or_else(|| {
+                ApiError::ValidationError("Natal layer required for ashtakavarga calculation".to_string())
+            })?;
+            positions_by_layer
+                .get(&natal_context.layer_id)
+                .map(|positions| compute_ashtakavarga(&natal_context.layer_id, positions))
+                .transpose()
+                .map_err(|e| ApiError::CalculationError(format!("Ashtakavarga error: {}", e)))?
+        } else {
+            None
+        };
+
         Ok(VedicPayload {
             layers: vedic_layers,
             dashas,
+            ashtakavarga,
         })
     }
 
+    /// `positions` with the lunar nodes recalculated as `node_type`, for dashas that use a
+    /// different node than the chart or a chart that left the nodes out
+    fn with_node_type(
+        &self,
+        ctx: &LayerContext,
+        positions: &aphrodite_core::ephemeris::LayerPositions,
+        node_type: NodeType,
+    ) -> Result<aphrodite_core::ephemeris::LayerPositions, ApiError> {
+        let settings = EphemerisSettings {
+            node_type,
+            include_objects: vec!["north_node".to_string(), "south_node".to_string()],
+            ..ctx.settings.clone()
+        };
+        let mut adapter = SwissEphemerisAdapter::new(self.ephemeris_path.clone())?;
+        let nodes = adapter.calc_positions(ctx.datetime, None, &settings)?;
+        let mut positions = positions.clone();
+        positions.planets.extend(nodes.planets);
+        Ok(positions)
+    }
+
     /// Calculate Western data (dignities and decans)
     fn calculate_western_data(
         &self,
         positions_by_layer: &HashMap<String, aphrodite_core::ephemeris::LayerPositions>,
+        decan_system: &str,
+        term_system: &str,
     ) -> Result<HashMap<String, WesternLayerData>, ApiError> {
         let mut western_layers: HashMap<String, WesternLayerData> = HashMap::new();
+        let decan_system = DecanSystem::from_name(decan_system).ok_or_else(|| {
+            ApiError::ValidationError(format!("Invalid decanSystem: {}", decan_system))
+        })?;
+        let term_system = TermSystem::from_name(term_system).ok_or_else(|| {
+            ApiError::ValidationError(format!("Invalid termSystem: {}", term_system))
+        })?;
         let dignities_service = DignitiesService;
         let default_exact_exaltations = DignitiesService::get_default_exact_exaltations();
 
         for (layer_id, positions) in positions_by_layer {
             let mut dignities: HashMap<String, Vec<aphrodite_core::western::DignityResult>> = HashMap::new();
             let mut decans: HashMap<String, aphrodite_core::western::DecanInfo> = HashMap::new();
+            let mut essential_dignities: HashMap<String, EssentialDignityScore> = HashMap::new();
+            let analysis = compute_analysis(positions);
+            let is_day = analysis.sect.as_deref().map(|sect| sect == "day");
 
             // Calculate dignities for all planets
             for (planet_id, planet_pos) in &positions.planets {
@@ -605,8 +1764,17 @@ impl ChartService {
                     dignities.insert(planet_id.clone(), planet_dignities);
                 }
 
+                if let Some(score) = dignities_service.get_essential_dignities(
+                    planet_id,
+                    planet_pos.lon,
+                    is_day,
+                    term_system,
+                ) {
+                    essential_dignities.insert(planet_id.clone(), score);
+                }
+
                 // Calculate decan info
-                let decan_info = get_decan_info_from_longitude(planet_pos.lon);
+                let decan_info = get_decan_info_from_longitude_with_system(planet_pos.lon, decan_system);
                 decans.insert(planet_id.clone(), decan_info);
             }
 
@@ -614,6 +1782,11 @@ impl ChartService {
                 layer_id: layer_id.clone(),
                 dignities,
                 decans,
+                essential_dignities,
+                balance: compute_balance(positions),
+                analysis,
+                conditions: compute_conditions(positions),
+                interceptions: positions.houses.as_ref().and_then(compute_interceptions),
             });
         }
 
@@ -622,109 +1795,131 @@ impl ChartService {
 
     /// Resolve layer contexts from request
     fn resolve_layer_contexts(
-        &self,
         subjects: &[Subject],
         layer_config: &HashMap<String, LayerConfig>,
         settings: &ChartSettings,
     ) -> Result<Vec<LayerContext>, ApiError> {
-        let mut contexts = Vec::new();
-
-        for (layer_id, config) in layer_config {
-            let dt_utc = match config.kind.as_str() {
-                "natal" => {
-                    let subject_id = config
-                        .subject_id
-                        .as_ref()
-                        .ok_or_else(|| {
-                            ApiError::ValidationError(format!(
-                                "Layer '{}': natal layer must specify a 'subjectId'",
-                                layer_id
-                            ))
-                        })?;
-
-                    let subject = subjects
-                        .iter()
-                        .find(|s| s.id == *subject_id)
-                        .ok_or_else(|| {
-                            ApiError::ValidationError(format!(
-                                "Layer '{}': subjectId '{}' not found",
-                                layer_id, subject_id
-                            ))
-                        })?;
-
-                    let birth_dt = subject
-                        .birth_date_time
-                        .as_ref()
-                        .ok_or_else(|| {
-                            ApiError::ValidationError(format!(
-                                "Layer '{}': subject '{}' missing 'birthDateTime'",
-                                layer_id, subject_id
-                            ))
-                        })?;
-
-                    parse_datetime(birth_dt, subject.birth_timezone.as_deref())?
-                }
-                "transit" => {
-                    config
-                        .explicit_date_time
-                        .as_ref()
-                        .ok_or_else(|| {
-                            ApiError::ValidationError(format!(
-                                "Layer '{}': transit layer must specify 'explicitDateTime'",
-                                layer_id
-                            ))
-                        })
-                        .and_then(|dt| parse_datetime(dt, None))?
-                }
-                _ => {
-                    return Err(ApiError::ValidationError(format!(
-                        "Layer '{}': unsupported layer kind '{}'",
+        layer_config
+            .iter()
+            .map(|(layer_id, config)| Self::resolve_layer_context(layer_id, config, subjects, settings))
+            .collect()
+    }
+
+    /// Resolve one layer's datetime, location and ephemeris settings
+    pub(crate) fn resolve_layer_context(
+        layer_id: &str,
+        config: &LayerConfig,
+        subjects: &[Subject],
+        settings: &ChartSettings,
+    ) -> Result<LayerContext, ApiError> {
+        let birth = || {
+            let subject_id = config
+                .subject_id
+                .as_ref()
+                .ok_or_else(|| {
+                    ApiError::ValidationError(format!(
+                        "Layer '{}': {} layer must specify a 'subjectId'",
                         layer_id, config.kind
-                    )));
-                }
-            };
+                    ))
+                })?;
 
-            let location = config
-                .location
+            let subject = subjects
+                .iter()
+                .find(|s| s.id == *subject_id)
+                .ok_or_else(|| {
+                    ApiError::ValidationError(format!(
+                        "Layer '{}': subjectId '{}' not found",
+                        layer_id, subject_id
+                    ))
+                })?;
+
+            let birth_dt = subject
+                .birth_date_time
                 .as_ref()
-                .or_else(|| {
-                    // Try to get from subject
-                    if let Some(subject_id) = &config.subject_id {
-                        subjects
-                            .iter()
-                            .find(|s| s.id == *subject_id)
-                            .and_then(|s| s.location.as_ref())
-                    } else {
-                        None
-                    }
+                .ok_or_else(|| {
+                    ApiError::ValidationError(format!(
+                        "Layer '{}': subject '{}' missing 'birthDateTime'",
+                        layer_id, subject_id
+                    ))
+                })?;
+
+            parse_datetime(birth_dt, subject.birth_timezone.as_deref())
+        };
+        let explicit_date_time = || {
+            config
+                .explicit_date_time
+                .as_ref()
+                .ok_or_else(|| {
+                    ApiError::ValidationError(format!(
+                        "Layer '{}': {} layer must specify 'explicitDateTime'",
+                        layer_id, config.kind
+                    ))
                 })
-                .map(|loc| GeoLocation {
-                    lat: loc.lat,
-                    lon: loc.lon,
-                });
-
-            let ephemeris_settings = EphemerisSettings {
-                zodiac_type: settings.zodiac_type.clone(),
-                ayanamsa: settings.ayanamsa.clone(),
-                house_system: settings.house_system.clone(),
-                include_objects: settings.include_objects.clone(),
-            };
+                .and_then(|dt| parse_datetime(dt, None))
+        };
 
-            contexts.push(LayerContext {
-                layer_id: layer_id.clone(),
-                kind: config.kind.clone(),
-                datetime: dt_utc,
-                location,
-                settings: ephemeris_settings,
+        let (dt_utc, progression) = match config.kind.as_str() {
+            "natal" => (birth()?, None),
+            "transit" => (explicit_date_time()?, None),
+            "progressed" => {
+                let progression = Progression {
+                    birth: birth()?,
+                    options: config.progression.unwrap_or_default(),
+                };
+                (explicit_date_time()?, Some(progression))
+            }
+            _ => {
+                return Err(ApiError::ValidationError(format!(
+                    "Layer '{}': unsupported layer kind '{}'",
+                    layer_id, config.kind
+                )));
+            }
+        };
+
+        let location = config
+            .location
+            .as_ref()
+            .or_else(|| {
+                // Try to get from subject
+                if let Some(subject_id) = &config.subject_id {
+                    subjects
+                        .iter()
+                        .find(|s| s.id == *subject_id)
+                        .and_then(|s| s.location.as_ref())
+                } else {
+                    None
+                }
+            })
+            .map(|loc| GeoLocation {
+                lat: loc.lat,
+                lon: loc.lon,
             });
-        }
 
-        Ok(contexts)
+        let ephemeris_settings = EphemerisSettings {
+            zodiac_type: settings.zodiac_type.clone(),
+            ayanamsa: settings.ayanamsa.clone(),
+            custom_ayanamsa: settings.custom_ayanamsa,
+            house_system: settings.house_system.clone(),
+            include_objects: settings.include_objects.clone(),
+            node_type: settings.node_type,
+            lilith_type: settings.lilith_type,
+            moon_position: settings.moon_position,
+            polar_fallback: settings.polar_fallback,
+        };
+
+        Ok(LayerContext {
+            layer_id: layer_id.to_string(),
+            kind: config.kind.clone(),
+            datetime: dt_utc,
+            location,
+            settings: ephemeris_settings,
+            progression,
+        })
     }
 }
 
 /// Parse datetime string to UTC
-fn parse_datetime(dt_str: &str, _tz_str: Option<&str>) -> Result<DateTime<Utc>, ApiError> {
+pub(crate) fn parse_datetime(dt_str: &str, _tz_str: Option<&str>) -> Result<DateTime<Utc>, ApiError> {
     // Simple parser - in production, use a more robust date parser
     let dt = chrono::DateTime::parse_from_rfc3339(dt_str)
         .or_else(|_| {
@@ -737,3 +1932,198 @@ fn parse_datetime(dt_str: &str, _tz_str: Option<&str>) -> Result<DateTime<Utc>,
     Ok(dt)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn request(layer_config: serde_json::Value, orb_trine: f64) -> RenderRequest {
+        serde_json::from_value(json!({
+            "subjects": [{
+                "id": "person",
+                "label": "Person",
+                "birthDateTime": "1990-01-01T12:00:00Z",
+                "location": { "lat": 40.7128, "lon": -74.0060 }
+            }],
+            "settings": {
+                "zodiacType": "tropical",
+                "houseSystem": "placidus",
+                "includeObjects": ["sun", "moon"],
+                "orbSettings": { "conjunction": 8.0, "opposition": 8.0, "trine": orb_trine, "square": 6.0, "sextile": 4.0 }
+            },
+            "layer_config": layer_config
+        }))
+        .unwrap()
+    }
+
+    fn key(request: &RenderRequest) -> String {
+        ChartService::generate_cache_key(request, &request.settings).unwrap()
+    }
+
+    #[test]
+    fn test_cache_key_is_canonical() {
+        let layers = || {
+            let mut layers = serde_json::Map::new();
+            for id in ["natal", "transit_a", "transit_b", "transit_c"] {
+                let kind = if id == "natal" { "natal" } else { "transit" };
+                layers.insert(id.to_string(), json!({ "kind": kind, "subjectId": "person" }));
+            }
+            serde_json::Value::Object(layers)
+        };
+        // HashMap iteration order differs between instances; the key must not
+        let keys: Vec<String> = (0..8).map(|_| key(&request(layers(), 7.0))).collect();
+        assert!(keys.iter().all(|k| k == &keys[0]));
+        assert!(keys[0].starts_with("ephemeris:v2:"));
+        assert_eq!(keys[0].len(), "ephemeris:v2:".len() + 64);
+    }
+
+    #[test]
+    fn test_cache_key_covers_nested_settings() {
+        let layers = json!({ "natal": { "kind": "natal", "subjectId": "person" } });
+        let base = request(layers.clone(), 7.0);
+        assert_ne!(key(&base), key(&request(layers.clone(), 6.5)));
+
+        let mut vedic = base.clone();
+        vedic.settings.vedic_config = Some(serde_json::from_value(json!({ "include_nakshatras": true })).unwrap());
+        assert_ne!(key(&base), key(&vedic));
+    }
+
+    #[test]
+    fn test_exclude_out_of_sign_setting() {
+        let layers = json!({ "natal": { "kind": "natal", "subjectId": "person" } });
+        let mut settings = request(layers, 7.0).settings;
+        assert!(!ChartService::aspect_settings(&settings).exclude_out_of_sign);
+
+        let override_with = |value: serde_json::Value| HashMap::from([("excludeOutOfSign".to_string(), value)]);
+        ChartService::merge_settings_override(&mut settings, &override_with(json!(true))).unwrap();
+        assert!(ChartService::aspect_settings(&settings).exclude_out_of_sign);
+        assert!(ChartService::merge_settings_override(&mut settings, &override_with(json!("yes"))).is_err());
+    }
+
+    #[test]
+    fn test_calculation_mode_setting() {
+        let layers = json!({ "natal": { "kind": "natal", "subjectId": "person" } });
+        let mut settings = request(layers, 7.0).settings;
+        assert_eq!(settings.calculation_mode, CalculationMode::Lenient);
+
+        let override_with = |value: serde_json::Value| HashMap::from([("calculationMode".to_string(), value)]);
+        ChartService::merge_settings_override(&mut settings, &override_with(json!("strict"))).unwrap();
+        assert_eq!(settings.calculation_mode, CalculationMode::Strict);
+        assert!(ChartService::merge_settings_override(&mut settings, &override_with(json!(true))).is_err());
+    }
+
+    #[test]
+    fn test_house_warnings() {
+        let mut ctx = LayerContext {
+            layer_id: "transit".to_string(),
+            kind: "transit".to_string(),
+            datetime: Utc::now(),
+            location: None,
+            settings: EphemerisSettings {
+                zodiac_type: "tropical".to_string(),
+                ayanamsa: None,
+                custom_ayanamsa: None,
+                house_system: "placidus".to_string(),
+                include_objects: vec![],
+                node_type: NodeType::default(),
+                lilith_type: LilithType::default(),
+                moon_position: MoonPosition::default(),
+                polar_fallback: PolarFallback::default(),
+            },
+            progression: None,
+        };
+        let mut positions = aphrodite_core::ephemeris::LayerPositions { planets: HashMap::new(), houses: None };
+        let warning = ChartService::house_warnings(&ctx, &positions).unwrap();
+        assert_eq!(warning.object_id, "houses");
+
+        ctx.location = Some(GeoLocation { lat: 69.65, lon: 18.96 });
+        assert!(ChartService::house_warnings(&ctx, &positions).is_none());
+        positions.houses = Some(aphrodite_core::ephemeris::HousePositions {
+            system: "porphyry".to_string(),
+            cusps: HashMap::new(),
+            angles: HashMap::new(),
+        });
+        let warning = ChartService::house_warnings(&ctx, &positions).unwrap();
+        assert!(warning.message.contains("porphyry"), "{}", warning.message);
+    }
+
+    #[test]
+    fn test_node_and_lilith_type_settings() {
+        let layers = json!({ "natal": { "kind": "natal", "subjectId": "person" } });
+        let base = request(layers, 7.0);
+        let mut settings = base.settings.clone();
+        assert_eq!(settings.node_type, NodeType::True);
+        assert_eq!(settings.lilith_type, LilithType::Mean);
+
+        let overrides = HashMap::from([
+            ("nodeType".to_string(), json!("mean")),
+            ("lilithType".to_string(), json!("true")),
+        ]);
+        ChartService::merge_settings_override(&mut settings, &overrides).unwrap();
+        assert_eq!(settings.node_type, NodeType::Mean);
+        assert_eq!(settings.lilith_type, LilithType::True);
+
+        let mut mean = base.clone();
+        mean.settings = settings.clone();
+        assert_ne!(key(&base), key(&mean));
+
+        let invalid = HashMap::from([("nodeType".to_string(), json!("osculating"))]);
+        assert!(ChartService::merge_settings_override(&mut settings, &invalid).is_err());
+    }
+
+    #[test]
+    fn test_moon_position_setting() {
+        let layers = json!({ "natal": { "kind": "natal", "subjectId": "person" } });
+        let mut settings = request(layers, 7.0).settings;
+        assert_eq!(settings.moon_position, MoonPosition::Geocentric);
+
+        let override_with = |value: serde_json::Value| HashMap::from([("moonPosition".to_string(), value)]);
+        ChartService::merge_settings_override(&mut settings, &override_with(json!("topocentric"))).unwrap();
+        assert_eq!(settings.moon_position, MoonPosition::Topocentric);
+        assert!(ChartService::merge_settings_override(&mut settings, &override_with(json!("heliocentric"))).is_err());
+    }
+
+    #[test]
+    fn test_polar_fallback_setting() {
+        let layers = json!({ "natal": { "kind": "natal", "subjectId": "person" } });
+        let mut settings = request(layers, 7.0).settings;
+        assert_eq!(settings.polar_fallback, PolarFallback::Porphyry);
+
+        let override_with = |value: serde_json::Value| HashMap::from([("polarFallback".to_string(), value)]);
+        ChartService::merge_settings_override(&mut settings, &override_with(json!("whole_sign"))).unwrap();
+        assert_eq!(settings.polar_fallback, PolarFallback::WholeSign);
+        assert!(ChartService::merge_settings_override(&mut settings, &override_with(json!("equal"))).is_err());
+    }
+
+    #[test]
+    fn test_ascendant_prefers_natal_layer() {
+        use aphrodite_core::ephemeris::{HousePositions, LayerPositions};
+
+        let with_asc = |asc: f64| LayerPositions {
+            planets: HashMap::new(),
+            houses: Some(HousePositions {
+                system: "placidus".to_string(),
+                cusps: HashMap::new(),
+                angles: HashMap::from([("asc".to_string(), asc)]),
+            }),
+        };
+        let mut positions = HashMap::from([
+            ("a_transit".to_string(), with_asc(10.0)),
+            ("natal".to_string(), with_asc(200.0)),
+        ]);
+        let layers = request(
+            json!({
+                "natal": { "kind": "natal", "subjectId": "person" },
+                "a_transit": { "kind": "transit", "explicitDateTime": "2024-03-18T12:00:00Z" }
+            }),
+            7.0,
+        )
+        .layer_config;
+
+        assert_eq!(ChartService::ascendant(&positions, &layers), Some(200.0));
+        positions.get_mut("natal").unwrap().houses = None;
+        assert_eq!(ChartService::ascendant(&positions, &layers), Some(10.0));
+        positions.get_mut("a_transit").unwrap().houses = None;
+        assert_eq!(ChartService::ascendant(&positions, &layers), None);
+    }
+}
@@ -0,0 +1,91 @@
+//! Installed Swiss Ephemeris files, and downloading missing ones with the
+//! `ephemeris-download` feature.
+
+use crate::error::ApiError;
+use crate::schemas::response::{EphemerisCoverage, EphemerisDownloadResponse, EphemerisFilesResponse};
+use aphrodite_core::ephemeris::files::{coverage, installed_files, missing_standard_files};
+use aphrodite_core::ephemeris::{EphemerisFileKind, InstalledFile};
+use std::path::Path;
+
+/// Whether this build can download ephemeris files
+pub const DOWNLOAD_ENABLED: bool = cfg!(feature = "ephemeris-download");
+
+/// Files in `dir`, the years they cover and the standard files missing
+pub fn file_status(dir: &Path) -> Result<EphemerisFilesResponse, ApiError> {
+    let files = read_installed(dir)?;
+    let kind_coverage = |kind| coverage(files.iter().map(|installed| &installed.file), kind);
+    Ok(EphemerisFilesResponse {
+        path: dir.display().to_string(),
+        coverage: EphemerisCoverage {
+            planets: kind_coverage(EphemerisFileKind::Planets),
+            moon: kind_coverage(EphemerisFileKind::Moon),
+            asteroids: kind_coverage(EphemerisFileKind::Asteroids),
+        },
+        missing_standard_files: missing_standard_files(&files).into_iter().map(String::from).collect(),
+        files,
+        download_enabled: DOWNLOAD_ENABLED,
+    })
+}
+
+fn read_installed(dir: &Path) -> Result<Vec<InstalledFile>, ApiError> {
+    installed_files(dir)
+        .map_err(|e| ApiError::InternalError(format!("Failed to list ephemeris files in {}: {}", dir.display(), e)))
+}
+
+/// Download `names` (already validated) from `base_url` into `dir`, skipping installed files.
+/// Each file is written under a temporary name and renamed once complete.
+#[cfg(feature = "ephemeris-download")]
+pub async fn download_files(
+    dir: &Path,
+    base_url: &str,
+    names: Vec<String>,
+) -> Result<EphemerisDownloadResponse, ApiError> {
+    use aphrodite_core::ephemeris::EphemerisFile;
+
+    let listing_dir = dir.to_path_buf();
+    let installed = tokio::task::spawn_blocking(move || read_installed(&listing_dir))
+        .await
+        .map_err(|e| ApiError::InternalError(format!("File listing task failed: {}", e)))??;
+    let (already_present, wanted): (Vec<String>, Vec<String>) = names
+        .into_iter()
+        .partition(|name| installed.iter().any(|installed| installed.file.name == *name));
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(120))
+        .build()
+        .map_err(|e| ApiError::InternalError(format!("Failed to create HTTP client: {}", e)))?;
+    let mut downloaded = Vec::new();
+    for name in wanted {
+        let url = format!("{}/{}", base_url.trim_end_matches('/'), name);
+        let bytes = async { client.get(&url).send().await?.error_for_status()?.bytes().await }
+            .await
+            .map_err(|e| ApiError::InternalError(format!("Failed to download {}: {}", url, e)))?;
+
+        let target = dir.join(&name);
+        let partial = dir.join(format!("{}.part", name));
+        let write = async {
+            tokio::fs::write(&partial, &bytes).await?;
+            tokio::fs::rename(&partial, &target).await
+        };
+        write
+            .await
+            .map_err(|e| ApiError::InternalError(format!("Failed to write {}: {}", target.display(), e)))?;
+        tracing::info!(file = %name, bytes = bytes.len(), "Downloaded ephemeris file");
+
+        if let Some(file) = EphemerisFile::parse(&name) {
+            downloaded.push(InstalledFile { file, size: bytes.len() as u64 });
+        }
+    }
+    Ok(EphemerisDownloadResponse { downloaded, already_present })
+}
+
+#[cfg(not(feature = "ephemeris-download"))]
+pub async fn download_files(
+    _dir: &Path,
+    _base_url: &str,
+    _names: Vec<String>,
+) -> Result<EphemerisDownloadResponse, ApiError> {
+    Err(ApiError::NotFound(
+        "ephemeris downloads are not available in this build (enable the ephemeris-download feature)".to_string(),
+    ))
+}
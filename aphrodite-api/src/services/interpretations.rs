@@ -0,0 +1,16 @@
+//! Interpretation content packs served by `POST /interpretations`.
+
+use crate::error::ApiError;
+use aphrodite_core::interpretations::InterpretationLibrary;
+use std::path::Path;
+
+/// Load the packs in `dir`; a missing directory leaves the library empty
+pub fn load_library(dir: Option<&str>) -> Result<InterpretationLibrary, ApiError> {
+    let Some(dir) = dir.map(Path::new).filter(|dir| dir.is_dir()) else {
+        tracing::info!("No interpretation pack directory; /interpretations has no packs");
+        return Ok(InterpretationLibrary::new());
+    };
+    let library = InterpretationLibrary::load_dir(dir)?;
+    tracing::info!(path = %dir.display(), packs = library.packs().len(), "Interpretation packs loaded");
+    Ok(library)
+}
@@ -0,0 +1,185 @@
+//! Live "sky now vs natal" charts for WebSocket subscribers.
+
+use aphrodite_core::aspects::{AspectCalculator, AspectPair, AspectSettings};
+use aphrodite_core::ephemeris::{EphemerisSettings, GeoLocation, LayerPositions, SwissEphemerisAdapter};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::error::ApiError;
+use crate::schemas::request::LiveSubscription;
+use crate::schemas::response::LiveUpdate;
+use crate::services::chart::{parse_datetime, ChartService};
+
+/// A subscribed natal chart and the transit aspects seen at the previous update
+pub struct LiveChart {
+    adapter: SwissEphemerisAdapter,
+    natal: LayerPositions,
+    location: Option<GeoLocation>,
+    settings: EphemerisSettings,
+    aspect_settings: AspectSettings,
+    previous: HashMap<String, AspectPair>,
+}
+
+impl LiveChart {
+    /// Calculate the natal positions for a subscription; blocking
+    pub fn new(ephemeris_path: Option<PathBuf>, subscription: &LiveSubscription) -> Result<Self, ApiError> {
+        let subject = &subscription.subject;
+        let birth_dt = subject.birth_date_time.as_deref().ok_or_else(|| {
+            ApiError::ValidationError(format!("Subject '{}' missing 'birthDateTime'", subject.id))
+        })?;
+        let birth_dt = parse_datetime(birth_dt, subject.birth_timezone.as_deref())?;
+        let location = subject.location.as_ref().map(|loc| GeoLocation { lat: loc.lat, lon: loc.lon });
+        let chart_settings = &subscription.settings;
+        let settings = EphemerisSettings {
+            zodiac_type: chart_settings.zodiac_type.clone(),
+            ayanamsa: chart_settings.ayanamsa.clone(),
+            custom_ayanamsa: chart_settings.custom_ayanamsa,
+            house_system: chart_settings.house_system.clone(),
+            include_objects: chart_settings.include_objects.clone(),
+            node_type: chart_settings.node_type,
+            lilith_type: chart_settings.lilith_type,
+            moon_position: chart_settings.moon_position,
+            polar_fallback: chart_settings.polar_fallback,
+        };
+
+        let mut adapter = SwissEphemerisAdapter::new(ephemeris_path)
+            .map_err(|e| ApiError::InternalError(format!("Failed to create adapter: {}", e)))?;
+        let natal = adapter.calc_positions(birth_dt, location.clone(), &settings)?;
+
+        Ok(Self {
+            adapter,
+            natal,
+            location,
+            settings,
+            aspect_settings: ChartService::aspect_settings(chart_settings),
+            previous: HashMap::new(),
+        })
+    }
+
+    /// Transit positions and transit-to-natal aspects at `now`; blocking
+    pub fn update(&mut self, now: DateTime<Utc>) -> Result<LiveUpdate, ApiError> {
+        let transits = self.adapter.calc_positions(now, self.location.clone(), &self.settings)?;
+        let aspects = AspectCalculator::new()
+            .compute_inter_layer_aspects("transit", "natal", &transits, &self.natal, &self.aspect_settings)
+            .pairs;
+
+        let newly_exact = newly_exact(&self.previous, &aspects);
+        self.previous = aspects.iter().map(|pair| (aspect_key(pair), pair.clone())).collect();
+
+        Ok(LiveUpdate {
+            date_time: now,
+            transits: (&transits).into(),
+            aspects,
+            newly_exact,
+        })
+    }
+}
+
+/// Identity of an aspect between two objects, independent of its current orb
+fn aspect_key(pair: &AspectPair) -> String {
+    format!("{}:{}:{}", pair.from.object_id, pair.to.object_id, pair.aspect.aspect_type)
+}
+
+/// Aspects that perfected since the previous update: they are exact now but were not,
+/// or they were applying and are now separating (exactness passed between updates).
+fn newly_exact(previous: &HashMap<String, AspectPair>, current: &[AspectPair]) -> Vec<AspectPair> {
+    current
+        .iter()
+        .filter(|pair| match previous.get(&aspect_key(pair)) {
+            Some(before) => {
+                (pair.aspect.is_exact && !before.aspect.is_exact)
+                    || (before.aspect.is_applying && !pair.aspect.is_applying && !before.aspect.is_exact)
+            }
+            None => pair.aspect.is_exact,
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aphrodite_core::aspects::{AspectCore, AspectObjectRef};
+
+    fn pair(to: &str, orb: f64, is_applying: bool) -> AspectPair {
+        let object = |layer_id: &str, object_id: &str| AspectObjectRef {
+            layer_id: layer_id.to_string(),
+            object_type: "planet".to_string(),
+            object_id: object_id.to_string(),
+        };
+        AspectPair {
+            from: object("transit", "moon"),
+            to: object("natal", to),
+            aspect: AspectCore {
+                aspect_type: "trine".to_string(),
+                exact_angle: 120.0,
+                orb,
+                precision: orb,
+                is_applying,
+                is_exact: orb < 0.1,
+                is_retrograde: false,
+                out_of_sign: false,
+            },
+        }
+    }
+
+    fn ids(pairs: &[AspectPair]) -> Vec<&str> {
+        pairs.iter().map(|p| p.to.object_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_newly_exact_aspects() {
+        let first = vec![pair("sun", 0.05, true), pair("venus", 2.0, true), pair("mars", 1.0, true)];
+        let previous: HashMap<_, _> = HashMap::new();
+        assert_eq!(ids(&newly_exact(&previous, &first)), vec!["sun"]);
+
+        let previous: HashMap<_, _> = first.iter().map(|p| (aspect_key(p), p.clone())).collect();
+        let second = vec![
+            // Still exact: already reported
+            pair("sun", 0.02, false),
+            // Closing in but not exact yet
+            pair("venus", 0.5, true),
+            // Passed exactness between updates
+            pair("mars", 1.0, false),
+            // Entered orb already exact
+            pair("jupiter", 0.05, true),
+        ];
+        assert_eq!(ids(&newly_exact(&previous, &second)), vec!["mars", "jupiter"]);
+    }
+
+    #[test]
+    fn test_sidereal_updates_match_across_threads() {
+        let ephe_dir = std::env::temp_dir().join("aphrodite-live-empty-ephe");
+        std::fs::create_dir_all(&ephe_dir).unwrap();
+        let subscription: LiveSubscription = serde_json::from_value(serde_json::json!({
+            "subject": {
+                "id": "test_person",
+                "label": "Test Person",
+                "birthDateTime": "1990-01-01T12:00:00Z",
+                "location": { "lat": 40.7128, "lon": -74.0060 }
+            },
+            "settings": {
+                "zodiacType": "sidereal",
+                "ayanamsa": "lahiri",
+                "includeObjects": ["sun", "moon", "mars"]
+            }
+        }))
+        .unwrap();
+        let now = DateTime::parse_from_rfc3339("2024-06-01T00:00:00Z").unwrap().with_timezone(&Utc);
+
+        let mut chart = LiveChart::new(Some(ephe_dir), &subscription).unwrap();
+        let expected = serde_json::to_value(chart.update(now).unwrap().transits).unwrap();
+        // Each tick runs on whichever blocking thread is free, as in the WebSocket handler
+        for _ in 0..3 {
+            let (returned, transits) = std::thread::spawn(move || {
+                let update = chart.update(now).unwrap();
+                (chart, serde_json::to_value(update.transits).unwrap())
+            })
+            .join()
+            .unwrap();
+            chart = returned;
+            assert_eq!(transits, expected);
+        }
+    }
+}
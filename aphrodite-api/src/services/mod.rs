@@ -1,6 +1,14 @@
+pub mod cache;
 pub mod chart;
+pub mod ephemeris_files;
+pub mod interpretations;
+pub mod live;
 pub mod pool;
+pub mod wheel;
 
+pub use cache::{CacheStats, ResponseCache};
 pub use chart::ChartService;
+pub use live::LiveChart;
 pub use pool::ChartServicePool;
+pub use wheel::DefaultWheel;
 
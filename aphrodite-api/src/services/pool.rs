@@ -1,5 +1,6 @@
 use crate::error::ApiError;
-use crate::services::ChartService;
+use crate::services::cache::ResponseCache;
+use crate::services::{ChartService, DefaultWheel};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -8,22 +9,28 @@ use tokio::sync::Mutex;
 /// Pool of ChartService instances for concurrent request handling
 pub struct ChartServicePool {
     services: Vec<Arc<Mutex<ChartService>>>,
+    cache: Arc<ResponseCache>,
+    default_wheel: Arc<DefaultWheel>,
     counter: AtomicUsize,
 }
 
 impl ChartServicePool {
-    /// Create a new service pool with the specified number of instances
-    pub fn new(pool_size: usize, ephemeris_path: Option<PathBuf>, cache_size: usize, default_wheel_json_path: Option<String>) -> Result<Self, ApiError> {
+    /// Create a new service pool with the specified number of instances sharing `cache`
+    pub fn new(pool_size: usize, ephemeris_path: Option<PathBuf>, cache: ResponseCache, default_wheel_json_path: Option<String>) -> Result<Self, ApiError> {
         let mut services = Vec::with_capacity(pool_size);
-        
+        let cache = Arc::new(cache);
+        let default_wheel = Arc::new(DefaultWheel::load(default_wheel_json_path));
+
         for _ in 0..pool_size {
-            let service = ChartService::new(ephemeris_path.clone(), cache_size, default_wheel_json_path.clone())
+            let service = ChartService::with_cache(ephemeris_path.clone(), cache.clone(), default_wheel.clone())
                 .map_err(|e| ApiError::InternalError(format!("Failed to create service in pool: {}", e)))?;
             services.push(Arc::new(Mutex::new(service)));
         }
 
         Ok(Self {
             services,
+            cache,
+            default_wheel,
             counter: AtomicUsize::new(0),
         })
     }
@@ -33,5 +40,20 @@ impl ChartServicePool {
         let index = self.counter.fetch_add(1, Ordering::Relaxed) % self.services.len();
         self.services[index].clone()
     }
+
+    /// Number of services in the pool
+    pub fn size(&self) -> usize {
+        self.services.len()
+    }
+
+    /// Response cache shared by all services in the pool
+    pub fn cache(&self) -> &Arc<ResponseCache> {
+        &self.cache
+    }
+
+    /// Default wheel shared by all services in the pool
+    pub fn default_wheel(&self) -> &Arc<DefaultWheel> {
+        &self.default_wheel
+    }
 }
 
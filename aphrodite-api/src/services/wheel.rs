@@ -0,0 +1,71 @@
+//! Default wheel definition shared by the service pool, reloadable without a restart.
+
+use crate::error::ApiError;
+use crate::schemas::response::WheelReloadResponse;
+use aphrodite_core::layout::{load_wheel_definition_from_json, DEFAULT_WHEEL_JSON};
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+
+/// Wheel JSON used when a render request does not bring its own
+pub struct DefaultWheel {
+    path: Option<PathBuf>,
+    json: RwLock<Arc<str>>,
+}
+
+impl DefaultWheel {
+    /// Read the wheel at `path`, or use the embedded default when it is unset or unreadable
+    pub fn load(path: Option<String>) -> Self {
+        let json = path
+            .as_deref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .unwrap_or_else(|| DEFAULT_WHEEL_JSON.to_string());
+        Self {
+            path: path.map(PathBuf::from),
+            json: RwLock::new(Arc::from(json)),
+        }
+    }
+
+    /// The current wheel JSON
+    pub fn json(&self) -> Arc<str> {
+        self.json.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Re-read the wheel file and swap it in once it validates. On any error the wheel in
+    /// use is kept.
+    pub fn reload(&self) -> Result<WheelReloadResponse, ApiError> {
+        let path = self.path.as_ref().ok_or_else(|| {
+            ApiError::ValidationError("No default wheel file is configured (DEFAULT_WHEEL_JSON_PATH)".to_string())
+        })?;
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| ApiError::InternalError(format!("Failed to read {}: {}", path.display(), e)))?;
+        let definition = load_wheel_definition_from_json(&json)?;
+
+        *self.json.write().unwrap_or_else(|e| e.into_inner()) = Arc::from(json);
+        tracing::info!(path = %path.display(), wheel = %definition.wheel.name, "Default wheel reloaded");
+        Ok(WheelReloadResponse {
+            path: path.display().to_string(),
+            name: definition.wheel.name,
+            rings: definition.wheel.rings.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reload_keeps_current_wheel_on_invalid_file() {
+        let path = std::env::temp_dir().join(format!("aphrodite-wheel-{}.json", std::process::id()));
+        std::fs::write(&path, DEFAULT_WHEEL_JSON).unwrap();
+        let wheel = DefaultWheel::load(Some(path.display().to_string()));
+        assert!(wheel.reload().is_ok());
+
+        std::fs::write(&path, r#"{"name": "broken"}"#).unwrap();
+        assert!(matches!(wheel.reload(), Err(ApiError::Core(aphrodite_core::Error::WheelDefinition(_)))));
+        assert_eq!(&*wheel.json(), DEFAULT_WHEEL_JSON);
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(DefaultWheel::load(None).reload(), Err(ApiError::ValidationError(_))));
+    }
+}
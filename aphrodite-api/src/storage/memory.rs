@@ -0,0 +1,126 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+
+use super::{Collection, Storage, StorageError, StoredRecord};
+
+/// In-process storage, used by default and in tests
+#[derive(Default)]
+pub struct MemoryStorage {
+    records: RwLock<HashMap<(Collection, String), StoredRecord>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryStorage {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn migrate(&self) -> Result<(), StorageError> {
+        Ok(())
+    }
+
+    async fn put(
+        &self,
+        collection: Collection,
+        id: &str,
+        data: serde_json::Value,
+    ) -> Result<StoredRecord, StorageError> {
+        let now = Utc::now();
+        let mut records = self.records.write().await;
+        let created_at = records
+            .get(&(collection, id.to_string()))
+            .map(|existing| existing.created_at)
+            .unwrap_or(now);
+
+        let record = StoredRecord {
+            collection,
+            id: id.to_string(),
+            data,
+            created_at,
+            updated_at: now,
+        };
+        records.insert((collection, id.to_string()), record.clone());
+        Ok(record)
+    }
+
+    async fn get(&self, collection: Collection, id: &str) -> Result<Option<StoredRecord>, StorageError> {
+        let records = self.records.read().await;
+        Ok(records.get(&(collection, id.to_string())).cloned())
+    }
+
+    async fn delete(&self, collection: Collection, id: &str) -> Result<bool, StorageError> {
+        let mut records = self.records.write().await;
+        Ok(records.remove(&(collection, id.to_string())).is_some())
+    }
+
+    async fn list(
+        &self,
+        collection: Collection,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<StoredRecord>, StorageError> {
+        let records = self.records.read().await;
+        let mut matching: Vec<StoredRecord> = records
+            .values()
+            .filter(|r| r.collection == collection)
+            .cloned()
+            .collect();
+        matching.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then_with(|| a.id.cmp(&b.id)));
+        Ok(matching.into_iter().skip(offset).take(limit).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_put_get_delete() {
+        let storage = MemoryStorage::new();
+        let record = storage
+            .put(Collection::Subjects, "s1", json!({"name": "Test"}))
+            .await
+            .unwrap();
+        assert_eq!(record.id, "s1");
+
+        let fetched = storage.get(Collection::Subjects, "s1").await.unwrap().unwrap();
+        assert_eq!(fetched.data["name"], "Test");
+
+        // Same id in another collection is independent
+        assert!(storage.get(Collection::Wheels, "s1").await.unwrap().is_none());
+
+        assert!(storage.delete(Collection::Subjects, "s1").await.unwrap());
+        assert!(!storage.delete(Collection::Subjects, "s1").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_put_preserves_created_at() {
+        let storage = MemoryStorage::new();
+        let first = storage.put(Collection::Presets, "p", json!(1)).await.unwrap();
+        let second = storage.put(Collection::Presets, "p", json!(2)).await.unwrap();
+        assert_eq!(first.created_at, second.created_at);
+        assert_eq!(second.data, json!(2));
+    }
+
+    #[tokio::test]
+    async fn test_list_pagination() {
+        let storage = MemoryStorage::new();
+        for i in 0..5 {
+            storage.put(Collection::Jobs, &format!("job{}", i), json!(i)).await.unwrap();
+        }
+        storage.put(Collection::Audit, "entry", json!({})).await.unwrap();
+
+        assert_eq!(storage.list(Collection::Jobs, 10, 0).await.unwrap().len(), 5);
+        assert_eq!(storage.list(Collection::Jobs, 2, 4).await.unwrap().len(), 1);
+        assert_eq!(storage.list(Collection::Audit, 10, 0).await.unwrap().len(), 1);
+    }
+}
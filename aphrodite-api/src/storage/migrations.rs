@@ -0,0 +1,73 @@
+//! Schema migrations shared by the SQL storage backends.
+//!
+//! Migrations are applied in version order and recorded in `schema_migrations`.
+//! The SQL is kept portable between SQLite and Postgres.
+
+/// A single schema migration
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub sql: &'static str,
+}
+
+/// Table used to record applied migrations
+pub const MIGRATIONS_TABLE_SQL: &str = "\
+CREATE TABLE IF NOT EXISTS schema_migrations (
+    version BIGINT PRIMARY KEY,
+    name TEXT NOT NULL,
+    applied_at TEXT NOT NULL
+)";
+
+/// All migrations, in order
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "create_records",
+    sql: "\
+CREATE TABLE IF NOT EXISTS records (
+    collection TEXT NOT NULL,
+    id TEXT NOT NULL,
+    data TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    PRIMARY KEY (collection, id)
+);
+CREATE INDEX IF NOT EXISTS idx_records_collection_updated ON records (collection, updated_at)",
+}];
+
+impl Migration {
+    /// Individual SQL statements in this migration
+    pub fn statements(&self) -> impl Iterator<Item = &'static str> {
+        self.sql.split(';').map(str::trim).filter(|stmt| !stmt.is_empty())
+    }
+}
+
+/// Migrations newer than the given applied version
+pub fn pending(applied_version: i64) -> impl Iterator<Item = &'static Migration> {
+    MIGRATIONS.iter().filter(move |m| m.version > applied_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_versions_are_increasing() {
+        for pair in MIGRATIONS.windows(2) {
+            assert!(pair[0].version < pair[1].version);
+        }
+    }
+
+    #[test]
+    fn test_pending() {
+        assert_eq!(pending(0).count(), MIGRATIONS.len());
+        let latest = MIGRATIONS.last().unwrap().version;
+        assert_eq!(pending(latest).count(), 0);
+    }
+
+    #[test]
+    fn test_statements_split() {
+        let first = &MIGRATIONS[0];
+        assert_eq!(first.statements().count(), 2);
+        assert!(first.statements().all(|stmt| !stmt.ends_with(';')));
+    }
+}
@@ -0,0 +1,160 @@
+//! Pluggable persistence for API subsystems.
+//!
+//! Records are JSON documents keyed by collection and id, with one collection per
+//! planned subsystem (subjects, wheels, presets, jobs, audit entries). The backend
+//! (memory, SQLite or Postgres) is chosen from configuration so subsystems never
+//! depend on a concrete database. Nothing reads or writes records yet; each
+//! subsystem moves onto this trait when it is built.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use thiserror::Error;
+
+use crate::config::Config;
+
+pub mod memory;
+pub mod migrations;
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+
+pub use memory::MemoryStorage;
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresStorage;
+#[cfg(feature = "sqlite")]
+pub use sqlite::SqliteStorage;
+
+/// Collections shared by the persistence-backed subsystems
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Collection {
+    Subjects,
+    Wheels,
+    Presets,
+    Jobs,
+    Audit,
+}
+
+impl Collection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Collection::Subjects => "subjects",
+            Collection::Wheels => "wheels",
+            Collection::Presets => "presets",
+            Collection::Jobs => "jobs",
+            Collection::Audit => "audit",
+        }
+    }
+}
+
+/// A stored JSON document
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredRecord {
+    pub collection: Collection,
+    pub id: String,
+    pub data: serde_json::Value,
+    #[serde(rename = "createdAt")]
+    pub created_at: DateTime<Utc>,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Storage errors
+#[derive(Error, Debug)]
+pub enum StorageError {
+    #[error("Record not found: {collection}/{id}")]
+    NotFound { collection: String, id: String },
+    #[error("Unsupported storage backend: {0}")]
+    UnsupportedBackend(String),
+    #[error("Storage configuration error: {0}")]
+    Configuration(String),
+    #[error("Storage backend error: {0}")]
+    Backend(String),
+    #[error("Stored data is invalid: {0}")]
+    InvalidData(String),
+}
+
+/// Storage backend shared by all persistence-backed subsystems
+#[async_trait]
+pub trait Storage: Send + Sync {
+    /// Backend name ("memory", "sqlite", "postgres")
+    fn backend(&self) -> &'static str;
+
+    /// Apply pending schema migrations
+    async fn migrate(&self) -> Result<(), StorageError>;
+
+    /// Insert or replace a record
+    async fn put(
+        &self,
+        collection: Collection,
+        id: &str,
+        data: serde_json::Value,
+    ) -> Result<StoredRecord, StorageError>;
+
+    /// Fetch a record by id
+    async fn get(&self, collection: Collection, id: &str) -> Result<Option<StoredRecord>, StorageError>;
+
+    /// Delete a record, returning whether it existed
+    async fn delete(&self, collection: Collection, id: &str) -> Result<bool, StorageError>;
+
+    /// List records in a collection, most recently updated first
+    async fn list(
+        &self,
+        collection: Collection,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<StoredRecord>, StorageError>;
+}
+
+/// Create the configured storage backend.
+///
+/// SQL backends connect lazily and apply migrations on first use.
+pub fn from_config(config: &Config) -> Result<Arc<dyn Storage>, StorageError> {
+    match config.storage_backend.as_str() {
+        "memory" => Ok(Arc::new(MemoryStorage::new())),
+        #[cfg(feature = "sqlite")]
+        "sqlite" => {
+            let url = config.storage_url.as_deref().unwrap_or("sqlite://aphrodite.db?mode=rwc");
+            Ok(Arc::new(SqliteStorage::connect_lazy(url)?))
+        }
+        #[cfg(feature = "postgres")]
+        "postgres" => {
+            let url = config.storage_url.as_deref().ok_or_else(|| {
+                StorageError::Configuration("STORAGE_URL is required for the postgres backend".to_string())
+            })?;
+            Ok(Arc::new(PostgresStorage::connect_lazy(url)?))
+        }
+        other => Err(StorageError::UnsupportedBackend(format!(
+            "{} (available: {})",
+            other,
+            available_backends().join(", ")
+        ))),
+    }
+}
+
+/// Storage backends compiled into this build
+pub fn available_backends() -> Vec<&'static str> {
+    #[allow(unused_mut)]
+    let mut backends = vec!["memory"];
+    #[cfg(feature = "sqlite")]
+    backends.push("sqlite");
+    #[cfg(feature = "postgres")]
+    backends.push("postgres");
+    backends
+}
+
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+pub(crate) fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, StorageError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| StorageError::InvalidData(format!("invalid timestamp '{}': {}", value, e)))
+}
+
+#[cfg(any(feature = "sqlite", feature = "postgres"))]
+pub(crate) fn parse_collection(value: &str) -> Result<Collection, StorageError> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| StorageError::InvalidData(format!("unknown collection '{}'", value)))
+}
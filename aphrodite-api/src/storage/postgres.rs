@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::postgres::{PgPool, PgPoolOptions, PgRow};
+use sqlx::Row;
+use tokio::sync::OnceCell;
+
+use super::migrations::{self, MIGRATIONS_TABLE_SQL};
+use super::{parse_collection, parse_timestamp, Collection, Storage, StorageError, StoredRecord};
+
+/// Postgres-backed storage
+pub struct PostgresStorage {
+    pool: PgPool,
+    migrated: OnceCell<()>,
+}
+
+impl PostgresStorage {
+    /// Create a storage handle without opening a connection yet
+    pub fn connect_lazy(url: &str) -> Result<Self, StorageError> {
+        let pool = PgPoolOptions::new()
+            .connect_lazy(url)
+            .map_err(|e| StorageError::Configuration(format!("Invalid Postgres URL '{}': {}", url, e)))?;
+        Ok(Self {
+            pool,
+            migrated: OnceCell::new(),
+        })
+    }
+
+    async fn ready(&self) -> Result<&PgPool, StorageError> {
+        self.migrated
+            .get_or_try_init(|| run_migrations(&self.pool))
+            .await?;
+        Ok(&self.pool)
+    }
+}
+
+async fn run_migrations(pool: &PgPool) -> Result<(), StorageError> {
+    sqlx::query(MIGRATIONS_TABLE_SQL).execute(pool).await.map_err(backend_error)?;
+
+    let applied: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(version), 0) FROM schema_migrations")
+        .fetch_one(pool)
+        .await
+        .map_err(backend_error)?;
+
+    for migration in migrations::pending(applied) {
+        let mut tx = pool.begin().await.map_err(backend_error)?;
+        for statement in migration.statements() {
+            sqlx::query(statement).execute(&mut *tx).await.map_err(backend_error)?;
+        }
+        sqlx::query("INSERT INTO schema_migrations (version, name, applied_at) VALUES ($1, $2, $3)")
+            .bind(migration.version)
+            .bind(migration.name)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .map_err(backend_error)?;
+        tx.commit().await.map_err(backend_error)?;
+        tracing::info!(version = migration.version, name = migration.name, "Applied Postgres migration");
+    }
+
+    Ok(())
+}
+
+fn backend_error(err: sqlx::Error) -> StorageError {
+    StorageError::Backend(err.to_string())
+}
+
+fn row_to_record(row: &PgRow) -> Result<StoredRecord, StorageError> {
+    let data: String = row.try_get("data").map_err(backend_error)?;
+    let collection: String = row.try_get("collection").map_err(backend_error)?;
+    let created_at: String = row.try_get("created_at").map_err(backend_error)?;
+    let updated_at: String = row.try_get("updated_at").map_err(backend_error)?;
+
+    Ok(StoredRecord {
+        collection: parse_collection(&collection)?,
+        id: row.try_get("id").map_err(backend_error)?,
+        data: serde_json::from_str(&data).map_err(|e| StorageError::InvalidData(e.to_string()))?,
+        created_at: parse_timestamp(&created_at)?,
+        updated_at: parse_timestamp(&updated_at)?,
+    })
+}
+
+#[async_trait]
+impl Storage for PostgresStorage {
+    fn backend(&self) -> &'static str {
+        "postgres"
+    }
+
+    async fn migrate(&self) -> Result<(), StorageError> {
+        self.ready().await.map(|_| ())
+    }
+
+    async fn put(
+        &self,
+        collection: Collection,
+        id: &str,
+        data: serde_json::Value,
+    ) -> Result<StoredRecord, StorageError> {
+        let pool = self.ready().await?;
+        let now = Utc::now().to_rfc3339();
+        let row = sqlx::query(
+            "INSERT INTO records (collection, id, data, created_at, updated_at) VALUES ($1, $2, $3, $4, $5) \
+             ON CONFLICT (collection, id) DO UPDATE SET data = excluded.data, updated_at = excluded.updated_at \
+             RETURNING collection, id, data, created_at, updated_at",
+        )
+        .bind(collection.as_str())
+        .bind(id)
+        .bind(data.to_string())
+        .bind(&now)
+        .bind(&now)
+        .fetch_one(pool)
+        .await
+        .map_err(backend_error)?;
+        row_to_record(&row)
+    }
+
+    async fn get(&self, collection: Collection, id: &str) -> Result<Option<StoredRecord>, StorageError> {
+        let pool = self.ready().await?;
+        let row = sqlx::query(
+            "SELECT collection, id, data, created_at, updated_at FROM records WHERE collection = $1 AND id = $2",
+        )
+        .bind(collection.as_str())
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(backend_error)?;
+        row.as_ref().map(row_to_record).transpose()
+    }
+
+    async fn delete(&self, collection: Collection, id: &str) -> Result<bool, StorageError> {
+        let pool = self.ready().await?;
+        let result = sqlx::query("DELETE FROM records WHERE collection = $1 AND id = $2")
+            .bind(collection.as_str())
+            .bind(id)
+            .execute(pool)
+            .await
+            .map_err(backend_error)?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn list(
+        &self,
+        collection: Collection,
+        limit: usize,
+        offset: usize,
+    ) -> Result<Vec<StoredRecord>, StorageError> {
+        let pool = self.ready().await?;
+        let rows = sqlx::query(
+            "SELECT collection, id, data, created_at, updated_at FROM records WHERE collection = $1 \
+             ORDER BY updated_at DESC, id ASC LIMIT $2 OFFSET $3",
+        )
+        .bind(collection.as_str())
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(pool)
+        .await
+        .map_err(backend_error)?;
+        rows.iter().map(row_to_record).collect()
+    }
+}
@@ -0,0 +1,89 @@
+//! Tracing setup: console logging plus optional OTLP span export.
+//!
+//! Export is enabled by building with the `otel` feature and setting
+//! `OTEL_EXPORTER_OTLP_ENDPOINT`; spans are sent over OTLP/HTTP to `<endpoint>/v1/traces`.
+
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+use crate::config::Config;
+
+/// Flushes exported spans when dropped; keep it alive for the lifetime of the server
+#[derive(Default)]
+pub struct TelemetryGuard {
+    #[cfg(feature = "otel")]
+    provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.provider.take() {
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Failed to flush OTLP spans: {}", e);
+            }
+        }
+    }
+}
+
+/// Install the global tracing subscriber
+pub fn init(config: &Config) -> TelemetryGuard {
+    // RUST_LOG wins; otherwise the configured level, e.g. `log_level` from the config file
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
+        EnvFilter::try_new(&config.log_level).unwrap_or_else(|e| {
+            eprintln!("Invalid log_level '{}' ({}); using info", config.log_level, e);
+            EnvFilter::new("info")
+        })
+    });
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer());
+
+    #[cfg(feature = "otel")]
+    if let Some(endpoint) = &config.otlp_endpoint {
+        match otlp_provider(endpoint, &config.otel_service_name) {
+            Ok(provider) => {
+                use opentelemetry::trace::TracerProvider;
+                let tracer = provider.tracer("aphrodite-api");
+                registry
+                    .with(tracing_opentelemetry::layer().with_tracer(tracer))
+                    .init();
+                tracing::info!(endpoint = %endpoint, "Exporting traces over OTLP");
+                return TelemetryGuard { provider: Some(provider) };
+            }
+            Err(e) => {
+                registry.init();
+                tracing::error!(error = %e, "Failed to create OTLP exporter; traces will not be exported");
+                return TelemetryGuard::default();
+            }
+        }
+    }
+
+    registry.init();
+    if config.otlp_endpoint.is_some() && cfg!(not(feature = "otel")) {
+        tracing::warn!("OTEL_EXPORTER_OTLP_ENDPOINT is set but the server was built without the `otel` feature");
+    }
+    TelemetryGuard::default()
+}
+
+#[cfg(feature = "otel")]
+fn otlp_provider(
+    endpoint: &str,
+    service_name: &str,
+) -> Result<opentelemetry_sdk::trace::SdkTracerProvider, opentelemetry_otlp::ExporterBuildError> {
+    use opentelemetry_otlp::WithExportConfig;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()?;
+    let resource = opentelemetry_sdk::Resource::builder()
+        .with_service_name(service_name.to_string())
+        .build();
+
+    Ok(opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource)
+        .build())
+}
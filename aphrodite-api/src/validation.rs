@@ -1,6 +1,25 @@
-use crate::error::ApiError;
-use crate::schemas::request::{ChartSettings, LayerConfig, RenderRequest, Subject};
+use crate::error::{ApiError, FieldError, FieldErrorCode};
+use crate::projection::FieldSelection;
+use axum::async_trait;
+use axum::extract::{FromRef, FromRequest, Request};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use crate::schemas::request::{
+    AspectFilterSettings, ChartLayout, ChartSettings, ElectionalSearchRequest, EphemerisDownloadRequest, EphemerisTableQuery, InterpretationRequest, LayerConfig, LifeEvent, LiveClientMessage, LiveSubscription,
+    Location, MuhurtaRequest, OrbSettings, PositionsQuery, RectificationRequest, RenderRequest, Subject, SynastryRequest, TransitTimelineRequest, VedicConfig, WheelValidateRequest,
+};
+use aphrodite_core::aspects::{DEFAULT_MINOR_ORBS, DEFAULT_ORBS};
+use aphrodite_core::electional::parse_constraint;
+use aphrodite_core::layout::{CustomPoint, WheelPresets};
+use aphrodite_core::ephemeris::adapter::{is_supported_ayanamsa, supported_ayanamsa_names, CUSTOM_AYANAMSA};
+use aphrodite_core::ephemeris::{EphemerisFile, ProgressionOptions};
+use aphrodite_core::zodiac::SIGN_NAMES;
+use aphrodite_core::vedic::MuhurtaConstraints;
+use aphrodite_core::vedic::nakshatra::NAKSHATRA_ORDER;
+use aphrodite_core::vedic::panchanga::WEEKDAYS;
+use aphrodite_core::vedic::{resolve_varga_id, BHAVA_METHODS};
 use chrono::{DateTime, Utc};
+use serde::de::{DeserializeOwned, Deserializer, Visitor};
 use std::collections::HashMap;
 
 /// Valid house systems
@@ -13,23 +32,14 @@ const VALID_HOUSE_SYSTEMS: &[&str] = &[
     "campanus",
     "alcabitius",
     "morinus",
+    "porphyry",
 ];
 
-/// Valid ayanamsas
-const VALID_AYANAMSAS: &[&str] = &[
-    "lahiri",
-    "chitrapaksha",
-    "fagan_bradley",
-    "de_luce",
-    "raman",
-    "krishnamurti",
-    "yukteshwar",
-    "djwhal_khul",
-    "true_citra",
-    "true_revati",
-    "aryabhata",
-    "aryabhata_mean_sun",
-];
+/// Valid decan ruler systems
+const VALID_DECAN_SYSTEMS: &[&str] = &["triplicity", "chaldean"];
+
+/// Valid term (bounds) systems
+const VALID_TERM_SYSTEMS: &[&str] = &["egyptian", "ptolemaic"];
 
 /// Valid planet/object names
 const VALID_PLANETS: &[&str] = &[
@@ -46,6 +56,7 @@ const VALID_PLANETS: &[&str] = &[
     "chiron",
     "north_node",
     "south_node",
+    "lilith",
 ];
 
 /// Valid layer kinds
@@ -55,236 +66,931 @@ const VALID_LAYER_KINDS: &[&str] = &["natal", "transit", "progressed"];
 const MIN_YEAR: i32 = -1000; // 1000 BCE
 const MAX_YEAR: i32 = 3000;  // 3000 CE
 
+/// Muhurta search limits
+const MAX_MUHURTA_RANGE_DAYS: i64 = 31;
+const MIN_MUHURTA_STEP_MINUTES: u32 = 5;
+const MAX_MUHURTA_STEP_MINUTES: u32 = 240;
+
+/// Electional search limits
+const MAX_ELECTIONAL_RANGE_DAYS: i64 = 31;
+const MIN_ELECTIONAL_STEP_MINUTES: u32 = 5;
+const MAX_ELECTIONAL_STEP_MINUTES: u32 = 240;
+
+/// Rectification limits
+const MAX_RECTIFICATION_WINDOW_HOURS: i64 = 48;
+const MIN_RECTIFICATION_STEP_MINUTES: u32 = 1;
+const MAX_RECTIFICATION_STEP_MINUTES: u32 = 60;
+const MAX_RECTIFICATION_EVENTS: usize = 50;
+const MAX_RECTIFICATION_CANDIDATES: usize = 20;
+
+/// Longest transit timeline, in days
+const MAX_TRANSIT_TIMELINE_DAYS: i64 = 366;
+
+/// Maximum rows of an ephemeris table, about ten years of days
+const MAX_EPHEMERIS_TABLE_ROWS: u64 = 4000;
+
+/// ChartSpec canvas limits, in pixels
+const MIN_CHART_SIZE: f32 = 100.0;
+const MAX_CHART_SIZE: f32 = 4096.0;
+
+/// Chart angles that can take part in aspects
+const ASPECT_ANGLES: &[&str] = &["asc", "mc"];
+
 /// Orb setting limits
 const MIN_ORB: f64 = 0.0;
 const MAX_ORB: f64 = 30.0;
 
+/// Live chart update interval limits, in seconds
+const MIN_LIVE_INTERVAL_SECONDS: u64 = 5;
+const MAX_LIVE_INTERVAL_SECONDS: u64 = 3600;
+
+/// Field errors collected while validating a request
+#[derive(Default)]
+struct FieldErrors(Vec<FieldError>);
+
+impl FieldErrors {
+    fn push(&mut self, field: impl Into<String>, code: FieldErrorCode, message: impl Into<String>) {
+        self.0.push(FieldError {
+            field: field.into(),
+            code,
+            message: message.into(),
+        });
+    }
+
+    fn into_result(self) -> Result<(), ApiError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::InvalidFields(self.0))
+        }
+    }
+}
+
 /// Request validator
 pub struct RequestValidator;
 
 impl RequestValidator {
-    /// Validate a complete render request
+    /// Validate a complete render request, reporting every invalid field
     pub fn validate_request(request: &RenderRequest) -> Result<(), ApiError> {
-        Self::validate_subjects(&request.subjects)?;
-        Self::validate_settings(&request.settings)?;
-        Self::validate_layer_config(&request.layer_config, &request.subjects)?;
-        Ok(())
+        let mut errors = FieldErrors::default();
+        Self::check_subjects(&mut errors, &request.subjects);
+        Self::check_settings(&mut errors, "settings", &request.settings);
+        Self::check_layer_config(&mut errors, &request.layer_config, &request.subjects);
+        if let Some(layout) = &request.layout {
+            Self::check_layout(&mut errors, layout);
+        }
+        Self::check_custom_points(&mut errors, &request.custom_points);
+        if let Some(wheel) = request.wheel.as_deref().filter(|wheel| WheelPresets::get(wheel).is_none()) {
+            errors.push(
+                "wheel",
+                FieldErrorCode::InvalidValue,
+                format!("Invalid wheel preset: {}. Valid presets: {:?}", wheel, WheelPresets::names()),
+            );
+        }
+        errors.into_result()
     }
 
-    /// Validate subjects
-    pub fn validate_subjects(subjects: &[Subject]) -> Result<(), ApiError> {
-        if subjects.is_empty() {
-            return Err(ApiError::ValidationError(
-                "At least one subject is required".to_string(),
-            ));
+    /// Validate a synastry request
+    pub fn validate_synastry_request(request: &SynastryRequest) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        if request.subjects.len() != 2 {
+            errors.push(
+                "subjects",
+                FieldErrorCode::OutOfRange,
+                format!("Synastry requires exactly two subjects, got {}", request.subjects.len()),
+            );
         }
+        Self::check_subjects(&mut errors, &request.subjects);
+        for (idx, subject) in request.subjects.iter().enumerate() {
+            if subject.birth_date_time.is_none() {
+                errors.push(
+                    format!("subjects[{}].birthDateTime", idx),
+                    FieldErrorCode::Required,
+                    format!("Subject[{}].birthDateTime is required for synastry", idx),
+                );
+            }
+        }
+        Self::check_settings(&mut errors, "settings", &request.settings);
+        errors.into_result()
+    }
 
-        let mut subject_ids = std::collections::HashSet::new();
-        for (idx, subject) in subjects.iter().enumerate() {
-            // Validate subject ID
-            if subject.id.is_empty() {
-                return Err(ApiError::ValidationError(format!(
-                    "Subject[{}].id cannot be empty",
-                    idx
-                )));
+    /// Validate a muhurta search request
+    pub fn validate_muhurta_request(request: &MuhurtaRequest) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        let start = Self::check_datetime(&mut errors, "start", &request.start);
+        let end = Self::check_datetime(&mut errors, "end", &request.end);
+        if let (Some(start), Some(end)) = (start, end) {
+            if end <= start {
+                errors.push("end", FieldErrorCode::Conflict, "end must be after start");
+            } else if end - start > chrono::Duration::days(MAX_MUHURTA_RANGE_DAYS) {
+                errors.push(
+                    "end",
+                    FieldErrorCode::OutOfRange,
+                    format!("Muhurta search range cannot exceed {} days", MAX_MUHURTA_RANGE_DAYS),
+                );
             }
+        }
+        if !(MIN_MUHURTA_STEP_MINUTES..=MAX_MUHURTA_STEP_MINUTES).contains(&request.step_minutes) {
+            errors.push(
+                "stepMinutes",
+                FieldErrorCode::OutOfRange,
+                format!(
+                    "stepMinutes must be between {} and {}, got: {}",
+                    MIN_MUHURTA_STEP_MINUTES, MAX_MUHURTA_STEP_MINUTES, request.step_minutes
+                ),
+            );
+        }
+        Self::check_location(&mut errors, "location", request.location.lat, request.location.lon);
+        Self::check_ayanamsa(&mut errors, "", Some(&request.ayanamsa), request.custom_ayanamsa);
 
-            if subject_ids.contains(&subject.id) {
-                return Err(ApiError::ValidationError(format!(
-                    "Duplicate subject ID: {}",
-                    subject.id
-                )));
+        let constraints = &request.constraints;
+        for (list, values) in [("tithis", &constraints.tithis), ("avoidTithis", &constraints.avoid_tithis)] {
+            for (idx, tithi) in values.iter().enumerate() {
+                if !(1..=30).contains(tithi) {
+                    errors.push(
+                        format!("constraints.{}[{}]", list, idx),
+                        FieldErrorCode::OutOfRange,
+                        format!("Invalid tithi: {}. Tithis are numbered 1-30", tithi),
+                    );
+                }
+            }
+        }
+        for (list, values) in [("nakshatras", &constraints.nakshatras), ("avoidNakshatras", &constraints.avoid_nakshatras)] {
+            for (idx, nakshatra) in values.iter().enumerate() {
+                if !NAKSHATRA_ORDER.iter().any(|(id, _, _)| id == nakshatra) {
+                    errors.push(
+                        format!("constraints.{}[{}]", list, idx),
+                        FieldErrorCode::InvalidValue,
+                        format!("Invalid nakshatra: {}", nakshatra),
+                    );
+                }
+            }
+        }
+        for (idx, weekday) in constraints.weekdays.iter().enumerate() {
+            if !WEEKDAYS.contains(&weekday.as_str()) {
+                errors.push(
+                    format!("constraints.weekdays[{}]", idx),
+                    FieldErrorCode::InvalidValue,
+                    format!("Invalid weekday: {}. Valid weekdays: {:?}", weekday, WEEKDAYS),
+                );
+            }
+        }
+        for (idx, lagna) in constraints.lagnas.iter().enumerate() {
+            if !SIGN_NAMES.contains(&lagna.as_str()) {
+                errors.push(
+                    format!("constraints.lagnas[{}]", idx),
+                    FieldErrorCode::InvalidValue,
+                    format!("Invalid lagna: {}. Valid signs: {:?}", lagna, SIGN_NAMES),
+                );
             }
-            subject_ids.insert(subject.id.clone());
+        }
 
-            // Validate birth date if provided
-            if let Some(birth_dt_str) = &subject.birth_date_time {
-                let birth_dt = Self::parse_and_validate_datetime(birth_dt_str)
-                    .map_err(|e| ApiError::ValidationError(format!(
-                        "Subject[{}].birthDateTime: {}",
-                        idx, e
-                    )))?;
-                Self::validate_date_range(birth_dt)?;
+        if constraints.needs_natal_moon() {
+            match &request.subject {
+                None => errors.push(
+                    "subject",
+                    FieldErrorCode::Required,
+                    "subject is required for requireTarabala and requireChandrabala",
+                ),
+                Some(subject) => {
+                    Self::check_subject(&mut errors, "subject", subject);
+                    if subject.birth_date_time.is_none() {
+                        errors.push(
+                            "subject.birthDateTime",
+                            FieldErrorCode::Required,
+                            "subject.birthDateTime is required for requireTarabala and requireChandrabala",
+                        );
+                    }
+                }
             }
+        }
+        errors.into_result()
+    }
 
-            // Validate location if provided
-            if let Some(loc) = &subject.location {
-                Self::validate_location(loc.lat, loc.lon)
-                    .map_err(|e| ApiError::ValidationError(format!(
-                        "Subject[{}].location: {}",
-                        idx, e
-                    )))?;
+    /// Validate an electional search request
+    pub fn validate_electional_request(request: &ElectionalSearchRequest) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        let start = Self::check_datetime(&mut errors, "start", &request.start);
+        let end = Self::check_datetime(&mut errors, "end", &request.end);
+        if let (Some(start), Some(end)) = (start, end) {
+            if end <= start {
+                errors.push("end", FieldErrorCode::Conflict, "end must be after start");
+            } else if end - start > chrono::Duration::days(MAX_ELECTIONAL_RANGE_DAYS) {
+                errors.push(
+                    "end",
+                    FieldErrorCode::OutOfRange,
+                    format!("Electional search range cannot exceed {} days", MAX_ELECTIONAL_RANGE_DAYS),
+                );
+            }
+        }
+        if !(MIN_ELECTIONAL_STEP_MINUTES..=MAX_ELECTIONAL_STEP_MINUTES).contains(&request.step_minutes) {
+            errors.push(
+                "stepMinutes",
+                FieldErrorCode::OutOfRange,
+                format!(
+                    "stepMinutes must be between {} and {}, got: {}",
+                    MIN_ELECTIONAL_STEP_MINUTES, MAX_ELECTIONAL_STEP_MINUTES, request.step_minutes
+                ),
+            );
+        }
+        Self::check_location(&mut errors, "location", request.location.lat, request.location.lon);
+        if request.constraints.is_empty() {
+            errors.push("constraints", FieldErrorCode::Required, "At least one constraint is required");
+        }
+        for (idx, constraint) in request.constraints.iter().enumerate() {
+            if let Err(message) = parse_constraint(constraint) {
+                errors.push(format!("constraints[{}]", idx), FieldErrorCode::InvalidValue, message);
+            }
+        }
+        Self::check_settings(&mut errors, "settings", &request.settings);
+        errors.into_result()
+    }
+
+    /// Validate a rectification request
+    pub fn validate_rectification_request(request: &RectificationRequest) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        let start = Self::check_datetime(&mut errors, "windowStart", &request.window_start);
+        let end = Self::check_datetime(&mut errors, "windowEnd", &request.window_end);
+        if let (Some(start), Some(end)) = (start, end) {
+            if end <= start {
+                errors.push("windowEnd", FieldErrorCode::Conflict, "windowEnd must be after windowStart");
+            } else if end - start > chrono::Duration::hours(MAX_RECTIFICATION_WINDOW_HOURS) {
+                errors.push(
+                    "windowEnd",
+                    FieldErrorCode::OutOfRange,
+                    format!("Rectification window cannot exceed {} hours", MAX_RECTIFICATION_WINDOW_HOURS),
+                );
+            }
+        }
+        if !(MIN_RECTIFICATION_STEP_MINUTES..=MAX_RECTIFICATION_STEP_MINUTES).contains(&request.step_minutes) {
+            errors.push(
+                "stepMinutes",
+                FieldErrorCode::OutOfRange,
+                format!(
+                    "stepMinutes must be between {} and {}, got: {}",
+                    MIN_RECTIFICATION_STEP_MINUTES, MAX_RECTIFICATION_STEP_MINUTES, request.step_minutes
+                ),
+            );
+        }
+        Self::check_location(&mut errors, "location", request.location.lat, request.location.lon);
+        if request.events.is_empty() {
+            errors.push("events", FieldErrorCode::Required, "At least one event is required");
+        } else if request.events.len() > MAX_RECTIFICATION_EVENTS {
+            errors.push(
+                "events",
+                FieldErrorCode::OutOfRange,
+                format!("At most {} events are allowed, got: {}", MAX_RECTIFICATION_EVENTS, request.events.len()),
+            );
+        }
+        for (idx, event) in request.events.iter().enumerate() {
+            let path = format!("events[{}].date", idx);
+            let date = Self::check_datetime(&mut errors, &path, &event.date);
+            if let (Some(date), Some(end)) = (date, end) {
+                if date < end {
+                    errors.push(path, FieldErrorCode::Conflict, "Events must fall after the birth time window");
+                }
+            }
+        }
+        if !(1..=MAX_RECTIFICATION_CANDIDATES).contains(&request.limit) {
+            errors.push(
+                "limit",
+                FieldErrorCode::OutOfRange,
+                format!("limit must be between 1 and {}, got: {}", MAX_RECTIFICATION_CANDIDATES, request.limit),
+            );
+        }
+        Self::check_settings(&mut errors, "settings", &request.settings);
+        errors.into_result()
+    }
+
+    /// Validate a transit timeline request
+    pub fn validate_transit_timeline_request(request: &TransitTimelineRequest) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        Self::check_subject(&mut errors, "subject", &request.subject);
+        if request.subject.birth_date_time.is_none() {
+            errors.push(
+                "subject.birthDateTime",
+                FieldErrorCode::Required,
+                "subject.birthDateTime is required for a transit timeline",
+            );
+        }
+        let start = Self::check_datetime(&mut errors, "start", &request.start);
+        let end = Self::check_datetime(&mut errors, "end", &request.end);
+        if let (Some(start), Some(end)) = (start, end) {
+            if end <= start {
+                errors.push("end", FieldErrorCode::Conflict, "end must be after start");
+            } else if end - start > chrono::Duration::days(MAX_TRANSIT_TIMELINE_DAYS) {
+                errors.push(
+                    "end",
+                    FieldErrorCode::OutOfRange,
+                    format!("Transit timeline cannot exceed {} days", MAX_TRANSIT_TIMELINE_DAYS),
+                );
+            }
+        }
+        Self::check_settings(&mut errors, "settings", &request.settings);
+        errors.into_result()
+    }
+
+    /// Validate a `GET /positions` query
+    pub fn validate_positions_query(query: &PositionsQuery) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        Self::check_datetime(&mut errors, "datetime", &query.datetime);
+        match (query.lat, query.lon) {
+            (Some(lat), Some(lon)) => {
+                for (name, value, limit) in [("lat", lat, 90.0), ("lon", lon, 180.0)] {
+                    if !(value.is_finite() && (-limit..=limit).contains(&value)) {
+                        errors.push(
+                            name,
+                            FieldErrorCode::OutOfRange,
+                            format!("{} must be between -{} and {}, got {}", name, limit, limit, value),
+                        );
+                    }
+                }
+            }
+            (None, None) => {}
+            (lat, _) => errors.push(
+                if lat.is_some() { "lon" } else { "lat" },
+                FieldErrorCode::Required,
+                "lat and lon must be given together",
+            ),
+        }
+        Self::check_object_ids(&mut errors, &query.object_ids());
+        if query.zodiac_type != "tropical" && query.zodiac_type != "sidereal" {
+            errors.push(
+                "zodiacType",
+                FieldErrorCode::InvalidValue,
+                format!("Invalid zodiacType: {}. Must be 'tropical' or 'sidereal'", query.zodiac_type),
+            );
+        }
+        Self::check_ayanamsa(&mut errors, "", query.ayanamsa.as_deref(), query.custom_ayanamsa);
+        errors.into_result()
+    }
+
+    /// Validate a `GET /ephemeris` query
+    pub fn validate_ephemeris_table_query(query: &EphemerisTableQuery) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        let from = Self::check_datetime(&mut errors, "from", &query.from);
+        let to = Self::check_datetime(&mut errors, "to", &query.to);
+        let step = query.step_duration();
+        if step.is_none() {
+            errors.push(
+                "step",
+                FieldErrorCode::InvalidFormat,
+                format!("step must be a number of days, hours or minutes such as 1d, 6h or 30m, got: {}", query.step),
+            );
+        }
+        if let (Some(from), Some(to)) = (from, to) {
+            if to <= from {
+                errors.push("to", FieldErrorCode::Conflict, "to must be after from");
+            } else if let Some(step) = step {
+                let rows = ((to - from).num_minutes() as u64).div_ceil(step.num_minutes() as u64);
+                if rows > MAX_EPHEMERIS_TABLE_ROWS {
+                    errors.push(
+                        "to",
+                        FieldErrorCode::OutOfRange,
+                        format!("An ephemeris table cannot exceed {} rows, got {}", MAX_EPHEMERIS_TABLE_ROWS, rows),
+                    );
+                }
+            }
+        }
+        Self::check_object_ids(&mut errors, &query.object_ids());
+        errors.into_result()
+    }
+
+    /// Validate the ids of an `objects` query parameter
+    fn check_object_ids(errors: &mut FieldErrors, object_ids: &[String]) {
+        for id in object_ids {
+            if !VALID_PLANETS.contains(&id.as_str()) {
+                errors.push(
+                    "objects",
+                    FieldErrorCode::InvalidValue,
+                    format!("Invalid object: {}. Valid objects: {:?}", id, VALID_PLANETS),
+                );
+            }
+        }
+    }
+
+    /// Validate the file names of an ephemeris download
+    pub fn validate_ephemeris_download(request: &EphemerisDownloadRequest) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        for (i, name) in request.files.iter().flatten().enumerate() {
+            if EphemerisFile::parse(name).is_none() {
+                errors.push(
+                    format!("files[{}]", i),
+                    FieldErrorCode::InvalidValue,
+                    format!("Unknown ephemeris file: {}. Expected a name such as sepl_18.se1", name),
+                );
+            }
+        }
+        errors.into_result()
+    }
+
+    /// Validate an interpretation request
+    pub fn validate_interpretation_request(request: &InterpretationRequest) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        Self::check_subject(&mut errors, "subject", &request.subject);
+        if request.subject.birth_date_time.is_none() {
+            errors.push(
+                "subject.birthDateTime",
+                FieldErrorCode::Required,
+                "subject.birthDateTime is required for interpretations",
+            );
+        }
+        Self::check_settings(&mut errors, "settings", &request.settings);
+        if request.pack.is_empty() {
+            errors.push("pack", FieldErrorCode::Required, "pack must be a content pack id");
+        }
+        if request.locale.is_empty() {
+            errors.push("locale", FieldErrorCode::Required, "locale must be a language tag such as \"en\"");
+        }
+        if let Some(at) = &request.at {
+            Self::check_datetime(&mut errors, "at", at);
+        }
+        errors.into_result()
+    }
+
+    /// Validate a wheel check request; problems with the wheel itself are reported in the response
+    pub fn validate_wheel_check(request: &WheelValidateRequest) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        if !request.wheel.is_object() {
+            errors.push("wheel", FieldErrorCode::InvalidFormat, "wheel must be a wheel definition object");
+        }
+        for (i, id) in request.layer_ids.iter().flatten().enumerate() {
+            if id.is_empty() {
+                errors.push(format!("layerIds[{}]", i), FieldErrorCode::Required, "Layer ids must be non-empty");
+            }
+        }
+        errors.into_result()
+    }
+
+    /// Validate `fields` / `exclude` response paths
+    pub fn validate_field_selection(selection: &FieldSelection) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        for (param, paths) in [("fields", selection.field_paths()), ("exclude", selection.exclude_paths())] {
+            for path in paths.iter().filter(|path| path.iter().any(|segment| segment.is_empty())) {
+                errors.push(
+                    param,
+                    FieldErrorCode::InvalidFormat,
+                    format!("{} path '{}' has an empty segment", param, path.join(".")),
+                );
             }
         }
+        errors.into_result()
+    }
 
-        Ok(())
+    /// Validate a live chart subscription
+    pub fn validate_live_subscription(subscription: &LiveSubscription) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        Self::check_subject(&mut errors, "subject", &subscription.subject);
+        if subscription.subject.birth_date_time.is_none() {
+            errors.push(
+                "subject.birthDateTime",
+                FieldErrorCode::Required,
+                "subject.birthDateTime is required for a live chart",
+            );
+        }
+        Self::check_settings(&mut errors, "settings", &subscription.settings);
+        if !(MIN_LIVE_INTERVAL_SECONDS..=MAX_LIVE_INTERVAL_SECONDS).contains(&subscription.interval_seconds) {
+            errors.push(
+                "intervalSeconds",
+                FieldErrorCode::OutOfRange,
+                format!(
+                    "intervalSeconds must be between {} and {}, got: {}",
+                    MIN_LIVE_INTERVAL_SECONDS, MAX_LIVE_INTERVAL_SECONDS, subscription.interval_seconds
+                ),
+            );
+        }
+        errors.into_result()
+    }
+
+    /// Validate subjects
+    pub fn validate_subjects(subjects: &[Subject]) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        Self::check_subjects(&mut errors, subjects);
+        errors.into_result()
     }
 
     /// Validate chart settings
     pub fn validate_settings(settings: &ChartSettings) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        Self::check_settings(&mut errors, "settings", settings);
+        errors.into_result()
+    }
+
+    /// Validate layer configuration
+    pub fn validate_layer_config(
+        layer_config: &HashMap<String, LayerConfig>,
+        subjects: &[Subject],
+    ) -> Result<(), ApiError> {
+        let mut errors = FieldErrors::default();
+        Self::check_layer_config(&mut errors, layer_config, subjects);
+        errors.into_result()
+    }
+
+    fn check_subjects(errors: &mut FieldErrors, subjects: &[Subject]) {
+        if subjects.is_empty() {
+            errors.push("subjects", FieldErrorCode::Required, "At least one subject is required");
+            return;
+        }
+
+        let mut subject_ids = std::collections::HashSet::new();
+        for (idx, subject) in subjects.iter().enumerate() {
+            let path = format!("subjects[{}]", idx);
+            if !subject.id.is_empty() && !subject_ids.insert(&subject.id) {
+                errors.push(
+                    format!("{}.id", path),
+                    FieldErrorCode::Duplicate,
+                    format!("Duplicate subject ID: {}", subject.id),
+                );
+            }
+            Self::check_subject(errors, &path, subject);
+        }
+    }
+
+    /// Validate one subject at `path`
+    fn check_subject(errors: &mut FieldErrors, path: &str, subject: &Subject) {
+        if subject.id.is_empty() {
+            errors.push(
+                format!("{}.id", path),
+                FieldErrorCode::Required,
+                format!("{}.id cannot be empty", display_path(path)),
+            );
+        }
+
+        // Validate birth date if provided
+        if let Some(birth_dt_str) = &subject.birth_date_time {
+            Self::check_datetime(errors, &format!("{}.birthDateTime", path), birth_dt_str);
+        }
+
+        // Validate location if provided
+        if let Some(loc) = &subject.location {
+            Self::check_location(errors, &format!("{}.location", path), loc.lat, loc.lon);
+        }
+    }
+
+    fn check_settings(errors: &mut FieldErrors, path: &str, settings: &ChartSettings) {
         // Validate zodiac type
         if settings.zodiac_type != "tropical" && settings.zodiac_type != "sidereal" {
-            return Err(ApiError::ValidationError(format!(
-                "Invalid zodiacType: {}. Must be 'tropical' or 'sidereal'",
-                settings.zodiac_type
-            )));
+            errors.push(
+                format!("{}.zodiacType", path),
+                FieldErrorCode::InvalidValue,
+                format!("Invalid zodiacType: {}. Must be 'tropical' or 'sidereal'", settings.zodiac_type),
+            );
         }
 
         // Validate house system
         if !VALID_HOUSE_SYSTEMS.contains(&settings.house_system.as_str()) {
-            return Err(ApiError::ValidationError(format!(
-                "Invalid houseSystem: {}. Valid systems: {:?}",
-                settings.house_system, VALID_HOUSE_SYSTEMS
-            )));
+            errors.push(
+                format!("{}.houseSystem", path),
+                FieldErrorCode::InvalidValue,
+                format!("Invalid houseSystem: {}. Valid systems: {:?}", settings.house_system, VALID_HOUSE_SYSTEMS),
+            );
         }
 
-        // Validate ayanamsa if provided
-        if let Some(ayanamsa) = &settings.ayanamsa {
-            if !VALID_AYANAMSAS.contains(&ayanamsa.as_str()) {
-                return Err(ApiError::ValidationError(format!(
-                    "Invalid ayanamsa: {}. Valid ayanamsas: {:?}",
-                    ayanamsa, VALID_AYANAMSAS
-                )));
+        Self::check_ayanamsa(errors, path, settings.ayanamsa.as_deref(), settings.custom_ayanamsa);
+
+        // Validate decan system
+        if !VALID_DECAN_SYSTEMS.contains(&settings.decan_system.as_str()) {
+            errors.push(
+                format!("{}.decanSystem", path),
+                FieldErrorCode::InvalidValue,
+                format!("Invalid decanSystem: {}. Valid systems: {:?}", settings.decan_system, VALID_DECAN_SYSTEMS),
+            );
+        }
+
+        // Validate term system
+        if !VALID_TERM_SYSTEMS.contains(&settings.term_system.as_str()) {
+            errors.push(
+                format!("{}.termSystem", path),
+                FieldErrorCode::InvalidValue,
+                format!("Invalid termSystem: {}. Valid systems: {:?}", settings.term_system, VALID_TERM_SYSTEMS),
+            );
+        }
+
+        // Validate requested divisional charts
+        if let Some(vedic_config) = &settings.vedic_config {
+            for (idx, varga) in vedic_config.vargas.iter().enumerate() {
+                if resolve_varga_id(varga).is_none() {
+                    errors.push(
+                        format!("{}.vedicConfig.vargas[{}]", path, idx),
+                        FieldErrorCode::InvalidValue,
+                        format!(
+                            "Invalid varga: {}. Use a divisional chart id (d1-d60) or name such as 'navamsa'",
+                            varga
+                        ),
+                    );
+                }
+            }
+            if !BHAVA_METHODS.contains(&vedic_config.bhava_method.as_str()) {
+                errors.push(
+                    format!("{}.vedicConfig.bhava_method", path),
+                    FieldErrorCode::InvalidValue,
+                    format!("Invalid bhava_method: {}. Valid methods: {:?}", vedic_config.bhava_method, BHAVA_METHODS),
+                );
+            }
+            let from = vedic_config.dashas_from.as_deref().and_then(|value| {
+                Self::check_datetime(errors, &format!("{}.vedicConfig.dashas_from", path), value)
+            });
+            let to = vedic_config.dashas_to.as_deref().and_then(|value| {
+                Self::check_datetime(errors, &format!("{}.vedicConfig.dashas_to", path), value)
+            });
+            if let (Some(from), Some(to)) = (from, to) {
+                if from >= to {
+                    errors.push(
+                        format!("{}.vedicConfig.dashas_to", path),
+                        FieldErrorCode::InvalidValue,
+                        "dashas_to must be after dashas_from",
+                    );
+                }
             }
         }
 
         // Validate orb settings
-        Self::validate_orb_setting("conjunction", settings.orb_settings.conjunction)?;
-        Self::validate_orb_setting("opposition", settings.orb_settings.opposition)?;
-        Self::validate_orb_setting("trine", settings.orb_settings.trine)?;
-        Self::validate_orb_setting("square", settings.orb_settings.square)?;
-        Self::validate_orb_setting("sextile", settings.orb_settings.sextile)?;
+        let orbs = &settings.orb_settings;
+        for (name, value) in [
+            ("conjunction", orbs.conjunction),
+            ("opposition", orbs.opposition),
+            ("trine", orbs.trine),
+            ("square", orbs.square),
+            ("sextile", orbs.sextile),
+        ] {
+            Self::check_orb_setting(errors, path, "orbSettings", name, value);
+        }
+        let mut object_orbs: Vec<_> = settings.object_orbs.iter().collect();
+        object_orbs.sort_by(|a, b| a.0.cmp(b.0));
+        for (object, value) in object_orbs {
+            if VALID_PLANETS.contains(&object.as_str()) {
+                Self::check_orb_setting(errors, path, "objectOrbs", object, *value);
+            } else {
+                errors.push(
+                    format!("{}.objectOrbs.{}", path, object),
+                    FieldErrorCode::InvalidValue,
+                    format!("Invalid objectOrbs key: {}. Valid objects: {:?}", object, VALID_PLANETS),
+                );
+            }
+        }
+
+        Self::check_aspect_filter(errors, path, &settings.aspect_filter);
 
         // Validate include objects
         for (idx, obj) in settings.include_objects.iter().enumerate() {
             if !VALID_PLANETS.contains(&obj.as_str()) {
-                return Err(ApiError::ValidationError(format!(
-                    "Invalid includeObjects[{}]: {}. Valid objects: {:?}",
-                    idx, obj, VALID_PLANETS
-                )));
+                errors.push(
+                    format!("{}.includeObjects[{}]", path, idx),
+                    FieldErrorCode::InvalidValue,
+                    format!("Invalid includeObjects[{}]: {}. Valid objects: {:?}", idx, obj, VALID_PLANETS),
+                );
             }
         }
-
-        Ok(())
     }
 
-    /// Validate layer configuration
-    pub fn validate_layer_config(
-        layer_config: &HashMap<String, LayerConfig>,
-        subjects: &[Subject],
-    ) -> Result<(), ApiError> {
+    fn check_layer_config(errors: &mut FieldErrors, layer_config: &HashMap<String, LayerConfig>, subjects: &[Subject]) {
         if layer_config.is_empty() {
-            return Err(ApiError::ValidationError(
-                "At least one layer must be configured".to_string(),
-            ));
+            errors.push("layer_config", FieldErrorCode::Required, "At least one layer must be configured");
+            return;
         }
 
         let subject_ids: std::collections::HashSet<_> =
             subjects.iter().map(|s| &s.id).collect();
 
-        for (layer_id, config) in layer_config {
-            // Validate layer kind
-            if !VALID_LAYER_KINDS.contains(&config.kind.as_str()) {
-                return Err(ApiError::ValidationError(format!(
-                    "Layer '{}': Invalid kind '{}'. Valid kinds: {:?}",
-                    layer_id, config.kind, VALID_LAYER_KINDS
-                )));
-            }
+        // Sorted so errors come back in a stable order
+        let mut layers: Vec<_> = layer_config.iter().collect();
+        layers.sort_by_key(|(a, _)| *a);
+
+        for (layer_id, config) in layers {
+            let path = format!("layer_config.{}", layer_id);
 
             // Validate based on layer kind
-            match config.kind.as_str() {
-                "natal" => {
-                    if let Some(subject_id) = &config.subject_id {
-                        if !subject_ids.contains(subject_id) {
-                            return Err(ApiError::ValidationError(format!(
-                                "Layer '{}': subjectId '{}' not found in subjects",
-                                layer_id, subject_id
-                            )));
-                        }
-                    } else {
-                        return Err(ApiError::ValidationError(format!(
-                            "Layer '{}': natal layer must specify a subjectId",
-                            layer_id
-                        )));
-                    }
-                }
-                "transit" => {
-                    if config.explicit_date_time.is_none() {
-                        return Err(ApiError::ValidationError(format!(
-                            "Layer '{}': transit layer must specify explicitDateTime",
-                            layer_id
-                        )));
-                    }
-                    if let Some(dt_str) = &config.explicit_date_time {
-                        let dt = Self::parse_and_validate_datetime(dt_str)
-                            .map_err(|e| ApiError::ValidationError(format!(
-                                "Layer '{}'.explicitDateTime: {}",
-                                layer_id, e
-                            )))?;
-                        Self::validate_date_range(dt)?;
-                    }
+            if !VALID_LAYER_KINDS.contains(&config.kind.as_str()) {
+                errors.push(
+                    format!("{}.kind", path),
+                    FieldErrorCode::InvalidValue,
+                    format!("Layer '{}': Invalid kind '{}'. Valid kinds: {:?}", layer_id, config.kind, VALID_LAYER_KINDS),
+                );
+            }
+            if matches!(config.kind.as_str(), "natal" | "progressed") {
+                match &config.subject_id {
+                    Some(subject_id) if !subject_ids.contains(subject_id) => errors.push(
+                        format!("{}.subjectId", path),
+                        FieldErrorCode::UnknownReference,
+                        format!("Layer '{}': subjectId '{}' not found in subjects", layer_id, subject_id),
+                    ),
+                    Some(_) => {}
+                    None => errors.push(
+                        format!("{}.subjectId", path),
+                        FieldErrorCode::Required,
+                        format!("Layer '{}': {} layer must specify a subjectId", layer_id, config.kind),
+                    ),
                 }
-                "progressed" => {
-                    // Similar to transit, requires explicitDateTime
-                    if config.explicit_date_time.is_none() {
-                        return Err(ApiError::ValidationError(format!(
-                            "Layer '{}': progressed layer must specify explicitDateTime",
-                            layer_id
-                        )));
+            }
+            if matches!(config.kind.as_str(), "transit" | "progressed") {
+                match &config.explicit_date_time {
+                    Some(dt_str) => {
+                        Self::check_datetime(errors, &format!("{}.explicitDateTime", path), dt_str);
                     }
+                    None => errors.push(
+                        format!("{}.explicitDateTime", path),
+                        FieldErrorCode::Required,
+                        format!("Layer '{}': {} layer must specify explicitDateTime", layer_id, config.kind),
+                    ),
                 }
-                _ => {}
+            }
+            if config.progression.is_some() && config.kind != "progressed" {
+                errors.push(
+                    format!("{}.progression", path),
+                    FieldErrorCode::InvalidValue,
+                    format!("Layer '{}': progression only applies to progressed layers", layer_id),
+                );
             }
 
             // Validate location if provided
             if let Some(loc) = &config.location {
-                Self::validate_location(loc.lat, loc.lon)
-                    .map_err(|e| ApiError::ValidationError(format!(
-                        "Layer '{}'.location: {}",
-                        layer_id, e
-                    )))?;
+                Self::check_location(errors, &format!("{}.location", path), loc.lat, loc.lon);
             }
         }
+    }
+
+    /// Validate the ChartSpec canvas size and margin
+    fn check_layout(errors: &mut FieldErrors, layout: &ChartLayout) {
+        for (name, value) in [("width", layout.width), ("height", layout.height)] {
+            if !(value.is_finite() && (MIN_CHART_SIZE..=MAX_CHART_SIZE).contains(&value)) {
+                errors.push(
+                    format!("layout.{}", name),
+                    FieldErrorCode::OutOfRange,
+                    format!("layout.{} must be between {} and {} pixels, got {}", name, MIN_CHART_SIZE, MAX_CHART_SIZE, value),
+                );
+            }
+        }
+        let max_margin = layout.width.min(layout.height) / 2.0;
+        if !(layout.margin.is_finite() && layout.margin >= 0.0 && layout.margin < max_margin) {
+            errors.push(
+                "layout.margin",
+                FieldErrorCode::OutOfRange,
+                format!("layout.margin must be at least 0 and less than half the chart size, got {}", layout.margin),
+            );
+        }
+    }
 
-        Ok(())
+    /// Validate custom point ids and longitudes
+    fn check_custom_points(errors: &mut FieldErrors, point_sets: &HashMap<String, Vec<CustomPoint>>) {
+        let mut set_ids: Vec<_> = point_sets.keys().collect();
+        set_ids.sort();
+        for set_id in set_ids {
+            for (idx, point) in point_sets[set_id].iter().enumerate() {
+                let path = format!("customPoints.{}[{}]", set_id, idx);
+                if point.id.trim().is_empty() {
+                    errors.push(format!("{}.id", path), FieldErrorCode::Required, "Custom point id must not be empty");
+                }
+                if !point.lon.is_finite() {
+                    errors.push(
+                        format!("{}.lon", path),
+                        FieldErrorCode::OutOfRange,
+                        format!("Custom point longitude must be a finite number, got {}", point.lon),
+                    );
+                }
+            }
+        }
     }
 
-    /// Validate a single orb setting
-    fn validate_orb_setting(name: &str, value: f64) -> Result<(), ApiError> {
-        if value < MIN_ORB || value > MAX_ORB {
-            return Err(ApiError::ValidationError(format!(
-                "orbSettings.{} must be between {} and {} degrees, got {}",
-                name, MIN_ORB, MAX_ORB, value
-            )));
+    /// Validate aspect types and the objects named in pairs
+    fn check_aspect_filter(errors: &mut FieldErrors, path: &str, filter: &AspectFilterSettings) {
+        let path = format!("{}.aspectFilter", path);
+        let aspect_types: Vec<&str> = DEFAULT_ORBS.iter().chain(DEFAULT_MINOR_ORBS).map(|(name, _)| *name).collect();
+        for (idx, aspect_type) in filter.aspect_types.iter().flatten().enumerate() {
+            if !aspect_types.contains(&aspect_type.as_str()) {
+                errors.push(
+                    format!("{}.aspectTypes[{}]", path, idx),
+                    FieldErrorCode::InvalidValue,
+                    format!("Invalid aspect type: {}. Valid types: {:?}", aspect_type, aspect_types),
+                );
+            }
         }
+        let include_pairs = filter.include_pairs.as_deref().unwrap_or_default();
+        for (name, pairs) in [("includePairs", include_pairs), ("excludePairs", &filter.exclude_pairs[..])] {
+            for (idx, pair) in pairs.iter().enumerate() {
+                for (side, object) in pair.iter().enumerate() {
+                    if !VALID_PLANETS.contains(&object.as_str()) && !ASPECT_ANGLES.contains(&object.as_str()) {
+                        errors.push(
+                            format!("{}.{}[{}][{}]", path, name, idx, side),
+                            FieldErrorCode::InvalidValue,
+                            format!("Invalid object in {}: {}. Use a planet id, asc or mc", name, object),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Validate a single orb setting in the `group` table
+    fn check_orb_setting(errors: &mut FieldErrors, path: &str, group: &str, name: &str, value: f64) {
+        let field = format!("{}.{}.{}", path, group, name);
         if !value.is_finite() {
-            return Err(ApiError::ValidationError(format!(
-                "orbSettings.{} must be a finite number, got {}",
-                name, value
-            )));
+            errors.push(
+                field,
+                FieldErrorCode::OutOfRange,
+                format!("{}.{} must be a finite number, got {}", group, name, value),
+            );
+        } else if !(MIN_ORB..=MAX_ORB).contains(&value) {
+            errors.push(
+                field,
+                FieldErrorCode::OutOfRange,
+                format!("{}.{} must be between {} and {} degrees, got {}", group, name, MIN_ORB, MAX_ORB, value),
+            );
         }
-        Ok(())
     }
 
-    /// Validate location coordinates
-    fn validate_location(lat: f64, lon: f64) -> Result<(), String> {
+    /// Validate location coordinates at `path`
+    fn check_location(errors: &mut FieldErrors, path: &str, lat: f64, lon: f64) {
+        let label = display_path(path);
         if !lat.is_finite() {
-            return Err("latitude must be a finite number".to_string());
+            errors.push(
+                format!("{}.lat", path),
+                FieldErrorCode::OutOfRange,
+                format!("{}: latitude must be a finite number", label),
+            );
+        } else if !(-90.0..=90.0).contains(&lat) {
+            errors.push(
+                format!("{}.lat", path),
+                FieldErrorCode::OutOfRange,
+                format!("{}: latitude must be between -90 and 90, got {}", label, lat),
+            );
         }
         if !lon.is_finite() {
-            return Err("longitude must be a finite number".to_string());
+            errors.push(
+                format!("{}.lon", path),
+                FieldErrorCode::OutOfRange,
+                format!("{}: longitude must be a finite number", label),
+            );
+        } else if !(-180.0..=180.0).contains(&lon) {
+            errors.push(
+                format!("{}.lon", path),
+                FieldErrorCode::OutOfRange,
+                format!("{}: longitude must be between -180 and 180, got {}", label, lon),
+            );
         }
-        if lat < -90.0 || lat > 90.0 {
-            return Err(format!("latitude must be between -90 and 90, got {}", lat));
+    }
+
+    /// Validate an ayanamsa name and the custom ayanamsa value; `path` is the parent object
+    fn check_ayanamsa(errors: &mut FieldErrors, path: &str, ayanamsa: Option<&str>, custom_ayanamsa: Option<f64>) {
+        let field = |name: &str| if path.is_empty() { name.to_string() } else { format!("{}.{}", path, name) };
+
+        // Validate ayanamsa if provided
+        if let Some(ayanamsa) = ayanamsa {
+            if !is_supported_ayanamsa(ayanamsa) {
+                errors.push(
+                    field("ayanamsa"),
+                    FieldErrorCode::InvalidValue,
+                    format!("Invalid ayanamsa: {}. Valid ayanamsas: {:?}", ayanamsa, supported_ayanamsa_names()),
+                );
+            }
         }
-        if lon < -180.0 || lon > 180.0 {
-            return Err(format!("longitude must be between -180 and 180, got {}", lon));
+
+        // A custom ayanamsa needs a value, and a value needs the custom ayanamsa
+        let is_custom = ayanamsa == Some(CUSTOM_AYANAMSA);
+        match custom_ayanamsa {
+            None if is_custom => errors.push(
+                field("customAyanamsa"),
+                FieldErrorCode::Required,
+                "customAyanamsa is required when ayanamsa is 'custom'",
+            ),
+            Some(_) if !is_custom => errors.push(
+                field("customAyanamsa"),
+                FieldErrorCode::Conflict,
+                "customAyanamsa can only be used with ayanamsa 'custom'",
+            ),
+            Some(value) if !value.is_finite() || !(0.0..360.0).contains(&value) => errors.push(
+                field("customAyanamsa"),
+                FieldErrorCode::OutOfRange,
+                format!("customAyanamsa must be between 0 and 360 degrees, got: {}", value),
+            ),
+            _ => {}
         }
-        Ok(())
     }
 
-    /// Parse and validate datetime string
-    fn parse_and_validate_datetime(dt_str: &str) -> Result<DateTime<Utc>, String> {
+    /// Parse a datetime at `path` and check it is within the supported years
+    fn check_datetime(errors: &mut FieldErrors, path: &str, dt_str: &str) -> Option<DateTime<Utc>> {
+        let dt = match Self::parse_datetime(dt_str) {
+            Ok(dt) => dt,
+            Err(e) => {
+                errors.push(path, FieldErrorCode::InvalidFormat, format!("{}: {}", display_path(path), e));
+                return None;
+            }
+        };
+
+        use chrono::Datelike;
+        let year = dt.year();
+        if !(MIN_YEAR..=MAX_YEAR).contains(&year) {
+            errors.push(
+                path,
+                FieldErrorCode::OutOfRange,
+                format!("Date year {} is outside valid range ({} to {})", year, MIN_YEAR, MAX_YEAR),
+            );
+            return None;
+        }
+        Some(dt)
+    }
+
+    /// Parse datetime string
+    fn parse_datetime(dt_str: &str) -> Result<DateTime<Utc>, String> {
         let dt = chrono::DateTime::parse_from_rfc3339(dt_str)
             .or_else(|_| dt_str.parse::<DateTime<Utc>>().map(|dt| dt.with_timezone(&chrono::FixedOffset::east_opt(0).unwrap())))
             .map_err(|e| format!("Failed to parse datetime '{}': {}", dt_str, e))?
@@ -292,18 +998,807 @@ impl RequestValidator {
 
         Ok(dt)
     }
+}
 
-    /// Validate date is within reasonable range
-    fn validate_date_range(dt: DateTime<Utc>) -> Result<(), ApiError> {
-        use chrono::Datelike;
-        let year = dt.year();
-        if year < MIN_YEAR || year > MAX_YEAR {
-            return Err(ApiError::ValidationError(format!(
-                "Date year {} is outside valid range ({} to {})",
-                year, MIN_YEAR, MAX_YEAR
-            )));
+/// How request bodies are parsed
+#[derive(Debug, Clone, Copy)]
+pub struct ValidationOptions {
+    /// Reject unknown JSON fields and settings_override keys instead of ignoring them
+    pub strict: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        Self { strict: true }
+    }
+}
+
+/// Request bodies checked by [`ValidatedJson`]
+pub trait Validate {
+    fn validate(&self) -> Result<(), ApiError>;
+
+    /// Keys inside free-form maps that the server does not understand
+    fn unknown_keys(&self) -> Vec<FieldError> {
+        Vec::new()
+    }
+}
+
+impl Validate for RenderRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_request(self)
+    }
+
+    fn unknown_keys(&self) -> Vec<FieldError> {
+        let mut errors = FieldErrors::default();
+        let mut keys: Vec<_> = self.settings_override.keys().collect();
+        keys.sort();
+        for key in keys {
+            let accepted = struct_fields::<ChartSettings>();
+            if !accepted.contains(&key.as_str()) {
+                errors.push(
+                    format!("settings_override.{}", key),
+                    FieldErrorCode::UnknownField,
+                    format!("Unknown settings_override key '{}'. Accepted keys: {}", key, accepted.join(", ")),
+                );
+            }
+        }
+        if let Some(orbs) = self.settings_override.get("orbSettings").and_then(|v| v.as_object()) {
+            let accepted = struct_fields::<OrbSettings>();
+            for key in orbs.keys().filter(|key| !accepted.contains(&key.as_str())) {
+                errors.push(
+                    format!("settings_override.orbSettings.{}", key),
+                    FieldErrorCode::UnknownField,
+                    format!("Unknown orbSettings key '{}'. Accepted keys: {}", key, accepted.join(", ")),
+                );
+            }
+        }
+        errors.0
+    }
+}
+
+impl Validate for SynastryRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_synastry_request(self)
+    }
+}
+
+impl Validate for MuhurtaRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_muhurta_request(self)
+    }
+}
+
+impl Validate for ElectionalSearchRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_electional_request(self)
+    }
+}
+
+impl Validate for RectificationRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_rectification_request(self)
+    }
+}
+
+impl Validate for TransitTimelineRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_transit_timeline_request(self)
+    }
+}
+
+impl Validate for EphemerisTableQuery {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_ephemeris_table_query(self)
+    }
+}
+
+impl Validate for EphemerisDownloadRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_ephemeris_download(self)
+    }
+}
+
+impl Validate for InterpretationRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_interpretation_request(self)
+    }
+}
+
+impl Validate for WheelValidateRequest {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_wheel_check(self)
+    }
+}
+
+impl Validate for PositionsQuery {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_positions_query(self)
+    }
+}
+
+impl Validate for FieldSelection {
+    fn validate(&self) -> Result<(), ApiError> {
+        RequestValidator::validate_field_selection(self)
+    }
+}
+
+impl Validate for LiveClientMessage {
+    fn validate(&self) -> Result<(), ApiError> {
+        match self {
+            LiveClientMessage::Subscribe(subscription) => RequestValidator::validate_live_subscription(subscription),
+            LiveClientMessage::Unsubscribe => Ok(()),
+        }
+    }
+}
+
+/// Deserialize and validate a request body.
+///
+/// In strict mode unknown fields are reported alongside validation errors; otherwise
+/// they are logged and ignored. A value of the wrong type is reported against its field.
+pub fn parse_request<T>(body: serde_json::Value, options: ValidationOptions) -> Result<T, ApiError>
+where
+    T: Validate + DeserializeOwned,
+{
+    let mut unknown = Vec::new();
+    let deserializer = serde_ignored::Deserializer::new(body, |path| {
+        let mut segments = Vec::new();
+        path_segments(&path, &mut segments);
+        unknown.push(unknown_field_error::<T>(&segments));
+    });
+    let request: T = serde_path_to_error::deserialize(deserializer).map_err(deserialize_error)?;
+    unknown.extend(request.unknown_keys());
+
+    let mut errors = Vec::new();
+    if options.strict {
+        errors = unknown;
+    } else if !unknown.is_empty() {
+        let fields: Vec<_> = unknown.iter().map(|e| e.field.as_str()).collect();
+        tracing::warn!(fields = ?fields, "Ignoring unknown request fields");
+    }
+    match request.validate() {
+        Ok(()) => {}
+        Err(ApiError::InvalidFields(invalid)) => errors.extend(invalid),
+        Err(e) => return Err(e),
+    }
+    if errors.is_empty() {
+        Ok(request)
+    } else {
+        Err(ApiError::InvalidFields(errors))
+    }
+}
+
+/// Field error for a body that does not deserialize, e.g. an unknown enum variant
+fn deserialize_error(error: serde_path_to_error::Error<serde_json::Error>) -> ApiError {
+    let path = match error.path().to_string() {
+        root if root == "." => String::new(),
+        path => path,
+    };
+    let message = error.inner().to_string();
+    let missing = message
+        .strip_prefix("missing field `")
+        .and_then(|rest| rest.split('`').next());
+    let (field, code) = match missing {
+        Some(name) if path.is_empty() => (name.to_string(), FieldErrorCode::Required),
+        Some(name) => (format!("{}.{}", path, name), FieldErrorCode::Required),
+        None if path.is_empty() => return ApiError::ValidationError(format!("Invalid request body: {}", message)),
+        None => (path, FieldErrorCode::InvalidValue),
+    };
+    ApiError::InvalidFields(vec![FieldError {
+        message: format!("Invalid {}: {}", field, message),
+        field,
+        code,
+    }])
+}
+
+/// JSON body extractor that validates the request before the handler runs.
+///
+/// Invalid requests are rejected here, so they never wait on or hold a pooled
+/// `ChartService`. Bodies that are not JSON keep axum's usual rejection.
+pub struct ValidatedJson<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequest<S> for ValidatedJson<T>
+where
+    S: Send + Sync,
+    ValidationOptions: FromRef<S>,
+    T: Validate + DeserializeOwned,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let options = ValidationOptions::from_ref(state);
+        let Json(body) = Json::<serde_json::Value>::from_request(req, state)
+            .await
+            .map_err(IntoResponse::into_response)?;
+        tracing::info_span!("validate_request")
+            .in_scope(|| parse_request(body, options))
+            .map(Self)
+            .map_err(IntoResponse::into_response)
+    }
+}
+
+/// One step of a JSON path
+enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+fn path_segments(path: &serde_ignored::Path, segments: &mut Vec<PathSegment>) {
+    match path {
+        serde_ignored::Path::Root => {}
+        serde_ignored::Path::Seq { parent, index } => {
+            path_segments(parent, segments);
+            segments.push(PathSegment::Index(*index));
+        }
+        serde_ignored::Path::Map { parent, key } => {
+            path_segments(parent, segments);
+            segments.push(PathSegment::Key(key.clone()));
+        }
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => path_segments(parent, segments),
+    }
+}
+
+/// Error for an unknown field, listing the fields accepted by the enclosing object
+fn unknown_field_error<T: DeserializeOwned>(segments: &[PathSegment]) -> FieldError {
+    let mut field = String::new();
+    for segment in segments {
+        match segment {
+            PathSegment::Key(key) if field.is_empty() => field.push_str(key),
+            PathSegment::Key(key) => field.push_str(&format!(".{}", key)),
+            PathSegment::Index(index) => field.push_str(&format!("[{}]", index)),
         }
-        Ok(())
+    }
+    let (name, parent) = match segments.split_last() {
+        Some((PathSegment::Key(key), parent)) => (key.as_str(), parent),
+        _ => (field.as_str(), segments),
+    };
+
+    let accepted = accepted_fields::<T>(parent);
+    let message = if accepted.is_empty() {
+        format!("Unknown field '{}'", name)
+    } else {
+        format!("Unknown field '{}'. Accepted fields: {}", name, accepted.join(", "))
+    };
+    FieldError {
+        field,
+        code: FieldErrorCode::UnknownField,
+        message,
+    }
+}
+
+/// Fields of the request object found at `parent`
+fn accepted_fields<T: DeserializeOwned>(parent: &[PathSegment]) -> &'static [&'static str] {
+    let mut keys = parent.iter().rev().filter_map(|segment| match segment {
+        PathSegment::Key(key) => Some(key.as_str()),
+        PathSegment::Index(_) => None,
+    });
+    let (Some(last), grandparent) = (keys.next(), keys.next()) else {
+        return struct_fields::<T>();
+    };
+    if grandparent == Some("layer_config") {
+        return struct_fields::<LayerConfig>();
+    }
+    match last {
+        "subjects" | "subject" => struct_fields::<Subject>(),
+        "location" => struct_fields::<Location>(),
+        "settings" => struct_fields::<ChartSettings>(),
+        "orbSettings" => struct_fields::<OrbSettings>(),
+        "aspectFilter" => struct_fields::<AspectFilterSettings>(),
+        "vedicConfig" => struct_fields::<VedicConfig>(),
+        "constraints" => struct_fields::<MuhurtaConstraints>(),
+        "progression" => struct_fields::<ProgressionOptions>(),
+        "events" => struct_fields::<LifeEvent>(),
+        _ => &[],
+    }
+}
+
+/// Field names a derived `Deserialize` struct accepts
+fn struct_fields<T: DeserializeOwned>() -> &'static [&'static str] {
+    struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+    impl<'de> Deserializer<'de> for FieldNames<'_> {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("not a struct"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            *self.0 = fields;
+            Err(serde::de::Error::custom("field names collected"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+            option unit unit_struct newtype_struct seq tuple tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut fields: &'static [&'static str] = &[];
+    let _ = T::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// Field path as used in messages, e.g. `subjects[0]` -> `Subject[0]`, `layer_config.natal.location` -> `Layer 'natal'.location`
+fn display_path(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix("subjects") {
+        return format!("Subject{}", rest);
+    }
+    match path.strip_prefix("layer_config.").and_then(|rest| rest.rsplit_once('.')) {
+        Some((layer_id, field)) => format!("Layer '{}'.{}", layer_id, field),
+        None => path.to_string(),
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn details(result: Result<(), ApiError>) -> Vec<(String, FieldErrorCode)> {
+        match result {
+            Err(ApiError::InvalidFields(errors)) => errors.into_iter().map(|e| (e.field, e.code)).collect(),
+            other => panic!("expected field errors, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reports_every_invalid_field() {
+        let request: RenderRequest = serde_json::from_value(json!({
+            "subjects": [
+                { "id": "a", "label": "A", "birthDateTime": "not a date", "location": { "lat": 91.0, "lon": 0.0 } },
+                { "id": "a", "label": "A2" }
+            ],
+            "settings": {
+                "houseSystem": "placidu",
+                "includeObjects": ["sun", "vulcan"],
+                "orbSettings": { "conjunction": 8.0, "opposition": 8.0, "trine": 45.0, "square": 6.0, "sextile": 4.0 },
+                "objectOrbs": { "sun": 15.0, "vulcan": 5.0, "moon": -1.0 },
+                "aspectFilter": {
+                    "aspectTypes": ["trine", "quintile"],
+                    "excludePairs": [["north_node", "south_node"], ["sun", "vertex"]]
+                }
+            },
+            "layer_config": {
+                "natal": { "kind": "natal", "subjectId": "b" },
+                "transit": { "kind": "transit" }
+            }
+        }))
+        .unwrap();
+
+        let fields = details(RequestValidator::validate_request(&request));
+        assert_eq!(
+            fields,
+            vec![
+                ("subjects[0].birthDateTime".to_string(), FieldErrorCode::InvalidFormat),
+                ("subjects[0].location.lat".to_string(), FieldErrorCode::OutOfRange),
+                ("subjects[1].id".to_string(), FieldErrorCode::Duplicate),
+                ("settings.houseSystem".to_string(), FieldErrorCode::InvalidValue),
+                ("settings.orbSettings.trine".to_string(), FieldErrorCode::OutOfRange),
+                ("settings.objectOrbs.moon".to_string(), FieldErrorCode::OutOfRange),
+                ("settings.objectOrbs.vulcan".to_string(), FieldErrorCode::InvalidValue),
+                ("settings.aspectFilter.aspectTypes[1]".to_string(), FieldErrorCode::InvalidValue),
+                ("settings.aspectFilter.excludePairs[1][1]".to_string(), FieldErrorCode::InvalidValue),
+                ("settings.includeObjects[1]".to_string(), FieldErrorCode::InvalidValue),
+                ("layer_config.natal.subjectId".to_string(), FieldErrorCode::UnknownReference),
+                ("layer_config.transit.explicitDateTime".to_string(), FieldErrorCode::Required),
+            ]
+        );
+    }
+
+    async fn extract(body: serde_json::Value) -> Result<ValidatedJson<RenderRequest>, Response> {
+        let request = Request::builder()
+            .method("POST")
+            .header("content-type", "application/json")
+            .body(axum::body::Body::from(body.to_string()))
+            .unwrap();
+        ValidatedJson::<RenderRequest>::from_request(request, &ValidationOptions::default()).await
+    }
+
+    #[tokio::test]
+    async fn test_validated_json_rejects_invalid_request() {
+        let rejection = extract(json!({
+            "subjects": [{ "id": "a", "label": "A", "location": { "lat": 100.0, "lon": 0.0 } }],
+            "settings": {},
+            "layer_config": { "natal": { "kind": "natal", "subjectId": "a" } }
+        }))
+        .await
+        .err()
+        .unwrap();
+        assert_eq!(rejection.status(), axum::http::StatusCode::BAD_REQUEST);
+
+        let rejection = extract(json!({ "subjects": "not a list" })).await.err().unwrap();
+        assert_eq!(rejection.status(), axum::http::StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_validated_json_accepts_valid_request() {
+        let ValidatedJson(request) = extract(json!({
+            "subjects": [{ "id": "a", "label": "A", "birthDateTime": "1990-06-15T14:30:00Z" }],
+            "settings": {},
+            "layer_config": { "natal": { "kind": "natal", "subjectId": "a" } }
+        }))
+        .await
+        .ok()
+        .unwrap();
+        assert_eq!(request.subjects[0].id, "a");
+    }
+
+    fn render_body() -> serde_json::Value {
+        json!({
+            "subjects": [{ "id": "a", "label": "A", "nmae": "typo" }],
+            "settings": { "houseSytem": "koch" },
+            "layer_config": { "natal": { "kind": "natal", "subjectId": "a", "subjectID": "a" } },
+            "settings_override": { "zodiacTyp": "sidereal", "orbSettings": { "trin": 5.0 } }
+        })
+    }
+
+    #[test]
+    fn test_strict_rejects_unknown_fields() {
+        let strict = ValidationOptions { strict: true };
+        let errors = match parse_request::<RenderRequest>(render_body(), strict) {
+            Err(ApiError::InvalidFields(errors)) => errors,
+            other => panic!("expected field errors, got {:?}", other.map(|_| ())),
+        };
+        let fields: Vec<_> = errors.iter().map(|e| e.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            vec![
+                "layer_config.natal.subjectID",
+                "settings.houseSytem",
+                "subjects[0].nmae",
+                "settings_override.zodiacTyp",
+                "settings_override.orbSettings.trin",
+            ]
+        );
+        assert!(errors.iter().all(|e| e.code == FieldErrorCode::UnknownField));
+        assert!(errors[0].message.contains("subjectId"));
+        assert!(errors[1].message.contains("houseSystem"));
+        assert!(errors[2].message.contains("label"));
+        assert!(errors[3].message.contains("zodiacType"));
+        assert!(errors[4].message.contains("trine"));
+    }
+
+    #[test]
+    fn test_lenient_ignores_unknown_fields() {
+        let lenient = ValidationOptions { strict: false };
+        let request = parse_request::<RenderRequest>(render_body(), lenient).unwrap();
+        assert_eq!(request.settings.house_system, "placidus");
+    }
+
+    #[test]
+    fn test_undeserializable_values_name_their_field() {
+        let options = ValidationOptions { strict: true };
+        let mut body = json!({
+            "subjects": [{ "id": "a", "label": "A" }],
+            "settings": { "vedicConfig": { "dasha_node_type": "smoothed" } },
+            "layer_config": { "natal": { "kind": "natal", "subjectId": "a" } }
+        });
+        let field = |body: serde_json::Value| match parse_request::<RenderRequest>(body, options) {
+            Err(ApiError::InvalidFields(errors)) => (errors[0].field.clone(), errors[0].code),
+            other => panic!("expected field errors, got {:?}", other.map(|_| ())),
+        };
+        assert_eq!(
+            field(body.clone()),
+            ("settings.vedicConfig.dasha_node_type".to_string(), FieldErrorCode::InvalidValue)
+        );
+
+        body["settings"] = json!({});
+        body["subjects"][0].as_object_mut().unwrap().remove("id");
+        assert_eq!(field(body), ("subjects[0].id".to_string(), FieldErrorCode::Required));
+    }
+
+    #[test]
+    fn test_layout_limits() {
+        let mut request: RenderRequest = serde_json::from_value(json!({
+            "subjects": [{ "id": "a", "label": "A", "birthDateTime": "1990-01-01T12:00:00Z", "location": { "lat": 40.0, "lon": -74.0 } }],
+            "settings": {},
+            "layer_config": { "natal": { "kind": "natal", "subjectId": "a" } },
+            "layout": { "width": 50, "height": 600, "margin": 300, "rotate": "ascendant" }
+        }))
+        .unwrap();
+
+        let fields = details(RequestValidator::validate_request(&request));
+        assert_eq!(
+            fields,
+            vec![
+                ("layout.width".to_string(), FieldErrorCode::OutOfRange),
+                ("layout.margin".to_string(), FieldErrorCode::OutOfRange),
+            ]
+        );
+
+        request.layout = serde_json::from_value(json!({ "width": 1200, "margin": 40 })).unwrap();
+        request.custom_points = serde_json::from_value(json!({
+            "parts": [{ "id": "fortune", "label": "⊗", "lon": 123.4 }, { "id": " ", "label": "?", "lon": 0.0 }]
+        }))
+        .unwrap();
+        assert_eq!(
+            details(RequestValidator::validate_request(&request)),
+            vec![("customPoints.parts[1].id".to_string(), FieldErrorCode::Required)]
+        );
+        request.custom_points.clear();
+        assert!(RequestValidator::validate_request(&request).is_ok());
+        assert_eq!(request.layout.unwrap().height, 800.0);
+    }
+
+    #[test]
+    fn test_muhurta_field_paths() {
+        let request: MuhurtaRequest = serde_json::from_value(json!({
+            "start": "2024-03-18T00:00:00Z",
+            "end": "2024-03-17T00:00:00Z",
+            "location": { "lat": 28.6, "lon": 200.0 },
+            "stepMinutes": 1,
+            "constraints": { "tithis": [2, 31], "requireTarabala": true }
+        }))
+        .unwrap();
+
+        let fields = details(RequestValidator::validate_muhurta_request(&request));
+        assert_eq!(
+            fields,
+            vec![
+                ("end".to_string(), FieldErrorCode::Conflict),
+                ("stepMinutes".to_string(), FieldErrorCode::OutOfRange),
+                ("location.lon".to_string(), FieldErrorCode::OutOfRange),
+                ("constraints.tithis[1]".to_string(), FieldErrorCode::OutOfRange),
+                ("subject".to_string(), FieldErrorCode::Required),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transit_timeline_field_paths() {
+        let request: TransitTimelineRequest = serde_json::from_value(json!({
+            "subject": { "id": "a", "label": "A" },
+            "start": "2024-01-01T00:00:00Z",
+            "end": "2025-06-01T00:00:00Z",
+            "settings": { "houseSystem": "placidu" }
+        }))
+        .unwrap();
+
+        let fields = details(RequestValidator::validate_transit_timeline_request(&request));
+        assert_eq!(
+            fields,
+            vec![
+                ("subject.birthDateTime".to_string(), FieldErrorCode::Required),
+                ("end".to_string(), FieldErrorCode::OutOfRange),
+                ("settings.houseSystem".to_string(), FieldErrorCode::InvalidValue),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_positions_query_field_paths() {
+        let query = PositionsQuery {
+            datetime: "2024-06-01T00:00:00Z".to_string(),
+            lat: Some(40.0),
+            lon: Some(200.0),
+            objects: Some("sun, moon,,pluto".to_string()),
+            zodiac_type: "tropical".to_string(),
+            ayanamsa: None,
+            custom_ayanamsa: None,
+            format: None,
+        };
+        assert_eq!(query.object_ids(), vec!["sun", "moon", "pluto"]);
+        let fields = details(RequestValidator::validate_positions_query(&query));
+        assert_eq!(fields, vec![("lon".to_string(), FieldErrorCode::OutOfRange)]);
+
+        let query = PositionsQuery { lon: None, objects: None, ..query };
+        let fields = details(RequestValidator::validate_positions_query(&query));
+        assert_eq!(fields, vec![("lon".to_string(), FieldErrorCode::Required)]);
+
+        let query = PositionsQuery {
+            lat: None,
+            zodiac_type: "sidereal".to_string(),
+            ayanamsa: Some("custom".to_string()),
+            ..query
+        };
+        let fields = details(RequestValidator::validate_positions_query(&query));
+        assert_eq!(fields, vec![("customAyanamsa".to_string(), FieldErrorCode::Required)]);
+    }
+
+    #[test]
+    fn test_ephemeris_table_query_field_paths() {
+        let query: EphemerisTableQuery = serde_json::from_value(json!({
+            "from": "2024-01-01T00:00:00Z",
+            "to": "2024-02-01T00:00:00Z",
+            "objects": "sun,moon"
+        }))
+        .unwrap();
+        assert_eq!(query.step_duration(), Some(chrono::Duration::days(1)));
+        assert!(RequestValidator::validate_ephemeris_table_query(&query).is_ok());
+
+        for step in ["0d", "2w", "h", "-1h", "1é"] {
+            let query = EphemerisTableQuery { step: step.to_string(), ..query.clone() };
+            let fields = details(RequestValidator::validate_ephemeris_table_query(&query));
+            assert_eq!(fields, vec![("step".to_string(), FieldErrorCode::InvalidFormat)], "{}", step);
+        }
+
+        let query = EphemerisTableQuery {
+            to: "2040-01-01T00:00:00Z".to_string(),
+            objects: Some("sun,vulcan".to_string()),
+            ..query
+        };
+        let fields = details(RequestValidator::validate_ephemeris_table_query(&query));
+        assert_eq!(
+            fields,
+            vec![
+                ("to".to_string(), FieldErrorCode::OutOfRange),
+                ("objects".to_string(), FieldErrorCode::InvalidValue),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ephemeris_download_field_paths() {
+        assert!(RequestValidator::validate_ephemeris_download(&EphemerisDownloadRequest::default()).is_ok());
+        let request = EphemerisDownloadRequest {
+            files: Some(vec!["sepl_18.se1".to_string(), "../../etc/passwd".to_string()]),
+        };
+        let fields = details(RequestValidator::validate_ephemeris_download(&request));
+        assert_eq!(fields, vec![("files[1]".to_string(), FieldErrorCode::InvalidValue)]);
+    }
+
+    #[test]
+    fn test_interpretation_field_paths() {
+        let request: InterpretationRequest = serde_json::from_value(json!({
+            "subject": { "id": "a", "label": "A", "location": { "lat": 0.0, "lon": 0.0 } },
+            "pack": "",
+            "at": "tomorrow"
+        }))
+        .unwrap();
+        assert_eq!(request.locale, "en");
+        let fields = details(RequestValidator::validate_interpretation_request(&request));
+        assert_eq!(
+            fields,
+            vec![
+                ("subject.birthDateTime".to_string(), FieldErrorCode::Required),
+                ("pack".to_string(), FieldErrorCode::Required),
+                ("at".to_string(), FieldErrorCode::InvalidFormat),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dasha_window_field_paths() {
+        let mut settings = ChartSettings::default();
+        let vedic_config = |window: serde_json::Value| serde_json::from_value(window).unwrap();
+        settings.vedic_config = Some(vedic_config(json!({
+            "dashas_from": "2024-01-01T00:00:00Z",
+            "dashas_to": "2025-01-01T00:00:00Z"
+        })));
+        assert!(RequestValidator::validate_settings(&settings).is_ok());
+
+        settings.vedic_config = Some(vedic_config(json!({
+            "dashas_from": "2025-01-01T00:00:00Z",
+            "dashas_to": "2024-01-01T00:00:00Z"
+        })));
+        let fields = details(RequestValidator::validate_settings(&settings));
+        assert_eq!(fields, vec![("settings.vedicConfig.dashas_to".to_string(), FieldErrorCode::InvalidValue)]);
+
+        settings.vedic_config = Some(vedic_config(json!({ "dashas_from": "soon" })));
+        let fields = details(RequestValidator::validate_settings(&settings));
+        assert_eq!(fields, vec![("settings.vedicConfig.dashas_from".to_string(), FieldErrorCode::InvalidFormat)]);
+    }
+
+    #[test]
+    fn test_wheel_check_field_paths() {
+        let request: WheelValidateRequest = serde_json::from_value(json!({
+            "wheel": [],
+            "layerIds": ["natal", ""]
+        }))
+        .unwrap();
+        let fields = details(RequestValidator::validate_wheel_check(&request));
+        assert_eq!(
+            fields,
+            vec![
+                ("wheel".to_string(), FieldErrorCode::InvalidFormat),
+                ("layerIds[1]".to_string(), FieldErrorCode::Required),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_electional_field_paths() {
+        let request: ElectionalSearchRequest = serde_json::from_value(json!({
+            "start": "2024-03-18T00:00:00Z",
+            "end": "2024-03-19T00:00:00Z",
+            "location": { "lat": 95.0, "lon": 0.0 },
+            "stepMinutes": 500,
+            "constraints": ["moon not void", "vulcan above horizon", "moon in narnia"]
+        }))
+        .unwrap();
+
+        let errors = match RequestValidator::validate_electional_request(&request) {
+            Err(ApiError::InvalidFields(errors)) => errors,
+            other => panic!("expected field errors, got {:?}", other),
+        };
+        let fields: Vec<_> = errors.iter().map(|e| (e.field.as_str(), e.code)).collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("stepMinutes", FieldErrorCode::OutOfRange),
+                ("location.lat", FieldErrorCode::OutOfRange),
+                ("constraints[1]", FieldErrorCode::InvalidValue),
+                ("constraints[2]", FieldErrorCode::InvalidValue),
+            ]
+        );
+        assert_eq!(errors[2].message, "Unknown object 'vulcan' in 'vulcan above horizon'");
+    }
+
+    #[test]
+    fn test_progressed_layer_field_paths() {
+        let request: RenderRequest = serde_json::from_value(json!({
+            "subjects": [{ "id": "a", "label": "A", "birthDateTime": "1990-01-01T12:00:00Z" }],
+            "settings": {},
+            "layer_config": {
+                "converse": { "kind": "progressed", "subjectId": "a", "explicitDateTime": "2020-01-01T00:00:00Z",
+                              "progression": { "angles": "naibod", "converse": true } },
+                "progressed": { "kind": "progressed", "explicitDateTime": "2020-01-01T00:00:00Z" },
+                "transit": { "kind": "transit", "explicitDateTime": "2020-01-01T00:00:00Z",
+                             "progression": { "converse": true } }
+            }
+        }))
+        .unwrap();
+
+        let fields = details(RequestValidator::validate_request(&request));
+        assert_eq!(
+            fields,
+            vec![
+                ("layer_config.progressed.subjectId".to_string(), FieldErrorCode::Required),
+                ("layer_config.transit.progression".to_string(), FieldErrorCode::InvalidValue),
+            ]
+        );
+
+        let strict = ValidationOptions { strict: true };
+        let body = json!({
+            "subjects": [{ "id": "a", "label": "A", "birthDateTime": "1990-01-01T12:00:00Z" }],
+            "settings": {},
+            "layer_config": {
+                "progressed": { "kind": "progressed", "subjectId": "a", "explicitDateTime": "2020-01-01T00:00:00Z",
+                                "progression": { "angle": "naibod" } }
+            }
+        });
+        let errors = match parse_request::<RenderRequest>(body, strict) {
+            Err(ApiError::InvalidFields(errors)) => errors,
+            other => panic!("expected field errors, got {:?}", other.map(|_| ())),
+        };
+        assert_eq!(errors[0].field, "layer_config.progressed.progression.angle");
+        assert!(errors[0].message.contains("angles, converse"), "{}", errors[0].message);
+    }
+
+    #[test]
+    fn test_rectification_field_paths() {
+        let request: RectificationRequest = serde_json::from_value(json!({
+            "windowStart": "1990-01-01T00:00:00Z",
+            "windowEnd": "1990-01-04T00:00:00Z",
+            "location": { "lat": 40.0, "lon": -74.0 },
+            "stepMinutes": 0,
+            "events": [
+                { "date": "2012-06-20T00:00:00Z" },
+                { "date": "1990-01-02T00:00:00Z" },
+                { "date": "someday" }
+            ],
+            "limit": 50
+        }))
+        .unwrap();
+
+        let fields = details(RequestValidator::validate_rectification_request(&request));
+        assert_eq!(
+            fields,
+            vec![
+                ("windowEnd".to_string(), FieldErrorCode::OutOfRange),
+                ("stepMinutes".to_string(), FieldErrorCode::OutOfRange),
+                ("events[1].date".to_string(), FieldErrorCode::Conflict),
+                ("events[2].date".to_string(), FieldErrorCode::InvalidFormat),
+                ("limit".to_string(), FieldErrorCode::OutOfRange),
+            ]
+        );
+    }
+}
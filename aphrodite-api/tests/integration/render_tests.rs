@@ -3,12 +3,18 @@ use aphrodite_api::routes;
 use axum_test::TestServer;
 use serde_json::json;
 
-/// Create a test server with a minimal configuration
-fn create_test_server() -> TestServer {
-    // Set environment variables for test configuration
+/// Set environment variables for a minimal test configuration
+fn configure_test_env() {
     std::env::set_var("SWISS_EPHEMERIS_PATH", "/usr/local/share/swisseph");
     std::env::set_var("SERVICE_POOL_SIZE", "2");
     std::env::set_var("CACHE_SIZE", "100");
+    std::env::set_var("ADMIN_TOKEN", "test-admin-token");
+    std::env::set_var("API_V2_ENABLED", "true");
+}
+
+/// Create a test server with a minimal configuration
+fn create_test_server() -> TestServer {
+    configure_test_env();
     
     let app = routes::create_router();
     TestServer::new(app).unwrap()
@@ -131,6 +137,38 @@ async fn test_health_endpoint() {
     assert_eq!(body["version"], "0.1.0");
 }
 
+#[tokio::test]
+async fn test_readiness_endpoint_structure() {
+    let server = create_test_server();
+
+    let response = server.get("/health/ready").await;
+    let body: serde_json::Value = response.json();
+    assert!(body["status"] == "ready" || body["status"] == "not_ready");
+    assert!(
+        body["ephemeris"]["backend"] == "swiss_ephemeris" || body["ephemeris"]["backend"] == "moshier",
+        "{}",
+        body["ephemeris"]["backend"]
+    );
+    assert_eq!(body["pool"]["size"], 2);
+    assert_eq!(body["storageBackend"], "memory");
+    assert_eq!(body["cacheBackend"], "memory");
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_readiness_endpoint_ready() {
+    let server = create_test_server();
+
+    let response = server.get("/health/ready").await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["ephemeris"]["ok"], true);
+    assert_eq!(body["ephemeris"]["backend"], "swiss_ephemeris");
+    assert_eq!(body["ephemeris"]["path"], "/usr/local/share/swisseph");
+    assert_eq!(body["pool"]["available"], true);
+}
+
 #[tokio::test]
 async fn test_health_endpoint_structure() {
     let server = create_test_server();
@@ -238,6 +276,33 @@ async fn test_render_endpoint_response_structure() {
     assert_eq!(body["settings"]["houseSystem"], "placidus");
 }
 
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_render_endpoint_include_aspects() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    
+    // Aspects are omitted unless requested
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert!(body.get("aspects").is_none());
+    
+    request["settings"]["includeAspects"] = json!(true);
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert!(body["aspects"]["natal"].is_object());
+    assert_eq!(body["aspects"]["natal"]["kind"], "intra_layer");
+    assert!(body["aspects"]["natal"]["pairs"].is_array());
+}
+
 #[tokio::test]
 #[ignore] // Requires Swiss Ephemeris files
 async fn test_render_endpoint_all_planets() {
@@ -328,6 +393,12 @@ async fn test_render_endpoint_tropical_vs_sidereal() {
     response_sidereal.assert_status_ok();
     let body_sidereal: serde_json::Value = response_sidereal.json();
     assert_eq!(body_sidereal["settings"]["zodiacType"], "sidereal");
+
+    // Only sidereal layers report the applied ayanamsa (Lahiri is ~23.7 in 1990)
+    assert!(body_tropical["layers"]["natal"].get("ayanamsa").is_none());
+    assert_eq!(body_sidereal["layers"]["natal"]["ayanamsa"]["name"], "lahiri");
+    let ayanamsa = body_sidereal["layers"]["natal"]["ayanamsa"]["value"].as_f64().unwrap();
+    assert!((23.0..24.5).contains(&ayanamsa), "ayanamsa {}", ayanamsa);
     
     // Positions should be different between tropical and sidereal
     let sun_tropical = body_tropical["layers"]["natal"]["positions"]["planets"]["sun"]["lon"].as_f64().unwrap();
@@ -577,6 +648,25 @@ async fn test_render_endpoint_validation_error_invalid_zodiac_type() {
     }
 }
 
+#[tokio::test]
+async fn test_render_endpoint_validation_error_invalid_decan_system() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["decanSystem"] = json!("invalid_decans");
+    
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+    
+    response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert!(body["error"]["message"].as_str().unwrap().contains("decanSystem"));
+    assert_eq!(body["error"]["details"][0]["field"], "settings.decanSystem");
+    assert_eq!(body["error"]["details"][0]["code"], "INVALID_VALUE");
+}
+
 #[tokio::test]
 async fn test_render_endpoint_validation_error_invalid_ayanamsa() {
     let server = create_test_server();
@@ -600,6 +690,60 @@ async fn test_render_endpoint_validation_error_invalid_ayanamsa() {
     }
 }
 
+#[tokio::test]
+async fn test_render_endpoint_validation_error_custom_ayanamsa_without_value() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("custom");
+
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+
+    assert!(response.status_code().is_client_error() || response.status_code().is_server_error());
+
+    if response.status_code().is_client_error() {
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+        assert!(body["error"]["message"].as_str().unwrap().contains("customAyanamsa"));
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_render_endpoint_custom_ayanamsa() {
+    let server = create_test_server();
+
+    let response_tropical = server
+        .post("/api/v1/render")
+        .json(&create_valid_request())
+        .await;
+    response_tropical.assert_status_ok();
+    let body_tropical: serde_json::Value = response_tropical.json();
+
+    let mut request = create_valid_request();
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("custom");
+    request["settings"]["customAyanamsa"] = json!(24.0);
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+
+    // The custom value is given at J2000; precession moves it by well under a degree
+    let sun_tropical = body_tropical["layers"]["natal"]["positions"]["planets"]["sun"]["lon"].as_f64().unwrap();
+    let sun_sidereal = body["layers"]["natal"]["positions"]["planets"]["sun"]["lon"].as_f64().unwrap();
+    let offset = (sun_tropical - sun_sidereal).rem_euclid(360.0);
+    assert!((offset - 24.0).abs() < 0.5, "offset {}", offset);
+    assert_eq!(body["layers"]["natal"]["ayanamsa"]["name"], "custom");
+    let ayanamsa = body["layers"]["natal"]["ayanamsa"]["value"].as_f64().unwrap();
+    assert!((ayanamsa - offset).abs() < 0.01);
+}
+
 #[tokio::test]
 async fn test_render_endpoint_validation_error_invalid_coordinates_latitude() {
     let server = create_test_server();
@@ -644,6 +788,32 @@ async fn test_render_endpoint_validation_error_invalid_coordinates_longitude() {
     }
 }
 
+#[tokio::test]
+async fn test_render_endpoint_validation_error_field_details() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["subjects"][0]["location"]["lat"] = json!(100.0);
+    request["settings"]["houseSystem"] = json!("invalid");
+
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+
+    response.assert_status_bad_request();
+
+    // Every invalid field is reported, not just the first
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    let details = body["error"]["details"].as_array().unwrap();
+    assert_eq!(details.len(), 2);
+    assert_eq!(details[0]["field"], "subjects[0].location.lat");
+    assert_eq!(details[0]["code"], "OUT_OF_RANGE");
+    assert!(details[0]["message"].as_str().unwrap().contains("latitude"));
+    assert_eq!(details[1]["field"], "settings.houseSystem");
+    assert_eq!(details[1]["code"], "INVALID_VALUE");
+}
+
 #[tokio::test]
 async fn test_render_endpoint_validation_error_invalid_coordinates_nan() {
     let server = create_test_server();
@@ -944,6 +1114,86 @@ async fn test_render_endpoint_validation_error_progressed_missing_datetime() {
     }
 }
 
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_render_endpoint_with_phenomena() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["includePhenomena"] = json!(true);
+
+    let response = server.post("/api/v1/render").json(&request).await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let phenomena = &body["layers"]["natal"]["phenomena"];
+    for planet in ["sun", "moon", "mercury", "venus", "mars"] {
+        assert!(phenomena[planet]["magnitude"].is_number(), "{}", planet);
+    }
+    let elongation = phenomena["venus"]["elongation"].as_f64().unwrap();
+    assert!(elongation <= 48.0);
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_render_endpoint_polar_house_fallback() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["subjects"][0]["location"] = json!({ "lat": 69.65, "lon": 18.96 });
+    request["settings"]["polarFallback"] = json!("whole_sign");
+
+    let response = server.post("/api/v1/render").json(&request).await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let natal = &body["layers"]["natal"];
+    assert_eq!(natal["positions"]["houses"]["system"], "whole_sign");
+    assert_eq!(natal["houseSystemFallback"], json!({ "requested": "placidus", "used": "whole_sign" }));
+}
+
+#[tokio::test]
+async fn test_render_endpoint_validation_error_progression_on_transit() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["layer_config"]["transit"] = json!({
+        "kind": "transit",
+        "explicitDateTime": "2024-01-01T00:00:00Z",
+        "progression": { "converse": true }
+    });
+
+    let response = server.post("/api/v1/render").json(&request).await;
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(body["error"]["details"][0]["field"], "layer_config.transit.progression");
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_render_endpoint_progressed_layer() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["layer_config"]["progressed"] = json!({
+        "kind": "progressed",
+        "subjectId": "test_person",
+        "explicitDateTime": "2020-01-01T12:00:00Z",
+        "progression": { "angles": "naibod", "converse": true }
+    });
+
+    let response = server.post("/api/v1/render").json(&request).await;
+
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let natal = &body["layers"]["natal"]["positions"];
+    let progressed = &body["layers"]["progressed"]["positions"];
+    assert_eq!(body["layers"]["progressed"]["kind"], "progressed");
+    // Thirty years of converse Naibod motion take about 29.6 degrees off the Midheaven
+    let natal_mc = natal["houses"]["angles"]["mc"].as_f64().unwrap();
+    let progressed_mc = progressed["houses"]["angles"]["mc"].as_f64().unwrap();
+    let arc = (natal_mc - progressed_mc).rem_euclid(360.0);
+    assert!((arc - 29.57).abs() < 0.05, "arc {}", arc);
+    assert!(progressed["planets"]["sun"]["lon"].as_f64() < natal["planets"]["sun"]["lon"].as_f64());
+}
+
 #[tokio::test]
 async fn test_render_endpoint_validation_error_invalid_planet_name() {
     let server = create_test_server();
@@ -1278,6 +1528,27 @@ async fn test_chartspec_endpoint_multiple_layers() {
     assert!(body["ephemeris"]["layers"]["transit"].is_object());
 }
 
+#[tokio::test]
+async fn test_chartspec_wheel_preset() {
+    let server = create_rate_limited_test_server();
+    let mut request = create_transit_request();
+
+    let response = server.post("/api/v1/render/chartspec").json(&request).await;
+    response.assert_status_ok();
+    assert!(!response.text().contains("ring_transits"));
+
+    request["wheel"] = json!("natal_transit");
+    let response = server.post("/api/v1/render/chartspec").json(&request).await;
+    response.assert_status_ok();
+    assert!(response.text().contains("ring_transits/planet/sun"));
+
+    request["wheel"] = json!("biwheel");
+    let response = server.post("/api/v1/render/chartspec").json(&request).await;
+    response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["details"][0]["field"], "wheel");
+}
+
 // ============================================================================
 // Error Response Structure Tests
 // ============================================================================
@@ -1511,3 +1782,1264 @@ async fn test_full_workflow_composite_chart() {
     assert!(chartspec_body["ephemeris"]["layers"]["natal1"].is_object());
     assert!(chartspec_body["ephemeris"]["layers"]["natal2"].is_object());
 }
+
+/// Create a synastry request from the multi-subject fixture
+fn create_synastry_request() -> serde_json::Value {
+    let request = create_multi_subject_request();
+    json!({
+        "subjects": request["subjects"],
+        "settings": request["settings"],
+        "includeChartSpec": true
+    })
+}
+
+#[tokio::test]
+async fn test_synastry_endpoint_requires_two_subjects() {
+    let server = create_test_server();
+    let mut request = create_synastry_request();
+    request["subjects"].as_array_mut().unwrap().pop();
+    
+    let response = server
+        .post("/api/v1/synastry")
+        .json(&request)
+        .await;
+    
+    // Should return 400 for validation error, or 500 if JSON parsing fails first
+    assert!(response.status_code().is_client_error() || response.status_code().is_server_error());
+    
+    if response.status_code().is_client_error() {
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+        assert!(body["error"]["message"].as_str().unwrap().contains("two subjects"));
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_synastry_endpoint_success() {
+    let server = create_test_server();
+    let request = create_synastry_request();
+    
+    let response = server
+        .post("/api/v1/synastry")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["report"]["layerIds"], json!(["person1", "person2"]));
+    assert_eq!(body["report"]["aspects"]["id"], "person1:person2");
+    assert!(!body["report"]["houseOverlays"].as_array().unwrap().is_empty());
+    
+    let score = body["report"]["compatibility"]["score"].as_f64().unwrap();
+    assert!((0.0..=100.0).contains(&score));
+    
+    assert!(body["ephemeris"]["layers"]["person1"].is_object());
+    assert!(body["ephemeris"]["layers"]["person2"].is_object());
+    assert!(body["spec"]["shapes"].is_array());
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_render_endpoint_ashtakavarga() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("lahiri");
+    request["settings"]["includeObjects"] =
+        json!(["sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn"]);
+    request["settings"]["vedicConfig"] = json!({ "include_ashtakavarga": true });
+
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+
+    let ashtakavarga = &body["vedic"]["ashtakavarga"];
+    assert_eq!(ashtakavarga["layerId"], "natal");
+    assert_eq!(ashtakavarga["total"], 337);
+    assert_eq!(ashtakavarga["sarvashtakavarga"].as_array().unwrap().len(), 12);
+    assert_eq!(ashtakavarga["bhinnashtakavarga"]["jupiter"]["total"], 56);
+}
+
+#[tokio::test]
+async fn test_render_endpoint_dasha_node_type() {
+    let server = create_rate_limited_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("lahiri");
+    request["settings"]["includeObjects"] =
+        json!(["sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn", "north_node", "south_node"]);
+    request["settings"]["vedicConfig"] =
+        json!({ "include_dashas": true, "dasha_systems": ["chara"], "dashas_depth": "mahadasha" });
+
+    let response = server.post("/api/v1/render").json(&request).await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["vedic"]["dashas"]["nodeType"], "true");
+
+    request["settings"]["vedicConfig"]["dasha_node_type"] = json!("mean");
+    let response = server.post("/api/v1/render").json(&request).await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["vedic"]["dashas"]["nodeType"], "mean");
+    assert_eq!(body["vedic"]["dashas"]["periods"].as_array().unwrap().len(), 12);
+    // The chart itself keeps the true node
+    assert_eq!(body["settings"]["nodeType"], "true");
+
+    request["settings"]["vedicConfig"]["dasha_node_type"] = json!("smoothed");
+    let response = server.post("/api/v1/render").json(&request).await;
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(body["error"]["details"][0]["field"], "settings.vedicConfig.dasha_node_type");
+    assert_eq!(body["error"]["details"][0]["code"], "INVALID_VALUE");
+}
+
+#[tokio::test]
+async fn test_render_endpoint_chara_dasha_without_nodes() {
+    let server = create_rate_limited_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("lahiri");
+    request["settings"]["includeObjects"] =
+        json!(["sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn", "north_node", "south_node"]);
+    request["settings"]["vedicConfig"] =
+        json!({ "include_dashas": true, "dasha_systems": ["chara"], "dashas_depth": "mahadasha" });
+
+    let response = server.post("/api/v1/render").json(&request).await;
+    response.assert_status_ok();
+    let with_nodes: serde_json::Value = response.json();
+
+    // Scorpio and Aquarius are co-ruled by the nodes, so they are calculated for the dashas
+    request["settings"]["includeObjects"] = json!(["sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn"]);
+    let response = server.post("/api/v1/render").json(&request).await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["vedic"]["dashas"]["periods"], with_nodes["vedic"]["dashas"]["periods"]);
+    assert!(body["layers"]["natal"]["positions"]["planets"].get("north_node").is_none());
+}
+
+#[tokio::test]
+async fn test_render_endpoint_dasha_window() {
+    let server = create_rate_limited_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("lahiri");
+    request["settings"]["vedicConfig"] = json!({
+        "include_dashas": true,
+        "dashas_depth": "antardasha",
+        "dashas_from": "2024-01-01T00:00:00Z",
+        "dashas_to": "2025-01-01T00:00:00Z"
+    });
+
+    let response = server.post("/api/v1/render").json(&request).await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let periods = body["vedic"]["dashas"]["periods"].as_array().unwrap();
+    assert!(!periods.is_empty() && periods.len() < 9, "{} mahadashas", periods.len());
+    for period in periods {
+        assert!(period["end"].as_str().unwrap() > "2024-01-01" && period["start"].as_str().unwrap() < "2025-01-01");
+        assert!(period["children"].as_array().unwrap().len() < 9);
+    }
+}
+
+#[tokio::test]
+async fn test_render_endpoint_validation_error_invalid_bhava_method() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["vedicConfig"] = json!({ "include_bhava_chalit": true, "bhava_method": "koch" });
+
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+    assert!(response.status_code().is_client_error() || response.status_code().is_server_error());
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_render_endpoint_bhava_chalit() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("lahiri");
+    request["settings"]["vedicConfig"] = json!({ "include_bhava_chalit": true });
+
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+
+    let bhava = &body["vedic"]["layers"]["natal"]["bhavaChalit"];
+    assert_eq!(bhava["method"], "sripati");
+    assert_eq!(bhava["madhya"].as_array().unwrap().len(), 12);
+    assert!(bhava["placements"]["sun"]["bhavaHouse"].is_number());
+}
+
+/// Create an electional search request over two days in London
+fn create_electional_request() -> serde_json::Value {
+    json!({
+        "start": "2024-03-18T00:00:00Z",
+        "end": "2024-03-20T00:00:00Z",
+        "location": { "lat": 51.5074, "lon": -0.1278 },
+        "stepMinutes": 60,
+        "constraints": ["moon not void", "venus above horizon", "no mars-saturn hard aspects"]
+    })
+}
+
+#[tokio::test]
+async fn test_electional_validation_error_unknown_constraint() {
+    let server = create_test_server();
+    let mut request = create_electional_request();
+    request["constraints"] = json!(["moon in narnia"]);
+
+    let response = server
+        .post("/api/v1/electional/search")
+        .json(&request)
+        .await;
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(body["error"]["details"][0]["field"], "constraints[0]");
+    assert_eq!(body["error"]["details"][0]["code"], "INVALID_VALUE");
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_electional_endpoint_success() {
+    let server = create_test_server();
+    let request = create_electional_request();
+
+    let response = server
+        .post("/api/v1/electional/search")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+
+    assert_eq!(body["stepMinutes"], 60);
+    assert_eq!(body["constraints"][0], json!({ "kind": "moon_void", "void": false }));
+    for window in body["windows"].as_array().unwrap() {
+        assert!(window["start"].as_str().unwrap() < window["end"].as_str().unwrap());
+    }
+}
+
+/// Create a transit timeline request over one week
+fn create_transit_timeline_request() -> serde_json::Value {
+    json!({
+        "subject": {
+            "id": "person",
+            "label": "Person",
+            "birthDateTime": "1990-01-01T12:00:00Z",
+            "location": { "lat": 40.7128, "lon": -74.0060 }
+        },
+        "start": "2024-06-01T00:00:00Z",
+        "end": "2024-06-08T00:00:00Z"
+    })
+}
+
+#[tokio::test]
+async fn test_transit_timeline_validation_error_range_too_long() {
+    let server = create_test_server();
+    let mut request = create_transit_timeline_request();
+    request["end"] = json!("2026-06-01T00:00:00Z");
+
+    let response = server
+        .post("/api/v1/transits/timeline")
+        .json(&request)
+        .await;
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(body["error"]["details"][0]["field"], "end");
+    assert_eq!(body["error"]["details"][0]["code"], "OUT_OF_RANGE");
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_transit_timeline_endpoint_success() {
+    let server = create_test_server();
+    let request = create_transit_timeline_request();
+
+    let response = server
+        .post("/api/v1/transits/timeline")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+
+    assert_eq!(body["subjectId"], "person");
+    assert_eq!(body["timeline"]["dates"].as_array().unwrap().len(), 7);
+    let rows = body["timeline"]["rows"].as_array().unwrap();
+    assert!(!rows.is_empty());
+    for row in rows {
+        let orbs = row["orbs"].as_array().unwrap();
+        assert_eq!(orbs.len(), 7);
+        assert!(orbs[row["peakIndex"].as_u64().unwrap() as usize].is_number());
+    }
+}
+
+#[tokio::test]
+async fn test_transit_timeline_stream_validation_error() {
+    let server = create_test_server();
+    let mut request = create_transit_timeline_request();
+    request["end"] = json!("2026-06-01T00:00:00Z");
+
+    let response = server
+        .post("/api/v1/transits/timeline/stream")
+        .json(&request)
+        .await;
+    response.assert_status_bad_request();
+    assert!(response.header("content-type").to_str().unwrap().starts_with("application/json"));
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(body["error"]["details"][0]["field"], "end");
+    assert_eq!(body["error"]["details"][0]["code"], "OUT_OF_RANGE");
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_transit_timeline_stream_matches_timeline() {
+    let server = create_test_server();
+    let request = create_transit_timeline_request();
+
+    let response = server
+        .post("/api/v1/transits/timeline")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let timeline = body["timeline"].clone();
+
+    let response = server
+        .post("/api/v1/transits/timeline/stream")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    assert!(response.header("content-type").to_str().unwrap().starts_with("text/event-stream"));
+
+    // Pair each `event:` line with the `data:` line that follows it
+    let text = response.text();
+    let mut events = Vec::new();
+    let mut name = "";
+    for line in text.lines() {
+        if let Some(event) = line.strip_prefix("event: ") {
+            name = event;
+        } else if let Some(data) = line.strip_prefix("data: ") {
+            events.push((name.to_string(), serde_json::from_str::<serde_json::Value>(data).unwrap()));
+        }
+    }
+
+    let (last, summary) = events.pop().unwrap();
+    assert_eq!(last, "done");
+    assert_eq!(summary, json!({ "subjectId": "person", "days": 7 }));
+    assert!(events.iter().all(|(name, _)| name == "day"));
+
+    // Every day's contacts are the matrix column for that date
+    let days: Vec<_> = events.into_iter().map(|(_, day)| day).collect();
+    let dates: Vec<_> = days.iter().map(|day| day["date"].clone()).collect();
+    assert_eq!(json!(dates), timeline["dates"]);
+    for (index, day) in days.iter().enumerate() {
+        let expected: Vec<_> = timeline["rows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|row| row["orbs"][index].is_number())
+            .map(|row| {
+                json!({
+                    "transitId": row["transitId"],
+                    "aspectType": row["aspectType"],
+                    "natalId": row["natalId"],
+                    "natalType": row["natalType"],
+                    "orb": row["orbs"][index],
+                })
+            })
+            .collect();
+        assert_eq!(day["contacts"], json!(expected));
+    }
+}
+
+/// Create a rectification request over a twelve hour window
+fn create_rectification_request() -> serde_json::Value {
+    json!({
+        "windowStart": "1990-01-01T06:00:00Z",
+        "windowEnd": "1990-01-01T18:00:00Z",
+        "location": { "lat": 40.7128, "lon": -74.0060 },
+        "stepMinutes": 10,
+        "events": [
+            { "date": "2012-06-20T00:00:00Z", "label": "Marriage" },
+            { "date": "2015-03-02T00:00:00Z" }
+        ],
+        "limit": 3
+    })
+}
+
+#[tokio::test]
+async fn test_rectification_validation_error_event_before_window() {
+    let server = create_test_server();
+    let mut request = create_rectification_request();
+    request["events"][1]["date"] = json!("1989-12-31T00:00:00Z");
+
+    let response = server
+        .post("/api/v1/rectification")
+        .json(&request)
+        .await;
+    assert!(response.status_code().is_client_error() || response.status_code().is_server_error());
+
+    if response.status_code().is_client_error() {
+        let body: serde_json::Value = response.json();
+        assert_eq!(body["error"]["details"][0]["field"], "events[1].date");
+    }
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_rectification_endpoint_success() {
+    let server = create_test_server();
+    let request = create_rectification_request();
+
+    let response = server
+        .post("/api/v1/rectification")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+
+    assert_eq!(body["candidatesScored"], 72);
+    let candidates = body["candidates"].as_array().unwrap();
+    assert_eq!(candidates.len(), 3);
+    let scores: Vec<f64> = candidates.iter().map(|c| c["score"].as_f64().unwrap()).collect();
+    assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+    for hit in candidates[0]["hits"].as_array().unwrap() {
+        assert!(hit["orb"].as_f64().unwrap() <= 1.0);
+        assert_ne!(hit["objectId"], "moon");
+    }
+}
+
+/// Create a muhurta search request over two days in Delhi
+fn create_muhurta_request() -> serde_json::Value {
+    json!({
+        "start": "2024-03-18T00:00:00Z",
+        "end": "2024-03-20T00:00:00Z",
+        "location": { "lat": 28.6139, "lon": 77.2090 },
+        "stepMinutes": 30,
+        "constraints": { "avoidRahuKala": true }
+    })
+}
+
+#[tokio::test]
+async fn test_muhurta_endpoint_validation_error_range_too_long() {
+    let server = create_test_server();
+    let mut request = create_muhurta_request();
+    request["end"] = json!("2024-06-01T00:00:00Z");
+
+    let response = server
+        .post("/api/v1/vedic/muhurta")
+        .json(&request)
+        .await;
+    assert!(response.status_code().is_client_error() || response.status_code().is_server_error());
+}
+
+#[tokio::test]
+async fn test_muhurta_endpoint_validation_error_invalid_tithi() {
+    let server = create_test_server();
+    let mut request = create_muhurta_request();
+    request["constraints"]["tithis"] = json!([31]);
+
+    let response = server
+        .post("/api/v1/vedic/muhurta")
+        .json(&request)
+        .await;
+    assert!(response.status_code().is_client_error() || response.status_code().is_server_error());
+}
+
+#[tokio::test]
+async fn test_muhurta_endpoint_validation_error_tarabala_without_subject() {
+    let server = create_test_server();
+    let mut request = create_muhurta_request();
+    request["constraints"]["requireTarabala"] = json!(true);
+
+    let response = server
+        .post("/api/v1/vedic/muhurta")
+        .json(&request)
+        .await;
+    assert!(response.status_code().is_client_error() || response.status_code().is_server_error());
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_muhurta_endpoint_success() {
+    let server = create_test_server();
+    let mut request = create_muhurta_request();
+    request["constraints"]["lagnas"] = json!(["leo", "scorpio", "aquarius", "taurus"]);
+
+    let response = server
+        .post("/api/v1/vedic/muhurta")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+
+    assert_eq!(body["ayanamsa"], "lahiri");
+    let windows = body["windows"].as_array().unwrap();
+    assert!(!windows.is_empty());
+    for window in windows {
+        assert!(["leo", "scorpio", "aquarius", "taurus"].contains(&window["lagna"].as_str().unwrap()));
+        assert!(window["panchanga"]["tithi"].is_number());
+        assert!(window["start"].as_str().unwrap() < window["end"].as_str().unwrap());
+    }
+}
+
+#[tokio::test]
+async fn test_muhurta_stream_validation_error() {
+    let server = create_test_server();
+    let mut request = create_muhurta_request();
+    request["constraints"]["tithis"] = json!([31]);
+
+    let response = server
+        .post("/api/v1/vedic/muhurta/stream")
+        .json(&request)
+        .await;
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    assert_eq!(body["error"]["details"][0]["field"], "constraints.tithis[0]");
+    assert_eq!(body["error"]["details"][0]["code"], "OUT_OF_RANGE");
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_muhurta_stream_matches_search() {
+    let server = create_test_server();
+    let mut request = create_muhurta_request();
+    request["constraints"]["lagnas"] = json!(["leo", "scorpio", "aquarius", "taurus"]);
+
+    let response = server
+        .post("/api/v1/vedic/muhurta")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let windows = body["windows"].as_array().unwrap().clone();
+
+    let response = server
+        .post("/api/v1/vedic/muhurta/stream")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    assert!(response.header("content-type").to_str().unwrap().starts_with("text/event-stream"));
+
+    // Pair each `event:` line with the `data:` line that follows it
+    let text = response.text();
+    let mut events = Vec::new();
+    let mut name = "";
+    for line in text.lines() {
+        if let Some(event) = line.strip_prefix("event: ") {
+            name = event;
+        } else if let Some(data) = line.strip_prefix("data: ") {
+            events.push((name.to_string(), serde_json::from_str::<serde_json::Value>(data).unwrap()));
+        }
+    }
+
+    let (last, summary) = events.pop().unwrap();
+    assert_eq!(last, "done");
+    assert_eq!(summary["windows"], windows.len());
+    assert_eq!(summary["ayanamsa"], "lahiri");
+    assert!(events.iter().all(|(name, _)| name == "window"));
+    let streamed: Vec<_> = events.into_iter().map(|(_, window)| window).collect();
+    assert_eq!(streamed, windows);
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_render_endpoint_avasthas() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("lahiri");
+    request["settings"]["vedicConfig"] = json!({ "include_avasthas": true });
+
+    let response = server
+        .post("/api/v1/render")
+        .json(&request)
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+
+    let sun = &body["vedic"]["layers"]["natal"]["avasthas"]["sun"];
+    assert!(sun["baladi"].is_string());
+    assert!(sun["jagradi"].is_string());
+    assert_eq!(sun["combust"], false);
+    assert!(sun["vargottama"].is_boolean());
+}
+
+#[tokio::test]
+async fn test_cache_stats() {
+    let server = create_test_server();
+
+    let response = server.get("/api/v1/cache/stats").await;
+    response.assert_status_ok();
+
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["backend"], "memory");
+    assert_eq!(body["capacity"], 100);
+    assert!(body["hits"].is_u64());
+    assert!(body["misses"].is_u64());
+    assert!(body["entries"].is_u64());
+    assert!(body["evictions"].is_u64());
+}
+
+#[tokio::test]
+async fn test_cache_clear_requires_admin_token() {
+    let server = create_test_server();
+
+    let response = server.post("/api/v1/cache/clear").await;
+    response.assert_status(axum::http::StatusCode::UNAUTHORIZED);
+
+    let response = server
+        .post("/api/v1/cache/clear")
+        .authorization_bearer("wrong-token")
+        .await;
+    response.assert_status(axum::http::StatusCode::UNAUTHORIZED);
+
+    let response = server
+        .post("/api/v1/cache/clear")
+        .authorization_bearer("test-admin-token")
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["cleared"], 0);
+}
+
+#[tokio::test]
+async fn test_admin_ephemeris_files() {
+    let server = create_test_server();
+
+    let response = server.get("/api/v1/admin/ephemeris").await;
+    response.assert_status(axum::http::StatusCode::UNAUTHORIZED);
+
+    let response = server
+        .get("/api/v1/admin/ephemeris")
+        .authorization_bearer("test-admin-token")
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["path"], "/usr/local/share/swisseph");
+    assert!(body["files"].is_array());
+    assert!(body["coverage"]["planets"].is_array());
+    assert!(body["missingStandardFiles"].is_array());
+    assert_eq!(body["downloadEnabled"], cfg!(feature = "ephemeris-download"));
+
+    let response = server
+        .post("/api/v1/admin/ephemeris/download")
+        .authorization_bearer("test-admin-token")
+        .json(&json!({ "files": ["sepl_18.se1", "passwd"] }))
+        .await;
+    response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["details"][0]["field"], "files[1]");
+}
+
+#[tokio::test]
+async fn test_admin_wheel_reload() {
+    let server = create_test_server();
+
+    let response = server.post("/api/v1/admin/wheel/reload").await;
+    response.assert_status(axum::http::StatusCode::UNAUTHORIZED);
+
+    let response = server
+        .post("/api/v1/admin/wheel/reload")
+        .authorization_bearer("test-admin-token")
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["path"], "wheels/default.json");
+    assert!(body["rings"].as_u64().unwrap() > 0);
+}
+
+#[tokio::test]
+async fn test_wheel_schema_and_validate() {
+    let server = create_test_server();
+
+    let response = server.get("/api/v1/wheels/schema").await;
+    response.assert_status_ok();
+    let schema: serde_json::Value = response.json();
+    assert_eq!(schema["required"], json!(["name", "rings"]));
+
+    let ring = |slug: &str, order: u32, inner: f64, outer: f64| {
+        json!({
+            "slug": slug, "type": "planets", "label": slug, "orderIndex": order,
+            "radiusInner": inner, "radiusOuter": outer,
+            "dataSource": { "kind": "layer_planets", "layerId": "natal" }
+        })
+    };
+    let response = server
+        .post("/api/v1/wheels/validate")
+        .json(&json!({
+            "wheel": { "name": "Overlap", "rings": [ring("outer", 0, 0.7, 1.0), ring("inner", 1, 0.5, 0.8)] },
+            "layerIds": ["transit"]
+        }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["valid"], true);
+    let codes: Vec<&str> = body["warnings"].as_array().unwrap().iter().map(|w| w["code"].as_str().unwrap()).collect();
+    assert_eq!(codes, vec!["OVERLAPPING_RADII", "DANGLING_LAYER_ID", "DANGLING_LAYER_ID"]);
+
+    let response = server
+        .post("/api/v1/wheels/validate")
+        .json(&json!({ "wheel": { "name": "Empty" } }))
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["valid"], false);
+    assert!(body["error"].as_str().unwrap().contains("rings"));
+}
+
+/// Test server on a real socket, needed for WebSocket upgrades
+fn create_ws_test_server() -> TestServer {
+    configure_test_env();
+    let app = routes::create_router();
+    TestServer::builder().http_transport().build(app).unwrap()
+}
+
+/// Live chart subscription for the default test subject
+fn create_live_subscription() -> serde_json::Value {
+    json!({
+        "type": "subscribe",
+        "subject": create_valid_request()["subjects"][0],
+        "settings": { "includeObjects": ["sun", "moon", "mercury", "venus", "mars"] },
+        "intervalSeconds": 5
+    })
+}
+
+#[tokio::test]
+async fn test_live_chart_rejects_invalid_subscription() {
+    let server = create_ws_test_server();
+    let mut socket = server.get_websocket("/api/v1/ws").await.into_websocket().await;
+
+    let mut subscription = create_live_subscription();
+    subscription["intervalSeconds"] = json!(1);
+    subscription["subject"]["birthDateTime"] = serde_json::Value::Null;
+    socket.send_json(&subscription).await;
+
+    let message: serde_json::Value = socket.receive_json().await;
+    assert_eq!(message["type"], "error");
+    assert_eq!(message["error"]["code"], "VALIDATION_ERROR");
+    let fields: Vec<_> = message["error"]["details"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|d| d["field"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(fields, vec!["subject.birthDateTime", "intervalSeconds"]);
+
+    socket.send_text("not json").await;
+    let message: serde_json::Value = socket.receive_json().await;
+    assert_eq!(message["error"]["code"], "VALIDATION_ERROR");
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_live_chart_pushes_transits() {
+    let server = create_ws_test_server();
+    let mut socket = server.get_websocket("/api/v1/ws").await.into_websocket().await;
+
+    socket.send_json(&create_live_subscription()).await;
+    let message: serde_json::Value = socket.receive_json().await;
+    assert_eq!(message["type"], "subscribed");
+    assert_eq!(message["subjectId"], "test_person");
+    assert_eq!(message["intervalSeconds"], 5);
+
+    // The first update is sent right after subscribing
+    let update: serde_json::Value = socket.receive_json().await;
+    assert_eq!(update["type"], "update");
+    assert!(update["dateTime"].is_string());
+    assert!(update["transits"]["planets"]["sun"]["lon"].is_number());
+    for aspect in update["aspects"].as_array().unwrap() {
+        assert_eq!(aspect["from"]["layer_id"], "transit");
+        assert_eq!(aspect["to"]["layer_id"], "natal");
+    }
+    assert!(update["newlyExact"].is_array());
+}
+
+// ============================================================================
+// API Versioning Tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_v1_responses_carry_api_version() {
+    let server = create_test_server();
+
+    let response = server.get("/api/v1/cache/stats").await;
+    response.assert_status_ok();
+    response.assert_header("api-version", "1");
+    assert!(response.maybe_header("deprecation").is_none());
+    assert!(response.maybe_header("sunset").is_none());
+}
+
+#[tokio::test]
+async fn test_v1_deprecation_headers() {
+    configure_test_env();
+    let mut config = aphrodite_api::config::Config::load().unwrap();
+    config.api_v1_sunset = Some("2027-06-30".to_string());
+    let server = TestServer::new(routes::create_router_with_config(config)).unwrap();
+
+    let response = server.get("/api/v1/cache/stats").await;
+    response.assert_status_ok();
+    response.assert_header("api-version", "1");
+    response.assert_header("deprecation", "true");
+    response.assert_header("sunset", "Wed, 30 Jun 2027 00:00:00 GMT");
+    response.assert_header("link", "</api/v2>; rel=\"successor-version\"");
+
+    // Unversioned routes are not deprecated
+    let response = server.get("/health").await;
+    assert!(response.maybe_header("deprecation").is_none());
+}
+
+#[tokio::test]
+async fn test_v2_routes_disabled_by_default() {
+    configure_test_env();
+    let mut config = aphrodite_api::config::Config::load().unwrap();
+    config.api_v2_enabled = false;
+    let server = TestServer::new(routes::create_router_with_config(config)).unwrap();
+
+    let response = server.post("/api/v2/render").json(&create_valid_request()).await;
+    response.assert_status_not_found();
+}
+
+#[tokio::test]
+async fn test_v2_render_validation_error() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["subjects"] = json!([]);
+
+    let response = server
+        .post("/api/v2/render")
+        .json(&request)
+        .await;
+    response.assert_status_bad_request();
+    response.assert_header("api-version", "2");
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    let details = body["error"]["details"].as_array().unwrap();
+    assert!(
+        details.iter().any(|d| d["field"] == "subjects" && d["code"] == "REQUIRED"),
+        "{:?}",
+        details
+    );
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_v2_render_nests_vedic_per_layer() {
+    let server = create_test_server();
+    let mut request = create_valid_request();
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("lahiri");
+    request["settings"]["includeObjects"] =
+        json!(["sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn"]);
+    request["settings"]["vedicConfig"] = json!({ "include_ashtakavarga": true, "include_bhava_chalit": true });
+
+    let response = server.post("/api/v2/render").json(&request).await;
+    response.assert_status_ok();
+    response.assert_header("api-version", "2");
+
+    let body: serde_json::Value = response.json();
+    let layer = &body["layers"]["natal"];
+    assert!(layer["positions"]["planets"]["sun"]["lon"].is_number());
+    assert_eq!(layer["vedic"]["bhavaChalit"]["method"], "sripati");
+    assert!(body["vedic"].get("layers").is_none());
+    assert_eq!(body["vedic"]["ashtakavarga"]["total"], 337);
+}
+
+/// Test server on a real socket with peer addresses, as the rate limiter keys on them
+fn create_rate_limited_test_server() -> TestServer {
+    configure_test_env();
+    let app = routes::create_router().into_make_service_with_connect_info::<std::net::SocketAddr>();
+    TestServer::builder().http_transport().build(app).unwrap()
+}
+
+#[tokio::test]
+async fn test_rate_limit_headers() {
+    let server = create_rate_limited_test_server();
+    let info: serde_json::Value = server.get("/").await.json();
+    assert_eq!(info["rateLimits"]["positions"], 120);
+    assert_eq!(info["rateLimits"]["rectification"], 10);
+
+    let response = server.get("/api/v1/positions").await;
+    response.assert_header("x-ratelimit-limit", "120");
+    let remaining: u32 = response.header("x-ratelimit-remaining").to_str().unwrap().parse().unwrap();
+    assert!(remaining < 120);
+}
+
+#[tokio::test]
+async fn test_rate_limit_error_envelope() {
+    let server = create_rate_limited_test_server();
+    // Rectification allows 10 requests a minute; the limiter runs before validation
+    for _ in 0..10 {
+        server.post("/api/v1/rectification").json(&json!({})).await.assert_status_bad_request();
+    }
+    let response = server.post("/api/v1/rectification").json(&json!({})).await;
+    response.assert_status(axum::http::StatusCode::TOO_MANY_REQUESTS);
+    response.assert_header("x-ratelimit-remaining", "0");
+    let retry_after = response.header("retry-after");
+
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "RATE_LIMIT_EXCEEDED");
+    assert!(body["error"]["correlation_id"].is_string());
+    assert_eq!(body["error"]["retry_after"].to_string(), retry_after.to_str().unwrap());
+}
+
+#[tokio::test]
+async fn test_interpretations_endpoint() {
+    let server = create_rate_limited_test_server();
+    let mut request = json!({
+        "subject": create_valid_request()["subjects"][0],
+        "settings": { "includeObjects": ["sun", "moon"] },
+        "pack": "basic",
+        "locale": "en-GB"
+    });
+
+    let response = server.post("/api/v1/interpretations").json(&request).await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["locale"], "en");
+    let first = &body["interpretations"][0];
+    assert_eq!(first["kind"], "planetInSign");
+    assert_eq!(first["key"], "sun.capricorn");
+    assert!(first["text"].as_str().unwrap().contains("Ambitious"));
+
+    request["pack"] = json!("missing");
+    let response = server.post("/api/v1/interpretations").json(&request).await;
+    response.assert_status(axum::http::StatusCode::NOT_FOUND);
+
+    request["at"] = json!("soon");
+    let response = server.post("/api/v1/interpretations").json(&request).await;
+    response.assert_status(axum::http::StatusCode::BAD_REQUEST);
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["details"][0]["field"], "at");
+}
+
+#[tokio::test]
+async fn test_positions_endpoint_validation_error() {
+    let server = create_rate_limited_test_server();
+    let response = server
+        .get("/api/v1/positions")
+        .add_query_param("datetime", "yesterday")
+        .add_query_param("lat", "95")
+        .add_query_param("objects", "sun,vulcan")
+        .await;
+    response.assert_status_bad_request();
+
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    let fields: Vec<&str> = body["error"]["details"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|detail| detail["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, vec!["datetime", "lon", "objects"]);
+
+    let response = server.get("/api/v1/positions").await;
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+
+    let response = server
+        .get("/api/v1/positions")
+        .add_query_param("datetime", "2024-06-01T00:00:00Z")
+        .add_query_param("format", "dms")
+        .await;
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_positions_endpoint() {
+    let server = create_rate_limited_test_server();
+    let response = server
+        .get("/api/v1/positions")
+        .add_query_param("datetime", "2024-06-01T00:00:00Z")
+        .add_query_param("objects", "sun,moon")
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let sun = body["longitudes"]["sun"].as_f64().unwrap();
+    assert!((sun - 71.0).abs() < 0.05, "sun {}", sun);
+    assert_eq!(body["longitudes"].as_object().unwrap().len(), 2);
+    assert!(body.get("angles").is_none());
+    assert!(body.get("formatted").is_none());
+
+    let response = server
+        .get("/api/v1/positions")
+        .add_query_param("datetime", "2024-06-01T00:00:00Z")
+        .add_query_param("lat", "40.7128")
+        .add_query_param("lon", "-74.0060")
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["angles"].as_object().unwrap().len(), 4);
+
+    let response = server
+        .get("/api/v1/positions")
+        .add_query_param("datetime", "2024-06-01T00:00:00Z")
+        .add_query_param("lat", "40.7128")
+        .add_query_param("lon", "-74.0060")
+        .add_query_param("objects", "sun")
+        .add_query_param("format", "zodiacal")
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    let sun = body["longitudes"]["sun"].as_f64().unwrap();
+    assert_eq!(body["formatted"]["sun"], aphrodite_core::format::format_zodiacal(sun));
+    assert!(body["formatted"]["sun"].as_str().unwrap().ends_with(" Gemini"));
+    // Angles are written out too
+    assert_eq!(body["formatted"].as_object().unwrap().len(), 5);
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_sidereal_positions_match_render() {
+    let server = create_rate_limited_test_server();
+    let positions = |ayanamsa: &'static str| {
+        server
+            .get("/api/v1/positions")
+            .add_query_param("datetime", "1990-01-01T12:00:00Z")
+            .add_query_param("objects", "sun,moon")
+            .add_query_param("zodiacType", "sidereal")
+            .add_query_param("ayanamsa", ayanamsa)
+    };
+    let render = |ayanamsa: &str| {
+        let mut request = create_valid_request();
+        request["settings"]["zodiacType"] = json!("sidereal");
+        request["settings"]["ayanamsa"] = json!(ayanamsa);
+        request
+    };
+
+    // Alternate ayanamsas so a stale sidereal mode on a reused thread would show
+    for _ in 0..3 {
+        for ayanamsa in ["lahiri", "fagan_bradley"] {
+            let response = positions(ayanamsa).await;
+            response.assert_status_ok();
+            let body: serde_json::Value = response.json();
+
+            let response = server.post("/api/v1/render").json(&render(ayanamsa)).await;
+            response.assert_status_ok();
+            let rendered: serde_json::Value = response.json();
+            let planets = &rendered["layers"]["natal"]["positions"]["planets"];
+            for object in ["sun", "moon"] {
+                let expected = planets[object]["lon"].as_f64().unwrap();
+                let actual = body["longitudes"][object].as_f64().unwrap();
+                assert!((actual - expected).abs() < 1e-9, "{} {}: {} vs {}", ayanamsa, object, actual, expected);
+            }
+        }
+    }
+
+    let response = positions("vedic").await;
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["details"][0]["field"], "ayanamsa");
+}
+
+#[tokio::test]
+async fn test_ephemeris_table_validation_error() {
+    let server = create_rate_limited_test_server();
+    let response = server
+        .get("/api/v1/ephemeris")
+        .add_query_param("from", "2024-02-01T00:00:00Z")
+        .add_query_param("to", "2024-01-01T00:00:00Z")
+        .add_query_param("step", "1w")
+        .await;
+    response.assert_status_bad_request();
+
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+    let fields: Vec<&str> = body["error"]["details"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|detail| detail["field"].as_str().unwrap())
+        .collect();
+    assert_eq!(fields, vec!["step", "to"]);
+}
+
+#[tokio::test]
+#[ignore] // Requires Swiss Ephemeris files
+async fn test_ephemeris_table() {
+    let server = create_rate_limited_test_server();
+    let response = server
+        .get("/api/v1/ephemeris")
+        .add_query_param("from", "2024-06-01T00:00:00Z")
+        .add_query_param("to", "2024-06-08T00:00:00Z")
+        .add_query_param("objects", "sun,moon")
+        .add_query_param("declination", "true")
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["objects"], json!(["sun", "moon"]));
+    let rows = body["rows"].as_array().unwrap();
+    assert_eq!(rows.len(), 7);
+    assert_eq!(rows[1]["dateTime"], "2024-06-02T00:00:00Z");
+    let sun = rows[0]["longitudes"][0].as_f64().unwrap();
+    assert!((sun - 71.0).abs() < 0.05, "sun {}", sun);
+    // The Sun is near its greatest declination in early June
+    let declination = rows[0]["declinations"][0].as_f64().unwrap();
+    assert!((declination - 22.09).abs() < 0.05, "declination {}", declination);
+    assert!(rows[0].get("speeds").is_none());
+}
+
+#[tokio::test]
+async fn test_admin_listener() {
+    configure_test_env();
+    let mut config = aphrodite_api::config::Config::load().unwrap();
+    config.admin_port = Some(9100);
+    let routers = routes::create_routers_with_config(config);
+    let api = TestServer::new(routers.api).unwrap();
+    let admin = TestServer::new(routers.admin.expect("admin router")).unwrap();
+
+    // Operational routes are gone from the public port
+    api.get("/api/v1/cache/stats").await.assert_status_not_found();
+    api.post("/api/v1/cache/clear")
+        .authorization_bearer("test-admin-token")
+        .await
+        .assert_status_not_found();
+    api.get("/metrics").await.assert_status_not_found();
+    api.get("/health").await.assert_status_ok();
+
+    let metrics = admin.get("/metrics").await;
+    metrics.assert_status_ok();
+    assert!(metrics.text().contains("aphrodite_cache_hits_total{backend=\"memory\"} 0"));
+    assert!(metrics.text().contains("\naphrodite_service_pool_size 2\n"));
+
+    admin.get("/cache/stats").await.assert_status_ok();
+    admin.post("/cache/clear").await.assert_status_unauthorized();
+    admin.get("/admin/config").await.assert_status_unauthorized();
+    let dump = admin.get("/admin/config").authorization_bearer("test-admin-token").await;
+    dump.assert_status_ok();
+    assert!(dump.text().contains("admin_port = 9100"));
+    assert!(dump.text().contains("admin_token = \"<redacted>\""));
+    assert!(!dump.text().contains("test-admin-token"));
+}
+
+#[tokio::test]
+async fn test_validate_endpoint() {
+    let server = create_test_server();
+
+    let response = server.post("/api/v1/validate").json(&create_valid_request()).await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["valid"], true);
+    assert_eq!(body["issues"], json!([]));
+
+    let mut request = create_valid_request();
+    request["subjects"][0]["location"]["lat"] = json!(95.0);
+    request["settings"]["houseSytem"] = json!("koch");
+    let body: serde_json::Value = server.post("/api/v1/validate").json(&request).await.json();
+    assert_eq!(body["valid"], false);
+    let fields: Vec<_> = body["issues"].as_array().unwrap().iter().map(|issue| issue["field"].as_str().unwrap()).collect();
+    assert!(fields.contains(&"subjects[0].location.lat"));
+    assert!(fields.contains(&"settings.houseSytem"));
+
+    // Settings overrides are only checked when they are applied
+    let mut request = create_valid_request();
+    request["settings_override"] = json!({ "zodiacType": 5 });
+    let body: serde_json::Value = server.post("/api/v1/validate").json(&request).await.json();
+    assert_eq!(body["valid"], false);
+    assert_eq!(body["issues"][0]["field"], "settings_override");
+    assert_eq!(body["issues"][0]["code"], "INVALID_VALUE");
+
+    // Not a render request at all
+    server.post("/api/v1/validate").json(&json!({ "subjects": 3 })).await.assert_status_bad_request();
+}
+
+#[tokio::test]
+async fn test_render_field_selection() {
+    let server = create_rate_limited_test_server();
+
+    let response = server
+        .post("/api/v1/render")
+        .add_query_param("fields", "layers.natal.positions.planets.sun,settings.zodiacType")
+        .json(&create_valid_request())
+        .await;
+    response.assert_status_ok();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["settings"], json!({ "zodiacType": "tropical" }));
+    let natal = body["layers"]["natal"].as_object().unwrap();
+    assert_eq!(natal.keys().collect::<Vec<_>>(), vec!["positions"]);
+    let planets = natal["positions"]["planets"].as_object().unwrap();
+    assert_eq!(planets.keys().collect::<Vec<_>>(), vec!["sun"]);
+    assert!(planets["sun"]["lon"].is_f64());
+
+    let body: serde_json::Value = server
+        .post("/api/v1/render/chartspec")
+        .add_query_param("exclude", "spec.shapes,ephemeris.layers.*.positions")
+        .json(&create_valid_request())
+        .await
+        .json();
+    assert!(body["spec"].get("shapes").is_none());
+    assert!(body["spec"]["width"].is_number());
+    assert!(body["ephemeris"]["layers"]["natal"].get("positions").is_none());
+    assert_eq!(body["ephemeris"]["layers"]["natal"]["kind"], "natal");
+
+    let response = server
+        .post("/api/v1/render")
+        .add_query_param("fields", "layers..positions")
+        .json(&create_valid_request())
+        .await;
+    response.assert_status_bad_request();
+    let body: serde_json::Value = response.json();
+    assert_eq!(body["error"]["details"][0]["field"], "fields");
+}
+
+#[tokio::test]
+async fn test_render_response_meta() {
+    let server = create_rate_limited_test_server();
+    let mut request = create_valid_request();
+    // Unique moment so the first request misses the shared cache
+    request["subjects"][0]["birthDateTime"] = json!("1987-06-05T04:03:02Z");
+
+    let body: serde_json::Value = server.post("/api/v1/render").json(&request).await.json();
+    let meta = &body["meta"];
+    assert_eq!(meta["cacheHit"], false);
+    assert!(meta["computationMs"].is_u64());
+    assert!(!meta["engineVersion"].as_str().unwrap().is_empty());
+    assert!(["swiss_ephemeris", "moshier"].contains(&meta["ephemerisBackend"].as_str().unwrap()));
+    assert!(meta.get("ayanamsa").is_none());
+    assert!(meta["warnings"].is_array());
+    assert!(body["settings"].is_object());
+
+    let body: serde_json::Value = server.post("/api/v1/render").json(&request).await.json();
+    assert_eq!(body["meta"]["cacheHit"], true);
+
+    request["settings"]["zodiacType"] = json!("sidereal");
+    request["settings"]["ayanamsa"] = json!("lahiri");
+    let body: serde_json::Value = server.post("/api/v1/render").json(&request).await.json();
+    assert_eq!(body["meta"]["ayanamsa"], "lahiri");
+}
+
+#[tokio::test]
+async fn test_render_settings_echo_disabled() {
+    configure_test_env();
+    let mut config = aphrodite_api::config::Config::load().unwrap();
+    config.echo_settings = false;
+    let app = routes::create_router_with_config(config).into_make_service_with_connect_info::<std::net::SocketAddr>();
+    let server = TestServer::builder().http_transport().build(app).unwrap();
+
+    let body: serde_json::Value = server.post("/api/v1/render").json(&create_valid_request()).await.json();
+    assert!(body.get("settings").is_none());
+    assert!(body["layers"]["natal"].is_object());
+    assert!(body["meta"].is_object());
+
+    let body: serde_json::Value = server.post("/api/v1/render/chartspec").json(&create_valid_request()).await.json();
+    assert!(body["ephemeris"].get("settings").is_none());
+    assert!(body["spec"].is_object());
+}
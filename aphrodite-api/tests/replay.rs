@@ -0,0 +1,123 @@
+// Record/replay harness: replays anonymized recordings against the current build
+use aphrodite_api::replay::{read_recordings, replay_exchanges, RecordedExchange};
+use aphrodite_api::routes;
+use serde_json::json;
+use std::path::PathBuf;
+
+fn configure_test_env() {
+    std::env::set_var("SWISS_EPHEMERIS_PATH", "/usr/local/share/swisseph");
+    std::env::set_var("SERVICE_POOL_SIZE", "1");
+    std::env::set_var("CACHE_SIZE", "10");
+}
+
+#[tokio::test]
+async fn test_record_and_replay_roundtrip() {
+    configure_test_env();
+    let path = std::env::temp_dir().join(format!("aphrodite-recordings-{}.jsonl", uuid::Uuid::new_v4()));
+
+    // Build a recording router, then clear the variable so later routers don't record
+    std::env::set_var("RECORD_REQUESTS_PATH", &path);
+    let recording_router = routes::create_router();
+    std::env::remove_var("RECORD_REQUESTS_PATH");
+
+    let invalid_request = RecordedExchange {
+        method: "POST".to_string(),
+        path: "/api/v1/render".to_string(),
+        request_body: json!({
+            "subjects": [{
+                "id": "p1",
+                "label": "Jane Doe",
+                "birthDateTime": "1990-01-01T12:00:00Z",
+                "location": { "name": "New York", "lat": 40.7128, "lon": -74.0060 }
+            }],
+            "settings": { "zodiacType": "invalid_zodiac" },
+            "layer_config": { "natal": { "kind": "natal", "subjectId": "p1" } }
+        }),
+        status: 400,
+        response_body: json!({}),
+        recorded_at: chrono::Utc::now(),
+    };
+    replay_exchanges(recording_router, &[invalid_request]).await;
+
+    let recordings = read_recordings(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(recordings.len(), 1);
+    let recorded = &recordings[0];
+    assert_eq!(recorded.status, 400);
+    assert_eq!(recorded.request_body["subjects"][0]["label"], "Subject 1");
+    assert!(recorded.request_body["subjects"][0]["location"].get("name").is_none());
+    assert_eq!(recorded.response_body["error"]["code"], "VALIDATION_ERROR");
+
+    // Correlation ids differ between runs but the structure must match
+    let outcomes = replay_exchanges(routes::create_router(), &recordings).await;
+    assert!(outcomes[0].passed(), "{:?}", outcomes[0]);
+}
+
+#[tokio::test]
+async fn test_recorder_passes_through_streams_and_large_bodies() {
+    use aphrodite_api::middleware::record_exchange;
+    use aphrodite_api::replay::{Recorder, MAX_RECORDED_BODY_BYTES};
+    use axum::body::{to_bytes, Body, Bytes};
+    use axum::http::{header, Request, StatusCode};
+    use axum::routing::post;
+    use axum::Router;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    let path = std::env::temp_dir().join(format!("aphrodite-recordings-{}.jsonl", uuid::Uuid::new_v4()));
+    let recorder = Arc::new(Recorder::new(&path));
+    let router = Router::new()
+        .route("/api/echo", post(|body: Bytes| async move { body }))
+        .route(
+            "/api/stream",
+            post(|| async { ([(header::CONTENT_TYPE, "text/event-stream")], "{}") }),
+        )
+        .layer(axum::middleware::from_fn_with_state(recorder, record_exchange));
+    let post_json = |uri: &str, body: String| {
+        Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    let response = router.clone().oneshot(post_json("/api/stream", "{}".to_string())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(to_bytes(response.into_body(), usize::MAX).await.unwrap(), "{}");
+
+    let large = format!(r#"{{"pad":"{}"}}"#, "x".repeat(MAX_RECORDED_BODY_BYTES));
+    let response = router.clone().oneshot(post_json("/api/echo", large.clone())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(to_bytes(response.into_body(), usize::MAX).await.unwrap(), large);
+    assert!(!path.exists(), "streams and oversized bodies must not be recorded");
+
+    let response = router.oneshot(post_json("/api/echo", r#"{"a":1}"#.to_string())).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let recordings = read_recordings(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(recordings.len(), 1);
+    assert_eq!(recordings[0].path, "/api/echo");
+}
+
+#[tokio::test]
+#[ignore] // Set REPLAY_RECORDINGS to a recordings file and run with --ignored
+async fn replay_recorded_requests() {
+    configure_test_env();
+    let path = PathBuf::from(
+        std::env::var("REPLAY_RECORDINGS").expect("REPLAY_RECORDINGS must point to a recordings file"),
+    );
+    let recordings = read_recordings(&path).unwrap();
+
+    let outcomes = replay_exchanges(routes::create_router(), &recordings).await;
+    let failures: Vec<_> = outcomes.iter().filter(|o| !o.passed()).collect();
+    for failure in &failures {
+        eprintln!(
+            "#{} {}: status {} -> {}\n  {}",
+            failure.index,
+            failure.path,
+            failure.expected_status,
+            failure.actual_status,
+            failure.differences.join("\n  ")
+        );
+    }
+    assert!(failures.is_empty(), "{} of {} replayed requests differ", failures.len(), outcomes.len());
+}
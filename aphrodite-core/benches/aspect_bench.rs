@@ -57,6 +57,9 @@ fn bench_compute_intra_layer_aspects(c: &mut Criterion) {
         orb_settings,
         include_objects: vec![],
         only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
     };
     
     c.bench_function("compute_intra_layer_aspects", |b| {
@@ -1,5 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use aphrodite_core::ephemeris::{EphemerisSettings, GeoLocation, SwissEphemerisAdapter};
+use aphrodite_core::ephemeris::{
+    EphemerisSettings, GeoLocation, LilithType, MoonPosition, NodeType, PolarFallback, SwissEphemerisAdapter,
+};
 use chrono::Utc;
 
 fn bench_calc_positions(c: &mut Criterion) {
@@ -8,6 +10,7 @@ fn bench_calc_positions(c: &mut Criterion) {
     let settings = EphemerisSettings {
         zodiac_type: "tropical".to_string(),
         ayanamsa: None,
+        custom_ayanamsa: None,
         house_system: "placidus".to_string(),
         include_objects: vec![
             "sun".to_string(),
@@ -16,6 +19,10 @@ fn bench_calc_positions(c: &mut Criterion) {
             "venus".to_string(),
             "mars".to_string(),
         ],
+        node_type: NodeType::default(),
+        lilith_type: LilithType::default(),
+        moon_position: MoonPosition::default(),
+        polar_fallback: PolarFallback::default(),
     };
     
     let location = Some(GeoLocation {
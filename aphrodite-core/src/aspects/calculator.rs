@@ -1,5 +1,8 @@
-use crate::aspects::types::{AspectCore, AspectPair, AspectObjectRef, AspectSet, AspectSettings};
+use crate::aspects::types::{
+    AspectCore, AspectObjectRef, AspectPair, AspectSet, AspectSettings, DEFAULT_MINOR_ORBS,
+};
 use crate::ephemeris::types::LayerPositions;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// Aspect angles in order of frequency (most common first)
@@ -11,6 +14,25 @@ const ASPECT_ANGLES: &[(&str, f64)] = &[
     ("sextile", 60.0),
 ];
 
+/// Minor aspect angles, computed on request
+const MINOR_ASPECT_ANGLES: &[(&str, f64)] = &[
+    ("quincunx", 150.0),
+    ("semisextile", 30.0),
+    ("semisquare", 45.0),
+    ("sesquiquadrate", 135.0),
+];
+
+/// Chart angles that take part in aspects when `include_angles` is set
+const ASPECT_CHART_ANGLES: &[&str] = &["asc", "mc"];
+
+/// A planet or chart angle taking part in aspects
+struct AspectObject<'a> {
+    id: &'a str,
+    object_type: &'static str,
+    lon: f64,
+    speed: f64,
+}
+
 /// Aspect calculator
 pub struct AspectCalculator;
 
@@ -27,58 +49,21 @@ impl AspectCalculator {
         positions: &LayerPositions,
         settings: &AspectSettings,
     ) -> AspectSet {
-        let planets = &positions.planets;
-        let mut planet_ids: Vec<String> = planets.keys().cloned().collect();
-
-        // Filter to included objects
-        if !settings.include_objects.is_empty() {
-            let include_set: std::collections::HashSet<&str> =
-                settings.include_objects.iter().map(|s| s.as_str()).collect();
-            planet_ids.retain(|pid| include_set.contains(pid.as_str()));
-        }
-
-        // Early exit if not enough planets
-        if planet_ids.len() < 2 {
-            return AspectSet {
-                id: layer_id.to_string(),
-                label: format!("{} Aspects", capitalize_first(layer_id)),
-                kind: "intra_layer".to_string(),
-                layer_ids: vec![layer_id.to_string()],
-                pairs: vec![],
-            };
-        }
+        let objects = Self::objects(positions, settings);
+        let aspect_angles = Self::aspect_angles(settings);
 
-        // Calculate aspects between all planet pairs
+        // Calculate aspects between all object pairs
         let mut pairs = Vec::new();
-        for i in 0..planet_ids.len() {
-            for j in (i + 1)..planet_ids.len() {
-                let p1_id = &planet_ids[i];
-                let p2_id = &planet_ids[j];
-
-                let p1_pos = &planets[p1_id];
-                let p2_pos = &planets[p2_id];
-
-                if let Some(aspect) = self.calculate_aspect(
-                    p1_pos.lon,
-                    p2_pos.lon,
-                    p1_pos.speed_lon,
-                    p2_pos.speed_lon,
-                    &settings.orb_settings,
-                ) {
-                    pairs.push(AspectPair {
-                        from: AspectObjectRef {
-                            layer_id: layer_id.to_string(),
-                            object_type: "planet".to_string(),
-                            object_id: p1_id.clone(),
-                        },
-                        to: AspectObjectRef {
-                            layer_id: layer_id.to_string(),
-                            object_type: "planet".to_string(),
-                            object_id: p2_id.clone(),
-                        },
-                        aspect,
-                    });
-                }
+        for (i, object1) in objects.iter().enumerate() {
+            for object2 in &objects[i + 1..] {
+                pairs.extend(self.pair_aspect(
+                    layer_id,
+                    object1,
+                    layer_id,
+                    object2,
+                    settings,
+                    &aspect_angles,
+                ));
             }
         }
 
@@ -100,63 +85,172 @@ impl AspectCalculator {
         positions_b: &LayerPositions,
         settings: &AspectSettings,
     ) -> AspectSet {
-        let planets_a = &positions_a.planets;
-        let planets_b = &positions_b.planets;
-
-        let mut planet_ids_a: Vec<String> = planets_a.keys().cloned().collect();
-        let mut planet_ids_b: Vec<String> = planets_b.keys().cloned().collect();
-
-        // Filter to included objects
-        if !settings.include_objects.is_empty() {
-            let include_set: std::collections::HashSet<&str> =
-                settings.include_objects.iter().map(|s| s.as_str()).collect();
-            planet_ids_a.retain(|pid| include_set.contains(pid.as_str()));
-            planet_ids_b.retain(|pid| include_set.contains(pid.as_str()));
+        let pairs = self.cross_layer_pairs(layer_id_a, layer_id_b, positions_a, positions_b, settings, false);
+
+        AspectSet {
+            id: format!("{}:{}", layer_id_a, layer_id_b),
+            label: format!("{} / {} Aspects", capitalize_first(layer_id_a), capitalize_first(layer_id_b)),
+            kind: "inter_layer".to_string(),
+            layer_ids: vec![layer_id_a.to_string(), layer_id_b.to_string()],
+            pairs,
         }
+    }
 
-        // Calculate aspects between all planet pairs
+    /// Compute aspects from a transit layer to a natal layer.
+    ///
+    /// Unlike [`Self::compute_inter_layer_aspects`], a transiting planet also aspects its own
+    /// natal place (e.g. a Saturn return).
+    pub fn compute_transit_aspects(
+        &self,
+        transit_layer_id: &str,
+        natal_layer_id: &str,
+        transit_positions: &LayerPositions,
+        natal_positions: &LayerPositions,
+        settings: &AspectSettings,
+    ) -> AspectSet {
+        let pairs = self.cross_layer_pairs(
+            transit_layer_id,
+            natal_layer_id,
+            transit_positions,
+            natal_positions,
+            settings,
+            true,
+        );
+
+        AspectSet {
+            id: format!("{}:{}", transit_layer_id, natal_layer_id),
+            label: format!("{} to {} Aspects", capitalize_first(transit_layer_id), capitalize_first(natal_layer_id)),
+            kind: "inter_layer".to_string(),
+            layer_ids: vec![transit_layer_id.to_string(), natal_layer_id.to_string()],
+            pairs,
+        }
+    }
+
+    /// Aspects between every object of layer A and every object of layer B
+    fn cross_layer_pairs(
+        &self,
+        layer_id_a: &str,
+        layer_id_b: &str,
+        positions_a: &LayerPositions,
+        positions_b: &LayerPositions,
+        settings: &AspectSettings,
+        include_same_object: bool,
+    ) -> Vec<AspectPair> {
+        let objects_a = Self::objects(positions_a, settings);
+        let objects_b = Self::objects(positions_b, settings);
+        let aspect_angles = Self::aspect_angles(settings);
+
+        // Calculate aspects between all object pairs
         let mut pairs = Vec::new();
-        for p1_id in &planet_ids_a {
-            for p2_id in &planet_ids_b {
-                // Skip if same planet
-                if p1_id == p2_id {
+        for object1 in &objects_a {
+            for object2 in &objects_b {
+                // Skip if same object
+                if object1.id == object2.id && !include_same_object {
                     continue;
                 }
+                pairs.extend(self.pair_aspect(
+                    layer_id_a,
+                    object1,
+                    layer_id_b,
+                    object2,
+                    settings,
+                    &aspect_angles,
+                ));
+            }
+        }
+        pairs
+    }
 
-                let p1_pos = &planets_a[p1_id];
-                let p2_pos = &planets_b[p2_id];
-
-                if let Some(aspect) = self.calculate_aspect(
-                    p1_pos.lon,
-                    p2_pos.lon,
-                    p1_pos.speed_lon,
-                    p2_pos.speed_lon,
-                    &settings.orb_settings,
-                ) {
-                    pairs.push(AspectPair {
-                        from: AspectObjectRef {
-                            layer_id: layer_id_a.to_string(),
-                            object_type: "planet".to_string(),
-                            object_id: p1_id.clone(),
-                        },
-                        to: AspectObjectRef {
-                            layer_id: layer_id_b.to_string(),
-                            object_type: "planet".to_string(),
-                            object_id: p2_id.clone(),
-                        },
-                        aspect,
+    /// Included planets, then chart angles when requested, each sorted by id so pairs are
+    /// oriented the same way on every run
+    fn objects<'a>(
+        positions: &'a LayerPositions,
+        settings: &AspectSettings,
+    ) -> Vec<AspectObject<'a>> {
+        let mut objects: Vec<AspectObject> = positions
+            .planets
+            .iter()
+            .filter(|(id, _)| {
+                settings.include_objects.is_empty() || settings.include_objects.contains(id)
+            })
+            .map(|(id, planet)| AspectObject {
+                id,
+                object_type: "planet",
+                lon: planet.lon,
+                speed: planet.speed_lon,
+            })
+            .collect();
+        objects.sort_by(|a, b| a.id.cmp(b.id));
+
+        if let Some(houses) = positions
+            .houses
+            .as_ref()
+            .filter(|_| settings.filter.include_angles)
+        {
+            for angle_id in ASPECT_CHART_ANGLES {
+                if let Some(lon) = houses.angles.get(*angle_id) {
+                    objects.push(AspectObject {
+                        id: angle_id,
+                        object_type: "angle",
+                        lon: *lon,
+                        speed: 0.0,
                     });
                 }
             }
         }
+        objects
+    }
 
-        AspectSet {
-            id: format!("{}:{}", layer_id_a, layer_id_b),
-            label: format!("{} / {} Aspects", capitalize_first(layer_id_a), capitalize_first(layer_id_b)),
-            kind: "inter_layer".to_string(),
-            layer_ids: vec![layer_id_a.to_string(), layer_id_b.to_string()],
-            pairs,
+    /// Aspects to look for: the major aspects, plus the minor ones when `only_major` is
+    /// `Some(false)`, narrowed to `filter.aspect_types` when given
+    fn aspect_angles(settings: &AspectSettings) -> Vec<(&'static str, f64)> {
+        let minor = settings.only_major == Some(false);
+        ASPECT_ANGLES
+            .iter()
+            .chain(MINOR_ASPECT_ANGLES)
+            .filter(|(name, _)| match &settings.filter.aspect_types {
+                Some(types) => types.iter().any(|aspect_type| aspect_type == name),
+                None => minor || ASPECT_ANGLES.iter().any(|(major, _)| major == name),
+            })
+            .copied()
+            .collect()
+    }
+
+    /// The aspect between two objects, if any passes the settings
+    fn pair_aspect(
+        &self,
+        layer_id1: &str,
+        object1: &AspectObject,
+        layer_id2: &str,
+        object2: &AspectObject,
+        settings: &AspectSettings,
+        aspect_angles: &[(&str, f64)],
+    ) -> Option<AspectPair> {
+        if !settings.filter.allows_pair(object1.id, object2.id) {
+            return None;
+        }
+        let orb_settings = Self::pair_orbs(settings, object1.id, object2.id, aspect_angles);
+        let aspect = self.calculate_aspect_among(
+            object1.lon,
+            object2.lon,
+            object1.speed,
+            object2.speed,
+            &orb_settings,
+            aspect_angles,
+        )?;
+        if aspect.out_of_sign && settings.exclude_out_of_sign {
+            return None;
         }
+        let object_ref = |layer_id: &str, object: &AspectObject| AspectObjectRef {
+            layer_id: layer_id.to_string(),
+            object_type: object.object_type.to_string(),
+            object_id: object.id.to_string(),
+        };
+        Some(AspectPair {
+            from: object_ref(layer_id1, object1),
+            to: object_ref(layer_id2, object2),
+            aspect,
+        })
     }
 
     /// Compute all aspect sets for multiple layers
@@ -166,7 +260,9 @@ impl AspectCalculator {
         settings: &AspectSettings,
     ) -> HashMap<String, AspectSet> {
         let mut aspect_sets = HashMap::new();
-        let layer_ids: Vec<String> = layers.keys().cloned().collect();
+        // Sorted so inter-layer set ids and pair orientation are stable between runs
+        let mut layer_ids: Vec<String> = layers.keys().cloned().collect();
+        layer_ids.sort();
 
         // Intra-layer aspects
         for layer_id in &layer_ids {
@@ -200,7 +296,7 @@ impl AspectCalculator {
         aspect_sets
     }
 
-    /// Calculate aspect between two longitudes using planet speeds
+    /// Calculate the major aspect between two longitudes using planet speeds
     pub fn calculate_aspect(
         &self,
         lon1: f64,
@@ -208,6 +304,19 @@ impl AspectCalculator {
         speed1: f64,
         speed2: f64,
         orb_settings: &HashMap<String, f64>,
+    ) -> Option<AspectCore> {
+        self.calculate_aspect_among(lon1, lon2, speed1, speed2, orb_settings, ASPECT_ANGLES)
+    }
+
+    /// First aspect of `aspect_angles` within its orb
+    fn calculate_aspect_among(
+        &self,
+        lon1: f64,
+        lon2: f64,
+        speed1: f64,
+        speed2: f64,
+        orb_settings: &HashMap<String, f64>,
+        aspect_angles: &[(&str, f64)],
     ) -> Option<AspectCore> {
         // Calculate angle difference (normalized to 0-180)
         let raw_diff = (lon1 - lon2).abs();
@@ -227,7 +336,7 @@ impl AspectCalculator {
         }
 
         // Check each aspect type in order of frequency (most common first)
-        for (aspect_name, aspect_angle) in ASPECT_ANGLES {
+        for (aspect_name, aspect_angle) in aspect_angles {
             let orb = orb_settings.get(*aspect_name).copied().unwrap_or(8.0);
             let orb_value = (angle_diff - aspect_angle).abs();
 
@@ -243,6 +352,7 @@ impl AspectCalculator {
                 );
                 let is_exact = orb_value < 0.1; // Within 0.1 degrees is "exact"
                 let is_retrograde = speed1 < 0.0 || speed2 < 0.0;
+                let out_of_sign = Self::is_out_of_sign(lon1, lon2, *aspect_angle);
 
                 return Some(AspectCore {
                     aspect_type: aspect_name.to_string(),
@@ -252,6 +362,7 @@ impl AspectCalculator {
                     is_applying,
                     is_exact,
                     is_retrograde,
+                    out_of_sign,
                 });
             }
         }
@@ -259,6 +370,61 @@ impl AspectCalculator {
         None
     }
 
+    /// Orbs per aspect type for a pair of objects, combining `object_orbs` with the aspect
+    /// orbs; minor aspects missing from `orb_settings` get their default orb
+    fn pair_orbs<'a>(
+        settings: &'a AspectSettings,
+        id1: &str,
+        id2: &str,
+        aspect_angles: &[(&str, f64)],
+    ) -> Cow<'a, HashMap<String, f64>> {
+        let (orb1, orb2) = (settings.object_orbs.get(id1), settings.object_orbs.get(id2));
+        let minor_missing = aspect_angles.iter().any(|(name, _)| {
+            !settings.orb_settings.contains_key(*name) && Self::minor_orb(name).is_some()
+        });
+        if orb1.is_none() && orb2.is_none() && !minor_missing {
+            return Cow::Borrowed(&settings.orb_settings);
+        }
+        Cow::Owned(
+            aspect_angles
+                .iter()
+                .map(|(aspect_name, _)| {
+                    let aspect_orb = settings
+                        .orb_settings
+                        .get(*aspect_name)
+                        .copied()
+                        .or_else(|| Self::minor_orb(aspect_name))
+                        .unwrap_or(8.0);
+                    let orb = (orb1.copied().unwrap_or(aspect_orb)
+                        + orb2.copied().unwrap_or(aspect_orb))
+                        / 2.0;
+                    (aspect_name.to_string(), orb)
+                })
+                .collect(),
+        )
+    }
+
+    fn minor_orb(aspect_name: &str) -> Option<f64> {
+        DEFAULT_MINOR_ORBS
+            .iter()
+            .find(|(name, _)| *name == aspect_name)
+            .map(|(_, orb)| *orb)
+    }
+
+    /// Whether the signs of two longitudes don't stand in an aspect's sign relationship.
+    /// Only aspects spanning whole signs have one, so the semisquare and sesquiquadrate
+    /// are never out of sign.
+    fn is_out_of_sign(lon1: f64, lon2: f64, aspect_angle: f64) -> bool {
+        aspect_angle % 30.0 == 0.0 && Self::sign_distance(lon1, lon2) as f64 * 30.0 != aspect_angle
+    }
+
+    /// Number of signs between the signs of two longitudes, 0 to 6
+    fn sign_distance(lon1: f64, lon2: f64) -> u8 {
+        let sign = |lon: f64| (lon.rem_euclid(360.0) / 30.0).floor() as i32 % 12;
+        let distance = (sign(lon1) - sign(lon2)).rem_euclid(12);
+        distance.min(12 - distance) as u8
+    }
+
     /// Determine if an aspect is applying (approaching exact) or separating
     fn is_aspect_applying(
         &self,
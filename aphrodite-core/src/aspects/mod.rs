@@ -3,6 +3,7 @@ pub mod types;
 
 pub use calculator::AspectCalculator;
 pub use types::{
-    AspectCore, AspectObjectRef, AspectPair, AspectSet, AspectSettings,
+    AspectCore, AspectFilter, AspectObjectRef, AspectPair, AspectSet, AspectSettings,
+    DEFAULT_MINOR_ORBS, DEFAULT_ORBS,
 };
 
@@ -17,6 +17,11 @@ pub struct AspectCore {
     pub is_exact: bool,
     /// Whether either planet is retrograde
     pub is_retrograde: bool,
+    /// Whether the planets' signs do not stand in the aspect's sign relationship, e.g. a
+    /// trine from late Aries to early Virgo. Always false for the semisquare and
+    /// sesquiquadrate, which don't span whole signs.
+    #[serde(rename = "outOfSign", default)]
+    pub out_of_sign: bool,
 }
 
 /// Reference to an object in an aspect
@@ -45,6 +50,47 @@ pub struct AspectSet {
     pub pairs: Vec<AspectPair>,
 }
 
+/// Default orbs in degrees for the major aspects
+pub const DEFAULT_ORBS: &[(&str, f64)] = &[
+    ("conjunction", 8.0),
+    ("opposition", 8.0),
+    ("trine", 7.0),
+    ("square", 6.0),
+    ("sextile", 4.0),
+];
+
+/// Default orbs in degrees for the minor aspects
+pub const DEFAULT_MINOR_ORBS: &[(&str, f64)] = &[
+    ("quincunx", 3.0),
+    ("semisextile", 2.0),
+    ("semisquare", 2.0),
+    ("sesquiquadrate", 2.0),
+];
+
+/// Which aspects and object pairs to compute
+#[derive(Debug, Clone, Default)]
+pub struct AspectFilter {
+    /// Aspect types to look for, major or minor; `None` follows `only_major`
+    pub aspect_types: Option<Vec<String>>,
+    /// When set, only these object pairs are compared, in either order
+    pub include_pairs: Option<Vec<(String, String)>>,
+    /// Object pairs never compared, in either order, e.g. the lunar nodes
+    pub exclude_pairs: Vec<(String, String)>,
+    /// Also aspect the Ascendant and Midheaven of layers with houses
+    pub include_angles: bool,
+}
+
+impl AspectFilter {
+    /// Whether objects `id1` and `id2` are compared
+    pub fn allows_pair(&self, id1: &str, id2: &str) -> bool {
+        let matches = |(a, b): &(String, String)| (a == id1 && b == id2) || (a == id2 && b == id1);
+        self.include_pairs
+            .as_ref()
+            .is_none_or(|pairs| pairs.iter().any(matches))
+            && !self.exclude_pairs.iter().any(matches)
+    }
+}
+
 /// Settings for aspect calculations
 #[derive(Debug, Clone)]
 pub struct AspectSettings {
@@ -52,7 +98,14 @@ pub struct AspectSettings {
     pub orb_settings: std::collections::HashMap<String, f64>,
     /// List of planet IDs to include
     pub include_objects: Vec<String>,
-    /// Whether to only include major aspects
+    /// Whether to only include major aspects; `Some(false)` adds the minor aspects
     pub only_major: Option<bool>,
+    /// Leave out aspects whose planets' signs don't match the aspect
+    pub exclude_out_of_sign: bool,
+    /// Orb per object, as in traditional moiety tables. A pair's orb is the mean of both
+    /// objects' orbs, with the aspect's orb standing in for objects not listed.
+    pub object_orbs: std::collections::HashMap<String, f64>,
+    /// Aspect types and object pairs to compute, and whether angles take part
+    pub filter: AspectFilter,
 }
 
@@ -0,0 +1,283 @@
+//! A calculated chart with the usual questions answered: which house a planet is in, its
+//! dignities, the aspects between two objects, and the Western analysis of a layer.
+//!
+//! The API service, bindings and apps all need the same glue between positions, aspect sets
+//! and the analysis modules; `Chart` keeps it in one place.
+
+use crate::aspects::{AspectCalculator, AspectObjectRef, AspectPair, AspectSet, AspectSettings, DEFAULT_ORBS};
+use crate::ephemeris::{HousePositions, LayerPositions, PlanetPosition};
+use crate::layout::rings::get_house_index;
+use crate::western::{
+    compute_analysis, compute_balance, compute_conditions, BalanceSummary, DignitiesService,
+    DignityResult, PlanetCondition, WesternAnalysis,
+};
+use crate::zodiac::sign_name;
+use std::collections::HashMap;
+
+/// Layers of one chart and the aspects within and between them
+#[derive(Debug, Clone)]
+pub struct Chart {
+    layers: HashMap<String, LayerPositions>,
+    aspect_sets: HashMap<String, AspectSet>,
+    primary: String,
+}
+
+impl Chart {
+    /// A single-layer chart, with the major aspects at their default orbs
+    pub fn from_positions(layer_id: &str, positions: LayerPositions) -> Self {
+        let settings = AspectSettings {
+            orb_settings: DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect(),
+            include_objects: positions.planets.keys().cloned().collect(),
+            only_major: None,
+            exclude_out_of_sign: false,
+            object_orbs: HashMap::new(),
+            filter: Default::default(),
+        };
+        Self::new(layer_id, HashMap::from([(layer_id.to_string(), positions)]), &settings)
+    }
+
+    /// A chart of `layers`, with aspect sets computed from `settings`. `primary` is the layer
+    /// [`planet`](Self::planet) and [`aspects_between`](Self::aspects_between) look at.
+    pub fn new(primary: &str, layers: HashMap<String, LayerPositions>, settings: &AspectSettings) -> Self {
+        let aspect_sets = AspectCalculator::new().compute_all_aspect_sets(&layers, settings);
+        Self::with_aspect_sets(primary, layers, aspect_sets)
+    }
+
+    /// A chart of `layers` and aspect sets calculated elsewhere
+    pub fn with_aspect_sets(
+        primary: &str,
+        layers: HashMap<String, LayerPositions>,
+        aspect_sets: HashMap<String, AspectSet>,
+    ) -> Self {
+        Self { layers, aspect_sets, primary: primary.to_string() }
+    }
+
+    pub fn layer(&self, layer_id: &str) -> Option<ChartLayer<'_>> {
+        self.layers
+            .get_key_value(layer_id)
+            .map(|(id, positions)| ChartLayer { id, positions })
+    }
+
+    /// The primary layer
+    pub fn primary(&self) -> Option<ChartLayer<'_>> {
+        self.layer(&self.primary)
+    }
+
+    pub fn layers(&self) -> &HashMap<String, LayerPositions> {
+        &self.layers
+    }
+
+    pub fn aspect_sets(&self) -> &HashMap<String, AspectSet> {
+        &self.aspect_sets
+    }
+
+    /// A planet of the primary layer
+    pub fn planet(&self, planet_id: &str) -> Option<ChartPlanet<'_>> {
+        self.primary()?.planet(planet_id)
+    }
+
+    /// Aspects between two objects of the primary layer, in either order
+    pub fn aspects_between(&self, object1: &str, object2: &str) -> Vec<&AspectPair> {
+        self.aspect_sets
+            .get(&self.primary)
+            .into_iter()
+            .flat_map(|set| &set.pairs)
+            .filter(|pair| {
+                let (from, to) = (pair.from.object_id.as_str(), pair.to.object_id.as_str());
+                (from == object1 && to == object2) || (from == object2 && to == object1)
+            })
+            .collect()
+    }
+
+    /// Aspects from an object in `layer1` to one in `layer2`, e.g. a transit to a natal planet
+    pub fn aspects_across(&self, layer1: &str, object1: &str, layer2: &str, object2: &str) -> Vec<&AspectPair> {
+        self.aspect_sets
+            .values()
+            .filter(|set| set.kind == "inter_layer")
+            .flat_map(|set| &set.pairs)
+            .filter(|pair| {
+                let is = |end: &AspectObjectRef, layer: &str, object: &str| end.layer_id == layer && end.object_id == object;
+                (is(&pair.from, layer1, object1) && is(&pair.to, layer2, object2))
+                    || (is(&pair.from, layer2, object2) && is(&pair.to, layer1, object1))
+            })
+            .collect()
+    }
+}
+
+/// One layer of a [`Chart`]
+#[derive(Debug, Clone, Copy)]
+pub struct ChartLayer<'a> {
+    id: &'a str,
+    positions: &'a LayerPositions,
+}
+
+impl<'a> ChartLayer<'a> {
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+
+    pub fn positions(&self) -> &'a LayerPositions {
+        self.positions
+    }
+
+    /// None when the layer has no location
+    pub fn houses(&self) -> Option<&'a HousePositions> {
+        self.positions.houses.as_ref()
+    }
+
+    pub fn planet(&self, planet_id: &str) -> Option<ChartPlanet<'a>> {
+        self.positions
+            .planets
+            .get_key_value(planet_id)
+            .map(|(id, position)| ChartPlanet { id, position, houses: self.houses() })
+    }
+
+    /// Planets sorted by id
+    pub fn planets(&self) -> Vec<ChartPlanet<'a>> {
+        let mut planets: Vec<ChartPlanet<'a>> = self
+            .positions
+            .planets
+            .iter()
+            .map(|(id, position)| ChartPlanet { id, position, houses: self.houses() })
+            .collect();
+        planets.sort_by_key(|planet| planet.id);
+        planets
+    }
+
+    /// Chart ruler, sect, almuten and dispositors
+    pub fn analysis(&self) -> WesternAnalysis {
+        compute_analysis(self.positions)
+    }
+
+    /// Element, modality, quadrant and hemisphere counts
+    pub fn balance(&self) -> BalanceSummary {
+        compute_balance(self.positions)
+    }
+
+    /// Accidental condition of each planet: speed, solar phase, declination and out of bounds
+    pub fn conditions(&self) -> HashMap<String, PlanetCondition> {
+        compute_conditions(self.positions)
+    }
+}
+
+/// A planet of a [`ChartLayer`]
+#[derive(Debug, Clone, Copy)]
+pub struct ChartPlanet<'a> {
+    id: &'a str,
+    position: &'a PlanetPosition,
+    houses: Option<&'a HousePositions>,
+}
+
+impl<'a> ChartPlanet<'a> {
+    pub fn id(&self) -> &'a str {
+        self.id
+    }
+
+    pub fn position(&self) -> &'a PlanetPosition {
+        self.position
+    }
+
+    pub fn lon(&self) -> f64 {
+        self.position.lon
+    }
+
+    pub fn is_retrograde(&self) -> bool {
+        self.position.retrograde
+    }
+
+    /// Sign name, "aries" to "pisces"
+    pub fn sign(&self) -> &'static str {
+        sign_name(self.position.lon)
+    }
+
+    /// Degrees into the sign (0-30)
+    pub fn sign_degree(&self) -> f64 {
+        self.position.lon.rem_euclid(30.0)
+    }
+
+    /// House number (1-12); None when the layer has no houses
+    pub fn house(&self) -> Option<u8> {
+        self.houses
+            .filter(|houses| !houses.cusps.is_empty())
+            .and_then(|houses| get_house_index(self.position.lon, &houses.cusps))
+            .map(|index| index + 1)
+    }
+
+    /// Rulership, exaltation, detriment and fall
+    pub fn dignities(&self) -> Vec<DignityResult> {
+        DignitiesService.get_dignities(self.id, self.position.lon, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planet(lon: f64) -> PlanetPosition {
+        PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde: false }
+    }
+
+    fn natal() -> LayerPositions {
+        LayerPositions {
+            planets: HashMap::from([
+                ("sun".to_string(), planet(130.0)),
+                ("moon".to_string(), planet(10.5)),
+                ("mars".to_string(), planet(280.0)),
+            ]),
+            houses: Some(HousePositions {
+                system: "equal".to_string(),
+                cusps: (1..=12).map(|i| (i.to_string(), ((i - 1) * 30) as f64)).collect(),
+                angles: HashMap::from([("asc".to_string(), 0.0), ("mc".to_string(), 270.0)]),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_planet_accessors() {
+        let chart = Chart::from_positions("natal", natal());
+        let sun = chart.planet("sun").unwrap();
+        assert_eq!(sun.sign(), "leo");
+        assert_eq!(sun.house(), Some(5));
+        assert!((sun.sign_degree() - 10.0).abs() < 1e-9);
+        assert!(sun.dignities().iter().any(|d| d.dignity_type == crate::western::DignityType::Rulership));
+        assert!(chart.planet("pluto").is_none());
+
+        let mut without_houses = natal();
+        without_houses.houses = None;
+        assert_eq!(Chart::from_positions("natal", without_houses).planet("sun").unwrap().house(), None);
+    }
+
+    #[test]
+    fn test_aspects_between() {
+        let chart = Chart::from_positions("natal", natal());
+        // Sun at 10° Leo trines the Moon at 10°30' Aries, in either order
+        let aspects = chart.aspects_between("sun", "moon");
+        assert_eq!(aspects.len(), 1);
+        assert_eq!(aspects[0].aspect.aspect_type, "trine");
+        assert_eq!(chart.aspects_between("moon", "sun").len(), 1);
+        assert!(chart.aspects_between("sun", "mars").is_empty());
+    }
+
+    #[test]
+    fn test_aspects_across_layers() {
+        let transit = LayerPositions {
+            planets: HashMap::from([("mars".to_string(), planet(130.0))]),
+            houses: None,
+        };
+        let layers = HashMap::from([("natal".to_string(), natal()), ("transit".to_string(), transit)]);
+        let settings = AspectSettings {
+            orb_settings: DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect(),
+            include_objects: vec!["sun".to_string(), "moon".to_string(), "mars".to_string()],
+            only_major: None,
+            exclude_out_of_sign: false,
+            object_orbs: HashMap::new(),
+            filter: Default::default(),
+        };
+        let chart = Chart::new("natal", layers, &settings);
+        let conjunction = chart.aspects_across("transit", "mars", "natal", "sun");
+        assert_eq!(conjunction.len(), 1);
+        assert_eq!(conjunction[0].aspect.aspect_type, "conjunction");
+        assert_eq!(chart.aspects_across("natal", "sun", "transit", "mars").len(), 1);
+        assert_eq!(chart.layer("transit").unwrap().planets().len(), 1);
+    }
+}
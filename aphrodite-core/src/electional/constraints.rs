@@ -0,0 +1,307 @@
+//! Constraint DSL for electional searches.
+//!
+//! Each constraint is a short phrase, matched case-insensitively:
+//!
+//! - `moon not void`, `moon void` (void of course until it leaves its sign)
+//! - `venus above horizon`, `saturn below horizon`
+//! - `moon in taurus|cancer`, `mars not in cancer`
+//! - `mercury direct`, `mercury not retrograde`, `jupiter retrograde`
+//! - `venus trine jupiter`
+//! - `no mars-saturn hard aspects`, `no moon-saturn square`, `no sun-moon aspects`
+
+use std::collections::HashMap;
+use crate::aspects::AspectCalculator;
+use crate::electional::types::ElectionalConstraint;
+use crate::ephemeris::types::LayerPositions;
+use crate::zodiac::{sign_name, SIGN_NAMES};
+
+/// Objects a constraint can name
+const CONSTRAINT_OBJECTS: &[&str] = &[
+    "sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn", "uranus", "neptune", "pluto",
+    "chiron", "north_node", "south_node", "lilith",
+];
+
+const MAJOR_ASPECTS: &[&str] = &["conjunction", "opposition", "trine", "square", "sextile"];
+const HARD_ASPECTS: &[&str] = &["conjunction", "opposition", "square"];
+const SOFT_ASPECTS: &[&str] = &["trine", "sextile"];
+
+/// Planets whose aspects end the Moon's void of course (the traditional seven)
+const VOID_OF_COURSE_PLANETS: &[&str] = &["sun", "mercury", "venus", "mars", "jupiter", "saturn"];
+
+/// Angles at which the Moon perfects a major aspect, measured ahead of the other planet
+const VOID_OF_COURSE_ANGLES: &[f64] = &[0.0, 60.0, 90.0, 120.0, 180.0, 240.0, 270.0, 300.0];
+
+/// Parse one constraint phrase
+pub fn parse_constraint(text: &str) -> Result<ElectionalConstraint, String> {
+    let lower = text.to_lowercase();
+    let words: Vec<&str> = lower.split_whitespace().collect();
+    let unrecognized = || format!("Unrecognized constraint '{}'", text.trim());
+
+    let constraint = match words.as_slice() {
+        ["moon", "void", ..] => ElectionalConstraint::MoonVoid { void: true },
+        ["moon", "not", "void", ..] => ElectionalConstraint::MoonVoid { void: false },
+        [planet, side @ ("above" | "below"), "horizon"] => ElectionalConstraint::Horizon {
+            planet: object(planet, text)?,
+            above: *side == "above",
+        },
+        [planet, "in", signs @ ..] | [planet, "not", "in", signs @ ..] if !signs.is_empty() => {
+            ElectionalConstraint::InSigns {
+                planet: object(planet, text)?,
+                signs: sign_list(&signs.join(" "), text)?,
+                negated: words[1] == "not",
+            }
+        }
+        [planet, "retrograde"] => ElectionalConstraint::Retrograde { planet: object(planet, text)?, retrograde: true },
+        [planet, "direct"] | [planet, "not", "retrograde"] => {
+            ElectionalConstraint::Retrograde { planet: object(planet, text)?, retrograde: false }
+        }
+        ["no", pair, rest @ ..] => {
+            let (from, to) = pair.split_once('-').ok_or_else(unrecognized)?;
+            let rest: Vec<&str> = rest.iter().copied().filter(|word| !matches!(*word, "aspect" | "aspects")).collect();
+            let aspect_types = match rest.as_slice() {
+                [] => MAJOR_ASPECTS.to_vec(),
+                ["hard"] => HARD_ASPECTS.to_vec(),
+                ["soft"] => SOFT_ASPECTS.to_vec(),
+                [aspect] => vec![aspect_name(aspect, text)?],
+                _ => return Err(unrecognized()),
+            };
+            ElectionalConstraint::Aspect {
+                from: object(from, text)?,
+                to: object(to, text)?,
+                aspect_types: aspect_types.into_iter().map(str::to_string).collect(),
+                present: false,
+            }
+        }
+        [from, aspect, to] => ElectionalConstraint::Aspect {
+            from: object(from, text)?,
+            to: object(to, text)?,
+            aspect_types: vec![aspect_name(aspect, text)?.to_string()],
+            present: true,
+        },
+        _ => return Err(unrecognized()),
+    };
+    Ok(constraint)
+}
+
+fn object(name: &str, text: &str) -> Result<String, String> {
+    CONSTRAINT_OBJECTS
+        .iter()
+        .find(|id| **id == name)
+        .map(|id| id.to_string())
+        .ok_or_else(|| format!("Unknown object '{}' in '{}'", name, text.trim()))
+}
+
+/// A major aspect name, singular or plural
+fn aspect_name(word: &str, text: &str) -> Result<&'static str, String> {
+    MAJOR_ASPECTS
+        .iter()
+        .copied()
+        .find(|name| *name == word || word.strip_suffix('s') == Some(*name))
+        .ok_or_else(|| format!("Unknown aspect '{}' in '{}'", word, text.trim()))
+}
+
+/// Signs separated by `|`, `,` or spaces
+fn sign_list(list: &str, text: &str) -> Result<Vec<String>, String> {
+    list.split(['|', ',', ' '])
+        .filter(|sign| !sign.is_empty())
+        .map(|sign| {
+            if SIGN_NAMES.contains(&sign) {
+                Ok(sign.to_string())
+            } else {
+                Err(format!("Unknown sign '{}' in '{}'", sign, text.trim()))
+            }
+        })
+        .collect()
+}
+
+impl ElectionalConstraint {
+    /// Objects that must be calculated to evaluate this constraint
+    pub fn objects(&self) -> Vec<&str> {
+        match self {
+            ElectionalConstraint::MoonVoid { .. } => {
+                std::iter::once("moon").chain(VOID_OF_COURSE_PLANETS.iter().copied()).collect()
+            }
+            ElectionalConstraint::Horizon { planet, .. }
+            | ElectionalConstraint::InSigns { planet, .. }
+            | ElectionalConstraint::Retrograde { planet, .. } => vec![planet],
+            ElectionalConstraint::Aspect { from, to, .. } => vec![from, to],
+        }
+    }
+
+    /// Whether the chart meets this constraint; aspects use `orb_settings`.
+    ///
+    /// Fails when a named object was not calculated, or for horizon constraints on a chart
+    /// without houses.
+    pub fn evaluate(&self, positions: &LayerPositions, orb_settings: &HashMap<String, f64>) -> Result<bool, String> {
+        let planet = |id: &str| {
+            positions
+                .planets
+                .get(id)
+                .ok_or_else(|| format!("{} is not among the calculated objects", id))
+        };
+        match self {
+            ElectionalConstraint::MoonVoid { void } => Ok(is_moon_void_of_course(positions)? == *void),
+            ElectionalConstraint::Horizon { planet: id, above } => {
+                let asc = positions
+                    .houses
+                    .as_ref()
+                    .and_then(|houses| houses.angles.get("asc"))
+                    .ok_or_else(|| "Horizon constraints need a location".to_string())?;
+                // Houses 7-12 lie above the horizon
+                let is_above = (planet(id)?.lon - asc).rem_euclid(360.0) >= 180.0;
+                Ok(is_above == *above)
+            }
+            ElectionalConstraint::InSigns { planet: id, signs, negated } => {
+                let sign = sign_name(planet(id)?.lon);
+                Ok(signs.iter().any(|s| s == sign) != *negated)
+            }
+            ElectionalConstraint::Retrograde { planet: id, retrograde } => Ok(planet(id)?.retrograde == *retrograde),
+            ElectionalConstraint::Aspect { from, to, aspect_types, present } => {
+                let (p1, p2) = (planet(from)?, planet(to)?);
+                let found = AspectCalculator::new()
+                    .calculate_aspect(p1.lon, p2.lon, p1.speed_lon, p2.speed_lon, orb_settings)
+                    .is_some_and(|aspect| aspect_types.contains(&aspect.aspect_type));
+                Ok(found == *present)
+            }
+        }
+    }
+}
+
+/// Whether the Moon makes no further major aspect to the traditional planets before it
+/// leaves its sign.
+///
+/// Motion is projected linearly from the current speeds, which is accurate to well under
+/// a degree over the two and a half days the Moon spends in a sign.
+pub fn is_moon_void_of_course(positions: &LayerPositions) -> Result<bool, String> {
+    let moon = positions
+        .planets
+        .get("moon")
+        .ok_or_else(|| "moon is not among the calculated objects".to_string())?;
+    if moon.speed_lon <= 0.0 {
+        return Err("The Moon's speed is needed for void of course".to_string());
+    }
+    let lon = moon.lon.rem_euclid(360.0);
+    let days_left = ((lon / 30.0).floor() * 30.0 + 30.0 - lon) / moon.speed_lon;
+
+    for id in VOID_OF_COURSE_PLANETS {
+        let Some(planet) = positions.planets.get(*id) else {
+            continue;
+        };
+        let relative_speed = moon.speed_lon - planet.speed_lon;
+        if relative_speed <= 0.0 {
+            continue;
+        }
+        let separation = (lon - planet.lon).rem_euclid(360.0);
+        let perfects = VOID_OF_COURSE_ANGLES
+            .iter()
+            .any(|angle| (angle - separation).rem_euclid(360.0) / relative_speed <= days_left);
+        if perfects {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::{HousePositions, PlanetPosition};
+
+    fn planet(lon: f64, speed_lon: f64) -> PlanetPosition {
+        PlanetPosition { lon, lat: 0.0, speed_lon, retrograde: speed_lon < 0.0 }
+    }
+
+    fn chart(planets: &[(&str, f64, f64)]) -> LayerPositions {
+        LayerPositions {
+            planets: planets.iter().map(|(id, lon, speed)| (id.to_string(), planet(*lon, *speed))).collect(),
+            houses: Some(HousePositions {
+                system: "whole_sign".to_string(),
+                cusps: HashMap::new(),
+                angles: HashMap::from([("asc".to_string(), 90.0)]),
+            }),
+        }
+    }
+
+    fn orbs() -> HashMap<String, f64> {
+        crate::aspects::DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect()
+    }
+
+    #[test]
+    fn test_parse_constraints() {
+        assert_eq!(parse_constraint("Moon not void").unwrap(), ElectionalConstraint::MoonVoid { void: false });
+        assert_eq!(
+            parse_constraint("venus above horizon").unwrap(),
+            ElectionalConstraint::Horizon { planet: "venus".to_string(), above: true }
+        );
+        assert_eq!(
+            parse_constraint("moon in taurus|cancer").unwrap(),
+            ElectionalConstraint::InSigns {
+                planet: "moon".to_string(),
+                signs: vec!["taurus".to_string(), "cancer".to_string()],
+                negated: false,
+            }
+        );
+        assert_eq!(
+            parse_constraint("mercury direct").unwrap(),
+            ElectionalConstraint::Retrograde { planet: "mercury".to_string(), retrograde: false }
+        );
+        assert_eq!(
+            parse_constraint("no Mars-Saturn hard aspects").unwrap(),
+            ElectionalConstraint::Aspect {
+                from: "mars".to_string(),
+                to: "saturn".to_string(),
+                aspect_types: vec!["conjunction".to_string(), "opposition".to_string(), "square".to_string()],
+                present: false,
+            }
+        );
+        assert_eq!(
+            parse_constraint("venus trines jupiter").unwrap(),
+            ElectionalConstraint::Aspect {
+                from: "venus".to_string(),
+                to: "jupiter".to_string(),
+                aspect_types: vec!["trine".to_string()],
+                present: true,
+            }
+        );
+
+        assert_eq!(parse_constraint("vulcan above horizon").unwrap_err(), "Unknown object 'vulcan' in 'vulcan above horizon'");
+        assert_eq!(parse_constraint("moon in narnia").unwrap_err(), "Unknown sign 'narnia' in 'moon in narnia'");
+        assert_eq!(parse_constraint("no mars saturn").unwrap_err(), "Unrecognized constraint 'no mars saturn'");
+        assert!(parse_constraint("").is_err());
+
+        assert_eq!(parse_constraint("no sun-moon aspects").unwrap().objects(), vec!["sun", "moon"]);
+        assert_eq!(parse_constraint("moon void").unwrap().objects().len(), 7);
+    }
+
+    #[test]
+    fn test_moon_void_of_course() {
+        // The Moon at 25° Aries reaches the Sun's sextile only after leaving Aries
+        assert!(is_moon_void_of_course(&chart(&[("moon", 25.0, 13.0), ("sun", 0.0, 1.0)])).unwrap());
+        // ... but perfects a trine to Mars at 28° Cancer first
+        assert!(!is_moon_void_of_course(&chart(&[("moon", 25.0, 13.0), ("sun", 0.0, 1.0), ("mars", 118.0, 0.5)])).unwrap());
+        // A just-separated aspect does not count
+        assert!(is_moon_void_of_course(&chart(&[("moon", 25.0, 13.0), ("mars", 114.0, 0.5)])).unwrap());
+        assert!(is_moon_void_of_course(&chart(&[("sun", 0.0, 1.0)])).is_err());
+    }
+
+    #[test]
+    fn test_evaluate_constraints() {
+        let positions = chart(&[("moon", 25.0, 13.0), ("venus", 300.0, 1.2), ("mars", 10.0, -0.3), ("saturn", 100.0, 0.1)]);
+        let holds = |text: &str| parse_constraint(text).unwrap().evaluate(&positions, &orbs()).unwrap();
+
+        // Ascendant at 0° Cancer: Venus at 0° Aquarius is in the 8th house, the Moon in the 10th
+        assert!(holds("venus above horizon"));
+        assert!(!holds("saturn above horizon"));
+        assert!(holds("moon in aries|taurus"));
+        assert!(holds("mars not in cancer"));
+        assert!(holds("mars retrograde"));
+        assert!(!holds("venus retrograde"));
+        // Mars squares Saturn within 6°
+        assert!(!holds("no mars-saturn hard aspects"));
+        assert!(holds("no mars-saturn soft aspects"));
+        assert!(holds("mars square saturn"));
+
+        let missing = parse_constraint("jupiter direct").unwrap().evaluate(&positions, &orbs());
+        assert_eq!(missing.unwrap_err(), "jupiter is not among the calculated objects");
+    }
+}
@@ -0,0 +1,7 @@
+pub mod constraints;
+pub mod search;
+pub mod types;
+
+pub use constraints::{is_moon_void_of_course, parse_constraint};
+pub use search::search_elections;
+pub use types::{ElectionalConstraint, ElectionalWindow};
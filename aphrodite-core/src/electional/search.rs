@@ -0,0 +1,79 @@
+//! Electional search over a range of calculated charts.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use crate::electional::types::{ElectionalConstraint, ElectionalWindow};
+use crate::ephemeris::types::LayerPositions;
+
+/// Merge consecutive samples that meet every constraint into windows.
+///
+/// `samples` are chart moments in chronological order, as returned by a range
+/// calculation. A window ends at the first failing sample, or at `end` when it is still
+/// open after the last one, so boundaries are accurate to the sampling step.
+pub fn search_elections(
+    samples: &[(DateTime<Utc>, LayerPositions)],
+    end: DateTime<Utc>,
+    constraints: &[ElectionalConstraint],
+    orb_settings: &HashMap<String, f64>,
+) -> Result<Vec<ElectionalWindow>, String> {
+    let mut windows = Vec::new();
+    let mut open: Option<DateTime<Utc>> = None;
+    for (moment, positions) in samples {
+        let mut passes = true;
+        for constraint in constraints {
+            if !constraint.evaluate(positions, orb_settings)? {
+                passes = false;
+                break;
+            }
+        }
+        match (passes, open) {
+            (true, None) => open = Some(*moment),
+            (false, Some(start)) => {
+                windows.push(ElectionalWindow { start, end: *moment });
+                open = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = open {
+        windows.push(ElectionalWindow { start, end });
+    }
+    Ok(windows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::electional::parse_constraint;
+    use crate::ephemeris::types::PlanetPosition;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_windows_follow_passing_samples() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        // The Moon at 0° Aries, moving 12° per sample: Aries, Aries, Aries, Taurus, ...
+        let samples: Vec<_> = (0..8)
+            .map(|i| {
+                let moon = PlanetPosition { lon: i as f64 * 12.0, lat: 0.0, speed_lon: 13.0, retrograde: false };
+                let positions = LayerPositions { planets: HashMap::from([("moon".to_string(), moon)]), houses: None };
+                (start + Duration::hours(i), positions)
+            })
+            .collect();
+        let end = start + Duration::hours(8);
+        let constraints = [parse_constraint("moon in aries|gemini").unwrap()];
+
+        let windows = search_elections(&samples, end, &constraints, &HashMap::new()).unwrap();
+        assert_eq!(
+            windows,
+            vec![
+                ElectionalWindow { start, end: start + Duration::hours(3) },
+                ElectionalWindow { start: start + Duration::hours(5), end },
+            ]
+        );
+
+        // No constraints: the whole range
+        assert_eq!(search_elections(&samples, end, &[], &HashMap::new()).unwrap(), vec![ElectionalWindow { start, end }]);
+        let horizon = [parse_constraint("moon above horizon").unwrap()];
+        assert!(search_elections(&samples, end, &horizon, &HashMap::new()).is_err());
+    }
+}
@@ -0,0 +1,33 @@
+//! Electional search types.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A condition a chart moment must meet, parsed from the constraint DSL
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ElectionalConstraint {
+    /// `moon void` / `moon not void`
+    MoonVoid { void: bool },
+    /// `<planet> above horizon` / `<planet> below horizon`
+    Horizon { planet: String, above: bool },
+    /// `<planet> in <sign>[|<sign>...]` / `<planet> not in <sign>...`
+    InSigns { planet: String, signs: Vec<String>, negated: bool },
+    /// `<planet> retrograde` / `<planet> direct` / `<planet> not retrograde`
+    Retrograde { planet: String, retrograde: bool },
+    /// `<planet> <aspect> <planet>` / `no <planet>-<planet> [hard|soft|<aspect>] aspects`
+    Aspect {
+        from: String,
+        to: String,
+        #[serde(rename = "aspectTypes")]
+        aspect_types: Vec<String>,
+        present: bool,
+    },
+}
+
+/// A stretch of time in which every constraint holds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ElectionalWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
@@ -1,35 +1,27 @@
+use super::files::{coverage, installed_files, EphemerisFile, EphemerisFileKind};
+use super::systems::{get_house_system_byte, J2000};
+use super::progressions::{progressed_positions, ProgressionOptions};
 use crate::ephemeris::types::{
-    EphemerisSettings, GeoLocation, HousePositions, LayerPositions, PlanetPosition,
+    EphemerisSettings, GeoLocation, HousePositions, LayerPositions, MoonPosition, PlanetPhenomena, PlanetPosition,
+    PositionWarning, HOUSE_POINTS,
 };
-use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc};
+use chrono::{DateTime, Datelike, Duration, TimeZone, Timelike, Utc};
 use std::collections::HashMap;
 use std::env;
-use std::path::PathBuf;
-use thiserror::Error;
-use swisseph::swe::{calc_ut, julday, revjul};
+use std::path::{Path, PathBuf};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use swisseph::swe::{calc_ut, get_ayanamsa_ut, julday, revjul};
+
+pub use super::error::EphemerisError;
+pub use super::systems::{
+    is_supported_ayanamsa, supported_ayanamsa_names, AYANAMSAS, CUSTOM_AYANAMSA, DEFAULT_AYANAMSA,
+};
 
 // Note: swisseph crate API - these constants and functions should be available
 // If the crate API differs, adjust accordingly
 
-/// Errors that can occur during ephemeris calculations
-#[derive(Error, Debug)]
-pub enum EphemerisError {
-    #[error("Ephemeris file not found at path: {path}. {message}")]
-    FileNotFound { path: String, message: String },
-    #[error("Invalid house system: {system}. Valid systems: {valid:?}")]
-    InvalidHouseSystem { system: String, valid: Vec<String> },
-    #[error("Invalid ayanamsa: {ayanamsa}. Valid ayanamsas: {valid:?}")]
-    InvalidAyanamsa { ayanamsa: String, valid: Vec<String> },
-    #[error("Failed to calculate position for {planet_id} at {datetime}: {message}")]
-    CalculationFailed {
-        planet_id: String,
-        datetime: DateTime<Utc>,
-        message: String,
-    },
-    #[error("House calculation failed: {message}")]
-    HouseCalculationFailed { message: String },
-}
-
 // Swiss Ephemeris planet IDs - adjust based on actual swisseph crate API
 // Typical values: SUN=0, MOON=1, MERCURY=2, VENUS=3, MARS=4, JUPITER=5,
 // SATURN=6, URANUS=7, NEPTUNE=8, PLUTO=9, CHIRON=15, TRUE_NODE=11
@@ -46,41 +38,43 @@ const PLANET_IDS: &[(&str, i32)] = &[
     ("pluto", 9),
     ("chiron", 15),
     ("north_node", 11), // TRUE_NODE
+    ("true_node", 11),
+    ("mean_node", 10),         // MEAN_NODE
+    ("mean_apogee", 12),       // MEAN_APOG, Black Moon Lilith
+    ("osculating_apogee", 13), // OSCU_APOG
 ];
 
-/// House system mapping
-const HOUSE_SYSTEMS: &[(&str, u8)] = &[
-    ("placidus", b'P' as u8),
-    ("whole_sign", b'W' as u8),
-    ("koch", b'K' as u8),
-    ("equal", b'E' as u8),
-    ("regiomontanus", b'R' as u8),
-    ("campanus", b'C' as u8),
-    ("alcabitius", b'A' as u8),
-    ("morinus", b'M' as u8),
-];
+/// Calculated points without a disc to observe
+const NO_PHENOMENA: &[&str] = &["north_node", "true_node", "mean_node", "mean_apogee", "osculating_apogee"];
 
-/// Ayanamsa mapping - using Swiss Ephemeris constants
-/// These values match the Swiss Ephemeris library constants
-const AYANAMSAS: &[(&str, i32)] = &[
-    ("lahiri", 1),      // SIDM_LAHIRI
-    ("chitrapaksha", 1), // SIDM_LAHIRI (same as Lahiri)
-    ("fagan_bradley", 2), // SIDM_FAGAN_BRADLEY
-    ("de_luce", 3),     // SIDM_DELUCE
-    ("raman", 4),       // SIDM_RAMAN
-    ("krishnamurti", 5), // SIDM_KRISHNAMURTI
-    ("yukteshwar", 6),  // SIDM_YUKTESHWAR
-    ("djwhal_khul", 7), // SIDM_DJWHAL_KHUL
-    ("true_citra", 8),  // SIDM_TRUE_CITRA
-    ("true_revati", 9), // SIDM_TRUE_REVATI
-    ("aryabhata", 10),  // SIDM_ARYABHATA
-    ("aryabhata_mean_sun", 11), // SIDM_ARYABHATA_MSUN
-];
+/// SE_SIDM_USER: ayanamsa given as a value at a reference epoch
+const SIDM_USER: i32 = 255;
+
+/// Sidereal mode passed to swe_set_sid_mode: (mode, t0, ayan_t0)
+type SiderealMode = (i32, f64, f64);
 
 /// Swiss Ephemeris adapter implementation
 pub struct SwissEphemerisAdapter {
-    _ephemeris_path: PathBuf,
-    current_sidereal_mode: Option<i32>,
+    ephemeris_path: PathBuf,
+    /// Ephemeris files found in `ephemeris_path`, listed again after [`reload_ephemeris_files`]
+    installed: Mutex<InstalledFiles>,
+}
+
+/// Listing of the ephemeris directory, and the [`FILES_GENERATION`] it was taken in
+struct InstalledFiles {
+    generation: u64,
+    files: Vec<EphemerisFile>,
+}
+
+impl InstalledFiles {
+    /// An unreadable directory counts as empty: every date then falls back to Moshier
+    fn list(path: &Path) -> Self {
+        let generation = FILES_GENERATION.load(Ordering::Acquire);
+        let files = installed_files(path)
+            .map(|files| files.into_iter().map(|installed| installed.file).collect())
+            .unwrap_or_default();
+        Self { generation, files }
+    }
 }
 
 impl SwissEphemerisAdapter {
@@ -100,72 +94,217 @@ impl SwissEphemerisAdapter {
             });
         }
 
-        // Set ephemeris path
-        // Note: Adjust based on actual swisseph crate API
-        // Typical API: swisseph::set_ephe_path(path_str)
-        let _path_str = path.to_string_lossy();
-        // This will need to be adjusted based on the actual crate API
-        // For now, we'll assume the path is set correctly
+        // Calculations set it again on threads that haven't read from this path yet
+        use_ephemeris_path(&path);
 
+        let installed = Mutex::new(InstalledFiles::list(&path));
         Ok(Self {
-            _ephemeris_path: path,
-            current_sidereal_mode: None,
+            ephemeris_path: path,
+            installed,
         })
     }
 
-    /// Calculate planetary and house positions
+    /// Directory the ephemeris files are read from
+    pub fn ephemeris_path(&self) -> &Path {
+        &self.ephemeris_path
+    }
+
+    /// `swiss_ephemeris` when planet files are installed, otherwise `moshier`
+    pub fn backend(&self) -> &'static str {
+        if self.with_installed(|files| coverage(files, EphemerisFileKind::Planets).is_empty()) {
+            "moshier"
+        } else {
+            "swiss_ephemeris"
+        }
+    }
+
+    /// Run `f` on the installed files, listing the directory again if files were added since
+    fn with_installed<R>(&self, f: impl FnOnce(&[EphemerisFile]) -> R) -> R {
+        let mut installed = self.installed.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if installed.generation != FILES_GENERATION.load(Ordering::Acquire) {
+            *installed = InstalledFiles::list(&self.ephemeris_path);
+        }
+        f(&installed.files)
+    }
+
+    /// Reject a date outside the years covered by the installed planet and Moon files.
+    /// Without any files of a kind, calculations use the Moshier fallback and are not checked.
+    pub fn check_coverage(&self, dt_utc: DateTime<Utc>) -> Result<(), EphemerisError> {
+        let year = dt_utc.year();
+        for kind in [EphemerisFileKind::Planets, EphemerisFileKind::Moon] {
+            let ranges = self.with_installed(|files| coverage(files, kind));
+            if !ranges.is_empty() && !ranges.iter().any(|range| range.contains(year)) {
+                let coverage = ranges
+                    .iter()
+                    .map(|range| format!("{}-{}", range.start_year, range.end_year))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                return Err(EphemerisError::OutsideCoverage {
+                    date: dt_utc.format("%Y-%m-%d").to_string(),
+                    coverage,
+                    file: EphemerisFile::covering(kind, year).name,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Calculate planetary and house positions; objects that fail to calculate are left out
     pub fn calc_positions(
         &mut self,
         dt_utc: DateTime<Utc>,
         location: Option<GeoLocation>,
         settings: &EphemerisSettings,
     ) -> Result<LayerPositions, EphemerisError> {
+        self.calc_positions_with_warnings(dt_utc, location, settings).map(|(positions, _)| positions)
+    }
+
+    /// Calculate planetary and house positions, with a warning for each included object that
+    /// was left out because its calculation failed, such as Chiron without its ephemeris file
+    pub fn calc_positions_with_warnings(
+        &mut self,
+        dt_utc: DateTime<Utc>,
+        location: Option<GeoLocation>,
+        settings: &EphemerisSettings,
+    ) -> Result<(LayerPositions, Vec<PositionWarning>), EphemerisError> {
+        self.check_coverage(dt_utc)?;
         let jd = datetime_to_julian_day(dt_utc);
         let house_system_byte = get_house_system_byte(&settings.house_system)?;
         let flags = self.configure_flags(settings)?;
+        if let Some(loc) = location.as_ref().filter(|_| settings.moon_position == MoonPosition::Topocentric) {
+            // Observer for SEFLG_TOPOCTR; the altitude is not known, so sea level.
+            // SAFETY: swe_set_topo takes no pointers and only stores the observer in the Swiss
+            // Ephemeris state, which the topocentric calls below read back. It is per thread
+            // where the library is built with TLS; on Apple and Windows callers hold
+            // lock_swiss_state() so another thread can't move the observer in between.
+            unsafe { libswisseph_sys::swe_set_topo(loc.lon, loc.lat, 0.0) };
+        }
+        let body_flags = |body_id: &str| {
+            if location.is_some() && settings.is_topocentric(body_id) {
+                flags | libswisseph_sys::SEFLG_TOPOCTR as i32 // 32 * 1024
+            } else {
+                flags
+            }
+        };
 
         // Calculate planets
         let mut planets = HashMap::new();
+        let mut warnings = Vec::new();
         for obj_id in &settings.include_objects {
             let obj_id_lower = obj_id.to_lowercase();
 
             // Handle special case: south_node
-            if obj_id_lower == "south_node" {
-                if let Ok(north_node_pos) = self.calc_planet_position("north_node", jd, flags) {
-                    let south_lon = (north_node_pos.lon + 180.0) % 360.0;
-                    planets.insert(
-                        "south_node".to_string(),
-                        PlanetPosition {
-                            lon: south_lon,
-                            lat: 0.0,
-                            speed_lon: north_node_pos.speed_lon,
-                            retrograde: north_node_pos.retrograde,
-                        },
-                    );
+            let position = if obj_id_lower == "south_node" {
+                self.calc_planet_position(settings.body_id("north_node"), jd, flags).map(|north_node_pos| {
+                    PlanetPosition {
+                        lon: (north_node_pos.lon + 180.0) % 360.0,
+                        lat: 0.0,
+                        speed_lon: north_node_pos.speed_lon,
+                        retrograde: north_node_pos.retrograde,
+                    }
+                })
+            } else {
+                let body_id = settings.body_id(&obj_id_lower);
+                self.calc_planet_position(body_id, jd, body_flags(body_id))
+            };
+            match position {
+                Ok(planet_pos) => {
+                    planets.insert(obj_id_lower, planet_pos);
                 }
-                continue;
-            }
-
-            if let Ok(planet_pos) = self.calc_planet_position(&obj_id_lower, jd, flags) {
-                planets.insert(obj_id_lower.clone(), planet_pos);
+                Err(e) => warnings.push(PositionWarning { object_id: obj_id_lower, message: e.to_string() }),
             }
         }
 
-        // Calculate houses if location is provided
+        // Calculate houses if location is provided; Placidus and Koch give way to the polar
+        // fallback where they are undefined, and `system` names the one used
         let houses = if let Some(loc) = location {
-            Some(self.calc_houses(
-                jd,
-                loc.lat,
-                loc.lon,
-                house_system_byte,
-                &settings.house_system,
-                flags,
-            )?)
+            let houses = match self.calc_houses(jd, loc.lat, loc.lon, house_system_byte, &settings.house_system, flags) {
+                Err(EphemerisError::UndefinedHouseSystem { .. }) => {
+                    let fallback = settings.polar_fallback.house_system();
+                    self.calc_houses(jd, loc.lat, loc.lon, get_house_system_byte(fallback)?, fallback, flags)
+                }
+                houses => houses,
+            };
+            Some(houses?)
         } else {
             None
         };
 
-        Ok(LayerPositions { planets, houses })
+        Ok((LayerPositions { planets, houses }, warnings))
+    }
+
+    /// Calculate secondary progressed positions of a birth for `target`; see
+    /// [`progressions::progressed_positions`](super::progressions::progressed_positions)
+    pub fn calc_progressed_positions(
+        &mut self,
+        birth: DateTime<Utc>,
+        target: DateTime<Utc>,
+        location: Option<GeoLocation>,
+        settings: &EphemerisSettings,
+        options: &ProgressionOptions,
+    ) -> Result<LayerPositions, EphemerisError> {
+        self.calc_progressed_positions_with_warnings(birth, target, location, settings, options)
+            .map(|(positions, _)| positions)
+    }
+
+    /// [`calc_progressed_positions`](Self::calc_progressed_positions) with the warnings of
+    /// [`calc_positions_with_warnings`](Self::calc_positions_with_warnings)
+    pub fn calc_progressed_positions_with_warnings(
+        &mut self,
+        birth: DateTime<Utc>,
+        target: DateTime<Utc>,
+        location: Option<GeoLocation>,
+        settings: &EphemerisSettings,
+        options: &ProgressionOptions,
+    ) -> Result<(LayerPositions, Vec<PositionWarning>), EphemerisError> {
+        // Later passes only recompute the Sun and houses, so keep each object's first warning
+        let mut warnings: Vec<PositionWarning> = Vec::new();
+        let positions = progressed_positions(birth, target, location, settings, options, |dt, location, settings| {
+            let (positions, pass_warnings) = self.calc_positions_with_warnings(dt, location, settings)?;
+            for warning in pass_warnings {
+                if !warnings.iter().any(|w| w.object_id == warning.object_id) {
+                    warnings.push(warning);
+                }
+            }
+            Ok(positions)
+        })?;
+        Ok((positions, warnings))
+    }
+
+    /// Calculate positions every `step` from `start` (inclusive) to `end` (exclusive); empty
+    /// when `step` is not positive
+    pub fn calc_positions_range(
+        &mut self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        step: Duration,
+        location: Option<GeoLocation>,
+        settings: &EphemerisSettings,
+    ) -> Result<Vec<(DateTime<Utc>, LayerPositions)>, EphemerisError> {
+        let mut samples = Vec::new();
+        if step <= Duration::zero() {
+            return Ok(samples);
+        }
+        let mut moment = start;
+        while moment < end {
+            samples.push((moment, self.calc_positions(moment, location.clone(), settings)?));
+            moment += step;
+        }
+        Ok(samples)
+    }
+
+    /// Ayanamsa in degrees applied at a moment, or None for the tropical zodiac
+    pub fn calc_ayanamsa(
+        &mut self,
+        dt_utc: DateTime<Utc>,
+        settings: &EphemerisSettings,
+    ) -> Result<Option<f64>, EphemerisError> {
+        if settings.zodiac_type != "sidereal" {
+            return Ok(None);
+        }
+        let mode = self.resolve_ayanamsa(settings.ayanamsa.as_deref(), settings.custom_ayanamsa)?;
+        set_sidereal_mode(mode);
+        Ok(Some(get_ayanamsa_ut(datetime_to_julian_day(dt_utc))))
     }
 
     /// Calculate position for a single planet
@@ -185,12 +324,14 @@ impl SwissEphemerisAdapter {
                 message: format!("Unknown planet ID: {}", planet_id),
             })?;
 
+        use_ephemeris_path(&self.ephemeris_path);
         // Calculate planet position using swisseph crate
         let result = calc_ut(jd, planet_code as u32, flags as u32)
             .map_err(|e| EphemerisError::CalculationFailed {
                 planet_id: planet_id.to_string(),
                 datetime: julian_day_to_datetime(jd),
-                message: format!("Swiss Ephemeris error: {}", e),
+                // The C error buffer comes back NUL padded
+                message: format!("Swiss Ephemeris error: {}", e.split('\0').next().unwrap_or_default().trim()),
             })?;
 
         let result_array = result.out;
@@ -207,7 +348,63 @@ impl SwissEphemerisAdapter {
         })
     }
 
-    /// Calculate house cusps and angles
+    /// Calculate phase, elongation, apparent diameter and magnitude for the included
+    /// objects; objects without a disc, such as the nodes, are left out
+    pub fn calc_phenomena(
+        &mut self,
+        dt_utc: DateTime<Utc>,
+        settings: &EphemerisSettings,
+    ) -> Result<HashMap<String, PlanetPhenomena>, EphemerisError> {
+        let jd = datetime_to_julian_day(dt_utc);
+        let mut phenomena = HashMap::new();
+        for obj_id in &settings.include_objects {
+            let obj_id_lower = obj_id.to_lowercase();
+            if let Ok(planet_phenomena) = self.calc_planet_phenomena(settings.body_id(&obj_id_lower), jd) {
+                phenomena.insert(obj_id_lower, planet_phenomena);
+            }
+        }
+        Ok(phenomena)
+    }
+
+    /// Calculate the phenomena of a single planet
+    pub fn calc_planet_phenomena(&self, planet_id: &str, jd: f64) -> Result<PlanetPhenomena, EphemerisError> {
+        let failed = |message: String| EphemerisError::CalculationFailed {
+            planet_id: planet_id.to_string(),
+            datetime: julian_day_to_datetime(jd),
+            message,
+        };
+        let planet_code = PLANET_IDS
+            .iter()
+            .filter(|(id, _)| !NO_PHENOMENA.contains(id))
+            .find(|(id, _)| *id == planet_id)
+            .map(|(_, code)| *code)
+            .ok_or_else(|| failed(format!("No phenomena for {}", planet_id)))?;
+
+        use_ephemeris_path(&self.ephemeris_path);
+        // swisseph 0.1 does not wrap swe_pheno_ut, so call the raw binding.
+        // SAFETY: attr has the 20 slots swe_pheno_ut may write and serr the 256 bytes of an
+        // error message.
+        let mut attr = [0.0f64; 20];
+        let mut serr = [0 as std::os::raw::c_char; 256];
+        let result =
+            unsafe { libswisseph_sys::swe_pheno_ut(jd, planet_code, 2, attr.as_mut_ptr(), serr.as_mut_ptr()) };
+        if result < 0 {
+            // SAFETY: on error swe_pheno_ut leaves a NUL-terminated message in serr
+            let message = unsafe { std::ffi::CStr::from_ptr(serr.as_ptr()) };
+            return Err(failed(format!("Swiss Ephemeris error: {}", message.to_string_lossy())));
+        }
+
+        Ok(PlanetPhenomena {
+            phase_angle: attr[0],
+            phase: attr[1],
+            elongation: attr[2],
+            apparent_diameter: attr[3],
+            magnitude: attr[4],
+        })
+    }
+
+    /// Calculate house cusps and angles; `UndefinedHouseSystem` when the system has no cusps
+    /// at this latitude
     pub fn calc_houses(
         &self,
         jd: f64,
@@ -217,12 +414,21 @@ impl SwissEphemerisAdapter {
         house_system_str: &str,
         flags: i32,
     ) -> Result<HousePositions, EphemerisError> {
-        // Calculate houses using swisseph crate
-        // Use houses_ex2 which takes HouseSystemKind, but we'll use the lower-level API
-        // Since HouseSystemKind is private, use houses_ex from swe module
-        use swisseph::swe::houses_ex;
-        let (c, a) = houses_ex(jd, flags, lat, lon, house_system_byte as i32);
-        
+        // swisseph's houses_ex drops the return code, which is the only sign that the Swiss
+        // Ephemeris swapped in Porphyry for an undefined system; call it directly
+        let mut c = [0.0; 13];
+        let mut a = [0.0; 10];
+        // SAFETY: swe_houses_ex writes cusps 1-12 after an unused slot 0, 13 doubles, and
+        // the 10 ascmc values, so both buffers are exactly the size it fills. The sidereal
+        // mode it reads is per thread where the library is built with TLS; on Apple and
+        // Windows callers hold lock_swiss_state() over the shared copy.
+        let rc = unsafe {
+            libswisseph_sys::swe_houses_ex(jd, flags, lat, lon, house_system_byte as i32, c.as_mut_ptr(), a.as_mut_ptr())
+        };
+        if rc < 0 {
+            return Err(EphemerisError::UndefinedHouseSystem { system: house_system_str.to_string(), lat });
+        }
+
         // Convert arrays to Cusp and AscMc structs
         use swisseph::{Cusp, AscMc};
         let cusps = Cusp::from_array(c);
@@ -245,61 +451,138 @@ impl SwissEphemerisAdapter {
         let mc = ascmc.mc % 360.0;
         let ic = (mc + 180.0) % 360.0;
         let dc = (asc + 180.0) % 360.0;
+        let points = [
+            ascmc.armc,
+            ascmc.vertex,
+            ascmc.equatorial_ascendant,
+            ascmc.co_ascendant_wk,
+            ascmc.co_ascendant_mm,
+            ascmc.polar_ascendant,
+        ];
+
+        let mut angles = HashMap::from([
+            ("asc".to_string(), asc),
+            ("mc".to_string(), mc),
+            ("ic".to_string(), ic),
+            ("dc".to_string(), dc),
+        ]);
+        angles.extend(HOUSE_POINTS.iter().zip(points).map(|(id, lon)| (id.to_string(), lon % 360.0)));
 
         Ok(HousePositions {
             system: house_system_str.to_string(),
             cusps: cusps_dict,
-            angles: HashMap::from([
-                ("asc".to_string(), asc),
-                ("mc".to_string(), mc),
-                ("ic".to_string(), ic),
-                ("dc".to_string(), dc),
-            ]),
+            angles,
         })
     }
 
     /// Configure Swiss Ephemeris flags for the requested zodiac
-    fn configure_flags(&mut self, settings: &EphemerisSettings) -> Result<i32, EphemerisError> {
+    fn configure_flags(&self, settings: &EphemerisSettings) -> Result<i32, EphemerisError> {
         // FLG_SWIEPH = 2 (use Swiss Ephemeris files)
         let mut flags = 2; // swisseph::FLG_SWIEPH
 
         if settings.zodiac_type == "sidereal" {
-            let mode = self.resolve_ayanamsa(settings.ayanamsa.as_deref())?;
-            self.ensure_sidereal_mode(mode)?;
-            flags |= 64; // swisseph::FLG_SIDEREAL
+            let mode = self.resolve_ayanamsa(settings.ayanamsa.as_deref(), settings.custom_ayanamsa)?;
+            set_sidereal_mode(mode);
+            flags |= libswisseph_sys::SEFLG_SIDEREAL as i32; // 64 * 1024; 64 is SEFLG_NONUT
         }
 
         Ok(flags)
     }
 
-    /// Map ayanamsa string to Swiss constant
-    fn resolve_ayanamsa(&self, ayanamsa: Option<&str>) -> Result<i32, EphemerisError> {
-        let ayanamsa = ayanamsa.unwrap_or("lahiri");
+    /// Map ayanamsa string to a Swiss sidereal mode
+    fn resolve_ayanamsa(
+        &self,
+        ayanamsa: Option<&str>,
+        custom_ayanamsa: Option<f64>,
+    ) -> Result<SiderealMode, EphemerisError> {
+        let ayanamsa = ayanamsa.unwrap_or(DEFAULT_AYANAMSA).to_lowercase();
+        if ayanamsa == CUSTOM_AYANAMSA {
+            return match custom_ayanamsa {
+                Some(value) if value.is_finite() => Ok((SIDM_USER, J2000, value)),
+                _ => Err(EphemerisError::InvalidAyanamsa {
+                    ayanamsa: format!("{} (requires a finite custom ayanamsa value)", ayanamsa),
+                    valid: supported_ayanamsa_names(),
+                }),
+            };
+        }
         AYANAMSAS
             .iter()
-            .find(|(name, _)| *name == ayanamsa.to_lowercase())
-            .map(|(_, mode)| *mode)
+            .find(|(name, _)| *name == ayanamsa)
+            .map(|(_, mode)| (*mode, 0.0, 0.0))
             .ok_or_else(|| EphemerisError::InvalidAyanamsa {
                 ayanamsa: ayanamsa.to_string(),
-                valid: AYANAMSAS.iter().map(|(name, _)| name.to_string()).collect(),
+                valid: supported_ayanamsa_names(),
             })
     }
+}
+
+/// Serialize Swiss Ephemeris calculations where the library's state is shared.
+///
+/// `sweodef.h` keeps the Swiss Ephemeris state (sidereal mode, observer, open files) in
+/// thread-local storage, except on Apple and Windows builds, where a single copy is shared
+/// by every thread. There the returned guard must be held across each calculation, from
+/// setting the mode to reading the results, so threads don't race on that copy. Elsewhere
+/// no lock is needed and `None` is returned.
+pub fn lock_swiss_state() -> Option<MutexGuard<'static, ()>> {
+    if cfg!(any(target_vendor = "apple", windows)) {
+        static SWISS_STATE: Mutex<()> = Mutex::new(());
+        Some(SWISS_STATE.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+    } else {
+        None
+    }
+}
 
-    /// Cache sidereal mode configuration to avoid redundant calls
-    fn ensure_sidereal_mode(&mut self, mode: i32) -> Result<(), EphemerisError> {
-        if self.current_sidereal_mode == Some(mode) {
-            return Ok(());
+/// Bumped by [`reload_ephemeris_files`] so each Swiss Ephemeris state sets its path again
+static FILES_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Make the Swiss Ephemeris reopen its data files, e.g. after files were added to the
+/// ephemeris directory; takes effect on each thread's next calculation
+pub fn reload_ephemeris_files() {
+    FILES_GENERATION.fetch_add(1, Ordering::AcqRel);
+}
+
+/// Point the Swiss Ephemeris at `path` unless it already reads from there.
+///
+/// The path lives in the same state as the sidereal mode (see [`lock_swiss_state`]), so it
+/// is tracked per thread where that state is thread-local and once per process on Apple and
+/// Windows. Setting it closes the open files, so it is only set again for another path or
+/// after [`reload_ephemeris_files`].
+fn use_ephemeris_path(path: &Path) {
+    type Current = Option<(PathBuf, u64)>;
+    let generation = FILES_GENERATION.load(Ordering::Acquire);
+    let update = |current: &mut Current| {
+        if current.as_ref().is_none_or(|(set, set_generation)| set != path || *set_generation != generation) {
+            swisseph::swe::set_ephe_path(&path.to_string_lossy());
+            *current = Some((path.to_path_buf(), generation));
         }
-        // Set sidereal mode
-        // Note: set_sid_mode may not be available in swisseph 0.1.x
-        // The sidereal mode is typically set via flags, so we'll skip explicit mode setting
-        // If needed, we can add it when the function is available
-        // For now, the flags should handle sidereal calculations
-        self.current_sidereal_mode = Some(mode);
-        Ok(())
+    };
+
+    if cfg!(any(target_vendor = "apple", windows)) {
+        static SHARED: Mutex<Current> = Mutex::new(None);
+        update(&mut SHARED.lock().unwrap_or_else(|poisoned| poisoned.into_inner()));
+    } else {
+        thread_local! {
+            static PER_THREAD: RefCell<Current> = const { RefCell::new(None) };
+        }
+        PER_THREAD.with(|current| update(&mut current.borrow_mut()));
     }
 }
 
+/// Select the sidereal mode for the next calculation.
+///
+/// The mode is set before every sidereal calculation rather than remembered per
+/// adapter, because other adapters may change it meanwhile. Swiss Ephemeris keeps it
+/// in thread-local state on Linux and Android, but `sweodef.h` disables TLS on Apple
+/// and Windows builds, where the mode is process-global and shared by every thread.
+fn set_sidereal_mode((sid_mode, t0, ayan_t0): SiderealMode) {
+    // swisseph 0.1 does not wrap swe_set_sid_mode, so call the raw binding.
+    // SAFETY: swe_set_sid_mode takes no pointers and only stores the mode in the Swiss
+    // Ephemeris state: the calling thread's copy where the library is built with TLS, the
+    // single process-global copy on Apple and Windows. On those platforms callers hold
+    // lock_swiss_state() so calculations don't race on that copy.
+    unsafe { libswisseph_sys::swe_set_sid_mode(sid_mode, t0, ayan_t0) };
+}
+
 /// Convert UTC datetime to Julian Day
 fn datetime_to_julian_day(dt: DateTime<Utc>) -> f64 {
     let year = dt.year();
@@ -328,16 +611,3 @@ fn julian_day_to_datetime(jd: f64) -> DateTime<Utc> {
         .single()
         .unwrap_or_else(|| chrono::Utc::now())
 }
-
-/// Convert house system string to byte format
-fn get_house_system_byte(house_system: &str) -> Result<u8, EphemerisError> {
-    HOUSE_SYSTEMS
-        .iter()
-        .find(|(name, _)| *name == house_system.to_lowercase())
-        .map(|(_, byte)| *byte)
-        .ok_or_else(|| EphemerisError::InvalidHouseSystem {
-            system: house_system.to_string(),
-            valid: HOUSE_SYSTEMS.iter().map(|(name, _)| name.to_string()).collect(),
-        })
-}
-
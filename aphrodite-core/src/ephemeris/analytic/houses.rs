@@ -0,0 +1,303 @@
+//! House cusps from sidereal time, latitude and obliquity, following the formulas used by
+//! the Swiss Ephemeris (swehouse.c). Placidus and Koch fall back to Porphyry inside the
+//! polar circles, where they are undefined.
+
+use super::time::normalize;
+
+const VERY_SMALL: f64 = 1e-10;
+
+fn sind(x: f64) -> f64 {
+    x.to_radians().sin()
+}
+
+fn cosd(x: f64) -> f64 {
+    x.to_radians().cos()
+}
+
+fn tand(x: f64) -> f64 {
+    x.to_radians().tan()
+}
+
+fn atand(x: f64) -> f64 {
+    x.atan().to_degrees()
+}
+
+fn asind(x: f64) -> f64 {
+    x.clamp(-1.0, 1.0).asin().to_degrees()
+}
+
+/// Ecliptic longitude where the great circle through the east point of a horizon with pole
+/// height `f` meets the ecliptic, for right ascension `x` of that point
+fn asc1(x: f64, f: f64, sine: f64, cose: f64) -> f64 {
+    let x = normalize(x);
+    let quadrant = (x / 90.0) as i32 + 1;
+    let asc = match quadrant {
+        1 => asc2(x, f, sine, cose),
+        2 => 180.0 - asc2(180.0 - x, -f, sine, cose),
+        3 => 180.0 + asc2(x - 180.0, -f, sine, cose),
+        _ => 360.0 - asc2(360.0 - x, f, sine, cose),
+    };
+    normalize(asc)
+}
+
+fn asc2(x: f64, f: f64, sine: f64, cose: f64) -> f64 {
+    let mut ass = -tand(f) * sine + cose * cosd(x);
+    if ass.abs() < VERY_SMALL {
+        ass = 0.0;
+    }
+    let mut sinx = sind(x);
+    if sinx.abs() < VERY_SMALL {
+        sinx = 0.0;
+    }
+    let mut asc = if sinx == 0.0 {
+        if ass < 0.0 {
+            -VERY_SMALL
+        } else {
+            VERY_SMALL
+        }
+    } else if ass == 0.0 {
+        if sinx < 0.0 {
+            -90.0
+        } else {
+            90.0
+        }
+    } else {
+        atand(sinx / ass)
+    };
+    if asc < 0.0 {
+        asc += 180.0;
+    }
+    asc
+}
+
+/// Ecliptic longitude of the point with right ascension `ra` on the ecliptic
+fn ecliptic_from_ra(ra: f64, cose: f64) -> f64 {
+    normalize(sind(ra).atan2(cosd(ra) * cose).to_degrees())
+}
+
+/// Whether a system has no cusps at a latitude: Placidus and Koch inside the polar circles,
+/// where some degrees of the ecliptic never rise
+pub fn is_undefined(system: u8, lat: f64, obliquity: f64) -> bool {
+    matches!(system, b'P' | b'K') && lat.abs() >= 90.0 - obliquity
+}
+
+/// Cusps "1".."12" (index 0 = first house), ascendant and MC in degrees
+pub struct Cusps {
+    pub cusps: [f64; 12],
+    pub asc: f64,
+    pub mc: f64,
+    /// Vertex, equatorial ascendant, Koch and Munkasey co-ascendants and polar ascendant, in
+    /// the order of `HOUSE_POINTS` after the ARMC
+    pub points: [f64; 5],
+}
+
+/// Calculate cusps for a Swiss house system code (see `systems::HOUSE_SYSTEMS`).
+///
+/// Results are measured from `zodiac_offset` (the ayanamsa for sidereal charts), so whole
+/// sign houses start at the sign holding the sidereal ascendant.
+pub fn calc(armc: f64, lat: f64, obliquity: f64, system: u8, zodiac_offset: f64) -> Cusps {
+    let th = normalize(armc);
+    let (sine, cose) = (sind(obliquity), cosd(obliquity));
+    let mut mc = ecliptic_from_ra(th, cose);
+    let mut asc = asc1(th + 90.0, lat, sine, cose);
+
+    // Inside the polar circles the ascendant can fall west of the meridian; use its opposite
+    let polar = lat.abs() >= 90.0 - obliquity;
+    let flipped = polar && normalize(asc - mc) > 180.0;
+    if flipped {
+        asc = normalize(asc + 180.0);
+    }
+    // Campanus and Regiomontanus turn the whole house circle, MC included
+    let turn = if flipped { 180.0 } else { 0.0 };
+
+    let mut cusps = [0.0; 12];
+    match system {
+        b'E' => {
+            for (i, cusp) in cusps.iter_mut().enumerate() {
+                *cusp = asc + 30.0 * i as f64;
+            }
+        }
+        b'W' => {
+            let first = (normalize(asc - zodiac_offset) / 30.0).floor() * 30.0 + zodiac_offset;
+            for (i, cusp) in cusps.iter_mut().enumerate() {
+                *cusp = first + 30.0 * i as f64;
+            }
+        }
+        b'M' => {
+            // Equal divisions of the equator from the ARMC, projected along ecliptic latitude
+            for (i, cusp) in cusps.iter_mut().enumerate() {
+                let ra = th + 90.0 + 30.0 * i as f64;
+                *cusp = (sind(ra) * cose).atan2(cosd(ra)).to_degrees();
+            }
+        }
+        b'P' if !polar => {
+            let fallback = porphyry(asc, mc);
+            let cusp = |index: usize, fraction: f64, above: bool| {
+                placidus_cusp(th, lat, sine, cose, fraction, above).unwrap_or(fallback[index])
+            };
+            quadrants(
+                &mut cusps,
+                asc,
+                mc,
+                [cusp(10, 1.0 / 3.0, true), cusp(11, 2.0 / 3.0, true), cusp(1, 2.0 / 3.0, false), cusp(2, 1.0 / 3.0, false)],
+            );
+        }
+        b'K' if !polar => {
+            let sina = (sind(mc) * sine / cosd(lat)).clamp(-1.0, 1.0);
+            let cosa = (1.0 - sina * sina).sqrt();
+            let c = atand(tand(lat) / cosa);
+            let ad3 = asind(sind(c) * sina) / 3.0;
+            quadrants(
+                &mut cusps,
+                asc,
+                mc,
+                [
+                    asc1(th + 30.0 - 2.0 * ad3, lat, sine, cose),
+                    asc1(th + 60.0 - ad3, lat, sine, cose),
+                    asc1(th + 120.0 + ad3, lat, sine, cose),
+                    asc1(th + 150.0 + 2.0 * ad3, lat, sine, cose),
+                ],
+            );
+        }
+        b'R' => {
+            let fh1 = atand(tand(lat) * 0.5);
+            let fh2 = atand(tand(lat) * cosd(30.0));
+            mc = normalize(mc + turn);
+            quadrants(
+                &mut cusps,
+                asc,
+                mc,
+                [
+                    asc1(th + 30.0, fh1, sine, cose) + turn,
+                    asc1(th + 60.0, fh2, sine, cose) + turn,
+                    asc1(th + 120.0, fh2, sine, cose) + turn,
+                    asc1(th + 150.0, fh1, sine, cose) + turn,
+                ],
+            );
+        }
+        b'C' => {
+            let fh1 = asind(sind(lat) / 2.0);
+            let fh2 = asind(3.0_f64.sqrt() / 2.0 * sind(lat));
+            let cosfi = cosd(lat);
+            let (xh1, xh2) = if cosfi.abs() < VERY_SMALL {
+                (90.0, 90.0)
+            } else {
+                (atand(3.0_f64.sqrt() / cosfi), atand(1.0 / 3.0_f64.sqrt() / cosfi))
+            };
+            mc = normalize(mc + turn);
+            quadrants(
+                &mut cusps,
+                asc,
+                mc,
+                [
+                    asc1(th + 90.0 - xh1, fh1, sine, cose) + turn,
+                    asc1(th + 90.0 - xh2, fh2, sine, cose) + turn,
+                    asc1(th + 90.0 + xh2, fh2, sine, cose) + turn,
+                    asc1(th + 90.0 + xh1, fh1, sine, cose) + turn,
+                ],
+            );
+        }
+        b'A' => {
+            // Alcabitius: trisect the ascendant's diurnal and nocturnal semi-arcs on the equator
+            let declination = asind(sind(asc) * sine);
+            let sda = (-(tand(lat) * tand(declination)).clamp(-1.0, 1.0)).acos().to_degrees();
+            let (sd3, sn3) = (sda / 3.0, (180.0 - sda) / 3.0);
+            quadrants(
+                &mut cusps,
+                asc,
+                mc,
+                [
+                    asc1(th + sd3, 0.0, sine, cose),
+                    asc1(th + 2.0 * sd3, 0.0, sine, cose),
+                    asc1(th + 180.0 - 2.0 * sn3, 0.0, sine, cose),
+                    asc1(th + 180.0 - sn3, 0.0, sine, cose),
+                ],
+            );
+        }
+        _ => cusps = porphyry(asc, mc),
+    }
+
+    let points = points(th, lat, sine, cose, ecliptic_from_ra(th, cose), obliquity);
+    let shift = |deg: f64| normalize(deg - zodiac_offset);
+    Cusps {
+        cusps: cusps.map(shift),
+        asc: shift(asc),
+        mc: shift(mc),
+        points: points.map(shift),
+    }
+}
+
+/// Vertex, equatorial ascendant, co-ascendants and polar ascendant as in swehouse.c
+fn points(th: f64, lat: f64, sine: f64, cose: f64, mc: f64, obliquity: f64) -> [f64; 5] {
+    let colatitude = if lat >= 0.0 { 90.0 - lat } else { -90.0 - lat };
+    let mut vertex = asc1(th - 90.0, colatitude, sine, cose);
+    // Between the tropics the vertex can cross the meridian; keep it in the west
+    if lat.abs() <= obliquity && normalize(vertex - mc + 180.0) - 180.0 > 0.0 {
+        vertex = normalize(vertex + 180.0);
+    }
+    let equatorial_asc = ecliptic_from_ra(th + 90.0, cose);
+    let co_asc_koch = normalize(asc1(th - 90.0, lat, sine, cose) + 180.0);
+    let co_asc_munkasey = asc1(th + 90.0, colatitude, sine, cose);
+    let polar_asc = asc1(th - 90.0, lat, sine, cose);
+    [vertex, equatorial_asc, co_asc_koch, co_asc_munkasey, polar_asc]
+}
+
+/// Fill all twelve cusps from the angles and cusps 11, 12, 2 and 3
+fn quadrants(cusps: &mut [f64; 12], asc: f64, mc: f64, [c11, c12, c2, c3]: [f64; 4]) {
+    *cusps = [
+        asc,
+        c2,
+        c3,
+        mc + 180.0,
+        c11 + 180.0,
+        c12 + 180.0,
+        asc + 180.0,
+        c2 + 180.0,
+        c3 + 180.0,
+        mc,
+        c11,
+        c12,
+    ];
+}
+
+/// Trisect each quadrant between the angles in ecliptic longitude
+fn porphyry(asc: f64, mc: f64) -> [f64; 12] {
+    let mut asc = asc;
+    let mut arc = normalize(asc - mc);
+    if arc > 180.0 {
+        asc = normalize(asc + 180.0);
+        arc = normalize(asc - mc);
+    }
+    let lower = 180.0 - arc;
+    let mut cusps = [0.0; 12];
+    quadrants(
+        &mut cusps,
+        asc,
+        mc,
+        [mc + arc / 3.0, mc + 2.0 * arc / 3.0, asc + lower / 3.0, asc + 2.0 * lower / 3.0],
+    );
+    cusps.map(normalize)
+}
+
+/// Placidus cusp by iterating on the declination of the cusp point: `fraction` of the
+/// diurnal semi-arc east of the MC (`above`) or of the nocturnal semi-arc before the IC
+fn placidus_cusp(th: f64, lat: f64, sine: f64, cose: f64, fraction: f64, above: bool) -> Option<f64> {
+    let mut ra = if above { th + 90.0 * fraction } else { th + 180.0 - 90.0 * fraction };
+    let mut lon = ecliptic_from_ra(ra, cose);
+    for _ in 0..100 {
+        let declination = asind(sind(lon) * sine);
+        let x = tand(lat) * tand(declination);
+        if x.abs() > 1.0 {
+            return None;
+        }
+        let ad = x.asin().to_degrees();
+        ra = if above { th + fraction * (90.0 + ad) } else { th + 180.0 - fraction * (90.0 - ad) };
+        let next = ecliptic_from_ra(ra, cose);
+        let delta = (next - lon + 540.0).rem_euclid(360.0) - 180.0;
+        lon = next;
+        if delta.abs() < 1e-9 {
+            break;
+        }
+    }
+    Some(lon)
+}
@@ -0,0 +1,606 @@
+//! Pure-Rust ephemeris for builds without the Swiss Ephemeris C library, such as WASM.
+//!
+//! Positions come from analytic theories rather than ephemeris files: the truncated
+//! ELP-2000/82 series for the Moon (Meeus) and mean orbital elements with the main
+//! perturbations for the planets (Schlyter). They agree with the Swiss Ephemeris to about an
+//! arcminute between 1800 and 2100 - enough to draw and read a chart, not for timing exact
+//! aspects to the second. Chiron is not available.
+
+mod houses;
+mod moon;
+mod planets;
+pub(crate) mod time;
+
+use super::coordinates::{ecliptic_to_topocentric, Ecliptic};
+use super::error::EphemerisError;
+use super::progressions::{progressed_positions, ProgressionOptions};
+use super::systems::{get_house_system_byte, supported_ayanamsa_names, CUSTOM_AYANAMSA, DEFAULT_AYANAMSA};
+use super::types::{EphemerisSettings, GeoLocation, HousePositions, LayerPositions, PlanetPosition, HOUSE_POINTS};
+use chrono::{DateTime, Duration, Utc};
+use planets::Planet;
+use std::collections::HashMap;
+
+pub use time::julian_day;
+
+/// Ayanamsa at J2000.0 in degrees as given by the Swiss Ephemeris; other epochs add
+/// general precession
+const AYANAMSA_J2000: &[(&str, f64)] = &[
+    ("fagan_bradley", 24.740300),
+    ("lahiri", 23.857092),
+    ("chitrapaksha", 23.857092),
+    ("de_luce", 27.815753),
+    ("raman", 22.410791),
+    ("ushashashi", 20.057541),
+    ("krishnamurti", 23.760240),
+    ("djwhal_khul", 28.359679),
+    ("yukteshwar", 22.478803),
+    ("jn_bhasin", 22.762137),
+    ("babylonian_kugler_1", 23.533640),
+    ("babylonian_kugler_2", 24.933640),
+    ("babylonian_kugler_3", 25.783640),
+    ("babylonian_huber", 24.733640),
+    ("babylonian_eta_piscium", 24.522528),
+    ("aldebaran_15_taurus", 24.758924),
+    ("hipparchos", 20.247788),
+    ("sassanian", 19.992959),
+    ("galactic_center_0_sagittarius", 26.846046),
+    ("j2000", 0.000000),
+    ("j1900", 1.396581),
+    ("b1950", 0.698370),
+    ("suryasiddhanta", 20.895059),
+    ("suryasiddhanta_mean_sun", 20.680425),
+    ("aryabhata", 20.895060),
+    ("aryabhata_mean_sun", 20.657427),
+    ("ss_revati", 20.103388),
+    ("ss_citra", 23.005763),
+    ("true_citra", 23.840015),
+    ("true_revati", 20.045165),
+    ("true_pushya", 22.727092),
+    ("galactic_center_gil_brand", 22.469105),
+    ("galactic_equator_iau1958", 30.023172),
+    ("galactic_equator", 30.076092),
+    ("galactic_equator_mula", 23.409426),
+    ("galactic_alignment_mardyks", 30.017794),
+    ("true_mula", 24.579973),
+    ("galactic_center_mula_wilhelm", 20.039233),
+    ("aryabhata_522", 20.575847),
+    ("babylonian_britton", 24.615753),
+    ("true_sheoran", 25.234449),
+    ("galactic_center_cochrane", 356.846046),
+    ("galactic_equator_fiorenza", 25.000019),
+    ("valens_moon", 22.795609),
+    ("lahiri_1940", 23.842323),
+    ("lahiri_vp285", 23.863481),
+    ("krishnamurti_vp291", 23.780365),
+    ("lahiri_icrc", 23.856789),
+];
+
+/// Step in days for the central difference giving speeds
+const SPEED_STEP: f64 = 1.0 / 24.0;
+
+/// Analytic ephemeris with the same interface as `SwissEphemerisAdapter`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AnalyticEphemeris;
+
+impl AnalyticEphemeris {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Calculate planetary and house positions
+    pub fn calc_positions(
+        &self,
+        dt_utc: DateTime<Utc>,
+        location: Option<GeoLocation>,
+        settings: &EphemerisSettings,
+    ) -> Result<LayerPositions, EphemerisError> {
+        let jd = julian_day(dt_utc);
+        let house_system_byte = get_house_system_byte(&settings.house_system)?;
+        let ayanamsa = self.calc_ayanamsa(dt_utc, settings)?;
+
+        let mut planets = HashMap::new();
+        for obj_id in &settings.include_objects {
+            let obj_id_lower = obj_id.to_lowercase();
+
+            if obj_id_lower == "south_node" {
+                if let Ok(north_node_pos) = self.calc_planet_position(settings.body_id("north_node"), jd, ayanamsa) {
+                    planets.insert(
+                        "south_node".to_string(),
+                        PlanetPosition {
+                            lon: (north_node_pos.lon + 180.0) % 360.0,
+                            lat: 0.0,
+                            ..north_node_pos
+                        },
+                    );
+                }
+                continue;
+            }
+
+            let body_id = settings.body_id(&obj_id_lower);
+            let observer = location.as_ref().filter(|_| settings.is_topocentric(body_id));
+            if let Ok(planet_pos) = self.calc_body_position(body_id, jd, ayanamsa, observer) {
+                planets.insert(obj_id_lower, planet_pos);
+            }
+        }
+
+        // Placidus and Koch give way to the polar fallback where they are undefined
+        let houses = location
+            .map(|loc| {
+                match self.calc_houses(jd, loc.lat, loc.lon, house_system_byte, &settings.house_system, ayanamsa) {
+                    Err(EphemerisError::UndefinedHouseSystem { .. }) => {
+                        let fallback = settings.polar_fallback.house_system();
+                        self.calc_houses(jd, loc.lat, loc.lon, get_house_system_byte(fallback)?, fallback, ayanamsa)
+                    }
+                    houses => houses,
+                }
+            })
+            .transpose()?;
+
+        Ok(LayerPositions { planets, houses })
+    }
+
+    /// Calculate secondary progressed positions of a birth for `target`; see
+    /// [`progressions::progressed_positions`](super::progressions::progressed_positions)
+    pub fn calc_progressed_positions(
+        &self,
+        birth: DateTime<Utc>,
+        target: DateTime<Utc>,
+        location: Option<GeoLocation>,
+        settings: &EphemerisSettings,
+        options: &ProgressionOptions,
+    ) -> Result<LayerPositions, EphemerisError> {
+        progressed_positions(birth, target, location, settings, options, |dt, location, settings| {
+            self.calc_positions(dt, location, settings)
+        })
+    }
+
+    /// Calculate positions every `step` from `start` (inclusive) to `end` (exclusive); empty
+    /// when `step` is not positive
+    pub fn calc_positions_range(
+        &self,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        step: Duration,
+        location: Option<GeoLocation>,
+        settings: &EphemerisSettings,
+    ) -> Result<Vec<(DateTime<Utc>, LayerPositions)>, EphemerisError> {
+        let mut samples = Vec::new();
+        if step <= Duration::zero() {
+            return Ok(samples);
+        }
+        let mut moment = start;
+        while moment < end {
+            samples.push((moment, self.calc_positions(moment, location.clone(), settings)?));
+            moment += step;
+        }
+        Ok(samples)
+    }
+
+    /// Ayanamsa in degrees applied at a moment, or None for the tropical zodiac
+    pub fn calc_ayanamsa(
+        &self,
+        dt_utc: DateTime<Utc>,
+        settings: &EphemerisSettings,
+    ) -> Result<Option<f64>, EphemerisError> {
+        if settings.zodiac_type != "sidereal" {
+            return Ok(None);
+        }
+        let at_j2000 = ayanamsa_at_j2000(settings.ayanamsa.as_deref(), settings.custom_ayanamsa)?;
+        let t = time::centuries(time::terrestrial_time(julian_day(dt_utc)));
+        let precession = (5028.796195 * t + 1.1054348 * t * t) / 3600.0;
+        Ok(Some(at_j2000 + precession))
+    }
+
+    /// Calculate position for a single object at a UT Julian Day; `ayanamsa` selects the
+    /// sidereal zodiac
+    pub fn calc_planet_position(
+        &self,
+        planet_id: &str,
+        jd: f64,
+        ayanamsa: Option<f64>,
+    ) -> Result<PlanetPosition, EphemerisError> {
+        self.calc_body_position(planet_id, jd, ayanamsa, None)
+    }
+
+    /// Position seen from `observer` when given, otherwise from the Earth's centre; only the
+    /// Moon has a parallax worth applying
+    fn calc_body_position(
+        &self,
+        planet_id: &str,
+        jd: f64,
+        ayanamsa: Option<f64>,
+        observer: Option<&GeoLocation>,
+    ) -> Result<PlanetPosition, EphemerisError> {
+        let position = |jd_ut: f64| -> Result<(f64, f64), EphemerisError> {
+            let jd_tt = time::terrestrial_time(jd_ut);
+            let (lon, lat) = match planet_id {
+                "sun" => (planets::sun(jd_tt), 0.0),
+                "moon" => moon::position(jd_tt),
+                "north_node" | "true_node" => (moon::true_node(jd_tt), 0.0),
+                "mean_node" => (moon::mean_node(jd_tt), 0.0),
+                "mean_apogee" => moon::mean_apogee(jd_tt),
+                _ => match Planet::from_id(planet_id) {
+                    Some(planet) => planets::planet(planet, jd_tt),
+                    None => {
+                        return Err(EphemerisError::CalculationFailed {
+                            planet_id: planet_id.to_string(),
+                            datetime: time::datetime_from_julian_day(jd_ut),
+                            message: format!("Not available in the analytic ephemeris: {}", planet_id),
+                        })
+                    }
+                },
+            };
+            let (lon, lat) = match observer {
+                Some(observer) if planet_id == "moon" => {
+                    let obliquity = time::mean_obliquity(jd_tt);
+                    let lst = time::apparent_sidereal_time(jd_ut, time::nutation(jd_tt).0, obliquity) + observer.lon;
+                    let geocentric = Ecliptic { lon, lat };
+                    let topocentric =
+                        ecliptic_to_topocentric(geocentric, moon::distance(jd_tt), obliquity, lst, observer.lat);
+                    (topocentric.lon, topocentric.lat)
+                }
+                _ => (lon, lat),
+            };
+            let lon = match ayanamsa {
+                // Sidereal longitudes are measured from the mean equinox, as in the Swiss Ephemeris
+                Some(ayanamsa) => lon - ayanamsa,
+                None => lon + time::nutation(jd_tt).0,
+            };
+            Ok((time::normalize(lon), lat))
+        };
+
+        let (lon, lat) = position(jd)?;
+        let (before, _) = position(jd - SPEED_STEP)?;
+        let (after, _) = position(jd + SPEED_STEP)?;
+        let speed_lon = ((after - before + 540.0).rem_euclid(360.0) - 180.0) / (2.0 * SPEED_STEP);
+
+        Ok(PlanetPosition {
+            lon,
+            lat,
+            speed_lon,
+            retrograde: speed_lon < 0.0,
+        })
+    }
+
+    /// Calculate house cusps and angles at a UT Julian Day; `UndefinedHouseSystem` when the
+    /// system has no cusps at this latitude
+    pub fn calc_houses(
+        &self,
+        jd: f64,
+        lat: f64,
+        lon: f64,
+        house_system_byte: u8,
+        house_system_str: &str,
+        ayanamsa: Option<f64>,
+    ) -> Result<HousePositions, EphemerisError> {
+        let jd_tt = time::terrestrial_time(jd);
+        let (nutation_lon, nutation_obl) = time::nutation(jd_tt);
+        let obliquity = time::mean_obliquity(jd_tt) + nutation_obl;
+        if houses::is_undefined(house_system_byte, lat, obliquity) {
+            return Err(EphemerisError::UndefinedHouseSystem { system: house_system_str.to_string(), lat });
+        }
+        let armc = time::apparent_sidereal_time(jd, nutation_lon, obliquity) + lon;
+        let offset = ayanamsa.map_or(0.0, |ayanamsa| ayanamsa + nutation_lon);
+        let result = houses::calc(armc, lat, obliquity, house_system_byte, offset);
+        let (asc, mc) = (result.asc, result.mc);
+
+        let mut angles = HashMap::from([
+            ("asc".to_string(), asc),
+            ("mc".to_string(), mc),
+            ("ic".to_string(), (mc + 180.0) % 360.0),
+            ("dc".to_string(), (asc + 180.0) % 360.0),
+        ]);
+        let points = std::iter::once(time::normalize(armc)).chain(result.points);
+        angles.extend(HOUSE_POINTS.iter().zip(points).map(|(id, lon)| (id.to_string(), lon)));
+
+        Ok(HousePositions {
+            system: house_system_str.to_string(),
+            cusps: result
+                .cusps
+                .iter()
+                .enumerate()
+                .map(|(i, cusp)| ((i + 1).to_string(), *cusp))
+                .collect(),
+            angles,
+        })
+    }
+}
+
+/// Resolve an ayanamsa name (or a custom value) to its value at J2000.0
+fn ayanamsa_at_j2000(ayanamsa: Option<&str>, custom_ayanamsa: Option<f64>) -> Result<f64, EphemerisError> {
+    let ayanamsa = ayanamsa.unwrap_or(DEFAULT_AYANAMSA).to_lowercase();
+    if ayanamsa == CUSTOM_AYANAMSA {
+        return match custom_ayanamsa {
+            Some(value) if value.is_finite() => Ok(value),
+            _ => Err(EphemerisError::InvalidAyanamsa {
+                ayanamsa: format!("{} (requires a finite custom ayanamsa value)", ayanamsa),
+                valid: supported_ayanamsa_names(),
+            }),
+        };
+    }
+    AYANAMSA_J2000
+        .iter()
+        .find(|(name, _)| *name == ayanamsa)
+        .map(|(_, value)| *value)
+        .ok_or_else(|| EphemerisError::InvalidAyanamsa {
+            ayanamsa,
+            valid: supported_ayanamsa_names(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::{LilithType, MoonPosition, NodeType, PolarFallback, ANGLE_IDS};
+    use chrono::TimeZone;
+
+    /// Swiss Ephemeris longitudes for 1990-01-01 12:00 UTC (tropical)
+    const REFERENCE: &[(&str, f64)] = &[
+        ("sun", 280.814),
+        ("moon", 333.268),
+        ("mercury", 295.673),
+        ("venus", 306.222),
+        ("mars", 250.000),
+        ("jupiter", 95.149),
+        ("saturn", 285.658),
+        ("uranus", 275.785),
+        ("neptune", 282.038),
+        ("pluto", 227.093),
+        ("north_node", 316.869),
+    ];
+
+    fn settings(zodiac_type: &str, house_system: &str) -> EphemerisSettings {
+        EphemerisSettings {
+            zodiac_type: zodiac_type.to_string(),
+            ayanamsa: None,
+            custom_ayanamsa: None,
+            house_system: house_system.to_string(),
+            include_objects: REFERENCE.iter().map(|(id, _)| id.to_string()).collect(),
+            node_type: NodeType::default(),
+            lilith_type: LilithType::default(),
+            moon_position: MoonPosition::default(),
+            polar_fallback: PolarFallback::default(),
+        }
+    }
+
+    fn calc(settings: &EphemerisSettings) -> LayerPositions {
+        let dt = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+        let new_york = GeoLocation { lat: 40.7128, lon: -74.006 };
+        AnalyticEphemeris::new().calc_positions(dt, Some(new_york), settings).unwrap()
+    }
+
+    fn separation(a: f64, b: f64) -> f64 {
+        ((a - b + 540.0).rem_euclid(360.0) - 180.0).abs()
+    }
+
+    #[test]
+    fn test_planets_match_swiss_ephemeris() {
+        let positions = calc(&settings("tropical", "placidus"));
+        for (id, expected) in REFERENCE {
+            let lon = positions.planets[*id].lon;
+            assert!(separation(lon, *expected) < 0.05, "{}: {} vs {}", id, lon, expected);
+        }
+        assert!(positions.planets["moon"].speed_lon > 11.0);
+        assert!(positions.planets["north_node"].lat.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_positions_range_samples_each_step() {
+        let start = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+        let ephemeris = AnalyticEphemeris::new();
+        let settings = settings("tropical", "placidus");
+        let samples = ephemeris
+            .calc_positions_range(start, start + Duration::hours(6), Duration::hours(2), None, &settings)
+            .unwrap();
+        let moments: Vec<_> = samples.iter().map(|(moment, _)| *moment).collect();
+        assert_eq!(moments, vec![start, start + Duration::hours(2), start + Duration::hours(4)]);
+        assert_eq!(samples[0].1.planets["moon"].lon, calc(&settings).planets["moon"].lon);
+        assert!(samples[0].1.houses.is_none());
+
+        let empty = ephemeris.calc_positions_range(start, start + Duration::hours(6), Duration::zero(), None, &settings);
+        assert!(empty.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_progressed_angles_follow_the_arc() {
+        use crate::ephemeris::progressions::{self, AngleProgression, NAIBOD_RATE};
+
+        let birth = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+        let target = Utc.with_ymd_and_hms(2020, 1, 1, 12, 0, 0).unwrap();
+        let years = progressions::years_of_life(birth, target);
+        let new_york = GeoLocation { lat: 40.7128, lon: -74.006 };
+        let ephemeris = AnalyticEphemeris::new();
+        let settings = settings("tropical", "placidus");
+        let natal = calc(&settings);
+        let natal_houses = natal.houses.as_ref().unwrap();
+        let progress = |options: ProgressionOptions| {
+            ephemeris
+                .calc_progressed_positions(birth, target, Some(new_york.clone()), &settings, &options)
+                .unwrap()
+        };
+
+        let solar_arc = progress(ProgressionOptions::default());
+        let moment = progressions::progressed_datetime(birth, target, false);
+        let at_moment = ephemeris.calc_positions(moment, None, &settings).unwrap();
+        assert_eq!(solar_arc.planets["sun"].lon, at_moment.planets["sun"].lon);
+        let arc = solar_arc.planets["sun"].lon - natal.planets["sun"].lon;
+        let houses = solar_arc.houses.as_ref().unwrap();
+        assert!(separation(houses.angles["mc"], natal_houses.angles["mc"] + arc) < 1e-5);
+        // The Ascendant is derived at the birth latitude rather than moved by the arc
+        assert!(separation(houses.angles["asc"], natal_houses.angles["asc"] + arc) > 1.0);
+        assert!(separation(houses.cusps["1"], houses.angles["asc"]) < 1e-9);
+
+        let naibod = progress(ProgressionOptions { angles: AngleProgression::Naibod, converse: false });
+        let mc = naibod.houses.as_ref().unwrap().angles["mc"];
+        assert!(separation(mc, natal_houses.angles["mc"] + years * NAIBOD_RATE) < 1e-5);
+
+        let converse = progress(ProgressionOptions { angles: AngleProgression::Naibod, converse: true });
+        let mc = converse.houses.as_ref().unwrap().angles["mc"];
+        assert!(separation(mc, natal_houses.angles["mc"] - years * NAIBOD_RATE) < 1e-5);
+        assert!(converse.planets["sun"].lon < natal.planets["sun"].lon);
+    }
+
+    #[test]
+    fn test_houses_match_swiss_ephemeris() {
+        let cases: [(&str, [f64; 12]); 2] = [
+            (
+                "placidus",
+                [274.639, 314.589, 356.109, 28.913, 53.620, 74.276, 94.639, 134.589, 176.109, 208.913, 233.620, 254.276],
+            ),
+            (
+                "koch",
+                [274.639, 303.770, 343.480, 28.913, 49.851, 71.192, 94.639, 123.770, 163.480, 208.913, 229.851, 251.192],
+            ),
+        ];
+        for (system, expected) in cases {
+            let houses = calc(&settings("tropical", system)).houses.unwrap();
+            for (i, cusp) in expected.iter().enumerate() {
+                let actual = houses.cusps[&(i + 1).to_string()];
+                assert!(separation(actual, *cusp) < 0.005, "{} cusp {}: {} vs {}", system, i + 1, actual, cusp);
+            }
+            assert!(separation(houses.angles["asc"], 274.639) < 0.005);
+            assert!(separation(houses.angles["mc"], 208.913) < 0.005);
+        }
+    }
+
+    #[test]
+    fn test_sidereal_positions() {
+        let settings = settings("sidereal", "whole_sign");
+        let dt = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+        let ayanamsa = AnalyticEphemeris::new().calc_ayanamsa(dt, &settings).unwrap().unwrap();
+        assert!((ayanamsa - 23.717).abs() < 0.001, "lahiri ayanamsa {}", ayanamsa);
+
+        let positions = calc(&settings);
+        assert!(separation(positions.planets["sun"].lon, 257.094) < 0.05);
+        let houses = positions.houses.unwrap();
+        assert!(separation(houses.angles["asc"], 250.918) < 0.005);
+        assert_eq!(houses.cusps["1"], 240.0);
+        assert_eq!(houses.cusps["6"], 30.0);
+    }
+
+    #[test]
+    fn test_south_node_and_unavailable_objects() {
+        let mut settings = settings("tropical", "equal");
+        settings.include_objects = vec!["north_node".to_string(), "south_node".to_string(), "chiron".to_string()];
+        let positions = calc(&settings);
+        let (north, south) = (&positions.planets["north_node"], &positions.planets["south_node"]);
+        assert!(separation(north.lon + 180.0, south.lon) < 1e-9);
+        assert!(!positions.planets.contains_key("chiron"));
+    }
+
+    #[test]
+    fn test_node_and_lilith_types() {
+        let mut settings = settings("tropical", "equal");
+        settings.include_objects = vec!["north_node".to_string(), "south_node".to_string(), "lilith".to_string()];
+        let true_node = calc(&settings);
+        // Swiss Ephemeris mean apogee
+        assert!(separation(true_node.planets["lilith"].lon, 216.464) < 0.001);
+        assert!((true_node.planets["lilith"].lat + 5.034).abs() < 0.001);
+
+        settings.node_type = NodeType::Mean;
+        let mean_node = calc(&settings);
+        let (north, south) = (&mean_node.planets["north_node"], &mean_node.planets["south_node"]);
+        // Swiss Ephemeris mean node
+        assert!(separation(north.lon, 318.435) < 0.001);
+        assert!(separation(north.lon + 180.0, south.lon) < 1e-9);
+        assert!(separation(north.lon, true_node.planets["north_node"].lon) > 1.0);
+
+        // The osculating apogee needs the full lunar orbit, which the analytic backend lacks
+        settings.lilith_type = LilithType::True;
+        assert!(!calc(&settings).planets.contains_key("lilith"));
+    }
+
+    #[test]
+    fn test_topocentric_moon() {
+        // Meeus example 47.a: 1992 April 12 0h TD
+        assert!((moon::distance(2448724.5) - 368409.7).abs() < 1.0);
+
+        let dt = Utc.with_ymd_and_hms(1990, 3, 1, 7, 0, 0).unwrap();
+        let new_york = GeoLocation { lat: 40.7, lon: -74.0 };
+        let mut settings = settings("tropical", "placidus");
+        settings.include_objects = vec!["moon".to_string(), "sun".to_string()];
+        settings.moon_position = MoonPosition::Topocentric;
+        let ephemeris = AnalyticEphemeris::new();
+        let topocentric = ephemeris.calc_positions(dt, Some(new_york), &settings).unwrap();
+        let geocentric = ephemeris.calc_positions(dt, None, &settings).unwrap();
+
+        // Swiss Ephemeris topocentric Moon; the geocentric one is at 33.205, +5.095
+        let moon = &topocentric.planets["moon"];
+        assert!(separation(moon.lon, 32.683) < 0.005, "{:?}", moon);
+        assert!((moon.lat - 4.410).abs() < 0.005, "{:?}", moon);
+        assert!(separation(geocentric.planets["moon"].lon, 33.205) < 0.005);
+        assert_eq!(topocentric.planets["sun"].lon, geocentric.planets["sun"].lon);
+    }
+
+    #[test]
+    fn test_house_points() {
+        let dt = Utc.with_ymd_and_hms(1990, 3, 1, 7, 0, 0).unwrap();
+        let new_york = GeoLocation { lat: 40.7, lon: -74.0 };
+        let houses = AnalyticEphemeris::new()
+            .calc_positions(dt, Some(new_york), &settings("tropical", "placidus"))
+            .unwrap()
+            .houses
+            .unwrap();
+
+        // Swiss Ephemeris houses_ex
+        let expected = [
+            ("armc", 189.8275),
+            ("vertex", 122.1425),
+            ("equatorial_asc", 279.0303),
+            ("co_asc_koch", 296.8490),
+            ("co_asc_munkasey", 252.7515),
+            ("polar_asc", 116.8490),
+        ];
+        assert_eq!(houses.angles.len(), ANGLE_IDS.len() + HOUSE_POINTS.len());
+        for (id, lon) in expected {
+            assert!(separation(houses.angles[id], lon) < 0.001, "{} {}", id, houses.angles[id]);
+        }
+    }
+
+    #[test]
+    fn test_polar_house_fallback() {
+        let ephemeris = AnalyticEphemeris::new();
+        let dt = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+        let tromso = GeoLocation { lat: 69.65, lon: 18.96 };
+        let jd = julian_day(dt);
+        let result = ephemeris.calc_houses(jd, tromso.lat, tromso.lon, b'P', "placidus", None);
+        assert!(matches!(result, Err(EphemerisError::UndefinedHouseSystem { .. })));
+
+        let mut settings = settings("tropical", "placidus");
+        let houses = ephemeris.calc_positions(dt, Some(tromso.clone()), &settings).unwrap().houses.unwrap();
+        assert_eq!(houses.system, "porphyry");
+        assert!(separation(houses.cusps["1"], houses.angles["asc"]) < 1e-9);
+
+        settings.house_system = "koch".to_string();
+        settings.polar_fallback = PolarFallback::WholeSign;
+        let houses = ephemeris.calc_positions(dt, Some(tromso), &settings).unwrap().houses.unwrap();
+        assert_eq!(houses.system, "whole_sign");
+        assert_eq!(houses.cusps["1"] % 30.0, 0.0);
+
+        let new_york = GeoLocation { lat: 40.7128, lon: -74.0060 };
+        let houses = ephemeris.calc_positions(dt, Some(new_york), &settings).unwrap().houses.unwrap();
+        assert_eq!(houses.system, "koch");
+    }
+
+    #[test]
+    fn test_invalid_settings() {
+        let ephemeris = AnalyticEphemeris::new();
+        let dt = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+
+        let result = ephemeris.calc_positions(dt, None, &settings("tropical", "topocentric"));
+        assert!(matches!(result, Err(EphemerisError::InvalidHouseSystem { .. })));
+
+        let mut custom = settings("sidereal", "placidus");
+        custom.ayanamsa = Some(CUSTOM_AYANAMSA.to_string());
+        assert!(matches!(ephemeris.calc_ayanamsa(dt, &custom), Err(EphemerisError::InvalidAyanamsa { .. })));
+        custom.custom_ayanamsa = Some(24.0);
+        assert!((ephemeris.calc_ayanamsa(dt, &custom).unwrap().unwrap() - 23.86).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_every_ayanamsa_has_a_reference_value() {
+        for (name, _) in crate::ephemeris::systems::AYANAMSAS {
+            assert!(AYANAMSA_J2000.iter().any(|(id, _)| id == name), "missing {}", name);
+        }
+    }
+}
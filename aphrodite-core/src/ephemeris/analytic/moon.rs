@@ -0,0 +1,268 @@
+//! Lunar position, distance and node from the truncated ELP-2000/82 series in Meeus ch. 47 (about 10").
+
+use super::time::{centuries, normalize};
+
+/// Multiples of D, M, M', F and the coefficient in 1e-6 degrees (Meeus table 47.A)
+const LONGITUDE_TERMS: &[(i8, i8, i8, i8, f64)] = &[
+    (0, 0, 1, 0, 6288774.0),
+    (2, 0, -1, 0, 1274027.0),
+    (2, 0, 0, 0, 658314.0),
+    (0, 0, 2, 0, 213618.0),
+    (0, 1, 0, 0, -185116.0),
+    (0, 0, 0, 2, -114332.0),
+    (2, 0, -2, 0, 58793.0),
+    (2, -1, -1, 0, 57066.0),
+    (2, 0, 1, 0, 53322.0),
+    (2, -1, 0, 0, 45758.0),
+    (0, 1, -1, 0, -40923.0),
+    (1, 0, 0, 0, -34720.0),
+    (0, 1, 1, 0, -30383.0),
+    (2, 0, 0, -2, 15327.0),
+    (0, 0, 1, 2, -12528.0),
+    (0, 0, 1, -2, 10980.0),
+    (4, 0, -1, 0, 10675.0),
+    (0, 0, 3, 0, 10034.0),
+    (4, 0, -2, 0, 8548.0),
+    (2, 1, -1, 0, -7888.0),
+    (2, 1, 0, 0, -6766.0),
+    (1, 0, -1, 0, -5163.0),
+    (1, 1, 0, 0, 4987.0),
+    (2, -1, 1, 0, 4036.0),
+    (2, 0, 2, 0, 3994.0),
+    (4, 0, 0, 0, 3861.0),
+    (2, 0, -3, 0, 3665.0),
+    (0, 1, -2, 0, -2689.0),
+    (2, 0, -1, 2, -2602.0),
+    (2, -1, -2, 0, 2390.0),
+    (1, 0, 1, 0, -2348.0),
+    (2, -2, 0, 0, 2236.0),
+    (0, 1, 2, 0, -2120.0),
+    (0, 2, 0, 0, -2069.0),
+    (2, -2, -1, 0, 2048.0),
+    (2, 0, 1, -2, -1773.0),
+    (2, 0, 0, 2, -1595.0),
+    (4, -1, -1, 0, 1215.0),
+    (0, 0, 2, 2, -1110.0),
+    (3, 0, -1, 0, -892.0),
+    (2, 1, 1, 0, -810.0),
+    (4, -1, -2, 0, 759.0),
+    (0, 2, -1, 0, -713.0),
+    (2, 2, -1, 0, -700.0),
+    (2, 1, -2, 0, 691.0),
+    (2, -1, 0, -2, 596.0),
+    (4, 0, 1, 0, 549.0),
+    (0, 0, 4, 0, 537.0),
+    (4, -1, 0, 0, 520.0),
+    (1, 0, -2, 0, -487.0),
+    (2, 1, 0, -2, -399.0),
+    (0, 0, 2, -2, -381.0),
+    (1, 1, 1, 0, 351.0),
+    (3, 0, -2, 0, -340.0),
+    (4, 0, -3, 0, 330.0),
+    (2, -1, 2, 0, 327.0),
+    (0, 2, 1, 0, -323.0),
+    (1, 1, -1, 0, 299.0),
+    (2, 0, 3, 0, 294.0),
+];
+
+/// Multiples of D, M, M', F and the coefficient in 1e-6 degrees (Meeus table 47.B)
+const LATITUDE_TERMS: &[(i8, i8, i8, i8, f64)] = &[
+    (0, 0, 0, 1, 5128122.0),
+    (0, 0, 1, 1, 280602.0),
+    (0, 0, 1, -1, 277693.0),
+    (2, 0, 0, -1, 173237.0),
+    (2, 0, -1, 1, 55413.0),
+    (2, 0, -1, -1, 46271.0),
+    (2, 0, 0, 1, 32573.0),
+    (0, 0, 2, 1, 17198.0),
+    (2, 0, 1, -1, 9266.0),
+    (0, 0, 2, -1, 8822.0),
+    (2, -1, 0, -1, 8216.0),
+    (2, 0, -2, -1, 4324.0),
+    (2, 0, 1, 1, 4200.0),
+    (2, 1, 0, -1, -3359.0),
+    (2, -1, -1, 1, 2463.0),
+    (2, -1, 0, 1, 2211.0),
+    (2, -1, -1, -1, 2065.0),
+    (0, 1, -1, -1, -1870.0),
+    (4, 0, -1, -1, 1828.0),
+    (0, 1, 0, 1, -1794.0),
+    (0, 0, 0, 3, -1749.0),
+    (0, 1, -1, 1, -1565.0),
+    (1, 0, 0, 1, -1491.0),
+    (0, 1, 1, 1, -1475.0),
+    (0, 1, 1, -1, -1410.0),
+    (0, 1, 0, -1, -1344.0),
+    (1, 0, 0, -1, -1335.0),
+    (0, 0, 3, 1, 1107.0),
+    (4, 0, 0, -1, 1021.0),
+    (4, 0, -1, 1, 833.0),
+    (0, 0, 1, -3, 777.0),
+    (4, 0, -2, 1, 671.0),
+    (2, 0, 0, -3, 607.0),
+    (2, 0, 2, -1, 596.0),
+    (2, -1, 1, -1, 491.0),
+    (2, 0, -2, 1, -451.0),
+    (0, 0, 3, -1, 439.0),
+    (2, 0, 2, 1, 422.0),
+    (2, 0, -3, -1, 421.0),
+    (2, 1, -1, 1, -366.0),
+    (2, 1, 0, 1, -351.0),
+    (4, 0, 0, 1, 331.0),
+    (2, -1, 1, 1, 315.0),
+    (2, -2, 0, -1, 302.0),
+    (0, 0, 1, 3, -283.0),
+    (2, 1, 1, -1, -229.0),
+    (1, 1, 0, -1, 223.0),
+    (1, 1, 0, 1, 223.0),
+    (0, 1, -2, -1, -220.0),
+    (2, 1, -1, -1, -220.0),
+    (1, 0, 1, 1, -185.0),
+    (2, -1, -2, -1, 181.0),
+    (0, 1, 2, 1, -177.0),
+    (4, 0, -2, -1, 176.0),
+    (4, -1, -1, -1, 166.0),
+    (1, 0, 1, -1, -164.0),
+    (4, 0, 1, -1, 132.0),
+    (1, 0, -1, -1, -119.0),
+    (4, -1, 0, -1, 115.0),
+    (2, -2, 0, 1, 107.0),
+];
+
+/// Multiples of D, M, M', F and the coefficient in 1e-3 km (Meeus table 47.A)
+const DISTANCE_TERMS: &[(i8, i8, i8, i8, f64)] = &[
+    (0, 0, 1, 0, -20905355.0),
+    (2, 0, -1, 0, -3699111.0),
+    (2, 0, 0, 0, -2955968.0),
+    (0, 0, 2, 0, -569925.0),
+    (0, 1, 0, 0, 48888.0),
+    (0, 0, 0, 2, -3149.0),
+    (2, 0, -2, 0, 246158.0),
+    (2, -1, -1, 0, -152138.0),
+    (2, 0, 1, 0, -170733.0),
+    (2, -1, 0, 0, -204586.0),
+    (0, 1, -1, 0, -129620.0),
+    (1, 0, 0, 0, 108743.0),
+    (0, 1, 1, 0, 104755.0),
+    (2, 0, 0, -2, 10321.0),
+    (0, 0, 1, -2, 79661.0),
+    (4, 0, -1, 0, -34782.0),
+    (0, 0, 3, 0, -23210.0),
+    (4, 0, -2, 0, -21636.0),
+    (2, 1, -1, 0, 24208.0),
+    (2, 1, 0, 0, 30824.0),
+    (1, 0, -1, 0, -8379.0),
+    (1, 1, 0, 0, -16675.0),
+    (2, -1, 1, 0, -12831.0),
+    (2, 0, 2, 0, -10445.0),
+    (4, 0, 0, 0, -11650.0),
+    (2, 0, -3, 0, 14403.0),
+    (0, 1, -2, 0, -7003.0),
+    (2, -1, -2, 0, 10056.0),
+    (1, 0, 1, 0, 6322.0),
+    (2, -2, 0, 0, -9884.0),
+    (0, 1, 2, 0, 5751.0),
+    (2, -2, -1, 0, -4950.0),
+    (2, 0, 1, -2, 4130.0),
+    (4, -1, -1, 0, -3958.0),
+    (3, 0, -1, 0, 3258.0),
+    (2, 1, 1, 0, 2616.0),
+    (4, -1, -2, 0, -1897.0),
+    (0, 2, -1, 0, -2117.0),
+    (2, 2, -1, 0, 2354.0),
+    (4, 0, 1, 0, -1423.0),
+    (0, 0, 4, 0, -1117.0),
+    (4, -1, 0, 0, -1571.0),
+    (1, 0, -2, 0, -1739.0),
+    (0, 0, 2, -2, -4421.0),
+    (0, 2, 1, 0, 1165.0),
+    (2, 0, -1, -2, 8752.0),
+];
+
+/// Fundamental arguments in degrees: (L', D, M, M', F) and the eccentricity factor E
+fn arguments(t: f64) -> ([f64; 5], f64) {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let t4 = t3 * t;
+    let mean_lon = 218.3164477 + 481267.88123421 * t - 0.0015786 * t2 + t3 / 538841.0 - t4 / 65_194_000.0;
+    let elongation = 297.8501921 + 445267.1114034 * t - 0.0018819 * t2 + t3 / 545868.0 - t4 / 113_065_000.0;
+    let sun_anomaly = 357.5291092 + 35999.0502909 * t - 0.0001536 * t2 + t3 / 24_490_000.0;
+    let moon_anomaly = 134.9633964 + 477198.8675055 * t + 0.0087414 * t2 + t3 / 69699.0 - t4 / 14_712_000.0;
+    let latitude_arg = 93.2720950 + 483202.0175233 * t - 0.0036539 * t2 - t3 / 3_526_000.0 + t4 / 863_310_000.0;
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    ([mean_lon, elongation, sun_anomaly, moon_anomaly, latitude_arg], e)
+}
+
+fn series(terms: &[(i8, i8, i8, i8, f64)], args: &[f64; 5], e: f64, trig: fn(f64) -> f64) -> f64 {
+    let [_, d, m, mp, f] = *args;
+    terms
+        .iter()
+        .map(|&(cd, cm, cmp, cf, coeff)| {
+            let angle = cd as f64 * d + cm as f64 * m + cmp as f64 * mp + cf as f64 * f;
+            coeff * e.powi(cm.unsigned_abs() as i32) * trig(angle.to_radians())
+        })
+        .sum()
+}
+
+/// Geocentric ecliptic longitude and latitude of the Moon (mean equinox of date, no nutation)
+pub fn position(jd_tt: f64) -> (f64, f64) {
+    let t = centuries(jd_tt);
+    let (args, e) = arguments(t);
+    let [mean_lon, _, _, mp, f] = args;
+    let a1 = (119.75 + 131.849 * t).to_radians();
+    let a2 = (53.09 + 479264.290 * t).to_radians();
+    let a3 = (313.45 + 481266.484 * t).to_radians();
+    let (l, mp, f) = (mean_lon.to_radians(), mp.to_radians(), f.to_radians());
+
+    let sum_l = series(LONGITUDE_TERMS, &args, e, f64::sin) + 3958.0 * a1.sin() + 1962.0 * (l - f).sin()
+        + 318.0 * a2.sin();
+    let sum_b = series(LATITUDE_TERMS, &args, e, f64::sin) - 2235.0 * l.sin() + 382.0 * a3.sin()
+        + 175.0 * (a1 - f).sin()
+        + 175.0 * (a1 + f).sin()
+        + 127.0 * (l - mp).sin()
+        - 115.0 * (l + mp).sin();
+
+    (normalize(mean_lon + sum_l / 1e6), sum_b / 1e6)
+}
+
+/// Distance between the centres of the Earth and the Moon in km
+pub fn distance(jd_tt: f64) -> f64 {
+    let (args, e) = arguments(centuries(jd_tt));
+    385_000.56 + series(DISTANCE_TERMS, &args, e, f64::cos) / 1000.0
+}
+
+/// Longitude of the true (osculating) ascending lunar node, from the plane through
+/// the Moon's positions shortly before and after (mean equinox of date, no nutation)
+pub fn true_node(jd_tt: f64) -> f64 {
+    const STEP: f64 = 0.01;
+    let unit = |jd: f64| {
+        let (lon, lat) = position(jd);
+        let (lon, lat) = (lon.to_radians(), lat.to_radians());
+        [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+    };
+    let (a, b) = (unit(jd_tt - STEP), unit(jd_tt + STEP));
+    // Orbit normal; the ascending node lies along ecliptic pole x normal
+    let hx = a[1] * b[2] - a[2] * b[1];
+    let hy = a[2] * b[0] - a[0] * b[2];
+    normalize(hx.atan2(-hy).to_degrees())
+}
+
+/// Longitude of the mean ascending lunar node (mean equinox of date, no nutation)
+pub fn mean_node(jd_tt: f64) -> f64 {
+    let t = centuries(jd_tt);
+    normalize(125.0445479 - 1934.1362891 * t + 0.0020754 * t * t + t.powi(3) / 467_441.0 - t.powi(4) / 60_616_000.0)
+}
+
+/// Longitude and latitude of the mean lunar apogee, Black Moon Lilith, on the mean lunar
+/// orbit (mean equinox of date, no nutation)
+pub fn mean_apogee(jd_tt: f64) -> (f64, f64) {
+    const INCLINATION: f64 = 5.145_396;
+    let t = centuries(jd_tt);
+    let perigee = 83.3532465 + 4069.0137287 * t - 0.0103200 * t * t - t.powi(3) / 80_053.0 + t.powi(4) / 18_999_000.0;
+    // Distance along the orbit from the node, projected onto the ecliptic
+    let argument = (perigee + 180.0 - mean_node(jd_tt)).to_radians();
+    let inclination = INCLINATION.to_radians();
+    let lon = mean_node(jd_tt) + (argument.sin() * inclination.cos()).atan2(argument.cos()).to_degrees();
+    let lat = (argument.sin() * inclination.sin()).asin().to_degrees();
+    (normalize(lon), lat)
+}
@@ -0,0 +1,251 @@
+//! Sun and planet positions from mean orbital elements of date with the main Jupiter,
+//! Saturn and Uranus perturbations, and a periodic series for Pluto (P. Schlyter,
+//! "How to compute planetary positions"). Accurate to about an arcminute.
+
+use super::time::normalize;
+
+/// Light travel time for one AU in days
+const LIGHT_TIME_PER_AU: f64 = 0.0057755183;
+
+/// Constant of annual aberration in degrees
+const ABERRATION: f64 = 20.49552 / 3600.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+    Pluto,
+}
+
+impl Planet {
+    pub fn from_id(id: &str) -> Option<Self> {
+        Some(match id {
+            "mercury" => Self::Mercury,
+            "venus" => Self::Venus,
+            "mars" => Self::Mars,
+            "jupiter" => Self::Jupiter,
+            "saturn" => Self::Saturn,
+            "uranus" => Self::Uranus,
+            "neptune" => Self::Neptune,
+            "pluto" => Self::Pluto,
+            _ => return None,
+        })
+    }
+}
+
+/// Orbital elements: node, inclination, argument of perihelion, mean anomaly (degrees),
+/// semi-major axis (AU) and eccentricity
+struct Elements {
+    node: f64,
+    incl: f64,
+    peri: f64,
+    axis: f64,
+    ecc: f64,
+    anomaly: f64,
+}
+
+/// Elements at `d` days from 2000 Jan 0.0 TT
+fn elements(planet: Planet, d: f64) -> Elements {
+    let (node, incl, peri, axis, ecc, anomaly) = match planet {
+        Planet::Mercury => (
+            48.3313 + 3.24587e-5 * d,
+            7.0047 + 5.00e-8 * d,
+            29.1241 + 1.01444e-5 * d,
+            0.387098,
+            0.205635 + 5.59e-10 * d,
+            168.6562 + 4.0923344368 * d,
+        ),
+        Planet::Venus => (
+            76.6799 + 2.46590e-5 * d,
+            3.3946 + 2.75e-8 * d,
+            54.8910 + 1.38374e-5 * d,
+            0.723330,
+            0.006773 - 1.302e-9 * d,
+            48.0052 + 1.6021302244 * d,
+        ),
+        Planet::Mars => (
+            49.5574 + 2.11081e-5 * d,
+            1.8497 - 1.78e-8 * d,
+            286.5016 + 2.92961e-5 * d,
+            1.523688,
+            0.093405 + 2.516e-9 * d,
+            18.6021 + 0.5240207766 * d,
+        ),
+        Planet::Jupiter => (
+            100.4542 + 2.76854e-5 * d,
+            1.3030 - 1.557e-7 * d,
+            273.8777 + 1.64505e-5 * d,
+            5.20256,
+            0.048498 + 4.469e-9 * d,
+            19.8950 + 0.0830853001 * d,
+        ),
+        Planet::Saturn => (
+            113.6634 + 2.38980e-5 * d,
+            2.4886 - 1.081e-7 * d,
+            339.3939 + 2.97661e-5 * d,
+            9.55475,
+            0.055546 - 9.499e-9 * d,
+            316.9670 + 0.0334442282 * d,
+        ),
+        Planet::Uranus => (
+            74.0005 + 1.3978e-5 * d,
+            0.7733 + 1.9e-8 * d,
+            96.6612 + 3.0565e-5 * d,
+            19.18171 - 1.55e-8 * d,
+            0.047318 + 7.45e-9 * d,
+            142.5905 + 0.011725806 * d,
+        ),
+        Planet::Neptune => (
+            131.7806 + 3.0173e-5 * d,
+            1.7700 - 2.55e-7 * d,
+            272.8461 - 6.027e-6 * d,
+            30.05826 + 3.313e-8 * d,
+            0.008606 + 2.15e-9 * d,
+            260.2471 + 0.005995147 * d,
+        ),
+        Planet::Pluto => unreachable!("Pluto uses a periodic series, not orbital elements"),
+    };
+    Elements { node, incl, peri, axis, ecc, anomaly }
+}
+
+/// Solve Kepler's equation; returns (true anomaly in degrees, radius vector)
+fn solve_kepler(anomaly: f64, ecc: f64, axis: f64) -> (f64, f64) {
+    let m = normalize(anomaly).to_radians();
+    let mut e = m + ecc * m.sin() * (1.0 + ecc * m.cos());
+    for _ in 0..20 {
+        let delta = (e - ecc * e.sin() - m) / (1.0 - ecc * e.cos());
+        e -= delta;
+        if delta.abs() < 1e-12 {
+            break;
+        }
+    }
+    let x = axis * (e.cos() - ecc);
+    let y = axis * (1.0 - ecc * ecc).sqrt() * e.sin();
+    (y.atan2(x).to_degrees(), x.hypot(y))
+}
+
+/// Heliocentric ecliptic longitude, latitude (degrees) and distance (AU), equinox of date
+fn heliocentric(planet: Planet, d: f64) -> (f64, f64, f64) {
+    if planet == Planet::Pluto {
+        return pluto(d);
+    }
+    let el = elements(planet, d);
+    let (v, r) = solve_kepler(el.anomaly, el.ecc, el.axis);
+    let (node, incl, arg) = (el.node.to_radians(), el.incl.to_radians(), (v + el.peri).to_radians());
+    let x = r * (node.cos() * arg.cos() - node.sin() * arg.sin() * incl.cos());
+    let y = r * (node.sin() * arg.cos() + node.cos() * arg.sin() * incl.cos());
+    let z = r * arg.sin() * incl.sin();
+    let (mut lon, mut lat) = (y.atan2(x).to_degrees(), z.atan2(x.hypot(y)).to_degrees());
+
+    let mj = elements(Planet::Jupiter, d).anomaly;
+    let ms = elements(Planet::Saturn, d).anomaly;
+    let sin = |deg: f64| deg.to_radians().sin();
+    let cos = |deg: f64| deg.to_radians().cos();
+    match planet {
+        Planet::Jupiter => {
+            lon += -0.332 * sin(2.0 * mj - 5.0 * ms - 67.6) - 0.056 * sin(2.0 * mj - 2.0 * ms + 21.0)
+                + 0.042 * sin(3.0 * mj - 5.0 * ms + 21.0)
+                - 0.036 * sin(mj - 2.0 * ms)
+                + 0.022 * cos(mj - ms)
+                + 0.023 * sin(2.0 * mj - 3.0 * ms + 52.0)
+                - 0.016 * sin(mj - 5.0 * ms - 69.0);
+        }
+        Planet::Saturn => {
+            lon += 0.812 * sin(2.0 * mj - 5.0 * ms - 67.6) - 0.229 * cos(2.0 * mj - 4.0 * ms - 2.0)
+                + 0.119 * sin(mj - 2.0 * ms - 3.0)
+                + 0.046 * sin(2.0 * mj - 6.0 * ms - 69.0)
+                + 0.014 * sin(mj - 3.0 * ms + 32.0);
+            lat += -0.020 * cos(2.0 * mj - 4.0 * ms - 2.0) + 0.018 * sin(2.0 * mj - 6.0 * ms - 49.0);
+        }
+        Planet::Uranus => {
+            let mu = el.anomaly;
+            lon += 0.040 * sin(ms - 2.0 * mu + 6.0) + 0.035 * sin(ms - 3.0 * mu + 33.0)
+                - 0.015 * sin(mj - mu + 20.0);
+        }
+        _ => {}
+    }
+    (lon, lat, r)
+}
+
+/// Pluto from a periodic series fitted for 1885-2099
+fn pluto(d: f64) -> (f64, f64, f64) {
+    let s = (50.03 + 0.033459652 * d).to_radians();
+    let p = (238.95 + 0.003968789 * d).to_radians();
+    let lon = 238.9508 + 0.00400703 * d - 19.799 * p.sin() + 19.848 * p.cos() + 0.897 * (2.0 * p).sin()
+        - 4.956 * (2.0 * p).cos()
+        + 0.610 * (3.0 * p).sin()
+        + 1.211 * (3.0 * p).cos()
+        - 0.341 * (4.0 * p).sin()
+        - 0.190 * (4.0 * p).cos()
+        + 0.128 * (5.0 * p).sin()
+        - 0.034 * (5.0 * p).cos()
+        - 0.038 * (6.0 * p).sin()
+        + 0.031 * (6.0 * p).cos()
+        + 0.020 * (s - p).sin()
+        - 0.010 * (s - p).cos();
+    let lat = -3.9082 - 5.453 * p.sin() - 14.975 * p.cos() + 3.527 * (2.0 * p).sin() + 1.673 * (2.0 * p).cos()
+        - 1.051 * (3.0 * p).sin()
+        + 0.328 * (3.0 * p).cos()
+        + 0.179 * (4.0 * p).sin()
+        - 0.292 * (4.0 * p).cos()
+        + 0.019 * (5.0 * p).sin()
+        + 0.100 * (5.0 * p).cos()
+        - 0.031 * (6.0 * p).sin()
+        - 0.026 * (6.0 * p).cos()
+        + 0.011 * (s - p).cos();
+    let r = 40.72 + 6.68 * p.sin() + 6.90 * p.cos() - 1.18 * (2.0 * p).sin() - 0.03 * (2.0 * p).cos()
+        + 0.15 * (3.0 * p).sin()
+        - 0.14 * (3.0 * p).cos();
+    (lon, lat, r)
+}
+
+fn jd_to_days(jd_tt: f64) -> f64 {
+    jd_tt - 2451543.5
+}
+
+/// Geocentric ecliptic coordinates of the Sun: (longitude, distance), geometric
+fn sun_geometric(d: f64) -> (f64, f64) {
+    let peri = 282.9404 + 4.70935e-5 * d;
+    let ecc = 0.016709 - 1.151e-9 * d;
+    let anomaly = 356.0470 + 0.9856002585 * d;
+    let (v, r) = solve_kepler(anomaly, ecc, 1.0);
+    (normalize(v + peri), r)
+}
+
+fn rectangular(lon: f64, lat: f64, r: f64) -> [f64; 3] {
+    let (lon, lat) = (lon.to_radians(), lat.to_radians());
+    [r * lat.cos() * lon.cos(), r * lat.cos() * lon.sin(), r * lat.sin()]
+}
+
+/// Apparent geocentric longitude of the Sun (mean equinox of date, no nutation)
+pub fn sun(jd_tt: f64) -> f64 {
+    let (lon, r) = sun_geometric(jd_to_days(jd_tt));
+    normalize(lon - ABERRATION / r)
+}
+
+/// Apparent geocentric ecliptic longitude and latitude of a planet (mean equinox of date,
+/// no nutation), corrected for light time and annual aberration
+pub fn planet(planet: Planet, jd_tt: f64) -> (f64, f64) {
+    let d = jd_to_days(jd_tt);
+    let (sun_lon, sun_r) = sun_geometric(d);
+    // Geocentric = heliocentric planet minus heliocentric Earth = plus the geocentric Sun
+    let sun_vector = rectangular(sun_lon, 0.0, sun_r);
+
+    let geocentric = |t: f64| {
+        let (lon, lat, r) = heliocentric(planet, t);
+        let p = rectangular(lon, lat, r);
+        let (x, y, z) = (p[0] + sun_vector[0], p[1] + sun_vector[1], p[2] + sun_vector[2]);
+        let distance = (x * x + y * y + z * z).sqrt();
+        (y.atan2(x).to_degrees(), z.atan2(x.hypot(y)).to_degrees(), distance)
+    };
+    let (_, _, distance) = geocentric(d);
+    let (lon, lat, _) = geocentric(d - distance * LIGHT_TIME_PER_AU);
+
+    let aberration = -ABERRATION * (sun_lon - lon).to_radians().cos() / lat.to_radians().cos();
+    (normalize(lon + aberration), lat)
+}
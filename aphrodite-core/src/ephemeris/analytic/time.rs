@@ -0,0 +1,111 @@
+//! Time scales, nutation and sidereal time (Meeus, "Astronomical Algorithms", ch. 10-22).
+
+use crate::ephemeris::systems::J2000;
+use chrono::{DateTime, Utc};
+
+/// Julian Day (UT) of a UTC datetime, proleptic Gregorian calendar
+pub fn julian_day(dt: DateTime<Utc>) -> f64 {
+    dt.timestamp_millis() as f64 / 86_400_000.0 + 2_440_587.5
+}
+
+/// Julian centuries since J2000.0
+pub fn centuries(jd: f64) -> f64 {
+    (jd - J2000) / 36525.0
+}
+
+/// TT - UT in seconds (Espenak & Meeus polynomials, parabolic outside 1800-2150)
+pub fn delta_t(jd_ut: f64) -> f64 {
+    let y = 2000.0 + (jd_ut - J2000) / 365.25;
+    let parabola = |y: f64| {
+        let u = (y - 1820.0) / 100.0;
+        -20.0 + 32.0 * u * u
+    };
+    match y {
+        y if (1800.0..1860.0).contains(&y) => {
+            let t = y - 1800.0;
+            13.72 - 0.332447 * t + 0.0068612 * t.powi(2) + 0.0041116 * t.powi(3) - 0.00037436 * t.powi(4)
+                + 0.0000121272 * t.powi(5)
+                - 0.0000001699 * t.powi(6)
+                + 0.000000000875 * t.powi(7)
+        }
+        y if (1860.0..1900.0).contains(&y) => {
+            let t = y - 1860.0;
+            7.62 + 0.5737 * t - 0.251754 * t.powi(2) + 0.01680668 * t.powi(3) - 0.0004473624 * t.powi(4)
+                + t.powi(5) / 233174.0
+        }
+        y if (1900.0..1920.0).contains(&y) => {
+            let t = y - 1900.0;
+            -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3) - 0.000197 * t.powi(4)
+        }
+        y if (1920.0..1941.0).contains(&y) => {
+            let t = y - 1920.0;
+            21.20 + 0.84493 * t - 0.076100 * t.powi(2) + 0.0020936 * t.powi(3)
+        }
+        y if (1941.0..1961.0).contains(&y) => {
+            let t = y - 1950.0;
+            29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+        }
+        y if (1961.0..1986.0).contains(&y) => {
+            let t = y - 1975.0;
+            45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+        }
+        y if (1986.0..2005.0).contains(&y) => {
+            let t = y - 2000.0;
+            63.86 + 0.3345 * t - 0.060374 * t.powi(2) + 0.0017275 * t.powi(3) + 0.000651814 * t.powi(4)
+                + 0.00002373599 * t.powi(5)
+        }
+        y if (2005.0..2050.0).contains(&y) => {
+            let t = y - 2000.0;
+            62.92 + 0.32217 * t + 0.005589 * t.powi(2)
+        }
+        y if (2050.0..2150.0).contains(&y) => parabola(y) - 0.5628 * (2150.0 - y),
+        y => parabola(y),
+    }
+}
+
+/// Julian Day in Terrestrial Time for a UT Julian Day
+pub fn terrestrial_time(jd_ut: f64) -> f64 {
+    jd_ut + delta_t(jd_ut) / 86400.0
+}
+
+/// Nutation in longitude and obliquity in degrees (accurate to about 0.5")
+pub fn nutation(jd_tt: f64) -> (f64, f64) {
+    let t = centuries(jd_tt);
+    let omega = (125.04452 - 1934.136261 * t).to_radians();
+    let sun = (280.4665 + 36000.7698 * t).to_radians();
+    let moon = (218.3165 + 481267.8813 * t).to_radians();
+    let dpsi = -17.20 * omega.sin() - 1.32 * (2.0 * sun).sin() - 0.23 * (2.0 * moon).sin()
+        + 0.21 * (2.0 * omega).sin();
+    let deps = 9.20 * omega.cos() + 0.57 * (2.0 * sun).cos() + 0.10 * (2.0 * moon).cos()
+        - 0.09 * (2.0 * omega).cos();
+    (dpsi / 3600.0, deps / 3600.0)
+}
+
+/// Mean obliquity of the ecliptic in degrees
+pub fn mean_obliquity(jd_tt: f64) -> f64 {
+    let t = centuries(jd_tt);
+    23.439291111 - (46.8150 * t + 0.00059 * t * t - 0.001813 * t * t * t) / 3600.0
+}
+
+/// Greenwich apparent sidereal time in degrees
+pub fn apparent_sidereal_time(jd_ut: f64, nutation_lon: f64, obliquity: f64) -> f64 {
+    let t = centuries(jd_ut);
+    let mean = 280.46061837 + 360.98564736629 * (jd_ut - J2000) + 0.000387933 * t * t
+        - t * t * t / 38_710_000.0;
+    normalize(mean + nutation_lon * obliquity.to_radians().cos())
+}
+
+/// Normalize an angle to [0, 360)
+pub fn normalize(degrees: f64) -> f64 {
+    let d = degrees.rem_euclid(360.0);
+    if d >= 360.0 {
+        0.0
+    } else {
+        d
+    }
+}
+
+/// UTC datetime of a UT Julian Day
+pub fn datetime_from_julian_day(jd: f64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(((jd - 2_440_587.5) * 86_400_000.0).round() as i64).unwrap_or_default()
+}
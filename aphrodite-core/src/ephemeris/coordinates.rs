@@ -0,0 +1,212 @@
+//! Obliquity, nutation and conversions between ecliptic, equatorial and horizontal
+//! coordinates (Meeus, "Astronomical Algorithms", ch. 13 and 22).
+//!
+//! All angles are in degrees. Azimuth is measured from north through east, and right
+//! ascension in degrees rather than hours. The obliquity and nutation come from the short
+//! series used by the analytic ephemeris and are good to about half an arcsecond.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::ephemeris::analytic::time;
+use crate::ephemeris::types::GeoLocation;
+
+/// Earth's equatorial radius in km
+const EQUATORIAL_RADIUS_KM: f64 = 6378.14;
+
+/// Earth's polar radius over its equatorial radius
+const POLAR_RATIO: f64 = 0.99664719;
+
+/// Nutation in longitude and obliquity, in degrees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Nutation {
+    pub longitude: f64,
+    pub obliquity: f64,
+}
+
+/// Ecliptic longitude and latitude
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Ecliptic {
+    pub lon: f64,
+    pub lat: f64,
+}
+
+/// Right ascension and declination
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Equatorial {
+    pub ra: f64,
+    pub dec: f64,
+}
+
+/// Azimuth from north through east, and altitude above the horizon
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Horizontal {
+    pub azimuth: f64,
+    pub altitude: f64,
+}
+
+/// Nutation at a moment
+pub fn nutation(dt: DateTime<Utc>) -> Nutation {
+    let (longitude, obliquity) = time::nutation(time::terrestrial_time(time::julian_day(dt)));
+    Nutation { longitude, obliquity }
+}
+
+/// Mean obliquity of the ecliptic at a moment
+pub fn mean_obliquity(dt: DateTime<Utc>) -> f64 {
+    time::mean_obliquity(time::terrestrial_time(time::julian_day(dt)))
+}
+
+/// True obliquity of the ecliptic at a moment: the mean obliquity plus nutation
+pub fn true_obliquity(dt: DateTime<Utc>) -> f64 {
+    mean_obliquity(dt) + nutation(dt).obliquity
+}
+
+/// Apparent sidereal time at `lon` (east positive)
+pub fn local_sidereal_time(dt: DateTime<Utc>, lon: f64) -> f64 {
+    let jd = time::julian_day(dt);
+    let nutation = nutation(dt);
+    let obliquity = time::mean_obliquity(time::terrestrial_time(jd)) + nutation.obliquity;
+    time::normalize(time::apparent_sidereal_time(jd, nutation.longitude, obliquity) + lon)
+}
+
+/// Ecliptic to equatorial coordinates for the given obliquity
+pub fn ecliptic_to_equatorial(ecliptic: Ecliptic, obliquity: f64) -> Equatorial {
+    let (sin_e, cos_e) = obliquity.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = ecliptic.lon.to_radians().sin_cos();
+    let (sin_lat, cos_lat) = ecliptic.lat.to_radians().sin_cos();
+    let ra = (sin_lon * cos_e - sin_lat / cos_lat * sin_e).atan2(cos_lon);
+    let dec = (sin_lat * cos_e + cos_lat * sin_e * sin_lon).asin();
+    Equatorial { ra: time::normalize(ra.to_degrees()), dec: dec.to_degrees() }
+}
+
+/// Equatorial to ecliptic coordinates for the given obliquity
+pub fn equatorial_to_ecliptic(equatorial: Equatorial, obliquity: f64) -> Ecliptic {
+    let (sin_e, cos_e) = obliquity.to_radians().sin_cos();
+    let (sin_ra, cos_ra) = equatorial.ra.to_radians().sin_cos();
+    let (sin_dec, cos_dec) = equatorial.dec.to_radians().sin_cos();
+    let lon = (sin_ra * cos_e + sin_dec / cos_dec * sin_e).atan2(cos_ra);
+    let lat = (sin_dec * cos_e - cos_dec * sin_e * sin_ra).asin();
+    Ecliptic { lon: time::normalize(lon.to_degrees()), lat: lat.to_degrees() }
+}
+
+/// Equatorial to horizontal coordinates at latitude `lat` and local sidereal time `lst`.
+/// Refraction is not applied.
+pub fn equatorial_to_horizontal(equatorial: Equatorial, lst: f64, lat: f64) -> Horizontal {
+    let (sin_h, cos_h) = (lst - equatorial.ra).to_radians().sin_cos();
+    let (sin_lat, cos_lat) = lat.to_radians().sin_cos();
+    let (sin_dec, cos_dec) = equatorial.dec.to_radians().sin_cos();
+    // Meeus measures azimuth from the south; turn it to start from the north
+    let azimuth = sin_h.atan2(cos_h * sin_lat - sin_dec / cos_dec * cos_lat);
+    let altitude = (sin_lat * sin_dec + cos_lat * cos_dec * cos_h).asin();
+    Horizontal { azimuth: time::normalize(azimuth.to_degrees() + 180.0), altitude: altitude.to_degrees() }
+}
+
+/// Horizontal to equatorial coordinates at latitude `lat` and local sidereal time `lst`
+pub fn horizontal_to_equatorial(horizontal: Horizontal, lst: f64, lat: f64) -> Equatorial {
+    let (sin_a, cos_a) = (horizontal.azimuth - 180.0).to_radians().sin_cos();
+    let (sin_lat, cos_lat) = lat.to_radians().sin_cos();
+    let (sin_alt, cos_alt) = horizontal.altitude.to_radians().sin_cos();
+    let hour_angle = sin_a.atan2(cos_a * sin_lat + sin_alt / cos_alt * cos_lat);
+    let dec = (sin_lat * sin_alt - cos_lat * cos_alt * cos_a).asin();
+    Equatorial { ra: time::normalize(lst - hour_angle.to_degrees()), dec: dec.to_degrees() }
+}
+
+/// Where an ecliptic position stands in the sky of `location` at `dt`, using the true
+/// obliquity of the date
+pub fn ecliptic_to_horizontal(ecliptic: Ecliptic, dt: DateTime<Utc>, location: &GeoLocation) -> Horizontal {
+    let equatorial = ecliptic_to_equatorial(ecliptic, true_obliquity(dt));
+    equatorial_to_horizontal(equatorial, local_sidereal_time(dt, location.lon), location.lat)
+}
+
+/// Ecliptic position seen from latitude `lat` at sea level rather than from the Earth's
+/// centre, for a body `distance_km` away at local sidereal time `lst` (Meeus ch. 11 and 40)
+pub fn ecliptic_to_topocentric(ecliptic: Ecliptic, distance_km: f64, obliquity: f64, lst: f64, lat: f64) -> Ecliptic {
+    let u = (POLAR_RATIO * lat.to_radians().tan()).atan();
+    let (rho_sin, rho_cos) = (POLAR_RATIO * u.sin(), u.cos());
+    let sin_parallax = EQUATORIAL_RADIUS_KM / distance_km;
+    let (sin_e, cos_e) = obliquity.to_radians().sin_cos();
+    let (sin_lst, cos_lst) = lst.to_radians().sin_cos();
+    let (sin_lon, cos_lon) = ecliptic.lon.to_radians().sin_cos();
+    let (sin_lat, cos_lat) = ecliptic.lat.to_radians().sin_cos();
+
+    // Geocentric direction minus the observer's offset from the centre, in Earth radii
+    let x = cos_lat * cos_lon - sin_parallax * rho_cos * cos_lst;
+    let y = cos_lat * sin_lon - sin_parallax * (rho_sin * sin_e + rho_cos * cos_e * sin_lst);
+    let z = sin_lat - sin_parallax * (rho_sin * cos_e - rho_cos * sin_e * sin_lst);
+    Ecliptic { lon: time::normalize(y.atan2(x).to_degrees()), lat: z.atan2(x.hypot(y)).to_degrees() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        ((a - b + 540.0).rem_euclid(360.0) - 180.0).abs() < tolerance
+    }
+
+    #[test]
+    fn test_ecliptic_equatorial_round_trip() {
+        // Meeus example 13.a: Pollux
+        let pollux = Equatorial { ra: 116.328942, dec: 28.026183 };
+        let ecliptic = equatorial_to_ecliptic(pollux, 23.4392911);
+        assert!(close(ecliptic.lon, 113.215630, 1e-5), "{:?}", ecliptic);
+        assert!(close(ecliptic.lat, 6.684170, 1e-5), "{:?}", ecliptic);
+
+        let back = ecliptic_to_equatorial(ecliptic, 23.4392911);
+        assert!(close(back.ra, pollux.ra, 1e-9) && close(back.dec, pollux.dec, 1e-9), "{:?}", back);
+    }
+
+    #[test]
+    fn test_equatorial_horizontal_round_trip() {
+        // Meeus example 13.b: Venus from Washington, 1987 April 10 19:21 UT
+        let venus = Equatorial { ra: 347.3193375, dec: -6.719892 };
+        let lst = 128.7368875 - 77.065556;
+        let horizontal = equatorial_to_horizontal(venus, lst, 38.921389);
+        assert!(close(horizontal.azimuth, 68.0337 + 180.0, 2e-4), "{:?}", horizontal);
+        assert!(close(horizontal.altitude, 15.1249, 1e-4), "{:?}", horizontal);
+
+        let back = horizontal_to_equatorial(horizontal, lst, 38.921389);
+        assert!(close(back.ra, venus.ra, 1e-9) && close(back.dec, venus.dec, 1e-9), "{:?}", back);
+    }
+
+    #[test]
+    fn test_obliquity_and_nutation_at_date() {
+        // Meeus example 22.a: 1987 April 10 0h TT
+        let dt = Utc.with_ymd_and_hms(1987, 4, 9, 23, 58, 55).unwrap();
+        let nutation = nutation(dt);
+        assert!((nutation.longitude * 3600.0 + 3.788).abs() < 0.5, "{:?}", nutation);
+        assert!((nutation.obliquity * 3600.0 - 9.443).abs() < 0.5, "{:?}", nutation);
+        assert!((mean_obliquity(dt) - 23.440946).abs() < 1e-5);
+        assert!((true_obliquity(dt) - 23.443569).abs() < 2e-4);
+    }
+
+    #[test]
+    fn test_sidereal_time_and_sky_position() {
+        // Meeus example 12.b: 1987 April 10 19:21 UT, apparent sidereal time 8h34m56.853s
+        let dt = Utc.with_ymd_and_hms(1987, 4, 10, 19, 21, 0).unwrap();
+        assert!(close(local_sidereal_time(dt, 0.0), 128.7368875, 1e-3));
+        assert!(close(local_sidereal_time(dt, -77.065556), 128.7368875 - 77.065556, 1e-3));
+
+        // The point due east on the horizon comes back there through ecliptic coordinates
+        let washington = GeoLocation { lat: 38.921389, lon: -77.065556 };
+        let lst = local_sidereal_time(dt, washington.lon);
+        let east = horizontal_to_equatorial(Horizontal { azimuth: 90.0, altitude: 0.0 }, lst, washington.lat);
+        let ecliptic = equatorial_to_ecliptic(east, true_obliquity(dt));
+        let horizontal = ecliptic_to_horizontal(ecliptic, dt, &washington);
+        assert!(close(horizontal.azimuth, 90.0, 1e-9) && horizontal.altitude.abs() < 1e-9, "{:?}", horizontal);
+    }
+
+    #[test]
+    fn test_topocentric_moon() {
+        // Meeus ch. 40 exercise: horizontal parallax 0°59'27.7"
+        let moon = Ecliptic { lon: 181.772917, lat: 2.290611 };
+        let distance = EQUATORIAL_RADIUS_KM / (59.0 / 60.0 + 27.7 / 3600.0_f64).to_radians().sin();
+        let topocentric = ecliptic_to_topocentric(moon, distance, 23.466889, 209.768861, 50.085500);
+        assert!(close(topocentric.lon, 181.801389, 1e-4), "{:?}", topocentric);
+        assert!(close(topocentric.lat, 1.485306, 1e-4), "{:?}", topocentric);
+
+        // Parallax vanishes with distance
+        let far = ecliptic_to_topocentric(moon, 1e12, 23.466889, 209.768861, 50.085500);
+        assert!(close(far.lon, moon.lon, 1e-6) && close(far.lat, moon.lat, 1e-6), "{:?}", far);
+    }
+}
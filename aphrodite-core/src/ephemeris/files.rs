@@ -0,0 +1,208 @@
+//! Swiss Ephemeris data files: which years each one covers and which are installed.
+//!
+//! Files hold 600-year blocks and are named after the first century of the block:
+//! `sepl_18.se1` has the planets for 1800-2399, `semo_18.se1` the Moon and `seas_18.se1`
+//! the main asteroids. Blocks before year 0 carry an `m`, as in `seplm06.se1` for -600 to -1.
+
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::Path;
+
+/// Years in one file
+const BLOCK_YEARS: i32 = 600;
+
+/// Files installed by default: planets, Moon and asteroids for 1200-2399
+pub const STANDARD_FILES: &[&str] = &[
+    "sepl_12.se1", "semo_12.se1", "seas_12.se1",
+    "sepl_18.se1", "semo_18.se1", "seas_18.se1",
+];
+
+/// What an ephemeris file holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EphemerisFileKind {
+    /// Sun, planets and Pluto (`sepl`)
+    Planets,
+    /// The Moon (`semo`)
+    Moon,
+    /// Chiron, Pholus, Ceres, Pallas, Juno and Vesta (`seas`)
+    Asteroids,
+}
+
+impl EphemerisFileKind {
+    pub const ALL: [EphemerisFileKind; 3] = [Self::Planets, Self::Moon, Self::Asteroids];
+
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::Planets => "sepl",
+            Self::Moon => "semo",
+            Self::Asteroids => "seas",
+        }
+    }
+}
+
+/// Inclusive range of years
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YearRange {
+    #[serde(rename = "startYear")]
+    pub start_year: i32,
+    #[serde(rename = "endYear")]
+    pub end_year: i32,
+}
+
+impl YearRange {
+    pub fn contains(&self, year: i32) -> bool {
+        (self.start_year..=self.end_year).contains(&year)
+    }
+}
+
+/// A standard ephemeris data file, identified by name
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EphemerisFile {
+    pub name: String,
+    pub kind: EphemerisFileKind,
+    #[serde(flatten)]
+    pub years: YearRange,
+}
+
+impl EphemerisFile {
+    /// The file of `kind` whose block contains `year`
+    pub fn covering(kind: EphemerisFileKind, year: i32) -> Self {
+        let start_year = year.div_euclid(BLOCK_YEARS) * BLOCK_YEARS;
+        let name = if start_year >= 0 {
+            format!("{}_{:02}.se1", kind.prefix(), start_year / 100)
+        } else {
+            format!("{}m{:02}.se1", kind.prefix(), -start_year / 100)
+        };
+        Self { name, kind, years: YearRange { start_year, end_year: start_year + BLOCK_YEARS - 1 } }
+    }
+
+    /// Parse a file name such as `sepl_18.se1` or `semom06.se1`; None for anything else,
+    /// including the numbered single-asteroid files
+    pub fn parse(name: &str) -> Option<Self> {
+        let stem = name.strip_suffix(".se1")?;
+        let kind = EphemerisFileKind::ALL.into_iter().find(|kind| stem.starts_with(kind.prefix()))?;
+        let block = &stem[kind.prefix().len()..];
+        let (sign, century) = match block.split_at_checked(1)? {
+            ("_", century) => (1, century),
+            ("m", century) => (-1, century),
+            _ => return None,
+        };
+        if century.len() != 2 || !century.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let start_year = sign * century.parse::<i32>().ok()? * 100;
+        if start_year % BLOCK_YEARS != 0 {
+            return None;
+        }
+        Some(Self {
+            name: name.to_string(),
+            kind,
+            years: YearRange { start_year, end_year: start_year + BLOCK_YEARS - 1 },
+        })
+    }
+}
+
+/// An ephemeris file found in a directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstalledFile {
+    #[serde(flatten)]
+    pub file: EphemerisFile,
+    /// Size in bytes
+    pub size: u64,
+}
+
+/// Ephemeris files in `dir`, sorted by kind and first year. Other files are ignored.
+pub fn installed_files(dir: &Path) -> io::Result<Vec<InstalledFile>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let Some(file) = entry.file_name().to_str().and_then(EphemerisFile::parse) else {
+            continue;
+        };
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            files.push(InstalledFile { file, size: metadata.len() });
+        }
+    }
+    files.sort_by_key(|installed| (installed.file.kind as u8, installed.file.years.start_year));
+    Ok(files)
+}
+
+/// Years covered by files of one kind, with adjacent blocks merged
+pub fn coverage<'a>(files: impl IntoIterator<Item = &'a EphemerisFile>, kind: EphemerisFileKind) -> Vec<YearRange> {
+    let mut years: Vec<YearRange> = files.into_iter().filter(|file| file.kind == kind).map(|file| file.years).collect();
+    years.sort_by_key(|range| range.start_year);
+    let mut merged: Vec<YearRange> = Vec::new();
+    for range in years {
+        match merged.last_mut() {
+            Some(last) if range.start_year <= last.end_year + 1 => last.end_year = last.end_year.max(range.end_year),
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// [`STANDARD_FILES`] not present in `installed`
+pub fn missing_standard_files(installed: &[InstalledFile]) -> Vec<&'static str> {
+    STANDARD_FILES
+        .iter()
+        .copied()
+        .filter(|name| !installed.iter().any(|installed| installed.file.name == *name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_file_names() {
+        let file = EphemerisFile::parse("sepl_18.se1").unwrap();
+        assert_eq!(file.kind, EphemerisFileKind::Planets);
+        assert_eq!(file.years, YearRange { start_year: 1800, end_year: 2399 });
+
+        let file = EphemerisFile::parse("semom06.se1").unwrap();
+        assert_eq!(file.kind, EphemerisFileKind::Moon);
+        assert_eq!(file.years, YearRange { start_year: -600, end_year: -1 });
+
+        assert_eq!(EphemerisFile::covering(EphemerisFileKind::Planets, 1492).name, "sepl_12.se1");
+        assert_eq!(EphemerisFile::covering(EphemerisFileKind::Moon, -1), EphemerisFile::parse("semom06.se1").unwrap());
+
+        for name in ["sepl_17.se1", "sepl_18.txt", "se00433.se1", "seas_1.se1", "../sepl_18.se1", "sepl_ü8.se1"] {
+            assert!(EphemerisFile::parse(name).is_none(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_coverage_merges_adjacent_blocks() {
+        let files: Vec<EphemerisFile> = ["sepl_12.se1", "sepl_18.se1", "sepl_30.se1", "semo_18.se1"]
+            .iter()
+            .filter_map(|name| EphemerisFile::parse(name))
+            .collect();
+        assert_eq!(
+            coverage(&files, EphemerisFileKind::Planets),
+            vec![
+                YearRange { start_year: 1200, end_year: 2399 },
+                YearRange { start_year: 3000, end_year: 3599 },
+            ]
+        );
+        assert_eq!(coverage(&files, EphemerisFileKind::Asteroids), vec![]);
+    }
+
+    #[test]
+    fn test_installed_files() {
+        let dir = std::env::temp_dir().join(format!("aphrodite-ephe-files-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("semo_18.se1"), [0u8; 16]).unwrap();
+        std::fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let installed = installed_files(&dir).unwrap();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].file.name, "semo_18.se1");
+        assert_eq!(installed[0].size, 16);
+        assert_eq!(missing_standard_files(&installed).len(), STANDARD_FILES.len() - 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,80 @@
+//! Bright fixed stars used in chart work.
+//!
+//! Positions are tropical ecliptic coordinates at J2000.0, carried to other dates by general
+//! precession in longitude. Proper motion is ignored; for these stars it stays well under an
+//! arcminute within a few centuries of J2000, which is below what a chart wheel can show.
+
+use crate::ephemeris::analytic::julian_day;
+use crate::ephemeris::systems::J2000;
+use chrono::{DateTime, Utc};
+
+/// A fixed star with its J2000.0 ecliptic position
+#[derive(Debug, Clone, Copy)]
+pub struct FixedStar {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// Tropical ecliptic longitude at J2000.0 in degrees
+    pub lon_j2000: f64,
+    /// Ecliptic latitude in degrees
+    pub lat: f64,
+    /// Visual magnitude
+    pub magnitude: f64,
+}
+
+const fn star(id: &'static str, name: &'static str, lon_j2000: f64, lat: f64, magnitude: f64) -> FixedStar {
+    FixedStar { id, name, lon_j2000, lat, magnitude }
+}
+
+/// Catalogue of fixed stars, in order of longitude
+pub const FIXED_STARS: &[FixedStar] = &[
+    star("algol", "Algol", 56.167, 22.43, 2.12),
+    star("alcyone", "Alcyone", 59.983, 4.05, 2.87),
+    star("aldebaran", "Aldebaran", 69.783, -5.47, 0.85),
+    star("rigel", "Rigel", 76.833, -31.12, 0.13),
+    star("capella", "Capella", 81.850, 22.87, 0.08),
+    star("betelgeuse", "Betelgeuse", 88.750, -16.03, 0.50),
+    star("sirius", "Sirius", 104.083, -39.61, -1.46),
+    star("castor", "Castor", 110.233, 10.09, 1.58),
+    star("pollux", "Pollux", 113.217, 6.68, 1.14),
+    star("procyon", "Procyon", 115.783, -16.02, 0.34),
+    star("regulus", "Regulus", 149.833, 0.46, 1.35),
+    star("spica", "Spica", 203.833, -2.05, 0.98),
+    star("arcturus", "Arcturus", 204.233, 30.73, -0.05),
+    star("antares", "Antares", 249.767, -4.57, 1.06),
+    star("vega", "Vega", 285.317, 61.73, 0.03),
+    star("altair", "Altair", 301.783, 29.30, 0.77),
+    star("fomalhaut", "Fomalhaut", 333.867, -21.14, 1.16),
+    star("achernar", "Achernar", 345.317, -59.38, 0.46),
+];
+
+/// Look up a star by id (case-insensitive)
+pub fn find_fixed_star(id: &str) -> Option<&'static FixedStar> {
+    FIXED_STARS.iter().find(|star| star.id.eq_ignore_ascii_case(id))
+}
+
+/// Tropical ecliptic longitude of `star` at `datetime`, in [0, 360)
+pub fn fixed_star_longitude(star: &FixedStar, datetime: DateTime<Utc>) -> f64 {
+    let t = (julian_day(datetime) - J2000) / 36525.0;
+    let precession = (5028.796195 * t + 1.1054348 * t * t) / 3600.0;
+    (star.lon_j2000 + precession).rem_euclid(360.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_precession_moves_stars_forward() {
+        let regulus = find_fixed_star("Regulus").unwrap();
+        let j2000 = Utc.with_ymd_and_hms(2000, 1, 1, 12, 0, 0).unwrap();
+        assert!((fixed_star_longitude(regulus, j2000) - 149.833).abs() < 1e-6);
+
+        // Regulus entered Virgo in late 2011; a degree takes about 72 years
+        let before = Utc.with_ymd_and_hms(2010, 1, 1, 0, 0, 0).unwrap();
+        let after = Utc.with_ymd_and_hms(2013, 1, 1, 0, 0, 0).unwrap();
+        assert!(fixed_star_longitude(regulus, before) < 150.0);
+        assert!(fixed_star_longitude(regulus, after) > 150.0);
+        assert!(find_fixed_star("vulcan").is_none());
+    }
+}
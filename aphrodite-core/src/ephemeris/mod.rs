@@ -1,8 +1,22 @@
+#[cfg(feature = "swisseph")]
 pub mod adapter;
+pub mod analytic;
+pub mod coordinates;
+pub mod error;
+pub mod files;
+pub mod fixed_stars;
+pub mod progressions;
+pub mod systems;
 pub mod types;
 
-pub use adapter::SwissEphemerisAdapter;
+#[cfg(feature = "swisseph")]
+pub use adapter::{lock_swiss_state, reload_ephemeris_files, SwissEphemerisAdapter};
+pub use analytic::AnalyticEphemeris;
+pub use error::EphemerisError;
+pub use files::{EphemerisFile, EphemerisFileKind, InstalledFile, YearRange};
+pub use fixed_stars::{find_fixed_star, fixed_star_longitude, FixedStar, FIXED_STARS};
+pub use progressions::{AngleProgression, Progression, ProgressionOptions};
 pub use types::{
-    EphemerisSettings, GeoLocation, HousePositions, LayerContext, LayerPositions, PlanetPosition,
+    EphemerisSettings, GeoLocation, HousePositions, LayerContext, LayerPositions, LilithType, MoonPosition, NodeType,
+    PlanetPhenomena, PlanetPosition, PolarFallback, PositionWarning, ANGLE_IDS, DEFAULT_OBJECTS, HOUSE_POINTS,
 };
-
@@ -0,0 +1,169 @@
+//! Secondary progressions: a day after birth for each year of life.
+//!
+//! Planets are taken at the progressed moment. The Midheaven moves by the solar arc or at
+//! the Naibod rate, and the remaining angles and house cusps follow from it at the birth
+//! latitude. Converse progressions count the days backwards from birth.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::ephemeris::error::EphemerisError;
+use crate::ephemeris::types::{EphemerisSettings, GeoLocation, HousePositions, LayerPositions};
+
+/// Days in a tropical year
+pub const TROPICAL_YEAR_DAYS: f64 = 365.242_19;
+
+/// The Sun's mean motion in degrees per day, applied to the Midheaven per year of life
+pub const NAIBOD_RATE: f64 = 0.985_647_36;
+
+/// Passes of the house calculation used to land the progressed Midheaven
+const MC_ITERATIONS: usize = 8;
+
+/// How the Midheaven moves in a progressed chart
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AngleProgression {
+    /// By the progressed Sun's distance from the natal Sun
+    #[default]
+    SolarArc,
+    /// By the Sun's mean daily motion per year
+    Naibod,
+}
+
+/// Options for a progressed layer
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgressionOptions {
+    #[serde(default)]
+    pub angles: AngleProgression,
+    /// Count the progressed days backwards from birth
+    #[serde(default)]
+    pub converse: bool,
+}
+
+/// A progressed layer: the birth it is progressed from and how
+#[derive(Debug, Clone, Copy)]
+pub struct Progression {
+    pub birth: DateTime<Utc>,
+    pub options: ProgressionOptions,
+}
+
+/// Years between birth and the target date
+pub fn years_of_life(birth: DateTime<Utc>, target: DateTime<Utc>) -> f64 {
+    (target - birth).num_seconds() as f64 / 86_400.0 / TROPICAL_YEAR_DAYS
+}
+
+/// The moment whose planets stand for `target`: one day per year, before birth when converse
+pub fn progressed_datetime(birth: DateTime<Utc>, target: DateTime<Utc>, converse: bool) -> DateTime<Utc> {
+    let days = years_of_life(birth, target);
+    let offset = Duration::milliseconds((days * 86_400_000.0).round() as i64);
+    if converse {
+        birth - offset
+    } else {
+        birth + offset
+    }
+}
+
+/// Signed arc the Midheaven moves by, given the natal and progressed Sun
+pub fn angle_arc(options: &ProgressionOptions, years: f64, natal_sun: f64, progressed_sun: f64) -> f64 {
+    match options.angles {
+        AngleProgression::SolarArc => (progressed_sun - natal_sun + 540.0).rem_euclid(360.0) - 180.0,
+        AngleProgression::Naibod if options.converse => -years * NAIBOD_RATE,
+        AngleProgression::Naibod => years * NAIBOD_RATE,
+    }
+}
+
+/// Progressed positions for `target`, using `positions_at` to calculate charts.
+///
+/// Houses are only returned with a birth `location`; they are the houses of the birth
+/// moment and latitude whose Midheaven is the natal Midheaven plus the arc.
+pub fn progressed_positions<F>(
+    birth: DateTime<Utc>,
+    target: DateTime<Utc>,
+    location: Option<GeoLocation>,
+    settings: &EphemerisSettings,
+    options: &ProgressionOptions,
+    mut positions_at: F,
+) -> Result<LayerPositions, EphemerisError>
+where
+    F: FnMut(DateTime<Utc>, Option<GeoLocation>, &EphemerisSettings) -> Result<LayerPositions, EphemerisError>,
+{
+    let moment = progressed_datetime(birth, target, options.converse);
+    let mut positions = positions_at(moment, None, settings)?;
+    let Some(location) = location else {
+        return Ok(positions);
+    };
+
+    let sun_settings = EphemerisSettings { include_objects: vec!["sun".to_string()], ..settings.clone() };
+    let sun = |positions: &LayerPositions, datetime: DateTime<Utc>| {
+        positions.planets.get("sun").map(|sun| sun.lon).ok_or_else(|| EphemerisError::CalculationFailed {
+            planet_id: "sun".to_string(),
+            datetime,
+            message: "Sun needed for the solar arc".to_string(),
+        })
+    };
+    let natal = positions_at(birth, Some(location.clone()), &sun_settings)?;
+    let progressed_sun = sun(&positions_at(moment, None, &sun_settings)?, moment)?;
+    let arc = angle_arc(options, years_of_life(birth, target), sun(&natal, birth)?, progressed_sun);
+
+    let mut houses = natal.houses.ok_or_else(|| EphemerisError::HouseCalculationFailed {
+        message: "Natal houses missing".to_string(),
+    })?;
+    let target_mc = (houses.angles["mc"] + arc).rem_euclid(360.0);
+    // The Midheaven moves almost one for one with the local sidereal time, so shifting the
+    // longitude by the remaining error converges in a few passes
+    let mut shift = 0.0;
+    for _ in 0..MC_ITERATIONS {
+        let error = signed_difference(target_mc, houses.angles["mc"]);
+        if error.abs() < 1e-7 {
+            break;
+        }
+        shift += error;
+        let shifted = GeoLocation {
+            lat: location.lat,
+            lon: (location.lon + shift + 180.0).rem_euclid(360.0) - 180.0,
+        };
+        houses = houses_of(positions_at(birth, Some(shifted), &sun_settings)?)?;
+    }
+    positions.houses = Some(houses);
+    Ok(positions)
+}
+
+fn houses_of(positions: LayerPositions) -> Result<HousePositions, EphemerisError> {
+    positions.houses.ok_or_else(|| EphemerisError::HouseCalculationFailed {
+        message: "Progressed houses missing".to_string(),
+    })
+}
+
+/// `a - b` in degrees, in -180..180
+fn signed_difference(a: f64, b: f64) -> f64 {
+    (a - b + 540.0).rem_euclid(360.0) - 180.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_progressed_datetime_is_a_day_per_year() {
+        let birth = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+        let target = birth + Duration::seconds((30.0 * TROPICAL_YEAR_DAYS * 86_400.0) as i64);
+        assert!((years_of_life(birth, target) - 30.0).abs() < 1e-6);
+        let forward = progressed_datetime(birth, target, false) - (birth + Duration::days(30));
+        assert!(forward.num_milliseconds().abs() < 10);
+        let converse = progressed_datetime(birth, target, true) - (birth - Duration::days(30));
+        assert!(converse.num_milliseconds().abs() < 10);
+    }
+
+    #[test]
+    fn test_angle_arc() {
+        let solar_arc = ProgressionOptions::default();
+        assert!((angle_arc(&solar_arc, 30.0, 350.0, 20.5) - 30.5).abs() < 1e-9);
+        let converse = ProgressionOptions { converse: true, ..solar_arc };
+        assert!((angle_arc(&converse, 30.0, 20.5, 350.0) + 30.5).abs() < 1e-9);
+
+        let naibod = ProgressionOptions { angles: AngleProgression::Naibod, converse: false };
+        assert!((angle_arc(&naibod, 10.0, 0.0, 0.0) - 9.8564736).abs() < 1e-9);
+        let converse_naibod = ProgressionOptions { converse: true, ..naibod };
+        assert!((angle_arc(&converse_naibod, 10.0, 0.0, 0.0) + 9.8564736).abs() < 1e-9);
+    }
+}
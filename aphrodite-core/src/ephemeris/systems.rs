@@ -0,0 +1,104 @@
+//! House system and ayanamsa names shared by the ephemeris backends.
+
+use super::error::EphemerisError;
+
+/// House system mapping
+pub const HOUSE_SYSTEMS: &[(&str, u8)] = &[
+    ("placidus", b'P'),
+    ("whole_sign", b'W'),
+    ("koch", b'K'),
+    ("equal", b'E'),
+    ("regiomontanus", b'R'),
+    ("campanus", b'C'),
+    ("alcabitius", b'A'),
+    ("morinus", b'M'),
+    ("porphyry", b'O'),
+];
+
+/// Ayanamsa mapping - the full Swiss Ephemeris SE_SIDM_* list
+pub const AYANAMSAS: &[(&str, i32)] = &[
+    ("fagan_bradley", 0),             // SE_SIDM_FAGAN_BRADLEY
+    ("lahiri", 1),                    // SE_SIDM_LAHIRI
+    ("chitrapaksha", 1),              // Chitrapaksha is the Lahiri ayanamsa
+    ("de_luce", 2),                   // SE_SIDM_DELUCE
+    ("raman", 3),                     // SE_SIDM_RAMAN
+    ("ushashashi", 4),                // SE_SIDM_USHASHASHI
+    ("krishnamurti", 5),              // SE_SIDM_KRISHNAMURTI
+    ("djwhal_khul", 6),               // SE_SIDM_DJWHAL_KHUL
+    ("yukteshwar", 7),                // SE_SIDM_YUKTESHWAR
+    ("jn_bhasin", 8),                 // SE_SIDM_JN_BHASIN
+    ("babylonian_kugler_1", 9),       // SE_SIDM_BABYL_KUGLER1
+    ("babylonian_kugler_2", 10),      // SE_SIDM_BABYL_KUGLER2
+    ("babylonian_kugler_3", 11),      // SE_SIDM_BABYL_KUGLER3
+    ("babylonian_huber", 12),         // SE_SIDM_BABYL_HUBER
+    ("babylonian_eta_piscium", 13),   // SE_SIDM_BABYL_ETPSC
+    ("aldebaran_15_taurus", 14),      // SE_SIDM_ALDEBARAN_15TAU
+    ("hipparchos", 15),               // SE_SIDM_HIPPARCHOS
+    ("sassanian", 16),                // SE_SIDM_SASSANIAN
+    ("galactic_center_0_sagittarius", 17), // SE_SIDM_GALCENT_0SAG
+    ("j2000", 18),                    // SE_SIDM_J2000
+    ("j1900", 19),                    // SE_SIDM_J1900
+    ("b1950", 20),                    // SE_SIDM_B1950
+    ("suryasiddhanta", 21),           // SE_SIDM_SURYASIDDHANTA
+    ("suryasiddhanta_mean_sun", 22),  // SE_SIDM_SURYASIDDHANTA_MSUN
+    ("aryabhata", 23),                // SE_SIDM_ARYABHATA
+    ("aryabhata_mean_sun", 24),       // SE_SIDM_ARYABHATA_MSUN
+    ("ss_revati", 25),                // SE_SIDM_SS_REVATI
+    ("ss_citra", 26),                 // SE_SIDM_SS_CITRA
+    ("true_citra", 27),               // SE_SIDM_TRUE_CITRA
+    ("true_revati", 28),              // SE_SIDM_TRUE_REVATI
+    ("true_pushya", 29),              // SE_SIDM_TRUE_PUSHYA
+    ("galactic_center_gil_brand", 30), // SE_SIDM_GALCENT_RGILBRAND
+    ("galactic_equator_iau1958", 31), // SE_SIDM_GALEQU_IAU1958
+    ("galactic_equator", 32),         // SE_SIDM_GALEQU_TRUE
+    ("galactic_equator_mula", 33),    // SE_SIDM_GALEQU_MULA
+    ("galactic_alignment_mardyks", 34), // SE_SIDM_GALALIGN_MARDYKS
+    ("true_mula", 35),                // SE_SIDM_TRUE_MULA
+    ("galactic_center_mula_wilhelm", 36), // SE_SIDM_GALCENT_MULA_WILHELM
+    ("aryabhata_522", 37),            // SE_SIDM_ARYABHATA_522
+    ("babylonian_britton", 38),       // SE_SIDM_BABYL_BRITTON
+    ("true_sheoran", 39),             // SE_SIDM_TRUE_SHEORAN
+    ("galactic_center_cochrane", 40), // SE_SIDM_GALCENT_COCHRANE
+    ("galactic_equator_fiorenza", 41), // SE_SIDM_GALEQU_FIORENZA
+    ("valens_moon", 42),              // SE_SIDM_VALENS_MOON
+    ("lahiri_1940", 43),              // SE_SIDM_LAHIRI_1940
+    ("lahiri_vp285", 44),             // SE_SIDM_LAHIRI_VP285
+    ("krishnamurti_vp291", 45),       // SE_SIDM_KRISHNAMURTI_VP291
+    ("lahiri_icrc", 46),              // SE_SIDM_LAHIRI_ICRC
+];
+
+/// Ayanamsa used for the sidereal zodiac when none is given
+pub const DEFAULT_AYANAMSA: &str = "lahiri";
+
+/// Ayanamsa name for a user-supplied value (see `EphemerisSettings::custom_ayanamsa`)
+pub const CUSTOM_AYANAMSA: &str = "custom";
+
+/// Reference epoch for custom ayanamsa values (J2000.0)
+pub const J2000: f64 = 2451545.0;
+
+/// Check whether an ayanamsa name is supported (including "custom")
+pub fn is_supported_ayanamsa(name: &str) -> bool {
+    let name = name.to_lowercase();
+    name == CUSTOM_AYANAMSA || AYANAMSAS.iter().any(|(id, _)| *id == name)
+}
+
+/// Names of all supported ayanamsas, including "custom"
+pub fn supported_ayanamsa_names() -> Vec<String> {
+    AYANAMSAS
+        .iter()
+        .map(|(name, _)| name.to_string())
+        .chain(std::iter::once(CUSTOM_AYANAMSA.to_string()))
+        .collect()
+}
+
+/// Convert house system string to byte format
+pub(crate) fn get_house_system_byte(house_system: &str) -> Result<u8, EphemerisError> {
+    HOUSE_SYSTEMS
+        .iter()
+        .find(|(name, _)| *name == house_system.to_lowercase())
+        .map(|(_, byte)| *byte)
+        .ok_or_else(|| EphemerisError::InvalidHouseSystem {
+            system: house_system.to_string(),
+            valid: HOUSE_SYSTEMS.iter().map(|(name, _)| name.to_string()).collect(),
+        })
+}
@@ -21,6 +21,32 @@ pub struct PlanetPosition {
     pub retrograde: bool,
 }
 
+/// An included object left out of a layer because its calculation failed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PositionWarning {
+    #[serde(rename = "objectId")]
+    pub object_id: String,
+    /// Why the calculation failed
+    pub message: String,
+}
+
+/// How a planet appears from Earth, for visibility
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetPhenomena {
+    /// Sun-planet-Earth angle in degrees
+    #[serde(rename = "phaseAngle")]
+    pub phase_angle: f64,
+    /// Illuminated fraction of the disc (0-1)
+    pub phase: f64,
+    /// Angular distance from the Sun in degrees
+    pub elongation: f64,
+    /// Apparent diameter of the disc in degrees
+    #[serde(rename = "apparentDiameter")]
+    pub apparent_diameter: f64,
+    /// Apparent visual magnitude
+    pub magnitude: f64,
+}
+
 /// House system positions
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HousePositions {
@@ -28,7 +54,7 @@ pub struct HousePositions {
     pub system: String,
     /// House cusps: "1".."12" -> degrees
     pub cusps: HashMap<String, f64>,
-    /// Angles: "asc", "mc", "ic", "dc" -> degrees
+    /// Angles ([`ANGLE_IDS`]) and the extra house points ([`HOUSE_POINTS`]) -> degrees
     pub angles: HashMap<String, f64>,
 }
 
@@ -41,6 +67,23 @@ pub struct LayerPositions {
     pub houses: Option<HousePositions>,
 }
 
+/// Objects calculated when a caller does not choose any
+pub const DEFAULT_OBJECTS: &[&str] = &[
+    "sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn", "uranus", "neptune", "pluto",
+    "north_node",
+];
+
+/// The four angles of a chart
+pub const ANGLE_IDS: &[&str] = &["asc", "mc", "ic", "dc"];
+
+/// Extra points from the house calculation, returned in `HousePositions::angles` next to the
+/// angles. All are ecliptic longitudes except "armc", the right ascension of the MC:
+/// - "vertex": where the prime vertical meets the ecliptic in the west
+/// - "equatorial_asc": the ascendant for an observer on the equator
+/// - "co_asc_koch" and "co_asc_munkasey": the co-ascendants of W. Koch and M. Munkasey
+/// - "polar_asc": the polar ascendant of M. Munkasey
+pub const HOUSE_POINTS: &[&str] = &["armc", "vertex", "equatorial_asc", "co_asc_koch", "co_asc_munkasey", "polar_asc"];
+
 /// Settings for ephemeris calculations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EphemerisSettings {
@@ -48,10 +91,99 @@ pub struct EphemerisSettings {
     pub zodiac_type: String,
     /// Ayanamsa name (for sidereal zodiac)
     pub ayanamsa: Option<String>,
+    /// Ayanamsa in degrees at J2000, used when `ayanamsa` is "custom"
+    pub custom_ayanamsa: Option<f64>,
     /// House system name
     pub house_system: String,
     /// List of planet IDs to include
     pub include_objects: Vec<String>,
+    /// How `north_node` and `south_node` are calculated
+    #[serde(default)]
+    pub node_type: NodeType,
+    /// How `lilith` is calculated
+    #[serde(default)]
+    pub lilith_type: LilithType,
+    /// Where the Moon is seen from when a location is given
+    #[serde(default)]
+    pub moon_position: MoonPosition,
+    /// House system used where `house_system` is undefined, inside the polar circles
+    #[serde(default)]
+    pub polar_fallback: PolarFallback,
+}
+
+impl EphemerisSettings {
+    /// Body calculated for an object id once the node and Lilith choices are applied:
+    /// `north_node` becomes `true_node` or `mean_node`, and `lilith` becomes `mean_apogee`
+    /// or `osculating_apogee`
+    pub fn body_id<'a>(&self, object_id: &'a str) -> &'a str {
+        match (object_id, self.node_type, self.lilith_type) {
+            ("north_node", NodeType::True, _) => "true_node",
+            ("north_node", NodeType::Mean, _) => "mean_node",
+            ("lilith", _, LilithType::Mean) => "mean_apogee",
+            ("lilith", _, LilithType::True) => "osculating_apogee",
+            _ => object_id,
+        }
+    }
+
+    /// Whether a body is seen from the observer's location rather than the Earth's centre;
+    /// only the Moon is close enough for the parallax to matter
+    pub fn is_topocentric(&self, body_id: &str) -> bool {
+        body_id == "moon" && self.moon_position == MoonPosition::Topocentric
+    }
+}
+
+/// Lunar node calculation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NodeType {
+    /// Osculating node, which wobbles around the mean node by up to 1.5°
+    #[default]
+    True,
+    /// Mean node, which always moves backwards
+    Mean,
+}
+
+/// Black Moon Lilith (lunar apogee) calculation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LilithType {
+    /// Mean apogee
+    #[default]
+    Mean,
+    /// Osculating apogee, which can stray from the mean by up to 30°
+    True,
+}
+
+/// Observer for the Moon's position
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MoonPosition {
+    /// Seen from the Earth's centre
+    #[default]
+    Geocentric,
+    /// Seen from the location, shifted by up to a degree of parallax
+    Topocentric,
+}
+
+/// Replacement for Placidus and Koch inside the polar circles, where some ecliptic degrees
+/// never rise and the cusps cannot be found
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PolarFallback {
+    /// Trisect the quadrants between the angles
+    #[default]
+    Porphyry,
+    WholeSign,
+}
+
+impl PolarFallback {
+    /// House system name, as in `HOUSE_SYSTEMS`
+    pub fn house_system(self) -> &'static str {
+        match self {
+            PolarFallback::Porphyry => "porphyry",
+            PolarFallback::WholeSign => "whole_sign",
+        }
+    }
 }
 
 /// Context for calculating positions for a chart layer
@@ -62,5 +194,7 @@ pub struct LayerContext {
     pub datetime: chrono::DateTime<chrono::Utc>,
     pub location: Option<GeoLocation>,
     pub settings: EphemerisSettings,
+    /// Set for progressed layers, whose `datetime` is the date progressed to
+    pub progression: Option<crate::ephemeris::progressions::Progression>,
 }
 
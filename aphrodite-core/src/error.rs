@@ -0,0 +1,88 @@
+use crate::ephemeris::EphemerisError;
+use crate::layout::WheelDefinitionError;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Result with the crate [`Error`]
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// What kind of failure an [`Error`] is, for bindings choosing a status code or exception
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The caller's input is wrong; retrying with the same input fails the same way
+    InvalidInput,
+    /// Ephemeris files are missing or unreadable: a deployment problem, not the caller's
+    EphemerisData,
+    /// The input is valid but the calculation is undefined or failed for it
+    Calculation,
+}
+
+/// Any error from the core library
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error(transparent)]
+    Ephemeris(#[from] EphemerisError),
+    #[error("Invalid wheel definition: {0}")]
+    WheelDefinition(#[from] WheelDefinitionError),
+    #[error("Invalid input: {0}")]
+    InvalidInput(String),
+    #[error("Calculation failed: {0}")]
+    Calculation(String),
+}
+
+impl Error {
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::Ephemeris(EphemerisError::FileNotFound { .. }) => ErrorCategory::EphemerisData,
+            Error::Ephemeris(
+                EphemerisError::InvalidHouseSystem { .. }
+                | EphemerisError::InvalidAyanamsa { .. }
+                | EphemerisError::OutsideCoverage { .. },
+            ) => ErrorCategory::InvalidInput,
+            Error::Ephemeris(
+                EphemerisError::CalculationFailed { .. }
+                | EphemerisError::HouseCalculationFailed { .. }
+                | EphemerisError::UndefinedHouseSystem { .. },
+            ) => ErrorCategory::Calculation,
+            Error::WheelDefinition(_) | Error::InvalidInput(_) => ErrorCategory::InvalidInput,
+            Error::Calculation(_) => ErrorCategory::Calculation,
+        }
+    }
+
+    /// Stable machine-readable code, more specific than the category
+    pub fn code(&self) -> &'static str {
+        match self {
+            Error::Ephemeris(EphemerisError::FileNotFound { .. }) => "EPHEMERIS_FILE_NOT_FOUND",
+            Error::Ephemeris(EphemerisError::InvalidHouseSystem { .. }) => "INVALID_HOUSE_SYSTEM",
+            Error::Ephemeris(EphemerisError::InvalidAyanamsa { .. }) => "INVALID_AYANAMSA",
+            Error::Ephemeris(EphemerisError::CalculationFailed { .. }) => "POSITION_CALCULATION_FAILED",
+            Error::Ephemeris(EphemerisError::HouseCalculationFailed { .. }) => "HOUSE_CALCULATION_FAILED",
+            Error::Ephemeris(EphemerisError::UndefinedHouseSystem { .. }) => "UNDEFINED_HOUSE_SYSTEM",
+            Error::Ephemeris(EphemerisError::OutsideCoverage { .. }) => "DATE_OUTSIDE_EPHEMERIS_RANGE",
+            Error::WheelDefinition(_) => "INVALID_WHEEL_DEFINITION",
+            Error::InvalidInput(_) => "INVALID_INPUT",
+            Error::Calculation(_) => "CALCULATION_FAILED",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_categories_and_codes() {
+        let error = Error::from(EphemerisError::InvalidHouseSystem { system: "x".to_string(), valid: vec![] });
+        assert_eq!(error.category(), ErrorCategory::InvalidInput);
+        assert_eq!(error.code(), "INVALID_HOUSE_SYSTEM");
+        assert_eq!(error.to_string(), "Invalid house system: x. Valid systems: []");
+
+        let error = Error::from(EphemerisError::FileNotFound { path: "/ephe".to_string(), message: String::new() });
+        assert_eq!(error.category(), ErrorCategory::EphemerisData);
+
+        let error = Error::from(WheelDefinitionError::MissingField("name".to_string()));
+        assert_eq!(error.category(), ErrorCategory::InvalidInput);
+        assert_eq!(error.to_string(), "Invalid wheel definition: Missing required field: name");
+    }
+}
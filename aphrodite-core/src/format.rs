@@ -0,0 +1,101 @@
+//! Display formatting for longitudes, shared by the renderer and every frontend so a
+//! position reads the same in a chart label, the API, WASM, Slint and the CLI.
+//!
+//! Degrees are truncated to the minute rather than rounded, so a planet at 29°59.8′ Aries
+//! is never shown in Taurus.
+
+use crate::vedic::nakshatra::{get_nakshatra_for_longitude, normalize_degrees};
+use crate::zodiac::SIGN_LABELS;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// How a longitude is written out
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DegreeFormat {
+    /// Degrees within the sign and the sign name, "15°32′ Taurus"
+    #[default]
+    Zodiacal,
+    /// Degrees along the ecliptic, "45°32′"
+    Absolute,
+    /// Nakshatra and pada, "Rohini pada 2"
+    NakshatraPada,
+}
+
+impl DegreeFormat {
+    pub const ALL: [DegreeFormat; 3] = [DegreeFormat::Zodiacal, DegreeFormat::Absolute, DegreeFormat::NakshatraPada];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DegreeFormat::Zodiacal => "zodiacal",
+            DegreeFormat::Absolute => "absolute",
+            DegreeFormat::NakshatraPada => "nakshatraPada",
+        }
+    }
+}
+
+impl FromStr for DegreeFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|format| format.as_str() == value)
+            .ok_or_else(|| {
+                let names: Vec<_> = Self::ALL.iter().map(DegreeFormat::as_str).collect();
+                format!("Unknown degree format '{}' (available: {})", value, names.join(", "))
+            })
+    }
+}
+
+/// Write `lon` (ecliptic degrees, any range) in `format`
+pub fn format_longitude(lon: f64, format: DegreeFormat) -> String {
+    match format {
+        DegreeFormat::Zodiacal => format_zodiacal(lon),
+        DegreeFormat::Absolute => format_degrees(normalize_degrees(lon)),
+        DegreeFormat::NakshatraPada => format_nakshatra_pada(lon),
+    }
+}
+
+/// `15°32′ Taurus`
+pub fn format_zodiacal(lon: f64) -> String {
+    let minutes = (normalize_degrees(lon) * 60.0).floor() as u32;
+    let sign = (minutes / (30 * 60)) as usize % 12;
+    format!("{} {}", format_minutes(minutes % (30 * 60)), SIGN_LABELS[sign])
+}
+
+/// Degrees and minutes without a sign, `15°32′`; for degrees within a sign pass
+/// `lon % 30`
+pub fn format_degrees(degrees: f64) -> String {
+    format_minutes((degrees.max(0.0) * 60.0).floor() as u32)
+}
+
+/// `Rohini pada 2`
+pub fn format_nakshatra_pada(lon: f64) -> String {
+    let nakshatra = get_nakshatra_for_longitude(lon);
+    format!("{} pada {}", nakshatra.base.name, nakshatra.pada)
+}
+
+fn format_minutes(minutes: u32) -> String {
+    format!("{}°{:02}′", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degree_formats() {
+        assert_eq!(format_zodiacal(0.0), "0°00′ Aries");
+        assert_eq!(format_zodiacal(45.54), "15°32′ Taurus");
+        assert_eq!(format_zodiacal(-0.5), "29°30′ Pisces");
+        assert_eq!(format_zodiacal(29.9999), "29°59′ Aries");
+        assert_eq!(format_longitude(405.54, DegreeFormat::Absolute), "45°32′");
+        assert_eq!(format_degrees(5.25), "5°15′");
+        assert_eq!(format_longitude(45.54, DegreeFormat::NakshatraPada), "Rohini pada 2");
+        assert_eq!(format_longitude(359.9, DegreeFormat::NakshatraPada), "Revati pada 4");
+
+        assert_eq!("nakshatraPada".parse::<DegreeFormat>(), Ok(DegreeFormat::NakshatraPada));
+        assert!("dms".parse::<DegreeFormat>().unwrap_err().contains("zodiacal, absolute, nakshatraPada"));
+    }
+}
@@ -0,0 +1,125 @@
+//! Chart factors that content packs describe.
+
+use crate::aspects::types::AspectSet;
+use crate::ephemeris::types::LayerPositions;
+use crate::layout::rings::get_house_index;
+use crate::vedic::dashas::{DashaLevel, DashaPeriod};
+use crate::zodiac::sign_name;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A computed chart factor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Factor {
+    PlanetInSign { planet: String, sign: String },
+    /// House 1-12
+    PlanetInHouse { planet: String, house: u8 },
+    Aspect { from: String, aspect: String, to: String },
+    /// Dasha lord running at the moment asked about
+    Dasha { level: DashaLevel, planet: String },
+}
+
+/// Sign and house placements of a layer's planets, ordered by planet id, then its aspects
+/// between planets in the order of the set
+pub fn chart_factors(positions: &LayerPositions, aspects: Option<&AspectSet>) -> Vec<Factor> {
+    let mut planets: Vec<_> = positions.planets.iter().collect();
+    planets.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut factors = Vec::new();
+    for (planet, position) in &planets {
+        factors.push(Factor::PlanetInSign {
+            planet: planet.to_string(),
+            sign: sign_name(position.lon).to_string(),
+        });
+    }
+    if let Some(houses) = &positions.houses {
+        for (planet, position) in &planets {
+            if let Some(house) = get_house_index(position.lon, &houses.cusps) {
+                factors.push(Factor::PlanetInHouse { planet: planet.to_string(), house: house + 1 });
+            }
+        }
+    }
+    for pair in aspects.iter().flat_map(|set| &set.pairs) {
+        if pair.from.object_type == "planet" && pair.to.object_type == "planet" {
+            factors.push(Factor::Aspect {
+                from: pair.from.object_id.clone(),
+                aspect: pair.aspect.aspect_type.clone(),
+                to: pair.to.object_id.clone(),
+            });
+        }
+    }
+    factors
+}
+
+/// Lords of the periods running at `at`, from the mahadasha down
+pub fn dasha_factors(periods: &[DashaPeriod], at: DateTime<Utc>) -> Vec<Factor> {
+    let mut factors = Vec::new();
+    let mut level = periods;
+    while let Some(period) = level.iter().find(|period| period.start <= at && at < period.end) {
+        factors.push(Factor::Dasha { level: period.level, planet: period.planet.clone() });
+        level = &period.children;
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::{HousePositions, PlanetPosition};
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_chart_and_dasha_factors() {
+        let planet = |lon: f64| PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde: false };
+        let positions = LayerPositions {
+            planets: HashMap::from([("sun".to_string(), planet(10.0)), ("moon".to_string(), planet(125.0))]),
+            houses: Some(HousePositions {
+                system: "equal".to_string(),
+                cusps: (1..=12).map(|n| (n.to_string(), (n - 1) as f64 * 30.0)).collect(),
+                angles: HashMap::new(),
+            }),
+        };
+        assert_eq!(
+            chart_factors(&positions, None),
+            vec![
+                Factor::PlanetInSign { planet: "moon".to_string(), sign: "leo".to_string() },
+                Factor::PlanetInSign { planet: "sun".to_string(), sign: "aries".to_string() },
+                Factor::PlanetInHouse { planet: "moon".to_string(), house: 5 },
+                Factor::PlanetInHouse { planet: "sun".to_string(), house: 1 },
+            ]
+        );
+
+        let period = |planet: &str, level, start: i32, end: i32, children| DashaPeriod {
+            planet: planet.to_string(),
+            sign: None,
+            start: Utc.with_ymd_and_hms(start, 1, 1, 0, 0, 0).unwrap(),
+            end: Utc.with_ymd_and_hms(end, 1, 1, 0, 0, 0).unwrap(),
+            duration_days: 0.0,
+            level,
+            children,
+        };
+        let periods = vec![
+            period("venus", DashaLevel::Mahadasha, 2000, 2020, vec![]),
+            period(
+                "sun",
+                DashaLevel::Mahadasha,
+                2020,
+                2026,
+                vec![
+                    period("sun", DashaLevel::Antardasha, 2020, 2021, vec![]),
+                    period("moon", DashaLevel::Antardasha, 2021, 2026, vec![]),
+                ],
+            ),
+        ];
+        let at = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            dasha_factors(&periods, at),
+            vec![
+                Factor::Dasha { level: DashaLevel::Mahadasha, planet: "sun".to_string() },
+                Factor::Dasha { level: DashaLevel::Antardasha, planet: "moon".to_string() },
+            ]
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Interpretation texts for computed chart factors.
+//!
+//! Texts come from content packs ([`ContentPack`]) identified by an id and a locale, so the
+//! same pack can ship in several languages. [`InterpretationLibrary`] holds the loaded packs
+//! and matches factors from [`chart_factors`] and [`dasha_factors`] against them.
+
+pub mod factors;
+pub mod pack;
+
+pub use factors::{chart_factors, dasha_factors, Factor};
+pub use pack::{ContentPack, InterpretationError};
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Text for one factor
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interpretation {
+    #[serde(flatten)]
+    pub factor: Factor,
+    /// Pack key the text was found under, e.g. "sun.aries"
+    pub key: String,
+    pub text: String,
+}
+
+/// Loaded content packs
+#[derive(Debug, Clone, Default)]
+pub struct InterpretationLibrary {
+    packs: Vec<ContentPack>,
+}
+
+impl InterpretationLibrary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load every `.toml` and `.json` file in `dir`, in file name order
+    pub fn load_dir(dir: &Path) -> Result<Self, InterpretationError> {
+        let io_error = |e: std::io::Error| InterpretationError::Io { path: dir.display().to_string(), message: e.to_string() };
+        let mut paths: Vec<_> = std::fs::read_dir(dir)
+            .map_err(io_error)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<_, _>>()
+            .map_err(io_error)?;
+        paths.retain(|path| matches!(path.extension().and_then(|ext| ext.to_str()), Some("toml" | "json")));
+        paths.sort();
+
+        let mut library = Self::new();
+        for path in paths {
+            library.add(ContentPack::load(&path)?);
+        }
+        Ok(library)
+    }
+
+    /// Add a pack, replacing one with the same id and locale
+    pub fn add(&mut self, pack: ContentPack) {
+        self.packs.retain(|existing| !(existing.id == pack.id && existing.locale == pack.locale));
+        self.packs.push(pack);
+    }
+
+    pub fn packs(&self) -> &[ContentPack] {
+        &self.packs
+    }
+
+    /// Pack `id` in `locale`, falling back from a regional locale ("pt-BR") to its
+    /// language ("pt")
+    pub fn get(&self, id: &str, locale: &str) -> Option<&ContentPack> {
+        let find = |locale: &str| {
+            self.packs
+                .iter()
+                .find(|pack| pack.id == id && pack.locale.eq_ignore_ascii_case(locale))
+        };
+        find(locale).or_else(|| find(locale.split(['-', '_']).next()?))
+    }
+
+    /// Texts for the factors the pack covers, in factor order
+    pub fn interpret(&self, id: &str, locale: &str, factors: &[Factor]) -> Result<Vec<Interpretation>, InterpretationError> {
+        let pack = self.get(id, locale).ok_or_else(|| InterpretationError::UnknownPack {
+            id: id.to_string(),
+            locale: locale.to_string(),
+        })?;
+        Ok(factors
+            .iter()
+            .filter_map(|factor| {
+                pack.text(factor).map(|(key, text)| Interpretation {
+                    factor: factor.clone(),
+                    key,
+                    text: text.to_string(),
+                })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACK: &str = r#"
+id = "basic"
+locale = "pt"
+
+[planetInSign]
+"sun.aries" = "Natureza pioneira."
+
+[aspect]
+"sun.trine.moon" = "Vontade e sentimentos em harmonia."
+"#;
+
+    #[test]
+    fn test_interpret_with_locale_fallback() {
+        let mut library = InterpretationLibrary::new();
+        library.add(ContentPack::from_toml(PACK).unwrap());
+
+        let factors = vec![
+            Factor::PlanetInSign { planet: "sun".to_string(), sign: "aries".to_string() },
+            Factor::PlanetInSign { planet: "moon".to_string(), sign: "leo".to_string() },
+            Factor::Aspect { from: "moon".to_string(), aspect: "trine".to_string(), to: "sun".to_string() },
+        ];
+        let texts = library.interpret("basic", "pt-BR", &factors).unwrap();
+        assert_eq!(texts.len(), 2);
+        assert_eq!(texts[0].text, "Natureza pioneira.");
+        assert_eq!(texts[1].key, "sun.trine.moon");
+
+        assert!(matches!(
+            library.interpret("basic", "en", &factors),
+            Err(InterpretationError::UnknownPack { .. })
+        ));
+        assert!(ContentPack::from_toml("locale = \"en\"").is_err());
+    }
+}
@@ -0,0 +1,100 @@
+//! Content packs: delineation texts keyed by factor, loaded from TOML or JSON.
+//!
+//! ```toml
+//! id = "basic"
+//! locale = "en"
+//! name = "Basic delineations"
+//!
+//! [planetInSign]
+//! "sun.aries" = "A pioneering, self-starting nature."
+//!
+//! [planetInHouse]
+//! "moon.4" = "Emotional security is rooted in home and family."
+//!
+//! [aspect]
+//! "sun.trine.moon" = "Will and feelings work together."
+//!
+//! [dasha]
+//! "mahadasha.jupiter" = "A period of growth, teaching and good counsel."
+//! ```
+
+use crate::interpretations::Factor;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use thiserror::Error;
+
+/// Errors loading content packs or looking them up
+#[derive(Error, Debug)]
+pub enum InterpretationError {
+    #[error("Failed to read {path}: {message}")]
+    Io { path: String, message: String },
+    #[error("Invalid content pack {path}: {message}")]
+    Parse { path: String, message: String },
+    #[error("Unknown content pack '{id}' for locale '{locale}'")]
+    UnknownPack { id: String, locale: String },
+}
+
+/// Texts for one pack id and locale
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContentPack {
+    pub id: String,
+    /// BCP 47 language tag, e.g. "en" or "pt-BR"
+    pub locale: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Keyed "planet.sign", e.g. "sun.aries"
+    #[serde(rename = "planetInSign", alias = "planet_in_sign", default)]
+    pub planet_in_sign: HashMap<String, String>,
+    /// Keyed "planet.house", e.g. "moon.4"
+    #[serde(rename = "planetInHouse", alias = "planet_in_house", default)]
+    pub planet_in_house: HashMap<String, String>,
+    /// Keyed "planet.aspect.planet", e.g. "sun.trine.moon"; either order matches
+    #[serde(default)]
+    pub aspect: HashMap<String, String>,
+    /// Keyed "level.planet", e.g. "mahadasha.jupiter"
+    #[serde(default)]
+    pub dasha: HashMap<String, String>,
+}
+
+impl ContentPack {
+    pub fn from_toml(toml: &str) -> Result<Self, InterpretationError> {
+        toml::from_str(toml).map_err(|e| InterpretationError::Parse { path: "<toml>".to_string(), message: e.to_string() })
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, InterpretationError> {
+        serde_json::from_str(json).map_err(|e| InterpretationError::Parse { path: "<json>".to_string(), message: e.to_string() })
+    }
+
+    /// Load a `.toml` or `.json` pack
+    pub fn load(path: &Path) -> Result<Self, InterpretationError> {
+        let display = path.display().to_string();
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| InterpretationError::Io { path: display.clone(), message: e.to_string() })?;
+        let pack = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json(&text),
+            _ => Self::from_toml(&text),
+        };
+        pack.map_err(|e| match e {
+            InterpretationError::Parse { message, .. } => InterpretationError::Parse { path: display, message },
+            other => other,
+        })
+    }
+
+    /// The key and text for `factor`, if the pack has one
+    pub fn text(&self, factor: &Factor) -> Option<(String, &str)> {
+        let (table, keys) = match factor {
+            Factor::PlanetInSign { planet, sign } => (&self.planet_in_sign, vec![format!("{}.{}", planet, sign)]),
+            Factor::PlanetInHouse { planet, house } => (&self.planet_in_house, vec![format!("{}.{}", planet, house)]),
+            Factor::Aspect { from, aspect, to } => (
+                &self.aspect,
+                vec![format!("{}.{}.{}", from, aspect, to), format!("{}.{}.{}", to, aspect, from)],
+            ),
+            Factor::Dasha { level, planet } => {
+                let level = serde_json::to_value(level).ok()?.as_str()?.to_string();
+                (&self.dasha, vec![format!("{}.{}", level, planet)])
+            }
+        };
+        keys.into_iter().find_map(|key| table.get(&key).map(|text| (key, text.as_str())))
+    }
+}
@@ -1,9 +1,13 @@
 use crate::aspects::types::AspectSet;
-use crate::ephemeris::types::LayerPositions;
+use crate::ephemeris::types::{LayerPositions, ANGLE_IDS};
 use crate::layout::rings::{
-    build_house_items, build_planet_items, build_static_zodiac_items, RingItem,
+    build_house_items, build_planet_items, build_point_items, build_static_nakshatra_items, build_static_zodiac_items, RingItem,
 };
-use crate::layout::types::{RingDefinition, WheelDefinition};
+use crate::layout::types::{
+    ItemStyle, RingDataSource, RingDefinition, StyleCondition, WheelDefinition, WheelDirection, WheelPoints,
+    WheelProjection,
+};
+use crate::western::Element;
 use std::collections::HashMap;
 
 /// Assembled wheel with resolved ring items
@@ -14,20 +18,50 @@ pub struct AssembledWheel {
     pub description: Option<String>,
     pub radius_inner: f32,
     pub radius_outer: f32,
+    pub direction: WheelDirection,
+    pub projection: WheelProjection,
+    /// Cusps of houses 1-12 when the wheel uses the house projection
+    pub house_cusps: Option<Vec<f64>>,
     pub rings: Vec<AssembledRing>,
 }
 
+impl AssembledWheel {
+    /// Position of an ecliptic longitude on the wheel, in degrees from 0° Aries.
+    ///
+    /// Zodiacal wheels place longitudes as they are; house wheels stretch each house to
+    /// 30°, so the cusp of house `n` lands at `(n - 1) * 30`.
+    pub fn project_longitude(&self, longitude: f64) -> f64 {
+        let Some(cusps) = &self.house_cusps else {
+            return longitude;
+        };
+        for (index, cusp) in cusps.iter().enumerate() {
+            let width = (cusps[(index + 1) % cusps.len()] - cusp).rem_euclid(360.0);
+            let offset = (longitude - cusp).rem_euclid(360.0);
+            if offset < width {
+                return index as f64 * 30.0 + offset / width * 30.0;
+            }
+        }
+        longitude
+    }
+}
+
 /// Assembled ring with resolved items
 #[derive(Debug, Clone)]
 pub struct AssembledRing {
     pub id: String,
+    /// Slug from the wheel definition
+    pub slug: String,
     pub ring_type: String,
     pub label: String,
     pub order: u32,
     pub radius_inner: f32,
     pub radius_outer: f32,
     pub data_source: crate::layout::types::RingDataSource,
+    /// Display options from the wheel definition
+    pub display_options: HashMap<String, serde_json::Value>,
     pub items: Vec<RingItem>,
+    /// Styles from the ring's style rules, by item id; unstyled items are absent
+    pub item_styles: HashMap<String, ItemStyle>,
 }
 
 /// Wheel assembler
@@ -40,6 +74,23 @@ impl WheelAssembler {
         positions_by_layer: &HashMap<String, LayerPositions>,
         aspect_sets: &HashMap<String, AspectSet>,
         include_objects: Option<&[String]>,
+    ) -> AssembledWheel {
+        Self::build_wheel_with_points(
+            wheel_config,
+            positions_by_layer,
+            aspect_sets,
+            include_objects,
+            &WheelPoints::default(),
+        )
+    }
+
+    /// Build a complete wheel, filling `custom_points` and `fixed_stars` rings from `points`
+    pub fn build_wheel_with_points(
+        wheel_config: &WheelDefinition,
+        positions_by_layer: &HashMap<String, LayerPositions>,
+        aspect_sets: &HashMap<String, AspectSet>,
+        include_objects: Option<&[String]>,
+        points: &WheelPoints,
     ) -> AssembledWheel {
         let mut ring_dtos = Vec::new();
 
@@ -50,6 +101,7 @@ impl WheelAssembler {
                 aspect_sets,
                 &ring_dtos,
                 include_objects,
+                points,
             );
             ring_dtos.push(ring_dto);
         }
@@ -71,16 +123,52 @@ impl WheelAssembler {
             (0.0, 1.0)
         };
 
+        let house_cusps = match wheel_config.projection {
+            WheelProjection::Zodiac => None,
+            WheelProjection::Houses => Self::projection_cusps(wheel_config, positions_by_layer),
+        };
+
         AssembledWheel {
             id: uuid::Uuid::new_v4().to_string(),
             name: wheel_config.name.clone(),
             description: wheel_config.description.clone(),
             radius_inner: inner_radius,
             radius_outer: outer_radius,
+            direction: wheel_config.direction,
+            projection: wheel_config.projection,
+            house_cusps,
             rings: ring_dtos,
         }
     }
 
+    /// Cusps of houses 1-12 for the house projection, from `projectionLayerId` or the first
+    /// house ring's layer. `None` (a zodiacal wheel) when that layer has no complete houses.
+    fn projection_cusps(
+        wheel_config: &WheelDefinition,
+        positions_by_layer: &HashMap<String, LayerPositions>,
+    ) -> Option<Vec<f64>> {
+        let layer_id = wheel_config.projection_layer_id.as_deref().or_else(|| {
+            wheel_config.rings.iter().find_map(|ring| match &ring.data_source {
+                RingDataSource::LayerHouses { layer_id } => Some(layer_id.as_str()),
+                _ => None,
+            })
+        })?;
+        let cusps = &positions_by_layer.get(layer_id)?.houses.as_ref()?.cusps;
+        (1..=12).map(|house| cusps.get(&house.to_string()).copied()).collect()
+    }
+
+    /// Map an ecliptic longitude onto the wheel for the given direction.
+    ///
+    /// Returns the angle in [0, 360) measured in the standard counter-clockwise
+    /// sense; clockwise wheels mirror the longitude.
+    pub fn map_angle(longitude: f64, direction: WheelDirection) -> f64 {
+        let angle = match direction {
+            WheelDirection::Ccw => longitude,
+            WheelDirection::Cw => -longitude,
+        };
+        angle.rem_euclid(360.0)
+    }
+
     /// Build a single ring with resolved items
     fn build_ring(
         ring_config: &RingDefinition,
@@ -88,6 +176,7 @@ impl WheelAssembler {
         aspect_sets: &HashMap<String, AspectSet>,
         _existing_rings: &[AssembledRing],
         include_objects: Option<&[String]>,
+        points: &WheelPoints,
     ) -> AssembledRing {
         let slug = &ring_config.slug;
         let mut items: Vec<RingItem> = Vec::new();
@@ -122,21 +211,116 @@ impl WheelAssembler {
                 }
             }
             crate::layout::types::RingDataSource::StaticNakshatras => {
-                // Nakshatras - deferred to Phase 6
-                // For now, leave items empty
+                let nakshatra_items = build_static_nakshatra_items(slug);
+                items.extend(nakshatra_items.into_iter().map(RingItem::Nakshatra));
+            }
+            crate::layout::types::RingDataSource::CustomPoints { point_set_id } => {
+                if let Some(point_set) = points.point_sets.get(point_set_id) {
+                    let point_items = build_point_items(slug, "customPoint", point_set, None);
+                    items.extend(point_items.into_iter().map(RingItem::Point));
+                }
+            }
+            crate::layout::types::RingDataSource::FixedStars { layer_id, stars } => {
+                if let Some(layer_stars) = points.fixed_stars.get(layer_id) {
+                    let point_items = build_point_items(slug, "fixedStar", layer_stars, stars.as_deref());
+                    items.extend(point_items.into_iter().map(RingItem::Point));
+                }
             }
         }
 
+        let angles = ring_config
+            .data_source
+            .layer_id()
+            .and_then(|layer_id| positions_by_layer.get(layer_id))
+            .and_then(|positions| positions.houses.as_ref())
+            .map(|houses| &houses.angles);
+        let item_styles = Self::resolve_styles(ring_config, &items, angles);
+
         AssembledRing {
             id: uuid::Uuid::new_v4().to_string(),
+            slug: slug.clone(),
             ring_type: format!("{:?}", ring_config.ring_type).to_lowercase(),
             label: ring_config.label.clone(),
             order: ring_config.order_index,
             radius_inner: ring_config.radius_inner,
             radius_outer: ring_config.radius_outer,
             data_source: ring_config.data_source.clone(),
+            display_options: ring_config.display_options.clone(),
             items,
+            item_styles,
+        }
+    }
+
+    /// Apply the ring's style rules to its sign, planet and point items. Each matching rule
+    /// overrides the fields it sets, so later rules win.
+    fn resolve_styles(
+        ring_config: &RingDefinition,
+        items: &[RingItem],
+        angles: Option<&HashMap<String, f64>>,
+    ) -> HashMap<String, ItemStyle> {
+        let mut styles = HashMap::new();
+        if ring_config.style_rules.is_empty() {
+            return styles;
+        }
+        for item in items {
+            let (id, object, lon, retrograde) = match item {
+                RingItem::Sign(sign) => (&sign.id, None, sign.start_lon, None),
+                RingItem::Planet(planet) => (&planet.id, Some(planet.planet_id.as_str()), planet.lon, planet.retrograde),
+                RingItem::Point(point) => (&point.id, Some(point.point_id.as_str()), point.lon, None),
+                RingItem::House(_) | RingItem::Aspect(_) | RingItem::Nakshatra(_) => continue,
+            };
+            let mut style: Option<ItemStyle> = None;
+            for rule in &ring_config.style_rules {
+                if Self::matches(&rule.when, object, lon, retrograde, angles) {
+                    style.get_or_insert_with(ItemStyle::default).merge(&rule.style);
+                }
+            }
+            if let Some(style) = style {
+                styles.insert(id.clone(), style);
+            }
+        }
+        styles
+    }
+
+    fn matches(
+        when: &StyleCondition,
+        object: Option<&str>,
+        lon: f64,
+        retrograde: Option<bool>,
+        angles: Option<&HashMap<String, f64>>,
+    ) -> bool {
+        const ELEMENTS: [Element; 4] = [Element::Fire, Element::Earth, Element::Air, Element::Water];
+
+        if let Some(element) = when.element {
+            if ELEMENTS[(lon.rem_euclid(360.0) / 30.0) as usize % 4] != element {
+                return false;
+            }
+        }
+        if let Some(wanted) = when.retrograde {
+            if retrograde != Some(wanted) {
+                return false;
+            }
+        }
+        if let Some(objects) = &when.objects {
+            if !object.is_some_and(|object| objects.iter().any(|id| id.eq_ignore_ascii_case(object))) {
+                return false;
+            }
+        }
+        if let Some(orb) = when.near_angle {
+            // An angle drawn on the ring is not near itself
+            let near = angles.is_some_and(|angles| {
+                ANGLE_IDS.iter().filter(|angle_id| Some(**angle_id) != object).any(|angle_id| {
+                    angles.get(*angle_id).is_some_and(|angle_lon| {
+                        let distance = (lon - angle_lon).rem_euclid(360.0);
+                        distance.min(360.0 - distance) <= orb
+                    })
+                })
+            });
+            if !near {
+                return false;
+            }
         }
+        true
     }
 }
 
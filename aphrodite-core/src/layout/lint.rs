@@ -0,0 +1,257 @@
+//! Design-time checks for wheel definitions.
+//!
+//! [`load_wheel_definition_from_json`] rejects definitions that cannot be drawn at all.
+//! The lint goes further and reports wheels that load but probably do not look as intended:
+//! rings drawn on top of each other, rings whose `orderIndex` disagrees with their radii, and
+//! rings that draw layers the chart will not have.
+
+use crate::layout::loader::{load_wheel_definition_from_json, WheelDefinitionError};
+use crate::layout::types::{RingDataSource, WheelDefinition};
+use serde::{Deserialize, Serialize};
+
+/// JSON Schema (draft 2020-12) for the wheel definition format
+pub const WHEEL_DEFINITION_SCHEMA: &str = include_str!("wheel-definition.schema.json");
+
+/// [`WHEEL_DEFINITION_SCHEMA`] parsed
+pub fn wheel_definition_schema() -> serde_json::Value {
+    serde_json::from_str(WHEEL_DEFINITION_SCHEMA).expect("embedded wheel schema is valid JSON")
+}
+
+/// What a lint warning is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WheelWarningCode {
+    /// Two rings share part of their radius band
+    OverlappingRadii,
+    /// `orderIndex` runs against the radii, or two rings share one
+    RingOrder,
+    /// Two rings share a slug
+    DuplicateSlug,
+    /// A ring or the projection refers to a layer that is not drawn or supplied
+    DanglingLayerId,
+}
+
+impl WheelWarningCode {
+    /// The serialized code, e.g. "OVERLAPPING_RADII"
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OverlappingRadii => "OVERLAPPING_RADII",
+            Self::RingOrder => "RING_ORDER",
+            Self::DuplicateSlug => "DUPLICATE_SLUG",
+            Self::DanglingLayerId => "DANGLING_LAYER_ID",
+        }
+    }
+}
+
+/// A problem found by [`lint_wheel`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WheelWarning {
+    pub code: WheelWarningCode,
+    /// Location in the definition, e.g. `rings[2].dataSource.layerId`
+    pub path: String,
+    pub message: String,
+}
+
+impl WheelWarning {
+    fn new(code: WheelWarningCode, path: String, message: String) -> Self {
+        Self { code, path, message }
+    }
+}
+
+/// Load `json` and lint it. Errors are definitions that cannot be loaded at all.
+///
+/// `layer_ids` are the layers the wheel will be drawn with; when None, a layer counts as
+/// present if any ring draws it.
+pub fn validate_wheel_definition(
+    json: &str,
+    layer_ids: Option<&[&str]>,
+) -> Result<Vec<WheelWarning>, WheelDefinitionError> {
+    let definition = load_wheel_definition_from_json(json)?;
+    Ok(lint_wheel(&definition.wheel, layer_ids))
+}
+
+/// Warnings for a loaded wheel, in ring order
+pub fn lint_wheel(wheel: &WheelDefinition, layer_ids: Option<&[&str]>) -> Vec<WheelWarning> {
+    let mut warnings = Vec::new();
+    let rings = &wheel.rings;
+
+    for (i, ring) in rings.iter().enumerate() {
+        if let Some(first) = rings[..i].iter().position(|other| other.slug == ring.slug) {
+            warnings.push(WheelWarning::new(
+                WheelWarningCode::DuplicateSlug,
+                format!("rings[{}].slug", i),
+                format!("Slug '{}' is already used by rings[{}]", ring.slug, first),
+            ));
+        }
+    }
+
+    for (i, ring) in rings.iter().enumerate() {
+        for (j, other) in rings.iter().enumerate().skip(i + 1) {
+            let (outer, inner) = if ring.radius_outer >= other.radius_outer { (ring, other) } else { (other, ring) };
+            if inner.radius_outer > outer.radius_inner {
+                warnings.push(WheelWarning::new(
+                    WheelWarningCode::OverlappingRadii,
+                    format!("rings[{}]", j),
+                    format!(
+                        "Ring '{}' ({}-{}) overlaps ring '{}' ({}-{})",
+                        other.slug, other.radius_inner, other.radius_outer,
+                        ring.slug, ring.radius_inner, ring.radius_outer
+                    ),
+                ));
+            }
+        }
+    }
+
+    // orderIndex 0 is the outermost ring
+    for (i, ring) in rings.iter().enumerate() {
+        for (j, other) in rings.iter().enumerate().skip(i + 1) {
+            if ring.order_index == other.order_index {
+                warnings.push(WheelWarning::new(
+                    WheelWarningCode::RingOrder,
+                    format!("rings[{}].orderIndex", j),
+                    format!("Rings '{}' and '{}' share orderIndex {}", ring.slug, other.slug, ring.order_index),
+                ));
+                continue;
+            }
+            let (before, after) = if ring.order_index < other.order_index { (ring, other) } else { (other, ring) };
+            if after.radius_outer > before.radius_outer {
+                warnings.push(WheelWarning::new(
+                    WheelWarningCode::RingOrder,
+                    format!("rings[{}].orderIndex", j),
+                    format!(
+                        "Ring '{}' (orderIndex {}) lies outside ring '{}' (orderIndex {})",
+                        after.slug, after.order_index, before.slug, before.order_index
+                    ),
+                ));
+            }
+        }
+    }
+
+    let drawn: Vec<&str> = rings.iter().filter_map(|ring| ring.data_source.layer_id()).collect();
+    let known = |id: &str| match layer_ids {
+        Some(layer_ids) => layer_ids.contains(&id),
+        None => drawn.contains(&id),
+    };
+    let mut dangling = |path: String, id: &str| {
+        if !known(id) {
+            warnings.push(WheelWarning::new(
+                WheelWarningCode::DanglingLayerId,
+                path,
+                format!("Layer '{}' is not present", id),
+            ));
+        }
+    };
+    for (i, ring) in rings.iter().enumerate() {
+        match &ring.data_source {
+            RingDataSource::AspectSet { aspect_set_id, .. } => {
+                // "natal" for aspects within a layer, "transit:natal" for aspects between two
+                for id in aspect_set_id.split(':') {
+                    dangling(format!("rings[{}].dataSource.aspectSetId", i), id);
+                }
+            }
+            // Only supplied layers are checked; a ring always counts as drawing its own layer
+            source => {
+                if let (Some(id), Some(_)) = (source.layer_id(), layer_ids) {
+                    dangling(format!("rings[{}].dataSource.layerId", i), id);
+                }
+            }
+        }
+    }
+    if let Some(id) = &wheel.projection_layer_id {
+        dangling("projectionLayerId".to_string(), id);
+    }
+
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::loader::{DATA_SOURCE_KINDS, RING_TYPES, DEFAULT_WHEEL_JSON};
+    use crate::layout::WheelPresets;
+
+    fn ring(slug: &str, order: u32, inner: f32, outer: f32, source: &str) -> String {
+        format!(
+            r#"{{"slug": "{}", "type": "planets", "label": "{}", "orderIndex": {}, "radiusInner": {}, "radiusOuter": {}, "dataSource": {}}}"#,
+            slug, slug, order, inner, outer, source
+        )
+    }
+
+    fn wheel(rings: &[String]) -> String {
+        format!(r#"{{"name": "Test", "rings": [{}]}}"#, rings.join(","))
+    }
+
+    fn codes(warnings: &[WheelWarning]) -> Vec<WheelWarningCode> {
+        warnings.iter().map(|warning| warning.code).collect()
+    }
+
+    const NATAL: &str = r#"{"kind": "layer_planets", "layerId": "natal"}"#;
+
+    #[test]
+    fn test_presets_have_no_warnings() {
+        for preset in WheelPresets::all() {
+            assert_eq!(validate_wheel_definition(preset.json, None).unwrap(), vec![], "{}", preset.name);
+        }
+        let warnings = validate_wheel_definition(DEFAULT_WHEEL_JSON, Some(&["transit"])).unwrap();
+        assert!(warnings.iter().all(|warning| warning.code == WheelWarningCode::DanglingLayerId));
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_overlapping_radii_and_order() {
+        let json = wheel(&[
+            ring("outer", 0, 0.8, 1.0, NATAL),
+            ring("middle", 1, 0.7, 0.85, NATAL),
+            ring("inner", 2, 0.2, 0.9, NATAL),
+        ]);
+        let warnings = validate_wheel_definition(&json, None).unwrap();
+        assert_eq!(
+            codes(&warnings),
+            vec![
+                WheelWarningCode::OverlappingRadii,
+                WheelWarningCode::OverlappingRadii,
+                WheelWarningCode::OverlappingRadii,
+                WheelWarningCode::RingOrder,
+            ]
+        );
+        assert_eq!(warnings[3].path, "rings[2].orderIndex");
+    }
+
+    #[test]
+    fn test_duplicate_slugs_and_order_index() {
+        let json = wheel(&[ring("planets", 0, 0.8, 1.0, NATAL), ring("planets", 0, 0.5, 0.8, NATAL)]);
+        let warnings = validate_wheel_definition(&json, None).unwrap();
+        assert_eq!(codes(&warnings), vec![WheelWarningCode::DuplicateSlug, WheelWarningCode::RingOrder]);
+        assert_eq!(warnings[0].path, "rings[1].slug");
+        assert_eq!(serde_json::to_value(warnings[0].code).unwrap(), warnings[0].code.as_str());
+    }
+
+    #[test]
+    fn test_dangling_layer_ids() {
+        let json = wheel(&[
+            ring("planets", 0, 0.8, 1.0, NATAL),
+            ring("aspects", 1, 0.0, 0.8, r#"{"kind": "aspect_set", "aspectSetId": "transit:natal"}"#),
+        ]);
+        let warnings = validate_wheel_definition(&json, None).unwrap();
+        assert_eq!(codes(&warnings), vec![WheelWarningCode::DanglingLayerId]);
+        assert_eq!(warnings[0].path, "rings[1].dataSource.aspectSetId");
+        assert!(warnings[0].message.contains("'transit'"));
+
+        assert_eq!(validate_wheel_definition(&json, Some(&["natal", "transit"])).unwrap(), vec![]);
+        let warnings = validate_wheel_definition(&json, Some(&["transit"])).unwrap();
+        assert_eq!(warnings[0].path, "rings[0].dataSource.layerId");
+        assert_eq!(warnings.len(), 2);
+
+        assert!(validate_wheel_definition(r#"{"name": "Test"}"#, None).is_err());
+    }
+
+    #[test]
+    fn test_schema_enums_match_loader() {
+        let schema = wheel_definition_schema();
+        let strings = |value: &serde_json::Value| -> Vec<String> {
+            value.as_array().unwrap().iter().map(|v| v.as_str().unwrap().to_string()).collect()
+        };
+        assert_eq!(strings(&schema["$defs"]["ring"]["properties"]["type"]["enum"]), RING_TYPES);
+        assert_eq!(strings(&schema["$defs"]["dataSource"]["properties"]["kind"]["enum"]), DATA_SOURCE_KINDS);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::layout::types::WheelDefinitionWithPresets;
+use crate::rendering::primitives::Color;
 use serde_json;
 use thiserror::Error;
 
@@ -15,6 +16,57 @@ pub enum WheelDefinitionError {
     InvalidFieldValue(String),
 }
 
+/// Accepted ring `type` values
+pub(crate) const RING_TYPES: &[&str] = &["signs", "houses", "planets", "aspects", "nakshatras", "points"];
+
+/// Accepted `dataSource.kind` values
+pub(crate) const DATA_SOURCE_KINDS: &[&str] = &[
+    "static_zodiac",
+    "static_nakshatras",
+    "layer_houses",
+    "layer_planets",
+    "layer_varga_planets",
+    "aspect_set",
+    "custom_points",
+    "fixed_stars",
+];
+
+/// Standard natal wheel (signs, houses and planets of the "natal" layer)
+pub const DEFAULT_WHEEL_JSON: &str = r#"
+{
+  "name": "Standard Natal Wheel",
+  "rings": [
+    {
+      "slug": "ring_signs",
+      "type": "signs",
+      "label": "Zodiac Signs",
+      "orderIndex": 0,
+      "radiusInner": 0.85,
+      "radiusOuter": 1.0,
+      "dataSource": { "kind": "static_zodiac" }
+    },
+    {
+      "slug": "ring_houses",
+      "type": "houses",
+      "label": "Houses",
+      "orderIndex": 1,
+      "radiusInner": 0.75,
+      "radiusOuter": 0.85,
+      "dataSource": { "kind": "layer_houses", "layerId": "natal" }
+    },
+    {
+      "slug": "ring_planets",
+      "type": "planets",
+      "label": "Natal Planets",
+      "orderIndex": 2,
+      "radiusInner": 0.55,
+      "radiusOuter": 0.75,
+      "dataSource": { "kind": "layer_planets", "layerId": "natal" }
+    }
+  ]
+}
+"#;
+
 /// Load a wheel definition from JSON string
 pub fn load_wheel_definition_from_json(
     json: &str,
@@ -22,14 +74,14 @@ pub fn load_wheel_definition_from_json(
     let parsed: serde_json::Value = serde_json::from_str(json)
         .map_err(|e| WheelDefinitionError::InvalidJson(e.to_string()))?;
 
-    validate_wheel_definition(&parsed)?;
+    check_wheel_definition(&parsed)?;
 
     serde_json::from_value(parsed)
         .map_err(|e| WheelDefinitionError::ValidationError(e.to_string()))
 }
 
 /// Validate a wheel definition
-fn validate_wheel_definition(
+fn check_wheel_definition(
     definition: &serde_json::Value,
 ) -> Result<(), WheelDefinitionError> {
     // Check that it's an object
@@ -86,6 +138,22 @@ fn validate_wheel_definition(
         }
     }
 
+    if let Some(direction) = obj.get("direction") {
+        if !direction.is_null() && !["ccw", "cw"].contains(&direction.as_str().unwrap_or("")) {
+            return Err(WheelDefinitionError::InvalidFieldValue(
+                "direction must be one of: ccw, cw".to_string(),
+            ));
+        }
+    }
+
+    if let Some(projection) = obj.get("projection") {
+        if !projection.is_null() && !["zodiac", "houses"].contains(&projection.as_str().unwrap_or("")) {
+            return Err(WheelDefinitionError::InvalidFieldValue(
+                "projection must be one of: zodiac, houses".to_string(),
+            ));
+        }
+    }
+
     if let Some(version) = obj.get("version") {
         if !version.is_null() && !version.is_string() {
             return Err(WheelDefinitionError::InvalidFieldValue(
@@ -179,10 +247,11 @@ fn validate_ring_definition(
             )));
         }
         let type_str = ring_type.as_str().unwrap();
-        if !["signs", "houses", "planets", "aspects"].contains(&type_str) {
+        if !RING_TYPES.contains(&type_str) {
             return Err(WheelDefinitionError::InvalidFieldValue(format!(
-                "rings[{}].type must be one of: signs, houses, planets, aspects",
-                index
+                "rings[{}].type must be one of: {}",
+                index,
+                RING_TYPES.join(", ")
             )));
         }
     }
@@ -313,24 +382,16 @@ fn validate_ring_definition(
             )));
         }
         let kind_str = kind.as_str().unwrap();
-        let valid_kinds = [
-            "static_zodiac",
-            "static_nakshatras",
-            "layer_houses",
-            "layer_planets",
-            "layer_varga_planets",
-            "aspect_set",
-        ];
-        if !valid_kinds.contains(&kind_str) {
+        if !DATA_SOURCE_KINDS.contains(&kind_str) {
             return Err(WheelDefinitionError::InvalidFieldValue(format!(
                 "rings[{}].dataSource.kind must be one of: {}",
                 index,
-                valid_kinds.join(", ")
+                DATA_SOURCE_KINDS.join(", ")
             )));
         }
 
         // Validate layer-specific requirements
-        if kind_str == "layer_houses" || kind_str == "layer_planets" {
+        if kind_str == "layer_houses" || kind_str == "layer_planets" || kind_str == "fixed_stars" {
             if !data_source_obj.contains_key("layerId") {
                 return Err(WheelDefinitionError::MissingField(format!(
                     "rings[{}].dataSource.layerId (required for {})",
@@ -356,6 +417,24 @@ fn validate_ring_definition(
             }
         }
 
+        if kind_str == "custom_points" {
+            match data_source_obj.get("pointSetId") {
+                None => {
+                    return Err(WheelDefinitionError::MissingField(format!(
+                        "rings[{}].dataSource.pointSetId (required for custom_points)",
+                        index
+                    )));
+                }
+                Some(point_set_id) if !point_set_id.is_string() || point_set_id.as_str().unwrap().is_empty() => {
+                    return Err(WheelDefinitionError::InvalidFieldValue(format!(
+                        "rings[{}].dataSource.pointSetId must be a non-empty string",
+                        index
+                    )));
+                }
+                Some(_) => {}
+            }
+        }
+
         if kind_str == "aspect_set" {
             if !data_source_obj.contains_key("aspectSetId") {
                 return Err(WheelDefinitionError::MissingField(format!(
@@ -374,6 +453,52 @@ fn validate_ring_definition(
         }
     }
 
+    if let Some(rules) = ring_obj.get("styleRules") {
+        let Some(rules) = rules.as_array() else {
+            return Err(WheelDefinitionError::InvalidFieldValue(format!(
+                "rings[{}].styleRules must be an array",
+                index
+            )));
+        };
+        for (rule_index, rule) in rules.iter().enumerate() {
+            validate_style_rule(rule, &format!("rings[{}].styleRules[{}]", index, rule_index))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate the values serde cannot check in a style rule: colors, opacity and orb
+fn validate_style_rule(rule: &serde_json::Value, path: &str) -> Result<(), WheelDefinitionError> {
+    let Some(style) = rule.get("style") else {
+        return Err(WheelDefinitionError::MissingField(format!("{}.style", path)));
+    };
+    for key in ["color", "highlight"] {
+        if let Some(color) = style.get(key) {
+            if color.as_str().and_then(Color::from_hex).is_none() {
+                return Err(WheelDefinitionError::InvalidFieldValue(format!(
+                    "{}.style.{} must be a color such as \"#FF4500\"",
+                    path, key
+                )));
+            }
+        }
+    }
+    if let Some(opacity) = style.get("opacity") {
+        if !opacity.as_f64().is_some_and(|opacity| (0.0..=1.0).contains(&opacity)) {
+            return Err(WheelDefinitionError::InvalidFieldValue(format!(
+                "{}.style.opacity must be between 0 and 1",
+                path
+            )));
+        }
+    }
+    if let Some(orb) = rule.get("when").and_then(|when| when.get("nearAngle")) {
+        if !orb.as_f64().is_some_and(|orb| (0.0..=30.0).contains(&orb)) {
+            return Err(WheelDefinitionError::InvalidFieldValue(format!(
+                "{}.when.nearAngle must be between 0 and 30 degrees",
+                path
+            )));
+        }
+    }
     Ok(())
 }
 
@@ -1,12 +1,19 @@
 pub mod assembler;
+pub mod lint;
 pub mod loader;
+pub mod presets;
 pub mod rings;
 pub mod types;
 
 pub use assembler::{AssembledRing, AssembledWheel, WheelAssembler};
-pub use loader::{load_wheel_definition_from_json, WheelDefinitionError};
+pub use lint::{
+    lint_wheel, validate_wheel_definition, wheel_definition_schema, WheelWarning, WheelWarningCode,
+    WHEEL_DEFINITION_SCHEMA,
+};
+pub use loader::{load_wheel_definition_from_json, WheelDefinitionError, DEFAULT_WHEEL_JSON};
+pub use presets::{WheelPreset, WheelPresets};
 pub use types::{
-    AspectSetFilter, RingDataSource, RingDefinition, RingType, WheelDefinition,
-    WheelDefinitionWithPresets,
+    AspectSetFilter, CustomPoint, ItemStyle, RingDataSource, RingDefinition, RingType, StyleCondition, StyleRule,
+    WheelDefinition, WheelDefinitionWithPresets, WheelDirection, WheelPoints, WheelProjection,
 };
 
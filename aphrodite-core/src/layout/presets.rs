@@ -0,0 +1,80 @@
+//! Wheel definitions shipped with the library, selectable by name.
+//!
+//! Presets draw the layer ids "natal" and, for `natal_transit`, "transit".
+
+use crate::layout::loader::{load_wheel_definition_from_json, DEFAULT_WHEEL_JSON};
+use crate::layout::types::WheelDefinitionWithPresets;
+
+/// A named, embedded wheel definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WheelPreset {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Wheel definition JSON
+    pub json: &'static str,
+}
+
+impl WheelPreset {
+    /// The parsed definition
+    pub fn definition(&self) -> WheelDefinitionWithPresets {
+        load_wheel_definition_from_json(self.json).expect("embedded wheel presets are valid")
+    }
+}
+
+const PRESETS: &[WheelPreset] = &[
+    WheelPreset {
+        name: "natal",
+        description: "Signs, houses and planets of a single chart",
+        json: DEFAULT_WHEEL_JSON,
+    },
+    WheelPreset {
+        name: "natal_transit",
+        description: "Natal chart inside, transiting planets outside",
+        json: include_str!("presets/natal_transit.json"),
+    },
+    WheelPreset {
+        name: "vedic_rasi",
+        description: "Rasis and nakshatras around the bhavas and grahas",
+        json: include_str!("presets/vedic_rasi.json"),
+    },
+    WheelPreset {
+        name: "minimal",
+        description: "Signs and planets only",
+        json: include_str!("presets/minimal.json"),
+    },
+];
+
+/// The preset library
+pub struct WheelPresets;
+
+impl WheelPresets {
+    /// Preset by name, e.g. "natal_transit"
+    pub fn get(name: &str) -> Option<&'static WheelPreset> {
+        PRESETS.iter().find(|preset| preset.name == name)
+    }
+
+    pub fn all() -> &'static [WheelPreset] {
+        PRESETS
+    }
+
+    pub fn names() -> Vec<&'static str> {
+        PRESETS.iter().map(|preset| preset.name).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in WheelPresets::all() {
+            let definition = load_wheel_definition_from_json(preset.json)
+                .unwrap_or_else(|e| panic!("{}: {}", preset.name, e));
+            assert!(!definition.wheel.rings.is_empty());
+        }
+        assert_eq!(WheelPresets::get("natal").unwrap().json, DEFAULT_WHEEL_JSON);
+        assert_eq!(WheelPresets::get("natal_transit").unwrap().definition().wheel.rings.len(), 4);
+        assert!(WheelPresets::get("Natal").is_none());
+    }
+}
@@ -1,4 +1,6 @@
 use crate::ephemeris::types::LayerPositions;
+use crate::layout::types::CustomPoint;
+use crate::vedic::nakshatra::{NAKSHATRA_ORDER, NAKSHATRA_SEGMENT_SIZE};
 use std::collections::HashMap;
 
 /// Sign names and glyphs
@@ -88,6 +90,30 @@ pub struct HouseRingItem {
     pub lon: f64,
 }
 
+/// Nakshatra ring item
+#[derive(Debug, Clone)]
+pub struct NakshatraRingItem {
+    pub id: String,
+    pub kind: String,
+    /// 0 = Ashwini .. 26 = Revati
+    pub index: u8,
+    pub label: String,
+    /// Planetary lord
+    pub lord: String,
+    pub start_lon: f64,
+    pub end_lon: f64,
+}
+
+/// Custom point or fixed star ring item
+#[derive(Debug, Clone)]
+pub struct PointRingItem {
+    pub id: String,
+    pub kind: String,
+    pub point_id: String,
+    pub label: String,
+    pub lon: f64,
+}
+
 /// Planet ring item
 #[derive(Debug, Clone)]
 pub struct PlanetRingItem {
@@ -122,6 +148,8 @@ pub enum RingItem {
     House(HouseRingItem),
     Planet(PlanetRingItem),
     Aspect(AspectRingItem),
+    Nakshatra(NakshatraRingItem),
+    Point(PointRingItem),
 }
 
 /// Build static zodiac items (12 signs)
@@ -152,6 +180,38 @@ pub fn build_static_zodiac_items(slug: &str) -> Vec<SignRingItem> {
     items
 }
 
+/// Build static nakshatra items (27 segments of 13°20')
+pub fn build_static_nakshatra_items(slug: &str) -> Vec<NakshatraRingItem> {
+    NAKSHATRA_ORDER
+        .iter()
+        .enumerate()
+        .map(|(i, (nakshatra_slug, display_name, lord))| NakshatraRingItem {
+            id: format!("{}_nakshatra_{}", slug, nakshatra_slug),
+            kind: "nakshatra".to_string(),
+            index: i as u8,
+            label: display_name.to_string(),
+            lord: lord.to_string(),
+            start_lon: i as f64 * NAKSHATRA_SEGMENT_SIZE,
+            end_lon: (i + 1) as f64 * NAKSHATRA_SEGMENT_SIZE,
+        })
+        .collect()
+}
+
+/// Build point items, keeping only the ids in `only` when it is given
+pub fn build_point_items(slug: &str, kind: &str, points: &[CustomPoint], only: Option<&[String]>) -> Vec<PointRingItem> {
+    points
+        .iter()
+        .filter(|point| only.is_none_or(|ids| ids.iter().any(|id| id.eq_ignore_ascii_case(&point.id))))
+        .map(|point| PointRingItem {
+            id: format!("{}_{}", slug, point.id),
+            kind: kind.to_string(),
+            point_id: point.id.clone(),
+            label: point.label.clone(),
+            lon: point.lon.rem_euclid(360.0),
+        })
+        .collect()
+}
+
 /// Build house items from layer positions
 pub fn build_house_items(
     slug: &str,
@@ -174,6 +234,7 @@ pub fn build_house_items(
             }
         }
     }
+    items.sort_by_key(|item| item.house_index);
 
     items
 }
@@ -190,7 +251,9 @@ pub fn build_planet_items(
     let planets = &positions.planets;
     let houses = &positions.houses;
 
-    // Add planets
+    // Add planets, ordered by id so shapes come out the same on every run
+    let mut planets: Vec<_> = planets.iter().collect();
+    planets.sort_by(|a, b| a.0.cmp(b.0));
     for (planet_id, planet_pos) in planets {
         let lon = planet_pos.lon;
         let sign_index = get_sign_index(lon);
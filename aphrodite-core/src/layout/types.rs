@@ -1,3 +1,4 @@
+use crate::western::Element;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -9,6 +10,30 @@ pub enum RingType {
     Houses,
     Planets,
     Aspects,
+    Nakshatras,
+    Points,
+}
+
+/// Direction in which the zodiac runs around the wheel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WheelDirection {
+    /// Counter-clockwise (standard chart orientation)
+    #[default]
+    Ccw,
+    /// Clockwise
+    Cw,
+}
+
+/// How longitudes are spread around the wheel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WheelProjection {
+    /// Zodiacal: every sign spans 30°, houses are as wide as their cusps make them
+    #[default]
+    Zodiac,
+    /// House wheel: every house spans 30°, signs stretch or shrink between the cusps
+    Houses,
 }
 
 /// Data source for a ring
@@ -18,19 +43,68 @@ pub enum RingDataSource {
     StaticZodiac,
     StaticNakshatras,
     LayerHouses {
+        #[serde(rename = "layerId", alias = "layer_id")]
         layer_id: String,
     },
     LayerPlanets {
+        #[serde(rename = "layerId", alias = "layer_id")]
         layer_id: String,
     },
     LayerVargaPlanets {
+        #[serde(rename = "layerId", alias = "layer_id")]
         layer_id: String,
+        #[serde(rename = "vargaId", alias = "varga_id")]
         varga_id: String,
     },
     AspectSet {
+        #[serde(rename = "aspectSetId", alias = "aspect_set_id")]
         aspect_set_id: String,
         filter: Option<AspectSetFilter>,
     },
+    /// Labeled longitudes supplied with the chart (Arabic parts, midpoints, ...)
+    CustomPoints {
+        #[serde(rename = "pointSetId", alias = "point_set_id")]
+        point_set_id: String,
+    },
+    /// Fixed stars at a layer's moment; every catalogued star unless `stars` lists ids
+    FixedStars {
+        #[serde(rename = "layerId", alias = "layer_id")]
+        layer_id: String,
+        #[serde(default)]
+        stars: Option<Vec<String>>,
+    },
+}
+
+impl RingDataSource {
+    /// Layer the ring draws, if it draws one
+    pub fn layer_id(&self) -> Option<&str> {
+        match self {
+            Self::LayerHouses { layer_id }
+            | Self::LayerPlanets { layer_id }
+            | Self::LayerVargaPlanets { layer_id, .. }
+            | Self::FixedStars { layer_id, .. } => Some(layer_id),
+            Self::StaticZodiac | Self::StaticNakshatras | Self::AspectSet { .. } | Self::CustomPoints { .. } => None,
+        }
+    }
+}
+
+/// A labeled longitude drawn on a points ring
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomPoint {
+    pub id: String,
+    /// Text drawn on the wheel, such as a glyph or short name
+    pub label: String,
+    /// Ecliptic longitude in degrees, in the chart's zodiac
+    pub lon: f64,
+}
+
+/// Points supplied alongside layer positions for `custom_points` and `fixed_stars` rings
+#[derive(Debug, Clone, Default)]
+pub struct WheelPoints {
+    /// Point set id -> points
+    pub point_sets: HashMap<String, Vec<CustomPoint>>,
+    /// Layer id -> fixed star positions at the layer moment, with star ids as point ids
+    pub fixed_stars: HashMap<String, Vec<CustomPoint>>,
 }
 
 /// Filter for aspect sets
@@ -58,6 +132,65 @@ pub struct RingDefinition {
     pub data_source: RingDataSource,
     #[serde(rename = "displayOptions", default)]
     pub display_options: HashMap<String, serde_json::Value>,
+    /// Conditional styles for the ring's items, applied in order
+    #[serde(rename = "styleRules", alias = "style_rules", default, skip_serializing_if = "Vec::is_empty")]
+    pub style_rules: Vec<StyleRule>,
+}
+
+/// Style applied to the items of a ring that match `when`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StyleRule {
+    #[serde(default)]
+    pub when: StyleCondition,
+    pub style: ItemStyle,
+}
+
+/// Item properties a style rule matches; every condition given must hold, and a rule
+/// without conditions matches every item
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StyleCondition {
+    /// Element of the sign the item is in (or, on a sign ring, of the sign itself)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub element: Option<Element>,
+    /// Whether the planet is retrograde; items without a speed never match
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retrograde: Option<bool>,
+    /// Planet or point ids, e.g. ["sun", "moon"]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub objects: Option<Vec<String>>,
+    /// Within this many degrees of the Ascendant, Midheaven, Descendant or IC of the
+    /// ring's layer
+    #[serde(rename = "nearAngle", alias = "near_angle", default, skip_serializing_if = "Option::is_none")]
+    pub near_angle: Option<f64>,
+}
+
+/// Style overrides for matching items; fields left out keep the theme's value
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ItemStyle {
+    /// Fill or glyph color, "#RRGGBB" or "#RRGGBBAA"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Multiplier for the color's opacity, 0-1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub opacity: Option<f32>,
+    /// Color of a disc drawn behind planet and point glyphs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+}
+
+impl ItemStyle {
+    /// Overlay `other`: its fields replace the ones it sets
+    pub fn merge(&mut self, other: &ItemStyle) {
+        if other.color.is_some() {
+            self.color = other.color.clone();
+        }
+        if other.opacity.is_some() {
+            self.opacity = other.opacity;
+        }
+        if other.highlight.is_some() {
+            self.highlight = other.highlight.clone();
+        }
+    }
 }
 
 /// Complete wheel definition with all rings
@@ -68,6 +201,13 @@ pub struct WheelDefinition {
     pub description: Option<String>,
     pub rings: Vec<RingDefinition>,
     #[serde(default)]
+    pub direction: WheelDirection,
+    #[serde(default)]
+    pub projection: WheelProjection,
+    /// Layer whose cusps drive the house projection; defaults to the first house ring's layer
+    #[serde(rename = "projectionLayerId", alias = "projection_layer_id", default, skip_serializing_if = "Option::is_none")]
+    pub projection_layer_id: Option<String>,
+    #[serde(default)]
     pub config: HashMap<String, serde_json::Value>,
 }
 
@@ -1,7 +1,22 @@
 pub mod ephemeris;
 pub mod aspects;
+pub mod chart;
+pub mod electional;
+pub mod error;
+pub mod format;
+pub mod interpretations;
 pub mod layout;
+pub mod rectification;
 pub mod rendering;
+pub mod synastry;
+pub mod transits;
 pub mod vedic;
 pub mod western;
+pub mod zodiac;
 
+
+/// Version of the calculation engine
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub use chart::{Chart, ChartLayer, ChartPlanet};
+pub use error::{Error, ErrorCategory, Result};
@@ -0,0 +1,5 @@
+pub mod scoring;
+pub mod types;
+
+pub use scoring::{rank_birth_times, DIRECTION_ORB, HIT_ASPECTS, TRANSIT_ORB};
+pub use types::{EventSky, RectificationCandidate, RectificationHit};
@@ -0,0 +1,119 @@
+//! Rectification: rank candidate birth times by how closely dated events hit their angles.
+//!
+//! An event counts for a candidate when a transiting planet, or a natal planet directed by
+//! solar arc, makes a conjunction or hard aspect to the candidate's Ascendant or Midheaven.
+//! The angles move a degree every four minutes or so while the planets barely move, so the
+//! angles are what tell candidate times within a day apart.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+use crate::aspects::{AspectCalculator, AspectFilter, AspectSettings};
+use crate::ephemeris::types::LayerPositions;
+use crate::rectification::types::{EventSky, RectificationCandidate, RectificationHit};
+
+/// Aspects that count as hits to an angle
+pub const HIT_ASPECTS: &[&str] = &["conjunction", "opposition", "square"];
+
+/// Orb in degrees for transits to the angles
+pub const TRANSIT_ORB: f64 = 1.0;
+
+/// Orb in degrees for solar arc directions to the angles
+pub const DIRECTION_ORB: f64 = 1.0;
+
+/// Transiting bodies too fast to time an event; the Moon crosses every angle within days
+const FAST_TRANSITS: &[&str] = &["moon"];
+
+/// Score each candidate birth time against the events and return the best `limit`, highest
+/// score first and earlier times first among equals.
+///
+/// Candidates are natal positions with houses; those without houses are skipped.
+pub fn rank_birth_times(
+    candidates: &[(DateTime<Utc>, LayerPositions)],
+    events: &[EventSky],
+    limit: usize,
+) -> Vec<RectificationCandidate> {
+    let calculator = AspectCalculator::new();
+    let transit_settings = hit_settings(TRANSIT_ORB);
+    let direction_settings = hit_settings(DIRECTION_ORB);
+
+    let mut ranked: Vec<RectificationCandidate> = candidates
+        .iter()
+        .filter_map(|(birth, natal)| {
+            let houses = natal.houses.as_ref()?;
+            // Only the angles of the candidate are aspected
+            let angles = LayerPositions { planets: HashMap::new(), houses: Some(houses.clone()) };
+            let mut hits = Vec::new();
+            for (event_index, event) in events.iter().enumerate() {
+                let transits = LayerPositions {
+                    planets: event
+                        .transits
+                        .planets
+                        .iter()
+                        .filter(|(id, _)| !FAST_TRANSITS.contains(&id.as_str()))
+                        .map(|(id, planet)| (id.clone(), planet.clone()))
+                        .collect(),
+                    houses: None,
+                };
+                let directed = LayerPositions {
+                    planets: natal
+                        .planets
+                        .iter()
+                        .map(|(id, planet)| {
+                            let mut planet = planet.clone();
+                            planet.lon = (planet.lon + event.solar_arc).rem_euclid(360.0);
+                            (id.clone(), planet)
+                        })
+                        .collect(),
+                    houses: None,
+                };
+                for (technique, positions, settings) in
+                    [("transit", &transits, &transit_settings), ("direction", &directed, &direction_settings)]
+                {
+                    let aspects = calculator.compute_inter_layer_aspects(technique, "natal", positions, &angles, settings);
+                    hits.extend(aspects.pairs.into_iter().map(|pair| RectificationHit {
+                        event_index,
+                        technique: technique.to_string(),
+                        object_id: pair.from.object_id,
+                        aspect_type: pair.aspect.aspect_type,
+                        angle_id: pair.to.object_id,
+                        orb: pair.aspect.orb,
+                    }));
+                }
+            }
+            let score = hits
+                .iter()
+                .map(|hit| {
+                    let orb = if hit.technique == "transit" { TRANSIT_ORB } else { DIRECTION_ORB };
+                    1.0 - hit.orb / orb
+                })
+                .sum();
+            Some(RectificationCandidate {
+                birth_date_time: *birth,
+                score,
+                asc: houses.angles.get("asc").copied().unwrap_or(0.0),
+                mc: houses.angles.get("mc").copied().unwrap_or(0.0),
+                hits,
+            })
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.birth_date_time.cmp(&b.birth_date_time)));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Aspect settings for hits to the angles within `orb`
+fn hit_settings(orb: f64) -> AspectSettings {
+    AspectSettings {
+        orb_settings: HIT_ASPECTS.iter().map(|aspect| (aspect.to_string(), orb)).collect(),
+        include_objects: Vec::new(),
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: AspectFilter {
+            aspect_types: Some(HIT_ASPECTS.iter().map(|aspect| aspect.to_string()).collect()),
+            include_angles: true,
+            ..AspectFilter::default()
+        },
+    }
+}
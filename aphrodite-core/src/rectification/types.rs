@@ -0,0 +1,45 @@
+//! Rectification types.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::ephemeris::types::LayerPositions;
+
+/// The sky of a dated life event, shared by every candidate birth time
+#[derive(Debug, Clone)]
+pub struct EventSky {
+    pub date: DateTime<Utc>,
+    /// Transiting planets at the event
+    pub transits: LayerPositions,
+    /// Solar arc from birth to the event in degrees
+    pub solar_arc: f64,
+}
+
+/// A transit or solar arc direction touching an angle of a candidate chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RectificationHit {
+    /// Index of the event in the request
+    #[serde(rename = "eventIndex")]
+    pub event_index: usize,
+    /// `"transit"` or `"direction"`
+    pub technique: String,
+    #[serde(rename = "objectId")]
+    pub object_id: String,
+    #[serde(rename = "aspectType")]
+    pub aspect_type: String,
+    /// `"asc"` or `"mc"`
+    #[serde(rename = "angleId")]
+    pub angle_id: String,
+    pub orb: f64,
+}
+
+/// A candidate birth time and how well the events fall on its angles
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RectificationCandidate {
+    #[serde(rename = "birthDateTime")]
+    pub birth_date_time: DateTime<Utc>,
+    /// Sum of the hits, each worth 1 when exact and nothing at the edge of its orb
+    pub score: f64,
+    pub asc: f64,
+    pub mc: f64,
+    pub hits: Vec<RectificationHit>,
+}
@@ -0,0 +1,327 @@
+//! Uranian dial charts.
+//!
+//! A dial folds the zodiac onto a smaller circle: on the 90° dial every longitude is taken
+//! modulo 90°, so conjunctions, squares and oppositions all coincide, and the 45° dial adds
+//! the semisquares and sesquisquares. The dial reads counter-clockwise from 0° at the top.
+//! A pointer marks one dial position; midpoints of every pair of objects are drawn as ticks
+//! inside the dial, highlighted when the pointer falls on them.
+
+use crate::ephemeris::LayerPositions;
+use crate::rendering::primitives::{Color, Point, Shape, Stroke, TextAnchor, TextBaseline};
+use crate::rendering::spec::{ChartMetadata, ChartShape, ChartSpec, LayerMetadata};
+use crate::rendering::visual_config::{GlyphConfig, VisualConfig};
+use std::collections::BTreeMap;
+
+/// Ring slug given to every dial shape
+pub const DIAL_RING: &str = "dial";
+
+/// Angles drawn on the dial alongside the planets, when the layer has houses
+const DIAL_ANGLES: &[&str] = &["asc", "mc"];
+
+/// Span of the folded zodiac
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DialSize {
+    /// Hard aspects: conjunction, square, opposition
+    Ninety,
+    /// Adds semisquare and sesquisquare
+    FortyFive,
+}
+
+impl DialSize {
+    /// Degrees of longitude covered by one turn of the dial
+    pub fn degrees(self) -> f64 {
+        match self {
+            DialSize::Ninety => 90.0,
+            DialSize::FortyFive => 45.0,
+        }
+    }
+
+    /// Position of a longitude on the dial, in [0, degrees)
+    pub fn fold(self, lon: f64) -> f64 {
+        lon.rem_euclid(self.degrees())
+    }
+}
+
+/// Midpoint of two objects on the dial
+#[derive(Debug, Clone)]
+pub struct DialMidpoint {
+    pub from: String,
+    pub to: String,
+    /// Midpoint longitude of the shorter arc between the objects
+    pub lon: f64,
+    /// Whether the pointer lies within orb of the midpoint on the dial
+    pub active: bool,
+}
+
+/// Dial chart generator - draws one layer's objects on a 90° or 45° dial
+pub struct DialChartGenerator {
+    visual_config: VisualConfig,
+    glyph_config: GlyphConfig,
+    dial: DialSize,
+    /// Longitude the pointer indicates
+    pointer: f64,
+    midpoints: bool,
+    /// Orb in dial degrees for midpoints to count as on the pointer
+    orb: f64,
+}
+
+impl DialChartGenerator {
+    /// Create a dial generator with default configs, the pointer at 0° and midpoints shown
+    pub fn new(dial: DialSize) -> Self {
+        Self::with_configs(dial, VisualConfig::default(), GlyphConfig::default())
+    }
+
+    /// Create a dial generator with custom configs
+    pub fn with_configs(dial: DialSize, visual_config: VisualConfig, glyph_config: GlyphConfig) -> Self {
+        Self {
+            visual_config,
+            glyph_config,
+            dial,
+            pointer: 0.0,
+            midpoints: true,
+            orb: 1.5,
+        }
+    }
+
+    /// Point the dial at longitude `lon`
+    pub fn with_pointer(mut self, lon: f64) -> Self {
+        self.pointer = lon;
+        self
+    }
+
+    /// Show or hide midpoint markers
+    pub fn with_midpoints(mut self, midpoints: bool) -> Self {
+        self.midpoints = midpoints;
+        self
+    }
+
+    /// Orb in dial degrees within which a midpoint is on the pointer
+    pub fn with_orb(mut self, orb: f64) -> Self {
+        self.orb = orb;
+        self
+    }
+
+    /// Midpoints of every pair of objects in `positions`, ordered by object id
+    pub fn midpoints(&self, positions: &LayerPositions, include_objects: Option<&[String]>) -> Vec<DialMidpoint> {
+        let objects = Self::objects(positions, include_objects);
+        let pointer = self.dial.fold(self.pointer);
+        let mut midpoints = Vec::new();
+        for (i, (from, from_lon)) in objects.iter().enumerate() {
+            for (to, to_lon) in &objects[i + 1..] {
+                let arc = (to_lon - from_lon).rem_euclid(360.0);
+                let lon = if arc <= 180.0 {
+                    from_lon + arc / 2.0
+                } else {
+                    to_lon + (360.0 - arc) / 2.0
+                }
+                .rem_euclid(360.0);
+                let distance = (self.dial.fold(lon) - pointer).abs();
+                let distance = distance.min(self.dial.degrees() - distance);
+                midpoints.push(DialMidpoint {
+                    from: from.clone(),
+                    to: to.clone(),
+                    lon,
+                    active: distance <= self.orb,
+                });
+            }
+        }
+        midpoints
+    }
+
+    /// Generate the dial for the layer `layer_id`
+    pub fn generate(
+        &self,
+        layer_id: &str,
+        positions: &LayerPositions,
+        include_objects: Option<&[String]>,
+        width: f32,
+        height: f32,
+    ) -> ChartSpec {
+        let mut spec = ChartSpec::new(width, height);
+        spec.background_color = self.visual_config.background_color;
+        spec.metadata = ChartMetadata {
+            layers: vec![LayerMetadata {
+                id: layer_id.to_string(),
+                kind: "dial".to_string(),
+            }],
+            aspect_sets: Vec::new(),
+        };
+        let center = spec.center;
+        let radius = width.min(height) / 2.0 - 20.0;
+        let stroke = |width: f32| Stroke {
+            color: self.visual_config.stroke_color,
+            width,
+            dash_array: None,
+        };
+        let stroke_width = self.visual_config.stroke_width.unwrap_or(1.0);
+        let pointer_color = self
+            .visual_config
+            .aspect_colors
+            .get("conjunction")
+            .copied()
+            .unwrap_or(Color::WHITE);
+        let layer = [layer_id.to_string()];
+        let mut shapes = Vec::new();
+
+        shapes.push(Self::tag(
+            Shape::Circle { center, radius, fill: None, stroke: Some(stroke(stroke_width)) },
+            "dial/face".to_string(),
+            &[("kind", "face".to_string())],
+            &[],
+        ));
+
+        // Degree ticks, longer every 5°, numbered every 5°
+        let degrees = self.dial.degrees() as u32;
+        for degree in 0..degrees {
+            let length = if degree % 5 == 0 { 0.06 } else { 0.03 };
+            let angle = self.angle(degree as f64);
+            shapes.push(Self::tag(
+                Shape::Line {
+                    from: Self::at(center, angle, radius),
+                    to: Self::at(center, angle, radius * (1.0 - length)),
+                    stroke: stroke(stroke_width * 0.5),
+                },
+                format!("dial/tick/{}", degree),
+                &[("kind", "tick".to_string()), ("degree", degree.to_string())],
+                &[],
+            ));
+            if degree % 5 == 0 {
+                shapes.push(Self::tag(
+                    Shape::Text {
+                        position: Self::at(center, angle, radius * 0.88),
+                        content: degree.to_string(),
+                        size: (radius * 0.04).max(8.0),
+                        color: self.visual_config.stroke_color,
+                        anchor: TextAnchor::Middle,
+                        baseline: TextBaseline::Middle,
+                        rotation: None,
+                    },
+                    format!("dial/label/{}", degree),
+                    &[("kind", "label".to_string()), ("degree", degree.to_string())],
+                    &[],
+                ));
+            }
+        }
+
+        if self.midpoints {
+            for midpoint in self.midpoints(positions, include_objects) {
+                let angle = self.angle(midpoint.lon);
+                let (color, outer) = if midpoint.active {
+                    (pointer_color, 0.4)
+                } else {
+                    (self.visual_config.stroke_color, 0.3)
+                };
+                shapes.push(Self::tag(
+                    Shape::Line {
+                        from: Self::at(center, angle, radius * 0.25),
+                        to: Self::at(center, angle, radius * outer),
+                        stroke: Stroke { color, width: stroke_width * 0.5, dash_array: None },
+                    },
+                    format!("dial/midpoint/{}/{}", midpoint.from, midpoint.to),
+                    &[
+                        ("kind", "midpoint".to_string()),
+                        ("from", midpoint.from.clone()),
+                        ("to", midpoint.to.clone()),
+                        ("active", midpoint.active.to_string()),
+                    ],
+                    &layer,
+                ));
+            }
+        }
+
+        for (object_id, lon) in Self::objects(positions, include_objects) {
+            let retrograde = positions.planets.get(&object_id).is_some_and(|planet| planet.retrograde);
+            shapes.push(Self::tag(
+                Shape::PlanetGlyph {
+                    center: Self::at(center, self.angle(lon), radius * 0.72),
+                    planet_id: object_id.clone(),
+                    size: self.glyph_config.glyph_size.unwrap_or(12.0),
+                    color: self.visual_config.planet_colors.get(&object_id).copied().unwrap_or(Color::WHITE),
+                    retrograde,
+                },
+                format!("dial/planet/{}", object_id),
+                &[("kind", "planet".to_string()), ("planet", object_id.clone())],
+                &layer,
+            ));
+        }
+
+        // The pointer spans the dial; its far end marks the midway point of the dial
+        let angle = self.angle(self.pointer);
+        let tip = Self::at(center, angle, radius * 0.95);
+        let base = Self::at(center, angle, radius * 0.85);
+        let side = |offset: f32| Self::at(base, angle + 90.0, offset);
+        shapes.push(Self::tag(
+            Shape::Line {
+                from: Self::at(center, angle + 180.0, radius * 0.95),
+                to: tip,
+                stroke: Stroke { color: pointer_color, width: stroke_width * 1.5, dash_array: None },
+            },
+            "dial/pointer".to_string(),
+            &[("kind", "pointer".to_string()), ("lon", format!("{:.4}", self.pointer))],
+            &[],
+        ));
+        shapes.push(Self::tag(
+            Shape::Path {
+                points: vec![tip, side(radius * 0.03), side(-radius * 0.03)],
+                closed: true,
+                fill: Some(pointer_color),
+                stroke: None,
+            },
+            "dial/pointer/head".to_string(),
+            &[("kind", "pointer".to_string())],
+            &[],
+        ));
+
+        spec.shapes = shapes;
+        spec
+    }
+
+    /// Planets and chart angles, ordered by id
+    fn objects(positions: &LayerPositions, include_objects: Option<&[String]>) -> Vec<(String, f64)> {
+        let included = |id: &str| include_objects.is_none_or(|ids| ids.iter().any(|object| object == id));
+        let mut objects: Vec<(String, f64)> = positions
+            .planets
+            .iter()
+            .filter(|(id, _)| included(id))
+            .map(|(id, planet)| (id.clone(), planet.lon))
+            .collect();
+        if let Some(houses) = &positions.houses {
+            for angle_id in DIAL_ANGLES {
+                if let Some(lon) = houses.angles.get(*angle_id).filter(|_| included(angle_id)) {
+                    objects.push((angle_id.to_string(), *lon));
+                }
+            }
+        }
+        objects.sort_by(|a, b| a.0.cmp(&b.0));
+        objects
+    }
+
+    /// Canvas angle (degrees clockwise from 3 o'clock) of a longitude on the dial
+    fn angle(&self, lon: f64) -> f32 {
+        (-90.0 - self.dial.fold(lon) * 360.0 / self.dial.degrees()) as f32
+    }
+
+    fn at(center: Point, angle: f32, radius: f32) -> Point {
+        let radians = angle.to_radians();
+        Point {
+            x: center.x + radius * radians.cos(),
+            y: center.y + radius * radians.sin(),
+        }
+    }
+
+    fn tag(shape: Shape, id: String, meta: &[(&str, String)], layer_ids: &[String]) -> ChartShape {
+        let mut tags: BTreeMap<String, String> =
+            meta.iter().map(|(key, value)| (key.to_string(), value.clone())).collect();
+        tags.insert("ring".to_string(), DIAL_RING.to_string());
+        if let [layer_id] = layer_ids {
+            tags.insert("layer".to_string(), layer_id.clone());
+        }
+        ChartShape {
+            shape,
+            id,
+            meta: tags,
+            ring: Some(DIAL_RING.to_string()),
+            layer_ids: layer_ids.to_vec(),
+        }
+    }
+}
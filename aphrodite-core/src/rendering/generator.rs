@@ -1,16 +1,36 @@
-use crate::aspects::types::AspectSet;
-use crate::layout::{AssembledRing, AssembledWheel};
+use crate::aspects::types::{AspectCore, AspectObjectRef, AspectSet};
+use crate::format::format_degrees;
+use crate::layout::{AssembledRing, AssembledWheel, ItemStyle, WheelAssembler};
 use crate::rendering::primitives::{
-    Color, Point, Shape, Stroke,
+    Color, LineStyle, Point, Shape, Stroke, TextAnchor, TextBaseline,
 };
-use crate::rendering::spec::{AspectSetMetadata, ChartMetadata, ChartSpec};
+use crate::rendering::spec::{AspectSetMetadata, ChartMetadata, ChartShape, ChartSpec, ASPECT_LINES_RING};
 use crate::rendering::visual_config::{GlyphConfig, VisualConfig};
-use crate::layout::rings::RingItem;
+use crate::layout::rings::{NakshatraRingItem, PlanetRingItem, PointRingItem, RingItem};
+use crate::vedic::nakshatra::PADA_SIZE;
+use crate::western::condition::{mean_daily_motion, speed_class};
+use std::collections::{BTreeMap, HashMap};
+
+/// Default padding between the outermost ring and the canvas edge
+pub const DEFAULT_MARGIN: f32 = 20.0;
+
+/// Marker drawn beside retrograde planets
+const RETROGRADE_MARKER: &str = "℞";
+/// Glyph widths kept between neighbouring planet glyphs
+const GLYPH_SPACING: f32 = 1.2;
+/// Opacity kept by stationary planets when `dim_stationary` is set
+const STATIONARY_ALPHA: f32 = 0.45;
+/// Radius of a style rule highlight disc, in glyph sizes
+const HIGHLIGHT_RADIUS: f32 = 0.75;
 
 /// ChartSpec generator - converts assembled wheel to ChartSpec
 pub struct ChartSpecGenerator {
     visual_config: VisualConfig,
     glyph_config: GlyphConfig,
+    /// Padding between the outermost ring and the canvas edge
+    margin: f32,
+    /// Degrees added to every wheel position before it is drawn
+    rotation_offset: f64,
 }
 
 impl ChartSpecGenerator {
@@ -19,6 +39,8 @@ impl ChartSpecGenerator {
         Self {
             visual_config: VisualConfig::default(),
             glyph_config: GlyphConfig::default(),
+            margin: DEFAULT_MARGIN,
+            rotation_offset: 0.0,
         }
     }
 
@@ -27,14 +49,34 @@ impl ChartSpecGenerator {
         Self {
             visual_config,
             glyph_config,
+            margin: DEFAULT_MARGIN,
+            rotation_offset: 0.0,
         }
     }
 
+    /// Set the padding between the outermost ring and the canvas edge
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Rotate the wheel by `degrees`; 0 keeps 0° Aries at the 3 o'clock position
+    pub fn with_rotation(mut self, degrees: f64) -> Self {
+        self.rotation_offset = degrees;
+        self
+    }
+
+    /// Rotation that places wheel position `lon` (e.g. the Ascendant, projected with
+    /// [`AssembledWheel::project_longitude`]) on the left, at 9 o'clock
+    pub fn rotation_for_left(lon: f64) -> f64 {
+        (180.0 - lon).rem_euclid(360.0)
+    }
+
     /// Generate ChartSpec from assembled wheel
     pub fn generate(
         &self,
         wheel: &AssembledWheel,
-        aspect_sets: &std::collections::HashMap<String, AspectSet>,
+        aspect_sets: &HashMap<String, AspectSet>,
         width: f32,
         height: f32,
     ) -> ChartSpec {
@@ -42,30 +84,46 @@ impl ChartSpecGenerator {
             x: width / 2.0,
             y: height / 2.0,
         };
-        let max_radius = width.min(height) / 2.0 - 20.0; // padding
+        let max_radius = (width.min(height) / 2.0 - self.margin).max(0.0);
 
         let mut shapes = Vec::new();
 
         // Generate shapes for each ring (in order)
         for ring in &wheel.rings {
-            let ring_shapes = self.generate_ring_shapes(ring, center, max_radius);
+            let ring_shapes = self.generate_ring_shapes(ring, center, max_radius, wheel);
             shapes.extend(ring_shapes);
         }
 
-        // Generate aspect lines
-        for aspect_set in aspect_sets.values() {
-            let aspect_shapes = self.generate_aspect_shapes(aspect_set, center, max_radius);
+        // Generate aspect lines inside the innermost ring, in aspect set id order
+        let aspect_radius = wheel
+            .rings
+            .iter()
+            .map(|ring| ring.radius_inner)
+            .fold(1.0_f32, f32::min)
+            * max_radius;
+        let planet_lons = Self::planet_longitudes(wheel);
+        let mut aspect_set_ids: Vec<&String> = aspect_sets.keys().collect();
+        aspect_set_ids.sort();
+        for aspect_set_id in &aspect_set_ids {
+            let aspect_shapes = self.generate_aspect_shapes(
+                &aspect_sets[*aspect_set_id],
+                &planet_lons,
+                center,
+                aspect_radius,
+                wheel,
+            );
             shapes.extend(aspect_shapes);
         }
+        Self::dedup_ids(&mut shapes);
 
         // Build metadata
         let metadata = ChartMetadata {
             layers: vec![], // TODO: Extract from wheel if available
-            aspect_sets: aspect_sets
-                .values()
-                .map(|a| AspectSetMetadata {
-                    id: a.id.clone(),
-                    layer_ids: a.layer_ids.clone(),
+            aspect_sets: aspect_set_ids
+                .iter()
+                .map(|id| AspectSetMetadata {
+                    id: aspect_sets[*id].id.clone(),
+                    layer_ids: aspect_sets[*id].layer_ids.clone(),
                 })
                 .collect(),
         };
@@ -74,7 +132,7 @@ impl ChartSpecGenerator {
             width,
             height,
             center,
-            rotation_offset: 0.0,
+            rotation_offset: self.rotation_offset as f32,
             background_color: self.visual_config.background_color,
             shapes,
             metadata,
@@ -87,16 +145,20 @@ impl ChartSpecGenerator {
         ring: &AssembledRing,
         center: Point,
         max_radius: f32,
-    ) -> Vec<Shape> {
+        wheel: &AssembledWheel,
+    ) -> Vec<ChartShape> {
+        let layer_ids: Vec<String> = ring.data_source.layer_id().map(str::to_string).into_iter().collect();
         let mut shapes = Vec::new();
+        let mut tagged = Vec::new();
+        let planet_positions = self.planet_display_positions(ring, max_radius, wheel);
 
         for item in &ring.items {
             match item {
                 RingItem::Sign(sign_item) => {
                     let radius_inner = max_radius * ring.radius_inner;
                     let radius_outer = max_radius * ring.radius_outer;
-                    let start_angle = self.astro_to_svg_angle(sign_item.start_lon, 0.0);
-                    let end_angle = self.astro_to_svg_angle(sign_item.end_lon, 0.0);
+                    let start_angle = self.astro_to_svg_angle(sign_item.start_lon, wheel);
+                    let end_angle = self.astro_to_svg_angle(sign_item.end_lon, wheel);
 
                     let sign_color = self
                         .visual_config
@@ -104,6 +166,7 @@ impl ChartSpecGenerator {
                         .get(sign_item.index as usize)
                         .copied()
                         .unwrap_or(Color::WHITE);
+                    let sign_color = Self::styled(sign_color, ring.item_styles.get(&sign_item.id));
 
                     shapes.push(Shape::SignSegment {
                         center,
@@ -120,75 +183,568 @@ impl ChartSpecGenerator {
                         }),
                     });
                 }
-                RingItem::House(_house_item) => {
-                    // House cusps are typically drawn as lines, not segments
-                    // For now, we'll skip house cusp rendering in the generator
-                    // This can be enhanced later
-                }
-                RingItem::Planet(planet_item) => {
-                    let radius = max_radius
-                        * (ring.radius_inner + ring.radius_outer) / 2.0;
-                    let angle = self.astro_to_svg_angle(planet_item.lon, 0.0);
-                    let pos = self.polar_to_cartesian(angle, radius, center);
+                RingItem::House(house_item) => {
+                    // Each house spans from its cusp to the next house's cusp
+                    let next_lon = ring
+                        .items
+                        .iter()
+                        .find_map(|other| match other {
+                            RingItem::House(next) if next.house_index == (house_item.house_index + 1) % 12 => {
+                                Some(next.lon)
+                            }
+                            _ => None,
+                        })
+                        .unwrap_or(house_item.lon + 30.0);
+                    let radius_inner = max_radius * ring.radius_inner;
+                    let radius_outer = max_radius * ring.radius_outer;
+                    let start_angle = self.astro_to_svg_angle(house_item.lon, wheel);
+                    let end_angle = self.astro_to_svg_angle(next_lon, wheel);
 
-                    let planet_color = self
+                    let house_color = self
                         .visual_config
-                        .planet_colors
-                        .get(&planet_item.planet_id)
+                        .house_colors
+                        .get(house_item.house_index as usize)
                         .copied()
                         .unwrap_or(Color::WHITE);
 
-                    shapes.push(Shape::PlanetGlyph {
-                        center: pos,
-                        planet_id: planet_item.planet_id.clone(),
-                        size: self.glyph_config.glyph_size.unwrap_or(12.0),
-                        color: planet_color,
-                        retrograde: planet_item.retrograde.unwrap_or(false),
+                    shapes.push(Shape::HouseSegment {
+                        center,
+                        house_num: house_item.house_index + 1,
+                        start_angle,
+                        end_angle,
+                        radius_inner,
+                        radius_outer,
+                        fill: house_color,
+                        stroke: Some(Stroke {
+                            color: self.visual_config.stroke_color,
+                            width: self.visual_config.stroke_width.unwrap_or(1.0),
+                            dash_array: None,
+                        }),
                     });
                 }
+                RingItem::Planet(planet_item) => {
+                    let display = planet_positions[&planet_item.id];
+                    tagged.extend(self.planet_shapes(planet_item, display, ring, center, max_radius, wheel, &layer_ids));
+                }
                 RingItem::Aspect(_) => {
                     // Aspects are handled separately
                 }
+                RingItem::Nakshatra(nakshatra_item) => {
+                    tagged.extend(self.nakshatra_shapes(nakshatra_item, ring, center, max_radius, wheel));
+                }
+                RingItem::Point(point_item) => {
+                    tagged.extend(self.point_shapes(point_item, ring, center, max_radius, wheel, &layer_ids));
+                }
+            }
+        }
+
+        shapes
+            .into_iter()
+            .map(|shape| Self::tag(shape, &ring.slug, layer_ids.clone()))
+            .chain(tagged)
+            .collect()
+    }
+
+    /// Segment outline, pada ticks and label for one nakshatra. The ring's `padas` display
+    /// option (default off) adds ticks at the three inner pada boundaries; `labels` (default
+    /// on) writes the nakshatra name radially across the ring.
+    fn nakshatra_shapes(
+        &self,
+        item: &NakshatraRingItem,
+        ring: &AssembledRing,
+        center: Point,
+        max_radius: f32,
+        wheel: &AssembledWheel,
+    ) -> Vec<ChartShape> {
+        let option = |name: &str, default: bool| {
+            ring.display_options.get(name).and_then(|value| value.as_bool()).unwrap_or(default)
+        };
+        let radius_inner = max_radius * ring.radius_inner;
+        let radius_outer = max_radius * ring.radius_outer;
+        let stroke_width = self.visual_config.stroke_width.unwrap_or(1.0);
+        let base_id = format!("{}/nakshatra/{}", ring.slug, item.index);
+        let mut shapes = Vec::new();
+        let mut push = |shape: Shape, id: String, kind: &str, pada: Option<usize>| {
+            let mut meta = vec![("kind", kind.to_string()), ("nakshatra", item.index.to_string())];
+            meta.extend(pada.map(|pada| ("pada", pada.to_string())));
+            shapes.push(Self::tag_as(shape, &ring.slug, Vec::new(), id, &meta));
+        };
+
+        // Arc angles are canvas angles swept clockwise; take the short way in either direction
+        let mut start_angle = 90.0 - self.astro_to_svg_angle(item.start_lon, wheel);
+        let mut end_angle = 90.0 - self.astro_to_svg_angle(item.end_lon, wheel);
+        if (end_angle - start_angle).rem_euclid(360.0) > 180.0 {
+            std::mem::swap(&mut start_angle, &mut end_angle);
+        }
+        push(
+            Shape::Arc {
+                center,
+                radius_inner,
+                radius_outer,
+                start_angle,
+                end_angle,
+                fill: None,
+                stroke: Some(Stroke {
+                    color: self.visual_config.stroke_color,
+                    width: stroke_width,
+                    dash_array: None,
+                }),
+            },
+            base_id.clone(),
+            "nakshatra",
+            None,
+        );
+
+        if option("padas", false) {
+            let tick_outer = radius_inner + (radius_outer - radius_inner) * 0.25;
+            for pada in 2..=4 {
+                let lon = item.start_lon + (pada - 1) as f64 * PADA_SIZE;
+                let angle = self.astro_to_svg_angle(lon, wheel);
+                push(
+                    Shape::Line {
+                        from: self.polar_to_cartesian(angle, radius_inner, center),
+                        to: self.polar_to_cartesian(angle, tick_outer, center),
+                        stroke: Stroke {
+                            color: self.visual_config.stroke_color,
+                            width: stroke_width * 0.5,
+                            dash_array: None,
+                        },
+                    },
+                    format!("{}/pada/{}", base_id, pada),
+                    "pada",
+                    Some(pada),
+                );
             }
         }
 
+        if option("labels", true) {
+            let angle = self.astro_to_svg_angle((item.start_lon + item.end_lon) / 2.0, wheel);
+            // Read outward along the radius, flipped on the left half so it is never upside down
+            let canvas_angle = (90.0 - angle).rem_euclid(360.0);
+            let rotation = if canvas_angle > 90.0 && canvas_angle < 270.0 {
+                canvas_angle - 180.0
+            } else {
+                canvas_angle
+            };
+            let fit = (radius_outer - radius_inner) * 0.9 / (item.label.chars().count() as f32 * 0.55);
+            push(
+                Shape::Text {
+                    position: self.polar_to_cartesian(angle, (radius_inner + radius_outer) / 2.0, center),
+                    content: item.label.clone(),
+                    size: fit.min(self.glyph_config.glyph_size.unwrap_or(12.0) * 0.8),
+                    color: self.visual_config.stroke_color,
+                    anchor: TextAnchor::Middle,
+                    baseline: TextBaseline::Middle,
+                    rotation: Some(rotation),
+                },
+                format!("{}/label", base_id),
+                "text",
+                None,
+            );
+        }
+
         shapes
     }
 
-    /// Generate aspect line shapes
+    /// Wheel positions for the ring's planet glyphs, keyed by item id. Glyphs closer than
+    /// their own width are pushed apart, keeping their order around the wheel.
+    fn planet_display_positions(&self, ring: &AssembledRing, max_radius: f32, wheel: &AssembledWheel) -> HashMap<String, f64> {
+        let planets: Vec<&PlanetRingItem> = ring
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                RingItem::Planet(planet) => Some(planet),
+                _ => None,
+            })
+            .collect();
+        let radius = max_radius * (ring.radius_inner + ring.radius_outer) / 2.0;
+        if planets.is_empty() || radius <= 0.0 {
+            return planets.iter().map(|planet| (planet.id.clone(), wheel.project_longitude(planet.lon))).collect();
+        }
+
+        let size = self.glyph_config.glyph_size.unwrap_or(12.0);
+        let min_separation = ((size * GLYPH_SPACING / radius) as f64).to_degrees();
+        let positions: Vec<f64> = planets.iter().map(|planet| wheel.project_longitude(planet.lon)).collect();
+        planets
+            .iter()
+            .zip(Self::spread(&positions, min_separation))
+            .map(|(planet, position)| (planet.id.clone(), position))
+            .collect()
+    }
+
+    /// Move positions (degrees around a circle) apart until neighbours are at least
+    /// `min_separation` apart. Crowded runs are centred on their mean position; the circle is
+    /// cut at its widest gap so runs never straddle the cut. Returns positions in input order.
+    fn spread(positions: &[f64], min_separation: f64) -> Vec<f64> {
+        let count = positions.len();
+        if count < 2 {
+            return positions.to_vec();
+        }
+        let min_separation = min_separation.min(360.0 / count as f64);
+
+        let mut order: Vec<usize> = (0..count).collect();
+        order.sort_by(|a, b| positions[*a].rem_euclid(360.0).total_cmp(&positions[*b].rem_euclid(360.0)));
+        let sorted: Vec<f64> = order.iter().map(|index| positions[*index].rem_euclid(360.0)).collect();
+        let widest_gap_end = (0..count)
+            .max_by(|a, b| {
+                let gap = |i: usize| (sorted[i] - sorted[(i + count - 1) % count]).rem_euclid(360.0);
+                gap(*a).total_cmp(&gap(*b))
+            })
+            .unwrap_or(0);
+        let start = sorted[widest_gap_end];
+        let unwrapped: Vec<f64> = (0..count)
+            .map(|k| start + (sorted[(widest_gap_end + k) % count] - start).rem_euclid(360.0))
+            .collect();
+
+        // Runs as (first position, members, sum of original positions)
+        let mut runs: Vec<(f64, usize, f64)> = Vec::new();
+        for &position in &unwrapped {
+            runs.push((position, 1, position));
+            while runs.len() > 1 {
+                let (first, members, sum) = runs[runs.len() - 1];
+                let (prev_first, prev_members, prev_sum) = runs[runs.len() - 2];
+                if first >= prev_first + prev_members as f64 * min_separation {
+                    break;
+                }
+                runs.truncate(runs.len() - 2);
+                let (members, sum) = (members + prev_members, sum + prev_sum);
+                let first = sum / members as f64 - (members - 1) as f64 * min_separation / 2.0;
+                runs.push((first, members, sum));
+            }
+        }
+
+        let mut spread = vec![0.0; count];
+        let mut k = 0;
+        for (first, members, _) in runs {
+            for offset in 0..members {
+                let index = order[(widest_gap_end + k) % count];
+                spread[index] = (first + offset as f64 * min_separation).rem_euclid(360.0);
+                k += 1;
+            }
+        }
+        spread
+    }
+
+    /// Planet glyph at wheel position `display`, with a "℞" beside it when retrograde and,
+    /// when the ring's `degreeLabels` display option is set, its sign and DD°MM′ inside it.
+    /// Stationary planets are faded when `dim_stationary` is set; the glyph's `speed` meta
+    /// carries the speed class.
+    #[allow(clippy::too_many_arguments)]
+    fn planet_shapes(
+        &self,
+        item: &PlanetRingItem,
+        display: f64,
+        ring: &AssembledRing,
+        center: Point,
+        max_radius: f32,
+        wheel: &AssembledWheel,
+        layer_ids: &[String],
+    ) -> Vec<ChartShape> {
+        let radius = max_radius * (ring.radius_inner + ring.radius_outer) / 2.0;
+        let angle = self.wheel_to_svg_angle(display, wheel);
+        let pos = self.polar_to_cartesian(angle, radius, center);
+        let size = self.glyph_config.glyph_size.unwrap_or(12.0);
+        let retrograde = item.retrograde.unwrap_or(false);
+        let speed = item
+            .speed_lon
+            .zip(mean_daily_motion(&item.planet_id))
+            .map(|(speed, mean)| speed_class(speed.abs() / mean));
+
+        let mut color = self
+            .visual_config
+            .planet_colors
+            .get(&item.planet_id)
+            .copied()
+            .unwrap_or(Color::WHITE);
+        let style = ring.item_styles.get(&item.id);
+        color = Self::styled(color, style);
+        if self.visual_config.dim_stationary && speed == Some("stationary") {
+            color.a = (color.a as f32 * STATIONARY_ALPHA).round() as u8;
+        }
+        let mut shapes = Vec::new();
+        if let Some(highlight) = Self::highlight(pos, size, style) {
+            let id = format!("{}/planet/{}/highlight", ring.slug, item.planet_id);
+            let meta = [("kind", "highlight".to_string()), ("planet", item.planet_id.clone())];
+            shapes.push(Self::tag_as(highlight, &ring.slug, layer_ids.to_vec(), id, &meta));
+        }
+
+        let glyph = Shape::PlanetGlyph {
+            center: pos,
+            planet_id: item.planet_id.clone(),
+            size,
+            color,
+            retrograde,
+        };
+        let mut glyph = Self::tag(glyph, &ring.slug, layer_ids.to_vec());
+        if let Some(speed) = speed {
+            glyph.meta.insert("speed".to_string(), speed.to_string());
+        }
+        shapes.push(glyph);
+
+        if retrograde && self.visual_config.retrograde_marker {
+            let marker = Shape::Text {
+                position: Point { x: pos.x + size * 0.6, y: pos.y + size * 0.5 },
+                content: RETROGRADE_MARKER.to_string(),
+                size: size * 0.6,
+                color,
+                anchor: TextAnchor::Start,
+                baseline: TextBaseline::Middle,
+                rotation: None,
+            };
+            let id = format!("{}/planet/{}/retrograde", ring.slug, item.planet_id);
+            let meta = [("kind", "retrograde".to_string()), ("planet", item.planet_id.clone())];
+            shapes.push(Self::tag_as(marker, &ring.slug, layer_ids.to_vec(), id, &meta));
+        }
+
+        let degree_labels = ring.display_options.get("degreeLabels").and_then(|value| value.as_bool());
+        if degree_labels.unwrap_or(false) {
+            let sign = self.glyph_config.sign_glyphs.get(&item.sign_index).cloned().unwrap_or_default();
+            let label = Shape::Text {
+                position: self.polar_to_cartesian(angle, radius - size * 1.4, center),
+                content: format!("{} {}", sign, format_degrees(item.sign_degree.rem_euclid(30.0))),
+                size: size * 0.6,
+                color,
+                anchor: TextAnchor::Middle,
+                baseline: TextBaseline::Middle,
+                rotation: None,
+            };
+            let id = format!("{}/planet/{}/degree", ring.slug, item.planet_id);
+            let meta = [("kind", "degree".to_string()), ("planet", item.planet_id.clone())];
+            shapes.push(Self::tag_as(label, &ring.slug, layer_ids.to_vec(), id, &meta));
+        }
+        shapes
+    }
+
+    /// Tick at the inner edge and label for a custom point or fixed star
+    fn point_shapes(
+        &self,
+        item: &PointRingItem,
+        ring: &AssembledRing,
+        center: Point,
+        max_radius: f32,
+        wheel: &AssembledWheel,
+        layer_ids: &[String],
+    ) -> Vec<ChartShape> {
+        let radius_inner = max_radius * ring.radius_inner;
+        let radius_outer = max_radius * ring.radius_outer;
+        let angle = self.astro_to_svg_angle(item.lon, wheel);
+        let id = format!("{}/point/{}", ring.slug, item.point_id);
+        let meta = |kind: &str| [("kind", kind.to_string()), ("point", item.point_id.clone())];
+        let style = ring.item_styles.get(&item.id);
+        let size = self.glyph_config.glyph_size.unwrap_or(12.0) * 0.8;
+        let label_position = self.polar_to_cartesian(angle, radius_inner + (radius_outer - radius_inner) * 0.6, center);
+
+        let tick = Shape::Line {
+            from: self.polar_to_cartesian(angle, radius_inner, center),
+            to: self.polar_to_cartesian(angle, radius_inner + (radius_outer - radius_inner) * 0.25, center),
+            stroke: Stroke {
+                color: self.visual_config.stroke_color,
+                width: self.visual_config.stroke_width.unwrap_or(1.0),
+                dash_array: None,
+            },
+        };
+        let label = Shape::Text {
+            position: label_position,
+            content: item.label.clone(),
+            size,
+            color: Self::styled(self.visual_config.stroke_color, style),
+            anchor: TextAnchor::Middle,
+            baseline: TextBaseline::Middle,
+            rotation: None,
+        };
+        let mut shapes = vec![Self::tag_as(tick, &ring.slug, layer_ids.to_vec(), format!("{}/tick", id), &meta("tick"))];
+        if let Some(highlight) = Self::highlight(label_position, size, style) {
+            shapes.push(Self::tag_as(highlight, &ring.slug, layer_ids.to_vec(), format!("{}/highlight", id), &meta("highlight")));
+        }
+        shapes.push(Self::tag_as(label, &ring.slug, layer_ids.to_vec(), id, &meta(&item.kind)));
+        shapes
+    }
+
+    /// `color` with a style rule's color and opacity applied
+    fn styled(color: Color, style: Option<&ItemStyle>) -> Color {
+        let Some(style) = style else {
+            return color;
+        };
+        let mut color = style.color.as_deref().and_then(Color::from_hex).unwrap_or(color);
+        if let Some(opacity) = style.opacity {
+            color.a = (color.a as f32 * opacity.clamp(0.0, 1.0)).round() as u8;
+        }
+        color
+    }
+
+    /// Disc behind a glyph of `size` at `center`, when a style rule sets a highlight
+    fn highlight(center: Point, size: f32, style: Option<&ItemStyle>) -> Option<Shape> {
+        let fill = style?.highlight.as_deref().and_then(Color::from_hex)?;
+        Some(Shape::Circle { center, radius: size * HIGHLIGHT_RADIUS, fill: Some(fill), stroke: None })
+    }
+
+    /// Tag a shape with an explicit id, overriding or adding metadata entries
+    fn tag_as(shape: Shape, ring: &str, layer_ids: Vec<String>, id: String, meta: &[(&str, String)]) -> ChartShape {
+        let mut chart_shape = Self::tag(shape, ring, layer_ids);
+        chart_shape.id = id;
+        for (key, value) in meta {
+            chart_shape.meta.insert(key.to_string(), value.clone());
+        }
+        chart_shape
+    }
+
+    /// Wrap a shape with its ring, layers, id and metadata
+    fn tag(shape: Shape, ring: &str, layer_ids: Vec<String>) -> ChartShape {
+        let mut meta = BTreeMap::new();
+        if let [layer_id] = layer_ids.as_slice() {
+            meta.insert("layer".to_string(), layer_id.clone());
+        }
+        let object = |object: &Option<AspectObjectRef>| {
+            object
+                .as_ref()
+                .map(|object| format!("{}.{}", object.layer_id, object.object_id))
+                .unwrap_or_default()
+        };
+
+        let (kind, path) = match &shape {
+            Shape::SignSegment { sign_index, .. } => {
+                meta.insert("sign".to_string(), sign_index.to_string());
+                ("sign", vec!["sign".to_string(), sign_index.to_string()])
+            }
+            Shape::HouseSegment { house_num, .. } => {
+                meta.insert("house".to_string(), house_num.to_string());
+                ("house", vec!["house".to_string(), house_num.to_string()])
+            }
+            Shape::PlanetGlyph { planet_id, .. } => {
+                meta.insert("planet".to_string(), planet_id.clone());
+                ("planet", vec!["planet".to_string(), planet_id.clone()])
+            }
+            Shape::AspectLine { aspect_type, from_object, to_object, .. } => {
+                let (from, to) = (object(from_object), object(to_object));
+                meta.insert("aspect".to_string(), aspect_type.clone());
+                meta.insert("from".to_string(), from.clone());
+                meta.insert("to".to_string(), to.clone());
+                ("aspect", vec![from, aspect_type.clone(), to])
+            }
+            Shape::Circle { .. } => ("circle", vec!["circle".to_string()]),
+            Shape::Arc { .. } => ("arc", vec!["arc".to_string()]),
+            Shape::Line { .. } => ("line", vec!["line".to_string()]),
+            Shape::Text { .. } => ("text", vec!["text".to_string()]),
+            Shape::Path { .. } => ("path", vec!["path".to_string()]),
+        };
+        meta.insert("kind".to_string(), kind.to_string());
+        meta.insert("ring".to_string(), ring.to_string());
+
+        ChartShape {
+            shape,
+            id: format!("{}/{}", ring, path.join("/")),
+            meta,
+            ring: Some(ring.to_string()),
+            layer_ids,
+        }
+    }
+
+    /// Number repeated ids in drawing order (`id`, `id#2`, ...) so every id is unique
+    fn dedup_ids(shapes: &mut [ChartShape]) {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        for shape in shapes {
+            let count = seen.entry(shape.id.clone()).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                shape.id = format!("{}#{}", shape.id, count);
+            }
+        }
+    }
+
+    /// Planet longitudes on the wheel keyed by (layer id, planet id)
+    fn planet_longitudes(wheel: &AssembledWheel) -> HashMap<(String, String), f64> {
+        wheel
+            .rings
+            .iter()
+            .flat_map(|ring| ring.items.iter())
+            .filter_map(|item| match item {
+                RingItem::Planet(planet) => {
+                    Some(((planet.layer_id.clone(), planet.planet_id.clone()), planet.lon))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Generate aspect line shapes between planets drawn on the wheel.
+    ///
+    /// Angles are drawn when a planet ring shows them; pairs whose objects are not on a
+    /// planet ring (houses, filtered planets) are skipped.
     fn generate_aspect_shapes(
         &self,
         aspect_set: &AspectSet,
-        _center: Point,
-        _max_radius: f32,
-    ) -> Vec<Shape> {
-        let shapes = Vec::new();
-
-        // For aspect lines, we need to find the planet positions
-        // This is a simplified version - full implementation would need
-        // to resolve planet positions from the wheel rings
-        // For now, we'll create a placeholder that can be enhanced
+        planet_lons: &HashMap<(String, String), f64>,
+        center: Point,
+        radius: f32,
+        wheel: &AssembledWheel,
+    ) -> Vec<ChartShape> {
+        let mut shapes = Vec::new();
 
         for pair in &aspect_set.pairs {
-            // Get aspect color
-            let _aspect_color = self
-                .visual_config
-                .aspect_colors
-                .get(&pair.aspect.aspect_type)
-                .copied()
-                .unwrap_or(Color::WHITE);
+            let lon_of = |object: &crate::aspects::types::AspectObjectRef| {
+                if object.object_type != "planet" && object.object_type != "angle" {
+                    return None;
+                }
+                planet_lons
+                    .get(&(object.layer_id.clone(), object.object_id.clone()))
+                    .copied()
+            };
+            let (Some(from_lon), Some(to_lon)) = (lon_of(&pair.from), lon_of(&pair.to)) else {
+                continue;
+            };
+
+            let (color, width, style) = self.aspect_line_style(&pair.aspect);
 
-            // Calculate positions (simplified - would need actual planet positions)
-            // For now, we'll skip rendering aspect lines without planet positions
-            // This can be enhanced when we have full planet position resolution
+            let from_angle = self.astro_to_svg_angle(from_lon, wheel);
+            let to_angle = self.astro_to_svg_angle(to_lon, wheel);
+
+            let mut layer_ids = vec![pair.from.layer_id.clone()];
+            if pair.to.layer_id != pair.from.layer_id {
+                layer_ids.push(pair.to.layer_id.clone());
+            }
+
+            let shape = Shape::AspectLine {
+                from: self.polar_to_cartesian(from_angle, radius, center),
+                to: self.polar_to_cartesian(to_angle, radius, center),
+                aspect_type: pair.aspect.aspect_type.clone(),
+                color,
+                width,
+                style,
+                from_object: Some(pair.from.clone()),
+                to_object: Some(pair.to.clone()),
+            };
+            let mut shape = Self::tag(shape, ASPECT_LINES_RING, layer_ids);
+            shape.meta.insert("orb".to_string(), format!("{:.2}", pair.aspect.orb));
+            shapes.push(shape);
         }
 
         shapes
     }
 
-    /// Convert astronomical angle to SVG angle
-    fn astro_to_svg_angle(&self, astro_angle: f64, rotation_offset: f64) -> f32 {
-        let mut angle = 90.0 - (astro_angle + rotation_offset);
+    /// Color, width and dash pattern of an aspect line. The aspect type picks the color and
+    /// dashes; the line fades and thins linearly from exact to the type's orb limit.
+    fn aspect_line_style(&self, aspect: &AspectCore) -> (Color, f32, LineStyle) {
+        let config = &self.visual_config;
+        let limit = config.aspect_orb_limits.get(&aspect.aspect_type).copied().unwrap_or(8.0);
+        let tightness = if limit > 0.0 { (1.0 - aspect.orb / limit).clamp(0.0, 1.0) as f32 } else { 1.0 };
+        let scale = |min: f32| min + (1.0 - min) * tightness;
+
+        let mut color = config.aspect_colors.get(&aspect.aspect_type).copied().unwrap_or(Color::WHITE);
+        color.a = (color.a as f32 * scale(config.aspect_min_alpha)).round() as u8;
+        let width = config.aspect_stroke_width.unwrap_or(1.0) * scale(config.aspect_min_width);
+        let style = config.aspect_styles.get(&aspect.aspect_type).copied().unwrap_or(LineStyle::Solid);
+        (color, width, style)
+    }
+
+    /// Convert astronomical angle to SVG angle, projected and rotated onto the wheel
+    fn astro_to_svg_angle(&self, astro_angle: f64, wheel: &AssembledWheel) -> f32 {
+        self.wheel_to_svg_angle(wheel.project_longitude(astro_angle), wheel)
+    }
+
+    /// Convert an already projected wheel position to SVG angle, rotated onto the wheel
+    fn wheel_to_svg_angle(&self, wheel_position: f64, wheel: &AssembledWheel) -> f32 {
+        let wheel_angle = wheel_position + self.rotation_offset;
+        let mut angle = 90.0 - WheelAssembler::map_angle(wheel_angle, wheel.direction);
         while angle < 0.0 {
             angle += 360.0;
         }
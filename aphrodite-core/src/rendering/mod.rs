@@ -1,12 +1,14 @@
+pub mod dial;
 pub mod generator;
 pub mod primitives;
 pub mod spec;
 pub mod visual_config;
 
-pub use generator::ChartSpecGenerator;
+pub use dial::{DialChartGenerator, DialMidpoint, DialSize, DIAL_RING};
+pub use generator::{ChartSpecGenerator, DEFAULT_MARGIN};
 pub use primitives::{
-    Color, LineStyle, Point, Shape, Stroke, TextAnchor,
+    Color, LineStyle, Point, Shape, Stroke, TextAnchor, TextBaseline,
 };
-pub use spec::{AspectSetMetadata, ChartMetadata, ChartSpec, LayerMetadata};
+pub use spec::{AspectSetMetadata, ChartMetadata, ChartShape, ChartSpec, LayerMetadata, ASPECT_LINES_RING};
 pub use visual_config::{GlyphConfig, VisualConfig};
 
@@ -1,3 +1,4 @@
+use crate::aspects::types::AspectObjectRef;
 use serde::{Deserialize, Serialize};
 
 /// Point in 2D space
@@ -84,6 +85,19 @@ pub enum TextAnchor {
     End,
 }
 
+/// Vertical text alignment relative to the text position
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextBaseline {
+    /// Top of the drawn glyphs
+    Top,
+    /// Visual center of the drawn glyphs
+    Middle,
+    #[default]
+    Alphabetic,
+    /// Bottom of the drawn glyphs, including descenders
+    Bottom,
+}
+
 /// Line style
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub enum LineStyle {
@@ -106,7 +120,7 @@ pub enum Shape {
         center: Point,
         radius_inner: f32,
         radius_outer: f32,
-        start_angle: f32, // degrees, 0 = top, clockwise
+        start_angle: f32, // canvas degrees, 0 = 3 o'clock, clockwise
         end_angle: f32,
         fill: Option<Color>,
         stroke: Option<Stroke>,
@@ -128,7 +142,9 @@ pub enum Shape {
         size: f32,
         color: Color,
         anchor: TextAnchor,
-        rotation: Option<f32>, // degrees
+        #[serde(default)]
+        baseline: TextBaseline,
+        rotation: Option<f32>, // degrees clockwise around `position`
     },
     PlanetGlyph {
         center: Point,
@@ -144,6 +160,11 @@ pub enum Shape {
         color: Color,
         width: f32,
         style: LineStyle,
+        /// Objects joined by the line, when it was generated from an aspect set
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from_object: Option<AspectObjectRef>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        to_object: Option<AspectObjectRef>,
     },
     HouseSegment {
         center: Point,
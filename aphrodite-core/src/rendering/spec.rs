@@ -1,5 +1,6 @@
 use crate::rendering::primitives::{Color, Point, Shape};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Chart metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +23,44 @@ pub struct AspectSetMetadata {
     pub layer_ids: Vec<String>,
 }
 
+/// Ring slug given to aspect lines, which are drawn inside the wheel rather than on a ring
+pub const ASPECT_LINES_RING: &str = "aspects";
+
+/// A shape tagged with the ring and layers it was generated from, so renderers can show or
+/// hide parts of a chart without a new spec. Serializes flat, as the shape's own fields
+/// plus `id`, `meta`, `ring` and `layer_ids`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChartShape {
+    #[serde(flatten)]
+    pub shape: Shape,
+    /// Identifier unique within the spec and stable across charts with the same wheel, e.g.
+    /// `ring_planets/planet/sun` or `aspects/natal.sun/trine/natal.moon`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub id: String,
+    /// What the shape depicts: `kind` plus `layer`, `planet`, `house`, `sign`, or `aspect`
+    /// with its `from` and `to` objects as `layer.object`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub meta: BTreeMap<String, String>,
+    /// Slug of the wheel ring, or `ASPECT_LINES_RING` for aspect lines
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ring: Option<String>,
+    /// Layers the shape shows; inter-layer aspect lines carry both
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layer_ids: Vec<String>,
+}
+
+impl From<Shape> for ChartShape {
+    fn from(shape: Shape) -> Self {
+        Self {
+            shape,
+            id: String::new(),
+            meta: BTreeMap::new(),
+            ring: None,
+            layer_ids: Vec::new(),
+        }
+    }
+}
+
 /// Chart specification - declarative description of chart to render
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChartSpec {
@@ -30,7 +69,7 @@ pub struct ChartSpec {
     pub center: Point,
     pub rotation_offset: f32, // For chart rotation
     pub background_color: Color,
-    pub shapes: Vec<Shape>,
+    pub shapes: Vec<ChartShape>,
     pub metadata: ChartMetadata,
 }
 
@@ -53,5 +92,10 @@ impl ChartSpec {
             },
         }
     }
+
+    /// The shape with identifier `id`
+    pub fn shape(&self, id: &str) -> Option<&ChartShape> {
+        self.shapes.iter().find(|shape| shape.id == id)
+    }
 }
 
@@ -1,4 +1,5 @@
-use crate::rendering::primitives::Color;
+use crate::aspects::DEFAULT_ORBS;
+use crate::rendering::primitives::{Color, LineStyle};
 use std::collections::HashMap;
 
 /// Visual styling configuration for chart elements
@@ -11,9 +12,21 @@ pub struct VisualConfig {
     pub planet_colors: HashMap<String, Color>,
     pub aspect_colors: HashMap<String, Color>,
     pub aspect_stroke_width: Option<f32>,
+    /// Dash pattern per aspect type; types not listed are drawn solid
+    pub aspect_styles: HashMap<String, LineStyle>,
+    /// Orb per aspect type at which a line is drawn faintest and thinnest
+    pub aspect_orb_limits: HashMap<String, f64>,
+    /// Opacity of a line at its orb limit relative to an exact aspect; 1.0 turns fading off
+    pub aspect_min_alpha: f32,
+    /// Width of a line at its orb limit relative to an exact aspect; 1.0 turns thinning off
+    pub aspect_min_width: f32,
     pub background_color: Color,
     pub stroke_color: Color,
     pub stroke_width: Option<f32>,
+    /// Draw "℞" beside retrograde planets
+    pub retrograde_marker: bool,
+    /// Fade planets moving at under a tenth of their mean daily motion
+    pub dim_stationary: bool,
 }
 
 impl Default for VisualConfig {
@@ -63,6 +76,7 @@ impl Default for VisualConfig {
         planet_colors.insert("chiron".to_string(), Color::from_hex("#8B7355").unwrap_or(Color::WHITE));
         planet_colors.insert("north_node".to_string(), Color::from_hex("#00CED1").unwrap_or(Color::WHITE));
         planet_colors.insert("south_node".to_string(), Color::from_hex("#00CED1").unwrap_or(Color::WHITE));
+        planet_colors.insert("lilith".to_string(), Color::from_hex("#4B0082").unwrap_or(Color::WHITE));
 
         let mut aspect_colors = HashMap::new();
         aspect_colors.insert("conjunction".to_string(), Color::from_hex("#DC143C").unwrap_or(Color::WHITE));
@@ -71,6 +85,16 @@ impl Default for VisualConfig {
         aspect_colors.insert("square".to_string(), Color::from_hex("#FF0000").unwrap_or(Color::WHITE));
         aspect_colors.insert("sextile".to_string(), Color::from_hex("#FFA500").unwrap_or(Color::WHITE));
 
+        // Hard aspects solid, soft aspects broken
+        let mut aspect_styles = HashMap::new();
+        aspect_styles.insert("conjunction".to_string(), LineStyle::Solid);
+        aspect_styles.insert("opposition".to_string(), LineStyle::Solid);
+        aspect_styles.insert("square".to_string(), LineStyle::Solid);
+        aspect_styles.insert("trine".to_string(), LineStyle::Dashed);
+        aspect_styles.insert("sextile".to_string(), LineStyle::Dotted);
+
+        let aspect_orb_limits = DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect();
+
         Self {
             ring_width: Some(30.0),
             ring_spacing: Some(10.0),
@@ -79,9 +103,15 @@ impl Default for VisualConfig {
             planet_colors,
             aspect_colors,
             aspect_stroke_width: Some(2.0),
+            aspect_styles,
+            aspect_orb_limits,
+            aspect_min_alpha: 0.35,
+            aspect_min_width: 0.5,
             background_color: Color::BLACK,
             stroke_color: Color::from_hex("#d4af37").unwrap_or(Color::WHITE), // Gold
             stroke_width: Some(1.0),
+            retrograde_marker: true,
+            dim_stationary: false,
         }
     }
 }
@@ -126,6 +156,7 @@ impl Default for GlyphConfig {
         planet_glyphs.insert("chiron".to_string(), "⚷".to_string());
         planet_glyphs.insert("north_node".to_string(), "☊".to_string());
         planet_glyphs.insert("south_node".to_string(), "☋".to_string());
+        planet_glyphs.insert("lilith".to_string(), "⚸".to_string());
 
         Self {
             sign_glyphs,
@@ -0,0 +1,239 @@
+//! Synastry calculations: cross-aspects, house overlays and compatibility scoring.
+
+use std::collections::HashMap;
+use crate::aspects::{AspectCalculator, AspectObjectRef, AspectPair, AspectSet, AspectSettings};
+use crate::ephemeris::types::LayerPositions;
+use crate::layout::rings::get_house_index;
+use crate::synastry::types::{CompatibilityScore, HouseOverlay, SynastryReport};
+
+/// Relative importance of each object in a synastry contact
+const PLANET_WEIGHTS: &[(&str, f64)] = &[
+    ("sun", 1.0),
+    ("moon", 1.0),
+    ("venus", 0.9),
+    ("mars", 0.8),
+    ("mercury", 0.7),
+    ("jupiter", 0.6),
+    ("saturn", 0.6),
+    ("north_node", 0.4),
+    ("south_node", 0.4),
+    ("uranus", 0.3),
+    ("neptune", 0.3),
+    ("pluto", 0.3),
+    ("chiron", 0.3),
+];
+
+/// Weight for objects not listed in PLANET_WEIGHTS
+const DEFAULT_PLANET_WEIGHT: f64 = 0.3;
+
+/// Signed contribution of each aspect type (positive = harmonious, negative = challenging)
+const ASPECT_WEIGHTS: &[(&str, f64)] = &[
+    ("conjunction", 1.0),
+    ("trine", 1.0),
+    ("sextile", 0.75),
+    ("square", -1.0),
+    ("opposition", -0.75),
+];
+
+/// Orb used for tightness when the settings don't specify one
+const DEFAULT_ORB: f64 = 8.0;
+
+/// Get the synastry weight of a planet
+pub fn planet_weight(planet_id: &str) -> f64 {
+    PLANET_WEIGHTS
+        .iter()
+        .find(|(id, _)| *id == planet_id)
+        .map(|(_, w)| *w)
+        .unwrap_or(DEFAULT_PLANET_WEIGHT)
+}
+
+/// Get the signed synastry weight of an aspect type
+pub fn aspect_weight(aspect_type: &str) -> f64 {
+    ASPECT_WEIGHTS
+        .iter()
+        .find(|(name, _)| *name == aspect_type)
+        .map(|(_, w)| *w)
+        .unwrap_or(0.0)
+}
+
+/// Compute cross-aspects between two charts.
+///
+/// Unlike inter-layer aspects, contacts between the same planet in both charts
+/// (e.g. Sun-Sun) are included. Pairs are ordered by planet id for stable output.
+pub fn compute_cross_aspects(
+    layer_id_a: &str,
+    layer_id_b: &str,
+    positions_a: &LayerPositions,
+    positions_b: &LayerPositions,
+    settings: &AspectSettings,
+) -> AspectSet {
+    let calculator = AspectCalculator::new();
+    let planet_ids_a = sorted_planet_ids(positions_a, settings);
+    let planet_ids_b = sorted_planet_ids(positions_b, settings);
+
+    let mut pairs = Vec::new();
+    for p1_id in &planet_ids_a {
+        for p2_id in &planet_ids_b {
+            let p1_pos = &positions_a.planets[p1_id];
+            let p2_pos = &positions_b.planets[p2_id];
+
+            if let Some(aspect) = calculator.calculate_aspect(
+                p1_pos.lon,
+                p2_pos.lon,
+                p1_pos.speed_lon,
+                p2_pos.speed_lon,
+                &settings.orb_settings,
+            ) {
+                pairs.push(AspectPair {
+                    from: AspectObjectRef {
+                        layer_id: layer_id_a.to_string(),
+                        object_type: "planet".to_string(),
+                        object_id: p1_id.clone(),
+                    },
+                    to: AspectObjectRef {
+                        layer_id: layer_id_b.to_string(),
+                        object_type: "planet".to_string(),
+                        object_id: p2_id.clone(),
+                    },
+                    aspect,
+                });
+            }
+        }
+    }
+
+    AspectSet {
+        id: format!("{}:{}", layer_id_a, layer_id_b),
+        label: format!("{} / {} Synastry", layer_id_a, layer_id_b),
+        kind: "inter_layer".to_string(),
+        layer_ids: vec![layer_id_a.to_string(), layer_id_b.to_string()],
+        pairs,
+    }
+}
+
+/// Place the planets of one chart in the houses of another.
+///
+/// Returns an empty list when the house chart has no houses (no birth location).
+pub fn compute_house_overlays(
+    planet_layer_id: &str,
+    planet_positions: &LayerPositions,
+    house_layer_id: &str,
+    house_positions: &LayerPositions,
+    include_objects: &[String],
+) -> Vec<HouseOverlay> {
+    let Some(houses) = &house_positions.houses else {
+        return vec![];
+    };
+
+    let mut planet_ids: Vec<&String> = planet_positions
+        .planets
+        .keys()
+        .filter(|id| include_objects.is_empty() || include_objects.contains(id))
+        .collect();
+    planet_ids.sort();
+
+    planet_ids
+        .into_iter()
+        .filter_map(|planet_id| {
+            let lon = planet_positions.planets[planet_id].lon;
+            get_house_index(lon, &houses.cusps).map(|index| HouseOverlay {
+                planet_layer_id: planet_layer_id.to_string(),
+                planet_id: planet_id.clone(),
+                lon,
+                house_layer_id: house_layer_id.to_string(),
+                house: index + 1,
+            })
+        })
+        .collect()
+}
+
+/// Score a set of cross-aspects.
+///
+/// Each pair contributes `aspect weight * planet weights * tightness`, where
+/// tightness falls linearly from 1 at exact to 0 at the edge of the orb.
+pub fn score_compatibility(aspects: &AspectSet, orb_settings: &HashMap<String, f64>) -> CompatibilityScore {
+    let mut harmony = 0.0;
+    let mut tension = 0.0;
+    let mut by_aspect_type: HashMap<String, f64> = HashMap::new();
+
+    for pair in &aspects.pairs {
+        let aspect_type = pair.aspect.aspect_type.as_str();
+        let orb_limit = orb_settings.get(aspect_type).copied().unwrap_or(DEFAULT_ORB);
+        let tightness = if orb_limit > 0.0 {
+            (1.0 - pair.aspect.orb / orb_limit).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+
+        let contribution = aspect_weight(aspect_type)
+            * planet_weight(&pair.from.object_id)
+            * planet_weight(&pair.to.object_id)
+            * tightness;
+
+        if contribution >= 0.0 {
+            harmony += contribution;
+        } else {
+            tension += -contribution;
+        }
+        *by_aspect_type.entry(aspect_type.to_string()).or_insert(0.0) += contribution;
+    }
+
+    let total = harmony + tension;
+    let score = if total > 0.0 {
+        50.0 + 50.0 * (harmony - tension) / total
+    } else {
+        50.0
+    };
+
+    CompatibilityScore {
+        harmony,
+        tension,
+        score,
+        by_aspect_type,
+    }
+}
+
+/// Build a full synastry report between two charts
+pub fn compute_synastry(
+    layer_id_a: &str,
+    layer_id_b: &str,
+    positions_a: &LayerPositions,
+    positions_b: &LayerPositions,
+    settings: &AspectSettings,
+) -> SynastryReport {
+    let aspects = compute_cross_aspects(layer_id_a, layer_id_b, positions_a, positions_b, settings);
+
+    let mut house_overlays = compute_house_overlays(
+        layer_id_a,
+        positions_a,
+        layer_id_b,
+        positions_b,
+        &settings.include_objects,
+    );
+    house_overlays.extend(compute_house_overlays(
+        layer_id_b,
+        positions_b,
+        layer_id_a,
+        positions_a,
+        &settings.include_objects,
+    ));
+
+    let compatibility = score_compatibility(&aspects, &settings.orb_settings);
+
+    SynastryReport {
+        layer_ids: vec![layer_id_a.to_string(), layer_id_b.to_string()],
+        aspects,
+        house_overlays,
+        compatibility,
+    }
+}
+
+fn sorted_planet_ids(positions: &LayerPositions, settings: &AspectSettings) -> Vec<String> {
+    let mut ids: Vec<String> = positions
+        .planets
+        .keys()
+        .filter(|id| settings.include_objects.is_empty() || settings.include_objects.contains(id))
+        .cloned()
+        .collect();
+    ids.sort();
+    ids
+}
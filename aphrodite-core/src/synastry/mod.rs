@@ -0,0 +1,8 @@
+pub mod calculator;
+pub mod types;
+
+pub use calculator::{
+    aspect_weight, compute_cross_aspects, compute_house_overlays, compute_synastry, planet_weight,
+    score_compatibility,
+};
+pub use types::{CompatibilityScore, HouseOverlay, SynastryReport};
@@ -0,0 +1,45 @@
+//! Synastry report types.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::aspects::AspectSet;
+
+/// A planet from one chart falling in a house of the other chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HouseOverlay {
+    #[serde(rename = "planetLayerId")]
+    pub planet_layer_id: String,
+    #[serde(rename = "planetId")]
+    pub planet_id: String,
+    pub lon: f64,
+    #[serde(rename = "houseLayerId")]
+    pub house_layer_id: String,
+    pub house: u8, // 1..=12
+}
+
+/// Weighted compatibility score derived from the cross-aspects
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatibilityScore {
+    /// Sum of weighted harmonious contributions
+    pub harmony: f64,
+    /// Sum of weighted challenging contributions (positive number)
+    pub tension: f64,
+    /// Overall score from 0 (all tension) to 100 (all harmony), 50 when balanced or empty
+    pub score: f64,
+    /// Net weighted contribution per aspect type
+    #[serde(rename = "byAspectType")]
+    pub by_aspect_type: HashMap<String, f64>,
+}
+
+/// Complete synastry report between two charts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SynastryReport {
+    #[serde(rename = "layerIds")]
+    pub layer_ids: Vec<String>,
+    /// Cross-aspects between the two charts (including same-planet contacts)
+    pub aspects: AspectSet,
+    /// Planets of each chart placed in the other chart's houses
+    #[serde(rename = "houseOverlays")]
+    pub house_overlays: Vec<HouseOverlay>,
+    pub compatibility: CompatibilityScore,
+}
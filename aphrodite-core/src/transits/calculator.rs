@@ -0,0 +1,103 @@
+//! Transit timeline: transit aspects to a natal chart sampled once a day.
+
+use std::collections::BTreeMap;
+use chrono::{DateTime, Duration, Utc};
+use crate::aspects::{AspectCalculator, AspectSettings};
+use crate::ephemeris::types::LayerPositions;
+use crate::transits::types::{TransitContact, TransitTimeline, TransitTimelineDay, TransitTimelineRow};
+
+/// Sample transits from `start` (inclusive) to `end` (exclusive) once a day and collect the
+/// aspects they make to `natal`.
+///
+/// `positions_at` supplies the transiting positions for each day; its first error stops the
+/// scan. Natal angles take part when `settings.filter.include_angles` is set.
+pub fn compute_transit_timeline<F>(
+    natal: &LayerPositions,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    settings: &AspectSettings,
+    positions_at: F,
+) -> Result<TransitTimeline, String>
+where
+    F: FnMut(DateTime<Utc>) -> Result<LayerPositions, String>,
+{
+    let mut dates = Vec::new();
+    // Keyed by (transit, natal, aspect type) for the row order
+    let mut contacts: BTreeMap<(String, String, String), TransitTimelineRow> = BTreeMap::new();
+
+    compute_transit_timeline_each(natal, start, end, settings, positions_at, |day| {
+        for contact in day.contacts {
+            let key = (contact.transit_id, contact.natal_id, contact.aspect_type);
+            let row = contacts.entry(key.clone()).or_insert_with(|| TransitTimelineRow {
+                transit_id: key.0,
+                aspect_type: key.2,
+                natal_id: key.1,
+                natal_type: contact.natal_type,
+                orbs: Vec::new(),
+                peak_index: 0,
+            });
+            row.orbs.resize(dates.len(), None);
+            row.orbs.push(Some(contact.orb));
+        }
+        dates.push(day.date);
+    })?;
+
+    let rows = contacts
+        .into_values()
+        .map(|mut row| {
+            row.orbs.resize(dates.len(), None);
+            row.peak_index = row
+                .orbs
+                .iter()
+                .enumerate()
+                .filter_map(|(index, orb)| orb.map(|orb| (index, orb)))
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map_or(0, |(index, _)| index);
+            row
+        })
+        .collect();
+
+    Ok(TransitTimeline { dates, rows })
+}
+
+/// Like [`compute_transit_timeline`], but hands each day to `on_day` as soon as it is sampled.
+///
+/// Days arrive in chronological order; an error from `positions_at` stops the scan.
+pub fn compute_transit_timeline_each<F, D>(
+    natal: &LayerPositions,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    settings: &AspectSettings,
+    mut positions_at: F,
+    mut on_day: D,
+) -> Result<(), String>
+where
+    F: FnMut(DateTime<Utc>) -> Result<LayerPositions, String>,
+    D: FnMut(TransitTimelineDay),
+{
+    let calculator = AspectCalculator::new();
+
+    let mut moment = start;
+    while moment < end {
+        let transits = positions_at(moment)?;
+        let aspects = calculator.compute_transit_aspects("transit", "natal", &transits, natal, settings);
+        let mut contacts: Vec<TransitContact> = aspects
+            .pairs
+            .into_iter()
+            .map(|pair| TransitContact {
+                transit_id: pair.from.object_id,
+                aspect_type: pair.aspect.aspect_type,
+                natal_id: pair.to.object_id,
+                natal_type: pair.to.object_type,
+                orb: pair.aspect.orb,
+            })
+            .collect();
+        contacts.sort_by(|a, b| {
+            (&a.transit_id, &a.natal_id, &a.aspect_type).cmp(&(&b.transit_id, &b.natal_id, &b.aspect_type))
+        });
+        on_day(TransitTimelineDay { date: moment, contacts });
+        moment += Duration::days(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod calculator;
+pub mod types;
+
+pub use calculator::{compute_transit_timeline, compute_transit_timeline_each};
+pub use types::{TransitContact, TransitTimeline, TransitTimelineDay, TransitTimelineRow};
@@ -0,0 +1,55 @@
+//! Transit timeline types.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// One transit-to-natal contact followed across the timeline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitTimelineRow {
+    #[serde(rename = "transitId")]
+    pub transit_id: String,
+    #[serde(rename = "aspectType")]
+    pub aspect_type: String,
+    #[serde(rename = "natalId")]
+    pub natal_id: String,
+    /// `"planet"` or `"angle"`
+    #[serde(rename = "natalType")]
+    pub natal_type: String,
+    /// Orb on each day of the timeline, `None` when the aspect is not active
+    pub orbs: Vec<Option<f64>>,
+    /// Index of the day with the tightest orb
+    #[serde(rename = "peakIndex")]
+    pub peak_index: usize,
+}
+
+/// A transit-to-natal aspect active on one sampled day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransitContact {
+    #[serde(rename = "transitId")]
+    pub transit_id: String,
+    #[serde(rename = "aspectType")]
+    pub aspect_type: String,
+    #[serde(rename = "natalId")]
+    pub natal_id: String,
+    /// `"planet"` or `"angle"`
+    #[serde(rename = "natalType")]
+    pub natal_type: String,
+    pub orb: f64,
+}
+
+/// One sampled day of a transit timeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransitTimelineDay {
+    pub date: DateTime<Utc>,
+    /// Contacts active on this day, ordered by transit, natal point and aspect type
+    pub contacts: Vec<TransitContact>,
+}
+
+/// Day-by-day matrix of transit aspects to a natal chart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransitTimeline {
+    /// Sampled moments, one per day; the columns of every row
+    pub dates: Vec<DateTime<Utc>>,
+    /// Contacts active on at least one day, ordered by transit, natal point and aspect type
+    pub rows: Vec<TransitTimelineRow>,
+}
@@ -0,0 +1,215 @@
+//! Ashtakavarga (eightfold strength) for Vedic astrology.
+//!
+//! Each of the seven planets gets a bhinnashtakavarga: the eight references (the seven
+//! planets and the lagna) contribute a bindu to the signs at fixed places counted from
+//! themselves, following Brihat Parashara Hora Shastra. The sarvashtakavarga is the
+//! per-sign sum of the seven charts (337 bindus in total).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ephemeris::types::LayerPositions;
+
+/// Contributing references: the seven planets and the lagna
+const REFERENCES: &[&str] = &[
+    "sun", "moon", "mars", "mercury", "jupiter", "venus", "saturn", "lagna",
+];
+
+/// Benefic places (1-12, counted from each reference) per planet, in REFERENCES order
+const BENEFIC_PLACES: &[(&str, [&[u8]; 8])] = &[
+    ("sun", [
+        &[1, 2, 4, 7, 8, 9, 10, 11],
+        &[3, 6, 10, 11],
+        &[1, 2, 4, 7, 8, 9, 10, 11],
+        &[3, 5, 6, 9, 10, 11, 12],
+        &[5, 6, 9, 11],
+        &[6, 7, 12],
+        &[1, 2, 4, 7, 8, 9, 10, 11],
+        &[3, 4, 6, 10, 11, 12],
+    ]),
+    ("moon", [
+        &[3, 6, 7, 8, 10, 11],
+        &[1, 3, 6, 7, 10, 11],
+        &[2, 3, 5, 6, 9, 10, 11],
+        &[1, 3, 4, 5, 7, 8, 10, 11],
+        &[1, 4, 7, 8, 10, 11, 12],
+        &[3, 4, 5, 7, 9, 10, 11],
+        &[3, 5, 6, 11],
+        &[3, 6, 10, 11],
+    ]),
+    ("mars", [
+        &[3, 5, 6, 10, 11],
+        &[3, 6, 11],
+        &[1, 2, 4, 7, 8, 10, 11],
+        &[3, 5, 6, 11],
+        &[6, 10, 11, 12],
+        &[6, 8, 11, 12],
+        &[1, 4, 7, 8, 9, 10, 11],
+        &[1, 3, 6, 10, 11],
+    ]),
+    ("mercury", [
+        &[5, 6, 9, 11, 12],
+        &[2, 4, 6, 8, 10, 11],
+        &[1, 2, 4, 7, 8, 9, 10, 11],
+        &[1, 3, 5, 6, 9, 10, 11, 12],
+        &[6, 8, 11, 12],
+        &[1, 2, 3, 4, 5, 8, 9, 11],
+        &[1, 2, 4, 7, 8, 9, 10, 11],
+        &[1, 2, 4, 6, 8, 10, 11],
+    ]),
+    ("jupiter", [
+        &[1, 2, 3, 4, 7, 8, 9, 10, 11],
+        &[2, 5, 7, 9, 11],
+        &[1, 2, 4, 7, 8, 10, 11],
+        &[1, 2, 4, 5, 6, 9, 10, 11],
+        &[1, 2, 3, 4, 7, 8, 10, 11],
+        &[2, 5, 6, 9, 10, 11],
+        &[3, 5, 6, 12],
+        &[1, 2, 4, 5, 6, 7, 9, 10, 11],
+    ]),
+    ("venus", [
+        &[8, 11, 12],
+        &[1, 2, 3, 4, 5, 8, 9, 11, 12],
+        &[3, 5, 6, 9, 11, 12],
+        &[3, 5, 6, 9, 11],
+        &[5, 8, 9, 10, 11],
+        &[1, 2, 3, 4, 5, 8, 9, 10, 11],
+        &[3, 4, 5, 8, 9, 10, 11],
+        &[1, 2, 3, 4, 5, 8, 9, 11],
+    ]),
+    ("saturn", [
+        &[1, 2, 4, 7, 8, 10, 11],
+        &[3, 6, 11],
+        &[3, 5, 6, 10, 11, 12],
+        &[6, 8, 9, 10, 11, 12],
+        &[5, 6, 11, 12],
+        &[6, 11, 12],
+        &[3, 5, 6, 11],
+        &[1, 3, 4, 6, 10, 11],
+    ]),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bhinnashtakavarga {
+    pub planet: String,
+    /// Bindus per sign, Aries first
+    pub bindus: Vec<u8>,
+    pub total: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Ashtakavarga {
+    #[serde(rename = "layerId")]
+    pub layer_id: String,
+    pub bhinnashtakavarga: HashMap<String, Bhinnashtakavarga>,
+    /// Sum of the seven bhinnashtakavargas per sign, Aries first
+    pub sarvashtakavarga: Vec<u8>,
+    pub total: u32,
+}
+
+fn sign_index(longitude: f64) -> usize {
+    (longitude.rem_euclid(360.0) / 30.0) as usize % 12
+}
+
+/// Compute the ashtakavarga for a layer.
+///
+/// Needs the seven planets and the ascendant; returns an error naming what is missing.
+pub fn compute_ashtakavarga(layer_id: &str, positions: &LayerPositions) -> Result<Ashtakavarga, String> {
+    let lagna = positions
+        .houses
+        .as_ref()
+        .and_then(|h| h.angles.get("asc"))
+        .ok_or_else(|| "Ashtakavarga requires the ascendant (layer location)".to_string())?;
+
+    let mut reference_signs = Vec::with_capacity(REFERENCES.len());
+    for reference in REFERENCES {
+        let lon = if *reference == "lagna" {
+            *lagna
+        } else {
+            positions
+                .planets
+                .get(*reference)
+                .map(|p| p.lon)
+                .ok_or_else(|| format!("Ashtakavarga requires {} in the layer", reference))?
+        };
+        reference_signs.push(sign_index(lon));
+    }
+
+    let mut bhinnashtakavarga = HashMap::new();
+    let mut sarvashtakavarga = vec![0u8; 12];
+    for (planet, places_by_reference) in BENEFIC_PLACES {
+        let mut bindus = vec![0u8; 12];
+        for (reference_sign, places) in reference_signs.iter().zip(places_by_reference.iter()) {
+            for place in *places {
+                bindus[(reference_sign + *place as usize - 1) % 12] += 1;
+            }
+        }
+        for (total, bindu) in sarvashtakavarga.iter_mut().zip(&bindus) {
+            *total += bindu;
+        }
+        let total = bindus.iter().map(|b| *b as u32).sum();
+        bhinnashtakavarga.insert(planet.to_string(), Bhinnashtakavarga {
+            planet: planet.to_string(),
+            bindus,
+            total,
+        });
+    }
+
+    Ok(Ashtakavarga {
+        layer_id: layer_id.to_string(),
+        bhinnashtakavarga,
+        total: sarvashtakavarga.iter().map(|b| *b as u32).sum(),
+        sarvashtakavarga,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::{HousePositions, PlanetPosition};
+
+    fn layer(asc: Option<f64>) -> LayerPositions {
+        let planets = [
+            ("sun", 10.0), ("moon", 100.0), ("mars", 200.0), ("mercury", 25.0),
+            ("jupiter", 250.0), ("venus", 340.0), ("saturn", 290.0),
+        ]
+        .iter()
+        .map(|(id, lon)| (id.to_string(), PlanetPosition { lon: *lon, lat: 0.0, speed_lon: 1.0, retrograde: false }))
+        .collect();
+        let houses = asc.map(|asc| HousePositions {
+            system: "whole_sign".to_string(),
+            cusps: HashMap::new(),
+            angles: HashMap::from([("asc".to_string(), asc)]),
+        });
+        LayerPositions { planets, houses }
+    }
+
+    #[test]
+    fn test_bindu_totals() {
+        let result = compute_ashtakavarga("natal", &layer(Some(75.0))).unwrap();
+        // Fixed totals regardless of the chart
+        let expected = [
+            ("sun", 48), ("moon", 49), ("mars", 39), ("mercury", 54),
+            ("jupiter", 56), ("venus", 52), ("saturn", 39),
+        ];
+        for (planet, total) in expected {
+            assert_eq!(result.bhinnashtakavarga[planet].total, total, "{}", planet);
+            assert!(result.bhinnashtakavarga[planet].bindus.iter().all(|b| *b <= 8));
+        }
+        assert_eq!(result.total, 337);
+        assert_eq!(result.sarvashtakavarga.len(), 12);
+    }
+
+    #[test]
+    fn test_sun_bindus_from_own_sign() {
+        let result = compute_ashtakavarga("natal", &layer(Some(75.0))).unwrap();
+        // Aries is 1st from the Sun, 10th from the Moon (Cancer), 7th from Mars (Libra),
+        // 5th from Jupiter (Sagittarius), 4th from Saturn (Capricorn) and 11th from the
+        // lagna (Gemini); it is 1st from Mercury and 2nd from Venus, which give nothing
+        assert_eq!(result.bhinnashtakavarga["sun"].bindus[0], 6);
+    }
+
+    #[test]
+    fn test_requires_ascendant() {
+        assert!(compute_ashtakavarga("natal", &layer(None)).is_err());
+    }
+}
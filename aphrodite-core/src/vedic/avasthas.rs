@@ -0,0 +1,189 @@
+//! Avasthas (planetary states) for Vedic astrology.
+//!
+//! Baladi avasthas divide each sign into five 6° ages, counted forward in odd signs and
+//! backward in even signs. Jagradi avasthas follow the sign's relationship to the
+//! planet: awake in its own or exaltation sign, dreaming in a friend's or neutral sign,
+//! asleep in an enemy's or its debilitation sign (natural friendships). Planets are
+//! also flagged as combust (classical orbs from the Sun), retrograde and vargottama
+//! (same sign in the rasi and navamsa charts).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ephemeris::types::LayerPositions;
+use crate::vedic::nakshatra::{get_nakshatra_for_longitude, normalize_degrees};
+use crate::zodiac::{sign_index, SIGN_NAMES};
+
+/// Sign lords, Aries first
+const SIGN_LORDS: &[&str] = &[
+    "mars", "venus", "mercury", "moon", "sun", "mercury",
+    "venus", "mars", "jupiter", "saturn", "saturn", "jupiter",
+];
+
+/// Exaltation sign of each planet (debilitation is the opposite sign)
+const EXALTATION_SIGNS: &[(&str, usize)] = &[
+    ("sun", 0), ("moon", 1), ("mars", 9), ("mercury", 5),
+    ("jupiter", 3), ("venus", 11), ("saturn", 6),
+];
+
+/// Natural (naisargika) enemies of each planet
+const NATURAL_ENEMIES: &[(&str, &[&str])] = &[
+    ("sun", &["venus", "saturn"]),
+    ("moon", &[]),
+    ("mars", &["mercury"]),
+    ("mercury", &["moon"]),
+    ("jupiter", &["mercury", "venus"]),
+    ("venus", &["sun", "moon"]),
+    ("saturn", &["sun", "moon", "mars"]),
+];
+
+/// Combustion orbs (direct, retrograde) in degrees from the Sun
+const COMBUSTION_ORBS: &[(&str, f64, f64)] = &[
+    ("moon", 12.0, 12.0),
+    ("mars", 17.0, 17.0),
+    ("mercury", 14.0, 12.0),
+    ("jupiter", 11.0, 11.0),
+    ("venus", 10.0, 8.0),
+    ("saturn", 15.0, 15.0),
+];
+
+/// Baladi ages in odd-sign order
+const BALADI_STATES: &[&str] = &["bala", "kumara", "yuva", "vriddha", "mrita"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetAvastha {
+    /// "bala", "kumara", "yuva", "vriddha" or "mrita"
+    pub baladi: String,
+    /// "jagrat", "swapna" or "sushupti"
+    pub jagradi: String,
+    pub combust: bool,
+    pub retrograde: bool,
+    pub vargottama: bool,
+    #[serde(rename = "navamsaSign")]
+    pub navamsa_sign: String,
+}
+
+fn separation(a: f64, b: f64) -> f64 {
+    let diff = normalize_degrees(a - b);
+    diff.min(360.0 - diff)
+}
+
+/// Baladi avastha from the longitude
+pub fn baladi_avastha(longitude: f64) -> &'static str {
+    let lon = normalize_degrees(longitude);
+    let part = ((lon % 30.0) / 6.0) as usize % 5;
+    // Aries is the first (odd) sign
+    if sign_index(lon).is_multiple_of(2) {
+        BALADI_STATES[part]
+    } else {
+        BALADI_STATES[4 - part]
+    }
+}
+
+/// Jagradi avastha of a planet in a sign
+pub fn jagradi_avastha(planet_id: &str, sign: usize) -> &'static str {
+    let exaltation = EXALTATION_SIGNS.iter().find(|(id, _)| *id == planet_id).map(|(_, s)| *s);
+    let lord = SIGN_LORDS[sign % 12];
+    let is_enemy = NATURAL_ENEMIES
+        .iter()
+        .find(|(id, _)| *id == planet_id)
+        .is_some_and(|(_, enemies)| enemies.contains(&lord));
+
+    if lord == planet_id || exaltation == Some(sign) {
+        "jagrat"
+    } else if is_enemy || exaltation.map(|s| (s + 6) % 12) == Some(sign) {
+        "sushupti"
+    } else {
+        "swapna"
+    }
+}
+
+/// Compute avasthas for the seven planets in a layer
+pub fn compute_avasthas(positions: &LayerPositions) -> HashMap<String, PlanetAvastha> {
+    let sun_lon = positions.planets.get("sun").map(|sun| sun.lon);
+
+    positions
+        .planets
+        .iter()
+        .filter(|(planet_id, _)| EXALTATION_SIGNS.iter().any(|(id, _)| id == planet_id))
+        .map(|(planet_id, pos)| {
+            let sign = sign_index(pos.lon);
+            let navamsa_sign = get_nakshatra_for_longitude(pos.lon).navamsa_sign;
+            let combust = COMBUSTION_ORBS
+                .iter()
+                .find(|(id, _, _)| id == planet_id)
+                .zip(sun_lon)
+                .is_some_and(|((_, direct, retrograde), sun_lon)| {
+                    let orb = if pos.retrograde { *retrograde } else { *direct };
+                    separation(pos.lon, sun_lon) <= orb
+                });
+
+            let avastha = PlanetAvastha {
+                baladi: baladi_avastha(pos.lon).to_string(),
+                jagradi: jagradi_avastha(planet_id, sign).to_string(),
+                combust,
+                retrograde: pos.retrograde,
+                vargottama: navamsa_sign == SIGN_NAMES[sign],
+                navamsa_sign,
+            };
+            (planet_id.clone(), avastha)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::PlanetPosition;
+
+    fn layer(planets: &[(&str, f64, bool)]) -> LayerPositions {
+        let planets = planets
+            .iter()
+            .map(|(id, lon, retrograde)| {
+                let pos = PlanetPosition { lon: *lon, lat: 0.0, speed_lon: if *retrograde { -0.5 } else { 1.0 }, retrograde: *retrograde };
+                (id.to_string(), pos)
+            })
+            .collect();
+        LayerPositions { planets, houses: None }
+    }
+
+    #[test]
+    fn test_baladi_avastha() {
+        // Odd sign (Aries) counts forward, even sign (Taurus) backward
+        assert_eq!(baladi_avastha(2.0), "bala");
+        assert_eq!(baladi_avastha(15.0), "yuva");
+        assert_eq!(baladi_avastha(29.0), "mrita");
+        assert_eq!(baladi_avastha(32.0), "mrita");
+        assert_eq!(baladi_avastha(58.0), "bala");
+    }
+
+    #[test]
+    fn test_jagradi_avastha() {
+        assert_eq!(jagradi_avastha("sun", 4), "jagrat"); // own sign
+        assert_eq!(jagradi_avastha("sun", 0), "jagrat"); // exalted
+        assert_eq!(jagradi_avastha("sun", 6), "sushupti"); // debilitated
+        assert_eq!(jagradi_avastha("sun", 9), "sushupti"); // Saturn is an enemy
+        assert_eq!(jagradi_avastha("sun", 8), "swapna"); // Jupiter is a friend
+        assert_eq!(jagradi_avastha("sun", 2), "swapna"); // Mercury is neutral
+    }
+
+    #[test]
+    fn test_flags() {
+        let avasthas = compute_avasthas(&layer(&[
+            ("sun", 100.0, false),
+            ("mercury", 112.0, true),
+            ("venus", 91.0, true),
+            ("mars", 1.0, false),
+            ("north_node", 200.0, true),
+        ]));
+        // Retrograde Mercury uses the 12° orb
+        assert!(avasthas["mercury"].combust);
+        assert!(avasthas["mercury"].retrograde);
+        // Retrograde Venus uses the 8° orb
+        assert!(!avasthas["venus"].combust);
+        assert!(!avasthas["sun"].combust);
+        // Mars at 1 Aries: first pada of Ashwini, Aries navamsa
+        assert!(avasthas["mars"].vargottama);
+        assert!(!avasthas["mercury"].vargottama);
+        assert!(!avasthas.contains_key("north_node"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Bhava chalit (unequal house) placement for Vedic astrology.
+//!
+//! Each bhava is centred on its madhya (mid-point) and extends between the sandhis
+//! (junctions) halfway to the neighbouring madhyas. Sripati madhyas trisect the
+//! quadrants between the ascendant, IC, descendant and MC; alternatively the layer's
+//! own house cusps (e.g. Placidus) can be used as madhyas. Planets are reported with
+//! both their whole-sign rasi house and their bhava house.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ephemeris::types::LayerPositions;
+
+/// Supported madhya methods
+pub const BHAVA_METHODS: &[&str] = &["sripati", "house_cusps"];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BhavaPlacement {
+    /// Whole-sign house counted from the lagna sign (1-12)
+    #[serde(rename = "rasiHouse")]
+    pub rasi_house: u8,
+    /// Bhava chalit house (1-12)
+    #[serde(rename = "bhavaHouse")]
+    pub bhava_house: u8,
+    /// True when the bhava house differs from the rasi house
+    pub shifted: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BhavaChalit {
+    #[serde(rename = "layerId")]
+    pub layer_id: String,
+    pub method: String,
+    /// Bhava madhya longitudes, house 1 first
+    pub madhya: Vec<f64>,
+    /// Sandhi longitudes; sandhi[i] is where bhava i + 1 begins
+    pub sandhi: Vec<f64>,
+    pub placements: HashMap<String, BhavaPlacement>,
+}
+
+fn normalize(lon: f64) -> f64 {
+    lon.rem_euclid(360.0)
+}
+
+/// Forward (zodiacal) arc from a to b
+fn arc(a: f64, b: f64) -> f64 {
+    normalize(b - a)
+}
+
+/// Sripati bhava madhyas from the ascendant and MC
+pub fn sripati_madhya(asc: f64, mc: f64) -> Vec<f64> {
+    let ic = normalize(mc + 180.0);
+    let dsc = normalize(asc + 180.0);
+    // Quadrants in house order: ASC->IC (1-3), IC->DSC (4-6), DSC->MC (7-9), MC->ASC (10-12)
+    [asc, ic, dsc, mc]
+        .iter()
+        .zip([ic, dsc, mc, asc].iter())
+        .flat_map(|(start, end)| {
+            let third = arc(*start, *end) / 3.0;
+            (0..3).map(move |i| normalize(start + third * i as f64))
+        })
+        .collect()
+}
+
+/// Sandhis halfway between consecutive madhyas; sandhi[i] precedes madhya[i]
+pub fn sandhis(madhya: &[f64]) -> Vec<f64> {
+    let n = madhya.len();
+    (0..n)
+        .map(|i| {
+            let prev = madhya[(i + n - 1) % n];
+            normalize(prev + arc(prev, madhya[i]) / 2.0)
+        })
+        .collect()
+}
+
+/// Bhava (1-12) containing a longitude
+fn bhava_of(lon: f64, sandhi: &[f64]) -> u8 {
+    let n = sandhi.len();
+    (0..n)
+        .find(|&i| arc(sandhi[i], lon) < arc(sandhi[i], sandhi[(i + 1) % n]))
+        .map(|i| i as u8 + 1)
+        .unwrap_or(1)
+}
+
+/// Compute bhava chalit placements for a layer.
+///
+/// `method` is "sripati" (from the ascendant and MC) or "house_cusps" (the layer's own
+/// house cusps as madhyas). Returns None when the layer has no houses.
+pub fn compute_bhava_chalit(layer_id: &str, positions: &LayerPositions, method: &str) -> Option<BhavaChalit> {
+    let houses = positions.houses.as_ref()?;
+    let asc = *houses.angles.get("asc")?;
+
+    let madhya = match method {
+        "house_cusps" => (1..=12)
+            .map(|n| houses.cusps.get(&n.to_string()).copied().map(normalize))
+            .collect::<Option<Vec<f64>>>()?,
+        _ => sripati_madhya(asc, *houses.angles.get("mc")?),
+    };
+    let sandhi = sandhis(&madhya);
+
+    let lagna_sign = (normalize(asc) / 30.0) as u8;
+    let placements = positions
+        .planets
+        .iter()
+        .map(|(planet_id, pos)| {
+            let sign = (normalize(pos.lon) / 30.0) as u8 % 12;
+            let rasi_house = (sign + 12 - lagna_sign) % 12 + 1;
+            let bhava_house = bhava_of(normalize(pos.lon), &sandhi);
+            (planet_id.clone(), BhavaPlacement {
+                rasi_house,
+                bhava_house,
+                shifted: rasi_house != bhava_house,
+            })
+        })
+        .collect();
+
+    Some(BhavaChalit {
+        layer_id: layer_id.to_string(),
+        method: method.to_string(),
+        madhya,
+        sandhi,
+        placements,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::{HousePositions, PlanetPosition};
+
+    fn layer(asc: f64, mc: f64, planets: &[(&str, f64)]) -> LayerPositions {
+        let planets = planets
+            .iter()
+            .map(|(id, lon)| (id.to_string(), PlanetPosition { lon: *lon, lat: 0.0, speed_lon: 1.0, retrograde: false }))
+            .collect();
+        LayerPositions {
+            planets,
+            houses: Some(HousePositions {
+                system: "placidus".to_string(),
+                cusps: (1..=12).map(|n| (n.to_string(), normalize(asc + (n - 1) as f64 * 30.0))).collect(),
+                angles: HashMap::from([("asc".to_string(), asc), ("mc".to_string(), mc)]),
+            }),
+        }
+    }
+
+    #[test]
+    fn test_sripati_madhya() {
+        // ASC 0, MC 270: every quadrant is 90 degrees, so madhyas are 30 apart
+        let madhya = sripati_madhya(0.0, 270.0);
+        assert_eq!(madhya.len(), 12);
+        for (i, m) in madhya.iter().enumerate() {
+            assert!((m - i as f64 * 30.0).abs() < 1e-9);
+        }
+        let sandhi = sandhis(&madhya);
+        assert!((sandhi[0] - 345.0).abs() < 1e-9);
+        assert!((sandhi[1] - 15.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_planet_shifts_bhava() {
+        // Lagna at 20 Aries: a planet at 2 Aries is in the 1st rasi house but falls
+        // before the first sandhi (5 Aries), so it belongs to the 12th bhava
+        let result = compute_bhava_chalit("natal", &layer(20.0, 290.0, &[("sun", 2.0), ("moon", 25.0)]), "sripati").unwrap();
+        assert_eq!(result.placements["sun"].rasi_house, 1);
+        assert_eq!(result.placements["sun"].bhava_house, 12);
+        assert!(result.placements["sun"].shifted);
+        assert_eq!(result.placements["moon"].bhava_house, 1);
+        assert!(!result.placements["moon"].shifted);
+    }
+
+    #[test]
+    fn test_house_cusps_method() {
+        let result = compute_bhava_chalit("natal", &layer(20.0, 290.0, &[("sun", 2.0)]), "house_cusps").unwrap();
+        assert_eq!(result.madhya[0], 20.0);
+        assert_eq!(result.placements["sun"].bhava_house, 12);
+    }
+}
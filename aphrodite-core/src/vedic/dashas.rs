@@ -1,11 +1,13 @@
 //! Vimshottari and other dasha calculations for Vedic astrology.
 //! 
 //! Dashas are time periods ruled by planets, calculated based on the Moon's nakshatra.
+//! Chara dasha (Jaimini) is sign-based and calculated from the ascendant instead.
 
 use chrono::{DateTime, Utc, Duration};
 use serde::{Deserialize, Serialize};
-use crate::ephemeris::types::LayerPositions;
-use crate::vedic::nakshatra::get_nakshatra_for_longitude;
+use crate::ephemeris::types::{LayerPositions, NodeType};
+use crate::vedic::nakshatra::{get_nakshatra_for_longitude, graha_longitude};
+use crate::zodiac::{sign_index, SIGN_NAMES};
 
 pub const VIMSHOTTARI_TOTAL_YEARS: f64 = 120.0;
 pub const VIMSHOTTARI_YEAR_DAYS: f64 = 365.25; // Placeholder synodic year
@@ -41,6 +43,9 @@ const VIMSHOTTARI_SEQUENCE: &[PlanetYears] = &[
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DashaPeriod {
     pub planet: String,
+    /// Ruling sign for sign-based systems (chara dasha); `planet` is then the sign lord
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign: Option<String>,
     pub start: DateTime<Utc>,
     pub end: DateTime<Utc>,
     #[serde(rename = "durationDays")]
@@ -55,6 +60,9 @@ pub struct VimshottariResponse {
     pub depth: DashaLevel,
     #[serde(rename = "birthDateTime")]
     pub birth_date_time: DateTime<Utc>,
+    /// Lunar node the periods were calculated with, when known
+    #[serde(rename = "nodeType", default, skip_serializing_if = "Option::is_none")]
+    pub node_type: Option<NodeType>,
     pub periods: Vec<DashaPeriod>,
 }
 
@@ -63,9 +71,9 @@ pub fn compute_vimshottari_dasha(
     birth_datetime: DateTime<Utc>,
     layer_positions: &LayerPositions,
     depth: DashaLevel,
-) -> Result<Vec<DashaPeriod>, String> {
+) -> crate::Result<Vec<DashaPeriod>> {
     let moon = layer_positions.planets.get("moon")
-        .ok_or_else(|| "Moon position required for Vimshottari dasha calculation".to_string())?;
+        .ok_or_else(|| crate::Error::InvalidInput("Moon position required for Vimshottari dasha calculation".to_string()))?;
     
     let moon_meta = get_nakshatra_for_longitude(moon.lon);
     let moon_lord = moon_meta.base.lord.clone();
@@ -114,13 +122,14 @@ fn build_period(
     sequence_start_index: usize,
     sequence: &[PlanetYears],
     total_years: f64,
-) -> Result<DashaPeriod, String> {
+) -> crate::Result<DashaPeriod> {
     let duration_days = duration_years * VIMSHOTTARI_YEAR_DAYS;
     let end = start + Duration::days(duration_days as i64);
     let level = DEPTH_LEVELS[level_index.min(DEPTH_LEVELS.len() - 1)];
     
     let mut period = DashaPeriod {
         planet: planet.to_string(),
+        sign: None,
         start,
         end,
         duration_days,
@@ -154,10 +163,10 @@ fn build_period(
     Ok(period)
 }
 
-fn find_sequence_index(planet: &str, sequence: &[PlanetYears]) -> Result<usize, String> {
+fn find_sequence_index(planet: &str, sequence: &[PlanetYears]) -> crate::Result<usize> {
     sequence.iter()
         .position(|(p, _)| *p == planet)
-        .ok_or_else(|| format!("Planet '{}' not found in sequence", planet))
+        .ok_or_else(|| crate::Error::Calculation(format!("Planet '{}' not found in sequence", planet)))
 }
 
 // Yogini Dasha (8 years cycle)
@@ -197,9 +206,9 @@ pub fn compute_yogini_dasha(
     birth_datetime: DateTime<Utc>,
     layer_positions: &LayerPositions,
     depth: DashaLevel,
-) -> Result<Vec<DashaPeriod>, String> {
+) -> crate::Result<Vec<DashaPeriod>> {
     let moon = layer_positions.planets.get("moon")
-        .ok_or_else(|| "Moon position required for Yogini dasha calculation".to_string())?;
+        .ok_or_else(|| crate::Error::InvalidInput("Moon position required for Yogini dasha calculation".to_string()))?;
     
     let moon_meta = get_nakshatra_for_longitude(moon.lon);
     let nakshatra_index = moon_meta.base.index;
@@ -249,13 +258,14 @@ fn build_period_yogini(
     level_index: usize,
     target_depth_index: usize,
     sequence_start_index: usize,
-) -> Result<DashaPeriod, String> {
+) -> crate::Result<DashaPeriod> {
     let duration_days = duration_years * VIMSHOTTARI_YEAR_DAYS;
     let end = start + Duration::days(duration_days as i64);
     let level = DEPTH_LEVELS[level_index.min(DEPTH_LEVELS.len() - 1)];
     
     let mut period = DashaPeriod {
         planet: planet.to_string(),
+        sign: None,
         start,
         end,
         duration_days,
@@ -309,9 +319,9 @@ pub fn compute_ashtottari_dasha(
     birth_datetime: DateTime<Utc>,
     layer_positions: &LayerPositions,
     depth: DashaLevel,
-) -> Result<Vec<DashaPeriod>, String> {
+) -> crate::Result<Vec<DashaPeriod>> {
     let moon = layer_positions.planets.get("moon")
-        .ok_or_else(|| "Moon position required for Ashtottari dasha calculation".to_string())?;
+        .ok_or_else(|| crate::Error::InvalidInput("Moon position required for Ashtottari dasha calculation".to_string()))?;
     
     let moon_meta = get_nakshatra_for_longitude(moon.lon);
     let nakshatra_index = moon_meta.base.index;
@@ -365,9 +375,9 @@ pub fn compute_kalachakra_dasha(
     birth_datetime: DateTime<Utc>,
     layer_positions: &LayerPositions,
     depth: DashaLevel,
-) -> Result<Vec<DashaPeriod>, String> {
+) -> crate::Result<Vec<DashaPeriod>> {
     let moon = layer_positions.planets.get("moon")
-        .ok_or_else(|| "Moon position required for Kalachakra dasha calculation".to_string())?;
+        .ok_or_else(|| crate::Error::InvalidInput("Moon position required for Kalachakra dasha calculation".to_string()))?;
     
     let moon_meta = get_nakshatra_for_longitude(moon.lon);
     let moon_lord = moon_meta.base.lord.clone();
@@ -411,10 +421,205 @@ pub fn compute_kalachakra_dasha(
     Ok(periods)
 }
 
+// Chara Dasha (Jaimini, sign-based)
+
+/// Sign lords; Scorpio and Aquarius have a node as co-lord
+const CHARA_SIGN_LORDS: &[&[&str]] = &[
+    &["mars"], &["venus"], &["mercury"], &["moon"], &["sun"], &["mercury"],
+    &["venus"], &["mars", "ketu"], &["jupiter"], &["saturn"], &["saturn", "rahu"], &["jupiter"],
+];
+
+/// Exaltation sign of each planet (debilitation is the opposite sign)
+const CHARA_EXALTATION_SIGNS: &[(&str, usize)] = &[
+    ("sun", 0), ("moon", 1), ("mars", 9), ("mercury", 5),
+    ("jupiter", 3), ("venus", 11), ("saturn", 6),
+];
+
+/// Signs counted zodiacally (savya); the rest are counted in reverse
+fn is_savya_sign(sign: usize) -> bool {
+    matches!(sign % 12, 0 | 1 | 2 | 6 | 7 | 8)
+}
+
+fn step_sign(sign: usize, steps: usize, forward: bool) -> usize {
+    if forward { (sign + steps) % 12 } else { (sign + 12 * steps - steps) % 12 }
+}
+
+/// Sign of a planet, with the nodes mapped to the chart's lunar nodes
+fn chara_planet_sign(planet: &str, layer_positions: &LayerPositions) -> Option<usize> {
+    graha_longitude(planet, layer_positions).map(sign_index)
+}
+
+/// Stronger of two co-lords: one outside the sign beats one inside it, then the one
+/// with more planets in its sign, then the one further advanced in its sign
+fn stronger_lord<'a>(sign: usize, lords: &[&'a str], layer_positions: &LayerPositions) -> crate::Result<&'a str> {
+    let mut candidates = Vec::new();
+    for lord in lords {
+        let lord_sign = chara_planet_sign(lord, layer_positions)
+            .ok_or_else(|| crate::Error::InvalidInput(format!("{} position required for Chara dasha calculation", lord)))?;
+        let companions = layer_positions.planets.values().filter(|p| sign_index(p.lon) == lord_sign).count();
+        let degree = match *lord {
+            "rahu" | "ketu" => 0.0,
+            _ => layer_positions.planets.get(*lord).map(|p| p.lon.rem_euclid(30.0)).unwrap_or(0.0),
+        };
+        candidates.push((*lord, lord_sign != sign, companions, degree));
+    }
+    candidates.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then(b.2.cmp(&a.2))
+            .then(b.3.partial_cmp(&a.3).unwrap_or(std::cmp::Ordering::Equal))
+    });
+    Ok(candidates[0].0)
+}
+
+/// Chara dasha years of a sign: the count from the sign to its lord (forward for savya
+/// signs, backward otherwise) less one, 12 if the lord is in the sign, +1 if the lord is
+/// exalted and -1 if debilitated.
+pub fn chara_dasha_years(sign: usize, layer_positions: &LayerPositions) -> crate::Result<(String, f64)> {
+    let sign = sign % 12;
+    let lord = stronger_lord(sign, CHARA_SIGN_LORDS[sign], layer_positions)?;
+    let lord_sign = chara_planet_sign(lord, layer_positions)
+        .ok_or_else(|| crate::Error::InvalidInput(format!("{} position required for Chara dasha calculation", lord)))?;
+
+    let distance = if is_savya_sign(sign) {
+        (lord_sign + 12 - sign) % 12
+    } else {
+        (sign + 12 - lord_sign) % 12
+    };
+    let mut years = if distance == 0 { 12.0 } else { distance as f64 };
+
+    if let Some((_, exaltation)) = CHARA_EXALTATION_SIGNS.iter().find(|(planet, _)| *planet == lord) {
+        if lord_sign == *exaltation {
+            years += 1.0;
+        } else if lord_sign == (exaltation + 6) % 12 {
+            years -= 1.0;
+        }
+    }
+
+    Ok((lord.to_string(), years.max(1.0)))
+}
+
+/// Compute Jaimini Chara dasha periods from the ascendant.
+///
+/// Mahadashas run through the twelve signs from the lagna, zodiacally when the 9th sign
+/// from the lagna is savya and in reverse otherwise. Each mahadasha has twelve equal
+/// sub-periods starting from the next sign in the direction of the mahadasha sign, with
+/// the mahadasha sign itself last.
+pub fn compute_chara_dasha(
+    birth_datetime: DateTime<Utc>,
+    layer_positions: &LayerPositions,
+    depth: DashaLevel,
+) -> crate::Result<Vec<DashaPeriod>> {
+    let asc = layer_positions.houses.as_ref()
+        .and_then(|h| h.angles.get("asc"))
+        .ok_or_else(|| crate::Error::InvalidInput("Ascendant required for Chara dasha calculation".to_string()))?;
+    let lagna = sign_index(*asc);
+    let forward = is_savya_sign(lagna + 8);
+
+    let target_depth_index = DEPTH_LEVELS.iter().position(|&d| d == depth)
+        .unwrap_or(0);
+    let mut current_start = birth_datetime;
+    let mut periods: Vec<DashaPeriod> = Vec::new();
+
+    for offset in 0..12 {
+        let sign = step_sign(lagna, offset, forward);
+        let (_, years) = chara_dasha_years(sign, layer_positions)?;
+        let period = build_period_chara(sign, current_start, years, 0, target_depth_index, layer_positions)?;
+        current_start = period.end;
+        periods.push(period);
+    }
+
+    Ok(periods)
+}
+
+fn build_period_chara(
+    sign: usize,
+    start: DateTime<Utc>,
+    duration_years: f64,
+    level_index: usize,
+    target_depth_index: usize,
+    layer_positions: &LayerPositions,
+) -> crate::Result<DashaPeriod> {
+    let duration_days = duration_years * VIMSHOTTARI_YEAR_DAYS;
+    let end = start + Duration::days(duration_days as i64);
+    let level = DEPTH_LEVELS[level_index.min(DEPTH_LEVELS.len() - 1)];
+    let lord = stronger_lord(sign, CHARA_SIGN_LORDS[sign], layer_positions)?;
+
+    let mut period = DashaPeriod {
+        planet: lord.to_string(),
+        sign: Some(SIGN_NAMES[sign].to_string()),
+        start,
+        end,
+        duration_days,
+        level,
+        children: Vec::new(),
+    };
+
+    if level_index >= target_depth_index {
+        return Ok(period);
+    }
+
+    let forward = is_savya_sign(sign);
+    let mut child_start = start;
+    for offset in 1..=12 {
+        let child_sign = step_sign(sign, offset, forward);
+        let child_period = build_period_chara(
+            child_sign,
+            child_start,
+            duration_years / 12.0,
+            level_index + 1,
+            target_depth_index,
+            layer_positions,
+        )?;
+        child_start = child_period.end;
+        period.children.push(child_period);
+    }
+
+    Ok(period)
+}
+
+/// Dasha systems accepted by `compute_dasha`
+pub const DASHA_SYSTEMS: &[&str] = &["vimshottari", "yogini", "ashtottari", "kalachakra", "chara"];
+
+/// Compute dasha periods for a system named in `DASHA_SYSTEMS`.
+pub fn compute_dasha(
+    system: &str,
+    birth_datetime: DateTime<Utc>,
+    layer_positions: &LayerPositions,
+    depth: DashaLevel,
+) -> crate::Result<Vec<DashaPeriod>> {
+    match system {
+        "vimshottari" => compute_vimshottari_dasha(birth_datetime, layer_positions, depth),
+        "yogini" => compute_yogini_dasha(birth_datetime, layer_positions, depth),
+        "ashtottari" => compute_ashtottari_dasha(birth_datetime, layer_positions, depth),
+        "kalachakra" => compute_kalachakra_dasha(birth_datetime, layer_positions, depth),
+        "chara" => compute_chara_dasha(birth_datetime, layer_positions, depth),
+        other => Err(crate::Error::InvalidInput(format!("Unknown dasha system: {}", other))),
+    }
+}
+
+/// Periods overlapping the window from `from` up to `to`, with their sub-periods trimmed
+/// the same way; an omitted bound leaves that side open. Periods are kept whole, so the
+/// first and last may start before `from` or end after `to`.
+pub fn filter_dasha_periods(
+    periods: Vec<DashaPeriod>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+) -> Vec<DashaPeriod> {
+    periods
+        .into_iter()
+        .filter(|period| from.is_none_or(|from| period.end > from) && to.is_none_or(|to| period.start < to))
+        .map(|mut period| {
+            period.children = filter_dasha_periods(std::mem::take(&mut period.children), from, to);
+            period
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ephemeris::types::{PlanetPosition, LayerPositions};
+    use chrono::TimeZone;
     use std::collections::HashMap;
     
     #[test]
@@ -445,5 +650,116 @@ mod tests {
         assert_eq!(periods.len(), 9);
         assert_eq!(periods[0].planet, "ketu");
     }
-}
 
+    #[test]
+    fn test_filter_dasha_periods() {
+        let layer_positions = LayerPositions {
+            planets: HashMap::from([(
+                "moon".to_string(),
+                PlanetPosition { lon: 13.33, lat: 0.0, speed_lon: 0.0, retrograde: false },
+            )]),
+            houses: None,
+        };
+        let birth = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+        let periods = compute_vimshottari_dasha(birth, &layer_positions, DashaLevel::Antardasha).unwrap();
+
+        let from = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let to = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let window = filter_dasha_periods(periods.clone(), Some(from), Some(to));
+        assert!(!window.is_empty() && window.len() < periods.len());
+        for period in &window {
+            assert!(period.end > from && period.start < to);
+            assert!(!period.children.is_empty() && period.children.len() < 9);
+            assert!(period.children.iter().all(|child| child.end > from && child.start < to));
+        }
+
+        let open_ended = filter_dasha_periods(periods.clone(), Some(from), None);
+        assert_eq!(open_ended.last().unwrap().end, periods.last().unwrap().end);
+        assert!(filter_dasha_periods(periods, None, Some(birth)).is_empty());
+    }
+    
+    fn chara_layer(asc: f64, planets: &[(&str, f64)]) -> LayerPositions {
+        let planets = planets
+            .iter()
+            .map(|(id, lon)| (id.to_string(), PlanetPosition { lon: *lon, lat: 0.0, speed_lon: 1.0, retrograde: false }))
+            .collect();
+        LayerPositions {
+            planets,
+            houses: Some(crate::ephemeris::types::HousePositions {
+                system: "whole_sign".to_string(),
+                cusps: HashMap::new(),
+                angles: HashMap::from([("asc".to_string(), asc)]),
+            }),
+        }
+    }
+    
+    #[test]
+    fn test_chara_dasha_years() {
+        let layer = chara_layer(5.0, &[
+            ("sun", 130.0), ("moon", 100.0), ("mars", 95.0), ("mercury", 160.0),
+            ("jupiter", 250.0), ("venus", 40.0), ("saturn", 290.0), ("north_node", 20.0),
+        ]);
+        // Aries (savya): Mars in Cancer is the 4th sign -> 3 years, debilitated -> 2
+        assert_eq!(chara_dasha_years(0, &layer).unwrap(), ("mars".to_string(), 2.0));
+        // Taurus: Venus in its own sign -> 12 years
+        assert_eq!(chara_dasha_years(1, &layer).unwrap().1, 12.0);
+        // Virgo: Mercury in its own (and exaltation) sign -> 12 + 1
+        assert_eq!(chara_dasha_years(5, &layer).unwrap().1, 13.0);
+        // Cancer (apasavya): Moon in its own sign -> 12
+        assert_eq!(chara_dasha_years(3, &layer).unwrap().1, 12.0);
+        // Leo (apasavya): counting backward from Leo to the Sun in Leo -> 12
+        assert_eq!(chara_dasha_years(4, &layer).unwrap().1, 12.0);
+        // Capricorn (apasavya): Saturn in Capricorn -> 12
+        assert_eq!(chara_dasha_years(9, &layer).unwrap().1, 12.0);
+        // Pisces (apasavya): Jupiter in Sagittarius, counted backward is the 4th -> 3
+        assert_eq!(chara_dasha_years(11, &layer).unwrap().1, 3.0);
+    }
+    
+    #[test]
+    fn test_compute_chara_dasha() {
+        let layer = chara_layer(5.0, &[
+            ("sun", 130.0), ("moon", 100.0), ("mars", 95.0), ("mercury", 160.0),
+            ("jupiter", 250.0), ("venus", 40.0), ("saturn", 290.0), ("north_node", 20.0),
+        ]);
+        let birth = Utc::now();
+        let periods = compute_chara_dasha(birth, &layer, DashaLevel::Antardasha).unwrap();
+        assert_eq!(periods.len(), 12);
+        // Aries lagna: 9th is Sagittarius (savya), so the sequence runs zodiacally
+        assert_eq!(periods[0].sign.as_deref(), Some("aries"));
+        assert_eq!(periods[1].sign.as_deref(), Some("taurus"));
+        assert_eq!(periods[0].start, birth);
+        // Sub-periods start from the next sign and end with the mahadasha sign
+        let children = &periods[0].children;
+        assert_eq!(children.len(), 12);
+        assert_eq!(children[0].sign.as_deref(), Some("taurus"));
+        assert_eq!(children[11].sign.as_deref(), Some("aries"));
+    }
+    
+    #[test]
+    fn test_chara_dasha_requires_ascendant() {
+        let mut layer = chara_layer(5.0, &[("moon", 100.0)]);
+        layer.houses = None;
+        assert!(compute_chara_dasha(Utc::now(), &layer, DashaLevel::Mahadasha).is_err());
+    }
+
+    #[test]
+    fn test_compute_dasha_by_name() {
+        let layer = chara_layer(5.0, &[
+            ("sun", 130.0), ("moon", 13.33), ("mars", 95.0), ("mercury", 160.0),
+            ("jupiter", 250.0), ("venus", 40.0), ("saturn", 290.0), ("north_node", 20.0),
+        ]);
+        let birth = Utc::now();
+        let periods = compute_dasha("vimshottari", birth, &layer, DashaLevel::Mahadasha).unwrap();
+        assert_eq!(periods[0].planet, "ketu");
+        for system in DASHA_SYSTEMS {
+            assert!(compute_dasha(system, birth, &layer, DashaLevel::Mahadasha).is_ok(), "{}", system);
+        }
+        let error = compute_dasha("solar", birth, &layer, DashaLevel::Mahadasha).unwrap_err();
+        assert_eq!(error.category(), crate::ErrorCategory::InvalidInput);
+
+        // A missing position is the caller's input, not a failed calculation
+        let no_moon = chara_layer(5.0, &[("sun", 130.0)]);
+        let error = compute_dasha("yogini", birth, &no_moon, DashaLevel::Mahadasha).unwrap_err();
+        assert_eq!(error.code(), "INVALID_INPUT");
+    }
+}
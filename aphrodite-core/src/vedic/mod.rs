@@ -2,11 +2,23 @@ pub mod nakshatra;
 pub mod vargas;
 pub mod dashas;
 pub mod yogas;
+pub mod tarabala;
+pub mod ashtakavarga;
+pub mod bhava;
+pub mod avasthas;
+pub mod panchanga;
+pub mod muhurta;
 pub mod types;
 
 pub use types::{VedicLayerData, VedicPayload, NakshatraLayer};
 pub use nakshatra::{NakshatraPlacement, annotate_layer_nakshatras};
-pub use vargas::{VargaLayer, VargaPlanetPosition, build_varga_layers};
-pub use dashas::{DashaPeriod, DashaLevel, VimshottariResponse, compute_vimshottari_dasha, compute_yogini_dasha, compute_ashtottari_dasha, compute_kalachakra_dasha};
+pub use vargas::{VargaLayer, VargaPlanetPosition, build_varga_layers, resolve_varga_id};
+pub use dashas::{DashaPeriod, DashaLevel, VimshottariResponse, DASHA_SYSTEMS, compute_dasha, filter_dasha_periods, compute_vimshottari_dasha, compute_yogini_dasha, compute_ashtottari_dasha, compute_kalachakra_dasha, compute_chara_dasha};
 pub use yogas::{Yoga, identify_yogas};
+pub use ashtakavarga::{Ashtakavarga, Bhinnashtakavarga, compute_ashtakavarga};
+pub use avasthas::{PlanetAvastha, compute_avasthas};
+pub use bhava::{BhavaChalit, BhavaPlacement, BHAVA_METHODS, compute_bhava_chalit};
+pub use panchanga::{InauspiciousPeriods, Panchanga, SolarDay, TimeWindow, compute_panchanga, inauspicious_periods, solar_day, vedic_day};
+pub use muhurta::{MuhurtaConstraints, MuhurtaWindow, evaluate_moment, search_muhurta, search_muhurta_each};
+pub use tarabala::{ChandraBala, DailyStrength, TaraBala, compute_chandrabala, compute_daily_strength, compute_tarabala};
 
@@ -0,0 +1,305 @@
+//! Muhurta (electional) window search.
+//!
+//! A date range is sampled at a fixed step; each moment is checked against the
+//! constraints using the panchanga, the daily inauspicious periods, the rising sign
+//! (lagna) and, when a natal Moon is given, the transit Moon's tarabala and
+//! chandrabala (gochara). Consecutive passing samples are merged into windows.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use crate::ephemeris::types::{GeoLocation, LayerPositions};
+use crate::vedic::panchanga::{compute_panchanga, inauspicious_periods, vedic_day, weekday_index, Panchanga};
+use crate::vedic::tarabala::{compute_chandrabala, compute_tarabala};
+use crate::zodiac::sign_name;
+
+/// Constraints a moment must satisfy; empty lists place no restriction
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MuhurtaConstraints {
+    #[serde(rename = "avoidRahuKala")]
+    pub avoid_rahu_kala: bool,
+    #[serde(rename = "avoidYamaganda")]
+    pub avoid_yamaganda: bool,
+    #[serde(rename = "avoidGulika")]
+    pub avoid_gulika: bool,
+    /// Allowed tithis (1-30)
+    pub tithis: Vec<u8>,
+    #[serde(rename = "avoidTithis")]
+    pub avoid_tithis: Vec<u8>,
+    /// Allowed Moon nakshatras (ids such as "rohini")
+    pub nakshatras: Vec<String>,
+    #[serde(rename = "avoidNakshatras")]
+    pub avoid_nakshatras: Vec<String>,
+    /// Allowed weekdays ("sunday".."saturday")
+    pub weekdays: Vec<String>,
+    /// Allowed rising signs ("aries".."pisces")
+    pub lagnas: Vec<String>,
+    /// Require a favorable tara from the natal Moon
+    #[serde(rename = "requireTarabala")]
+    pub require_tarabala: bool,
+    /// Require favorable chandrabala from the natal Moon
+    #[serde(rename = "requireChandrabala")]
+    pub require_chandrabala: bool,
+    #[serde(rename = "avoidVishti")]
+    pub avoid_vishti: bool,
+}
+
+impl MuhurtaConstraints {
+    /// Whether the constraints need a natal Moon
+    pub fn needs_natal_moon(&self) -> bool {
+        self.require_tarabala || self.require_chandrabala
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MuhurtaWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Panchanga at the start of the window
+    pub panchanga: Panchanga,
+    /// Rising sign at the start of the window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lagna: Option<String>,
+}
+
+/// Check a single moment; returns its panchanga and lagna when all constraints pass
+pub fn evaluate_moment(
+    datetime: DateTime<Utc>,
+    positions: &LayerPositions,
+    location: &GeoLocation,
+    constraints: &MuhurtaConstraints,
+    natal_moon_lon: Option<f64>,
+) -> Result<Option<(Panchanga, Option<String>)>, String> {
+    let sun = positions.planets.get("sun").ok_or("Muhurta requires the Sun position")?;
+    let moon = positions.planets.get("moon").ok_or("Muhurta requires the Moon position")?;
+
+    let (date, solar_day) = vedic_day(datetime, location.lat, location.lon);
+    let weekday = weekday_index(date);
+    let panchanga = compute_panchanga(sun.lon, moon.lon, weekday);
+    let lagna = positions
+        .houses
+        .as_ref()
+        .and_then(|h| h.angles.get("asc"))
+        .map(|asc| sign_name(*asc).to_string());
+
+    if let Some(day) = solar_day {
+        let periods = inauspicious_periods(&day, weekday);
+        if (constraints.avoid_rahu_kala && periods.rahu_kala.contains(datetime))
+            || (constraints.avoid_yamaganda && periods.yamaganda.contains(datetime))
+            || (constraints.avoid_gulika && periods.gulika.contains(datetime))
+        {
+            return Ok(None);
+        }
+    }
+
+    let allowed = |list: &[String], value: &str| list.is_empty() || list.iter().any(|v| v == value);
+    let passes_panchanga = (constraints.tithis.is_empty() || constraints.tithis.contains(&panchanga.tithi))
+        && !constraints.avoid_tithis.contains(&panchanga.tithi)
+        && allowed(&constraints.nakshatras, &panchanga.nakshatra)
+        && !constraints.avoid_nakshatras.contains(&panchanga.nakshatra)
+        && allowed(&constraints.weekdays, &panchanga.vara)
+        && !(constraints.avoid_vishti && panchanga.karana == "vishti");
+    if !passes_panchanga {
+        return Ok(None);
+    }
+
+    if !constraints.lagnas.is_empty() {
+        let lagna = lagna.as_deref().ok_or("Lagna constraints require the ascendant")?;
+        if !allowed(&constraints.lagnas, lagna) {
+            return Ok(None);
+        }
+    }
+
+    if constraints.needs_natal_moon() {
+        let natal_moon = natal_moon_lon.ok_or("Tarabala and chandrabala require the natal Moon")?;
+        if (constraints.require_tarabala && !compute_tarabala(natal_moon, moon.lon).favorable)
+            || (constraints.require_chandrabala && !compute_chandrabala(natal_moon, moon.lon).favorable)
+        {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some((panchanga, lagna)))
+}
+
+/// Search `[start, end)` for windows satisfying the constraints.
+///
+/// `positions_at` supplies sidereal positions (with houses for lagna constraints) for a
+/// moment. Window boundaries are accurate to the sampling step.
+pub fn search_muhurta<F>(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    step: Duration,
+    location: &GeoLocation,
+    constraints: &MuhurtaConstraints,
+    natal_moon_lon: Option<f64>,
+    positions_at: F,
+) -> Result<Vec<MuhurtaWindow>, String>
+where
+    F: FnMut(DateTime<Utc>) -> Result<LayerPositions, String>,
+{
+    let mut windows = Vec::new();
+    search_muhurta_each(start, end, step, location, constraints, natal_moon_lon, positions_at, |window| {
+        windows.push(window)
+    })?;
+    Ok(windows)
+}
+
+/// Like [`search_muhurta`], but hands each window to `on_window` as soon as it closes.
+///
+/// Windows arrive in chronological order; an error from `positions_at` stops the scan.
+#[allow(clippy::too_many_arguments)]
+pub fn search_muhurta_each<F, W>(
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    step: Duration,
+    location: &GeoLocation,
+    constraints: &MuhurtaConstraints,
+    natal_moon_lon: Option<f64>,
+    mut positions_at: F,
+    mut on_window: W,
+) -> Result<(), String>
+where
+    F: FnMut(DateTime<Utc>) -> Result<LayerPositions, String>,
+    W: FnMut(MuhurtaWindow),
+{
+    if step <= Duration::zero() {
+        return Err("Muhurta step must be positive".to_string());
+    }
+
+    let mut open: Option<MuhurtaWindow> = None;
+    let mut moment = start;
+    while moment < end {
+        let positions = positions_at(moment)?;
+        match evaluate_moment(moment, &positions, location, constraints, natal_moon_lon)? {
+            Some((panchanga, lagna)) => {
+                if open.is_none() {
+                    open = Some(MuhurtaWindow { start: moment, end: moment, panchanga, lagna });
+                }
+            }
+            None => {
+                if let Some(mut window) = open.take() {
+                    window.end = moment;
+                    on_window(window);
+                }
+            }
+        }
+        moment += step;
+    }
+    if let Some(mut window) = open {
+        window.end = end;
+        on_window(window);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::{HousePositions, PlanetPosition};
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+
+    /// Sun fixed at 0 Aries, Moon moving 0.5° per hour from 0 Aries
+    fn positions(start: DateTime<Utc>, moment: DateTime<Utc>) -> LayerPositions {
+        let hours = (moment - start).num_minutes() as f64 / 60.0;
+        let planets = [("sun", 0.0), ("moon", hours * 0.5)]
+            .iter()
+            .map(|(id, lon)| (id.to_string(), PlanetPosition { lon: *lon, lat: 0.0, speed_lon: 1.0, retrograde: false }))
+            .collect();
+        LayerPositions {
+            planets,
+            houses: Some(HousePositions {
+                system: "whole_sign".to_string(),
+                cusps: HashMap::new(),
+                angles: HashMap::from([("asc".to_string(), (hours * 15.0) % 360.0)]),
+            }),
+        }
+    }
+
+    fn location() -> GeoLocation {
+        GeoLocation { lat: 0.0, lon: 0.0 }
+    }
+
+    #[test]
+    fn test_tithi_window() {
+        // Tithi 2 spans elongation 12-24°, i.e. hours 24-48 of the synthetic motion
+        let start = Utc.with_ymd_and_hms(2024, 3, 18, 0, 0, 0).unwrap();
+        let end = start + Duration::days(3);
+        let constraints = MuhurtaConstraints { tithis: vec![2], ..Default::default() };
+        let windows = search_muhurta(start, end, Duration::hours(1), &location(), &constraints, None, |m| {
+            Ok(positions(start, m))
+        })
+        .unwrap();
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].start, start + Duration::hours(24));
+        assert_eq!(windows[0].end, start + Duration::hours(48));
+        assert_eq!(windows[0].panchanga.tithi, 2);
+    }
+
+    #[test]
+    fn test_avoid_rahu_kala_splits_day() {
+        // Monday: rahu kala is the second eighth of the daytime
+        let start = Utc.with_ymd_and_hms(2024, 3, 18, 6, 30, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 3, 18, 18, 0, 0).unwrap();
+        let constraints = MuhurtaConstraints { avoid_rahu_kala: true, ..Default::default() };
+        let windows = search_muhurta(start, end, Duration::minutes(15), &location(), &constraints, None, |m| {
+            Ok(positions(start, m))
+        })
+        .unwrap();
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].panchanga.vara, "monday");
+        assert!(windows[0].end < windows[1].start);
+        assert_eq!(windows[1].end, end);
+    }
+
+    #[test]
+    fn test_windows_emitted_before_scan_ends() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 18, 6, 30, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 3, 18, 18, 0, 0).unwrap();
+        let constraints = MuhurtaConstraints { avoid_rahu_kala: true, ..Default::default() };
+        let sampled = std::cell::Cell::new(start);
+        let mut emitted = Vec::new();
+        search_muhurta_each(
+            start,
+            end,
+            Duration::minutes(15),
+            &location(),
+            &constraints,
+            None,
+            |m| {
+                sampled.set(m);
+                Ok(positions(start, m))
+            },
+            |window| emitted.push((window.start, window.end, sampled.get())),
+        )
+        .unwrap();
+        // The first window is handed over as soon as rahu kala begins, mid-scan
+        assert_eq!(emitted.len(), 2);
+        assert_eq!(emitted[0].1, emitted[0].2);
+        assert!(emitted[0].2 < end);
+        assert_eq!(emitted[1].1, end);
+    }
+
+    #[test]
+    fn test_lagna_and_natal_moon_constraints() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 18, 0, 0, 0).unwrap();
+        let constraints = MuhurtaConstraints { lagnas: vec!["taurus".to_string()], ..Default::default() };
+        let windows = search_muhurta(start, start + Duration::hours(12), Duration::hours(1), &location(), &constraints, None, |m| {
+            Ok(positions(start, m))
+        })
+        .unwrap();
+        // Ascendant moves 15° per hour: Taurus rises during hours 2-4
+        assert_eq!(windows.len(), 1);
+        assert_eq!(windows[0].start, start + Duration::hours(2));
+        assert_eq!(windows[0].end, start + Duration::hours(4));
+        assert_eq!(windows[0].lagna.as_deref(), Some("taurus"));
+
+        let constraints = MuhurtaConstraints { require_tarabala: true, ..Default::default() };
+        let result = search_muhurta(start, start + Duration::hours(2), Duration::hours(1), &location(), &constraints, None, |m| {
+            Ok(positions(start, m))
+        });
+        assert!(result.is_err());
+    }
+}
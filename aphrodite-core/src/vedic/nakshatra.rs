@@ -5,7 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::ephemeris::types::LayerPositions;
+use crate::ephemeris::types::{LayerPositions, ANGLE_IDS};
+use crate::zodiac::SIGN_NAMES;
 
 pub const NAKSHATRA_SEGMENT_SIZE: f64 = 360.0 / 27.0;
 pub const PADA_SIZE: f64 = NAKSHATRA_SEGMENT_SIZE / 4.0;
@@ -41,11 +42,44 @@ pub const NAKSHATRA_ORDER: &[(&str, &str, &str)] = &[
     ("revati", "Revati", "mercury"),
 ];
 
+// (presiding deity, gana) in NAKSHATRA_ORDER order
+pub const NAKSHATRA_DETAILS: &[(&str, &str)] = &[
+    ("Ashwini Kumaras", "deva"),
+    ("Yama", "manushya"),
+    ("Agni", "rakshasa"),
+    ("Brahma", "manushya"),
+    ("Soma", "deva"),
+    ("Rudra", "manushya"),
+    ("Aditi", "deva"),
+    ("Brihaspati", "deva"),
+    ("Nagas", "rakshasa"),
+    ("Pitris", "rakshasa"),
+    ("Bhaga", "manushya"),
+    ("Aryaman", "manushya"),
+    ("Savitr", "deva"),
+    ("Vishvakarma", "rakshasa"),
+    ("Vayu", "deva"),
+    ("Indragni", "rakshasa"),
+    ("Mitra", "deva"),
+    ("Indra", "rakshasa"),
+    ("Nirriti", "rakshasa"),
+    ("Apas", "manushya"),
+    ("Vishvedevas", "manushya"),
+    ("Vishnu", "deva"),
+    ("Vasus", "rakshasa"),
+    ("Varuna", "rakshasa"),
+    ("Aja Ekapada", "manushya"),
+    ("Ahir Budhnya", "manushya"),
+    ("Pushan", "deva"),
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BaseNakshatraRecord {
     pub id: String,
     pub name: String,
     pub lord: String,
+    pub deity: String,
+    pub gana: String,
     pub start: f64,
     pub end: f64,
     pub index: usize,
@@ -59,6 +93,8 @@ pub struct NakshatraMetadata {
     pub progress: f64,
     pub pada: i32,
     pub pada_fraction: f64,
+    /// Navamsa (D9) sign of the pada
+    pub navamsa_sign: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,20 +111,28 @@ pub struct NakshatraPlacement {
     #[serde(rename = "endDegree")]
     pub end_degree: f64,
     pub lord: String,
+    pub deity: String,
+    /// "deva", "manushya" or "rakshasa"
+    pub gana: String,
     pub pada: i32,
     #[serde(rename = "padaFraction")]
     pub pada_fraction: f64,
+    /// Navamsa (D9) sign of the pada
+    #[serde(rename = "navamsaSign")]
+    pub navamsa_sign: String,
 }
 
 fn build_nakshatra_table() -> Vec<BaseNakshatraRecord> {
     let mut table = Vec::new();
-    for (idx, (slug, display_name, lord)) in NAKSHATRA_ORDER.iter().enumerate() {
+    for (idx, ((slug, display_name, lord), (deity, gana))) in NAKSHATRA_ORDER.iter().zip(NAKSHATRA_DETAILS).enumerate() {
         let start = idx as f64 * NAKSHATRA_SEGMENT_SIZE;
         let end = start + NAKSHATRA_SEGMENT_SIZE;
         table.push(BaseNakshatraRecord {
             id: slug.to_string(),
             name: display_name.to_string(),
             lord: lord.to_string(),
+            deity: deity.to_string(),
+            gana: gana.to_string(),
             start,
             end,
             index: idx,
@@ -110,6 +154,20 @@ pub fn normalize_degrees(value: f64) -> f64 {
     normalized
 }
 
+/// Longitude of a graha, with Rahu and Ketu read from the layer's lunar nodes; Ketu
+/// falls back to the point opposite the north node when the south node was not calculated.
+pub fn graha_longitude(graha: &str, layer_positions: &LayerPositions) -> Option<f64> {
+    let planets = &layer_positions.planets;
+    match graha {
+        "rahu" => planets.get("north_node").map(|p| p.lon),
+        "ketu" => planets
+            .get("south_node")
+            .map(|p| p.lon)
+            .or_else(|| planets.get("north_node").map(|p| p.lon + 180.0)),
+        _ => planets.get(graha).map(|p| p.lon),
+    }
+}
+
 /// Return metadata for the nakshatra containing the given longitude.
 /// 
 /// Returns a struct containing id, name, lord, index, start/end degrees,
@@ -123,6 +181,8 @@ pub fn get_nakshatra_for_longitude(longitude: f64) -> NakshatraMetadata {
     let pada = (offset / PADA_SIZE) as i32 + 1;
     let pada_offset = offset - ((pada - 1) as f64 * PADA_SIZE);
     let pada_fraction = pada_offset / PADA_SIZE;
+    // The 108 padas run through the signs from Aries, twelve to a cycle
+    let navamsa_sign = SIGN_NAMES[(lon / PADA_SIZE) as usize % 12];
     
     NakshatraMetadata {
         base: entry.clone(),
@@ -130,6 +190,7 @@ pub fn get_nakshatra_for_longitude(longitude: f64) -> NakshatraMetadata {
         progress: offset / NAKSHATRA_SEGMENT_SIZE,
         pada,
         pada_fraction,
+        navamsa_sign: navamsa_sign.to_string(),
     }
 }
 
@@ -143,8 +204,11 @@ fn build_placement(object_id: String, longitude: f64) -> NakshatraPlacement {
         start_degree: metadata.base.start,
         end_degree: metadata.base.end,
         lord: metadata.base.lord.clone(),
+        deity: metadata.base.deity.clone(),
+        gana: metadata.base.gana.clone(),
         pada: metadata.pada,
         pada_fraction: metadata.pada_fraction,
+        navamsa_sign: metadata.navamsa_sign.clone(),
     }
 }
 
@@ -171,7 +235,7 @@ pub fn annotate_layer_nakshatras(
     
     if include_angles {
         if let Some(houses) = &layer_positions.houses {
-            for (angle_id, lon) in &houses.angles {
+            for (angle_id, lon) in houses.angles.iter().filter(|(id, _)| ANGLE_IDS.contains(&id.as_str())) {
                 placements.insert(angle_id.clone(), build_placement(angle_id.clone(), *lon));
             }
         }
@@ -204,5 +268,23 @@ mod tests {
         assert_eq!(meta2.base.id, "ashwini");
         assert!(meta2.pada >= 1 && meta2.pada <= 4);
     }
+
+    #[test]
+    fn test_placement_details() {
+        // Rohini pada 2 (44°) is the 14th pada overall: Taurus navamsa
+        let placement = build_placement("moon".to_string(), 44.0);
+        assert_eq!(placement.nakshatra_id, "rohini");
+        assert_eq!(placement.lord, "moon");
+        assert_eq!(placement.deity, "Brahma");
+        assert_eq!(placement.gana, "manushya");
+        assert_eq!(placement.pada, 2);
+        assert_eq!(placement.navamsa_sign, "taurus");
+
+        // Revati pada 4 closes the cycle in Pisces
+        let placement = build_placement("sun".to_string(), 359.0);
+        assert_eq!(placement.gana, "deva");
+        assert_eq!(placement.navamsa_sign, "pisces");
+        assert_eq!(NAKSHATRA_DETAILS.len(), NAKSHATRA_ORDER.len());
+    }
 }
 
@@ -0,0 +1,270 @@
+//! Panchanga (five limbs of the day) and daily inauspicious periods.
+//!
+//! Tithi and karana come from the Moon-Sun elongation (12° and 6° steps), the nitya
+//! yoga from the sum of their sidereal longitudes (13°20' steps) and the nakshatra from
+//! the sidereal Moon. The Vedic day (vara) runs from sunrise to sunrise; sunrise and
+//! sunset use the standard sunrise equation with -0.833° solar altitude, and local
+//! dates are reckoned in local mean time from the longitude.
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use crate::vedic::nakshatra::{get_nakshatra_for_longitude, normalize_degrees};
+
+/// Tithi names within a paksha (the 15th is purnima or amavasya)
+pub const TITHI_NAMES: &[&str] = &[
+    "pratipada", "dwitiya", "tritiya", "chaturthi", "panchami",
+    "shashthi", "saptami", "ashtami", "navami", "dashami",
+    "ekadashi", "dwadashi", "trayodashi", "chaturdashi",
+];
+
+/// The 27 nitya yogas
+pub const NITYA_YOGA_NAMES: &[&str] = &[
+    "vishkambha", "priti", "ayushman", "saubhagya", "shobhana", "atiganda",
+    "sukarma", "dhriti", "shula", "ganda", "vriddhi", "dhruva",
+    "vyaghata", "harshana", "vajra", "siddhi", "vyatipata", "variyana",
+    "parigha", "shiva", "siddha", "sadhya", "shubha", "shukla",
+    "brahma", "indra", "vaidhriti",
+];
+
+/// Movable karanas, repeating from the second half of the first tithi
+const MOVABLE_KARANAS: &[&str] = &[
+    "bava", "balava", "kaulava", "taitila", "gara", "vanija", "vishti",
+];
+
+/// Weekdays (vara), Sunday first
+pub const WEEKDAYS: &[&str] = &[
+    "sunday", "monday", "tuesday", "wednesday", "thursday", "friday", "saturday",
+];
+
+/// Eighth of the daytime (1-8) holding each period, Sunday first
+const RAHU_KALA_PARTS: [u32; 7] = [8, 2, 7, 5, 6, 4, 3];
+const YAMAGANDA_PARTS: [u32; 7] = [5, 4, 3, 2, 1, 7, 6];
+const GULIKA_PARTS: [u32; 7] = [7, 6, 5, 4, 3, 2, 1];
+
+/// Solar altitude at sunrise/sunset, including refraction and the solar semi-diameter
+const SUNRISE_ALTITUDE: f64 = -0.833;
+const OBLIQUITY: f64 = 23.4397;
+const J2000: f64 = 2451545.0;
+const UNIX_EPOCH_JD: f64 = 2440587.5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Panchanga {
+    /// Tithi number 1-30 (1-15 shukla, 16-30 krishna)
+    pub tithi: u8,
+    #[serde(rename = "tithiName")]
+    pub tithi_name: String,
+    /// "shukla" (waxing) or "krishna" (waning)
+    pub paksha: String,
+    pub nakshatra: String,
+    #[serde(rename = "nakshatraPada")]
+    pub nakshatra_pada: i32,
+    pub yoga: String,
+    pub karana: String,
+    pub vara: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimeWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl TimeWindow {
+    pub fn contains(&self, datetime: DateTime<Utc>) -> bool {
+        datetime >= self.start && datetime < self.end
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SolarDay {
+    pub sunrise: DateTime<Utc>,
+    pub sunset: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct InauspiciousPeriods {
+    #[serde(rename = "rahuKala")]
+    pub rahu_kala: TimeWindow,
+    pub yamaganda: TimeWindow,
+    pub gulika: TimeWindow,
+}
+
+/// Tithi number (1-30) from sun and moon longitudes
+pub fn tithi_number(sun_lon: f64, moon_lon: f64) -> u8 {
+    (normalize_degrees(moon_lon - sun_lon) / 12.0) as u8 % 30 + 1
+}
+
+/// Name of a tithi (1-30)
+pub fn tithi_name(tithi: u8) -> &'static str {
+    match tithi {
+        15 => "purnima",
+        30 => "amavasya",
+        _ => TITHI_NAMES[((tithi - 1) % 15) as usize],
+    }
+}
+
+/// Karana name from sun and moon longitudes
+pub fn karana_name(sun_lon: f64, moon_lon: f64) -> &'static str {
+    let half_tithi = (normalize_degrees(moon_lon - sun_lon) / 6.0) as usize % 60;
+    match half_tithi {
+        0 => "kimstughna",
+        57 => "shakuni",
+        58 => "chatushpada",
+        59 => "naga",
+        _ => MOVABLE_KARANAS[(half_tithi - 1) % MOVABLE_KARANAS.len()],
+    }
+}
+
+/// Nitya yoga name from sidereal sun and moon longitudes
+pub fn nitya_yoga_name(sun_lon: f64, moon_lon: f64) -> &'static str {
+    let index = (normalize_degrees(sun_lon + moon_lon) / (360.0 / 27.0)) as usize % 27;
+    NITYA_YOGA_NAMES[index]
+}
+
+/// Compute the panchanga from sidereal sun and moon longitudes and the weekday (0 = Sunday)
+pub fn compute_panchanga(sun_lon: f64, moon_lon: f64, weekday: usize) -> Panchanga {
+    let tithi = tithi_number(sun_lon, moon_lon);
+    let nakshatra = get_nakshatra_for_longitude(moon_lon);
+    Panchanga {
+        tithi,
+        tithi_name: tithi_name(tithi).to_string(),
+        paksha: if tithi <= 15 { "shukla" } else { "krishna" }.to_string(),
+        nakshatra: nakshatra.base.id,
+        nakshatra_pada: nakshatra.pada,
+        yoga: nitya_yoga_name(sun_lon, moon_lon).to_string(),
+        karana: karana_name(sun_lon, moon_lon).to_string(),
+        vara: WEEKDAYS[weekday % 7].to_string(),
+    }
+}
+
+fn julian_day_to_datetime(jd: f64) -> DateTime<Utc> {
+    let millis = ((jd - UNIX_EPOCH_JD) * 86_400_000.0).round() as i64;
+    Utc.timestamp_millis_opt(millis).single().unwrap_or_default()
+}
+
+/// Sunrise and sunset for a local date; None when the Sun does not rise or set
+pub fn solar_day(date: NaiveDate, lat: f64, lon: f64) -> Option<SolarDay> {
+    let j2000_date = NaiveDate::from_ymd_opt(2000, 1, 1)?;
+    let days = (date - j2000_date).num_days() as f64;
+    let mean_solar_noon = days - lon / 360.0;
+
+    let anomaly = normalize_degrees(357.5291 + 0.98560028 * mean_solar_noon).to_radians();
+    let center = 1.9148 * anomaly.sin() + 0.02 * (2.0 * anomaly).sin() + 0.0003 * (3.0 * anomaly).sin();
+    let ecliptic_lon = normalize_degrees(anomaly.to_degrees() + center + 180.0 + 102.9372).to_radians();
+    let transit = J2000 + mean_solar_noon + 0.0053 * anomaly.sin() - 0.0069 * (2.0 * ecliptic_lon).sin();
+
+    let declination = (ecliptic_lon.sin() * OBLIQUITY.to_radians().sin()).asin();
+    let phi = lat.to_radians();
+    let cos_hour_angle = (SUNRISE_ALTITUDE.to_radians().sin() - phi.sin() * declination.sin())
+        / (phi.cos() * declination.cos());
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+    let hour_angle = cos_hour_angle.acos().to_degrees();
+
+    Some(SolarDay {
+        sunrise: julian_day_to_datetime(transit - hour_angle / 360.0),
+        sunset: julian_day_to_datetime(transit + hour_angle / 360.0),
+    })
+}
+
+/// Local mean date of a moment at a longitude
+pub fn local_date(datetime: DateTime<Utc>, lon: f64) -> NaiveDate {
+    (datetime + Duration::seconds((lon * 240.0) as i64)).date_naive()
+}
+
+/// Vedic day (sunrise to sunrise) containing a moment: its date and solar day.
+///
+/// Moments before the local sunrise belong to the previous day. Near the poles, where
+/// the Sun does not rise or set, the civil local date is used without a solar day.
+pub fn vedic_day(datetime: DateTime<Utc>, lat: f64, lon: f64) -> (NaiveDate, Option<SolarDay>) {
+    let date = local_date(datetime, lon);
+    match solar_day(date, lat, lon) {
+        Some(day) if datetime < day.sunrise => {
+            let previous = date.pred_opt().unwrap_or(date);
+            (previous, solar_day(previous, lat, lon))
+        }
+        day => (date, day),
+    }
+}
+
+/// Weekday index (0 = Sunday) of a date
+pub fn weekday_index(date: NaiveDate) -> usize {
+    date.weekday().num_days_from_sunday() as usize
+}
+
+fn daytime_part(day: &SolarDay, part: u32) -> TimeWindow {
+    let eighth = (day.sunset - day.sunrise) / 8;
+    let start = day.sunrise + eighth * (part as i32 - 1);
+    TimeWindow { start, end: start + eighth }
+}
+
+/// Rahu kala, yamaganda and gulika kala for a day (weekday 0 = Sunday)
+pub fn inauspicious_periods(day: &SolarDay, weekday: usize) -> InauspiciousPeriods {
+    let weekday = weekday % 7;
+    InauspiciousPeriods {
+        rahu_kala: daytime_part(day, RAHU_KALA_PARTS[weekday]),
+        yamaganda: daytime_part(day, YAMAGANDA_PARTS[weekday]),
+        gulika: daytime_part(day, GULIKA_PARTS[weekday]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tithi_and_karana() {
+        assert_eq!(tithi_number(10.0, 15.0), 1);
+        assert_eq!(tithi_name(1), "pratipada");
+        assert_eq!(tithi_number(0.0, 175.0), 15);
+        assert_eq!(tithi_name(15), "purnima");
+        assert_eq!(tithi_number(100.0, 95.0), 30);
+        assert_eq!(tithi_name(30), "amavasya");
+        assert_eq!(tithi_name(26), "ekadashi");
+
+        assert_eq!(karana_name(0.0, 3.0), "kimstughna");
+        assert_eq!(karana_name(0.0, 9.0), "bava");
+        assert_eq!(karana_name(0.0, 357.0), "naga");
+        // Second half of the 8th tithi is the 15th half-tithi: vishti (bhadra)
+        assert_eq!(karana_name(0.0, 14.0 * 6.0 + 1.0), "vishti");
+    }
+
+    #[test]
+    fn test_compute_panchanga() {
+        let panchanga = compute_panchanga(10.0, 200.0, 4);
+        assert_eq!(panchanga.tithi, 16);
+        assert_eq!(panchanga.paksha, "krishna");
+        assert_eq!(panchanga.nakshatra, "vishakha");
+        assert_eq!(panchanga.yoga, NITYA_YOGA_NAMES[15]);
+        assert_eq!(panchanga.vara, "thursday");
+    }
+
+    #[test]
+    fn test_solar_day_equator_equinox() {
+        // Near the equinox on the Greenwich meridian the day lasts about twelve hours
+        let date = NaiveDate::from_ymd_opt(2024, 3, 20).unwrap();
+        let day = solar_day(date, 0.0, 0.0).unwrap();
+        let length = (day.sunset - day.sunrise).num_minutes();
+        assert!((715..=735).contains(&length), "day length {}", length);
+        assert_eq!(day.sunrise.date_naive(), date);
+        assert!(solar_day(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap(), 80.0, 0.0).is_none());
+    }
+
+    #[test]
+    fn test_vedic_day_and_rahu_kala() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 17).unwrap(); // Sunday
+        let day = solar_day(date, 0.0, 0.0).unwrap();
+        // Before sunrise still belongs to Saturday
+        let (vedic_date, _) = vedic_day(day.sunrise - Duration::minutes(30), 0.0, 0.0);
+        assert_eq!(weekday_index(vedic_date), 6);
+        let (vedic_date, _) = vedic_day(day.sunrise + Duration::minutes(30), 0.0, 0.0);
+        assert_eq!(weekday_index(vedic_date), 0);
+
+        // Sunday rahu kala is the last eighth of the daytime
+        let periods = inauspicious_periods(&day, 0);
+        assert_eq!(periods.rahu_kala.end, day.sunset);
+        assert!(periods.rahu_kala.contains(day.sunset - Duration::minutes(1)));
+        assert!(!periods.rahu_kala.contains(day.sunrise));
+    }
+}
@@ -0,0 +1,189 @@
+//! Daily tarabala and chandrabala calculations.
+//!
+//! Tarabala counts the nakshatras from the natal Moon's nakshatra to the transiting
+//! Moon's nakshatra and groups the count into the nine taras. Chandrabala counts the
+//! signs from the natal Moon sign to the transiting Moon sign.
+
+use serde::{Deserialize, Serialize};
+use crate::ephemeris::types::LayerPositions;
+use crate::vedic::nakshatra::get_nakshatra_for_longitude;
+use crate::zodiac::{sign_index, SIGN_NAMES};
+
+/// The nine taras in counting order: (slug, name, favorable).
+pub const TARA_ORDER: &[(&str, &str, bool)] = &[
+    ("janma", "Janma", false),
+    ("sampat", "Sampat", true),
+    ("vipat", "Vipat", false),
+    ("kshema", "Kshema", true),
+    ("pratyari", "Pratyari", false),
+    ("sadhaka", "Sadhaka", true),
+    ("vadha", "Vadha", false),
+    ("mitra", "Mitra", true),
+    ("parama_mitra", "Parama Mitra", true),
+];
+
+/// Houses from the natal Moon in which the transiting Moon gives good chandrabala.
+pub const FAVORABLE_CHANDRABALA_HOUSES: &[u8] = &[1, 3, 6, 7, 10, 11];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaraBala {
+    #[serde(rename = "natalNakshatra")]
+    pub natal_nakshatra: String,
+    #[serde(rename = "transitNakshatra")]
+    pub transit_nakshatra: String,
+    pub count: u8, // 1..=27, counted inclusively from the natal nakshatra
+    #[serde(rename = "taraIndex")]
+    pub tara_index: u8, // 1..=9
+    #[serde(rename = "taraId")]
+    pub tara_id: String,
+    #[serde(rename = "taraName")]
+    pub tara_name: String,
+    pub favorable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChandraBala {
+    #[serde(rename = "natalMoonSign")]
+    pub natal_moon_sign: String,
+    #[serde(rename = "transitMoonSign")]
+    pub transit_moon_sign: String,
+    #[serde(rename = "houseFromNatalMoon")]
+    pub house_from_natal_moon: u8, // 1..=12
+    pub favorable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DailyStrength {
+    #[serde(rename = "layerId")]
+    pub layer_id: String,
+    #[serde(rename = "natalLayerId")]
+    pub natal_layer_id: String,
+    pub tarabala: TaraBala,
+    pub chandrabala: ChandraBala,
+}
+
+/// Compute tarabala from the natal and transiting Moon longitudes.
+pub fn compute_tarabala(natal_moon_lon: f64, transit_moon_lon: f64) -> TaraBala {
+    let natal = get_nakshatra_for_longitude(natal_moon_lon);
+    let transit = get_nakshatra_for_longitude(transit_moon_lon);
+
+    let count = ((transit.base.index + 27 - natal.base.index) % 27) as u8 + 1;
+    let tara_index = (count - 1) % 9 + 1;
+    let (tara_id, tara_name, favorable) = TARA_ORDER[(tara_index - 1) as usize];
+
+    TaraBala {
+        natal_nakshatra: natal.base.id,
+        transit_nakshatra: transit.base.id,
+        count,
+        tara_index,
+        tara_id: tara_id.to_string(),
+        tara_name: tara_name.to_string(),
+        favorable,
+    }
+}
+
+/// Compute chandrabala from the natal and transiting Moon longitudes.
+pub fn compute_chandrabala(natal_moon_lon: f64, transit_moon_lon: f64) -> ChandraBala {
+    let natal_sign = sign_index(natal_moon_lon);
+    let transit_sign = sign_index(transit_moon_lon);
+    let house = ((transit_sign + 12 - natal_sign) % 12) as u8 + 1;
+
+    ChandraBala {
+        natal_moon_sign: SIGN_NAMES[natal_sign].to_string(),
+        transit_moon_sign: SIGN_NAMES[transit_sign].to_string(),
+        house_from_natal_moon: house,
+        favorable: FAVORABLE_CHANDRABALA_HOUSES.contains(&house),
+    }
+}
+
+/// Compute tarabala and chandrabala for a layer relative to the natal layer's Moon.
+pub fn compute_daily_strength(
+    layer_id: &str,
+    natal_layer_id: &str,
+    natal_positions: &LayerPositions,
+    layer_positions: &LayerPositions,
+) -> Result<DailyStrength, String> {
+    let natal_moon = natal_positions.planets.get("moon")
+        .ok_or_else(|| "Moon position not found in natal layer".to_string())?;
+    let transit_moon = layer_positions.planets.get("moon")
+        .ok_or_else(|| format!("Moon position not found in layer {}", layer_id))?;
+
+    Ok(DailyStrength {
+        layer_id: layer_id.to_string(),
+        natal_layer_id: natal_layer_id.to_string(),
+        tarabala: compute_tarabala(natal_moon.lon, transit_moon.lon),
+        chandrabala: compute_chandrabala(natal_moon.lon, transit_moon.lon),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::PlanetPosition;
+    use crate::vedic::nakshatra::NAKSHATRA_SEGMENT_SIZE;
+    use std::collections::HashMap;
+
+    fn moon_only(lon: f64) -> LayerPositions {
+        let mut planets = HashMap::new();
+        planets.insert("moon".to_string(), PlanetPosition {
+            lon,
+            lat: 0.0,
+            speed_lon: 13.0,
+            retrograde: false,
+        });
+        LayerPositions { planets, houses: None }
+    }
+
+    #[test]
+    fn test_tarabala_same_nakshatra_is_janma() {
+        let tara = compute_tarabala(1.0, 5.0);
+        assert_eq!(tara.count, 1);
+        assert_eq!(tara.tara_id, "janma");
+        assert!(!tara.favorable);
+    }
+
+    #[test]
+    fn test_tarabala_counts_and_wraps() {
+        // Second nakshatra from Ashwini is Sampat
+        let tara = compute_tarabala(1.0, NAKSHATRA_SEGMENT_SIZE + 1.0);
+        assert_eq!(tara.count, 2);
+        assert_eq!(tara.tara_id, "sampat");
+        assert!(tara.favorable);
+
+        // Revati natal, Ashwini transit: count 2 after wrapping the zodiac
+        let tara = compute_tarabala(359.0, 1.0);
+        assert_eq!(tara.natal_nakshatra, "revati");
+        assert_eq!(tara.count, 2);
+
+        // Tenth nakshatra starts the second round of taras
+        let tara = compute_tarabala(1.0, 9.0 * NAKSHATRA_SEGMENT_SIZE + 1.0);
+        assert_eq!(tara.count, 10);
+        assert_eq!(tara.tara_index, 1);
+        assert_eq!(tara.tara_id, "janma");
+    }
+
+    #[test]
+    fn test_chandrabala() {
+        let bala = compute_chandrabala(15.0, 75.0);
+        assert_eq!(bala.natal_moon_sign, "aries");
+        assert_eq!(bala.transit_moon_sign, "gemini");
+        assert_eq!(bala.house_from_natal_moon, 3);
+        assert!(bala.favorable);
+
+        let bala = compute_chandrabala(345.0, 15.0);
+        assert_eq!(bala.house_from_natal_moon, 2);
+        assert!(!bala.favorable);
+    }
+
+    #[test]
+    fn test_compute_daily_strength_requires_moon() {
+        let natal = moon_only(10.0);
+        let transit = LayerPositions { planets: HashMap::new(), houses: None };
+        assert!(compute_daily_strength("transit", "natal", &natal, &transit).is_err());
+
+        let transit = moon_only(100.0);
+        let strength = compute_daily_strength("transit", "natal", &natal, &transit).unwrap();
+        assert_eq!(strength.layer_id, "transit");
+        assert_eq!(strength.chandrabala.house_from_natal_moon, 4);
+    }
+}
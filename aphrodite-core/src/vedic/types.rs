@@ -6,6 +6,10 @@ use crate::vedic::nakshatra::NakshatraPlacement;
 use crate::vedic::vargas::VargaLayer;
 use crate::vedic::yogas::Yoga;
 use crate::vedic::dashas::VimshottariResponse;
+use crate::vedic::tarabala::DailyStrength;
+use crate::vedic::ashtakavarga::Ashtakavarga;
+use crate::vedic::bhava::BhavaChalit;
+use crate::vedic::avasthas::PlanetAvastha;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NakshatraLayer {
@@ -22,6 +26,12 @@ pub struct VedicLayerData {
     pub nakshatras: Option<NakshatraLayer>,
     pub vargas: HashMap<String, VargaLayer>,
     pub yogas: Vec<Yoga>,
+    #[serde(rename = "dailyStrength", skip_serializing_if = "Option::is_none")]
+    pub daily_strength: Option<DailyStrength>,
+    #[serde(rename = "bhavaChalit", skip_serializing_if = "Option::is_none")]
+    pub bhava_chalit: Option<BhavaChalit>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avasthas: Option<HashMap<String, PlanetAvastha>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,5 +39,8 @@ pub struct VedicPayload {
     pub layers: HashMap<String, VedicLayerData>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dashas: Option<VimshottariResponse>,
+    /// Ashtakavarga of the natal layer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ashtakavarga: Option<Ashtakavarga>,
 }
 
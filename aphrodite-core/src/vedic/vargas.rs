@@ -2,6 +2,8 @@
 //! 
 //! Vargas are derived charts that divide each sign into multiple parts.
 //! Each varga has specific calculation rules based on sign qualities and planetary rulers.
+//! The Parashari shodasavarga (D1-D60) follow Brihat Parashara Hora Shastra; D5, D6 and
+//! D8 use the generic movable/fixed/dual scheme.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -29,27 +31,41 @@ pub struct VargaLayer {
 pub struct VargaSpec {
     pub label: &'static str,
     pub division: i32,
+    /// Alternate names accepted in VedicConfig.vargas (lowercase)
+    pub aliases: &'static [&'static str],
 }
 
 pub const SUPPORTED_VARGAS: &[(&str, VargaSpec)] = &[
-    ("d2", VargaSpec { label: "Hora", division: 2 }),
-    ("d3", VargaSpec { label: "Drekkana", division: 3 }),
-    ("d4", VargaSpec { label: "Chaturthamsa", division: 4 }),
-    ("d5", VargaSpec { label: "Panchamsa", division: 5 }),
-    ("d6", VargaSpec { label: "Shashthamsa", division: 6 }),
-    ("d7", VargaSpec { label: "Saptamsa", division: 7 }),
-    ("d8", VargaSpec { label: "Ashtamsa", division: 8 }),
-    ("d9", VargaSpec { label: "Navamsa", division: 9 }),
-    ("d10", VargaSpec { label: "Dasamsa", division: 10 }),
-    ("d12", VargaSpec { label: "Dvadasamsa", division: 12 }),
-    ("d16", VargaSpec { label: "Shodasamsa", division: 16 }),
-    ("d20", VargaSpec { label: "Vimsamsa", division: 20 }),
-    ("d24", VargaSpec { label: "ChaturVimsamsa", division: 24 }),
-    ("d27", VargaSpec { label: "Bhamsa", division: 27 }),
-    ("d30", VargaSpec { label: "Trimsamsa", division: 30 }),
-    ("d60", VargaSpec { label: "Shashtiamsa", division: 60 }),
+    ("d1", VargaSpec { label: "Rasi", division: 1, aliases: &["rasi", "rashi"] }),
+    ("d2", VargaSpec { label: "Hora", division: 2, aliases: &["hora"] }),
+    ("d3", VargaSpec { label: "Drekkana", division: 3, aliases: &["drekkana", "drekkana_parashara"] }),
+    ("d4", VargaSpec { label: "Chaturthamsa", division: 4, aliases: &["chaturthamsa", "turyamsa"] }),
+    ("d5", VargaSpec { label: "Panchamsa", division: 5, aliases: &["panchamsa"] }),
+    ("d6", VargaSpec { label: "Shashthamsa", division: 6, aliases: &["shashthamsa"] }),
+    ("d7", VargaSpec { label: "Saptamsa", division: 7, aliases: &["saptamsa"] }),
+    ("d8", VargaSpec { label: "Ashtamsa", division: 8, aliases: &["ashtamsa"] }),
+    ("d9", VargaSpec { label: "Navamsa", division: 9, aliases: &["navamsa"] }),
+    ("d10", VargaSpec { label: "Dasamsa", division: 10, aliases: &["dasamsa", "dashamsa"] }),
+    ("d12", VargaSpec { label: "Dvadasamsa", division: 12, aliases: &["dvadasamsa", "dwadasamsa"] }),
+    ("d16", VargaSpec { label: "Shodasamsa", division: 16, aliases: &["shodasamsa", "kalamsa"] }),
+    ("d20", VargaSpec { label: "Vimsamsa", division: 20, aliases: &["vimsamsa"] }),
+    ("d24", VargaSpec { label: "ChaturVimsamsa", division: 24, aliases: &["chaturvimsamsa", "siddhamsa"] }),
+    ("d27", VargaSpec { label: "Bhamsa", division: 27, aliases: &["bhamsa", "saptavimsamsa", "nakshatramsa"] }),
+    ("d30", VargaSpec { label: "Trimsamsa", division: 30, aliases: &["trimsamsa"] }),
+    ("d40", VargaSpec { label: "Khavedamsa", division: 40, aliases: &["khavedamsa", "chatvarimsamsa"] }),
+    ("d45", VargaSpec { label: "Akshavedamsa", division: 45, aliases: &["akshavedamsa", "panchachatvarimsamsa"] }),
+    ("d60", VargaSpec { label: "Shashtiamsa", division: 60, aliases: &["shashtiamsa", "shashtyamsa"] }),
 ];
 
+/// Resolve a varga id ("d9") or name ("navamsa") to its canonical id
+pub fn resolve_varga_id(name: &str) -> Option<&'static str> {
+    let name = name.to_lowercase();
+    SUPPORTED_VARGAS
+        .iter()
+        .find(|(id, spec)| *id == name || spec.aliases.contains(&name.as_str()))
+        .map(|(id, _)| *id)
+}
+
 const SIGN_QUALITIES: &[&str] = &[
     "movable", "fixed", "dual",
     "movable", "fixed", "dual",
@@ -73,15 +89,15 @@ pub fn build_varga_layers(
     let mut results: HashMap<String, VargaLayer> = HashMap::new();
     
     for varga in requested_vargas {
-        let varga_key = varga.to_lowercase();
-        let spec = SUPPORTED_VARGAS.iter().find(|(id, _)| *id == varga_key);
+        let spec = resolve_varga_id(varga)
+            .and_then(|varga_id| SUPPORTED_VARGAS.iter().find(|(id, _)| *id == varga_id));
         
-        if let Some((_, spec)) = spec {
+        if let Some((varga_key, spec)) = spec {
             if !planets.is_empty() {
-                let positions = build_varga_positions(planets, &varga_key);
-                results.insert(varga_key.clone(), VargaLayer {
+                let positions = build_varga_positions(planets, varga_key);
+                results.insert(varga_key.to_string(), VargaLayer {
                     base_layer_id: layer_id.to_string(),
-                    varga_id: varga_key,
+                    varga_id: varga_key.to_string(),
                     label: spec.label.to_string(),
                     planets: positions,
                 });
@@ -100,15 +116,20 @@ fn build_varga_positions(
     
     // Map varga IDs to their calculation functions
     let calculator: Option<fn(f64) -> f64> = match varga_id {
+        "d1" => Some(calculate_rasi_d1),
         "d2" => Some(calculate_hora_d2),
         "d3" => Some(calculate_drekkana_d3),
         "d4" => Some(calculate_chaturthamsa_d4),
         "d7" => Some(calculate_saptamsa_d7),
+        "d10" => Some(calculate_dasamsa_d10),
+        "d12" => Some(calculate_dvadasamsa_d12),
         "d16" => Some(calculate_shodasamsa_d16),
         "d20" => Some(calculate_vimsamsa_d20),
         "d24" => Some(calculate_chaturvimsamsa_d24),
         "d27" => Some(calculate_bhamsa_d27),
         "d30" => Some(calculate_trimsamsa_d30),
+        "d40" => Some(calculate_khavedamsa_d40),
+        "d45" => Some(calculate_akshavedamsa_d45),
         "d60" => Some(calculate_shashtiamsa_d60),
         _ => None,
     };
@@ -167,6 +188,11 @@ fn calculate_varga_longitude(longitude: f64, division: i32) -> f64 {
     (varga_sign as f64 * 30.0 + scaled_remainder) % 360.0
 }
 
+fn calculate_rasi_d1(longitude: f64) -> f64 {
+    // D1 (Rasi): the birth chart itself
+    longitude.rem_euclid(360.0)
+}
+
 fn calculate_hora_d2(longitude: f64) -> f64 {
     // D2 (Hora): Odd signs: 0-15° = Sun's hora (Leo), 15-30° = Moon's hora (Cancer)
     //            Even signs: 0-15° = Moon's hora (Cancer), 15-30° = Sun's hora (Leo)
@@ -262,6 +288,46 @@ fn calculate_saptamsa_d7(longitude: f64) -> f64 {
     (varga_sign as f64 * 30.0 + scaled_remainder) % 360.0
 }
 
+fn calculate_dasamsa_d10(longitude: f64) -> f64 {
+    // D10 (Dasamsa): Odd signs: start from sign itself
+    //                Even signs: start from 9th sign from current sign
+    let lon = longitude % 360.0;
+    let sign_index = (lon / 30.0) as i32;
+    let within_sign = lon - (sign_index as f64 * 30.0);
+    
+    let is_odd_sign = sign_index % 2 == 0; // 0-indexed: 0,2,4,6,8,10 are odd
+    let segment_size = 30.0 / 10.0;
+    let part_index = (within_sign / segment_size) as i32;
+    
+    let start_sign = if is_odd_sign {
+        sign_index
+    } else {
+        (sign_index + 8) % 12  // 9th sign
+    };
+    
+    let varga_sign = (start_sign + part_index) % 12;
+    let remainder = within_sign - (part_index as f64 * segment_size);
+    let scaled_remainder = remainder * 10.0;
+    
+    (varga_sign as f64 * 30.0 + scaled_remainder) % 360.0
+}
+
+fn calculate_dvadasamsa_d12(longitude: f64) -> f64 {
+    // D12 (Dvadasamsa): Starts from the sign itself for all signs.
+    let lon = longitude % 360.0;
+    let sign_index = (lon / 30.0) as i32;
+    let within_sign = lon - (sign_index as f64 * 30.0);
+    
+    let segment_size = 30.0 / 12.0;
+    let part_index = (within_sign / segment_size) as i32;
+    
+    let varga_sign = (sign_index + part_index) % 12;
+    let remainder = within_sign - (part_index as f64 * segment_size);
+    let scaled_remainder = remainder * 12.0;
+    
+    (varga_sign as f64 * 30.0 + scaled_remainder) % 360.0
+}
+
 fn calculate_shodasamsa_d16(longitude: f64) -> f64 {
     // D16 (Shodasamsa): Movable: start from Aries (0)
     //                    Fixed: start from Leo (4)
@@ -335,12 +401,15 @@ fn calculate_chaturvimsamsa_d24(longitude: f64) -> f64 {
 }
 
 fn calculate_bhamsa_d27(longitude: f64) -> f64 {
-    // D27 (Bhamsa): Starts from Aries (0) for all signs.
+    // D27 (Bhamsa): Fire signs: start from Aries (0)
+    //               Earth signs: start from Cancer (3)
+    //               Air signs: start from Libra (6)
+    //               Water signs: start from Capricorn (9)
     let lon = longitude % 360.0;
     let sign_index = (lon / 30.0) as i32;
     let within_sign = lon - (sign_index as f64 * 30.0);
     
-    let start_sign = 0; // Aries
+    let start_sign = (sign_index % 4) * 3;
     let segment_size = 30.0 / 27.0;
     let part_index = (within_sign / segment_size) as i32;
     
@@ -354,44 +423,36 @@ fn calculate_bhamsa_d27(longitude: f64) -> f64 {
 fn calculate_trimsamsa_d30(longitude: f64) -> f64 {
     // D30 (Trimsamsa): Unequal divisions with planet rulers.
     // Odd signs: Mars(0-5°), Saturn(5-10°), Jupiter(10-18°), Mercury(18-25°), Venus(25-30°)
-    // Even signs: Venus(0-5°), Mercury(5-10°), Jupiter(10-18°), Saturn(18-25°), Mars(25-30°)
+    // Even signs: Venus(0-5°), Mercury(5-12°), Jupiter(12-20°), Saturn(20-25°), Mars(25-30°)
+    // Odd signs map to the odd sign each planet rules, even signs to its even sign.
     let lon = longitude % 360.0;
     let sign_index = (lon / 30.0) as i32;
     let within_sign = lon - (sign_index as f64 * 30.0);
     
     let is_odd_sign = sign_index % 2 == 0; // 0-indexed: 0,2,4,6,8,10 are odd
     
-    // Planet rulers: Mars=Aries(0), Venus=Taurus(1), Mercury=Gemini(2), Moon=Cancer(3),
-    // Sun=Leo(4), Mercury=Virgo(5), Venus=Libra(6), Mars=Scorpio(7), Jupiter=Sagittarius(8),
-    // Saturn=Capricorn(9), Saturn=Aquarius(10), Jupiter=Pisces(11)
-    // For Trimsamsa: Mars=0, Saturn=9, Jupiter=8, Mercury=2, Venus=1
-    
     let (planet_sign, segment_start, segment_end) = if is_odd_sign {
-        // Odd signs: Mars(0-5°), Saturn(5-10°), Jupiter(10-18°), Mercury(18-25°), Venus(25-30°)
         if within_sign < 5.0 {
             (0, 0.0, 5.0)  // Mars -> Aries
         } else if within_sign < 10.0 {
-            (9, 5.0, 10.0)  // Saturn -> Capricorn
+            (10, 5.0, 10.0)  // Saturn -> Aquarius
         } else if within_sign < 18.0 {
             (8, 10.0, 18.0)  // Jupiter -> Sagittarius
         } else if within_sign < 25.0 {
             (2, 18.0, 25.0)  // Mercury -> Gemini
         } else {
-            (1, 25.0, 30.0)  // Venus -> Taurus
+            (6, 25.0, 30.0)  // Venus -> Libra
         }
+    } else if within_sign < 5.0 {
+        (1, 0.0, 5.0)  // Venus -> Taurus
+    } else if within_sign < 12.0 {
+        (5, 5.0, 12.0)  // Mercury -> Virgo
+    } else if within_sign < 20.0 {
+        (11, 12.0, 20.0)  // Jupiter -> Pisces
+    } else if within_sign < 25.0 {
+        (9, 20.0, 25.0)  // Saturn -> Capricorn
     } else {
-        // Even signs: Venus(0-5°), Mercury(5-10°), Jupiter(10-18°), Saturn(18-25°), Mars(25-30°)
-        if within_sign < 5.0 {
-            (1, 0.0, 5.0)  // Venus -> Taurus
-        } else if within_sign < 10.0 {
-            (2, 5.0, 10.0)  // Mercury -> Gemini
-        } else if within_sign < 18.0 {
-            (8, 10.0, 18.0)  // Jupiter -> Sagittarius
-        } else if within_sign < 25.0 {
-            (9, 18.0, 25.0)  // Saturn -> Capricorn
-        } else {
-            (0, 25.0, 30.0)  // Mars -> Aries
-        }
+        (7, 25.0, 30.0)  // Mars -> Scorpio
     };
     
     // Scale remainder to 0-30° in the target sign
@@ -402,13 +463,59 @@ fn calculate_trimsamsa_d30(longitude: f64) -> f64 {
     (planet_sign as f64 * 30.0 + scaled_remainder) % 360.0
 }
 
+fn calculate_khavedamsa_d40(longitude: f64) -> f64 {
+    // D40 (Khavedamsa): Odd signs: start from Aries (0)
+    //                   Even signs: start from Libra (6)
+    let lon = longitude % 360.0;
+    let sign_index = (lon / 30.0) as i32;
+    let within_sign = lon - (sign_index as f64 * 30.0);
+    
+    let is_odd_sign = sign_index % 2 == 0; // 0-indexed: 0,2,4,6,8,10 are odd
+    let start_sign = if is_odd_sign { 0 } else { 6 }; // Aries or Libra
+    
+    let segment_size = 30.0 / 40.0;
+    let part_index = (within_sign / segment_size) as i32;
+    
+    let varga_sign = (start_sign + part_index) % 12;
+    let remainder = within_sign - (part_index as f64 * segment_size);
+    let scaled_remainder = remainder * 40.0;
+    
+    (varga_sign as f64 * 30.0 + scaled_remainder) % 360.0
+}
+
+fn calculate_akshavedamsa_d45(longitude: f64) -> f64 {
+    // D45 (Akshavedamsa): Movable: start from Aries (0)
+    //                     Fixed: start from Leo (4)
+    //                     Dual: start from Sagittarius (8)
+    let lon = longitude % 360.0;
+    let sign_index = (lon / 30.0) as i32;
+    let within_sign = lon - (sign_index as f64 * 30.0);
+    
+    let quality = SIGN_QUALITIES[sign_index as usize % 12];
+    let start_sign = match quality {
+        "movable" => 0,  // Aries
+        "fixed" => 4,    // Leo
+        "dual" => 8,     // Sagittarius
+        _ => 0,
+    };
+    
+    let segment_size = 30.0 / 45.0;
+    let part_index = (within_sign / segment_size) as i32;
+    
+    let varga_sign = (start_sign + part_index) % 12;
+    let remainder = within_sign - (part_index as f64 * segment_size);
+    let scaled_remainder = remainder * 45.0;
+    
+    (varga_sign as f64 * 30.0 + scaled_remainder) % 360.0
+}
+
 fn calculate_shashtiamsa_d60(longitude: f64) -> f64 {
-    // D60 (Shashtiamsa): Starts from Aries (0) for all signs.
+    // D60 (Shashtiamsa): Starts from the sign itself for all signs.
     let lon = longitude % 360.0;
     let sign_index = (lon / 30.0) as i32;
     let within_sign = lon - (sign_index as f64 * 30.0);
     
-    let start_sign = 0; // Aries
+    let start_sign = sign_index;
     let segment_size = 30.0 / 60.0;
     let part_index = (within_sign / segment_size) as i32;
     
@@ -439,5 +546,61 @@ mod tests {
         // Should be in Leo (Sun's hora)
         assert!(result >= 120.0 && result < 150.0); // Leo range
     }
+    
+    fn sign_of(lon: f64) -> i32 {
+        (lon / 30.0) as i32
+    }
+    
+    #[test]
+    fn test_dasamsa_and_dvadasamsa() {
+        // 5° Taurus (even): 2nd dasamsa counted from the 9th sign, Capricorn -> Aquarius
+        assert_eq!(sign_of(calculate_dasamsa_d10(35.0)), 10);
+        // 5° Aries (odd): 2nd dasamsa from Aries itself -> Taurus
+        assert_eq!(sign_of(calculate_dasamsa_d10(5.0)), 1);
+        // 5° Leo: 3rd dvadasamsa from Leo -> Libra
+        assert_eq!(sign_of(calculate_dvadasamsa_d12(125.0)), 6);
+    }
+    
+    #[test]
+    fn test_bhamsa_and_shashtiamsa_start_signs() {
+        // First bhamsa of Taurus (earth) is Cancer; of Cancer (water) is Capricorn
+        assert_eq!(sign_of(calculate_bhamsa_d27(30.1)), 3);
+        assert_eq!(sign_of(calculate_bhamsa_d27(90.1)), 9);
+        // First shashtiamsa of Gemini is Gemini
+        assert_eq!(sign_of(calculate_shashtiamsa_d60(60.1)), 2);
+    }
+    
+    #[test]
+    fn test_trimsamsa_even_sign_bounds() {
+        // 11° Taurus (even) is Mercury's trimsamsa -> Virgo
+        assert_eq!(sign_of(calculate_trimsamsa_d30(41.0)), 5);
+        // 6° Aries (odd) is Saturn's trimsamsa -> Aquarius
+        assert_eq!(sign_of(calculate_trimsamsa_d30(6.0)), 10);
+    }
+    
+    #[test]
+    fn test_khavedamsa_and_akshavedamsa() {
+        // First khavedamsa of Taurus (even) is Libra
+        assert_eq!(sign_of(calculate_khavedamsa_d40(30.1)), 6);
+        // First akshavedamsa of Taurus (fixed) is Leo; second of Gemini (dual) is Capricorn
+        assert_eq!(sign_of(calculate_akshavedamsa_d45(30.1)), 4);
+        assert_eq!(sign_of(calculate_akshavedamsa_d45(60.0 + 30.0 / 45.0 + 0.01)), 9);
+    }
+    
+    #[test]
+    fn test_varga_selection_by_name() {
+        assert_eq!(resolve_varga_id("Navamsa"), Some("d9"));
+        assert_eq!(resolve_varga_id("D60"), Some("d60"));
+        assert_eq!(resolve_varga_id("khavedamsa"), Some("d40"));
+        assert_eq!(resolve_varga_id("d11"), None);
+        
+        let mut planets = HashMap::new();
+        planets.insert("sun".to_string(), PlanetPosition { lon: 45.0, lat: 0.0, speed_lon: 1.0, retrograde: false });
+        let layer = LayerPositions { planets, houses: None };
+        let vargas = vec!["navamsa".to_string(), "d45".to_string(), "d1".to_string()];
+        let layers = build_varga_layers("natal", &layer, &vargas);
+        assert_eq!(layers.len(), 3);
+        assert_eq!(layers["d9"].varga_id, "d9");
+        assert_eq!(layers["d1"].planets["sun"].lon, 45.0);
+    }
 }
-
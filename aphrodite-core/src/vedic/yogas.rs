@@ -1,21 +1,68 @@
 //! Yoga detection helpers for Vedic astrology.
-//! 
-//! Yogas are planetary combinations that indicate specific life outcomes.
+//!
+//! Yogas are planetary combinations that indicate specific life outcomes. Detection is
+//! driven by a table of rules (Raja, Dhana, Pancha Mahapurusha, Nabhasa, lunar/solar
+//! and affliction yogas) evaluated on whole-sign houses from the lagna. Each match
+//! reports its participating planets, the rule that matched and a strength estimate:
+//! the mean dignity of the participants (exalted 1.0, own sign 0.75, otherwise 0.5,
+//! debilitated 0.25), scaled down for wide conjunctions in conjunction-based yogas.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use crate::ephemeris::types::LayerPositions;
+use crate::vedic::nakshatra::graha_longitude;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Yoga {
     pub name: String,
     #[serde(rename = "type")]
     pub yoga_type: String, // "benefic", "malefic", "mixed"
+    /// "raja", "dhana", "mahapurusha", "nabhasa", "chandra", "surya" or "affliction"
+    pub category: String,
     pub description: String,
+    /// Planets taking part in the combination
+    pub planets: Vec<String>,
+    /// Strength estimate between 0 and 1
+    pub strength: f64,
+    /// The rule that matched
+    pub rule: String,
 }
 
-const BENEFIC_PLANETS: &[&str] = &["jupiter", "venus", "mercury", "moon"];
-const MALEFIC_PLANETS: &[&str] = &["saturn", "mars", "rahu", "ketu", "sun"];
+/// The seven visible planets plus the lunar nodes
+const PLANETS: &[&str] = &[
+    "sun", "moon", "mars", "mercury", "jupiter", "venus", "saturn", "rahu", "ketu",
+];
+const SEVEN_PLANETS: &[&str] = &["sun", "moon", "mars", "mercury", "jupiter", "venus", "saturn"];
+const NATURAL_BENEFICS: &[&str] = &["jupiter", "venus", "mercury"];
+const NATURAL_MALEFICS: &[&str] = &["sun", "mars", "saturn", "rahu", "ketu"];
+
+/// Sign lords, Aries first
+const SIGN_LORDS: &[&str] = &[
+    "mars", "venus", "mercury", "moon", "sun", "mercury",
+    "venus", "mars", "jupiter", "saturn", "saturn", "jupiter",
+];
+
+/// Exaltation sign of each planet (debilitation is the opposite sign)
+const EXALTATION_SIGNS: &[(&str, usize)] = &[
+    ("sun", 0), ("moon", 1), ("mars", 9), ("mercury", 5),
+    ("jupiter", 3), ("venus", 11), ("saturn", 6),
+];
+
+/// Special (graha drishti) aspects beyond the 7th, counted in signs
+const SPECIAL_ASPECTS: &[(&str, &[usize])] = &[
+    ("mars", &[4, 8]),
+    ("jupiter", &[5, 9]),
+    ("saturn", &[3, 10]),
+];
+
+const KENDRAS: &[usize] = &[1, 4, 7, 10];
+const TRIKONAS: &[usize] = &[5, 9];
+const DUSTHANAS: &[usize] = &[6, 8, 12];
+
+const EXALTED_DIGNITY: f64 = 1.0;
+const OWN_SIGN_DIGNITY: f64 = 0.75;
+const NEUTRAL_DIGNITY: f64 = 0.5;
+const DEBILITATED_DIGNITY: f64 = 0.25;
 
 /// Normalize degrees to [0, 360).
 fn normalize_degrees(value: f64) -> f64 {
@@ -32,23 +79,13 @@ fn angular_difference(lon1: f64, lon2: f64) -> f64 {
     diff.min(360.0 - diff)
 }
 
-/// Check if two planets are in conjunction.
-fn is_conjunction(lon1: f64, lon2: f64, orb: f64) -> bool {
-    angular_difference(lon1, lon2) <= orb
-}
-
-/// Check if two planets are in opposition.
-#[allow(dead_code)]
-fn is_opposition(lon1: f64, lon2: f64, orb: f64) -> bool {
-    let diff = angular_difference(lon1, lon2);
-    (diff - 180.0).abs() <= orb
+fn sign_of(longitude: f64) -> usize {
+    (normalize_degrees(longitude) / 30.0) as usize % 12
 }
 
-/// Get house number (1-12) for a given longitude.
+/// Get whole-sign house number (1-12) for a given longitude.
 fn get_house_number(longitude: f64, ascendant: f64) -> i32 {
-    let diff = normalize_degrees(longitude - ascendant);
-    let house = (diff / 30.0) as i32 + 1;
-    if house <= 12 { house } else { house - 12 }
+    ((sign_of(longitude) + 12 - sign_of(ascendant)) % 12) as i32 + 1
 }
 
 /// Check if planet is in a kendra (1, 4, 7, 10 houses).
@@ -57,237 +94,649 @@ fn is_in_kendra(longitude: f64, ascendant: f64) -> bool {
     matches!(house, 1 | 4 | 7 | 10)
 }
 
-/// Check if planet is in a trikona (1, 5, 9 houses).
-fn is_in_trikona(longitude: f64, ascendant: f64) -> bool {
-    let house = get_house_number(longitude, ascendant);
-    matches!(house, 1 | 5 | 9)
+/// Count of signs from one sign to another (1-12, inclusive of the start)
+fn sign_distance(from: usize, to: usize) -> usize {
+    (to + 12 - from) % 12 + 1
 }
 
-/// Identify classic Vedic yogas from planetary positions.
-pub fn identify_yogas(layer_positions: &LayerPositions) -> Vec<Yoga> {
-    let mut yogas: Vec<Yoga> = Vec::new();
-    
-    let planets = &layer_positions.planets;
-    let houses = layer_positions.houses.as_ref();
-    
-    if planets.is_empty() || houses.is_none() {
-        return yogas;
-    }
-    
-    let houses = houses.unwrap();
-    let angles = &houses.angles;
-    let ascendant = angles.get("asc").copied().unwrap_or(0.0);
-    
-    // Get planet longitudes
-    let planet_lons: HashMap<String, f64> = planets.iter()
-        .map(|(id, pos)| (id.clone(), pos.lon))
-        .collect();
-    
-    if planet_lons.is_empty() {
-        return yogas;
-    }
-    
-    // Helper to get planet longitude safely
-    let get_lon = |planet_id: &str| -> Option<f64> {
-        planet_lons.get(planet_id).copied()
-    };
-    
-    // 1. Gajakesari Yoga - Jupiter and Moon in kendras or trikonas
-    if let (Some(jupiter_lon), Some(moon_lon)) = (get_lon("jupiter"), get_lon("moon")) {
-        if (is_in_kendra(jupiter_lon, ascendant) || is_in_trikona(jupiter_lon, ascendant)) &&
-           (is_in_kendra(moon_lon, ascendant) || is_in_trikona(moon_lon, ascendant)) {
-            yogas.push(Yoga {
-                name: "Gajakesari Yoga".to_string(),
-                yoga_type: "benefic".to_string(),
-                description: "Jupiter and Moon in kendras or trikonas - brings wisdom and prosperity".to_string(),
-            });
-        }
+/// Chart facts shared by the yoga rules
+struct ChartFacts {
+    ascendant: f64,
+    longitudes: HashMap<&'static str, f64>,
+}
+
+impl ChartFacts {
+    fn from_layer(layer_positions: &LayerPositions) -> Option<Self> {
+        let ascendant = *layer_positions.houses.as_ref()?.angles.get("asc")?;
+        let longitudes = PLANETS
+            .iter()
+            .filter_map(|planet| Some((*planet, normalize_degrees(graha_longitude(planet, layer_positions)?))))
+            .collect();
+        Some(Self { ascendant, longitudes })
     }
-    
-    // 2. Budh Aditya Yoga - Mercury and Sun conjunction
-    if let (Some(mercury_lon), Some(sun_lon)) = (get_lon("mercury"), get_lon("sun")) {
-        if is_conjunction(mercury_lon, sun_lon, 15.0) {
-            yogas.push(Yoga {
-                name: "Budh Aditya Yoga".to_string(),
-                yoga_type: "benefic".to_string(),
-                description: "Mercury and Sun in conjunction - brings intelligence and communication skills".to_string(),
-            });
-        }
+
+    fn lon(&self, planet: &str) -> Option<f64> {
+        self.longitudes.get(planet).copied()
     }
-    
-    // 3. Raj Yoga - Benefic planets in kendras and trikonas
-    let mut benefic_in_kendra = false;
-    let mut benefic_in_trikona = false;
-    
-    for benefic in BENEFIC_PLANETS {
-        if let Some(lon) = get_lon(benefic) {
-            if is_in_kendra(lon, ascendant) {
-                benefic_in_kendra = true;
-            }
-            if is_in_trikona(lon, ascendant) {
-                benefic_in_trikona = true;
-            }
-        }
+
+    fn sign(&self, planet: &str) -> Option<usize> {
+        self.lon(planet).map(sign_of)
     }
-    
-    if benefic_in_kendra && benefic_in_trikona {
-        yogas.push(Yoga {
-            name: "Raj Yoga".to_string(),
-            yoga_type: "benefic".to_string(),
-            description: "Benefic planets in both kendras and trikonas - brings power and authority".to_string(),
-        });
-    }
-    
-    // 4. Dhan Yoga - 2nd and 11th house lords in good positions
-    // This is simplified - full implementation would need house lords
-    if let (Some(venus_lon), Some(jupiter_lon)) = (get_lon("venus"), get_lon("jupiter")) {
-        let venus_house = get_house_number(venus_lon, ascendant);
-        let jupiter_house = get_house_number(jupiter_lon, ascendant);
-        if venus_house == 2 || venus_house == 11 || jupiter_house == 2 || jupiter_house == 11 {
-            yogas.push(Yoga {
-                name: "Dhan Yoga".to_string(),
-                yoga_type: "benefic".to_string(),
-                description: "Wealth-giving planets in 2nd or 11th house - brings financial prosperity".to_string(),
-            });
-        }
+
+    fn house(&self, planet: &str) -> Option<usize> {
+        self.lon(planet).map(|lon| get_house_number(lon, self.ascendant) as usize)
     }
-    
-    // 5. Chandra-Mangal Yoga - Moon and Mars conjunction
-    if let (Some(moon_lon), Some(mars_lon)) = (get_lon("moon"), get_lon("mars")) {
-        if is_conjunction(moon_lon, mars_lon, 10.0) {
-            yogas.push(Yoga {
-                name: "Chandra-Mangal Yoga".to_string(),
-                yoga_type: "mixed".to_string(),
-                description: "Moon and Mars in conjunction - brings courage but may cause emotional volatility".to_string(),
-            });
-        }
+
+    fn lagna_sign(&self) -> usize {
+        sign_of(self.ascendant)
     }
-    
-    // 6. Shubh Kartari Yoga - Benefic planets on both sides of Moon
-    if let Some(moon_lon) = get_lon("moon") {
-        let mut benefics_around_moon = 0;
-        for benefic in BENEFIC_PLANETS {
-            if *benefic == "moon" {
-                continue;
-            }
-            if let Some(lon) = get_lon(benefic) {
-                let diff = angular_difference(moon_lon, lon);
-                if diff <= 30.0 {  // Within 30 degrees
-                    benefics_around_moon += 1;
-                }
-            }
-        }
-        
-        if benefics_around_moon >= 2 {
-            yogas.push(Yoga {
-                name: "Shubh Kartari Yoga".to_string(),
-                yoga_type: "benefic".to_string(),
-                description: "Two or more benefic planets around Moon - brings happiness and prosperity".to_string(),
-            });
+
+    fn lord_of_house(&self, house: usize) -> &'static str {
+        SIGN_LORDS[(self.lagna_sign() + house - 1) % 12]
+    }
+
+    fn has_all(&self, planets: &[&str]) -> bool {
+        planets.iter().all(|planet| self.longitudes.contains_key(planet))
+    }
+
+    /// Planets (from the given list) occupying a sign
+    fn occupants<'a>(&self, sign: usize, planets: &[&'a str]) -> Vec<&'a str> {
+        planets.iter().copied().filter(|planet| self.sign(planet) == Some(sign)).collect()
+    }
+
+    fn dignity(&self, planet: &str) -> f64 {
+        let Some(sign) = self.sign(planet) else {
+            return NEUTRAL_DIGNITY;
+        };
+        match EXALTATION_SIGNS.iter().find(|(id, _)| *id == planet) {
+            Some((_, exaltation)) if sign == *exaltation => EXALTED_DIGNITY,
+            Some((_, exaltation)) if sign == (exaltation + 6) % 12 => DEBILITATED_DIGNITY,
+            _ if SIGN_LORDS[sign] == planet => OWN_SIGN_DIGNITY,
+            _ => NEUTRAL_DIGNITY,
         }
     }
-    
-    // 7. Pap Kartari Yoga - Malefic planets on both sides of Moon
-    if let Some(moon_lon) = get_lon("moon") {
-        let mut malefics_around_moon = 0;
-        for malefic in MALEFIC_PLANETS {
-            if *malefic == "moon" {
-                continue;
-            }
-            if let Some(lon) = get_lon(malefic) {
-                let diff = angular_difference(moon_lon, lon);
-                if diff <= 30.0 {  // Within 30 degrees
-                    malefics_around_moon += 1;
+
+    fn is_exalted_or_own(&self, planet: &str) -> bool {
+        self.dignity(planet) >= OWN_SIGN_DIGNITY
+    }
+
+    fn is_debilitated(&self, planet: &str) -> bool {
+        self.dignity(planet) == DEBILITATED_DIGNITY
+    }
+
+    /// Whether a planet casts a (whole-sign) aspect on a sign
+    fn aspects_sign(&self, planet: &str, sign: usize) -> bool {
+        let Some(from) = self.sign(planet) else {
+            return false;
+        };
+        let distance = sign_distance(from, sign);
+        distance == 7
+            || SPECIAL_ASPECTS
+                .iter()
+                .any(|(id, places)| *id == planet && places.contains(&distance))
+    }
+
+    /// Conjunction in a sign, mutual aspect or sign exchange (parivartana)
+    fn associated(&self, a: &str, b: &str) -> bool {
+        let (Some(sign_a), Some(sign_b)) = (self.sign(a), self.sign(b)) else {
+            return false;
+        };
+        sign_a == sign_b
+            || (self.aspects_sign(a, sign_b) && self.aspects_sign(b, sign_a))
+            || (SIGN_LORDS[sign_a] == b && SIGN_LORDS[sign_b] == a)
+    }
+}
+
+/// A table-driven yoga rule; `detect` returns the participating planets on a match
+struct YogaRule {
+    name: &'static str,
+    category: &'static str,
+    yoga_type: &'static str,
+    description: &'static str,
+    rule: &'static str,
+    /// Conjunction-based yogas lose strength as the planets separate
+    conjunction: bool,
+    detect: fn(&ChartFacts) -> Option<Vec<&'static str>>,
+}
+
+const YOGA_RULES: &[YogaRule] = &[
+    YogaRule {
+        name: "Gajakesari Yoga",
+        category: "chandra",
+        yoga_type: "benefic",
+        description: "Jupiter in a kendra from the Moon - brings wisdom and prosperity",
+        rule: "Jupiter in the 1st, 4th, 7th or 10th sign from the Moon",
+        conjunction: false,
+        detect: detect_gajakesari,
+    },
+    YogaRule {
+        name: "Budh Aditya Yoga",
+        category: "surya",
+        yoga_type: "benefic",
+        description: "Mercury and Sun in conjunction - brings intelligence and communication skills",
+        rule: "Sun and Mercury in the same sign",
+        conjunction: true,
+        detect: |facts| same_sign(facts, "sun", "mercury"),
+    },
+    YogaRule {
+        name: "Chandra-Mangal Yoga",
+        category: "chandra",
+        yoga_type: "mixed",
+        description: "Moon and Mars in conjunction - brings wealth and courage but may cause emotional volatility",
+        rule: "Moon and Mars in the same sign",
+        conjunction: true,
+        detect: |facts| same_sign(facts, "moon", "mars"),
+    },
+    YogaRule {
+        name: "Raj Yoga",
+        category: "raja",
+        yoga_type: "benefic",
+        description: "Kendra and trikona lords combine - brings power and authority",
+        rule: "Lord of a kendra (1, 4, 7, 10) associated with lord of a trikona (5, 9) by conjunction, mutual aspect or sign exchange, or one planet ruling both",
+        conjunction: false,
+        detect: detect_raja,
+    },
+    YogaRule {
+        name: "Dhan Yoga",
+        category: "dhana",
+        yoga_type: "benefic",
+        description: "Wealth lords combine with lagna or trikona lords - brings financial prosperity",
+        rule: "Lord of the 2nd or 11th associated with lord of the 1st, 5th or 9th by conjunction, mutual aspect or sign exchange",
+        conjunction: false,
+        detect: detect_dhana,
+    },
+    YogaRule {
+        name: "Vipreet Raj Yoga",
+        category: "raja",
+        yoga_type: "benefic",
+        description: "Dusthana lords in dusthanas - turns adversity into success",
+        rule: "Lord of the 6th, 8th or 12th placed in the 6th, 8th or 12th",
+        conjunction: false,
+        detect: detect_vipreet_raja,
+    },
+    YogaRule {
+        name: "Neecha Bhanga Raj Yoga",
+        category: "raja",
+        yoga_type: "benefic",
+        description: "Debilitation cancelled - brings success after early struggle",
+        rule: "Debilitated planet whose debilitation sign lord or exaltation sign lord is in a kendra from the lagna or the Moon",
+        conjunction: false,
+        detect: detect_neecha_bhanga,
+    },
+    YogaRule {
+        name: "Ruchaka Yoga",
+        category: "mahapurusha",
+        yoga_type: "benefic",
+        description: "Strong Mars in a kendra - brings courage and leadership",
+        rule: "Mars in its own or exaltation sign in a kendra from the lagna",
+        conjunction: false,
+        detect: |facts| mahapurusha(facts, "mars"),
+    },
+    YogaRule {
+        name: "Bhadra Yoga",
+        category: "mahapurusha",
+        yoga_type: "benefic",
+        description: "Strong Mercury in a kendra - brings intellect and eloquence",
+        rule: "Mercury in its own or exaltation sign in a kendra from the lagna",
+        conjunction: false,
+        detect: |facts| mahapurusha(facts, "mercury"),
+    },
+    YogaRule {
+        name: "Hamsa Yoga",
+        category: "mahapurusha",
+        yoga_type: "benefic",
+        description: "Strong Jupiter in a kendra - brings virtue and learning",
+        rule: "Jupiter in its own or exaltation sign in a kendra from the lagna",
+        conjunction: false,
+        detect: |facts| mahapurusha(facts, "jupiter"),
+    },
+    YogaRule {
+        name: "Malavya Yoga",
+        category: "mahapurusha",
+        yoga_type: "benefic",
+        description: "Strong Venus in a kendra - brings comfort and refinement",
+        rule: "Venus in its own or exaltation sign in a kendra from the lagna",
+        conjunction: false,
+        detect: |facts| mahapurusha(facts, "venus"),
+    },
+    YogaRule {
+        name: "Sasa Yoga",
+        category: "mahapurusha",
+        yoga_type: "benefic",
+        description: "Strong Saturn in a kendra - brings authority over others",
+        rule: "Saturn in its own or exaltation sign in a kendra from the lagna",
+        conjunction: false,
+        detect: |facts| mahapurusha(facts, "saturn"),
+    },
+    YogaRule {
+        name: "Shubh Kartari Yoga",
+        category: "chandra",
+        yoga_type: "benefic",
+        description: "Benefics on both sides of the Moon - brings happiness and prosperity",
+        rule: "Natural benefics in both the 2nd and 12th signs from the Moon",
+        conjunction: false,
+        detect: |facts| kartari(facts, NATURAL_BENEFICS),
+    },
+    YogaRule {
+        name: "Pap Kartari Yoga",
+        category: "affliction",
+        yoga_type: "malefic",
+        description: "Malefics on both sides of the Moon - may cause difficulties",
+        rule: "Natural malefics in both the 2nd and 12th signs from the Moon",
+        conjunction: false,
+        detect: |facts| kartari(facts, NATURAL_MALEFICS),
+    },
+    YogaRule {
+        name: "Rajju Yoga",
+        category: "nabhasa",
+        yoga_type: "mixed",
+        description: "All planets in movable signs - fond of travel, restless",
+        rule: "All seven planets in movable signs",
+        conjunction: false,
+        detect: |facts| ashraya(facts, 0),
+    },
+    YogaRule {
+        name: "Musala Yoga",
+        category: "nabhasa",
+        yoga_type: "benefic",
+        description: "All planets in fixed signs - steady, proud and wealthy",
+        rule: "All seven planets in fixed signs",
+        conjunction: false,
+        detect: |facts| ashraya(facts, 1),
+    },
+    YogaRule {
+        name: "Nala Yoga",
+        category: "nabhasa",
+        yoga_type: "mixed",
+        description: "All planets in dual signs - skilful but changeable",
+        rule: "All seven planets in dual signs",
+        conjunction: false,
+        detect: |facts| ashraya(facts, 2),
+    },
+    YogaRule {
+        name: "Mala Yoga",
+        category: "nabhasa",
+        yoga_type: "benefic",
+        description: "Benefics in three kendras - brings comfort and enjoyment",
+        rule: "Natural benefics occupying three kendras from the lagna",
+        conjunction: false,
+        detect: |facts| dala(facts, NATURAL_BENEFICS),
+    },
+    YogaRule {
+        name: "Sarpa Yoga",
+        category: "nabhasa",
+        yoga_type: "malefic",
+        description: "Malefics in three kendras - brings hardship",
+        rule: "Natural malefics (Sun, Mars, Saturn) occupying three kendras from the lagna",
+        conjunction: false,
+        detect: |facts| dala(facts, &["sun", "mars", "saturn"]),
+    },
+    YogaRule {
+        name: "Vallaki Yoga",
+        category: "nabhasa",
+        yoga_type: "benefic",
+        description: "Planets spread over seven signs - fond of music and arts",
+        rule: "The seven planets occupy seven signs",
+        conjunction: false,
+        detect: |facts| sankhya(facts, 7),
+    },
+    YogaRule {
+        name: "Dama Yoga",
+        category: "nabhasa",
+        yoga_type: "benefic",
+        description: "Planets spread over six signs - generous and helpful",
+        rule: "The seven planets occupy six signs",
+        conjunction: false,
+        detect: |facts| sankhya(facts, 6),
+    },
+    YogaRule {
+        name: "Pasha Yoga",
+        category: "nabhasa",
+        yoga_type: "mixed",
+        description: "Planets spread over five signs - capable but bound by circumstance",
+        rule: "The seven planets occupy five signs",
+        conjunction: false,
+        detect: |facts| sankhya(facts, 5),
+    },
+    YogaRule {
+        name: "Kedara Yoga",
+        category: "nabhasa",
+        yoga_type: "benefic",
+        description: "Planets spread over four signs - useful to others, agricultural wealth",
+        rule: "The seven planets occupy four signs",
+        conjunction: false,
+        detect: |facts| sankhya(facts, 4),
+    },
+    YogaRule {
+        name: "Shoola Yoga",
+        category: "nabhasa",
+        yoga_type: "malefic",
+        description: "Planets in three signs - sharp and quarrelsome",
+        rule: "The seven planets occupy three signs",
+        conjunction: false,
+        detect: |facts| sankhya(facts, 3),
+    },
+    YogaRule {
+        name: "Yuga Yoga",
+        category: "nabhasa",
+        yoga_type: "malefic",
+        description: "Planets in two signs - unconventional, lacking wealth",
+        rule: "The seven planets occupy two signs",
+        conjunction: false,
+        detect: |facts| sankhya(facts, 2),
+    },
+    YogaRule {
+        name: "Gola Yoga",
+        category: "nabhasa",
+        yoga_type: "malefic",
+        description: "All planets in one sign - poverty and hardship",
+        rule: "The seven planets occupy a single sign",
+        conjunction: false,
+        detect: |facts| sankhya(facts, 1),
+    },
+    YogaRule {
+        name: "Kemadruma Yoga",
+        category: "affliction",
+        yoga_type: "malefic",
+        description: "Moon without support - loneliness and want",
+        rule: "No planet other than the Sun and nodes with the Moon or in the 2nd or 12th sign from it",
+        conjunction: false,
+        detect: detect_kemadruma,
+    },
+    YogaRule {
+        name: "Shakata Yoga",
+        category: "affliction",
+        yoga_type: "malefic",
+        description: "Moon in a dusthana from Jupiter - fluctuating fortune",
+        rule: "Moon in the 6th, 8th or 12th sign from Jupiter",
+        conjunction: false,
+        detect: detect_shakata,
+    },
+    YogaRule {
+        name: "Grahan Yoga",
+        category: "affliction",
+        yoga_type: "malefic",
+        description: "Luminary eclipsed by a node - clouded vitality or mind",
+        rule: "Sun or Moon in the same sign as Rahu or Ketu",
+        conjunction: true,
+        detect: detect_grahan,
+    },
+    YogaRule {
+        name: "Guru Chandal Yoga",
+        category: "affliction",
+        yoga_type: "malefic",
+        description: "Jupiter with Rahu - unorthodox judgement",
+        rule: "Jupiter and Rahu in the same sign",
+        conjunction: true,
+        detect: |facts| same_sign(facts, "jupiter", "rahu"),
+    },
+    YogaRule {
+        name: "Angarak Yoga",
+        category: "affliction",
+        yoga_type: "malefic",
+        description: "Mars with Rahu - anger and accidents",
+        rule: "Mars and Rahu in the same sign",
+        conjunction: true,
+        detect: |facts| same_sign(facts, "mars", "rahu"),
+    },
+    YogaRule {
+        name: "Kala Sarpa Yoga",
+        category: "affliction",
+        yoga_type: "malefic",
+        description: "All planets hemmed between the nodes - obstacles and delays",
+        rule: "All seven planets on one side of the Rahu-Ketu axis",
+        conjunction: false,
+        detect: detect_kala_sarpa,
+    },
+];
+
+fn same_sign(facts: &ChartFacts, a: &'static str, b: &'static str) -> Option<Vec<&'static str>> {
+    (facts.sign(a)? == facts.sign(b)?).then(|| vec![a, b])
+}
+
+fn detect_gajakesari(facts: &ChartFacts) -> Option<Vec<&'static str>> {
+    let distance = sign_distance(facts.sign("moon")?, facts.sign("jupiter")?);
+    KENDRAS.contains(&distance).then(|| vec!["jupiter", "moon"])
+}
+
+/// Planets associated across two groups of house lords
+fn lord_associations(facts: &ChartFacts, first: &[usize], second: &[usize]) -> Option<Vec<&'static str>> {
+    let mut planets: Vec<&'static str> = Vec::new();
+    for &a in first {
+        for &b in second {
+            let (lord_a, lord_b) = (facts.lord_of_house(a), facts.lord_of_house(b));
+            if lord_a == lord_b || facts.associated(lord_a, lord_b) {
+                for lord in [lord_a, lord_b] {
+                    if !planets.contains(&lord) {
+                        planets.push(lord);
+                    }
                 }
             }
         }
-        
-        if malefics_around_moon >= 2 {
-            yogas.push(Yoga {
-                name: "Pap Kartari Yoga".to_string(),
-                yoga_type: "malefic".to_string(),
-                description: "Two or more malefic planets around Moon - may cause difficulties".to_string(),
-            });
-        }
     }
-    
-    // 8. Neecha Bhanga Raj Yoga - Debilitated planet with benefic
-    // Simplified version - full implementation needs exaltation/debilitation tables
-    if let (Some(sun_lon), Some(jupiter_lon)) = (get_lon("sun"), get_lon("jupiter")) {
-        // Sun is debilitated in Libra (180-210 degrees)
-        let sun_normalized = normalize_degrees(sun_lon);
-        if sun_normalized >= 180.0 && sun_normalized <= 210.0 {
-            if is_conjunction(sun_lon, jupiter_lon, 10.0) ||
-               is_in_kendra(jupiter_lon, ascendant) {
-                yogas.push(Yoga {
-                    name: "Neecha Bhanga Raj Yoga".to_string(),
-                    yoga_type: "benefic".to_string(),
-                    description: "Debilitated planet with benefic - cancels debilitation and brings success".to_string(),
-                });
-            }
+    (!planets.is_empty()).then_some(planets)
+}
+
+fn detect_raja(facts: &ChartFacts) -> Option<Vec<&'static str>> {
+    lord_associations(facts, KENDRAS, TRIKONAS)
+}
+
+fn detect_dhana(facts: &ChartFacts) -> Option<Vec<&'static str>> {
+    lord_associations(facts, &[2, 11], &[1, 5, 9])
+}
+
+fn detect_vipreet_raja(facts: &ChartFacts) -> Option<Vec<&'static str>> {
+    let mut planets: Vec<&'static str> = Vec::new();
+    for &house in DUSTHANAS {
+        let lord = facts.lord_of_house(house);
+        if facts.house(lord).is_some_and(|h| DUSTHANAS.contains(&h)) && !planets.contains(&lord) {
+            planets.push(lord);
         }
     }
-    
-    // 9. Vipreet Raj Yoga - Malefic in 6th, 8th, or 12th house
-    for malefic in MALEFIC_PLANETS {
-        if *malefic == "sun" {
-            continue; // Sun is not typically considered malefic for this yoga
+    (!planets.is_empty()).then_some(planets)
+}
+
+fn detect_neecha_bhanga(facts: &ChartFacts) -> Option<Vec<&'static str>> {
+    let moon_sign = facts.sign("moon");
+    let in_kendra = |planet: &str| {
+        facts.lon(planet).is_some_and(|lon| is_in_kendra(lon, facts.ascendant))
+            || moon_sign
+                .zip(facts.sign(planet))
+                .is_some_and(|(moon, sign)| KENDRAS.contains(&sign_distance(moon, sign)))
+    };
+
+    let mut planets: Vec<&'static str> = Vec::new();
+    for (planet, exaltation) in EXALTATION_SIGNS {
+        if !facts.is_debilitated(planet) {
+            continue;
         }
-        if let Some(lon) = get_lon(malefic) {
-            let house = get_house_number(lon, ascendant);
-            if matches!(house, 6 | 8 | 12) {
-                yogas.push(Yoga {
-                    name: "Vipreet Raj Yoga".to_string(),
-                    yoga_type: "benefic".to_string(),
-                    description: format!("{} in {}th house - turns adversity into success", 
-                        malefic.chars().next().unwrap().to_uppercase().collect::<String>() + &malefic[1..], 
-                        house),
-                });
-                break;
+        let cancellers = [SIGN_LORDS[(exaltation + 6) % 12], SIGN_LORDS[*exaltation]];
+        if let Some(canceller) = cancellers.into_iter().find(|lord| in_kendra(lord)) {
+            planets.push(planet);
+            if !planets.contains(&canceller) {
+                planets.push(canceller);
             }
         }
     }
-    
-    // 10. Pancha Mahapurusha Yoga - Strong planets in own signs or exaltation
-    // Simplified - checks for planets in angular houses
-    let mut strong_planets = Vec::new();
-    for planet in &["sun", "moon", "mars", "mercury", "jupiter", "venus", "saturn"] {
-        if let Some(lon) = get_lon(planet) {
-            if is_in_kendra(lon, ascendant) {
-                strong_planets.push(*planet);
+    (!planets.is_empty()).then_some(planets)
+}
+
+fn mahapurusha(facts: &ChartFacts, planet: &'static str) -> Option<Vec<&'static str>> {
+    let lon = facts.lon(planet)?;
+    (facts.is_exalted_or_own(planet) && is_in_kendra(lon, facts.ascendant)).then(|| vec![planet])
+}
+
+fn kartari(facts: &ChartFacts, group: &[&'static str]) -> Option<Vec<&'static str>> {
+    let moon = facts.sign("moon")?;
+    let second = facts.occupants((moon + 1) % 12, group);
+    let twelfth = facts.occupants((moon + 11) % 12, group);
+    if second.is_empty() || twelfth.is_empty() {
+        return None;
+    }
+    Some(std::iter::once("moon").chain(second).chain(twelfth).collect())
+}
+
+/// Ashraya yogas; quality 0 = movable, 1 = fixed, 2 = dual
+fn ashraya(facts: &ChartFacts, quality: usize) -> Option<Vec<&'static str>> {
+    if !facts.has_all(SEVEN_PLANETS) {
+        return None;
+    }
+    SEVEN_PLANETS
+        .iter()
+        .all(|planet| facts.sign(planet).is_some_and(|sign| sign % 3 == quality))
+        .then(|| SEVEN_PLANETS.to_vec())
+}
+
+/// Dala yogas: the group occupies three of the four kendras
+fn dala(facts: &ChartFacts, group: &[&'static str]) -> Option<Vec<&'static str>> {
+    let occupied: Vec<usize> = KENDRAS
+        .iter()
+        .copied()
+        .filter(|kendra| group.iter().any(|planet| facts.house(planet) == Some(*kendra)))
+        .collect();
+    if occupied.len() < 3 {
+        return None;
+    }
+    Some(
+        group
+            .iter()
+            .copied()
+            .filter(|planet| facts.house(planet).is_some_and(|h| occupied.contains(&h)))
+            .collect(),
+    )
+}
+
+/// Sankhya yogas: number of signs occupied by the seven planets
+fn sankhya(facts: &ChartFacts, signs: usize) -> Option<Vec<&'static str>> {
+    if !facts.has_all(SEVEN_PLANETS) {
+        return None;
+    }
+    let mut occupied: Vec<usize> = SEVEN_PLANETS.iter().filter_map(|planet| facts.sign(planet)).collect();
+    occupied.sort_unstable();
+    occupied.dedup();
+    (occupied.len() == signs).then(|| SEVEN_PLANETS.to_vec())
+}
+
+fn detect_kemadruma(facts: &ChartFacts) -> Option<Vec<&'static str>> {
+    let moon = facts.sign("moon")?;
+    let supporters = ["mars", "mercury", "jupiter", "venus", "saturn"];
+    let supported = [moon, (moon + 1) % 12, (moon + 11) % 12]
+        .iter()
+        .any(|sign| !facts.occupants(*sign, &supporters).is_empty());
+    (!supported).then(|| vec!["moon"])
+}
+
+fn detect_shakata(facts: &ChartFacts) -> Option<Vec<&'static str>> {
+    let distance = sign_distance(facts.sign("jupiter")?, facts.sign("moon")?);
+    DUSTHANAS.contains(&distance).then(|| vec!["moon", "jupiter"])
+}
+
+fn detect_grahan(facts: &ChartFacts) -> Option<Vec<&'static str>> {
+    let mut planets: Vec<&'static str> = Vec::new();
+    for luminary in ["sun", "moon"] {
+        for node in ["rahu", "ketu"] {
+            if same_sign(facts, luminary, node).is_some() {
+                planets.extend([luminary, node]);
             }
         }
     }
-    
-    if strong_planets.len() >= 3 {
-        yogas.push(Yoga {
-            name: "Pancha Mahapurusha Yoga".to_string(),
-            yoga_type: "benefic".to_string(),
-            description: "Multiple planets in angular houses - brings great achievements".to_string(),
-        });
+    (!planets.is_empty()).then_some(planets)
+}
+
+fn detect_kala_sarpa(facts: &ChartFacts) -> Option<Vec<&'static str>> {
+    let rahu = facts.lon("rahu")?;
+    if !facts.has_all(SEVEN_PLANETS) {
+        return None;
+    }
+    let sides: Vec<bool> = SEVEN_PLANETS
+        .iter()
+        .filter_map(|planet| facts.lon(planet))
+        .map(|lon| normalize_degrees(lon - rahu) < 180.0)
+        .collect();
+    let one_side = sides.iter().all(|side| *side) || sides.iter().all(|side| !*side);
+    one_side.then(|| vec!["rahu", "ketu"])
+}
+
+/// Strength estimate for a matched rule
+fn yoga_strength(facts: &ChartFacts, rule: &YogaRule, planets: &[&str]) -> f64 {
+    if planets.is_empty() {
+        return NEUTRAL_DIGNITY;
+    }
+    let mut strength = planets.iter().map(|planet| facts.dignity(planet)).sum::<f64>() / planets.len() as f64;
+    if rule.conjunction {
+        // Same-sign conjunctions weaken towards half strength as the planets separate
+        if let (Some(a), Some(b)) = (facts.lon(planets[0]), facts.lon(planets[1])) {
+            strength *= 1.0 - angular_difference(a, b) / 60.0;
+        }
     }
-    
-    yogas
+    (strength * 100.0).round() / 100.0
+}
+
+/// Identify classic Vedic yogas from planetary positions.
+///
+/// Needs the ascendant; returns an empty list without houses.
+pub fn identify_yogas(layer_positions: &LayerPositions) -> Vec<Yoga> {
+    let Some(facts) = ChartFacts::from_layer(layer_positions) else {
+        return Vec::new();
+    };
+
+    YOGA_RULES
+        .iter()
+        .filter_map(|rule| {
+            let planets = (rule.detect)(&facts)?;
+            Some(Yoga {
+                name: rule.name.to_string(),
+                yoga_type: rule.yoga_type.to_string(),
+                category: rule.category.to_string(),
+                description: rule.description.to_string(),
+                strength: yoga_strength(&facts, rule, &planets),
+                planets: planets.iter().map(|planet| planet.to_string()).collect(),
+                rule: rule.rule.to_string(),
+            })
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+    use crate::ephemeris::types::{HousePositions, PlanetPosition};
+
+    fn layer(asc: f64, planets: &[(&str, f64)]) -> LayerPositions {
+        let planets = planets
+            .iter()
+            .map(|(id, lon)| (id.to_string(), PlanetPosition { lon: *lon, lat: 0.0, speed_lon: 1.0, retrograde: false }))
+            .collect();
+        LayerPositions {
+            planets,
+            houses: Some(HousePositions {
+                system: "whole_sign".to_string(),
+                cusps: HashMap::new(),
+                angles: HashMap::from([("asc".to_string(), asc)]),
+            }),
+        }
+    }
+
+    fn find<'a>(yogas: &'a [Yoga], name: &str) -> Option<&'a Yoga> {
+        yogas.iter().find(|yoga| yoga.name == name)
+    }
+
     #[test]
     fn test_angular_difference() {
         assert!((angular_difference(0.0, 10.0) - 10.0).abs() < 0.01);
         assert!((angular_difference(350.0, 10.0) - 20.0).abs() < 0.01);
     }
-    
+
     #[test]
     fn test_get_house_number() {
         let asc = 0.0; // Aries rising
         assert_eq!(get_house_number(0.0, asc), 1);
         assert_eq!(get_house_number(30.0, asc), 2);
         assert_eq!(get_house_number(90.0, asc), 4);
+        // Whole-sign: the whole lagna sign is the 1st house
+        assert_eq!(get_house_number(5.0, 25.0), 1);
     }
-    
+
     #[test]
     fn test_is_in_kendra() {
         let asc = 0.0;
@@ -297,5 +746,66 @@ mod tests {
         assert!(is_in_kendra(270.0, asc)); // 10th house
         assert!(!is_in_kendra(60.0, asc)); // 3rd house
     }
-}
 
+    #[test]
+    fn test_gajakesari_and_mahapurusha() {
+        // Aries lagna: Jupiter exalted in Cancer (4th, kendra), 4th from the Moon in Aries
+        let yogas = identify_yogas(&layer(0.0, &[("jupiter", 95.0), ("moon", 10.0)]));
+        let gajakesari = find(&yogas, "Gajakesari Yoga").unwrap();
+        assert_eq!(gajakesari.category, "chandra");
+        assert_eq!(gajakesari.planets, vec!["jupiter", "moon"]);
+        let hamsa = find(&yogas, "Hamsa Yoga").unwrap();
+        assert_eq!(hamsa.category, "mahapurusha");
+        assert_eq!(hamsa.strength, 1.0);
+        assert!(find(&yogas, "Malavya Yoga").is_none());
+    }
+
+    #[test]
+    fn test_raja_yoga_from_lords() {
+        // Cancer lagna: Mars rules the 5th and 10th, so it is a yogakaraka on its own;
+        // Moon (1st lord) with Jupiter (9th lord) in Cancer joins lagna and trikona lords
+        let yogas = identify_yogas(&layer(95.0, &[("moon", 100.0), ("jupiter", 110.0), ("mars", 200.0)]));
+        let raja = find(&yogas, "Raj Yoga").unwrap();
+        assert_eq!(raja.category, "raja");
+        assert!(raja.planets.contains(&"mars".to_string()));
+        assert!(raja.planets.contains(&"jupiter".to_string()));
+        assert!(raja.planets.contains(&"moon".to_string()));
+        assert!(raja.rule.contains("kendra"));
+    }
+
+    #[test]
+    fn test_nabhasa_sankhya() {
+        // Seven planets in three signs
+        let yogas = identify_yogas(&layer(0.0, &[
+            ("sun", 10.0), ("moon", 12.0), ("mars", 40.0), ("mercury", 42.0),
+            ("jupiter", 44.0), ("venus", 130.0), ("saturn", 135.0),
+        ]));
+        let shoola = find(&yogas, "Shoola Yoga").unwrap();
+        assert_eq!(shoola.category, "nabhasa");
+        assert_eq!(shoola.planets.len(), 7);
+        assert!(find(&yogas, "Kedara Yoga").is_none());
+    }
+
+    #[test]
+    fn test_affliction_yogas() {
+        // All planets between Rahu (0 Aries) and Ketu (0 Libra); Jupiter with Rahu
+        let yogas = identify_yogas(&layer(0.0, &[
+            ("sun", 20.0), ("moon", 150.0), ("mars", 60.0), ("mercury", 35.0),
+            ("jupiter", 5.0), ("venus", 50.0), ("saturn", 100.0), ("north_node", 2.0),
+        ]));
+        assert!(find(&yogas, "Kala Sarpa Yoga").is_some());
+        let guru_chandal = find(&yogas, "Guru Chandal Yoga").unwrap();
+        assert_eq!(guru_chandal.category, "affliction");
+        assert_eq!(guru_chandal.planets, vec!["jupiter", "rahu"]);
+        // 3 degrees apart: slightly below the mean dignity of 0.5
+        assert!(guru_chandal.strength > 0.45 && guru_chandal.strength < 0.5);
+        assert!(find(&yogas, "Grahan Yoga").is_some());
+    }
+
+    #[test]
+    fn test_requires_ascendant() {
+        let mut positions = layer(0.0, &[("sun", 10.0), ("mercury", 12.0)]);
+        positions.houses = None;
+        assert!(identify_yogas(&positions).is_empty());
+    }
+}
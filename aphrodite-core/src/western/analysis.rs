@@ -0,0 +1,323 @@
+//! Chart ruler, almuten figuris and dispositor analysis.
+//!
+//! The chart ruler is the traditional ruler of the ascendant sign. The almuten figuris
+//! is the planet with the most essential dignity across the Sun, Moon, ascendant,
+//! Part of Fortune and prenatal syzygy. Dispositor chains follow traditional domicile
+//! rulers until they reach a self-disposed planet, a loop, or a planet not in the chart.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ephemeris::types::LayerPositions;
+use crate::western::essential::{dignity_points_at, domicile_ruler, TRADITIONAL_PLANETS};
+
+/// Mean relative speed of the Moon from the Sun (degrees per day)
+const MEAN_SYNODIC_SPEED: f64 = 12.19;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Almuten {
+    /// Winning planet(s); more than one on a tie
+    pub planets: Vec<String>,
+    pub score: u32,
+    /// Total points per traditional planet
+    pub scores: HashMap<String, u32>,
+    /// Longitudes of the points that were scored ("sun", "moon", "asc", "fortune", "syzygy")
+    pub points: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispositorChain {
+    pub planet: String,
+    /// Planet followed by its successive dispositors
+    pub chain: Vec<String>,
+    /// "final" (ends at a self-disposed planet), "loop", or "open" (dispositor not in the chart)
+    pub terminal: String,
+    #[serde(rename = "finalDispositor", skip_serializing_if = "Option::is_none")]
+    pub final_dispositor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DispositorAnalysis {
+    /// Planet -> traditional domicile ruler of its sign
+    pub dispositors: HashMap<String, String>,
+    pub chains: Vec<DispositorChain>,
+    /// Planets in their own domicile
+    #[serde(rename = "finalDispositors")]
+    pub final_dispositors: Vec<String>,
+    /// Mutual receptions and longer dispositor cycles
+    pub loops: Vec<Vec<String>>,
+    /// Set when every chain ends at the same final dispositor
+    #[serde(rename = "soleFinalDispositor", skip_serializing_if = "Option::is_none")]
+    pub sole_final_dispositor: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WesternAnalysis {
+    #[serde(rename = "chartRuler", skip_serializing_if = "Option::is_none")]
+    pub chart_ruler: Option<String>,
+    /// "day" or "night"; requires the ascendant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sect: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub almuten: Option<Almuten>,
+    pub dispositors: DispositorAnalysis,
+}
+
+/// Whether the Sun is above the horizon (houses 7-12)
+pub fn is_day_chart(sun_lon: f64, asc: f64) -> bool {
+    (sun_lon - asc).rem_euclid(360.0) >= 180.0
+}
+
+/// Part of Fortune, reversed by night
+pub fn part_of_fortune(asc: f64, sun_lon: f64, moon_lon: f64, is_day: bool) -> f64 {
+    if is_day {
+        (asc + moon_lon - sun_lon).rem_euclid(360.0)
+    } else {
+        (asc + sun_lon - moon_lon).rem_euclid(360.0)
+    }
+}
+
+/// Approximate longitude of the prenatal new or full moon.
+///
+/// Extrapolates back from the current speeds to the most recent conjunction or
+/// opposition; the new moon degree is the conjunction degree, the full moon degree
+/// is the Moon's position at the opposition.
+pub fn prenatal_syzygy(sun_lon: f64, sun_speed: f64, moon_lon: f64, moon_speed: f64) -> f64 {
+    let relative_speed = if moon_speed - sun_speed > 0.0 {
+        moon_speed - sun_speed
+    } else {
+        MEAN_SYNODIC_SPEED
+    };
+    let elongation = (moon_lon - sun_lon).rem_euclid(360.0);
+    let days_since_new = elongation / relative_speed;
+    let days_since_full = (elongation - 180.0).rem_euclid(360.0) / relative_speed;
+
+    if days_since_new <= days_since_full {
+        (sun_lon - sun_speed * days_since_new).rem_euclid(360.0)
+    } else {
+        (moon_lon - moon_speed * days_since_full).rem_euclid(360.0)
+    }
+}
+
+/// Compute the almuten figuris from the scored points
+pub fn compute_almuten(points: HashMap<String, f64>, is_day: bool) -> Almuten {
+    let mut scores: HashMap<String, u32> =
+        TRADITIONAL_PLANETS.iter().map(|p| (p.to_string(), 0)).collect();
+    for lon in points.values() {
+        for (planet, points) in dignity_points_at(*lon, is_day) {
+            *scores.entry(planet.to_string()).or_insert(0) += points;
+        }
+    }
+
+    let score = scores.values().copied().max().unwrap_or(0);
+    // Traditional order keeps ties stable
+    let planets = TRADITIONAL_PLANETS
+        .iter()
+        .filter(|p| scores.get(**p) == Some(&score))
+        .map(|p| p.to_string())
+        .collect();
+
+    Almuten {
+        planets,
+        score,
+        scores,
+        points,
+    }
+}
+
+/// Compute dispositors, chains, final dispositors and loops
+pub fn compute_dispositors(positions: &LayerPositions) -> DispositorAnalysis {
+    let mut planet_ids: Vec<&String> = positions.planets.keys().collect();
+    planet_ids.sort();
+
+    let dispositors: HashMap<String, String> = planet_ids
+        .iter()
+        .map(|id| ((*id).clone(), domicile_ruler(positions.planets[*id].lon)))
+        .collect();
+
+    let final_dispositors: Vec<String> = planet_ids
+        .iter()
+        .filter(|id| dispositors.get(**id) == Some(*id))
+        .map(|id| (*id).clone())
+        .collect();
+
+    let mut chains = Vec::new();
+    let mut loops: Vec<Vec<String>> = Vec::new();
+
+    for planet_id in &planet_ids {
+        let mut chain = vec![(*planet_id).clone()];
+        let (terminal, final_dispositor) = loop {
+            let current = chain.last().unwrap();
+            let next = &dispositors[current];
+
+            if next == current {
+                break ("final", Some(current.clone()));
+            }
+            if !dispositors.contains_key(next) {
+                break ("open", None);
+            }
+            if let Some(start) = chain.iter().position(|p| p == next) {
+                let mut cycle = chain[start..].to_vec();
+                // Rotate so identical loops found from different planets compare equal
+                let min_index = cycle
+                    .iter()
+                    .enumerate()
+                    .min_by(|a, b| a.1.cmp(b.1))
+                    .map(|(i, _)| i)
+                    .unwrap_or(0);
+                cycle.rotate_left(min_index);
+                if !loops.contains(&cycle) {
+                    loops.push(cycle);
+                }
+                break ("loop", None);
+            }
+            chain.push(next.clone());
+        };
+
+        chains.push(DispositorChain {
+            planet: (*planet_id).clone(),
+            chain,
+            terminal: terminal.to_string(),
+            final_dispositor,
+        });
+    }
+
+    let sole_final_dispositor = match chains.first().and_then(|c| c.final_dispositor.clone()) {
+        Some(first) if chains.iter().all(|c| c.final_dispositor.as_ref() == Some(&first)) => Some(first),
+        _ => None,
+    };
+
+    DispositorAnalysis {
+        dispositors,
+        chains,
+        final_dispositors,
+        loops,
+        sole_final_dispositor,
+    }
+}
+
+/// Run the full analysis for a layer
+pub fn compute_analysis(positions: &LayerPositions) -> WesternAnalysis {
+    let asc = positions.houses.as_ref().and_then(|h| h.angles.get("asc").copied());
+    let sun = positions.planets.get("sun");
+    let moon = positions.planets.get("moon");
+
+    let sect = match (asc, sun) {
+        (Some(asc), Some(sun)) => Some(is_day_chart(sun.lon, asc)),
+        _ => None,
+    };
+
+    let almuten = match (asc, sun, moon, sect) {
+        (Some(asc), Some(sun), Some(moon), Some(is_day)) => {
+            let points: HashMap<String, f64> = [
+                ("sun", sun.lon),
+                ("moon", moon.lon),
+                ("asc", asc),
+                ("fortune", part_of_fortune(asc, sun.lon, moon.lon, is_day)),
+                ("syzygy", prenatal_syzygy(sun.lon, sun.speed_lon, moon.lon, moon.speed_lon)),
+            ]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+            Some(compute_almuten(points, is_day))
+        }
+        _ => None,
+    };
+
+    WesternAnalysis {
+        chart_ruler: asc.map(domicile_ruler),
+        sect: sect.map(|is_day| if is_day { "day" } else { "night" }.to_string()),
+        almuten,
+        dispositors: compute_dispositors(positions),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::{HousePositions, PlanetPosition};
+
+    fn layer(planets: &[(&str, f64, f64)], asc: Option<f64>) -> LayerPositions {
+        let planets = planets
+            .iter()
+            .map(|(id, lon, speed)| {
+                (id.to_string(), PlanetPosition { lon: *lon, lat: 0.0, speed_lon: *speed, retrograde: false })
+            })
+            .collect();
+        let houses = asc.map(|asc| HousePositions {
+            system: "equal".to_string(),
+            cusps: HashMap::new(),
+            angles: [("asc".to_string(), asc)].into_iter().collect(),
+        });
+        LayerPositions { planets, houses }
+    }
+
+    #[test]
+    fn test_sect_and_fortune() {
+        // Sun just above the ascendant is in the 12th house
+        assert!(is_day_chart(350.0, 0.0));
+        assert!(!is_day_chart(10.0, 0.0));
+        assert_eq!(part_of_fortune(0.0, 90.0, 120.0, true), 30.0);
+        assert_eq!(part_of_fortune(0.0, 90.0, 120.0, false), 330.0);
+    }
+
+    #[test]
+    fn test_prenatal_syzygy() {
+        // Moon 24 degrees past the Sun: new moon two days ago at ~98
+        let syzygy = prenatal_syzygy(100.0, 1.0, 124.0, 13.0);
+        assert!((syzygy - 98.0).abs() < 1e-9);
+        // Moon 190 degrees past the Sun: full moon under a day ago, Moon then at ~279.2
+        let syzygy = prenatal_syzygy(100.0, 1.0, 290.0, 13.0);
+        assert!((syzygy - (290.0 - 13.0 * 10.0 / 12.0)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_dispositor_final_and_chain() {
+        // Mercury in Leo -> Sun; Sun in Leo is self-disposed
+        let analysis = compute_dispositors(&layer(&[("sun", 130.0, 1.0), ("mercury", 140.0, 1.0)], None));
+        assert_eq!(analysis.final_dispositors, vec!["sun"]);
+        assert_eq!(analysis.sole_final_dispositor.as_deref(), Some("sun"));
+        let mercury = analysis.chains.iter().find(|c| c.planet == "mercury").unwrap();
+        assert_eq!(mercury.chain, vec!["mercury", "sun"]);
+        assert_eq!(mercury.terminal, "final");
+        assert!(analysis.loops.is_empty());
+    }
+
+    #[test]
+    fn test_dispositor_mutual_reception_loop() {
+        // Venus in Aries (Mars), Mars in Taurus (Venus), Moon in Libra (Venus)
+        let analysis = compute_dispositors(&layer(
+            &[("venus", 10.0, 1.0), ("mars", 40.0, 0.5), ("moon", 190.0, 13.0)],
+            None,
+        ));
+        assert!(analysis.final_dispositors.is_empty());
+        assert_eq!(analysis.loops, vec![vec!["mars".to_string(), "venus".to_string()]]);
+        let moon = analysis.chains.iter().find(|c| c.planet == "moon").unwrap();
+        assert_eq!(moon.terminal, "loop");
+        assert!(analysis.sole_final_dispositor.is_none());
+    }
+
+    #[test]
+    fn test_dispositor_open_chain() {
+        // Moon in Capricorn is disposed by Saturn, which is not in the chart
+        let analysis = compute_dispositors(&layer(&[("moon", 280.0, 13.0)], None));
+        assert_eq!(analysis.chains[0].terminal, "open");
+        assert_eq!(analysis.dispositors["moon"], "saturn");
+    }
+
+    #[test]
+    fn test_compute_analysis() {
+        let positions = layer(&[("sun", 10.0, 1.0), ("moon", 40.0, 13.0)], Some(130.0));
+        let analysis = compute_analysis(&positions);
+        assert_eq!(analysis.chart_ruler.as_deref(), Some("sun"));
+        assert_eq!(analysis.sect.as_deref(), Some("day"));
+        let almuten = analysis.almuten.unwrap();
+        assert_eq!(almuten.points.len(), 5);
+        assert!(!almuten.planets.is_empty());
+        assert_eq!(almuten.scores[&almuten.planets[0]], almuten.score);
+
+        // Without an ascendant there is no chart ruler, sect or almuten
+        let analysis = compute_analysis(&layer(&[("sun", 10.0, 1.0)], None));
+        assert!(analysis.chart_ruler.is_none());
+        assert!(analysis.almuten.is_none());
+    }
+}
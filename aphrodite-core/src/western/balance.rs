@@ -0,0 +1,213 @@
+//! Element, modality, quadrant and hemisphere balance for a chart layer.
+//!
+//! Each planet counts once in its bucket and adds its weight to the bucket score.
+//! Quadrants and hemispheres need house cusps and are omitted without them.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ephemeris::types::LayerPositions;
+use crate::layout::rings::get_house_index;
+use crate::western::rulers::get_sign_index;
+
+/// Weight of each object in the balance scores
+const PLANET_WEIGHTS: &[(&str, f64)] = &[
+    ("sun", 3.0),
+    ("moon", 3.0),
+    ("mercury", 2.0),
+    ("venus", 2.0),
+    ("mars", 2.0),
+    ("jupiter", 1.5),
+    ("saturn", 1.5),
+    ("uranus", 1.0),
+    ("neptune", 1.0),
+    ("pluto", 1.0),
+];
+
+/// Weight for objects not listed in PLANET_WEIGHTS (nodes, chiron, ...)
+const DEFAULT_WEIGHT: f64 = 0.5;
+
+const ELEMENTS: &[&str] = &["fire", "earth", "air", "water"];
+const MODALITIES: &[&str] = &["cardinal", "fixed", "mutable"];
+const QUADRANTS: &[&str] = &["1", "2", "3", "4"];
+const HEMISPHERES: &[&str] = &["eastern", "western", "northern", "southern"];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BalanceBucket {
+    pub count: u32,
+    pub score: f64,
+    pub planets: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceSummary {
+    pub elements: HashMap<String, BalanceBucket>,
+    pub modalities: HashMap<String, BalanceBucket>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quadrants: Option<HashMap<String, BalanceBucket>>, // "1".."4", counted from the ascendant
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hemispheres: Option<HashMap<String, BalanceBucket>>,
+    #[serde(rename = "dominantElement", skip_serializing_if = "Option::is_none")]
+    pub dominant_element: Option<String>,
+    #[serde(rename = "dominantModality", skip_serializing_if = "Option::is_none")]
+    pub dominant_modality: Option<String>,
+}
+
+/// Get the balance weight of a planet
+pub fn balance_weight(planet_id: &str) -> f64 {
+    PLANET_WEIGHTS
+        .iter()
+        .find(|(id, _)| *id == planet_id)
+        .map(|(_, w)| *w)
+        .unwrap_or(DEFAULT_WEIGHT)
+}
+
+/// Element of a sign index (0 = aries)
+pub fn element_for_sign(sign_index: u8) -> &'static str {
+    ELEMENTS[(sign_index % 4) as usize]
+}
+
+/// Modality of a sign index (0 = aries)
+pub fn modality_for_sign(sign_index: u8) -> &'static str {
+    MODALITIES[(sign_index % 3) as usize]
+}
+
+/// Hemispheres containing a house (1..=12)
+fn hemispheres_for_house(house: u8) -> [&'static str; 2] {
+    let east_west = if house >= 10 || house <= 3 { "eastern" } else { "western" };
+    let north_south = if house <= 6 { "northern" } else { "southern" };
+    [east_west, north_south]
+}
+
+fn empty_buckets(keys: &[&str]) -> HashMap<String, BalanceBucket> {
+    keys.iter().map(|k| (k.to_string(), BalanceBucket::default())).collect()
+}
+
+fn add(buckets: &mut HashMap<String, BalanceBucket>, key: &str, planet_id: &str, weight: f64) {
+    if let Some(bucket) = buckets.get_mut(key) {
+        bucket.count += 1;
+        bucket.score += weight;
+        bucket.planets.push(planet_id.to_string());
+    }
+}
+
+/// Highest-scoring bucket; None if empty or tied for first
+fn dominant(buckets: &HashMap<String, BalanceBucket>) -> Option<String> {
+    let mut ranked: Vec<(&String, f64)> = buckets.iter().map(|(k, b)| (k, b.score)).collect();
+    ranked.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+    match ranked.as_slice() {
+        [(key, top), rest @ ..] if *top > 0.0 && rest.first().is_none_or(|(_, next)| next < top) => {
+            Some((*key).clone())
+        }
+        _ => None,
+    }
+}
+
+/// Compute the balance summary for a layer
+pub fn compute_balance(positions: &LayerPositions) -> BalanceSummary {
+    let mut elements = empty_buckets(ELEMENTS);
+    let mut modalities = empty_buckets(MODALITIES);
+    let cusps = positions.houses.as_ref().map(|h| &h.cusps).filter(|c| !c.is_empty());
+    let mut quadrants = cusps.map(|_| empty_buckets(QUADRANTS));
+    let mut hemispheres = cusps.map(|_| empty_buckets(HEMISPHERES));
+
+    // Sorted for stable planet lists
+    let mut planet_ids: Vec<&String> = positions.planets.keys().collect();
+    planet_ids.sort();
+
+    for planet_id in planet_ids {
+        let lon = positions.planets[planet_id].lon.rem_euclid(360.0);
+        let weight = balance_weight(planet_id);
+        let sign_index = get_sign_index(lon);
+
+        add(&mut elements, element_for_sign(sign_index), planet_id, weight);
+        add(&mut modalities, modality_for_sign(sign_index), planet_id, weight);
+
+        if let Some(house) = cusps.and_then(|c| get_house_index(lon, c)).map(|i| i + 1) {
+            if let Some(quadrants) = quadrants.as_mut() {
+                add(quadrants, QUADRANTS[((house - 1) / 3) as usize], planet_id, weight);
+            }
+            if let Some(hemispheres) = hemispheres.as_mut() {
+                for hemisphere in hemispheres_for_house(house) {
+                    add(hemispheres, hemisphere, planet_id, weight);
+                }
+            }
+        }
+    }
+
+    BalanceSummary {
+        dominant_element: dominant(&elements),
+        dominant_modality: dominant(&modalities),
+        elements,
+        modalities,
+        quadrants,
+        hemispheres,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::{HousePositions, PlanetPosition};
+
+    fn layer(planets: &[(&str, f64)], asc: Option<f64>) -> LayerPositions {
+        let planets = planets
+            .iter()
+            .map(|(id, lon)| {
+                (id.to_string(), PlanetPosition { lon: *lon, lat: 0.0, speed_lon: 1.0, retrograde: false })
+            })
+            .collect();
+        // Equal houses from the ascendant
+        let houses = asc.map(|asc| HousePositions {
+            system: "equal".to_string(),
+            cusps: (1..=12).map(|n| (n.to_string(), (asc + (n - 1) as f64 * 30.0) % 360.0)).collect(),
+            angles: HashMap::new(),
+        });
+        LayerPositions { planets, houses }
+    }
+
+    #[test]
+    fn test_sign_qualities() {
+        assert_eq!(element_for_sign(0), "fire");
+        assert_eq!(element_for_sign(5), "earth");
+        assert_eq!(element_for_sign(10), "air");
+        assert_eq!(modality_for_sign(3), "cardinal");
+        assert_eq!(modality_for_sign(7), "fixed");
+        assert_eq!(modality_for_sign(11), "mutable");
+    }
+
+    #[test]
+    fn test_elements_and_modalities() {
+        // Sun in Aries, Moon in Leo, Saturn in Taurus
+        let summary = compute_balance(&layer(&[("sun", 10.0), ("moon", 130.0), ("saturn", 40.0)], None));
+        assert_eq!(summary.elements["fire"].count, 2);
+        assert_eq!(summary.elements["fire"].score, 6.0);
+        assert_eq!(summary.elements["fire"].planets, vec!["moon", "sun"]);
+        assert_eq!(summary.elements["earth"].count, 1);
+        assert_eq!(summary.elements["water"].count, 0);
+        assert_eq!(summary.modalities["fixed"].count, 2);
+        assert_eq!(summary.dominant_element.as_deref(), Some("fire"));
+        assert_eq!(summary.dominant_modality.as_deref(), Some("fixed"));
+        assert!(summary.quadrants.is_none());
+        assert!(summary.hemispheres.is_none());
+    }
+
+    #[test]
+    fn test_dominant_tie_is_none() {
+        let summary = compute_balance(&layer(&[("sun", 10.0), ("moon", 40.0)], None));
+        assert!(summary.dominant_element.is_none());
+    }
+
+    #[test]
+    fn test_quadrants_and_hemispheres() {
+        // Ascendant 0 Aries: sun in house 1, moon in house 8
+        let summary = compute_balance(&layer(&[("sun", 10.0), ("moon", 215.0)], Some(0.0)));
+        let quadrants = summary.quadrants.unwrap();
+        assert_eq!(quadrants["1"].planets, vec!["sun"]);
+        assert_eq!(quadrants["3"].planets, vec!["moon"]);
+        let hemispheres = summary.hemispheres.unwrap();
+        assert_eq!(hemispheres["eastern"].planets, vec!["sun"]);
+        assert_eq!(hemispheres["northern"].planets, vec!["sun"]);
+        assert_eq!(hemispheres["western"].planets, vec!["moon"]);
+        assert_eq!(hemispheres["southern"].planets, vec!["moon"]);
+    }
+}
@@ -0,0 +1,187 @@
+//! Planetary condition: solar phase, out-of-bounds declination and relative speed.
+//!
+//! Solar phase uses the traditional orbs: cazimi within 17', combust within 8°30' and
+//! under the beams within 17°. Declination is derived from ecliptic longitude and
+//! latitude with the J2000 mean obliquity; a planet is out of bounds when its
+//! declination exceeds the obliquity (the Sun's maximum declination).
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use crate::ephemeris::types::LayerPositions;
+
+/// Mean obliquity of the ecliptic at J2000 (degrees)
+pub const MEAN_OBLIQUITY: f64 = 23.4393;
+
+/// Cazimi orb: 17 arc minutes
+pub const CAZIMI_ORB: f64 = 17.0 / 60.0;
+/// Combustion orb
+pub const COMBUST_ORB: f64 = 8.5;
+/// Under the beams orb
+pub const UNDER_BEAMS_ORB: f64 = 17.0;
+
+/// Speed ratio below which a planet is considered stationary
+const STATIONARY_RATIO: f64 = 0.1;
+
+/// Mean geocentric daily motion (degrees per day)
+const MEAN_DAILY_MOTION: &[(&str, f64)] = &[
+    ("sun", 0.9856),
+    ("moon", 13.1764),
+    ("mercury", 1.383),
+    ("venus", 1.2),
+    ("mars", 0.524),
+    ("jupiter", 0.083),
+    ("saturn", 0.034),
+    ("uranus", 0.012),
+    ("neptune", 0.006),
+    ("pluto", 0.004),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlanetCondition {
+    /// "cazimi", "combust" or "underBeams"; omitted when clear of the Sun
+    #[serde(rename = "solarPhase", skip_serializing_if = "Option::is_none")]
+    pub solar_phase: Option<String>,
+    /// Angular distance from the Sun (degrees)
+    #[serde(rename = "sunDistance", skip_serializing_if = "Option::is_none")]
+    pub sun_distance: Option<f64>,
+    pub declination: f64,
+    #[serde(rename = "outOfBounds")]
+    pub out_of_bounds: bool,
+    /// |speed| / mean daily motion
+    #[serde(rename = "speedRatio", skip_serializing_if = "Option::is_none")]
+    pub speed_ratio: Option<f64>,
+    /// "fast", "slow" or "stationary"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<String>,
+}
+
+/// Get the mean daily motion of a planet, if known
+pub fn mean_daily_motion(planet_id: &str) -> Option<f64> {
+    MEAN_DAILY_MOTION
+        .iter()
+        .find(|(id, _)| *id == planet_id)
+        .map(|(_, motion)| *motion)
+}
+
+/// Declination from ecliptic longitude and latitude
+pub fn declination(lon: f64, lat: f64, obliquity: f64) -> f64 {
+    let (lon, lat, eps) = (lon.to_radians(), lat.to_radians(), obliquity.to_radians());
+    (lat.sin() * eps.cos() + lat.cos() * eps.sin() * lon.sin())
+        .asin()
+        .to_degrees()
+}
+
+/// Solar phase for a given distance from the Sun
+pub fn solar_phase(sun_distance: f64) -> Option<&'static str> {
+    if sun_distance <= CAZIMI_ORB {
+        Some("cazimi")
+    } else if sun_distance <= COMBUST_ORB {
+        Some("combust")
+    } else if sun_distance <= UNDER_BEAMS_ORB {
+        Some("underBeams")
+    } else {
+        None
+    }
+}
+
+/// Speed classification for a ratio to the mean daily motion
+pub fn speed_class(ratio: f64) -> &'static str {
+    if ratio < STATIONARY_RATIO {
+        "stationary"
+    } else if ratio >= 1.0 {
+        "fast"
+    } else {
+        "slow"
+    }
+}
+
+fn separation(a: f64, b: f64) -> f64 {
+    let diff = (a - b).rem_euclid(360.0);
+    diff.min(360.0 - diff)
+}
+
+/// Compute the condition of every planet in a layer
+pub fn compute_conditions(positions: &LayerPositions) -> HashMap<String, PlanetCondition> {
+    let sun_lon = positions.planets.get("sun").map(|sun| sun.lon);
+
+    positions
+        .planets
+        .iter()
+        .map(|(planet_id, pos)| {
+            let mean_motion = mean_daily_motion(planet_id);
+            // Solar phase only applies to bodies with a known mean motion (not nodes or points)
+            let sun_distance = sun_lon
+                .filter(|_| planet_id != "sun" && mean_motion.is_some())
+                .map(|sun_lon| separation(pos.lon, sun_lon));
+            let declination = declination(pos.lon, pos.lat, MEAN_OBLIQUITY);
+            let speed_ratio = mean_motion.map(|motion| pos.speed_lon.abs() / motion);
+
+            let condition = PlanetCondition {
+                solar_phase: sun_distance.and_then(solar_phase).map(str::to_string),
+                sun_distance,
+                declination,
+                out_of_bounds: declination.abs() > MEAN_OBLIQUITY,
+                speed_ratio,
+                speed: speed_ratio.map(|ratio| speed_class(ratio).to_string()),
+            };
+            (planet_id.clone(), condition)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ephemeris::types::PlanetPosition;
+
+    fn layer(planets: &[(&str, f64, f64, f64)]) -> LayerPositions {
+        let planets = planets
+            .iter()
+            .map(|(id, lon, lat, speed_lon)| {
+                let pos = PlanetPosition { lon: *lon, lat: *lat, speed_lon: *speed_lon, retrograde: *speed_lon < 0.0 };
+                (id.to_string(), pos)
+            })
+            .collect();
+        LayerPositions { planets, houses: None }
+    }
+
+    #[test]
+    fn test_declination() {
+        assert!(declination(0.0, 0.0, MEAN_OBLIQUITY).abs() < 1e-9);
+        assert!((declination(90.0, 0.0, MEAN_OBLIQUITY) - MEAN_OBLIQUITY).abs() < 1e-9);
+        assert!((declination(270.0, 0.0, MEAN_OBLIQUITY) + MEAN_OBLIQUITY).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_solar_phase() {
+        let conditions = compute_conditions(&layer(&[
+            ("sun", 100.0, 0.0, 0.98),
+            ("mercury", 100.1, 0.0, 1.5),
+            ("venus", 105.0, 0.0, 1.2),
+            ("mars", 355.0, 0.0, 0.6),
+            ("jupiter", 112.0, 0.0, 0.1),
+            ("mean_node", 100.0, 0.0, -0.05),
+        ]));
+        assert_eq!(conditions["mercury"].solar_phase.as_deref(), Some("cazimi"));
+        assert_eq!(conditions["venus"].solar_phase.as_deref(), Some("combust"));
+        assert_eq!(conditions["jupiter"].solar_phase.as_deref(), Some("underBeams"));
+        assert!(conditions["mars"].solar_phase.is_none());
+        assert!(conditions["sun"].sun_distance.is_none());
+        assert!(conditions["mean_node"].sun_distance.is_none());
+    }
+
+    #[test]
+    fn test_out_of_bounds_and_speed() {
+        // Moon at 0 Cancer with 5° north latitude is beyond the Sun's maximum declination
+        let conditions = compute_conditions(&layer(&[
+            ("moon", 90.0, 5.0, 14.5),
+            ("mars", 200.0, 0.0, 0.01),
+            ("saturn", 10.0, 0.0, 0.02),
+        ]));
+        assert!(conditions["moon"].out_of_bounds);
+        assert_eq!(conditions["moon"].speed.as_deref(), Some("fast"));
+        assert!(!conditions["mars"].out_of_bounds);
+        assert_eq!(conditions["mars"].speed.as_deref(), Some("stationary"));
+        assert_eq!(conditions["saturn"].speed.as_deref(), Some("slow"));
+    }
+}
@@ -1,6 +1,7 @@
 //! Western astrology decans calculations.
 //! 
-//! Each sign is divided into 3 decans (10 degrees each), with decan rulers based on element groups.
+//! Each sign is divided into 3 decans (10 degrees each). Decan rulers can be assigned
+//! either by element groups (triplicity / Hindu scheme) or by the Chaldean order of planets.
 
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +14,28 @@ pub enum Element {
     Water,
 }
 
+/// Scheme used to assign decan rulers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DecanSystem {
+    /// Rulers of the three signs of the same element, starting with the sign itself.
+    #[default]
+    Triplicity,
+    /// Planets in Chaldean order, starting with Mars for the first decan of Aries.
+    Chaldean,
+}
+
+impl DecanSystem {
+    /// Parse a decan system name ("triplicity" or "chaldean").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "triplicity" => Some(DecanSystem::Triplicity),
+            "chaldean" => Some(DecanSystem::Chaldean),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignMeta {
     pub name: String,
@@ -44,6 +67,11 @@ const SIGN_ORDER: &[&str] = &[
     "sagittarius", "capricorn", "aquarius", "pisces",
 ];
 
+/// Chaldean order of planets as it runs through the decans, beginning at 0° Aries.
+const CHALDEAN_DECAN_ORDER: &[&str] = &[
+    "mars", "sun", "venus", "mercury", "moon", "saturn", "jupiter",
+];
+
 // Build element groups
 lazy_static::lazy_static! {
     static ref ELEMENT_GROUPS: std::collections::HashMap<Element, Vec<SignMeta>> = {
@@ -71,6 +99,8 @@ pub struct DecanInfo {
     pub degree_in_sign: f64, // 0 <= x < 30
     #[serde(rename = "decanRuler")]
     pub decan_ruler: String,
+    #[serde(rename = "decanSystem", default)]
+    pub decan_system: DecanSystem,
 }
 
 /// Given degree in sign (0–29.999...), returns decan index 1, 2, or 3.
@@ -88,10 +118,19 @@ pub fn get_decan_index(degree_in_sign: f64) -> u8 {
     }
 }
 
-/// Compute decan info given a sign and degree in that sign.
+/// Compute decan info given a sign and degree in that sign (triplicity scheme).
 pub fn get_decan_info_for_sign_and_degree(
     sign: &str,
     degree_in_sign: f64,
+) -> Result<DecanInfo, String> {
+    get_decan_info_for_sign_and_degree_with_system(sign, degree_in_sign, DecanSystem::Triplicity)
+}
+
+/// Compute decan info given a sign, degree in that sign and decan ruler system.
+pub fn get_decan_info_for_sign_and_degree_with_system(
+    sign: &str,
+    degree_in_sign: f64,
+    system: DecanSystem,
 ) -> Result<DecanInfo, String> {
     let sign_meta = SIGNS.iter()
         .find(|s| s.name == sign)
@@ -99,16 +138,29 @@ pub fn get_decan_info_for_sign_and_degree(
     
     let decan_index = get_decan_index(degree_in_sign);
     
-    let group = ELEMENT_GROUPS.get(&sign_meta.element)
-        .ok_or_else(|| format!("Element group not found for {}", sign))?;
-    
-    let group_index = group.iter()
-        .position(|g| g.name == sign)
-        .ok_or_else(|| format!("Sign {} not found in element group", sign))?;
-    
-    // Rotate through the 3 rulers in the element group
-    let ruler_index = (group_index + (decan_index as usize - 1)) % group.len();
-    let decan_ruler = group[ruler_index].ruler.clone();
+    let decan_ruler = match system {
+        DecanSystem::Triplicity => {
+            let group = ELEMENT_GROUPS.get(&sign_meta.element)
+                .ok_or_else(|| format!("Element group not found for {}", sign))?;
+            
+            let group_index = group.iter()
+                .position(|g| g.name == sign)
+                .ok_or_else(|| format!("Sign {} not found in element group", sign))?;
+            
+            // Rotate through the 3 rulers in the element group
+            let ruler_index = (group_index + (decan_index as usize - 1)) % group.len();
+            group[ruler_index].ruler.clone()
+        }
+        DecanSystem::Chaldean => {
+            let sign_index = SIGN_ORDER.iter()
+                .position(|&name| name == sign)
+                .ok_or_else(|| format!("Unknown sign: {}", sign))?;
+            
+            // 36 decans cycle continuously through the 7 Chaldean planets
+            let decan_number = sign_index * 3 + (decan_index as usize - 1);
+            CHALDEAN_DECAN_ORDER[decan_number % CHALDEAN_DECAN_ORDER.len()].to_string()
+        }
+    };
     
     Ok(DecanInfo {
         sign: sign.to_string(),
@@ -117,17 +169,23 @@ pub fn get_decan_info_for_sign_and_degree(
         decan_index,
         degree_in_sign,
         decan_ruler,
+        decan_system: system,
     })
 }
 
-/// Optional helper: from absolute longitude 0–360.
+/// Optional helper: from absolute longitude 0–360 (triplicity scheme).
 pub fn get_decan_info_from_longitude(longitude: f64) -> DecanInfo {
+    get_decan_info_from_longitude_with_system(longitude, DecanSystem::Triplicity)
+}
+
+/// From absolute longitude 0–360 using the given decan ruler system.
+pub fn get_decan_info_from_longitude_with_system(longitude: f64, system: DecanSystem) -> DecanInfo {
     let lon = ((longitude % 360.0) + 360.0) % 360.0; // normalize
     let sign_index = (lon / 30.0) as usize;
     let degree_in_sign = lon - (sign_index as f64 * 30.0);
     let sign = SIGN_ORDER[sign_index % 12];
     
-    get_decan_info_for_sign_and_degree(sign, degree_in_sign)
+    get_decan_info_for_sign_and_degree_with_system(sign, degree_in_sign, system)
         .expect("Failed to get decan info")
 }
 
@@ -156,6 +214,39 @@ mod tests {
         assert_eq!(info.sign_ruler, "mars");
         // First decan of Aries (fire) should be ruled by Mars (first in fire group)
         assert_eq!(info.decan_ruler, "mars");
+        assert_eq!(info.decan_system, DecanSystem::Triplicity);
+    }
+    
+    #[test]
+    fn test_triplicity_decan_rulers() {
+        // Leo: Sun, Jupiter (Sagittarius), Mars (Aries)
+        assert_eq!(get_decan_info_from_longitude(125.0).decan_ruler, "sun");
+        assert_eq!(get_decan_info_from_longitude(135.0).decan_ruler, "jupiter");
+        assert_eq!(get_decan_info_from_longitude(145.0).decan_ruler, "mars");
+    }
+    
+    #[test]
+    fn test_chaldean_decan_rulers() {
+        let rulers: Vec<String> = (0..6)
+            .map(|i| {
+                get_decan_info_from_longitude_with_system(i as f64 * 10.0 + 5.0, DecanSystem::Chaldean)
+                    .decan_ruler
+            })
+            .collect();
+        // Aries: Mars, Sun, Venus; Taurus: Mercury, Moon, Saturn
+        assert_eq!(rulers, vec!["mars", "sun", "venus", "mercury", "moon", "saturn"]);
+        
+        // Last decan of Pisces closes the cycle with Mars
+        let last = get_decan_info_from_longitude_with_system(355.0, DecanSystem::Chaldean);
+        assert_eq!(last.decan_ruler, "mars");
+        assert_eq!(last.decan_system, DecanSystem::Chaldean);
+    }
+    
+    #[test]
+    fn test_decan_system_from_name() {
+        assert_eq!(DecanSystem::from_name("chaldean"), Some(DecanSystem::Chaldean));
+        assert_eq!(DecanSystem::from_name("triplicity"), Some(DecanSystem::Triplicity));
+        assert_eq!(DecanSystem::from_name("unknown"), None);
     }
 }
 
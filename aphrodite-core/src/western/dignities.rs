@@ -1,8 +1,14 @@
 //! Dignities calculation for Western astrology.
 //! 
-//! Calculates rulership, detriment, exaltation, fall, and exact exaltation for planets.
+//! Calculates rulership, detriment, exaltation, fall, and exact exaltation for planets,
+//! plus traditional essential dignity scores (triplicity, terms and faces).
 
 use serde::{Deserialize, Serialize};
+use crate::western::essential::{
+    domicile_ruler, exaltation_ruler, face_ruler, term_ruler, triplicity_ruler, TermSystem,
+    DOMICILE_POINTS, EXALTATION_POINTS, FACE_POINTS, TERM_POINTS, TRADITIONAL_PLANETS,
+    TRIPLICITY_POINTS,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -12,6 +18,26 @@ pub enum DignityType {
     Exaltation,
     Fall,
     ExactExaltation,
+    Triplicity,
+    Term,
+    Face,
+}
+
+/// Points subtracted for detriment
+pub const DETRIMENT_POINTS: i32 = -5;
+/// Points subtracted for fall
+pub const FALL_POINTS: i32 = -4;
+/// Points subtracted when a planet has no essential dignity (Lilly)
+pub const PEREGRINE_POINTS: i32 = -5;
+
+/// Traditional essential dignity score for a planet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EssentialDignityScore {
+    #[serde(rename = "planetId")]
+    pub planet_id: String,
+    pub dignities: Vec<DignityResult>,
+    pub peregrine: bool,
+    pub score: i32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -358,6 +384,62 @@ impl DignitiesService {
         result
     }
     
+    /// Score a traditional planet's essential dignities.
+    ///
+    /// Domicile +5, exaltation +4, triplicity +3, term +2, face +1, detriment -5,
+    /// fall -4, and -5 when peregrine. Triplicity uses the sect ruler and is skipped
+    /// when the sect is unknown. Returns None for non-traditional planets.
+    pub fn get_essential_dignities(
+        &self,
+        planet_id: &str,
+        longitude: f64,
+        is_day: Option<bool>,
+        term_system: TermSystem,
+    ) -> Option<EssentialDignityScore> {
+        let planet_id = planet_id.to_lowercase();
+        if !TRADITIONAL_PLANETS.contains(&planet_id.as_str()) {
+            return None;
+        }
+
+        let longitude = longitude.rem_euclid(360.0);
+        let opposite = (longitude + 180.0) % 360.0;
+        let mut held: Vec<(DignityType, i32)> = Vec::new();
+
+        if domicile_ruler(longitude) == planet_id {
+            held.push((DignityType::Rulership, DOMICILE_POINTS as i32));
+        }
+        if exaltation_ruler(longitude) == Some(planet_id.as_str()) {
+            held.push((DignityType::Exaltation, EXALTATION_POINTS as i32));
+        }
+        if is_day.is_some_and(|is_day| triplicity_ruler(longitude, is_day) == planet_id) {
+            held.push((DignityType::Triplicity, TRIPLICITY_POINTS as i32));
+        }
+        if term_ruler(longitude, term_system) == planet_id {
+            held.push((DignityType::Term, TERM_POINTS as i32));
+        }
+        if face_ruler(longitude) == planet_id {
+            held.push((DignityType::Face, FACE_POINTS as i32));
+        }
+        let peregrine = held.is_empty();
+
+        if domicile_ruler(opposite) == planet_id {
+            held.push((DignityType::Detriment, DETRIMENT_POINTS));
+        }
+        if exaltation_ruler(opposite) == Some(planet_id.as_str()) {
+            held.push((DignityType::Fall, FALL_POINTS));
+        }
+
+        let sign = get_sign_name(get_sign_index(longitude));
+        let score = held.iter().map(|(_, points)| points).sum::<i32>()
+            + if peregrine { PEREGRINE_POINTS } else { 0 };
+        let dignities = held
+            .into_iter()
+            .map(|(dignity_type, _)| DignityResult { dignity_type, sign: sign.clone(), degree: None })
+            .collect();
+
+        Some(EssentialDignityScore { planet_id, dignities, peregrine, score })
+    }
+
     /// Get default exact exaltation positions (based on Aleister Crowley)
     pub fn get_default_exact_exaltations() -> Vec<ExactExaltation> {
         vec![
@@ -391,5 +473,34 @@ mod tests {
         let dignities = service.get_dignities("moon", 105.0, None);
         assert!(dignities.iter().any(|d| d.dignity_type == DignityType::Rulership));
     }
-}
 
+    #[test]
+    fn test_essential_dignities_score() {
+        let service = DignitiesService;
+        // Sun at 10 Leo by day: domicile and day triplicity
+        let sun = service.get_essential_dignities("sun", 130.0, Some(true), TermSystem::Egyptian).unwrap();
+        assert_eq!(sun.score, 5 + 3);
+        assert!(!sun.peregrine);
+        // By night Jupiter rules the fire triplicity
+        let sun = service.get_essential_dignities("sun", 130.0, Some(false), TermSystem::Egyptian).unwrap();
+        assert_eq!(sun.score, 5);
+        // Mars at 25 Leo: face (Chaldean decan) and Egyptian term
+        let mars = service.get_essential_dignities("mars", 145.0, None, TermSystem::Egyptian).unwrap();
+        assert_eq!(mars.score, 2 + 1);
+    }
+
+    #[test]
+    fn test_essential_dignities_debilities() {
+        let service = DignitiesService;
+        // Saturn at 1 Aries: fall, Saturn holds no dignity there, so also peregrine
+        let saturn = service.get_essential_dignities("saturn", 1.0, Some(true), TermSystem::Egyptian).unwrap();
+        assert!(saturn.peregrine);
+        assert!(saturn.dignities.iter().any(|d| d.dignity_type == DignityType::Fall));
+        assert_eq!(saturn.score, -4 - 5);
+        // Venus at 13 Aries: detriment, but Ptolemaic term ruler
+        let venus = service.get_essential_dignities("venus", 13.0, None, TermSystem::Ptolemaic).unwrap();
+        assert!(!venus.peregrine);
+        assert_eq!(venus.score, 2 - 5);
+        assert!(service.get_essential_dignities("uranus", 13.0, None, TermSystem::Egyptian).is_none());
+    }
+}
@@ -0,0 +1,255 @@
+//! Traditional essential dignity tables.
+//!
+//! Domicile, exaltation, triplicity (Dorothean), Egyptian and Ptolemaic terms and
+//! faces for the seven traditional planets, plus the usual 5/4/3/2/1 point weights.
+
+use serde::{Deserialize, Serialize};
+use crate::western::decans::{get_decan_info_from_longitude_with_system, DecanSystem};
+use crate::western::rulers::{get_sign_index, get_sign_ruler};
+
+/// The seven traditional planets
+pub const TRADITIONAL_PLANETS: &[&str] = &[
+    "sun", "moon", "mercury", "venus", "mars", "jupiter", "saturn",
+];
+
+pub const DOMICILE_POINTS: u32 = 5;
+pub const EXALTATION_POINTS: u32 = 4;
+pub const TRIPLICITY_POINTS: u32 = 3;
+pub const TERM_POINTS: u32 = 2;
+pub const FACE_POINTS: u32 = 1;
+
+/// Table of terms (bounds) used for term dignity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TermSystem {
+    /// Egyptian terms, as given by Ptolemy and used by Hellenistic authors
+    #[default]
+    Egyptian,
+    /// Ptolemy's own terms, as tabulated by Lilly
+    Ptolemaic,
+}
+
+impl TermSystem {
+    /// Parse a term system name ("egyptian" or "ptolemaic").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "egyptian" => Some(TermSystem::Egyptian),
+            "ptolemaic" => Some(TermSystem::Ptolemaic),
+            _ => None,
+        }
+    }
+}
+
+/// Exaltation ruler per sign (None where no planet is exalted)
+const EXALTATION_RULERS: &[Option<&str>] = &[
+    Some("sun"),     // Aries
+    Some("moon"),    // Taurus
+    None,            // Gemini
+    Some("jupiter"), // Cancer
+    None,            // Leo
+    Some("mercury"), // Virgo
+    Some("saturn"),  // Libra
+    None,            // Scorpio
+    None,            // Sagittarius
+    Some("mars"),    // Capricorn
+    None,            // Aquarius
+    Some("venus"),   // Pisces
+];
+
+/// Dorothean triplicity rulers per element: (day, night, participating)
+const TRIPLICITY_RULERS: &[(&str, &str, &str)] = &[
+    ("sun", "jupiter", "saturn"),     // Fire
+    ("venus", "moon", "mars"),        // Earth
+    ("saturn", "mercury", "jupiter"), // Air
+    ("venus", "mars", "moon"),        // Water
+];
+
+/// Egyptian terms per sign: (ruler, end degree)
+pub const EGYPTIAN_TERMS: &[[(&str, f64); 5]] = &[
+    [("jupiter", 6.0), ("venus", 12.0), ("mercury", 20.0), ("mars", 25.0), ("saturn", 30.0)],
+    [("venus", 8.0), ("mercury", 14.0), ("jupiter", 22.0), ("saturn", 27.0), ("mars", 30.0)],
+    [("mercury", 6.0), ("jupiter", 12.0), ("venus", 17.0), ("mars", 24.0), ("saturn", 30.0)],
+    [("mars", 7.0), ("venus", 13.0), ("mercury", 19.0), ("jupiter", 26.0), ("saturn", 30.0)],
+    [("jupiter", 6.0), ("venus", 11.0), ("saturn", 18.0), ("mercury", 24.0), ("mars", 30.0)],
+    [("mercury", 7.0), ("venus", 17.0), ("jupiter", 21.0), ("mars", 28.0), ("saturn", 30.0)],
+    [("saturn", 6.0), ("mercury", 14.0), ("jupiter", 21.0), ("venus", 28.0), ("mars", 30.0)],
+    [("mars", 7.0), ("venus", 11.0), ("mercury", 19.0), ("jupiter", 24.0), ("saturn", 30.0)],
+    [("jupiter", 12.0), ("venus", 17.0), ("mercury", 21.0), ("saturn", 26.0), ("mars", 30.0)],
+    [("mercury", 7.0), ("jupiter", 14.0), ("venus", 22.0), ("saturn", 26.0), ("mars", 30.0)],
+    [("mercury", 7.0), ("venus", 13.0), ("jupiter", 20.0), ("mars", 25.0), ("saturn", 30.0)],
+    [("venus", 12.0), ("jupiter", 16.0), ("mercury", 19.0), ("mars", 28.0), ("saturn", 30.0)],
+];
+
+/// Ptolemaic terms per sign: (ruler, end degree)
+pub const PTOLEMAIC_TERMS: &[[(&str, f64); 5]] = &[
+    [("jupiter", 6.0), ("venus", 14.0), ("mercury", 21.0), ("mars", 26.0), ("saturn", 30.0)],
+    [("venus", 8.0), ("mercury", 15.0), ("jupiter", 22.0), ("saturn", 26.0), ("mars", 30.0)],
+    [("mercury", 7.0), ("jupiter", 14.0), ("venus", 21.0), ("saturn", 25.0), ("mars", 30.0)],
+    [("mars", 6.0), ("jupiter", 13.0), ("mercury", 20.0), ("venus", 27.0), ("saturn", 30.0)],
+    [("saturn", 6.0), ("mercury", 13.0), ("venus", 19.0), ("jupiter", 25.0), ("mars", 30.0)],
+    [("mercury", 7.0), ("venus", 13.0), ("jupiter", 18.0), ("saturn", 24.0), ("mars", 30.0)],
+    [("saturn", 6.0), ("venus", 11.0), ("jupiter", 19.0), ("mercury", 24.0), ("mars", 30.0)],
+    [("mars", 6.0), ("jupiter", 14.0), ("venus", 21.0), ("mercury", 27.0), ("saturn", 30.0)],
+    [("jupiter", 8.0), ("venus", 14.0), ("mercury", 19.0), ("saturn", 25.0), ("mars", 30.0)],
+    [("venus", 6.0), ("mercury", 12.0), ("jupiter", 19.0), ("mars", 25.0), ("saturn", 30.0)],
+    [("saturn", 6.0), ("mercury", 12.0), ("venus", 20.0), ("jupiter", 25.0), ("mars", 30.0)],
+    [("venus", 8.0), ("jupiter", 14.0), ("mercury", 20.0), ("mars", 26.0), ("saturn", 30.0)],
+];
+
+fn normalize(longitude: f64) -> f64 {
+    longitude.rem_euclid(360.0)
+}
+
+/// Traditional domicile ruler of the sign containing a longitude
+pub fn domicile_ruler(longitude: f64) -> String {
+    get_sign_ruler(get_sign_index(normalize(longitude)), false)
+}
+
+/// Planet exalted in the sign containing a longitude
+pub fn exaltation_ruler(longitude: f64) -> Option<&'static str> {
+    EXALTATION_RULERS[get_sign_index(normalize(longitude)) as usize % 12]
+}
+
+/// Triplicity rulers (day, night, participating) of the sign containing a longitude
+pub fn triplicity_rulers(longitude: f64) -> (&'static str, &'static str, &'static str) {
+    TRIPLICITY_RULERS[get_sign_index(normalize(longitude)) as usize % 4]
+}
+
+/// Triplicity ruler in sect: the day ruler by day, the night ruler by night
+pub fn triplicity_ruler(longitude: f64, is_day: bool) -> &'static str {
+    let (day, night, _) = triplicity_rulers(longitude);
+    if is_day { day } else { night }
+}
+
+/// Ruler of the term containing a longitude, using a terms table
+pub fn term_ruler_from_table(longitude: f64, table: &[[(&'static str, f64); 5]]) -> &'static str {
+    let lon = normalize(longitude);
+    let sign_index = get_sign_index(lon) as usize % 12;
+    let degree = lon - sign_index as f64 * 30.0;
+    table[sign_index]
+        .iter()
+        .find(|(_, end)| degree < *end)
+        .map(|(ruler, _)| *ruler)
+        .unwrap_or(table[sign_index][4].0)
+}
+
+/// Ruler of the Egyptian term containing a longitude
+pub fn egyptian_term_ruler(longitude: f64) -> &'static str {
+    term_ruler_from_table(longitude, EGYPTIAN_TERMS)
+}
+
+/// Ruler of the term containing a longitude in the given term system
+pub fn term_ruler(longitude: f64, system: TermSystem) -> &'static str {
+    match system {
+        TermSystem::Egyptian => egyptian_term_ruler(longitude),
+        TermSystem::Ptolemaic => term_ruler_from_table(longitude, PTOLEMAIC_TERMS),
+    }
+}
+
+/// Ruler of the face (Chaldean decan) containing a longitude
+pub fn face_ruler(longitude: f64) -> String {
+    get_decan_info_from_longitude_with_system(longitude, DecanSystem::Chaldean).decan_ruler
+}
+
+/// Essential dignity points each traditional planet holds at a longitude
+pub fn dignity_points_at(longitude: f64, is_day: bool) -> Vec<(&'static str, u32)> {
+    let domicile = domicile_ruler(longitude);
+    let exaltation = exaltation_ruler(longitude);
+    let triplicity = triplicity_ruler(longitude, is_day);
+    let term = egyptian_term_ruler(longitude);
+    let face = face_ruler(longitude);
+
+    TRADITIONAL_PLANETS
+        .iter()
+        .map(|planet| {
+            let mut points = 0;
+            if domicile == *planet {
+                points += DOMICILE_POINTS;
+            }
+            if exaltation == Some(*planet) {
+                points += EXALTATION_POINTS;
+            }
+            if triplicity == *planet {
+                points += TRIPLICITY_POINTS;
+            }
+            if term == *planet {
+                points += TERM_POINTS;
+            }
+            if face == *planet {
+                points += FACE_POINTS;
+            }
+            (*planet, points)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_egyptian_terms_cover_each_sign() {
+        for sign in EGYPTIAN_TERMS {
+            assert_eq!(sign[4].1, 30.0);
+            assert!(sign.windows(2).all(|w| w[0].1 < w[1].1));
+        }
+        // Classical totals: Jupiter 79, Venus 82, Mercury 76, Mars 66, Saturn 57
+        let total = |planet: &str| -> f64 {
+            EGYPTIAN_TERMS
+                .iter()
+                .map(|sign| {
+                    let mut start = 0.0;
+                    sign.iter()
+                        .map(|(ruler, end)| {
+                            let span = if *ruler == planet { end - start } else { 0.0 };
+                            start = *end;
+                            span
+                        })
+                        .sum::<f64>()
+                })
+                .sum()
+        };
+        assert_eq!(total("jupiter"), 79.0);
+        assert_eq!(total("venus"), 82.0);
+        assert_eq!(total("mercury"), 76.0);
+        assert_eq!(total("mars"), 66.0);
+        assert_eq!(total("saturn"), 57.0);
+    }
+
+    #[test]
+    fn test_ptolemaic_terms() {
+        for sign in PTOLEMAIC_TERMS {
+            assert_eq!(sign[4].1, 30.0);
+            assert!(sign.windows(2).all(|w| w[0].1 < w[1].1));
+        }
+        // 13 Aries: Venus (6-14) in the Ptolemaic table, Mercury (12-20) in the Egyptian table
+        assert_eq!(term_ruler(13.0, TermSystem::Ptolemaic), "venus");
+        assert_eq!(term_ruler(13.0, TermSystem::Egyptian), "mercury");
+        assert_eq!(TermSystem::from_name("ptolemaic"), Some(TermSystem::Ptolemaic));
+        assert_eq!(TermSystem::from_name("chaldean"), None);
+    }
+
+    #[test]
+    fn test_rulers_at_longitude() {
+        // 15 Aries: Mars domicile, Sun exaltation, Mercury term, Sun face
+        assert_eq!(domicile_ruler(15.0), "mars");
+        assert_eq!(exaltation_ruler(15.0), Some("sun"));
+        assert_eq!(egyptian_term_ruler(15.0), "mercury");
+        assert_eq!(face_ruler(15.0), "sun");
+        assert_eq!(triplicity_ruler(15.0, true), "sun");
+        assert_eq!(triplicity_ruler(15.0, false), "jupiter");
+        assert_eq!(exaltation_ruler(45.0), Some("moon"));
+        assert_eq!(exaltation_ruler(75.0), None);
+    }
+
+    #[test]
+    fn test_dignity_points_at() {
+        // 15 Aries by day: Sun has exaltation + triplicity + face = 8, Mars domicile = 5
+        let points = dignity_points_at(15.0, true);
+        let get = |planet: &str| points.iter().find(|(p, _)| *p == planet).unwrap().1;
+        assert_eq!(get("sun"), 8);
+        assert_eq!(get("mars"), 5);
+        assert_eq!(get("mercury"), 2);
+        assert_eq!(get("saturn"), 0);
+    }
+}
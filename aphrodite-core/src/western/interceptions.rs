@@ -0,0 +1,130 @@
+//! Intercepted and duplicated signs.
+//!
+//! With unequal (quadrant) house systems at higher latitudes some signs contain no
+//! house cusp (intercepted) while others hold two or more (duplicated). Equal and
+//! whole sign houses place exactly one cusp per sign, so they never report either.
+
+use serde::{Deserialize, Serialize};
+use crate::ephemeris::types::HousePositions;
+use crate::layout::rings::get_house_index;
+use crate::western::rulers::get_sign_index;
+use crate::zodiac::SIGN_NAMES;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InterceptedSign {
+    pub sign: String,
+    /// House (1-12) that fully contains the sign
+    pub house: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicatedSign {
+    pub sign: String,
+    /// Houses (1-12) whose cusps fall in the sign
+    pub houses: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InterceptionReport {
+    #[serde(rename = "interceptedSigns")]
+    pub intercepted_signs: Vec<InterceptedSign>,
+    #[serde(rename = "duplicatedSigns")]
+    pub duplicated_signs: Vec<DuplicatedSign>,
+}
+
+impl InterceptionReport {
+    pub fn has_interceptions(&self) -> bool {
+        !self.intercepted_signs.is_empty()
+    }
+}
+
+/// Find intercepted and duplicated signs from house cusps.
+///
+/// Returns None when fewer than twelve cusps are available.
+pub fn compute_interceptions(houses: &HousePositions) -> Option<InterceptionReport> {
+    let mut cusps: Vec<(u8, f64)> = houses
+        .cusps
+        .iter()
+        .filter_map(|(k, lon)| k.parse::<u8>().ok().map(|num| (num, lon.rem_euclid(360.0))))
+        .filter(|(num, _)| (1..=12).contains(num))
+        .collect();
+    if cusps.len() != 12 {
+        return None;
+    }
+    cusps.sort_by_key(|(num, _)| *num);
+
+    let mut houses_by_sign: Vec<Vec<u8>> = vec![Vec::new(); 12];
+    for (num, lon) in &cusps {
+        houses_by_sign[get_sign_index(*lon) as usize % 12].push(*num);
+    }
+
+    let mut report = InterceptionReport::default();
+    for (sign_index, sign_houses) in houses_by_sign.into_iter().enumerate() {
+        let sign = SIGN_NAMES[sign_index].to_string();
+        match sign_houses.len() {
+            0 => {
+                // No cusp in the sign, so the whole sign lies inside one house
+                let midpoint = sign_index as f64 * 30.0 + 15.0;
+                if let Some(house) = get_house_index(midpoint, &houses.cusps) {
+                    report.intercepted_signs.push(InterceptedSign { sign, house: house + 1 });
+                }
+            }
+            1 => {}
+            _ => report.duplicated_signs.push(DuplicatedSign { sign, houses: sign_houses }),
+        }
+    }
+
+    Some(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn houses(cusps: [f64; 12]) -> HousePositions {
+        HousePositions {
+            system: "placidus".to_string(),
+            cusps: cusps.iter().enumerate().map(|(i, lon)| ((i + 1).to_string(), *lon)).collect(),
+            angles: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_equal_houses_have_no_interceptions() {
+        let cusps: Vec<f64> = (0..12).map(|i| (15.0 + i as f64 * 30.0) % 360.0).collect();
+        let report = compute_interceptions(&houses(cusps.try_into().unwrap())).unwrap();
+        assert!(!report.has_interceptions());
+        assert!(report.duplicated_signs.is_empty());
+    }
+
+    #[test]
+    fn test_intercepted_and_duplicated_signs() {
+        // Houses 1 and 2 both start in Aries; house 3 spans all of Gemini
+        let report = compute_interceptions(&houses([
+            5.0, 25.0, 55.0, 95.0, 125.0, 155.0, 185.0, 205.0, 235.0, 275.0, 305.0, 335.0,
+        ]))
+        .unwrap();
+        assert_eq!(
+            report.intercepted_signs,
+            vec![
+                InterceptedSign { sign: "gemini".to_string(), house: 3 },
+                InterceptedSign { sign: "sagittarius".to_string(), house: 9 },
+            ]
+        );
+        assert_eq!(
+            report.duplicated_signs,
+            vec![
+                DuplicatedSign { sign: "aries".to_string(), houses: vec![1, 2] },
+                DuplicatedSign { sign: "libra".to_string(), houses: vec![7, 8] },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_missing_cusps() {
+        let mut positions = houses([0.0; 12]);
+        positions.cusps.remove("12");
+        assert!(compute_interceptions(&positions).is_none());
+    }
+}
@@ -1,10 +1,23 @@
 pub mod dignities;
 pub mod rulers;
 pub mod decans;
+pub mod balance;
+pub mod essential;
+pub mod condition;
+pub mod interceptions;
+pub mod analysis;
 pub mod types;
 
-pub use dignities::{DignitiesService, DignityResult, DignityType, ExactExaltation};
+pub use dignities::{DignitiesService, DignityResult, DignityType, EssentialDignityScore, ExactExaltation};
+pub use essential::TermSystem;
 pub use rulers::{get_sign_ruler, get_sign_ruler_from_longitude, get_sign_index};
-pub use decans::{DecanInfo, Element, get_decan_info_from_longitude, get_decan_info_for_sign_and_degree, get_decan_index};
+pub use decans::{
+    DecanInfo, DecanSystem, Element, get_decan_info_from_longitude, get_decan_info_from_longitude_with_system,
+    get_decan_info_for_sign_and_degree, get_decan_info_for_sign_and_degree_with_system, get_decan_index,
+};
+pub use balance::{BalanceBucket, BalanceSummary, compute_balance};
+pub use analysis::{Almuten, DispositorAnalysis, DispositorChain, WesternAnalysis, compute_analysis};
+pub use condition::{PlanetCondition, compute_conditions};
+pub use interceptions::{DuplicatedSign, InterceptedSign, InterceptionReport, compute_interceptions};
 pub use types::WesternLayerData;
 
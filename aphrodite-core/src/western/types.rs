@@ -2,8 +2,12 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use crate::western::dignities::DignityResult;
+use crate::western::dignities::{DignityResult, EssentialDignityScore};
 use crate::western::decans::DecanInfo;
+use crate::western::balance::BalanceSummary;
+use crate::western::analysis::WesternAnalysis;
+use crate::western::condition::PlanetCondition;
+use crate::western::interceptions::InterceptionReport;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WesternLayerData {
@@ -11,5 +15,12 @@ pub struct WesternLayerData {
     pub layer_id: String,
     pub dignities: HashMap<String, Vec<DignityResult>>,
     pub decans: HashMap<String, DecanInfo>,
+    #[serde(rename = "essentialDignities")]
+    pub essential_dignities: HashMap<String, EssentialDignityScore>,
+    pub balance: BalanceSummary,
+    pub analysis: WesternAnalysis,
+    pub conditions: HashMap<String, PlanetCondition>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interceptions: Option<InterceptionReport>,
 }
 
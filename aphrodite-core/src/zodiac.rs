@@ -0,0 +1,45 @@
+//! The twelve zodiac signs, named once for every module that reports or parses them.
+
+/// Sign ids in zodiac order from Aries, as they appear in requests and responses
+pub const SIGN_NAMES: [&str; 12] = [
+    "aries", "taurus", "gemini", "cancer", "leo", "virgo",
+    "libra", "scorpio", "sagittarius", "capricorn", "aquarius", "pisces",
+];
+
+/// Sign names for display, in the same order
+pub const SIGN_LABELS: [&str; 12] = [
+    "Aries", "Taurus", "Gemini", "Cancer", "Leo", "Virgo",
+    "Libra", "Scorpio", "Sagittarius", "Capricorn", "Aquarius", "Pisces",
+];
+
+/// Index of the sign containing an ecliptic longitude, 0 (Aries) to 11 (Pisces)
+pub fn sign_index(lon: f64) -> usize {
+    (lon.rem_euclid(360.0) / 30.0) as usize % 12
+}
+
+/// Id of the sign containing an ecliptic longitude
+pub fn sign_name(lon: f64) -> &'static str {
+    SIGN_NAMES[sign_index(lon)]
+}
+
+/// Display name of the sign containing an ecliptic longitude
+pub fn sign_label(lon: f64) -> &'static str {
+    SIGN_LABELS[sign_index(lon)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sign_of_longitude() {
+        assert_eq!(sign_name(0.0), "aries");
+        assert_eq!(sign_name(29.999), "aries");
+        assert_eq!(sign_name(45.0), "taurus");
+        assert_eq!(sign_name(359.9), "pisces");
+        assert_eq!(sign_name(360.0), "aries");
+        assert_eq!(sign_name(-15.0), "pisces");
+        assert_eq!(sign_index(735.0), 0);
+        assert_eq!(sign_label(280.0), "Capricorn");
+    }
+}
@@ -1,5 +1,5 @@
 use aphrodite_core::aspects::{AspectCalculator, AspectSettings};
-use aphrodite_core::ephemeris::{LayerPositions, PlanetPosition};
+use aphrodite_core::ephemeris::{HousePositions, LayerPositions, PlanetPosition};
 use std::collections::HashMap;
 
 #[test]
@@ -71,6 +71,9 @@ fn test_compute_intra_layer_aspects() {
         orb_settings,
         include_objects: vec![],
         only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
     };
     
     let aspect_set = calculator.compute_intra_layer_aspects("natal", &positions, &settings);
@@ -79,3 +82,266 @@ fn test_compute_intra_layer_aspects() {
     assert!(!aspect_set.pairs.is_empty());
 }
 
+
+#[test]
+fn test_out_of_sign_aspects() {
+    let calculator = AspectCalculator::new();
+    let orb_settings: HashMap<String, f64> = aphrodite_core::aspects::DEFAULT_ORBS
+        .iter()
+        .map(|(name, orb)| (name.to_string(), *orb))
+        .collect();
+
+    // 28° Aries to 2° Virgo is a trine by degree, but Aries and Virgo are five signs apart
+    let trine = calculator.calculate_aspect(28.0, 152.0, 1.0, 1.0, &orb_settings).unwrap();
+    assert_eq!(trine.aspect_type, "trine");
+    assert!(trine.out_of_sign);
+    // 10° Aries to 12° Leo is a trine in sign
+    assert!(!calculator.calculate_aspect(10.0, 132.0, 1.0, 1.0, &orb_settings).unwrap().out_of_sign);
+    // A conjunction across 0° Aries joins Pisces and Aries
+    let conjunction = calculator.calculate_aspect(358.0, 2.0, 1.0, 1.0, &orb_settings).unwrap();
+    assert_eq!(conjunction.aspect_type, "conjunction");
+    assert!(conjunction.out_of_sign);
+
+    let planet = |lon: f64| PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde: false };
+    let positions = LayerPositions {
+        planets: HashMap::from([
+            ("sun".to_string(), planet(28.0)),
+            ("moon".to_string(), planet(152.0)),
+            ("venus".to_string(), planet(158.0)),
+            ("mars".to_string(), planet(301.0)),
+        ]),
+        houses: None,
+    };
+    let mut settings = AspectSettings {
+        orb_settings,
+        include_objects: vec![],
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    };
+    let aspect_types = |settings: &AspectSettings| {
+        let set = calculator.compute_intra_layer_aspects("natal", &positions, settings);
+        let mut types: Vec<String> = set.pairs.iter().map(|pair| pair.aspect.aspect_type.clone()).collect();
+        types.sort();
+        types
+    };
+    // Sun-Moon trine and Aries-Aquarius Sun-Mars square are out of sign; Moon-Venus is in Virgo
+    assert_eq!(aspect_types(&settings), vec!["conjunction", "square", "trine"]);
+    settings.exclude_out_of_sign = true;
+    assert_eq!(aspect_types(&settings), vec!["conjunction"]);
+
+    // Serialized as outOfSign, and missing in older output means in sign
+    let json = serde_json::to_value(&trine).unwrap();
+    assert_eq!(json["outOfSign"], true);
+    let mut json = json;
+    json.as_object_mut().unwrap().remove("outOfSign");
+    let decoded: aphrodite_core::aspects::AspectCore = serde_json::from_value(json).unwrap();
+    assert!(!decoded.out_of_sign);
+}
+
+#[test]
+fn test_semisquare_and_sesquiquadrate_are_never_out_of_sign() {
+    let calculator = AspectCalculator::new();
+    let planet = |lon: f64| PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde: false };
+    // 10° Aries, 25° Taurus and 25° Leo
+    let positions = LayerPositions {
+        planets: HashMap::from([
+            ("sun".to_string(), planet(10.0)),
+            ("moon".to_string(), planet(55.0)),
+            ("mars".to_string(), planet(145.0)),
+        ]),
+        houses: None,
+    };
+    let mut settings = AspectSettings {
+        orb_settings: aphrodite_core::aspects::DEFAULT_ORBS
+            .iter()
+            .map(|(name, orb)| (name.to_string(), *orb))
+            .collect(),
+        include_objects: vec![],
+        only_major: Some(false),
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    };
+    let aspects = |settings: &AspectSettings| {
+        let set = calculator.compute_intra_layer_aspects("natal", &positions, settings);
+        let mut aspects: Vec<(String, bool)> = set
+            .pairs
+            .iter()
+            .map(|pair| (pair.aspect.aspect_type.clone(), pair.aspect.out_of_sign))
+            .collect();
+        aspects.sort();
+        aspects
+    };
+
+    // Moon-Mars is an in-sign square
+    let expected = vec![
+        ("semisquare".to_string(), false),
+        ("sesquiquadrate".to_string(), false),
+        ("square".to_string(), false),
+    ];
+    assert_eq!(aspects(&settings), expected);
+    settings.exclude_out_of_sign = true;
+    assert_eq!(aspects(&settings), expected);
+}
+
+#[test]
+fn test_object_orbs_average_with_aspect_orbs() {
+    let calculator = AspectCalculator::new();
+    let planet = |lon: f64| PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde: false };
+    let positions = LayerPositions {
+        planets: HashMap::from([
+            ("sun".to_string(), planet(0.0)),
+            ("moon".to_string(), planet(130.0)),
+            ("mars".to_string(), planet(100.0)),
+            ("venus".to_string(), planet(57.0)),
+            ("mercury".to_string(), planet(3.0)),
+        ]),
+        houses: None,
+    };
+    let mut settings = AspectSettings {
+        orb_settings: aphrodite_core::aspects::DEFAULT_ORBS
+            .iter()
+            .map(|(name, orb)| (name.to_string(), *orb))
+            .collect(),
+        include_objects: vec![],
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    };
+    let pairs = |settings: &AspectSettings| {
+        let set = calculator.compute_intra_layer_aspects("natal", &positions, settings);
+        let mut pairs: Vec<String> = set
+            .pairs
+            .iter()
+            .map(|pair| format!("{}-{}:{}", pair.from.object_id, pair.to.object_id, pair.aspect.aspect_type))
+            .collect();
+        pairs.sort();
+        pairs
+    };
+
+    // Per-aspect orbs alone: the 10° trine and 10° square are out of orb
+    assert_eq!(
+        pairs(&settings),
+        vec!["mercury-moon:trine", "mercury-sun:conjunction", "sun-venus:sextile"]
+    );
+
+    // Sun-Moon takes (15 + 12) / 2 = 13.5; Mars is unlisted, so Sun-Mars takes (15 + 6) / 2
+    // for the square; Mercury's narrow orb drops its 7° trine to the Moon
+    settings.object_orbs = HashMap::from([
+        ("sun".to_string(), 15.0),
+        ("moon".to_string(), 12.0),
+        ("mercury".to_string(), 1.0),
+        ("venus".to_string(), 3.0),
+    ]);
+    assert_eq!(
+        pairs(&settings),
+        vec!["mars-sun:square", "mercury-sun:conjunction", "moon-sun:trine", "sun-venus:sextile"]
+    );
+}
+
+#[test]
+fn test_aspect_filter_selects_types_pairs_and_angles() {
+    let calculator = AspectCalculator::new();
+    let planet = |lon: f64| PlanetPosition {
+        lon,
+        lat: 0.0,
+        speed_lon: 1.0,
+        retrograde: false,
+    };
+    let mut positions = LayerPositions {
+        planets: HashMap::from([
+            ("sun".to_string(), planet(0.0)),
+            ("moon".to_string(), planet(150.5)),
+            ("mars".to_string(), planet(90.0)),
+            ("north_node".to_string(), planet(200.0)),
+            ("south_node".to_string(), planet(20.0)),
+        ]),
+        houses: None,
+    };
+    let mut settings = AspectSettings {
+        orb_settings: aphrodite_core::aspects::DEFAULT_ORBS
+            .iter()
+            .map(|(name, orb)| (name.to_string(), *orb))
+            .collect(),
+        include_objects: vec![],
+        only_major: Some(true),
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    };
+    let pairs = |positions: &LayerPositions, settings: &AspectSettings| {
+        let set = calculator.compute_intra_layer_aspects("natal", positions, settings);
+        let mut pairs: Vec<String> = set
+            .pairs
+            .iter()
+            .map(|pair| {
+                format!(
+                    "{}-{}:{}",
+                    pair.from.object_id, pair.to.object_id, pair.aspect.aspect_type
+                )
+            })
+            .collect();
+        pairs.sort();
+        pairs
+    };
+
+    assert_eq!(
+        pairs(&positions, &settings),
+        vec![
+            "mars-moon:sextile",
+            "mars-sun:square",
+            "north_node-south_node:opposition"
+        ]
+    );
+
+    // Minor aspects use their default orbs when orb_settings leaves them out
+    settings.only_major = Some(false);
+    assert_eq!(
+        pairs(&positions, &settings),
+        vec![
+            "mars-moon:sextile",
+            "mars-sun:square",
+            "moon-sun:quincunx",
+            "north_node-south_node:opposition",
+        ]
+    );
+
+    settings.filter.aspect_types = Some(vec!["square".to_string(), "quincunx".to_string()]);
+    assert_eq!(
+        pairs(&positions, &settings),
+        vec!["mars-sun:square", "moon-sun:quincunx"]
+    );
+
+    settings.filter.aspect_types = None;
+    settings.filter.exclude_pairs = vec![("south_node".to_string(), "north_node".to_string())];
+    settings.filter.include_pairs = Some(vec![
+        ("sun".to_string(), "mars".to_string()),
+        ("north_node".to_string(), "south_node".to_string()),
+    ]);
+    assert_eq!(pairs(&positions, &settings), vec!["mars-sun:square"]);
+
+    // Angles only take part when asked for and when the layer has houses
+    settings.only_major = Some(true);
+    settings.filter.include_pairs = None;
+    settings.filter.include_angles = true;
+    assert!(pairs(&positions, &settings)
+        .iter()
+        .all(|pair| !pair.contains("asc")));
+    positions.houses = Some(HousePositions {
+        system: "whole_sign".to_string(),
+        cusps: HashMap::new(),
+        angles: HashMap::from([("asc".to_string(), 120.0), ("mc".to_string(), 22.0)]),
+    });
+    let set = calculator.compute_intra_layer_aspects("natal", &positions, &settings);
+    let asc_sun = set
+        .pairs
+        .iter()
+        .find(|pair| pair.to.object_id == "asc" && pair.from.object_id == "sun")
+        .expect("Ascendant trine Sun");
+    assert_eq!(asc_sun.to.object_type, "angle");
+    assert_eq!(asc_sun.aspect.aspect_type, "trine");
+    assert!(pairs(&positions, &settings).contains(&"south_node-mc:conjunction".to_string()));
+}
@@ -1,5 +1,9 @@
-use aphrodite_core::ephemeris::{EphemerisSettings, GeoLocation, SwissEphemerisAdapter};
-use chrono::Utc;
+#![cfg(feature = "swisseph")]
+
+use aphrodite_core::ephemeris::{
+    EphemerisSettings, GeoLocation, LilithType, MoonPosition, NodeType, PolarFallback, SwissEphemerisAdapter,
+};
+use chrono::{TimeZone, Utc};
 
 #[tokio::test]
 #[ignore] // Requires Swiss Ephemeris files
@@ -9,8 +13,13 @@ async fn test_calc_positions_basic() {
     let settings = EphemerisSettings {
         zodiac_type: "tropical".to_string(),
         ayanamsa: None,
+        custom_ayanamsa: None,
         house_system: "placidus".to_string(),
         include_objects: vec!["sun".to_string(), "moon".to_string()],
+        node_type: NodeType::default(),
+        lilith_type: LilithType::default(),
+        moon_position: MoonPosition::default(),
+        polar_fallback: PolarFallback::default(),
     };
     
     let location = Some(GeoLocation {
@@ -27,16 +36,153 @@ async fn test_calc_positions_basic() {
     assert!(positions.houses.is_some());
 }
 
+#[test]
+#[ignore] // Requires Swiss Ephemeris files
+fn test_calc_phenomena() {
+    let mut adapter = SwissEphemerisAdapter::new(None).unwrap();
+    let settings = EphemerisSettings {
+        zodiac_type: "tropical".to_string(),
+        ayanamsa: None,
+        custom_ayanamsa: None,
+        house_system: "placidus".to_string(),
+        include_objects: vec!["sun".to_string(), "venus".to_string(), "north_node".to_string()],
+        node_type: NodeType::default(),
+        lilith_type: LilithType::default(),
+        moon_position: MoonPosition::default(),
+        polar_fallback: PolarFallback::default(),
+    };
+
+    // Venus at greatest western elongation
+    let dt = Utc.with_ymd_and_hms(2023, 10, 23, 0, 0, 0).unwrap();
+    let phenomena = adapter.calc_phenomena(dt, &settings).unwrap();
+
+    assert!(!phenomena.contains_key("north_node"));
+    assert!(phenomena["sun"].magnitude < -26.0);
+    let venus = &phenomena["venus"];
+    assert!((venus.elongation - 46.4).abs() < 0.5, "elongation {}", venus.elongation);
+    assert!((venus.phase - 0.5).abs() < 0.05, "phase {}", venus.phase);
+    assert!(venus.magnitude < -4.0);
+}
+
+#[test]
+#[ignore] // Requires Swiss Ephemeris files
+fn test_polar_house_fallback() {
+    let mut adapter = SwissEphemerisAdapter::new(None).unwrap();
+    let settings = EphemerisSettings {
+        zodiac_type: "tropical".to_string(),
+        ayanamsa: None,
+        custom_ayanamsa: None,
+        house_system: "placidus".to_string(),
+        include_objects: vec!["sun".to_string()],
+        node_type: NodeType::default(),
+        lilith_type: LilithType::default(),
+        moon_position: MoonPosition::default(),
+        polar_fallback: PolarFallback::WholeSign,
+    };
+    let tromso = GeoLocation { lat: 69.65, lon: 18.96 };
+    let dt = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+
+    let houses = adapter.calc_positions(dt, Some(tromso), &settings).unwrap().houses.unwrap();
+    assert_eq!(houses.system, "whole_sign");
+    assert_eq!(houses.cusps["1"] % 30.0, 0.0);
+}
+
+#[test]
+fn test_failed_objects_are_reported_as_warnings() {
+    // An empty ephemeris directory: the planets fall back to Moshier, but Chiron has no fallback
+    let ephe_dir = std::env::temp_dir().join("aphrodite-empty-ephe");
+    std::fs::create_dir_all(&ephe_dir).unwrap();
+    let mut adapter = SwissEphemerisAdapter::new(Some(ephe_dir)).unwrap();
+    let settings = EphemerisSettings {
+        zodiac_type: "tropical".to_string(),
+        ayanamsa: None,
+        custom_ayanamsa: None,
+        house_system: "placidus".to_string(),
+        include_objects: vec!["sun".to_string(), "chiron".to_string()],
+        node_type: NodeType::default(),
+        lilith_type: LilithType::default(),
+        moon_position: MoonPosition::default(),
+        polar_fallback: PolarFallback::default(),
+    };
+    let dt = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+
+    let (positions, warnings) = adapter.calc_positions_with_warnings(dt, None, &settings).unwrap();
+    assert!(positions.planets.contains_key("sun"));
+    assert!(!positions.planets.contains_key("chiron"));
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(warnings[0].object_id, "chiron");
+    assert!(!warnings[0].message.contains('\0'), "{:?}", warnings[0].message);
+}
+
+#[test]
+fn test_dates_outside_installed_files_are_rejected() {
+    let ephe_dir = std::env::temp_dir().join("aphrodite-partial-ephe");
+    std::fs::create_dir_all(&ephe_dir).unwrap();
+    std::fs::write(ephe_dir.join("sepl_18.se1"), b"").unwrap();
+    let adapter = SwissEphemerisAdapter::new(Some(ephe_dir)).unwrap();
+
+    assert!(adapter.check_coverage(Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap()).is_ok());
+    let error = adapter.check_coverage(Utc.with_ymd_and_hms(1492, 10, 12, 0, 0, 0).unwrap()).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Date 1492-10-12 is outside the installed ephemeris range 1800-2399; install sepl_12.se1"
+    );
+    assert_eq!(aphrodite_core::Error::from(error).category(), aphrodite_core::ErrorCategory::InvalidInput);
+}
+
 #[test]
 fn test_ephemeris_settings_default() {
     let settings = EphemerisSettings {
         zodiac_type: "tropical".to_string(),
         ayanamsa: None,
+        custom_ayanamsa: None,
         house_system: "placidus".to_string(),
         include_objects: vec![],
+        node_type: NodeType::default(),
+        lilith_type: LilithType::default(),
+        moon_position: MoonPosition::default(),
+        polar_fallback: PolarFallback::default(),
     };
     
     assert_eq!(settings.zodiac_type, "tropical");
     assert_eq!(settings.house_system, "placidus");
 }
 
+
+#[test]
+fn test_adapters_with_different_ayanamsas_alternate_on_one_thread() {
+    let ephe_dir = std::env::temp_dir().join("aphrodite-empty-ephe");
+    std::fs::create_dir_all(&ephe_dir).unwrap();
+    let sidereal = |ayanamsa: &str, custom_ayanamsa: Option<f64>| EphemerisSettings {
+        zodiac_type: "sidereal".to_string(),
+        ayanamsa: Some(ayanamsa.to_string()),
+        custom_ayanamsa,
+        house_system: "placidus".to_string(),
+        include_objects: vec!["sun".to_string()],
+        node_type: NodeType::default(),
+        lilith_type: LilithType::default(),
+        moon_position: MoonPosition::default(),
+        polar_fallback: PolarFallback::default(),
+    };
+    let lahiri = sidereal("lahiri", None);
+    let custom = sidereal("custom", Some(10.0));
+    let mut lahiri_adapter = SwissEphemerisAdapter::new(Some(ephe_dir.clone())).unwrap();
+    let mut custom_adapter = SwissEphemerisAdapter::new(Some(ephe_dir)).unwrap();
+    let dt = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+    let sun = |adapter: &mut SwissEphemerisAdapter, settings: &EphemerisSettings| {
+        adapter.calc_positions(dt, None, settings).unwrap().planets["sun"].lon
+    };
+
+    let lahiri_sun = sun(&mut lahiri_adapter, &lahiri);
+    let custom_sun = sun(&mut custom_adapter, &custom);
+    assert!((lahiri_sun - custom_sun).abs() > 1.0);
+    for _ in 0..3 {
+        assert_eq!(sun(&mut lahiri_adapter, &lahiri), lahiri_sun);
+        assert_eq!(sun(&mut custom_adapter, &custom), custom_sun);
+    }
+
+    let lahiri_ayanamsa = lahiri_adapter.calc_ayanamsa(dt, &lahiri).unwrap();
+    let custom_ayanamsa = custom_adapter.calc_ayanamsa(dt, &custom).unwrap();
+    assert_ne!(lahiri_ayanamsa, custom_ayanamsa);
+    assert_eq!(lahiri_adapter.calc_ayanamsa(dt, &lahiri).unwrap(), lahiri_ayanamsa);
+}
@@ -28,6 +28,39 @@ fn test_load_wheel_definition_valid() {
     assert_eq!(wheel.wheel.rings.len(), 1);
 }
 
+#[test]
+fn test_load_wheel_definition_reads_camel_case_data_source_ids() {
+    use aphrodite_core::layout::RingDataSource;
+
+    let json = r#"
+    {
+      "name": "Test Wheel",
+      "rings": [
+        { "slug": "ring_planets", "type": "planets", "label": "Planets", "orderIndex": 0,
+          "radiusInner": 0.5, "radiusOuter": 0.7, "dataSource": { "kind": "layer_planets", "layerId": "natal" } },
+        { "slug": "ring_d9", "type": "planets", "label": "Navamsa", "orderIndex": 1,
+          "radiusInner": 0.7, "radiusOuter": 0.8,
+          "dataSource": { "kind": "layer_varga_planets", "layerId": "natal", "vargaId": "d9" } },
+        { "slug": "ring_aspects", "type": "aspects", "label": "Aspects", "orderIndex": 2,
+          "radiusInner": 0.0, "radiusOuter": 0.5, "dataSource": { "kind": "aspect_set", "aspectSetId": "natal" } }
+      ]
+    }
+    "#;
+
+    let wheel = load_wheel_definition_from_json(json).unwrap().wheel;
+    assert!(matches!(&wheel.rings[0].data_source,
+        RingDataSource::LayerPlanets { layer_id } if layer_id == "natal"));
+    assert!(matches!(&wheel.rings[1].data_source,
+        RingDataSource::LayerVargaPlanets { layer_id, varga_id } if layer_id == "natal" && varga_id == "d9"));
+    assert!(matches!(&wheel.rings[2].data_source,
+        RingDataSource::AspectSet { aspect_set_id, .. } if aspect_set_id == "natal"));
+
+    // Data sources serialized with the old snake_case keys still deserialize
+    let source: RingDataSource =
+        serde_json::from_str(r#"{ "kind": "layer_houses", "layer_id": "natal" }"#).unwrap();
+    assert!(matches!(source, RingDataSource::LayerHouses { layer_id } if layer_id == "natal"));
+}
+
 #[test]
 fn test_load_wheel_definition_invalid() {
     let json = r#"
@@ -53,3 +86,54 @@ fn test_load_wheel_definition_missing_rings() {
     assert!(result.is_err());
 }
 
+
+fn house_wheel_json(projection: &str) -> String {
+    format!(
+        r#"{{
+          "name": "House Wheel",
+          "projection": "{}",
+          "rings": [
+            {{ "slug": "ring_houses", "type": "houses", "label": "Houses", "orderIndex": 0,
+               "radiusInner": 0.7, "radiusOuter": 0.8, "dataSource": {{ "kind": "layer_houses", "layerId": "natal" }} }}
+          ]
+        }}"#,
+        projection
+    )
+}
+
+#[test]
+fn test_house_projection_spreads_houses_evenly() {
+    use aphrodite_core::ephemeris::{HousePositions, LayerPositions};
+    use aphrodite_core::layout::{WheelAssembler, WheelProjection};
+    use std::collections::HashMap;
+
+    // Unequal houses: 1-6 are 20° wide, 7-12 are 40° wide, house 1 starts at 350°
+    let cusps: HashMap<String, f64> = (0..12)
+        .map(|i| {
+            let lon = if i < 6 { 350.0 + 20.0 * i as f64 } else { 110.0 + 40.0 * (i - 6) as f64 };
+            ((i + 1).to_string(), lon % 360.0)
+        })
+        .collect();
+    let positions = HashMap::from([(
+        "natal".to_string(),
+        LayerPositions {
+            planets: HashMap::new(),
+            houses: Some(HousePositions { system: "placidus".to_string(), cusps, angles: HashMap::new() }),
+        },
+    )]);
+
+    let definition = load_wheel_definition_from_json(&house_wheel_json("houses")).unwrap();
+    assert_eq!(definition.wheel.projection, WheelProjection::Houses);
+    let wheel = WheelAssembler::build_wheel(&definition.wheel, &positions, &HashMap::new(), None);
+    assert!((wheel.project_longitude(350.0) - 0.0).abs() < 1e-9);
+    assert!((wheel.project_longitude(0.0) - 15.0).abs() < 1e-9);
+    assert!((wheel.project_longitude(110.0) - 180.0).abs() < 1e-9);
+    assert!((wheel.project_longitude(130.0) - 195.0).abs() < 1e-9);
+
+    let definition = load_wheel_definition_from_json(&house_wheel_json("zodiac")).unwrap();
+    let wheel = WheelAssembler::build_wheel(&definition.wheel, &positions, &HashMap::new(), None);
+    assert!(wheel.house_cusps.is_none());
+    assert_eq!(wheel.project_longitude(130.0), 130.0);
+
+    assert!(load_wheel_definition_from_json(&house_wheel_json("equal")).is_err());
+}
@@ -0,0 +1,79 @@
+use aphrodite_core::ephemeris::{HousePositions, LayerPositions, PlanetPosition};
+use aphrodite_core::rectification::{rank_birth_times, EventSky};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+fn planet(lon: f64) -> PlanetPosition {
+    PlanetPosition {
+        lon,
+        lat: 0.0,
+        speed_lon: 1.0,
+        retrograde: false,
+    }
+}
+
+fn birth() -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("1990-01-01T12:00:00Z").unwrap().with_timezone(&Utc)
+}
+
+/// A candidate chart whose angles stand at `asc` and `mc`
+fn candidate(minutes: i64, asc: f64, mc: f64) -> (DateTime<Utc>, LayerPositions) {
+    let positions = LayerPositions {
+        planets: HashMap::from([("sun".to_string(), planet(285.0)), ("mars".to_string(), planet(250.0))]),
+        houses: Some(HousePositions {
+            system: "placidus".to_string(),
+            cusps: HashMap::new(),
+            angles: HashMap::from([("asc".to_string(), asc), ("mc".to_string(), mc)]),
+        }),
+    };
+    (birth() + Duration::minutes(minutes), positions)
+}
+
+fn event(years: i64, transits: &[(&str, f64)], solar_arc: f64) -> EventSky {
+    EventSky {
+        date: birth() + Duration::days(365 * years),
+        transits: LayerPositions {
+            planets: transits.iter().map(|(id, lon)| (id.to_string(), planet(*lon))).collect(),
+            houses: None,
+        },
+        solar_arc,
+    }
+}
+
+#[test]
+fn test_candidates_ranked_by_hits_to_angles() {
+    let candidates = [candidate(0, 40.0, 280.0), candidate(4, 41.0, 281.0), candidate(8, 42.0, 282.0)];
+    let events = [
+        // Saturn squares an Ascendant of 41°; the Moon is too fast to count
+        event(20, &[("saturn", 131.0), ("moon", 41.0)], 20.0),
+        // Mars directed by 31° reaches 281°, on the second candidate's Midheaven
+        event(31, &[], 31.0),
+    ];
+
+    let ranked = rank_birth_times(&candidates, &events, 2);
+
+    assert_eq!(ranked.len(), 2);
+    let best = &ranked[0];
+    assert_eq!(best.birth_date_time, birth() + Duration::minutes(4));
+    assert_eq!(best.mc, 281.0);
+    let hits: Vec<_> = best
+        .hits
+        .iter()
+        .map(|hit| (hit.event_index, hit.technique.as_str(), hit.object_id.as_str(), hit.aspect_type.as_str(), hit.angle_id.as_str()))
+        .collect();
+    assert_eq!(
+        hits,
+        vec![(0, "transit", "saturn", "square", "asc"), (1, "direction", "mars", "conjunction", "mc")]
+    );
+    assert!((best.score - 2.0).abs() < 1e-9);
+    assert!(ranked[1].score < best.score);
+    assert!(best.hits.iter().all(|hit| hit.object_id != "moon"));
+}
+
+#[test]
+fn test_candidates_without_houses_are_skipped() {
+    let (moment, mut positions) = candidate(0, 10.0, 280.0);
+    positions.houses = None;
+    let ranked = rank_birth_times(&[(moment, positions)], &[event(20, &[("saturn", 100.0)], 20.0)], 5);
+    assert!(ranked.is_empty());
+}
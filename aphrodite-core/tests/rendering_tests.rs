@@ -1,4 +1,8 @@
-use aphrodite_core::rendering::{ChartSpec, Color, Point, Shape};
+use aphrodite_core::aspects::AspectObjectRef;
+use aphrodite_core::rendering::{
+    AspectSetMetadata, ChartMetadata, ChartShape, ChartSpec, Color, LayerMetadata, LineStyle, Point, Shape, Stroke,
+    TextAnchor, TextBaseline, ASPECT_LINES_RING,
+};
 
 #[test]
 fn test_chartspec_new() {
@@ -48,3 +52,882 @@ fn test_shape_circle_serialization() {
     assert!(json.is_ok());
 }
 
+/// Serialize, deserialize and serialize again; both encodings must agree
+fn assert_roundtrip<T: serde::Serialize + serde::de::DeserializeOwned>(value: &T) -> serde_json::Value {
+    let json = serde_json::to_value(value).unwrap();
+    let decoded: T = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+    json
+}
+
+/// One of every shape variant, with the optional fields both set and unset
+fn every_shape() -> Vec<Shape> {
+    let center = Point { x: 400.0, y: 400.0 };
+    let stroke = Stroke { color: Color::BLACK, width: 1.5, dash_array: Some(vec![4.0, 2.0]) };
+    let object = |layer_id: &str, object_id: &str| AspectObjectRef {
+        layer_id: layer_id.to_string(),
+        object_type: "planet".to_string(),
+        object_id: object_id.to_string(),
+    };
+    vec![
+        Shape::Circle { center, radius: 50.0, fill: Some(Color::WHITE), stroke: None },
+        Shape::Arc {
+            center,
+            radius_inner: 300.0,
+            radius_outer: 350.0,
+            start_angle: -15.0,
+            end_angle: 15.0,
+            fill: None,
+            stroke: Some(stroke.clone()),
+        },
+        Shape::Line { from: center, to: Point { x: 0.5, y: -0.25 }, stroke: stroke.clone() },
+        Shape::Path {
+            points: vec![center, Point { x: 10.0, y: 20.0 }, Point { x: 30.0, y: 5.0 }],
+            closed: true,
+            fill: Some(Color::from_hex("#336699").unwrap()),
+            stroke: None,
+        },
+        Shape::Text {
+            position: center,
+            content: "♈ 12°30′ <&>".to_string(),
+            size: 11.0,
+            color: Color::WHITE,
+            anchor: TextAnchor::End,
+            baseline: TextBaseline::Middle,
+            rotation: Some(-30.0),
+        },
+        Shape::PlanetGlyph {
+            center,
+            planet_id: "sun".to_string(),
+            size: 14.0,
+            color: Color { r: 255, g: 200, b: 0, a: 128 },
+            retrograde: true,
+        },
+        Shape::AspectLine {
+            from: center,
+            to: Point { x: 100.0, y: 100.0 },
+            aspect_type: "trine".to_string(),
+            color: Color::WHITE,
+            width: 1.0,
+            style: LineStyle::Dashed,
+            from_object: Some(object("natal", "sun")),
+            to_object: Some(object("transit", "moon")),
+        },
+        Shape::AspectLine {
+            from: center,
+            to: Point { x: 100.0, y: 100.0 },
+            aspect_type: "square".to_string(),
+            color: Color::WHITE,
+            width: 1.0,
+            style: LineStyle::Dotted,
+            from_object: None,
+            to_object: None,
+        },
+        Shape::HouseSegment {
+            center,
+            house_num: 12,
+            start_angle: 330.0,
+            end_angle: 360.0,
+            radius_inner: 200.0,
+            radius_outer: 250.0,
+            fill: Color::BLACK,
+            stroke: Some(Stroke { color: Color::WHITE, width: 1.0, dash_array: None }),
+        },
+        Shape::SignSegment {
+            center,
+            sign_index: 0,
+            start_angle: 0.0,
+            end_angle: 30.0,
+            radius_inner: 250.0,
+            radius_outer: 300.0,
+            fill: Color::WHITE,
+            stroke: None,
+        },
+    ]
+}
+
+#[test]
+fn test_every_shape_roundtrips() {
+    let shapes = every_shape();
+    let mut types: Vec<String> = shapes
+        .iter()
+        .map(|shape| assert_roundtrip(shape)["type"].as_str().unwrap().to_string())
+        .collect();
+    types.dedup();
+    assert_eq!(types.len(), 9, "every variant is covered: {:?}", types);
+
+    // Optional aspect line endpoints are left out rather than written as null
+    let json = serde_json::to_value(&shapes[7]).unwrap();
+    assert!(json.get("from_object").is_none());
+    // Text written before the baseline existed still loads
+    let mut text = serde_json::to_value(&shapes[4]).unwrap();
+    text.as_object_mut().unwrap().remove("baseline");
+    let Shape::Text { baseline, .. } = serde_json::from_value(text).unwrap() else { panic!("not text") };
+    assert_eq!(baseline, TextBaseline::Alphabetic);
+}
+
+#[test]
+fn test_chartspec_roundtrips_with_tags() {
+    let mut spec = ChartSpec::new(640.0, 480.0);
+    spec.rotation_offset = 45.0;
+    spec.metadata = ChartMetadata {
+        layers: vec![LayerMetadata { id: "natal".to_string(), kind: "natal".to_string() }],
+        aspect_sets: vec![AspectSetMetadata {
+            id: "natal:transit".to_string(),
+            layer_ids: vec!["natal".to_string(), "transit".to_string()],
+        }],
+    };
+    spec.shapes = every_shape()
+        .into_iter()
+        .enumerate()
+        .map(|(i, shape)| {
+            let mut chart_shape = ChartShape::from(shape);
+            // Leave every other shape untagged so the skipped fields are covered too
+            if i % 2 == 0 {
+                chart_shape.id = format!("ring/shape/{}", i);
+                chart_shape.meta.insert("kind".to_string(), "test".to_string());
+                chart_shape.ring = Some("ring".to_string());
+                chart_shape.layer_ids = vec!["natal".to_string()];
+            }
+            chart_shape
+        })
+        .collect();
+
+    let json = assert_roundtrip(&spec);
+    let shapes = json["shapes"].as_array().unwrap();
+    assert_eq!(shapes.len(), spec.shapes.len());
+    // Tags sit beside the shape's own fields, and empty tags are omitted
+    assert_eq!(shapes[0]["id"], "ring/shape/0");
+    assert_eq!(shapes[0]["type"], "Circle");
+    assert!(shapes[1].get("id").is_none() && shapes[1].get("meta").is_none());
+    assert!(shapes[1].get("ring").is_none() && shapes[1].get("layer_ids").is_none());
+}
+
+
+fn single_planet_wheel(direction: aphrodite_core::layout::WheelDirection) -> aphrodite_core::layout::AssembledWheel {
+    use aphrodite_core::ephemeris::{LayerPositions, PlanetPosition};
+    use aphrodite_core::layout::{
+        RingDataSource, RingDefinition, RingType, WheelAssembler, WheelDefinition,
+    };
+    use std::collections::HashMap;
+
+    let definition = WheelDefinition {
+        name: "Direction Test".to_string(),
+        description: None,
+        rings: vec![RingDefinition {
+            slug: "ring_planets".to_string(),
+            ring_type: RingType::Planets,
+            label: "Planets".to_string(),
+            order_index: 0,
+            radius_inner: 0.5,
+            radius_outer: 0.7,
+            data_source: RingDataSource::LayerPlanets { layer_id: "natal".to_string() },
+            display_options: HashMap::new(),
+            style_rules: Vec::new(),
+        }],
+        direction,
+        projection: Default::default(),
+        projection_layer_id: None,
+        config: HashMap::new(),
+    };
+
+    let mut planets = HashMap::new();
+    planets.insert(
+        "sun".to_string(),
+        PlanetPosition { lon: 30.0, lat: 0.0, speed_lon: 1.0, retrograde: false },
+    );
+    let mut positions = HashMap::new();
+    positions.insert("natal".to_string(), LayerPositions { planets, houses: None });
+
+    WheelAssembler::build_wheel(&definition, &positions, &HashMap::new(), None)
+}
+
+fn planet_glyph_center(spec: &ChartSpec) -> Point {
+    spec.shapes
+        .iter()
+        .find_map(|shape| match &shape.shape {
+            Shape::PlanetGlyph { center, .. } => Some(*center),
+            _ => None,
+        })
+        .expect("planet glyph")
+}
+
+#[test]
+fn test_wheel_direction_defaults_to_ccw() {
+    use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler, WheelDirection};
+
+    let json = r#"{
+      "name": "Default Direction",
+      "rings": [
+        {
+          "slug": "ring_signs",
+          "type": "signs",
+          "label": "Signs",
+          "orderIndex": 0,
+          "radiusInner": 0.8,
+          "radiusOuter": 1.0,
+          "dataSource": { "kind": "static_zodiac" }
+        }
+      ]
+    }"#;
+    let definition = load_wheel_definition_from_json(json).unwrap();
+    assert_eq!(definition.wheel.direction, WheelDirection::Ccw);
+
+    assert_eq!(WheelAssembler::map_angle(30.0, WheelDirection::Ccw), 30.0);
+    assert_eq!(WheelAssembler::map_angle(30.0, WheelDirection::Cw), 330.0);
+}
+
+#[test]
+fn test_wheel_direction_mirrors_planet_positions() {
+    use aphrodite_core::layout::WheelDirection;
+    use aphrodite_core::rendering::ChartSpecGenerator;
+    use std::collections::HashMap;
+
+    let generator = ChartSpecGenerator::new();
+    let ccw_wheel = single_planet_wheel(WheelDirection::Ccw);
+    let cw_wheel = single_planet_wheel(WheelDirection::Cw);
+    assert_eq!(cw_wheel.direction, WheelDirection::Cw);
+
+    let ccw = generator.generate(&ccw_wheel, &HashMap::new(), 800.0, 800.0);
+    let cw = generator.generate(&cw_wheel, &HashMap::new(), 800.0, 800.0);
+
+    let ccw_pos = planet_glyph_center(&ccw);
+    let cw_pos = planet_glyph_center(&cw);
+
+    // Same distance from center, mirrored across the horizontal axis
+    assert!((ccw_pos.x - cw_pos.x).abs() < 1e-3);
+    assert!(((ccw_pos.y - 400.0) + (cw_pos.y - 400.0)).abs() < 1e-3);
+    assert!((ccw_pos.y - cw_pos.y).abs() > 1.0);
+}
+
+#[test]
+fn test_layout_margin_and_rotation() {
+    use aphrodite_core::layout::WheelDirection;
+    use aphrodite_core::rendering::ChartSpecGenerator;
+    use std::collections::HashMap;
+
+    // Sun at 30°; rotating it to the left puts it at 9 o'clock in either direction
+    for direction in [WheelDirection::Ccw, WheelDirection::Cw] {
+        let generator = ChartSpecGenerator::new()
+            .with_margin(40.0)
+            .with_rotation(ChartSpecGenerator::rotation_for_left(30.0));
+        let spec = generator.generate(&single_planet_wheel(direction), &HashMap::new(), 600.0, 400.0);
+
+        assert_eq!((spec.width, spec.height), (600.0, 400.0));
+        assert_eq!(spec.rotation_offset, 150.0);
+        // Radius 200 - 40 = 160; the planet ring sits at 0.6 of it
+        let sun = planet_glyph_center(&spec);
+        assert!((sun.x - 204.0).abs() < 1e-3, "{:?}", sun);
+        assert!((sun.y - 200.0).abs() < 1e-3, "{:?}", sun);
+    }
+}
+
+#[test]
+fn test_nakshatra_ring() {
+    use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler};
+    use aphrodite_core::rendering::ChartSpecGenerator;
+    use std::collections::HashMap;
+
+    let json = r#"{
+      "name": "Nakshatras",
+      "rings": [
+        {
+          "slug": "ring_nakshatras",
+          "type": "nakshatras",
+          "label": "Nakshatras",
+          "orderIndex": 0,
+          "radiusInner": 0.8,
+          "radiusOuter": 1.0,
+          "dataSource": { "kind": "static_nakshatras" },
+          "displayOptions": { "padas": true }
+        }
+      ]
+    }"#;
+    let definition = load_wheel_definition_from_json(json).unwrap();
+    let wheel = WheelAssembler::build_wheel(&definition.wheel, &HashMap::new(), &HashMap::new(), None);
+    assert_eq!(wheel.rings[0].items.len(), 27);
+    let spec = ChartSpecGenerator::new().generate(&wheel, &HashMap::new(), 800.0, 800.0);
+
+    let count = |kind: &str| spec.shapes.iter().filter(|s| s.meta.get("kind").map(String::as_str) == Some(kind)).count();
+    assert_eq!(count("nakshatra"), 27);
+    assert_eq!(count("pada"), 27 * 3);
+    assert_eq!(count("text"), 27);
+
+    // Ashwini runs from 0° Aries to 13°20' Aries
+    match &spec.shape("ring_nakshatras/nakshatra/0").unwrap().shape {
+        Shape::Arc { start_angle, end_angle, radius_inner, radius_outer, .. } => {
+            assert!(start_angle.abs() < 1e-3, "{}", start_angle);
+            assert!((end_angle - 360.0 / 27.0).abs() < 1e-3, "{}", end_angle);
+            assert_eq!((*radius_inner, *radius_outer), (304.0, 380.0));
+        }
+        other => panic!("expected arc, got {:?}", other),
+    }
+    let label = spec.shape("ring_nakshatras/nakshatra/26/label").unwrap();
+    assert!(matches!(&label.shape, Shape::Text { content, .. } if content == "Revati"));
+    assert_eq!(label.meta["nakshatra"], "26");
+    assert!(spec.shape("ring_nakshatras/nakshatra/0/pada/2").is_some());
+}
+
+#[test]
+fn test_custom_point_and_fixed_star_rings() {
+    use aphrodite_core::ephemeris::{fixed_star_longitude, FIXED_STARS};
+    use aphrodite_core::layout::{load_wheel_definition_from_json, CustomPoint, WheelAssembler, WheelPoints};
+    use aphrodite_core::rendering::ChartSpecGenerator;
+    use chrono::{TimeZone, Utc};
+    use std::collections::HashMap;
+
+    let json = r#"{
+      "name": "Points",
+      "rings": [
+        { "slug": "ring_parts", "type": "points", "label": "Parts", "orderIndex": 0,
+          "radiusInner": 0.5, "radiusOuter": 0.6, "dataSource": { "kind": "custom_points", "pointSetId": "parts" } },
+        { "slug": "ring_stars", "type": "points", "label": "Stars", "orderIndex": 1,
+          "radiusInner": 0.6, "radiusOuter": 0.7,
+          "dataSource": { "kind": "fixed_stars", "layerId": "natal", "stars": ["regulus", "Spica"] } }
+      ]
+    }"#;
+    let definition = load_wheel_definition_from_json(json).unwrap();
+
+    let moment = Utc.with_ymd_and_hms(1990, 1, 1, 12, 0, 0).unwrap();
+    let stars = FIXED_STARS
+        .iter()
+        .map(|star| CustomPoint { id: star.id.to_string(), label: star.name.to_string(), lon: fixed_star_longitude(star, moment) })
+        .collect();
+    let points = WheelPoints {
+        point_sets: HashMap::from([(
+            "parts".to_string(),
+            vec![CustomPoint { id: "fortune".to_string(), label: "⊗".to_string(), lon: 90.0 }],
+        )]),
+        fixed_stars: HashMap::from([("natal".to_string(), stars)]),
+    };
+    let wheel = WheelAssembler::build_wheel_with_points(&definition.wheel, &HashMap::new(), &HashMap::new(), None, &points);
+    assert_eq!(wheel.rings[1].items.len(), 2);
+    let spec = ChartSpecGenerator::new().generate(&wheel, &HashMap::new(), 800.0, 800.0);
+
+    // 90° sits straight below the center; the label is 60% of the way across the ring
+    let fortune = spec.shape("ring_parts/point/fortune").unwrap();
+    assert_eq!(fortune.meta["kind"], "customPoint");
+    match &fortune.shape {
+        Shape::Text { position, content, .. } => {
+            assert_eq!(content, "⊗");
+            assert!((position.x - 400.0).abs() < 1e-3 && (position.y - 612.8).abs() < 1e-3, "{:?}", position);
+        }
+        other => panic!("expected text, got {:?}", other),
+    }
+    assert!(spec.shape("ring_parts/point/fortune/tick").is_some());
+
+    let regulus = spec.shape("ring_stars/point/regulus").unwrap();
+    assert_eq!(regulus.meta["kind"], "fixedStar");
+    assert_eq!(regulus.layer_ids, vec!["natal".to_string()]);
+    assert!(spec.shape("ring_stars/point/spica").is_some());
+    assert!(spec.shape("ring_stars/point/sirius").is_none());
+
+    // Without supplied points the rings stay empty
+    let bare = WheelAssembler::build_wheel(&definition.wheel, &HashMap::new(), &HashMap::new(), None);
+    assert!(bare.rings.iter().all(|ring| ring.items.is_empty()));
+}
+
+#[test]
+fn test_dial_chart() {
+    use aphrodite_core::ephemeris::{LayerPositions, PlanetPosition};
+    use aphrodite_core::rendering::{DialChartGenerator, DialSize};
+    use std::collections::HashMap;
+
+    let planet = |lon: f64| PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde: false };
+    let positions = LayerPositions {
+        planets: HashMap::from([
+            ("sun".to_string(), planet(0.0)),
+            ("moon".to_string(), planet(90.0)),
+            ("mars".to_string(), planet(225.0)),
+        ]),
+        houses: None,
+    };
+
+    // Sun and Moon are square, so they share the top of the 90° dial
+    let generator = DialChartGenerator::new(DialSize::Ninety).with_pointer(45.0);
+    let spec = generator.generate("natal", &positions, None, 800.0, 800.0);
+    let glyph = |id: &str| match &spec.shape(id).unwrap().shape {
+        Shape::PlanetGlyph { center, .. } => *center,
+        other => panic!("expected planet glyph, got {:?}", other),
+    };
+    let (sun, moon) = (glyph("dial/planet/sun"), glyph("dial/planet/moon"));
+    assert!((sun.x - 400.0).abs() < 1e-3 && (sun.y - (400.0 - 0.72 * 380.0)).abs() < 1e-3, "{:?}", sun);
+    assert!((moon.x - sun.x).abs() < 1e-3 && (moon.y - sun.y).abs() < 1e-3);
+    // Mars at 225° folds to 45°, the bottom of the dial
+    assert!((glyph("dial/planet/mars").y - (400.0 + 0.72 * 380.0)).abs() < 1e-3);
+
+    // Sun/Moon midpoint 45° is on the pointer; Moon/Mars (157.5°) is not
+    let midpoints = generator.midpoints(&positions, None);
+    assert_eq!(midpoints.len(), 3);
+    let moon_sun = midpoints.iter().find(|m| m.from == "moon" && m.to == "sun").unwrap();
+    assert!((moon_sun.lon - 45.0).abs() < 1e-9 && moon_sun.active);
+    let mars_moon = midpoints.iter().find(|m| m.from == "mars" && m.to == "moon").unwrap();
+    assert!((mars_moon.lon - 157.5).abs() < 1e-9 && !mars_moon.active);
+    assert_eq!(spec.shape("dial/midpoint/moon/sun").unwrap().meta["active"], "true");
+
+    assert_eq!(spec.shapes.iter().filter(|s| s.meta["kind"] == "tick").count(), 90);
+    assert!(spec.shape("dial/pointer").is_some());
+    let bare = generator.with_midpoints(false).generate("natal", &positions, None, 800.0, 800.0);
+    assert!(bare.shapes.iter().all(|s| s.meta["kind"] != "midpoint"));
+
+    let dial_45 = DialChartGenerator::new(DialSize::FortyFive).generate("natal", &positions, None, 800.0, 800.0);
+    assert_eq!(dial_45.shapes.iter().filter(|s| s.meta["kind"] == "label").count(), 9);
+}
+
+#[test]
+fn test_retrograde_marker_and_stationary_dimming() {
+    use aphrodite_core::ephemeris::{LayerPositions, PlanetPosition};
+    use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler};
+    use aphrodite_core::rendering::{ChartSpecGenerator, GlyphConfig, VisualConfig};
+    use std::collections::HashMap;
+
+    let json = r#"{
+      "name": "Planets",
+      "rings": [
+        { "slug": "ring_planets", "type": "planets", "label": "Planets", "orderIndex": 0,
+          "radiusInner": 0.5, "radiusOuter": 0.7, "dataSource": { "kind": "layer_planets", "layerId": "natal" } }
+      ]
+    }"#;
+    let definition = load_wheel_definition_from_json(json).unwrap();
+    let positions = HashMap::from([(
+        "natal".to_string(),
+        LayerPositions {
+            planets: HashMap::from([
+                ("mercury".to_string(), PlanetPosition { lon: 10.0, lat: 0.0, speed_lon: -0.05, retrograde: true }),
+                ("mars".to_string(), PlanetPosition { lon: 100.0, lat: 0.0, speed_lon: 0.7, retrograde: false }),
+            ]),
+            houses: None,
+        },
+    )]);
+    let wheel = WheelAssembler::build_wheel(&definition.wheel, &positions, &HashMap::new(), None);
+
+    let spec = ChartSpecGenerator::new().generate(&wheel, &HashMap::new(), 800.0, 800.0);
+    let marker = spec.shape("ring_planets/planet/mercury/retrograde").unwrap();
+    assert!(matches!(&marker.shape, Shape::Text { content, .. } if content == "℞"));
+    assert!(spec.shape("ring_planets/planet/mars/retrograde").is_none());
+    let mercury = spec.shape("ring_planets/planet/mercury").unwrap();
+    assert_eq!(mercury.meta["speed"], "stationary");
+    assert_eq!(spec.shape("ring_planets/planet/mars").unwrap().meta["speed"], "fast");
+    let alpha = |spec: &ChartSpec, id: &str| match &spec.shape(id).unwrap().shape {
+        Shape::PlanetGlyph { color, .. } => color.a,
+        other => panic!("expected planet glyph, got {:?}", other),
+    };
+    assert_eq!(alpha(&spec, "ring_planets/planet/mercury"), 255);
+
+    let visual = VisualConfig { dim_stationary: true, retrograde_marker: false, ..VisualConfig::default() };
+    let spec = ChartSpecGenerator::with_configs(visual, GlyphConfig::default()).generate(&wheel, &HashMap::new(), 800.0, 800.0);
+    assert_eq!(alpha(&spec, "ring_planets/planet/mercury"), 115);
+    assert_eq!(alpha(&spec, "ring_planets/planet/mars"), 255);
+    assert!(spec.shape("ring_planets/planet/mercury/retrograde").is_none());
+}
+
+#[test]
+fn test_degree_labels_follow_spread_glyphs() {
+    use aphrodite_core::ephemeris::{LayerPositions, PlanetPosition};
+    use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler};
+    use aphrodite_core::rendering::ChartSpecGenerator;
+    use std::collections::HashMap;
+
+    let json = r#"{
+      "name": "Planets",
+      "rings": [
+        { "slug": "ring_planets", "type": "planets", "label": "Planets", "orderIndex": 0,
+          "radiusInner": 0.5, "radiusOuter": 0.7, "dataSource": { "kind": "layer_planets", "layerId": "natal" },
+          "displayOptions": { "degreeLabels": true } }
+      ]
+    }"#;
+    let definition = load_wheel_definition_from_json(json).unwrap();
+    let planet = |lon: f64| PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde: false };
+    let positions = HashMap::from([(
+        "natal".to_string(),
+        LayerPositions {
+            planets: HashMap::from([
+                ("sun".to_string(), planet(10.5)),
+                ("moon".to_string(), planet(11.0)),
+                ("mars".to_string(), planet(200.25)),
+                ("venus".to_string(), planet(359.9)),
+                ("jupiter".to_string(), planet(0.1)),
+            ]),
+            houses: None,
+        },
+    )]);
+    let wheel = WheelAssembler::build_wheel(&definition.wheel, &positions, &HashMap::new(), None);
+    let spec = ChartSpecGenerator::new().generate(&wheel, &HashMap::new(), 800.0, 800.0);
+
+    let center = |id: &str| match &spec.shape(id).unwrap().shape {
+        Shape::PlanetGlyph { center, .. } | Shape::Text { position: center, .. } => *center,
+        other => panic!("unexpected shape {:?}", other),
+    };
+    let angle = |p: Point| (p.y - 400.0).atan2(p.x - 400.0).to_degrees();
+    // Sun and Moon are half a degree apart but drawn at least 1.2 glyph widths apart,
+    // centred on their mean position
+    let (sun, moon) = (angle(center("ring_planets/planet/sun")), angle(center("ring_planets/planet/moon")));
+    let radius = 380.0_f32 * 0.6;
+    assert!((moon - sun) >= (12.0 * 1.2 / radius).to_degrees() - 1e-3, "{} {}", sun, moon);
+    assert!(((sun + moon) / 2.0 - 10.75).abs() < 1e-3);
+    assert!((angle(center("ring_planets/planet/mars")) - (200.25 - 360.0)).abs() < 1e-3);
+    // Runs across 0° Aries are spread around it too
+    let (venus, jupiter) = (angle(center("ring_planets/planet/venus")), angle(center("ring_planets/planet/jupiter")));
+    assert!((venus + jupiter).abs() < 1e-3 && jupiter > 1.0, "{} {}", venus, jupiter);
+
+    // Labels sit inside their glyph, at the glyph's spread angle
+    let label = spec.shape("ring_planets/planet/sun/degree").unwrap();
+    assert!(matches!(&label.shape, Shape::Text { content, .. } if content == "♈ 10°30′"));
+    assert!((angle(center("ring_planets/planet/sun/degree")) - sun).abs() < 1e-3);
+    assert!(matches!(&spec.shape("ring_planets/planet/mars/degree").unwrap().shape,
+        Shape::Text { content, .. } if content == "♎ 20°15′"));
+}
+
+#[test]
+fn test_wheel_direction_invalid_value_rejected() {
+    use aphrodite_core::layout::load_wheel_definition_from_json;
+
+    let json = r#"{
+      "name": "Bad Direction",
+      "direction": "sideways",
+      "rings": [
+        {
+          "slug": "ring_signs",
+          "type": "signs",
+          "label": "Signs",
+          "orderIndex": 0,
+          "radiusInner": 0.8,
+          "radiusOuter": 1.0,
+          "dataSource": { "kind": "static_zodiac" }
+        }
+      ]
+    }"#;
+    assert!(load_wheel_definition_from_json(json).is_err());
+}
+
+/// Standard natal wheel with equal houses from 15° Aries and a sun-moon trine
+fn sun_moon_trine_spec() -> ChartSpec {
+    use aphrodite_core::aspects::{AspectCalculator, AspectSettings, DEFAULT_ORBS};
+    use aphrodite_core::ephemeris::{HousePositions, LayerPositions, PlanetPosition};
+    use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler, DEFAULT_WHEEL_JSON};
+    use aphrodite_core::rendering::ChartSpecGenerator;
+    use std::collections::HashMap;
+
+    let planet = |lon: f64| PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde: false };
+    let houses = HousePositions {
+        system: "equal".to_string(),
+        cusps: (1..=12).map(|n| (n.to_string(), (n - 1) as f64 * 30.0 + 15.0)).collect(),
+        angles: HashMap::new(),
+    };
+    let mut layers = HashMap::new();
+    layers.insert(
+        "natal".to_string(),
+        LayerPositions {
+            planets: HashMap::from([("sun".to_string(), planet(10.0)), ("moon".to_string(), planet(130.5))]),
+            houses: Some(houses),
+        },
+    );
+    let settings = AspectSettings {
+        orb_settings: DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect(),
+        include_objects: Vec::new(),
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    };
+    let aspects = AspectCalculator::new().compute_all_aspect_sets(&layers, &settings);
+
+    let wheel = load_wheel_definition_from_json(DEFAULT_WHEEL_JSON).unwrap();
+    let assembled = WheelAssembler::build_wheel(&wheel.wheel, &layers, &aspects, None);
+    ChartSpecGenerator::new().generate(&assembled, &aspects, 800.0, 800.0)
+}
+
+#[test]
+fn test_generate_house_segments_and_aspect_lines() {
+    let spec = sun_moon_trine_spec();
+
+    let mut house_nums: Vec<u8> = spec
+        .shapes
+        .iter()
+        .filter_map(|shape| match &shape.shape {
+            Shape::HouseSegment { house_num, .. } => Some(*house_num),
+            _ => None,
+        })
+        .collect();
+    house_nums.sort();
+    assert_eq!(house_nums, (1..=12).collect::<Vec<u8>>());
+
+    let lines: Vec<&ChartShape> =
+        spec.shapes.iter().filter(|shape| matches!(shape.shape, Shape::AspectLine { .. })).collect();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0].ring.as_deref(), Some(ASPECT_LINES_RING));
+    assert_eq!(lines[0].layer_ids, vec!["natal"]);
+    let Shape::AspectLine { from, to, aspect_type, from_object, to_object, .. } = &lines[0].shape else {
+        unreachable!()
+    };
+    assert_eq!(aspect_type, "trine");
+    let mut objects = vec![
+        from_object.as_ref().unwrap().object_id.clone(),
+        to_object.as_ref().unwrap().object_id.clone(),
+    ];
+    objects.sort();
+    assert_eq!(objects, vec!["moon", "sun"]);
+
+    // Endpoints sit on the inner edge of the planet ring (0.55 of the 380px radius)
+    for point in [from, to] {
+        let distance = ((point.x - 400.0).powi(2) + (point.y - 400.0).powi(2)).sqrt();
+        assert!((distance - 0.55 * 380.0).abs() < 1e-2);
+    }
+}
+
+/// Sun trine Moon 0.5° from exact and Sun square Mars 3° from exact, drawn with `visual`
+fn aspect_styling_spec(visual: aphrodite_core::rendering::VisualConfig) -> ChartSpec {
+    use aphrodite_core::aspects::{AspectCalculator, AspectSettings, DEFAULT_ORBS};
+    use aphrodite_core::ephemeris::{LayerPositions, PlanetPosition};
+    use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler, DEFAULT_WHEEL_JSON};
+    use aphrodite_core::rendering::{ChartSpecGenerator, GlyphConfig};
+    use std::collections::HashMap;
+
+    let planet = |lon: f64| PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde: false };
+    let planets = [("sun", 10.0), ("moon", 130.5), ("mars", 283.0)];
+    let layers = HashMap::from([(
+        "natal".to_string(),
+        LayerPositions {
+            planets: planets.iter().map(|(id, lon)| (id.to_string(), planet(*lon))).collect(),
+            houses: None,
+        },
+    )]);
+    let settings = AspectSettings {
+        orb_settings: DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect(),
+        include_objects: Vec::new(),
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    };
+    let aspects = AspectCalculator::new().compute_all_aspect_sets(&layers, &settings);
+
+    let wheel = load_wheel_definition_from_json(DEFAULT_WHEEL_JSON).unwrap();
+    let assembled = WheelAssembler::build_wheel(&wheel.wheel, &layers, &aspects, None);
+    ChartSpecGenerator::with_configs(visual, GlyphConfig::default()).generate(&assembled, &aspects, 800.0, 800.0)
+}
+
+#[test]
+fn test_aspect_lines_styled_by_type_and_orb() {
+    use aphrodite_core::rendering::VisualConfig;
+
+    let line = |spec: &ChartSpec, aspect: &str| {
+        spec.shapes
+            .iter()
+            .find_map(|shape| match &shape.shape {
+                Shape::AspectLine { aspect_type, color, width, style, .. } if aspect_type == aspect => {
+                    Some((*color, *width, *style, shape.meta["orb"].clone()))
+                }
+                _ => None,
+            })
+            .unwrap()
+    };
+
+    let spec = aspect_styling_spec(VisualConfig::default());
+    let (trine_color, trine_width, trine_style, trine_orb) = line(&spec, "trine");
+    let (square_color, square_width, square_style, square_orb) = line(&spec, "square");
+    assert!(matches!(trine_style, LineStyle::Dashed));
+    assert!(matches!(square_style, LineStyle::Solid));
+    assert_eq!((trine_orb.as_str(), square_orb.as_str()), ("0.50", "3.00"));
+    // 0.5° of a 7° trine orb is nearly exact; 3° of a 6° square orb is halfway out
+    assert!(trine_color.a > 240 && trine_width > 1.9, "{} {}", trine_color.a, trine_width);
+    assert_eq!(square_color.a, (255.0_f32 * (0.35 + 0.65 * 0.5)).round() as u8);
+    assert!((square_width - 2.0 * 0.75).abs() < 1e-4);
+
+    // The theme can restyle types and turn the orb scaling off
+    let mut visual = VisualConfig { aspect_min_alpha: 1.0, aspect_min_width: 1.0, ..VisualConfig::default() };
+    visual.aspect_styles.insert("trine".to_string(), LineStyle::Dotted);
+    visual.aspect_colors.insert("square".to_string(), Color::WHITE);
+    let spec = aspect_styling_spec(visual);
+    let (_, trine_width, trine_style, _) = line(&spec, "trine");
+    let (square_color, square_width, _, _) = line(&spec, "square");
+    assert!(matches!(trine_style, LineStyle::Dotted));
+    assert_eq!((square_color.r, square_color.g, square_color.b, square_color.a), (255, 255, 255, 255));
+    assert_eq!((trine_width, square_width), (2.0, 2.0));
+}
+
+#[test]
+fn test_shape_ids_and_meta() {
+    let spec = sun_moon_trine_spec();
+
+    let mut ids: Vec<&str> = spec.shapes.iter().map(|shape| shape.id.as_str()).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), spec.shapes.len(), "shape ids must be unique");
+    // The same input yields the same ids
+    let again = sun_moon_trine_spec();
+    let mut again: Vec<&str> = again.shapes.iter().map(|shape| shape.id.as_str()).collect();
+    again.sort();
+    assert_eq!(again, ids);
+
+    let sun = spec.shape("ring_planets/planet/sun").expect("sun glyph");
+    assert!(matches!(&sun.shape, Shape::PlanetGlyph { planet_id, .. } if planet_id == "sun"));
+    assert_eq!(sun.meta["kind"], "planet");
+    assert_eq!(sun.meta["layer"], "natal");
+    assert_eq!(sun.meta["ring"], "ring_planets");
+
+    let house = spec.shape("ring_houses/house/10").expect("tenth house");
+    assert_eq!(house.meta["house"], "10");
+    assert_eq!(spec.shape("ring_signs/sign/0").unwrap().meta["sign"], "0");
+
+    let aspect = spec
+        .shapes
+        .iter()
+        .find(|shape| shape.meta.get("kind").map(String::as_str) == Some("aspect"))
+        .expect("aspect line");
+    assert_eq!(aspect.meta["aspect"], "trine");
+    assert_eq!(aspect.id, format!("aspects/{}/trine/{}", aspect.meta["from"], aspect.meta["to"]));
+    assert_eq!(aspect.id, "aspects/natal.moon/trine/natal.sun");
+
+    let json = serde_json::to_value(sun).unwrap();
+    assert_eq!(json["id"], "ring_planets/planet/sun");
+    assert_eq!(json["meta"]["planet"], "sun");
+}
+
+#[test]
+fn test_shapes_tagged_with_ring_and_layer() {
+    use aphrodite_core::layout::WheelDirection;
+    use aphrodite_core::rendering::ChartSpecGenerator;
+    use std::collections::HashMap;
+
+    let spec = ChartSpecGenerator::new().generate(&single_planet_wheel(WheelDirection::Ccw), &HashMap::new(), 800.0, 800.0);
+    let glyph = spec
+        .shapes
+        .iter()
+        .find(|shape| matches!(shape.shape, Shape::PlanetGlyph { .. }))
+        .expect("planet glyph");
+    assert_eq!(glyph.ring.as_deref(), Some("ring_planets"));
+    assert_eq!(glyph.layer_ids, vec!["natal"]);
+
+    // Tags serialize next to the shape's own fields and round-trip
+    let json = serde_json::to_value(glyph).unwrap();
+    assert_eq!(json["type"], "PlanetGlyph");
+    assert_eq!(json["planet_id"], "sun");
+    assert_eq!(json["ring"], "ring_planets");
+    let parsed: ChartShape = serde_json::from_value(json).unwrap();
+    assert_eq!(parsed.layer_ids, vec!["natal"]);
+
+    // Untagged shapes omit the fields
+    let json = serde_json::to_value(ChartShape::from(Shape::Circle {
+        center: Point { x: 0.0, y: 0.0 },
+        radius: 1.0,
+        fill: None,
+        stroke: None,
+    }))
+    .unwrap();
+    assert!(json.get("ring").is_none() && json.get("layer_ids").is_none());
+    assert!(json.get("id").is_none() && json.get("meta").is_none());
+}
+
+#[test]
+fn test_text_baseline_defaults_to_alphabetic() {
+    use aphrodite_core::rendering::TextBaseline;
+
+    let json = r#"{"type": "Text", "position": {"x": 1, "y": 2}, "content": "10°", "size": 12,
+        "color": {"r": 255, "g": 255, "b": 255, "a": 255}, "anchor": "Middle", "rotation": null}"#;
+    let Shape::Text { baseline, .. } = serde_json::from_str(json).unwrap() else {
+        panic!("expected text")
+    };
+    assert_eq!(baseline, TextBaseline::Alphabetic);
+
+    let json = json.replace(r#""rotation": null"#, r#""baseline": "Middle", "rotation": 90"#);
+    let Shape::Text { baseline, rotation, .. } = serde_json::from_str(&json).unwrap() else {
+        panic!("expected text")
+    };
+    assert_eq!(baseline, TextBaseline::Middle);
+    assert_eq!(rotation, Some(90.0));
+}
+
+#[test]
+fn test_style_rules_color_dim_and_highlight() {
+    use aphrodite_core::ephemeris::{HousePositions, LayerPositions, PlanetPosition};
+    use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler};
+    use aphrodite_core::rendering::ChartSpecGenerator;
+    use std::collections::HashMap;
+
+    let json = r##"{
+      "name": "Styled",
+      "rings": [
+        {
+          "slug": "signs", "type": "signs", "label": "Signs", "orderIndex": 0,
+          "radiusInner": 0.8, "radiusOuter": 1.0, "dataSource": { "kind": "static_zodiac" },
+          "styleRules": [{ "when": { "element": "fire" }, "style": { "color": "#FF4500" } }]
+        },
+        {
+          "slug": "planets", "type": "planets", "label": "Planets", "orderIndex": 1,
+          "radiusInner": 0.5, "radiusOuter": 0.8,
+          "dataSource": { "kind": "layer_planets", "layerId": "natal" },
+          "styleRules": [
+            { "when": { "retrograde": true }, "style": { "opacity": 0.5 } },
+            { "when": { "nearAngle": 3 }, "style": { "highlight": "#FFFF0080" } }
+          ]
+        }
+      ]
+    }"##;
+    let wheel = load_wheel_definition_from_json(json).unwrap();
+
+    let planet = |lon: f64, retrograde: bool| PlanetPosition { lon, lat: 0.0, speed_lon: 1.0, retrograde };
+    let angles = [("asc", 90.0), ("mc", 0.0), ("dc", 270.0), ("ic", 180.0)];
+    let layers = HashMap::from([(
+        "natal".to_string(),
+        LayerPositions {
+            planets: HashMap::from([
+                ("sun".to_string(), planet(10.0, false)),
+                ("moon".to_string(), planet(120.0, true)),
+                ("mars".to_string(), planet(92.0, false)),
+            ]),
+            houses: Some(HousePositions {
+                system: "equal".to_string(),
+                cusps: (1..=12).map(|n| (n.to_string(), (n - 1) as f64 * 30.0 + 90.0)).collect(),
+                angles: angles.iter().map(|(id, lon)| (id.to_string(), *lon)).collect(),
+            }),
+        },
+    )]);
+    let assembled = WheelAssembler::build_wheel(&wheel.wheel, &layers, &HashMap::new(), None);
+    let spec = ChartSpecGenerator::new().generate(&assembled, &HashMap::new(), 800.0, 800.0);
+    let shape = |id: &str| &spec.shapes.iter().find(|shape| shape.id == id).unwrap_or_else(|| panic!("{}", id)).shape;
+
+    let Shape::SignSegment { fill, .. } = shape("signs/sign/4") else { panic!() };
+    assert_eq!((fill.r, fill.g, fill.b), (0xFF, 0x45, 0x00));
+    let Shape::SignSegment { fill, .. } = shape("signs/sign/1") else { panic!() };
+    assert_ne!((fill.r, fill.g, fill.b), (0xFF, 0x45, 0x00));
+
+    let Shape::PlanetGlyph { color, .. } = shape("planets/planet/moon") else { panic!() };
+    assert_eq!(color.a, 128);
+    let Shape::PlanetGlyph { color, .. } = shape("planets/planet/sun") else { panic!() };
+    assert_eq!(color.a, 255);
+
+    let highlights: Vec<&str> = spec
+        .shapes
+        .iter()
+        .filter(|shape| shape.meta.get("kind").map(String::as_str) == Some("highlight"))
+        .map(|shape| shape.id.as_str())
+        .collect();
+    assert_eq!(highlights, vec!["planets/planet/mars/highlight"]);
+    let Shape::Circle { fill: Some(fill), .. } = shape("planets/planet/mars/highlight") else { panic!() };
+    assert_eq!(fill.a, 0x80);
+}
+
+#[test]
+fn test_style_rules_rejects_bad_values() {
+    use aphrodite_core::layout::load_wheel_definition_from_json;
+
+    let wheel = |rule: &str| {
+        format!(
+            r#"{{"name": "Styled", "rings": [{{"slug": "signs", "type": "signs", "label": "Signs", "orderIndex": 0,
+                "radiusInner": 0.8, "radiusOuter": 1.0, "dataSource": {{ "kind": "static_zodiac" }},
+                "styleRules": [{}]}}]}}"#,
+            rule
+        )
+    };
+    assert!(load_wheel_definition_from_json(&wheel(r##"{"when": {"element": "air"}, "style": {"color": "#87CEEB"}}"##)).is_ok());
+    for rule in [
+        r#"{"when": {"element": "air"}, "style": {"color": "blue"}}"#,
+        r#"{"when": {"element": "aether"}, "style": {}}"#,
+        r#"{"style": {"opacity": 2}}"#,
+        r#"{"when": {"nearAngle": 45}, "style": {}}"#,
+        r#"{"when": {"retrograde": true}}"#,
+    ] {
+        assert!(load_wheel_definition_from_json(&wheel(rule)).is_err(), "{}", rule);
+    }
+}
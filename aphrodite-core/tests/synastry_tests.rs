@@ -0,0 +1,136 @@
+use aphrodite_core::aspects::AspectSettings;
+use aphrodite_core::ephemeris::{HousePositions, LayerPositions, PlanetPosition};
+use aphrodite_core::synastry::{
+    compute_cross_aspects, compute_house_overlays, compute_synastry, score_compatibility,
+};
+use std::collections::HashMap;
+
+fn planet(lon: f64) -> PlanetPosition {
+    PlanetPosition {
+        lon,
+        lat: 0.0,
+        speed_lon: 1.0,
+        retrograde: false,
+    }
+}
+
+fn layer(planets: &[(&str, f64)], with_houses: bool) -> LayerPositions {
+    let planets = planets
+        .iter()
+        .map(|(id, lon)| (id.to_string(), planet(*lon)))
+        .collect();
+    let houses = if with_houses {
+        // Equal houses starting at 0 Aries
+        let cusps = (1..=12)
+            .map(|n| (n.to_string(), (n - 1) as f64 * 30.0))
+            .collect();
+        Some(HousePositions {
+            system: "equal".to_string(),
+            cusps,
+            angles: HashMap::new(),
+        })
+    } else {
+        None
+    };
+    LayerPositions { planets, houses }
+}
+
+fn settings() -> AspectSettings {
+    let orb_settings = [
+        ("conjunction", 8.0),
+        ("opposition", 8.0),
+        ("trine", 7.0),
+        ("square", 6.0),
+        ("sextile", 4.0),
+    ]
+    .into_iter()
+    .map(|(k, v)| (k.to_string(), v))
+    .collect();
+    AspectSettings {
+        orb_settings,
+        include_objects: vec![],
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    }
+}
+
+#[test]
+fn test_cross_aspects_include_same_planet_contacts() {
+    let a = layer(&[("sun", 10.0)], false);
+    let b = layer(&[("sun", 12.0)], false);
+
+    let aspects = compute_cross_aspects("alice", "bob", &a, &b, &settings());
+    assert_eq!(aspects.id, "alice:bob");
+    assert_eq!(aspects.pairs.len(), 1);
+    assert_eq!(aspects.pairs[0].aspect.aspect_type, "conjunction");
+    assert_eq!(aspects.pairs[0].from.layer_id, "alice");
+    assert_eq!(aspects.pairs[0].to.layer_id, "bob");
+}
+
+#[test]
+fn test_house_overlays() {
+    let a = layer(&[("sun", 45.0), ("moon", 200.0)], false);
+    let b = layer(&[("venus", 10.0)], true);
+
+    let overlays = compute_house_overlays("alice", &a, "bob", &b, &[]);
+    assert_eq!(overlays.len(), 2);
+    // Sorted by planet id
+    assert_eq!(overlays[0].planet_id, "moon");
+    assert_eq!(overlays[0].house, 7);
+    assert_eq!(overlays[1].planet_id, "sun");
+    assert_eq!(overlays[1].house, 2);
+    assert_eq!(overlays[1].house_layer_id, "bob");
+
+    // No houses in the other chart, no overlays
+    assert!(compute_house_overlays("bob", &b, "alice", &a, &[]).is_empty());
+}
+
+#[test]
+fn test_score_harmony_and_tension() {
+    let a = layer(&[("sun", 0.0)], false);
+
+    let trine = compute_cross_aspects("a", "b", &a, &layer(&[("moon", 120.0)], false), &settings());
+    let score = score_compatibility(&trine, &settings().orb_settings);
+    assert!((score.score - 100.0).abs() < 1e-9);
+    assert!(score.harmony > 0.0);
+    assert_eq!(score.tension, 0.0);
+
+    let square = compute_cross_aspects("a", "b", &a, &layer(&[("moon", 90.0)], false), &settings());
+    let score = score_compatibility(&square, &settings().orb_settings);
+    assert!(score.score.abs() < 1e-9);
+    assert!(score.by_aspect_type["square"] < 0.0);
+
+    let none = compute_cross_aspects("a", "b", &a, &layer(&[("moon", 45.0)], false), &settings());
+    assert_eq!(score_compatibility(&none, &settings().orb_settings).score, 50.0);
+}
+
+#[test]
+fn test_tighter_aspects_score_higher() {
+    let a = layer(&[("sun", 0.0)], false);
+    let exact = compute_cross_aspects("a", "b", &a, &layer(&[("moon", 120.0)], false), &settings());
+    let wide = compute_cross_aspects("a", "b", &a, &layer(&[("moon", 126.0)], false), &settings());
+
+    let orbs = settings().orb_settings;
+    assert!(score_compatibility(&exact, &orbs).harmony > score_compatibility(&wide, &orbs).harmony);
+}
+
+#[test]
+fn test_compute_synastry_report() {
+    let a = layer(&[("sun", 15.0), ("venus", 100.0)], true);
+    let b = layer(&[("moon", 17.0), ("mars", 190.0)], true);
+
+    let report = compute_synastry("alice", "bob", &a, &b, &settings());
+    assert_eq!(report.layer_ids, vec!["alice", "bob"]);
+    assert_eq!(report.house_overlays.len(), 4);
+    assert!(report
+        .aspects
+        .pairs
+        .iter()
+        .any(|p| p.from.object_id == "sun" && p.to.object_id == "moon"));
+
+    let json = serde_json::to_value(&report).unwrap();
+    assert!(json["houseOverlays"].is_array());
+    assert!(json["compatibility"]["byAspectType"].is_object());
+}
@@ -0,0 +1,124 @@
+use aphrodite_core::aspects::{AspectSettings, DEFAULT_ORBS};
+use aphrodite_core::ephemeris::{HousePositions, LayerPositions, PlanetPosition};
+use aphrodite_core::transits::{compute_transit_timeline, compute_transit_timeline_each};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+
+fn planet(lon: f64) -> PlanetPosition {
+    PlanetPosition {
+        lon,
+        lat: 0.0,
+        speed_lon: 1.0,
+        retrograde: false,
+    }
+}
+
+fn settings() -> AspectSettings {
+    AspectSettings {
+        orb_settings: DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect(),
+        include_objects: vec![],
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    }
+}
+
+fn start() -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap().with_timezone(&Utc)
+}
+
+/// A transiting Sun that starts at 0° Aries and moves one degree a day
+fn transit_sun(moment: DateTime<Utc>) -> Result<LayerPositions, String> {
+    let days = (moment - start()).num_days() as f64;
+    Ok(LayerPositions {
+        planets: HashMap::from([("sun".to_string(), planet(days))]),
+        houses: None,
+    })
+}
+
+fn natal() -> LayerPositions {
+    LayerPositions {
+        planets: HashMap::from([
+            ("sun".to_string(), planet(2.0)),
+            ("mars".to_string(), planet(95.0)),
+            ("venus".to_string(), planet(65.5)),
+        ]),
+        houses: Some(HousePositions {
+            system: "whole_sign".to_string(),
+            cusps: HashMap::new(),
+            angles: HashMap::from([("asc".to_string(), 181.0)]),
+        }),
+    }
+}
+
+#[test]
+fn test_transit_timeline_matrix() {
+    let timeline =
+        compute_transit_timeline(&natal(), start(), start() + Duration::days(5), &settings(), transit_sun).unwrap();
+
+    assert_eq!(timeline.dates.len(), 5);
+    assert_eq!(timeline.dates[4], start() + Duration::days(4));
+
+    let rows: Vec<String> = timeline
+        .rows
+        .iter()
+        .map(|row| format!("{}-{}:{}", row.transit_id, row.natal_id, row.aspect_type))
+        .collect();
+    // The transiting Sun meets its own natal place; angles stay out by default
+    assert_eq!(rows, vec!["sun-mars:square", "sun-sun:conjunction", "sun-venus:sextile"]);
+
+    let conjunction = &timeline.rows[1];
+    assert_eq!(conjunction.natal_type, "planet");
+    assert_eq!(conjunction.peak_index, 2);
+    let orbs: Vec<f64> = conjunction.orbs.iter().map(|orb| orb.unwrap()).collect();
+    assert_eq!(orbs, vec![2.0, 1.0, 0.0, 1.0, 2.0]);
+
+    // The sextile comes into orb on the third day
+    let sextile = &timeline.rows[2];
+    assert_eq!(sextile.orbs, vec![None, None, Some(3.5), Some(2.5), Some(1.5)]);
+    assert_eq!(sextile.peak_index, 4);
+}
+
+#[test]
+fn test_transit_timeline_angles_and_errors() {
+    let mut settings = settings();
+    settings.filter.include_angles = true;
+    let timeline =
+        compute_transit_timeline(&natal(), start(), start() + Duration::days(3), &settings, transit_sun).unwrap();
+    let asc = timeline.rows.iter().find(|row| row.natal_id == "asc").unwrap();
+    assert_eq!(asc.natal_type, "angle");
+    assert_eq!(asc.aspect_type, "opposition");
+    assert_eq!(asc.peak_index, 1);
+
+    let empty = compute_transit_timeline(&natal(), start(), start(), &settings, transit_sun).unwrap();
+    assert!(empty.dates.is_empty() && empty.rows.is_empty());
+
+    let error = compute_transit_timeline(&natal(), start(), start() + Duration::days(3), &settings, |_| {
+        Err("no ephemeris".to_string())
+    });
+    assert_eq!(error.unwrap_err(), "no ephemeris");
+}
+
+#[test]
+fn test_transit_timeline_each_day() {
+    let mut days = Vec::new();
+    compute_transit_timeline_each(&natal(), start(), start() + Duration::days(3), &settings(), transit_sun, |day| {
+        days.push(day)
+    })
+    .unwrap();
+
+    assert_eq!(days.len(), 3);
+    assert_eq!(days[2].date, start() + Duration::days(2));
+    let contacts: Vec<String> = days[2]
+        .contacts
+        .iter()
+        .map(|contact| format!("{}-{}:{}:{}", contact.transit_id, contact.natal_id, contact.aspect_type, contact.orb))
+        .collect();
+    assert_eq!(contacts, vec!["sun-mars:square:3", "sun-sun:conjunction:0", "sun-venus:sextile:3.5"]);
+
+    // Each day carries the orbs the matrix collects
+    let timeline =
+        compute_transit_timeline(&natal(), start(), start() + Duration::days(3), &settings(), transit_sun).unwrap();
+    assert_eq!(timeline.dates, days.iter().map(|day| day.date).collect::<Vec<_>>());
+}
@@ -0,0 +1,84 @@
+#ifndef APHRODITE_H
+#define APHRODITE_H
+
+/* Generated by cbindgen from aphrodite-ffi; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Opaque engine handle owning an ephemeris adapter
+typedef struct AphroditeEngine AphroditeEngine;
+
+// Create an engine reading ephemeris files from `ephemeris_path`, or from
+// `SWISS_EPHEMERIS_PATH` / /usr/local/share/swisseph when it is NULL.
+//
+// Returns NULL on failure. When `error` is not NULL it then receives an error envelope,
+// to be released with `aphrodite_string_free`.
+//
+// # Safety
+// `ephemeris_path` must be NULL or a NUL-terminated UTF-8 string, and `error` must be
+// NULL or point to writable storage for one pointer.
+struct AphroditeEngine *aphrodite_engine_new(const char *ephemeris_path, char **error);
+
+// Release an engine created by `aphrodite_engine_new`; NULL is ignored.
+//
+// # Safety
+// `engine` must be NULL or a pointer returned by `aphrodite_engine_new` that has not
+// been freed yet.
+void aphrodite_engine_free(struct AphroditeEngine *engine);
+
+// Planet and house positions for a moment.
+//
+// Request: `{"datetime", "location"?, "zodiac_type"?, "ayanamsa"?, "custom_ayanamsa"?,
+// "house_system"?, "include_objects"?}`.
+//
+// # Safety
+// `engine` must be a live engine not used concurrently from another thread, and
+// `request` a NUL-terminated UTF-8 string.
+char *aphrodite_calc_positions(struct AphroditeEngine *engine, const char *request);
+
+// Intra- and inter-layer aspect sets keyed by set id.
+//
+// Request: `{"layers", "orbs"?, "include_objects"?}`.
+//
+// # Safety
+// `request` must be a NUL-terminated UTF-8 string.
+char *aphrodite_aspects(const char *request);
+
+// Dasha periods from a layer's sidereal positions.
+//
+// Request: `{"positions", "birth_datetime", "system"?, "depth"?}`.
+//
+// # Safety
+// `request` must be a NUL-terminated UTF-8 string.
+char *aphrodite_dashas(const char *request);
+
+// ChartSpec drawing instructions for a wheel.
+//
+// Request: `{"layers", "aspects"?, "wheel_json"?, "width"?, "height"?, "include_objects"?}`.
+//
+// # Safety
+// `request` must be a NUL-terminated UTF-8 string.
+char *aphrodite_chartspec(const char *request);
+
+// A longitude written the way chart labels write it, e.g. "15°32′ Taurus".
+//
+// Request: `{"longitude", "format"?}` with format "zodiacal", "absolute" or "nakshatraPada".
+//
+// # Safety
+// `request` must be a NUL-terminated UTF-8 string.
+char *aphrodite_format_longitude(const char *request);
+
+// Release a string returned by this library; NULL is ignored.
+//
+// # Safety
+// `value` must be NULL or a string returned by this library that has not been freed yet.
+void aphrodite_string_free(char *value);
+
+// Library version as a static NUL-terminated string; do not free it.
+const char *aphrodite_version(void);
+
+#endif  /* APHRODITE_H */
@@ -0,0 +1,396 @@
+//! C ABI for embedding the chart engine in native apps (Swift, Kotlin via JNI, C).
+//!
+//! Every calculation takes a JSON request (see `requests`) and returns a JSON envelope,
+//! either `{"result": ...}` or `{"error": {"code": ..., "message": ...}}`, plus the
+//! engine's own code in `"reason"` when the calculation failed (see
+//! `aphrodite_core::Error::code`). Returned
+//! strings belong to the caller and must be released with `aphrodite_string_free`.
+//! `include/aphrodite.h` is generated from this file by cbindgen.
+
+use aphrodite_core::aspects::{AspectCalculator, AspectSet, AspectSettings, DEFAULT_ORBS};
+use aphrodite_core::ephemeris::{lock_swiss_state, EphemerisError, LayerPositions, SwissEphemerisAdapter};
+use aphrodite_core::format::format_longitude;
+use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler, DEFAULT_WHEEL_JSON};
+use aphrodite_core::rendering::ChartSpecGenerator;
+use aphrodite_core::vedic::{compute_dasha, VimshottariResponse, DASHA_SYSTEMS};
+use aphrodite_core::ErrorCategory;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::ptr;
+use thiserror::Error;
+
+pub mod requests;
+
+use requests::{AspectsRequest, ChartSpecRequest, DashasRequest, FormatLongitudeRequest, PositionsRequest};
+
+/// Errors reported in the `error` envelope
+#[derive(Error, Debug)]
+enum FfiError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("{0}")]
+    Internal(String),
+    #[error("{0}")]
+    Core(aphrodite_core::Error),
+}
+
+impl FfiError {
+    fn code(&self) -> &'static str {
+        match self {
+            FfiError::InvalidRequest(_) => "INVALID_REQUEST",
+            FfiError::Internal(_) => "INTERNAL_ERROR",
+            FfiError::Core(error) => match error.category() {
+                ErrorCategory::InvalidInput => "INVALID_REQUEST",
+                ErrorCategory::EphemerisData => "EPHEMERIS_ERROR",
+                ErrorCategory::Calculation => "CALCULATION_ERROR",
+            },
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let mut error = json!({ "code": self.code(), "message": self.to_string() });
+        if let FfiError::Core(core) = self {
+            error["reason"] = json!(core.code());
+        }
+        json!({ "error": error })
+    }
+}
+
+impl From<aphrodite_core::Error> for FfiError {
+    fn from(error: aphrodite_core::Error) -> Self {
+        FfiError::Core(error)
+    }
+}
+
+impl From<EphemerisError> for FfiError {
+    fn from(error: EphemerisError) -> Self {
+        aphrodite_core::Error::from(error).into()
+    }
+}
+
+/// Opaque engine handle owning an ephemeris adapter
+pub struct AphroditeEngine {
+    adapter: SwissEphemerisAdapter,
+}
+
+/// Create an engine reading ephemeris files from `ephemeris_path`, or from
+/// `SWISS_EPHEMERIS_PATH` / /usr/local/share/swisseph when it is NULL.
+///
+/// Returns NULL on failure. When `error` is not NULL it then receives an error envelope,
+/// to be released with `aphrodite_string_free`.
+///
+/// # Safety
+/// `ephemeris_path` must be NULL or a NUL-terminated UTF-8 string, and `error` must be
+/// NULL or point to writable storage for one pointer.
+#[no_mangle]
+pub unsafe extern "C" fn aphrodite_engine_new(
+    ephemeris_path: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut AphroditeEngine {
+    let created = catch_unwind(|| -> Result<AphroditeEngine, FfiError> {
+        let path = if ephemeris_path.is_null() {
+            None
+        } else {
+            Some(PathBuf::from(read_str(ephemeris_path)?))
+        };
+        let adapter = SwissEphemerisAdapter::new(path)?;
+        Ok(AphroditeEngine { adapter })
+    })
+    .unwrap_or_else(|_| Err(FfiError::Internal("panic while creating engine".to_string())));
+
+    match created {
+        Ok(engine) => Box::into_raw(Box::new(engine)),
+        Err(e) => {
+            if !error.is_null() {
+                *error = into_c_string(&e.to_json());
+            }
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Release an engine created by `aphrodite_engine_new`; NULL is ignored.
+///
+/// # Safety
+/// `engine` must be NULL or a pointer returned by `aphrodite_engine_new` that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aphrodite_engine_free(engine: *mut AphroditeEngine) {
+    if !engine.is_null() {
+        drop(Box::from_raw(engine));
+    }
+}
+
+/// Planet and house positions for a moment.
+///
+/// Request: `{"datetime", "location"?, "zodiac_type"?, "ayanamsa"?, "custom_ayanamsa"?,
+/// "house_system"?, "include_objects"?}`.
+///
+/// # Safety
+/// `engine` must be a live engine not used concurrently from another thread, and
+/// `request` a NUL-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn aphrodite_calc_positions(
+    engine: *mut AphroditeEngine,
+    request: *const c_char,
+) -> *mut c_char {
+    respond(|| {
+        let engine = engine
+            .as_mut()
+            .ok_or_else(|| FfiError::InvalidRequest("engine is NULL".to_string()))?;
+        let request: PositionsRequest = parse_request(request)?;
+        // Engines may be driven from several threads; on Apple and Windows they share one Swiss Ephemeris state
+        let _swiss_state = lock_swiss_state();
+        engine
+            .adapter
+            .calc_positions(request.datetime, request.location.clone(), &request.settings())
+            .map_err(Into::into)
+    })
+}
+
+/// Intra- and inter-layer aspect sets keyed by set id.
+///
+/// Request: `{"layers", "orbs"?, "include_objects"?}`.
+///
+/// # Safety
+/// `request` must be a NUL-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn aphrodite_aspects(request: *const c_char) -> *mut c_char {
+    respond(|| {
+        let request: AspectsRequest = parse_request(request)?;
+        Ok(aspect_sets(&request.layers, request.orbs, request.include_objects))
+    })
+}
+
+/// Dasha periods from a layer's sidereal positions.
+///
+/// Request: `{"positions", "birth_datetime", "system"?, "depth"?}`.
+///
+/// # Safety
+/// `request` must be a NUL-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn aphrodite_dashas(request: *const c_char) -> *mut c_char {
+    respond(|| {
+        let request: DashasRequest = parse_request(request)?;
+        if !DASHA_SYSTEMS.contains(&request.system.as_str()) {
+            return Err(FfiError::InvalidRequest(format!(
+                "Unknown dasha system '{}' (available: {})",
+                request.system,
+                DASHA_SYSTEMS.join(", ")
+            )));
+        }
+        let periods = compute_dasha(&request.system, request.birth_datetime, &request.positions, request.depth)?;
+        Ok(VimshottariResponse {
+            system: request.system,
+            depth: request.depth,
+            birth_date_time: request.birth_datetime,
+            node_type: None,
+            periods,
+        })
+    })
+}
+
+/// ChartSpec drawing instructions for a wheel.
+///
+/// Request: `{"layers", "aspects"?, "wheel_json"?, "width"?, "height"?, "include_objects"?}`.
+///
+/// # Safety
+/// `request` must be a NUL-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn aphrodite_chartspec(request: *const c_char) -> *mut c_char {
+    respond(|| {
+        let request: ChartSpecRequest = parse_request(request)?;
+        let aspects = match request.aspects {
+            Some(aspects) => aspects,
+            None => aspect_sets(&request.layers, HashMap::new(), request.include_objects.clone()),
+        };
+        let wheel = load_wheel_definition_from_json(request.wheel_json.as_deref().unwrap_or(DEFAULT_WHEEL_JSON))
+            .map_err(aphrodite_core::Error::from)?;
+        let include_objects = Some(&request.include_objects[..]).filter(|objects| !objects.is_empty());
+        let assembled = WheelAssembler::build_wheel(&wheel.wheel, &request.layers, &aspects, include_objects);
+        Ok(ChartSpecGenerator::new().generate(&assembled, &aspects, request.width, request.height))
+    })
+}
+
+/// A longitude written the way chart labels write it, e.g. "15°32′ Taurus".
+///
+/// Request: `{"longitude", "format"?}` with format "zodiacal", "absolute" or "nakshatraPada".
+///
+/// # Safety
+/// `request` must be a NUL-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn aphrodite_format_longitude(request: *const c_char) -> *mut c_char {
+    respond(|| {
+        let request: FormatLongitudeRequest = parse_request(request)?;
+        Ok(format_longitude(request.longitude, request.format))
+    })
+}
+
+/// Release a string returned by this library; NULL is ignored.
+///
+/// # Safety
+/// `value` must be NULL or a string returned by this library that has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn aphrodite_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+/// Library version as a static NUL-terminated string; do not free it.
+#[no_mangle]
+pub extern "C" fn aphrodite_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+fn aspect_sets(
+    layers: &HashMap<String, LayerPositions>,
+    orbs: HashMap<String, f64>,
+    include_objects: Vec<String>,
+) -> HashMap<String, AspectSet> {
+    let mut orb_settings: HashMap<String, f64> =
+        DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect();
+    orb_settings.extend(orbs);
+    let settings = AspectSettings {
+        orb_settings,
+        include_objects,
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    };
+    AspectCalculator::new().compute_all_aspect_sets(layers, &settings)
+}
+
+/// Run a call, turning its result, error or panic into an envelope
+fn respond<T: Serialize>(call: impl FnOnce() -> Result<T, FfiError>) -> *mut c_char {
+    let envelope = match catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(result)) => match serde_json::to_value(result) {
+            Ok(result) => json!({ "result": result }),
+            Err(e) => FfiError::Internal(format!("Failed to serialize result: {}", e)).to_json(),
+        },
+        Ok(Err(e)) => e.to_json(),
+        Err(_) => FfiError::Internal("panic during calculation".to_string()).to_json(),
+    };
+    into_c_string(&envelope)
+}
+
+unsafe fn read_str<'a>(value: *const c_char) -> Result<&'a str, FfiError> {
+    if value.is_null() {
+        return Err(FfiError::InvalidRequest("request is NULL".to_string()));
+    }
+    CStr::from_ptr(value)
+        .to_str()
+        .map_err(|e| FfiError::InvalidRequest(format!("request is not valid UTF-8: {}", e)))
+}
+
+unsafe fn parse_request<T: DeserializeOwned>(request: *const c_char) -> Result<T, FfiError> {
+    serde_json::from_str(read_str(request)?).map_err(|e| FfiError::InvalidRequest(format!("Invalid request: {}", e)))
+}
+
+fn into_c_string(value: &Value) -> *mut c_char {
+    // serde_json escapes NUL, so serialized JSON never contains an interior NUL byte
+    CString::new(value.to_string())
+        .map(CString::into_raw)
+        .unwrap_or(ptr::null_mut())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(f: unsafe extern "C" fn(*const c_char) -> *mut c_char, request: &str) -> Value {
+        let request = CString::new(request).unwrap();
+        unsafe {
+            let response = f(request.as_ptr());
+            let value = serde_json::from_str(CStr::from_ptr(response).to_str().unwrap()).unwrap();
+            aphrodite_string_free(response);
+            value
+        }
+    }
+
+    fn layers() -> Value {
+        json!({ "natal": { "planets": {
+            "sun": { "lon": 10.0, "lat": 0.0, "speed_lon": 1.0, "retrograde": false },
+            "moon": { "lon": 130.5, "lat": 0.0, "speed_lon": 13.0, "retrograde": false }
+        }, "houses": null } })
+    }
+
+    #[test]
+    fn test_aspects_envelope() {
+        let response = call(aphrodite_aspects, &json!({ "layers": layers() }).to_string());
+        assert_eq!(response["result"]["natal"]["pairs"][0]["aspect"]["aspect_type"], "trine");
+
+        let response = call(aphrodite_aspects, &json!({ "layers": layers(), "orbs": { "trine": 0.25 } }).to_string());
+        assert_eq!(response["result"]["natal"]["pairs"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_format_longitude() {
+        let response = call(aphrodite_format_longitude, r#"{"longitude": 45.54}"#);
+        assert_eq!(response["result"], "15°32′ Taurus");
+        let response = call(aphrodite_format_longitude, r#"{"longitude": 45.54, "format": "nakshatraPada"}"#);
+        assert_eq!(response["result"], "Rohini pada 2");
+        let response = call(aphrodite_format_longitude, r#"{"longitude": 45.54, "format": "dms"}"#);
+        assert_eq!(response["error"]["code"], "INVALID_REQUEST");
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        let response = call(aphrodite_aspects, "{not json");
+        assert_eq!(response["error"]["code"], "INVALID_REQUEST");
+
+        let response = call(aphrodite_aspects, &json!({ "layers": {}, "layerz": {} }).to_string());
+        assert!(response["error"]["message"].as_str().unwrap().contains("layerz"));
+
+        let response = unsafe { aphrodite_aspects(ptr::null()) };
+        let value: Value = unsafe { serde_json::from_str(CStr::from_ptr(response).to_str().unwrap()).unwrap() };
+        unsafe { aphrodite_string_free(response) };
+        assert_eq!(value["error"]["message"], "request is NULL");
+    }
+
+    #[test]
+    fn test_dashas_and_chartspec() {
+        let request = json!({
+            "positions": layers()["natal"],
+            "birth_datetime": "1990-01-01T12:00:00Z",
+            "depth": "mahadasha"
+        });
+        let response = call(aphrodite_dashas, &request.to_string());
+        assert_eq!(response["result"]["system"], "vimshottari");
+        assert_eq!(response["result"]["periods"].as_array().unwrap().len(), 9);
+
+        let mut request = request;
+        request["system"] = json!("solar");
+        let response = call(aphrodite_dashas, &request.to_string());
+        assert_eq!(response["error"]["code"], "INVALID_REQUEST");
+        assert_eq!(response["error"]["reason"], "INVALID_INPUT");
+
+        let response = call(aphrodite_chartspec, &json!({ "layers": layers(), "width": 400.0, "height": 400.0 }).to_string());
+        assert_eq!(response["result"]["width"], 400.0);
+        assert!(!response["result"]["shapes"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_engine_lifecycle() {
+        let path = CString::new("/nonexistent/ephemeris").unwrap();
+        let mut error = ptr::null_mut();
+        let engine = unsafe { aphrodite_engine_new(path.as_ptr(), &mut error) };
+        assert!(engine.is_null());
+        let value: Value = unsafe { serde_json::from_str(CStr::from_ptr(error).to_str().unwrap()).unwrap() };
+        assert_eq!(value["error"]["code"], "EPHEMERIS_ERROR");
+        assert_eq!(value["error"]["reason"], "EPHEMERIS_FILE_NOT_FOUND");
+        unsafe {
+            aphrodite_string_free(error);
+            aphrodite_engine_free(ptr::null_mut());
+        }
+
+        let version = unsafe { CStr::from_ptr(aphrodite_version()) };
+        assert_eq!(version.to_str().unwrap(), env!("CARGO_PKG_VERSION"));
+    }
+}
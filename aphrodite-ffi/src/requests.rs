@@ -0,0 +1,140 @@
+//! JSON request bodies accepted by the exported functions.
+//!
+//! Field names match the serialized core types (snake_case), so positions and aspect sets
+//! returned by one call can be passed straight into another.
+
+use aphrodite_core::aspects::AspectSet;
+use aphrodite_core::ephemeris::{
+    EphemerisSettings, GeoLocation, LayerPositions, LilithType, MoonPosition, NodeType, PolarFallback, DEFAULT_OBJECTS,
+};
+use aphrodite_core::format::DegreeFormat;
+use aphrodite_core::vedic::DashaLevel;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// `aphrodite_calc_positions` request
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PositionsRequest {
+    pub datetime: DateTime<Utc>,
+    /// Houses are only calculated when a location is given
+    #[serde(default)]
+    pub location: Option<GeoLocation>,
+    #[serde(default = "default_zodiac_type")]
+    pub zodiac_type: String,
+    #[serde(default)]
+    pub ayanamsa: Option<String>,
+    #[serde(default)]
+    pub custom_ayanamsa: Option<f64>,
+    #[serde(default = "default_house_system")]
+    pub house_system: String,
+    #[serde(default = "default_objects")]
+    pub include_objects: Vec<String>,
+    #[serde(default)]
+    pub node_type: NodeType,
+    #[serde(default)]
+    pub lilith_type: LilithType,
+    /// Topocentric only takes effect with a location
+    #[serde(default)]
+    pub moon_position: MoonPosition,
+    #[serde(default)]
+    pub polar_fallback: PolarFallback,
+}
+
+impl PositionsRequest {
+    pub fn settings(&self) -> EphemerisSettings {
+        EphemerisSettings {
+            zodiac_type: self.zodiac_type.clone(),
+            ayanamsa: self.ayanamsa.clone(),
+            custom_ayanamsa: self.custom_ayanamsa,
+            house_system: self.house_system.clone(),
+            include_objects: self.include_objects.clone(),
+            node_type: self.node_type,
+            lilith_type: self.lilith_type,
+            moon_position: self.moon_position,
+            polar_fallback: self.polar_fallback,
+        }
+    }
+}
+
+/// `aphrodite_aspects` request
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AspectsRequest {
+    /// Positions keyed by layer id
+    pub layers: HashMap<String, LayerPositions>,
+    /// Orb overrides in degrees by aspect type
+    #[serde(default)]
+    pub orbs: HashMap<String, f64>,
+    /// Objects to consider; empty means every object in the layers
+    #[serde(default)]
+    pub include_objects: Vec<String>,
+}
+
+/// `aphrodite_dashas` request
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DashasRequest {
+    /// Sidereal positions of the natal layer
+    pub positions: LayerPositions,
+    pub birth_datetime: DateTime<Utc>,
+    #[serde(default = "default_dasha_system")]
+    pub system: String,
+    #[serde(default = "default_dasha_depth")]
+    pub depth: DashaLevel,
+}
+
+/// `aphrodite_chartspec` request
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChartSpecRequest {
+    /// Positions keyed by layer id
+    pub layers: HashMap<String, LayerPositions>,
+    /// Aspect sets to draw; calculated with the default orbs when omitted
+    #[serde(default)]
+    pub aspects: Option<HashMap<String, AspectSet>>,
+    /// Wheel definition JSON; the standard natal wheel when omitted
+    #[serde(default)]
+    pub wheel_json: Option<String>,
+    #[serde(default = "default_size")]
+    pub width: f32,
+    #[serde(default = "default_size")]
+    pub height: f32,
+    #[serde(default)]
+    pub include_objects: Vec<String>,
+}
+
+/// `aphrodite_format_longitude` request
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FormatLongitudeRequest {
+    pub longitude: f64,
+    /// "zodiacal" (default), "absolute" or "nakshatraPada"
+    #[serde(default)]
+    pub format: DegreeFormat,
+}
+
+fn default_zodiac_type() -> String {
+    "tropical".to_string()
+}
+
+fn default_house_system() -> String {
+    "placidus".to_string()
+}
+
+fn default_objects() -> Vec<String> {
+    DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect()
+}
+
+fn default_dasha_system() -> String {
+    "vimshottari".to_string()
+}
+
+fn default_dasha_depth() -> DashaLevel {
+    DashaLevel::Antardasha
+}
+
+fn default_size() -> f32 {
+    800.0
+}
@@ -0,0 +1,30 @@
+//! Keeps the committed C header in sync with the exported functions.
+//!
+//! Regenerate after changing the ABI with:
+//! `UPDATE_FFI_HEADER=1 cargo test -p aphrodite-ffi --test header`
+
+use std::path::Path;
+
+#[test]
+fn test_header_is_up_to_date() {
+    let crate_dir = env!("CARGO_MANIFEST_DIR");
+    let config = cbindgen::Config::from_file(Path::new(crate_dir).join("cbindgen.toml")).unwrap();
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(Path::new(crate_dir).join("src/lib.rs"))
+        .generate()
+        .unwrap()
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    let header_path = Path::new(crate_dir).join("include/aphrodite.h");
+    if std::env::var_os("UPDATE_FFI_HEADER").is_some() {
+        std::fs::write(&header_path, &generated).unwrap();
+    }
+    let committed = std::fs::read_to_string(&header_path).unwrap_or_default();
+    assert!(
+        committed == generated,
+        "include/aphrodite.h is out of date; rerun with UPDATE_FFI_HEADER=1"
+    );
+}
@@ -0,0 +1,252 @@
+//! Kotlin and Swift bindings for the chart engine, generated with uniffi.
+//!
+//! Apps bundle the Swiss Ephemeris data files, copy them to app storage and pass that
+//! directory to `ChartEngine`, so charts are calculated fully offline. Generate the
+//! platform sources with the `uniffi-bindgen` binary (see README).
+
+use aphrodite_core::aspects::{AspectCalculator, AspectSettings, DEFAULT_ORBS};
+use aphrodite_core::ephemeris::{
+    self, lock_swiss_state, EphemerisSettings, LilithType, MoonPosition, NodeType, PolarFallback, SwissEphemerisAdapter, DEFAULT_OBJECTS,
+};
+use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler, DEFAULT_WHEEL_JSON};
+use aphrodite_core::rendering::ChartSpecGenerator;
+use aphrodite_core::vedic::{compute_dasha, DASHA_SYSTEMS};
+use aphrodite_core::ErrorCategory;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+mod types;
+
+pub use types::{
+    AspectCore, AspectObjectRef, AspectPair, AspectSet, DashaLevel, DashaPeriod, DegreeFormat, GeoLocation, HousePositions,
+    LayerPositions, PlanetPosition, PositionsRequest,
+};
+
+uniffi::setup_scaffolding!("aphrodite");
+
+/// Errors thrown to Kotlin and Swift; `code` is the engine's `aphrodite_core::Error::code`
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+pub enum AphroditeError {
+    #[error("Invalid input: {message}")]
+    InvalidInput { code: String, message: String },
+    #[error("Ephemeris error: {message}")]
+    Ephemeris { code: String, message: String },
+    #[error("Calculation error: {message}")]
+    Calculation { code: String, message: String },
+}
+
+impl From<aphrodite_core::Error> for AphroditeError {
+    fn from(error: aphrodite_core::Error) -> Self {
+        let code = error.code().to_string();
+        let message = error.to_string();
+        match error.category() {
+            ErrorCategory::InvalidInput => AphroditeError::InvalidInput { code, message },
+            ErrorCategory::EphemerisData => AphroditeError::Ephemeris { code, message },
+            ErrorCategory::Calculation => AphroditeError::Calculation { code, message },
+        }
+    }
+}
+
+impl From<ephemeris::EphemerisError> for AphroditeError {
+    fn from(error: ephemeris::EphemerisError) -> Self {
+        aphrodite_core::Error::from(error).into()
+    }
+}
+
+/// Ephemeris-backed calculator; create one per app and share it
+#[derive(uniffi::Object)]
+pub struct ChartEngine {
+    adapter: Mutex<SwissEphemerisAdapter>,
+}
+
+#[uniffi::export]
+impl ChartEngine {
+    /// Read ephemeris files from `ephemeris_path`
+    #[uniffi::constructor]
+    pub fn new(ephemeris_path: String) -> Result<Arc<Self>, AphroditeError> {
+        let adapter = SwissEphemerisAdapter::new(Some(PathBuf::from(ephemeris_path)))?;
+        Ok(Arc::new(Self { adapter: Mutex::new(adapter) }))
+    }
+
+    /// Planet and house positions; houses are only calculated when a location is given
+    pub fn calc_positions(&self, request: PositionsRequest) -> Result<LayerPositions, AphroditeError> {
+        let include_objects = if request.include_objects.is_empty() {
+            DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect()
+        } else {
+            request.include_objects
+        };
+        let settings = EphemerisSettings {
+            zodiac_type: request.zodiac_type,
+            ayanamsa: request.ayanamsa,
+            custom_ayanamsa: request.custom_ayanamsa,
+            house_system: request.house_system,
+            include_objects,
+            node_type: NodeType::default(),
+            lilith_type: LilithType::default(),
+            moon_position: MoonPosition::default(),
+            polar_fallback: PolarFallback::default(),
+        };
+        let location = request.location.map(|l| ephemeris::GeoLocation { lat: l.lat, lon: l.lon });
+
+        // iOS apps share one Swiss Ephemeris state between threads; Android keeps one per thread
+        let _swiss_state = lock_swiss_state();
+        let mut adapter = self.adapter.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        adapter
+            .calc_positions(request.datetime.into(), location, &settings)
+            .map(Into::into)
+            .map_err(Into::into)
+    }
+}
+
+/// Intra- and inter-layer aspect sets for positions keyed by layer id.
+///
+/// `orbs` overrides the default orb per aspect type; an empty `include_objects` uses every
+/// object in the layers.
+#[uniffi::export]
+pub fn compute_aspects(
+    layers: HashMap<String, LayerPositions>,
+    orbs: HashMap<String, f64>,
+    include_objects: Vec<String>,
+) -> Vec<AspectSet> {
+    let layers = core_layers(layers);
+    let mut sets: Vec<AspectSet> = aspect_sets(&layers, orbs, include_objects)
+        .into_values()
+        .map(Into::into)
+        .collect();
+    sets.sort_by(|a, b| a.id.cmp(&b.id));
+    sets
+}
+
+/// Dasha periods for a system ("vimshottari", "yogini", "ashtottari", "kalachakra", "chara")
+/// from a layer's sidereal positions
+#[uniffi::export]
+pub fn compute_dashas(
+    positions: LayerPositions,
+    birth_datetime: SystemTime,
+    system: String,
+    depth: DashaLevel,
+) -> Result<Vec<DashaPeriod>, AphroditeError> {
+    if !DASHA_SYSTEMS.contains(&system.as_str()) {
+        return Err(AphroditeError::InvalidInput {
+            code: "INVALID_INPUT".to_string(),
+            message: format!("Unknown dasha system '{}' (available: {})", system, DASHA_SYSTEMS.join(", ")),
+        });
+    }
+    compute_dasha(&system, birth_datetime.into(), &positions.into(), depth.into())
+        .map(|periods| periods.into_iter().map(Into::into).collect())
+        .map_err(Into::into)
+}
+
+/// ChartSpec JSON for positions keyed by layer id, drawn with `wheel_json` or the
+/// standard natal wheel; aspects use the default orbs
+#[uniffi::export]
+pub fn chartspec_json(
+    layers: HashMap<String, LayerPositions>,
+    wheel_json: Option<String>,
+    width: f32,
+    height: f32,
+) -> Result<String, AphroditeError> {
+    let layers = core_layers(layers);
+    let aspects = aspect_sets(&layers, HashMap::new(), Vec::new());
+    let wheel = load_wheel_definition_from_json(wheel_json.as_deref().unwrap_or(DEFAULT_WHEEL_JSON))
+        .map_err(aphrodite_core::Error::from)?;
+    let assembled = WheelAssembler::build_wheel(&wheel.wheel, &layers, &aspects, None);
+    let spec = ChartSpecGenerator::new().generate(&assembled, &aspects, width, height);
+    serde_json::to_string(&spec).map_err(|e| AphroditeError::Calculation {
+        code: "CALCULATION_FAILED".to_string(),
+        message: e.to_string(),
+    })
+}
+
+/// A longitude written the way chart labels write it, e.g. "15°32′ Taurus"
+#[uniffi::export]
+pub fn format_longitude(lon: f64, format: DegreeFormat) -> String {
+    aphrodite_core::format::format_longitude(lon, format.into())
+}
+
+/// Library version
+#[uniffi::export]
+pub fn version() -> String {
+    env!("CARGO_PKG_VERSION").to_string()
+}
+
+fn core_layers(layers: HashMap<String, LayerPositions>) -> HashMap<String, ephemeris::LayerPositions> {
+    layers.into_iter().map(|(id, positions)| (id, positions.into())).collect()
+}
+
+fn aspect_sets(
+    layers: &HashMap<String, ephemeris::LayerPositions>,
+    orbs: HashMap<String, f64>,
+    include_objects: Vec<String>,
+) -> HashMap<String, aphrodite_core::aspects::AspectSet> {
+    let mut orb_settings: HashMap<String, f64> =
+        DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect();
+    orb_settings.extend(orbs);
+    let settings = AspectSettings {
+        orb_settings,
+        include_objects,
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    };
+    AspectCalculator::new().compute_all_aspect_sets(layers, &settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn natal() -> LayerPositions {
+        let planet = |lon: f64, speed_lon: f64| PlanetPosition { lon, lat: 0.0, speed_lon, retrograde: false };
+        LayerPositions {
+            planets: HashMap::from([
+                ("sun".to_string(), planet(10.0, 1.0)),
+                ("moon".to_string(), planet(130.5, 13.0)),
+            ]),
+            houses: None,
+        }
+    }
+
+    #[test]
+    fn test_compute_aspects() {
+        let layers = HashMap::from([("natal".to_string(), natal())]);
+        let sets = compute_aspects(layers.clone(), HashMap::new(), Vec::new());
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].pairs[0].aspect.aspect_type, "trine");
+
+        let sets = compute_aspects(layers, HashMap::from([("trine".to_string(), 0.25)]), Vec::new());
+        assert!(sets[0].pairs.is_empty());
+    }
+
+    #[test]
+    fn test_compute_dashas() {
+        let birth = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(631_195_200);
+        let periods = compute_dashas(natal(), birth, "vimshottari".to_string(), DashaLevel::Antardasha).unwrap();
+        assert_eq!(periods.len(), 9);
+        assert_eq!(periods[0].level, DashaLevel::Mahadasha);
+        assert_eq!(periods[0].children[0].level, DashaLevel::Antardasha);
+        assert!(periods[0].end > periods[0].start);
+
+        let error = compute_dashas(natal(), birth, "solar".to_string(), DashaLevel::Mahadasha).unwrap_err();
+        assert!(matches!(error, AphroditeError::InvalidInput { ref code, .. } if code == "INVALID_INPUT"));
+    }
+
+    #[test]
+    fn test_chartspec_json() {
+        let layers = HashMap::from([("natal".to_string(), natal())]);
+        let spec: serde_json::Value = serde_json::from_str(&chartspec_json(layers.clone(), None, 400.0, 400.0).unwrap()).unwrap();
+        assert_eq!(spec["width"], 400.0);
+        assert!(!spec["shapes"].as_array().unwrap().is_empty());
+
+        assert!(chartspec_json(layers, Some("[]".to_string()), 400.0, 400.0).is_err());
+    }
+
+    #[test]
+    fn test_engine_requires_ephemeris_path() {
+        let error = ChartEngine::new("/nonexistent/ephemeris".to_string()).err().unwrap();
+        assert!(matches!(error, AphroditeError::Ephemeris { ref code, .. } if code == "EPHEMERIS_FILE_NOT_FOUND"));
+    }
+}
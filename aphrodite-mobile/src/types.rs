@@ -0,0 +1,254 @@
+//! Records exported to Kotlin and Swift, mirroring the core types they convert from.
+
+use aphrodite_core::{aspects, ephemeris, format, vedic};
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct GeoLocation {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PlanetPosition {
+    /// Longitude in degrees (0-360)
+    pub lon: f64,
+    pub lat: f64,
+    /// Speed in longitude (degrees per day)
+    pub speed_lon: f64,
+    pub retrograde: bool,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct HousePositions {
+    pub system: String,
+    /// "1".."12" -> degrees
+    pub cusps: HashMap<String, f64>,
+    /// "asc", "mc", "ic", "dc" -> degrees
+    pub angles: HashMap<String, f64>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct LayerPositions {
+    pub planets: HashMap<String, PlanetPosition>,
+    /// Absent when no location was given
+    pub houses: Option<HousePositions>,
+}
+
+/// Inputs for `ChartEngine::calc_positions`
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct PositionsRequest {
+    pub datetime: SystemTime,
+    #[uniffi(default = None)]
+    pub location: Option<GeoLocation>,
+    #[uniffi(default = "tropical")]
+    pub zodiac_type: String,
+    #[uniffi(default = None)]
+    pub ayanamsa: Option<String>,
+    #[uniffi(default = None)]
+    pub custom_ayanamsa: Option<f64>,
+    #[uniffi(default = "placidus")]
+    pub house_system: String,
+    /// Empty selects the default objects (Sun through Pluto and the north node)
+    #[uniffi(default = [])]
+    pub include_objects: Vec<String>,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AspectObjectRef {
+    pub layer_id: String,
+    /// "planet", "house" or "angle"
+    pub object_type: String,
+    pub object_id: String,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AspectCore {
+    pub aspect_type: String,
+    pub exact_angle: f64,
+    pub orb: f64,
+    pub is_applying: bool,
+    pub is_exact: bool,
+    pub is_retrograde: bool,
+    pub out_of_sign: bool,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AspectPair {
+    pub from: AspectObjectRef,
+    pub to: AspectObjectRef,
+    pub aspect: AspectCore,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct AspectSet {
+    pub id: String,
+    pub label: String,
+    /// "intra_layer" or "inter_layer"
+    pub kind: String,
+    pub layer_ids: Vec<String>,
+    pub pairs: Vec<AspectPair>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum DashaLevel {
+    Mahadasha,
+    Antardasha,
+    Pratyantardasha,
+}
+
+/// How `format_longitude` writes a longitude
+#[derive(Debug, Clone, Copy, PartialEq, Eq, uniffi::Enum)]
+pub enum DegreeFormat {
+    /// "15°32′ Taurus"
+    Zodiacal,
+    /// "45°32′"
+    Absolute,
+    /// "Rohini pada 2"
+    NakshatraPada,
+}
+
+#[derive(Debug, Clone, uniffi::Record)]
+pub struct DashaPeriod {
+    pub planet: String,
+    /// Ruling sign for sign-based systems (chara)
+    pub sign: Option<String>,
+    pub start: SystemTime,
+    pub end: SystemTime,
+    pub duration_days: f64,
+    pub level: DashaLevel,
+    pub children: Vec<DashaPeriod>,
+}
+
+impl From<ephemeris::LayerPositions> for LayerPositions {
+    fn from(positions: ephemeris::LayerPositions) -> Self {
+        Self {
+            planets: positions
+                .planets
+                .into_iter()
+                .map(|(id, p)| {
+                    let position = PlanetPosition {
+                        lon: p.lon,
+                        lat: p.lat,
+                        speed_lon: p.speed_lon,
+                        retrograde: p.retrograde,
+                    };
+                    (id, position)
+                })
+                .collect(),
+            houses: positions.houses.map(|h| HousePositions {
+                system: h.system,
+                cusps: h.cusps,
+                angles: h.angles,
+            }),
+        }
+    }
+}
+
+impl From<LayerPositions> for ephemeris::LayerPositions {
+    fn from(positions: LayerPositions) -> Self {
+        Self {
+            planets: positions
+                .planets
+                .into_iter()
+                .map(|(id, p)| {
+                    let position = ephemeris::PlanetPosition {
+                        lon: p.lon,
+                        lat: p.lat,
+                        speed_lon: p.speed_lon,
+                        retrograde: p.retrograde,
+                    };
+                    (id, position)
+                })
+                .collect(),
+            houses: positions.houses.map(|h| ephemeris::HousePositions {
+                system: h.system,
+                cusps: h.cusps,
+                angles: h.angles,
+            }),
+        }
+    }
+}
+
+impl From<aspects::AspectObjectRef> for AspectObjectRef {
+    fn from(object: aspects::AspectObjectRef) -> Self {
+        Self {
+            layer_id: object.layer_id,
+            object_type: object.object_type,
+            object_id: object.object_id,
+        }
+    }
+}
+
+impl From<aspects::AspectSet> for AspectSet {
+    fn from(set: aspects::AspectSet) -> Self {
+        Self {
+            id: set.id,
+            label: set.label,
+            kind: set.kind,
+            layer_ids: set.layer_ids,
+            pairs: set
+                .pairs
+                .into_iter()
+                .map(|pair| AspectPair {
+                    from: pair.from.into(),
+                    to: pair.to.into(),
+                    aspect: AspectCore {
+                        aspect_type: pair.aspect.aspect_type,
+                        exact_angle: pair.aspect.exact_angle,
+                        orb: pair.aspect.orb,
+                        is_applying: pair.aspect.is_applying,
+                        is_exact: pair.aspect.is_exact,
+                        is_retrograde: pair.aspect.is_retrograde,
+                        out_of_sign: pair.aspect.out_of_sign,
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<DashaLevel> for vedic::DashaLevel {
+    fn from(level: DashaLevel) -> Self {
+        match level {
+            DashaLevel::Mahadasha => Self::Mahadasha,
+            DashaLevel::Antardasha => Self::Antardasha,
+            DashaLevel::Pratyantardasha => Self::Pratyantardasha,
+        }
+    }
+}
+
+impl From<vedic::DashaLevel> for DashaLevel {
+    fn from(level: vedic::DashaLevel) -> Self {
+        match level {
+            vedic::DashaLevel::Mahadasha => Self::Mahadasha,
+            vedic::DashaLevel::Antardasha => Self::Antardasha,
+            vedic::DashaLevel::Pratyantardasha => Self::Pratyantardasha,
+        }
+    }
+}
+
+impl From<DegreeFormat> for format::DegreeFormat {
+    fn from(value: DegreeFormat) -> Self {
+        match value {
+            DegreeFormat::Zodiacal => Self::Zodiacal,
+            DegreeFormat::Absolute => Self::Absolute,
+            DegreeFormat::NakshatraPada => Self::NakshatraPada,
+        }
+    }
+}
+
+impl From<vedic::DashaPeriod> for DashaPeriod {
+    fn from(period: vedic::DashaPeriod) -> Self {
+        Self {
+            planet: period.planet,
+            sign: period.sign,
+            start: period.start.into(),
+            end: period.end.into(),
+            duration_days: period.duration_days,
+            level: period.level.into(),
+            children: period.children.into_iter().map(Into::into).collect(),
+        }
+    }
+}
@@ -0,0 +1,43 @@
+//! Conversion between core types and plain Python objects.
+//!
+//! Values go through their serde JSON form, so Python sees the same field names as the
+//! HTTP API and anything returned by one function can be passed back into another.
+
+use chrono::{DateTime, FixedOffset, Utc};
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyString;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// Convert a serializable value into dicts, lists and scalars
+pub fn to_py<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value)
+        .map_err(|e| PyValueError::new_err(format!("Failed to serialize result: {}", e)))?;
+    let loads = py.import("json")?.getattr("loads")?;
+    Ok(loads.call1((json,))?.unbind())
+}
+
+/// Convert dicts, lists and scalars into a core type; `what` names the argument in errors
+pub fn from_py<T: DeserializeOwned>(value: &Bound<'_, PyAny>, what: &str) -> PyResult<T> {
+    let dumps = value.py().import("json")?.getattr("dumps")?;
+    let json: String = dumps
+        .call1((value,))
+        .map_err(|e| PyTypeError::new_err(format!("Invalid {}: {}", what, e)))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(|e| PyValueError::new_err(format!("Invalid {}: {}", what, e)))
+}
+
+/// Accept an RFC 3339 string or a timezone-aware `datetime.datetime`
+pub fn extract_datetime(value: &Bound<'_, PyAny>) -> PyResult<DateTime<Utc>> {
+    if let Ok(text) = value.downcast::<PyString>() {
+        let text = text.to_cow()?;
+        return DateTime::parse_from_rfc3339(&text)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|e| PyValueError::new_err(format!("Invalid datetime '{}': {}", text, e)));
+    }
+    value
+        .extract::<DateTime<FixedOffset>>()
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| PyTypeError::new_err("datetime must be an RFC 3339 string or a timezone-aware datetime"))
+}
@@ -0,0 +1,311 @@
+//! Python bindings for the Aphrodite chart engine.
+//!
+//! Exposes position calculation, aspects, Vedic dashas and ChartSpec generation without
+//! running the HTTP API. Results are plain dicts and lists (see `convert`). Build the
+//! extension with `maturin develop` from this directory.
+
+use aphrodite_core::aspects::{AspectCalculator, AspectSet, AspectSettings, DEFAULT_ORBS};
+use aphrodite_core::ephemeris::{
+    EphemerisSettings, GeoLocation, LayerPositions, LilithType, MoonPosition, NodeType, PolarFallback, SwissEphemerisAdapter,
+    DEFAULT_OBJECTS,
+};
+use aphrodite_core::format::{self, DegreeFormat};
+use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler, DEFAULT_WHEEL_JSON};
+use aphrodite_core::rendering::ChartSpecGenerator;
+use aphrodite_core::vedic::{compute_dasha, DashaLevel, VimshottariResponse, DASHA_SYSTEMS};
+use aphrodite_core::ErrorCategory;
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyValueError};
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+mod convert;
+
+use convert::{extract_datetime, from_py, to_py};
+
+create_exception!(aphrodite, AphroditeError, PyException, "Chart calculation failed");
+
+/// ValueError for bad input, AphroditeError for everything else; either carries the
+/// engine's error code (`aphrodite_core::Error::code`) as `code`
+fn core_error(error: impl Into<aphrodite_core::Error>) -> PyErr {
+    let error = error.into();
+    let err = match error.category() {
+        ErrorCategory::InvalidInput => PyValueError::new_err(error.to_string()),
+        ErrorCategory::EphemerisData | ErrorCategory::Calculation => AphroditeError::new_err(error.to_string()),
+    };
+    Python::with_gil(|py| {
+        // Only fails when Python is out of memory, and the exception is still worth raising
+        let _ = err.value(py).setattr("code", error.code());
+    });
+    err
+}
+
+/// Swiss Ephemeris handle; create one and reuse it for every calculation
+#[pyclass(module = "aphrodite")]
+pub struct Ephemeris {
+    adapter: SwissEphemerisAdapter,
+}
+
+#[pymethods]
+impl Ephemeris {
+    /// Read ephemeris files from `path`, or `SWISS_EPHEMERIS_PATH`, or /usr/local/share/swisseph
+    #[new]
+    #[pyo3(signature = (path=None))]
+    fn new(path: Option<PathBuf>) -> PyResult<Self> {
+        let adapter = SwissEphemerisAdapter::new(path).map_err(core_error)?;
+        Ok(Self { adapter })
+    }
+
+    /// Directory the ephemeris files are read from
+    #[getter]
+    fn path(&self) -> PathBuf {
+        self.adapter.ephemeris_path().to_path_buf()
+    }
+
+    /// Planet and house positions at a moment.
+    ///
+    /// Houses are only calculated when both `lat` and `lon` are given.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (
+        datetime, lat=None, lon=None, zodiac_type="tropical", ayanamsa=None, house_system="placidus",
+        include_objects=None, custom_ayanamsa=None
+    ))]
+    fn calc_positions(
+        &mut self,
+        py: Python<'_>,
+        datetime: &Bound<'_, PyAny>,
+        lat: Option<f64>,
+        lon: Option<f64>,
+        zodiac_type: &str,
+        ayanamsa: Option<String>,
+        house_system: &str,
+        include_objects: Option<Vec<String>>,
+        custom_ayanamsa: Option<f64>,
+    ) -> PyResult<PyObject> {
+        let datetime = extract_datetime(datetime)?;
+        let location = match (lat, lon) {
+            (Some(lat), Some(lon)) => Some(GeoLocation { lat, lon }),
+            (None, None) => None,
+            _ => return Err(PyValueError::new_err("lat and lon must be given together")),
+        };
+        let settings = EphemerisSettings {
+            zodiac_type: zodiac_type.to_string(),
+            ayanamsa,
+            custom_ayanamsa,
+            house_system: house_system.to_string(),
+            include_objects: include_objects
+                .unwrap_or_else(|| DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect()),
+            node_type: NodeType::default(),
+            lilith_type: LilithType::default(),
+            moon_position: MoonPosition::default(),
+            polar_fallback: PolarFallback::default(),
+        };
+        let positions = self
+            .adapter
+            .calc_positions(datetime, location, &settings)
+            .map_err(core_error)?;
+        to_py(py, &positions)
+    }
+}
+
+/// Intra- and inter-layer aspects for positions keyed by layer id, keyed by aspect set id
+#[pyfunction]
+#[pyo3(signature = (layers, orbs=None, include_objects=None))]
+fn aspects(
+    py: Python<'_>,
+    layers: &Bound<'_, PyAny>,
+    orbs: Option<HashMap<String, f64>>,
+    include_objects: Option<Vec<String>>,
+) -> PyResult<PyObject> {
+    let layers: HashMap<String, LayerPositions> = from_py(layers, "layers")?;
+    to_py(py, &aspect_sets(&layers, orbs, include_objects))
+}
+
+/// Dasha periods from a layer's sidereal positions (the Moon, or the ascendant for chara)
+#[pyfunction]
+#[pyo3(signature = (positions, birth_datetime, system="vimshottari", depth="antardasha"))]
+fn dashas(
+    py: Python<'_>,
+    positions: &Bound<'_, PyAny>,
+    birth_datetime: &Bound<'_, PyAny>,
+    system: &str,
+    depth: &str,
+) -> PyResult<PyObject> {
+    let positions: LayerPositions = from_py(positions, "positions")?;
+    let birth_datetime = extract_datetime(birth_datetime)?;
+    let depth: DashaLevel = serde_json::from_value(serde_json::Value::String(depth.to_string()))
+        .map_err(|_| PyValueError::new_err(format!("Unknown dasha depth '{}'", depth)))?;
+
+    if !DASHA_SYSTEMS.contains(&system) {
+        return Err(PyValueError::new_err(format!(
+            "Unknown dasha system '{}' (available: {})",
+            system,
+            DASHA_SYSTEMS.join(", ")
+        )));
+    }
+    let periods = compute_dasha(system, birth_datetime, &positions, depth).map_err(core_error)?;
+
+    to_py(
+        py,
+        &VimshottariResponse {
+            system: system.to_string(),
+            depth,
+            birth_date_time: birth_datetime,
+            node_type: None,
+            periods,
+        },
+    )
+}
+
+/// ChartSpec for positions keyed by layer id, drawn with `wheel_json` or the standard natal wheel
+#[pyfunction]
+#[pyo3(signature = (layers, aspects=None, wheel_json=None, width=800.0, height=800.0, include_objects=None))]
+fn chartspec(
+    py: Python<'_>,
+    layers: &Bound<'_, PyAny>,
+    aspects: Option<&Bound<'_, PyAny>>,
+    wheel_json: Option<&str>,
+    width: f32,
+    height: f32,
+    include_objects: Option<Vec<String>>,
+) -> PyResult<PyObject> {
+    let layers: HashMap<String, LayerPositions> = from_py(layers, "layers")?;
+    let aspects: HashMap<String, AspectSet> = match aspects {
+        Some(aspects) => from_py(aspects, "aspects")?,
+        None => aspect_sets(&layers, None, include_objects.clone()),
+    };
+    let wheel = load_wheel_definition_from_json(wheel_json.unwrap_or(DEFAULT_WHEEL_JSON))
+        .map_err(core_error)?;
+
+    let assembled = WheelAssembler::build_wheel(&wheel.wheel, &layers, &aspects, include_objects.as_deref());
+    let spec = ChartSpecGenerator::new().generate(&assembled, &aspects, width, height);
+    to_py(py, &spec)
+}
+
+/// An ecliptic longitude as "zodiacal" ("15°32′ Taurus"), "absolute" ("45°32′") or
+/// "nakshatraPada" ("Rohini pada 2"), as chart labels write it
+#[pyfunction]
+#[pyo3(signature = (lon, format="zodiacal"))]
+fn format_longitude(lon: f64, format: &str) -> PyResult<String> {
+    let format: DegreeFormat = format.parse().map_err(PyValueError::new_err)?;
+    Ok(format::format_longitude(lon, format))
+}
+
+fn aspect_sets(
+    layers: &HashMap<String, LayerPositions>,
+    orbs: Option<HashMap<String, f64>>,
+    include_objects: Option<Vec<String>>,
+) -> HashMap<String, AspectSet> {
+    let mut orb_settings: HashMap<String, f64> =
+        DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect();
+    orb_settings.extend(orbs.unwrap_or_default());
+    let settings = AspectSettings {
+        orb_settings,
+        include_objects: include_objects.unwrap_or_default(),
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    };
+    AspectCalculator::new().compute_all_aspect_sets(layers, &settings)
+}
+
+#[pymodule]
+fn aphrodite(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Ephemeris>()?;
+    m.add_function(wrap_pyfunction!(aspects, m)?)?;
+    m.add_function(wrap_pyfunction!(dashas, m)?)?;
+    m.add_function(wrap_pyfunction!(chartspec, m)?)?;
+    m.add_function(wrap_pyfunction!(format_longitude, m)?)?;
+    m.add("AphroditeError", m.py().get_type::<AphroditeError>())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pyo3::types::PyDict;
+
+    fn positions(py: Python<'_>) -> Bound<'_, PyAny> {
+        let code = c"{'natal': {'planets': {
+            'sun': {'lon': 10.0, 'lat': 0.0, 'speed_lon': 1.0, 'retrograde': False},
+            'moon': {'lon': 130.5, 'lat': 0.0, 'speed_lon': 13.0, 'retrograde': False}
+        }, 'houses': None}}";
+        py.eval(code, None, None).unwrap()
+    }
+
+    #[test]
+    fn test_aspects_from_dicts() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let result = aspects(py, &positions(py), None, None).unwrap();
+            let result = result.bind(py).downcast::<PyDict>().unwrap().clone();
+            let natal = result.get_item("natal").unwrap().unwrap();
+            let pairs = natal.get_item("pairs").unwrap();
+            assert_eq!(pairs.len().unwrap(), 1);
+            let aspect_type: String = pairs.get_item(0).unwrap().get_item("aspect").unwrap()
+                .get_item("aspect_type").unwrap().extract().unwrap();
+            assert_eq!(aspect_type, "trine");
+
+            // A tighter orb drops the aspect
+            let orbs = HashMap::from([("trine".to_string(), 0.25)]);
+            let result = aspects(py, &positions(py), Some(orbs), None).unwrap();
+            let pairs = result.bind(py).get_item("natal").unwrap().get_item("pairs").unwrap();
+            assert_eq!(pairs.len().unwrap(), 0);
+        });
+    }
+
+    #[test]
+    fn test_chartspec_uses_default_wheel() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let spec = chartspec(py, &positions(py), None, None, 400.0, 400.0, None).unwrap();
+            let spec = spec.bind(py);
+            let width: f32 = spec.get_item("width").unwrap().extract().unwrap();
+            assert_eq!(width, 400.0);
+            assert!(spec.get_item("shapes").unwrap().len().unwrap() > 0);
+        });
+    }
+
+    #[test]
+    fn test_dashas() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let natal = positions(py).get_item("natal").unwrap();
+            let birth = "1990-01-01T12:00:00Z".into_pyobject(py).unwrap().into_any();
+            let result = dashas(py, &natal, &birth, "vimshottari", "mahadasha").unwrap();
+            let result = result.bind(py);
+            let system: String = result.get_item("system").unwrap().extract().unwrap();
+            assert_eq!(system, "vimshottari");
+            assert!(result.get_item("periods").unwrap().len().unwrap() > 0);
+
+            let error = dashas(py, &natal, &birth, "unknown", "mahadasha").unwrap_err();
+            assert!(error.is_instance_of::<PyValueError>(py));
+
+            let no_moon = py.eval(c"{'planets': {}, 'houses': None}", None, None).unwrap();
+            let error = dashas(py, &no_moon, &birth, "vimshottari", "mahadasha").unwrap_err();
+            assert!(error.is_instance_of::<PyValueError>(py));
+            let code: String = error.value(py).getattr("code").unwrap().extract().unwrap();
+            assert_eq!(code, "INVALID_INPUT");
+            assert!(dashas(py, &natal, &birth, "vimshottari", "yearly").is_err());
+        });
+    }
+
+    #[test]
+    fn test_invalid_input_errors() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let layers = py.eval(c"{'natal': {'planets': 'sun'}}", None, None).unwrap();
+            let error = aspects(py, &layers, None, None).unwrap_err();
+            assert!(error.to_string().contains("Invalid layers"));
+
+            let naive = py.eval(c"__import__('datetime').datetime(1990, 1, 1)", None, None).unwrap();
+            assert!(extract_datetime(&naive).is_err());
+            let aware = py
+                .eval(c"__import__('datetime').datetime(1990, 1, 1, 12, tzinfo=__import__('datetime').timezone.utc)", None, None)
+                .unwrap();
+            assert_eq!(extract_datetime(&aware).unwrap().to_rfc3339(), "1990-01-01T12:00:00+00:00");
+        });
+    }
+}
@@ -0,0 +1,422 @@
+//! Reference desktop client: enter birth data, calculate the chart locally and click planets
+//! on the wheel for their positions. The live transit clock overlays the current sky on an
+//! outer ring and refreshes it every minute.
+//!
+//! Ephemeris files are read from `SWISS_EPHEMERIS_PATH`, or `/usr/local/share/swisseph`
+//! when it is unset. `--wheel <preset>` draws the chart with a wheel preset such as
+//! `vedic_rasi` or `minimal` instead of the standard natal wheel.
+
+use aphrodite_core::ephemeris::systems::HOUSE_SYSTEMS;
+use aphrodite_core::ephemeris::SwissEphemerisAdapter;
+use aphrodite_core::layout::{WheelPresets, DEFAULT_WHEEL_JSON};
+use aphrodite_core::rendering::ChartSpec;
+use aphrodite_slint::chart::{BirthData, NatalChart, TransitChart, TRANSIT_LAYER};
+use aphrodite_slint::export::{print_file, render_page_png, PageSize, PRINT_DPI};
+use aphrodite_slint::renderer::PlanetMarker;
+use aphrodite_slint::shapes::to_slint_color;
+use aphrodite_slint::SlintChartRenderer;
+use chrono::Utc;
+use slint::{Color, ModelRc, SharedString, VecModel};
+use std::cell::RefCell;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Width and height of the wheel in logical pixels
+const CHART_SIZE: f32 = 600.0;
+
+/// The calculated chart, the transits drawn around it and the planet markers, in the order
+/// of the `planets` model
+struct ShownChart {
+    natal: NatalChart,
+    transits: Option<TransitChart>,
+    markers: Vec<PlanetMarker>,
+}
+
+impl ShownChart {
+    fn spec(&self) -> &ChartSpec {
+        self.transits.as_ref().map_or(&self.natal.spec, |transits| &transits.spec)
+    }
+}
+
+slint::slint! {
+    import { Button, CheckBox, ComboBox, GroupBox, LineEdit } from "std-widgets.slint";
+
+    export struct ChartPath {
+        commands: string,
+        fill: color,
+        stroke: color,
+        stroke-width: length,
+    }
+
+    export struct ChartLabel {
+        x: length,
+        y: length,
+        text: string,
+        size: length,
+        color: color,
+        anchor-x: float,
+        anchor-y: float,
+        rotation: angle,
+    }
+
+    export struct ChartPlanet {
+        x: length,
+        y: length,
+        glyph: string,
+        size: length,
+        color: color,
+    }
+
+    // Ticks every minute while running; the owner recalculates the transits on each tick
+    component TransitClock inherits GroupBox {
+        title: "Live transits";
+
+        in-out property <bool> running;
+        in property <string> moment;
+
+        callback tick();
+
+        Timer {
+            interval: 60s;
+            running: root.running;
+            triggered => { root.tick(); }
+        }
+
+        VerticalLayout {
+            spacing: 6px;
+            CheckBox {
+                text: "Overlay the current sky";
+                checked <=> root.running;
+                toggled => { root.tick(); }
+            }
+            Text { text: root.moment; }
+        }
+    }
+
+    export component ChartViewer inherits Window {
+        title: "Aphrodite Chart Viewer";
+
+        in-out property <string> datetime: "1990-01-01 12:00";
+        in-out property <string> latitude: "40.7128";
+        in-out property <string> longitude: "-74.0060";
+        in property <[string]> house-systems;
+        in-out property <string> house-system: "placidus";
+        in property <length> chart-size;
+        in property <color> chart-background;
+        in property <[ChartPath]> paths;
+        in property <[ChartLabel]> labels;
+        in property <[ChartPlanet]> planets;
+        in property <string> details: "Click a planet for its position";
+        in property <string> status;
+        in property <[string]> page-sizes;
+        in-out property <string> page-size: "A4";
+        in-out property <string> export-path: "chart.png";
+        in-out property <bool> transits-running;
+        in property <string> transit-moment;
+
+        callback calculate();
+        callback planet-clicked(int);
+        callback export-png();
+        callback print-chart();
+        callback transit-tick();
+
+        HorizontalLayout {
+            padding: 12px;
+            spacing: 12px;
+
+            VerticalLayout {
+                width: 260px;
+                spacing: 8px;
+                alignment: start;
+
+                GroupBox {
+                    title: "Birth data";
+                    VerticalLayout {
+                        spacing: 6px;
+                        Text { text: "Date and time (UTC)"; }
+                        LineEdit { text <=> root.datetime; placeholder-text: "YYYY-MM-DD HH:MM"; }
+                        Text { text: "Latitude"; }
+                        LineEdit { text <=> root.latitude; input-type: decimal; }
+                        Text { text: "Longitude"; }
+                        LineEdit { text <=> root.longitude; input-type: decimal; }
+                        Text { text: "House system"; }
+                        ComboBox { model: root.house-systems; current-value <=> root.house-system; }
+                        Button {
+                            text: "Calculate";
+                            primary: true;
+                            clicked => { root.calculate(); }
+                        }
+                    }
+                }
+
+                Text { text: root.status; color: #c0392b; wrap: word-wrap; }
+
+                TransitClock {
+                    running <=> root.transits-running;
+                    moment: root.transit-moment;
+                    tick => { root.transit-tick(); }
+                }
+
+                GroupBox {
+                    title: "Planet";
+                    Text { text: root.details; wrap: word-wrap; }
+                }
+
+                GroupBox {
+                    title: "Print (300 DPI)";
+                    VerticalLayout {
+                        spacing: 6px;
+                        Text { text: "Page"; }
+                        ComboBox { model: root.page-sizes; current-value <=> root.page-size; }
+                        Text { text: "File"; }
+                        LineEdit { text <=> root.export-path; }
+                        HorizontalLayout {
+                            spacing: 6px;
+                            Button { text: "Export PNG"; clicked => { root.export-png(); } }
+                            Button { text: "Print"; clicked => { root.print-chart(); } }
+                        }
+                    }
+                }
+            }
+
+            Rectangle {
+                width: root.chart-size;
+                height: root.chart-size;
+                background: root.chart-background;
+                clip: true;
+
+                for path in root.paths: Path {
+                    x: 0;
+                    y: 0;
+                    width: root.chart-size;
+                    height: root.chart-size;
+                    viewbox-width: root.chart-size / 1px;
+                    viewbox-height: root.chart-size / 1px;
+                    commands: path.commands;
+                    fill: path.fill;
+                    stroke: path.stroke;
+                    stroke-width: path.stroke-width;
+                }
+
+                for label in root.labels: Text {
+                    x: label.x - self.width * label.anchor-x;
+                    y: label.y - self.height * label.anchor-y;
+                    text: label.text;
+                    font-size: label.size;
+                    color: label.color;
+                    transform-rotation: label.rotation;
+                }
+
+                for planet[index] in root.planets: Rectangle {
+                    x: planet.x - self.width / 2;
+                    y: planet.y - self.height / 2;
+                    width: planet.size + 4px;
+                    height: planet.size + 4px;
+                    border-radius: self.width / 2;
+                    background: touch.has-hover ? #ffffff30 : transparent;
+
+                    Text {
+                        width: parent.width;
+                        height: parent.height;
+                        horizontal-alignment: center;
+                        vertical-alignment: center;
+                        text: planet.glyph;
+                        font-size: planet.size;
+                        color: planet.color;
+                    }
+
+                    touch := TouchArea {
+                        mouse-cursor: pointer;
+                        clicked => { root.planet-clicked(index); }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Wheel JSON of the `--wheel` preset, or the standard natal wheel without one
+fn wheel_from_args() -> Result<&'static str, String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--wheel" {
+            let name = args.next().ok_or("--wheel needs a preset name")?;
+            return WheelPresets::get(&name)
+                .map(|preset| preset.json)
+                .ok_or_else(|| format!("Unknown wheel preset '{}'. Presets: {}", name, WheelPresets::names().join(", ")));
+        }
+    }
+    Ok(DEFAULT_WHEEL_JSON)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let wheel_json = wheel_from_args()?;
+    let viewer = ChartViewer::new()?;
+    let adapter = Rc::new(RefCell::new(SwissEphemerisAdapter::new(None)?));
+    let chart: Rc<RefCell<Option<ShownChart>>> = Rc::new(RefCell::new(None));
+
+    let house_systems: Vec<SharedString> = HOUSE_SYSTEMS.iter().map(|(name, _)| (*name).into()).collect();
+    viewer.set_house_systems(ModelRc::new(VecModel::from(house_systems)));
+    viewer.set_chart_size(CHART_SIZE);
+    let page_sizes: Vec<SharedString> = PageSize::ALL.iter().map(|page| page.name().into()).collect();
+    viewer.set_page_sizes(ModelRc::new(VecModel::from(page_sizes)));
+
+    viewer.on_calculate({
+        let weak = viewer.as_weak();
+        let chart = chart.clone();
+        let adapter = adapter.clone();
+        move || {
+            let Some(viewer) = weak.upgrade() else { return };
+            let result = BirthData::parse(&viewer.get_datetime(), &viewer.get_latitude(), &viewer.get_longitude(), &viewer.get_house_system())
+                .and_then(|birth| NatalChart::calculate_with_wheel(&mut adapter.borrow_mut(), &birth, wheel_json, CHART_SIZE));
+            match result {
+                Ok(natal) => {
+                    let markers = show_chart(&viewer, &natal.spec);
+                    viewer.set_status(SharedString::new());
+                    viewer.set_details("Click a planet for its position".into());
+                    *chart.borrow_mut() = Some(ShownChart { natal, transits: None, markers });
+                    if viewer.get_transits_running() {
+                        viewer.invoke_transit_tick();
+                    }
+                }
+                Err(message) => viewer.set_status(message.into()),
+            }
+        }
+    });
+
+    viewer.on_transit_tick({
+        let weak = viewer.as_weak();
+        let chart = chart.clone();
+        move || {
+            let Some(viewer) = weak.upgrade() else { return };
+            let mut chart = chart.borrow_mut();
+            let Some(shown) = chart.as_mut() else { return };
+
+            shown.transits = None;
+            if viewer.get_transits_running() {
+                match shown.natal.transits_at(&mut adapter.borrow_mut(), Utc::now(), CHART_SIZE) {
+                    Ok(transits) => {
+                        viewer.set_transit_moment(format!("{} UTC", transits.datetime.format("%Y-%m-%d %H:%M")).into());
+                        shown.transits = Some(transits);
+                    }
+                    Err(message) => {
+                        viewer.set_transits_running(false);
+                        viewer.set_status(message.into());
+                    }
+                }
+            }
+            if shown.transits.is_none() {
+                viewer.set_transit_moment(SharedString::new());
+            }
+            shown.markers = show_chart(&viewer, shown.spec());
+        }
+    });
+
+    viewer.on_export_png({
+        let weak = viewer.as_weak();
+        let chart = chart.clone();
+        move || {
+            let Some(viewer) = weak.upgrade() else { return };
+            let path = PathBuf::from(viewer.get_export_path().as_str());
+            let result = page_png(&viewer, &chart.borrow())
+                .and_then(|png| std::fs::write(&path, png).map_err(|e| format!("Failed to write {}: {}", path.display(), e)));
+            viewer.set_status(match result {
+                Ok(()) => format!("Saved {}", path.display()).into(),
+                Err(message) => message.into(),
+            });
+        }
+    });
+
+    viewer.on_print_chart({
+        let weak = viewer.as_weak();
+        let chart = chart.clone();
+        move || {
+            let Some(viewer) = weak.upgrade() else { return };
+            let path = std::env::temp_dir().join("aphrodite-chart.png");
+            let result = page_png(&viewer, &chart.borrow())
+                .and_then(|png| std::fs::write(&path, png).map_err(|e| format!("Failed to write {}: {}", path.display(), e)))
+                .and_then(|()| print_file(&path));
+            viewer.set_status(match result {
+                Ok(()) => "Sent to the printer".into(),
+                Err(message) => message.into(),
+            });
+        }
+    });
+
+    viewer.on_planet_clicked({
+        let weak = viewer.as_weak();
+        move |index| {
+            let Some(viewer) = weak.upgrade() else { return };
+            let chart = chart.borrow();
+            let Some(ShownChart { natal, transits, markers }) = chart.as_ref() else { return };
+            let details = usize::try_from(index).ok().and_then(|index| markers.get(index)).and_then(|marker| {
+                match transits {
+                    Some(transits) if marker.layer_id == TRANSIT_LAYER => natal.transit_details(transits, &marker.planet_id),
+                    _ => natal.planet_details(&marker.planet_id),
+                }
+            });
+            if let Some(details) = details {
+                viewer.set_details(details.into());
+            }
+        }
+    });
+
+    viewer.invoke_calculate();
+    viewer.run()?;
+    Ok(())
+}
+
+/// The shown chart on the selected page at print resolution
+fn page_png(viewer: &ChartViewer, chart: &Option<ShownChart>) -> Result<Vec<u8>, String> {
+    let shown = chart.as_ref().ok_or("Calculate a chart first")?;
+    let page = PageSize::from_name(&viewer.get_page_size()).unwrap_or(PageSize::A4);
+    render_page_png(&SlintChartRenderer::new(shown.spec().clone()), page, PRINT_DPI)
+}
+
+/// Push the chart's shapes into the view models; returns the planet markers
+fn show_chart(viewer: &ChartViewer, spec: &ChartSpec) -> Vec<PlanetMarker> {
+    let renderer = SlintChartRenderer::new(spec.clone());
+    let transparent = Color::from_argb_u8(0, 0, 0, 0);
+
+    let paths: Vec<ChartPath> = renderer
+        .paths()
+        .into_iter()
+        .map(|path| ChartPath {
+            commands: path.commands.into(),
+            fill: path.fill.map_or(transparent, to_slint_color),
+            stroke: path.stroke.map_or(transparent, to_slint_color),
+            stroke_width: path.stroke_width,
+        })
+        .collect();
+    let labels: Vec<ChartLabel> = renderer
+        .labels()
+        .into_iter()
+        .map(|label| ChartLabel {
+            x: label.position.x,
+            y: label.position.y,
+            text: label.text.into(),
+            size: label.size,
+            color: to_slint_color(label.color),
+            anchor_x: label.anchor_x,
+            anchor_y: label.anchor_y,
+            rotation: label.rotation,
+        })
+        .collect();
+    let markers = renderer.planet_markers();
+    let planets: Vec<ChartPlanet> = markers
+        .iter()
+        .map(|marker| ChartPlanet {
+            x: marker.center.x,
+            y: marker.center.y,
+            glyph: marker.glyph.as_str().into(),
+            size: marker.size,
+            color: to_slint_color(marker.color),
+        })
+        .collect();
+
+    viewer.set_chart_background(to_slint_color(spec.background_color));
+    viewer.set_paths(ModelRc::new(VecModel::from(paths)));
+    viewer.set_labels(ModelRc::new(VecModel::from(labels)));
+    viewer.set_planets(ModelRc::new(VecModel::from(planets)));
+    markers
+}
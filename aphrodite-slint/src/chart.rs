@@ -0,0 +1,302 @@
+//! Natal chart calculation for the desktop viewer, run locally against Swiss Ephemeris,
+//! with transits of a given moment overlaid on an outer ring.
+
+use aphrodite_core::aspects::{AspectCalculator, AspectSettings, DEFAULT_ORBS};
+use aphrodite_core::aspects::AspectSet;
+use aphrodite_core::ephemeris::{
+    EphemerisSettings, GeoLocation, LayerPositions, LilithType, MoonPosition, NodeType, PolarFallback, PlanetPosition,
+    SwissEphemerisAdapter, DEFAULT_OBJECTS,
+};
+use aphrodite_core::format::format_zodiacal;
+use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler, WheelPresets, DEFAULT_WHEEL_JSON};
+use aphrodite_core::rendering::{ChartSpec, ChartSpecGenerator};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::collections::HashMap;
+
+/// Layer id of the chart drawn by the viewer
+pub const NATAL_LAYER: &str = "natal";
+/// Layer id of the transits overlaid on the natal chart
+pub const TRANSIT_LAYER: &str = "transit";
+
+/// Birth moment and place as entered in the form
+#[derive(Debug, Clone)]
+pub struct BirthData {
+    pub datetime: DateTime<Utc>,
+    pub location: GeoLocation,
+    pub house_system: String,
+}
+
+impl BirthData {
+    /// Parse form fields; the datetime is RFC 3339 or `YYYY-MM-DD HH:MM` in UTC
+    pub fn parse(datetime: &str, lat: &str, lon: &str, house_system: &str) -> Result<Self, String> {
+        let datetime = datetime.trim();
+        let datetime = DateTime::parse_from_rfc3339(datetime)
+            .map(|dt| dt.with_timezone(&Utc))
+            .or_else(|_| NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M").map(|dt| dt.and_utc()))
+            .map_err(|_| format!("Invalid date and time '{}', expected YYYY-MM-DD HH:MM (UTC)", datetime))?;
+        let lat = parse_coordinate("Latitude", lat, 90.0)?;
+        let lon = parse_coordinate("Longitude", lon, 180.0)?;
+        Ok(Self {
+            datetime,
+            location: GeoLocation { lat, lon },
+            house_system: house_system.to_string(),
+        })
+    }
+}
+
+fn parse_coordinate(name: &str, value: &str, limit: f64) -> Result<f64, String> {
+    value
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.abs() <= limit)
+        .ok_or_else(|| format!("{} must be a number between -{} and {}", name, limit, limit))
+}
+
+/// Positions and the wheel drawn from them
+#[derive(Debug, Clone)]
+pub struct NatalChart {
+    pub birth: BirthData,
+    pub positions: LayerPositions,
+    pub spec: ChartSpec,
+}
+
+/// Transits at a moment, drawn around a natal chart
+#[derive(Debug, Clone)]
+pub struct TransitChart {
+    pub datetime: DateTime<Utc>,
+    pub positions: LayerPositions,
+    pub spec: ChartSpec,
+}
+
+impl NatalChart {
+    pub fn calculate(adapter: &mut SwissEphemerisAdapter, birth: &BirthData, size: f32) -> Result<Self, String> {
+        Self::calculate_with_wheel(adapter, birth, DEFAULT_WHEEL_JSON, size)
+    }
+
+    /// The chart drawn with another wheel definition, such as a [`WheelPresets`] entry
+    pub fn calculate_with_wheel(
+        adapter: &mut SwissEphemerisAdapter,
+        birth: &BirthData,
+        wheel_json: &str,
+        size: f32,
+    ) -> Result<Self, String> {
+        let positions = adapter
+            .calc_positions(birth.datetime, Some(birth.location.clone()), &ephemeris_settings(birth))
+            .map_err(|e| e.to_string())?;
+
+        let layers = HashMap::from([(NATAL_LAYER.to_string(), positions.clone())]);
+        let aspects = AspectCalculator::new().compute_all_aspect_sets(&layers, &aspect_settings());
+        let spec = chart_spec(wheel_json, &layers, &aspects, size)?;
+        Ok(Self {
+            birth: birth.clone(),
+            positions,
+            spec,
+        })
+    }
+
+    /// The natal wheel with transits at `datetime`, cast for the birth location. Aspect
+    /// lines show the natal aspects and transits to natal planets.
+    pub fn transits_at(
+        &self,
+        adapter: &mut SwissEphemerisAdapter,
+        datetime: DateTime<Utc>,
+        size: f32,
+    ) -> Result<TransitChart, String> {
+        let transits = adapter
+            .calc_positions(datetime, Some(self.birth.location.clone()), &ephemeris_settings(&self.birth))
+            .map_err(|e| e.to_string())?;
+
+        let settings = aspect_settings();
+        let calculator = AspectCalculator::new();
+        let aspects: HashMap<String, AspectSet> = [
+            calculator.compute_intra_layer_aspects(NATAL_LAYER, &self.positions, &settings),
+            calculator.compute_inter_layer_aspects(TRANSIT_LAYER, NATAL_LAYER, &transits, &self.positions, &settings),
+        ]
+        .into_iter()
+        .map(|set| (set.id.clone(), set))
+        .collect();
+
+        let layers = HashMap::from([
+            (NATAL_LAYER.to_string(), self.positions.clone()),
+            (TRANSIT_LAYER.to_string(), transits.clone()),
+        ]);
+        let spec = chart_spec(transit_wheel_json(), &layers, &aspects, size)?;
+        Ok(TransitChart {
+            datetime,
+            positions: transits,
+            spec,
+        })
+    }
+
+    /// House a longitude falls in, counting from the cusp it follows
+    pub fn house_of(&self, lon: f64) -> Option<u8> {
+        let cusps = &self.positions.houses.as_ref()?.cusps;
+        (1..=12u8).find(|house| {
+            let start = cusps.get(&house.to_string());
+            let end = cusps.get(&(house % 12 + 1).to_string());
+            match (start, end) {
+                (Some(start), Some(end)) => {
+                    (lon - start).rem_euclid(360.0) < (end - start).rem_euclid(360.0)
+                }
+                _ => false,
+            }
+        })
+    }
+
+    /// Multi-line description of a natal planet's position
+    pub fn planet_details(&self, planet_id: &str) -> Option<String> {
+        let position = self.positions.planets.get(planet_id)?;
+        Some(self.details(title_case(planet_id), position))
+    }
+
+    /// Multi-line description of a transiting planet, placed in the natal houses
+    pub fn transit_details(&self, transits: &TransitChart, planet_id: &str) -> Option<String> {
+        let position = transits.positions.planets.get(planet_id)?;
+        Some(self.details(format!("Transiting {}", title_case(planet_id)), position))
+    }
+
+    fn details(&self, title: String, position: &PlanetPosition) -> String {
+        let mut lines = vec![
+            title,
+            format!("Longitude: {}", format_zodiacal(position.lon)),
+            format!("Latitude: {:.2}°", position.lat),
+            format!(
+                "Speed: {:.4}°/day{}",
+                position.speed_lon,
+                if position.retrograde { " (retrograde)" } else { "" }
+            ),
+        ];
+        if let Some(house) = self.house_of(position.lon) {
+            lines.push(format!("House: {}", house));
+        }
+        lines.join("\n")
+    }
+}
+
+fn ephemeris_settings(birth: &BirthData) -> EphemerisSettings {
+    EphemerisSettings {
+        zodiac_type: "tropical".to_string(),
+        ayanamsa: None,
+        custom_ayanamsa: None,
+        house_system: birth.house_system.clone(),
+        include_objects: DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect(),
+        node_type: NodeType::default(),
+        lilith_type: LilithType::default(),
+        moon_position: MoonPosition::default(),
+        polar_fallback: PolarFallback::default(),
+    }
+}
+
+/// Natal wheel with the transiting planets on a ring between the signs and the houses
+fn transit_wheel_json() -> &'static str {
+    WheelPresets::get("natal_transit").map_or(DEFAULT_WHEEL_JSON, |preset| preset.json)
+}
+
+fn aspect_settings() -> AspectSettings {
+    AspectSettings {
+        orb_settings: DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect(),
+        include_objects: Vec::new(),
+        only_major: None,
+        exclude_out_of_sign: false,
+        object_orbs: HashMap::new(),
+        filter: Default::default(),
+    }
+}
+
+fn chart_spec(
+    wheel_json: &str,
+    layers: &HashMap<String, LayerPositions>,
+    aspects: &HashMap<String, AspectSet>,
+    size: f32,
+) -> Result<ChartSpec, String> {
+    let wheel = load_wheel_definition_from_json(wheel_json).map_err(|e| e.to_string())?;
+    let assembled = WheelAssembler::build_wheel(&wheel.wheel, layers, aspects, None);
+    Ok(ChartSpecGenerator::new().generate(&assembled, aspects, size, size))
+}
+
+fn title_case(id: &str) -> String {
+    id.split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aphrodite_core::rendering::Shape;
+
+    #[test]
+    fn test_parse_birth_data() {
+        let birth = BirthData::parse("1990-01-01 12:00", "40.7128", " -74.0060 ", "placidus").unwrap();
+        assert_eq!(birth.datetime.to_rfc3339(), "1990-01-01T12:00:00+00:00");
+        assert_eq!(birth.location.lon, -74.006);
+
+        let offset = BirthData::parse("1990-01-01T07:00:00-05:00", "0", "0", "koch").unwrap();
+        assert_eq!(offset.datetime, birth.datetime);
+
+        assert!(BirthData::parse("yesterday", "0", "0", "placidus").is_err());
+        assert!(BirthData::parse("1990-01-01 12:00", "91", "0", "placidus").is_err());
+    }
+
+    #[test]
+    fn test_format_zodiacal() {
+        assert_eq!(format_zodiacal(0.0), "0°00′ Aries");
+        assert_eq!(format_zodiacal(280.82), "10°49′ Capricorn");
+        assert_eq!(format_zodiacal(-0.5), "29°30′ Pisces");
+        assert_eq!(title_case("north_node"), "North Node");
+    }
+
+    #[test]
+    #[ignore] // Requires Swiss Ephemeris files
+    fn test_calculate_natal_chart() {
+        let mut adapter = SwissEphemerisAdapter::new(None).unwrap();
+        let birth = BirthData::parse("1990-01-01 12:00", "40.7128", "-74.0060", "placidus").unwrap();
+        let chart = NatalChart::calculate(&mut adapter, &birth, 600.0).unwrap();
+        assert_eq!(chart.spec.width, 600.0);
+        assert!(!chart.spec.shapes.is_empty());
+
+        let details = chart.planet_details("sun").unwrap();
+        assert!(details.starts_with("Sun\nLongitude: 10°"), "{}", details);
+        assert!(details.contains("Capricorn"));
+        assert!(details.contains("House: "));
+        assert!(chart.planet_details("vulcan").is_none());
+    }
+
+    #[test]
+    #[ignore] // Requires Swiss Ephemeris files
+    fn test_transits_overlay_natal_chart() {
+        let mut adapter = SwissEphemerisAdapter::new(None).unwrap();
+        let birth = BirthData::parse("1990-01-01 12:00", "40.7128", "-74.0060", "placidus").unwrap();
+        let natal = NatalChart::calculate(&mut adapter, &birth, 600.0).unwrap();
+        let now = BirthData::parse("2024-06-20 00:00", "0", "0", "placidus").unwrap().datetime;
+        let transits = natal.transits_at(&mut adapter, now, 600.0).unwrap();
+
+        let glyph_layers: Vec<&str> = transits
+            .spec
+            .shapes
+            .iter()
+            .filter(|shape| matches!(shape.shape, Shape::PlanetGlyph { .. }))
+            .filter_map(|shape| shape.layer_ids.first().map(String::as_str))
+            .collect();
+        assert!(glyph_layers.contains(&NATAL_LAYER));
+        assert!(glyph_layers.contains(&TRANSIT_LAYER));
+
+        // Transit aspects connect the transit layer to the natal layer only
+        assert!(transits.spec.shapes.iter().any(|shape| matches!(
+            &shape.shape,
+            Shape::AspectLine { from_object: Some(from), .. } if from.layer_id == TRANSIT_LAYER
+        )));
+
+        let details = natal.transit_details(&transits, "sun").unwrap();
+        assert!(details.starts_with("Transiting Sun\nLongitude: 29°"), "{}", details);
+        assert!(details.contains("Gemini"));
+    }
+}
@@ -0,0 +1,251 @@
+//! Print-resolution export: the chart is rebuilt as SVG from the renderer's paths, labels
+//! and planet glyphs, then rasterized with resvg using the system fonts.
+
+use crate::renderer::SlintChartRenderer;
+use crate::shapes::ShapePath;
+use aphrodite_core::rendering::Color;
+use resvg::tiny_skia::{self, Pixmap, Transform};
+use resvg::usvg;
+use std::fmt::Write;
+use std::path::Path;
+use std::process::Command;
+
+/// Resolution of printed pages
+pub const PRINT_DPI: f32 = 300.0;
+/// White border kept around the chart on a page
+pub const PAGE_MARGIN_INCHES: f32 = 0.5;
+
+/// Paper sizes offered for printing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PageSize {
+    A4,
+    Letter,
+}
+
+impl PageSize {
+    pub const ALL: [PageSize; 2] = [PageSize::A4, PageSize::Letter];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            PageSize::A4 => "A4",
+            PageSize::Letter => "Letter",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|page| page.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Portrait width and height in inches
+    pub fn inches(self) -> (f32, f32) {
+        match self {
+            PageSize::A4 => (210.0 / 25.4, 297.0 / 25.4),
+            PageSize::Letter => (8.5, 11.0),
+        }
+    }
+
+    /// Portrait width and height in pixels at `dpi`
+    pub fn pixels(self, dpi: f32) -> (u32, u32) {
+        let (width, height) = self.inches();
+        ((width * dpi).round() as u32, (height * dpi).round() as u32)
+    }
+}
+
+/// The chart as a standalone SVG document in spec coordinates
+pub fn chart_svg(renderer: &SlintChartRenderer) -> String {
+    let spec = renderer.spec();
+    let mut svg = format!(
+        r#"<svg width="{w}" height="{h}" viewBox="0 0 {w} {h}" xmlns="http://www.w3.org/2000/svg">"#,
+        w = spec.width,
+        h = spec.height
+    );
+    let _ = write!(
+        svg,
+        r#"<rect width="{}" height="{}" fill="{}"/>"#,
+        spec.width,
+        spec.height,
+        svg_color(Some(spec.background_color))
+    );
+
+    for ShapePath { commands, fill, stroke, stroke_width } in renderer.paths() {
+        let _ = write!(
+            svg,
+            r#"<path d="{}" fill="{}" stroke="{}" stroke-width="{}"/>"#,
+            commands,
+            svg_color(fill),
+            svg_color(stroke),
+            stroke_width
+        );
+    }
+
+    for label in renderer.labels() {
+        let anchor = if label.anchor_x <= 0.0 {
+            "start"
+        } else if label.anchor_x >= 1.0 {
+            "end"
+        } else {
+            "middle"
+        };
+        let baseline = if label.anchor_y <= 0.0 {
+            "text-before-edge"
+        } else if label.anchor_y >= 1.0 {
+            "text-after-edge"
+        } else if label.anchor_y == 0.5 {
+            "central"
+        } else {
+            "alphabetic"
+        };
+        let (x, y) = (label.position.x, label.position.y);
+        let _ = write!(
+            svg,
+            r#"<text x="{x}" y="{y}" font-size="{}" fill="{}" text-anchor="{}" dominant-baseline="{}" transform="rotate({} {x} {y})">{}</text>"#,
+            label.size,
+            svg_color(Some(label.color)),
+            anchor,
+            baseline,
+            label.rotation,
+            escape_xml(&label.text)
+        );
+    }
+
+    for planet in renderer.planet_markers() {
+        let _ = write!(
+            svg,
+            r#"<text x="{}" y="{}" font-size="{}" fill="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+            planet.center.x,
+            planet.center.y,
+            planet.size,
+            svg_color(Some(planet.color)),
+            escape_xml(&planet.glyph)
+        );
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// The chart as a `width` x `height` PNG, scaled to fit and centered on white
+pub fn render_png(renderer: &SlintChartRenderer, width: u32, height: u32) -> Result<Vec<u8>, String> {
+    render(renderer, width, height, 0.0)
+}
+
+/// A full page at `dpi` with the chart centered inside the page margins
+pub fn render_page_png(renderer: &SlintChartRenderer, page: PageSize, dpi: f32) -> Result<Vec<u8>, String> {
+    if !(dpi.is_finite() && dpi > 0.0) {
+        return Err(format!("DPI must be a positive number, got {}", dpi));
+    }
+    let (width, height) = page.pixels(dpi);
+    render(renderer, width, height, PAGE_MARGIN_INCHES * dpi)
+}
+
+fn render(renderer: &SlintChartRenderer, width: u32, height: u32, margin: f32) -> Result<Vec<u8>, String> {
+    let mut pixmap = Pixmap::new(width, height).ok_or_else(|| format!("Invalid image size {}x{}", width, height))?;
+    pixmap.fill(tiny_skia::Color::WHITE);
+
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    let tree = usvg::Tree::from_str(&chart_svg(renderer), &options).map_err(|e| format!("Failed to build chart SVG: {}", e))?;
+
+    let spec = renderer.spec();
+    let scale = ((width as f32 - 2.0 * margin) / spec.width).min((height as f32 - 2.0 * margin) / spec.height);
+    if scale <= 0.0 {
+        return Err(format!("Image size {}x{} leaves no room for the chart", width, height));
+    }
+    let offset_x = (width as f32 - spec.width * scale) / 2.0;
+    let offset_y = (height as f32 - spec.height * scale) / 2.0;
+    resvg::render(&tree, Transform::from_row(scale, 0.0, 0.0, scale, offset_x, offset_y), &mut pixmap.as_mut());
+
+    pixmap.encode_png().map_err(|e| format!("Failed to encode PNG: {}", e))
+}
+
+/// Hand an exported image to the operating system for printing. Windows opens the print
+/// dialog of the image's default app; elsewhere the file goes to the default CUPS printer
+/// through `lp`, scaled to the page.
+pub fn print_file(path: &Path) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("powershell");
+        command.args(["-NoProfile", "-Command", "Start-Process -FilePath $args[0] -Verb Print"]).arg(path);
+        command
+    } else {
+        let mut command = Command::new("lp");
+        command.args(["-o", "fit-to-page"]).arg(path);
+        command
+    };
+    let output = command.output().map_err(|e| format!("Failed to start the print command: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!("Printing failed: {}", String::from_utf8_lossy(&output.stderr).trim()))
+    }
+}
+
+fn svg_color(color: Option<Color>) -> String {
+    match color {
+        Some(Color { r, g, b, a }) => format!("rgba({},{},{},{:.3})", r, g, b, a as f32 / 255.0),
+        None => "none".to_string(),
+    }
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aphrodite_core::rendering::{ChartShape, ChartSpec, Point, Shape};
+
+    fn renderer() -> SlintChartRenderer {
+        let mut spec = ChartSpec::new(400.0, 400.0);
+        spec.shapes = vec![ChartShape::from(Shape::Circle {
+            center: Point { x: 200.0, y: 200.0 },
+            radius: 150.0,
+            fill: Some(Color { r: 255, g: 0, b: 0, a: 255 }),
+            stroke: None,
+        })];
+        SlintChartRenderer::new(spec)
+    }
+
+    #[test]
+    fn test_page_sizes() {
+        assert_eq!(PageSize::A4.pixels(PRINT_DPI), (2480, 3508));
+        assert_eq!(PageSize::Letter.pixels(PRINT_DPI), (2550, 3300));
+        assert_eq!(PageSize::from_name("letter"), Some(PageSize::Letter));
+        assert_eq!(PageSize::from_name("A3"), None);
+    }
+
+    #[test]
+    fn test_render_page_png() {
+        let png = render_page_png(&renderer(), PageSize::Letter, 100.0).unwrap();
+        let pixmap = Pixmap::decode_png(&png).unwrap();
+        assert_eq!((pixmap.width(), pixmap.height()), (850, 1100));
+
+        // The margin stays white; the chart is centered on the page
+        let margin = pixmap.pixel(10, 10).unwrap();
+        assert_eq!((margin.red(), margin.green(), margin.blue()), (255, 255, 255));
+        let center = pixmap.pixel(425, 550).unwrap();
+        assert_eq!((center.red(), center.green(), center.blue()), (255, 0, 0));
+        // Above the square chart, inside the page margin band
+        let above = pixmap.pixel(425, 100).unwrap();
+        assert_eq!((above.red(), above.green(), above.blue()), (255, 255, 255));
+
+        assert!(render_page_png(&renderer(), PageSize::A4, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_chart_svg_escapes_text() {
+        let mut spec = ChartSpec::new(100.0, 100.0);
+        spec.shapes = vec![ChartShape::from(Shape::Text {
+            position: Point { x: 50.0, y: 50.0 },
+            content: "A & B".to_string(),
+            size: 12.0,
+            color: Color::WHITE,
+            anchor: aphrodite_core::rendering::TextAnchor::Middle,
+            baseline: Default::default(),
+            rotation: None,
+        })];
+        let svg = chart_svg(&SlintChartRenderer::new(spec));
+        assert!(svg.contains(">A &amp; B</text>"));
+        assert!(svg.contains(r#"text-anchor="middle" dominant-baseline="alphabetic""#));
+    }
+}
@@ -1,5 +1,6 @@
+pub mod chart;
+pub mod export;
 pub mod renderer;
 pub mod shapes;
 
 pub use renderer::SlintChartRenderer;
-
@@ -1,23 +1,106 @@
-use aphrodite_core::rendering::{ChartSpec, Shape};
-use slint::SharedString;
+use crate::shapes::{shape_path, ShapePath};
+use aphrodite_core::rendering::{ChartSpec, Color, GlyphConfig, Point, Shape, TextAnchor, TextBaseline};
 
-/// Slint chart renderer - converts ChartSpec to Slint UI
+/// A text shape, positioned by fractions of its measured box
+#[derive(Debug, Clone)]
+pub struct ShapeLabel {
+    pub position: Point,
+    pub text: String,
+    pub size: f32,
+    pub color: Color,
+    /// Fraction of the text width left of `position`
+    pub anchor_x: f32,
+    /// Fraction of the text height above `position`
+    pub anchor_y: f32,
+    /// Degrees clockwise
+    pub rotation: f32,
+}
+
+/// A planet glyph, drawn as its symbol and clickable
+#[derive(Debug, Clone)]
+pub struct PlanetMarker {
+    pub center: Point,
+    pub planet_id: String,
+    /// Layer the planet belongs to
+    pub layer_id: String,
+    pub glyph: String,
+    pub size: f32,
+    pub color: Color,
+    pub retrograde: bool,
+}
+
+/// Slint chart renderer - converts ChartSpec to the models drawn by Slint `Path`, `Text`
+/// and `TouchArea` elements
 pub struct SlintChartRenderer {
     spec: ChartSpec,
+    glyphs: GlyphConfig,
 }
 
 impl SlintChartRenderer {
     /// Create a new renderer from a ChartSpec
     pub fn new(spec: ChartSpec) -> Self {
-        Self { spec }
+        Self {
+            spec,
+            glyphs: GlyphConfig::default(),
+        }
     }
 
-    /// Render the chart to a Slint component
-    /// This is a placeholder - full implementation would create Slint UI elements
-    pub fn render(&self) -> String {
-        // For now, return a simple representation
-        // Full implementation would create Slint components
-        format!("Chart: {}x{} with {} shapes", self.spec.width, self.spec.height, self.spec.shapes.len())
+    pub fn spec(&self) -> &ChartSpec {
+        &self.spec
     }
-}
 
+    /// Paths for every shape except text and planet glyphs, in drawing order
+    pub fn paths(&self) -> Vec<ShapePath> {
+        self.spec.shapes.iter().filter_map(|shape| shape_path(&shape.shape)).collect()
+    }
+
+    /// Text shapes, drawn over the paths
+    pub fn labels(&self) -> Vec<ShapeLabel> {
+        self.spec
+            .shapes
+            .iter()
+            .filter_map(|shape| match &shape.shape {
+                Shape::Text { position, content, size, color, anchor, baseline, rotation } => Some(ShapeLabel {
+                    position: *position,
+                    text: content.clone(),
+                    size: *size,
+                    color: *color,
+                    anchor_x: match anchor {
+                        TextAnchor::Start => 0.0,
+                        TextAnchor::Middle => 0.5,
+                        TextAnchor::End => 1.0,
+                    },
+                    anchor_y: match baseline {
+                        TextBaseline::Top => 0.0,
+                        TextBaseline::Middle => 0.5,
+                        // Approximate ascent of the default font
+                        TextBaseline::Alphabetic => 0.8,
+                        TextBaseline::Bottom => 1.0,
+                    },
+                    rotation: rotation.unwrap_or(0.0),
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Planet glyphs, drawn last so they stay clickable
+    pub fn planet_markers(&self) -> Vec<PlanetMarker> {
+        self.spec
+            .shapes
+            .iter()
+            .filter_map(|shape| match &shape.shape {
+                Shape::PlanetGlyph { center, planet_id, size, color, retrograde } => Some(PlanetMarker {
+                    center: *center,
+                    planet_id: planet_id.clone(),
+                    layer_id: shape.layer_ids.first().cloned().unwrap_or_default(),
+                    glyph: self.glyphs.planet_glyphs.get(planet_id).cloned().unwrap_or_else(|| planet_id.clone()),
+                    size: *size,
+                    color: *color,
+                    retrograde: *retrograde,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+}
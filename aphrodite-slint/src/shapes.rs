@@ -1,40 +1,178 @@
-use aphrodite_core::rendering::Shape;
+//! Conversion of ChartSpec shapes into Slint `Path` commands.
+//!
+//! Slint paths take SVG path syntax, so every shape except text and planet glyphs becomes
+//! a command string in spec coordinates. Slint paths cannot dash their outline, so dashed
+//! and dotted lines are drawn solid.
 
-/// Convert a ChartSpec shape to Slint representation
-/// This is a placeholder - full implementation would convert each shape type
-pub fn shape_to_slint(shape: &Shape) -> String {
+use aphrodite_core::rendering::{Color, Point, Shape};
+use std::fmt::Write;
+
+/// A shape as a Slint path, in spec coordinates
+#[derive(Debug, Clone)]
+pub struct ShapePath {
+    /// SVG path commands
+    pub commands: String,
+    pub fill: Option<Color>,
+    pub stroke: Option<Color>,
+    pub stroke_width: f32,
+}
+
+/// Path for a ChartSpec shape; text and planet glyphs have none
+pub fn shape_path(shape: &Shape) -> Option<ShapePath> {
     match shape {
-        Shape::Circle { center, radius, .. } => {
-            format!("Circle at ({}, {}) radius {}", center.x, center.y, radius)
-        }
-        Shape::Arc { center, radius_inner, radius_outer, start_angle, end_angle, .. } => {
-            format!("Arc at ({}, {}) from {} to {} (inner: {}, outer: {})", 
-                center.x, center.y, start_angle, end_angle, radius_inner, radius_outer)
-        }
-        Shape::Line { from, to, .. } => {
-            format!("Line from ({}, {}) to ({}, {})", from.x, from.y, to.x, to.y)
-        }
-        Shape::Text { position, content, .. } => {
-            format!("Text '{}' at ({}, {})", content, position.x, position.y)
-        }
-        Shape::PlanetGlyph { center, planet_id, .. } => {
-            format!("Planet {} at ({}, {})", planet_id, center.x, center.y)
-        }
-        Shape::AspectLine { from, to, aspect_type, .. } => {
-            format!("Aspect {} from ({}, {}) to ({}, {})", 
-                aspect_type, from.x, from.y, to.x, to.y)
+        Shape::Circle { center, radius, fill, stroke } => Some(ShapePath {
+            commands: circle(*center, *radius),
+            fill: *fill,
+            stroke: stroke.as_ref().map(|s| s.color),
+            stroke_width: stroke.as_ref().map_or(0.0, |s| s.width),
+        }),
+        Shape::Arc { center, radius_inner, radius_outer, start_angle, end_angle, fill, stroke } => {
+            // Canvas angles, swept clockwise
+            let sweep = (end_angle - start_angle).rem_euclid(360.0);
+            Some(ShapePath {
+                commands: sector(*center, *start_angle, sweep, *radius_inner, *radius_outer),
+                fill: *fill,
+                stroke: stroke.as_ref().map(|s| s.color),
+                stroke_width: stroke.as_ref().map_or(0.0, |s| s.width),
+            })
         }
-        Shape::HouseSegment { center, house_num, start_angle, end_angle, .. } => {
-            format!("House {} at ({}, {}) from {} to {}", 
-                house_num, center.x, center.y, start_angle, end_angle)
+        Shape::HouseSegment { center, start_angle, end_angle, radius_inner, radius_outer, fill, stroke, .. }
+        | Shape::SignSegment { center, start_angle, end_angle, radius_inner, radius_outer, fill, stroke, .. } => {
+            // Segment angles are the canvas angle 90° - a, swept the short way
+            let (start, end) = (90.0 - start_angle, 90.0 - end_angle);
+            let sweep = (end - start).rem_euclid(360.0);
+            let commands = if sweep <= 180.0 {
+                sector(*center, start, sweep, *radius_inner, *radius_outer)
+            } else {
+                sector(*center, end, 360.0 - sweep, *radius_inner, *radius_outer)
+            };
+            Some(ShapePath {
+                commands,
+                fill: Some(*fill),
+                stroke: stroke.as_ref().map(|s| s.color),
+                stroke_width: stroke.as_ref().map_or(0.0, |s| s.width),
+            })
         }
-        Shape::SignSegment { center, sign_index, start_angle, end_angle, .. } => {
-            format!("Sign {} at ({}, {}) from {} to {}", 
-                sign_index, center.x, center.y, start_angle, end_angle)
-        }
-        Shape::Path { points, .. } => {
-            format!("Path with {} points", points.len())
+        Shape::Line { from, to, stroke } => Some(ShapePath {
+            commands: polyline(&[*from, *to], false),
+            fill: None,
+            stroke: Some(stroke.color),
+            stroke_width: stroke.width,
+        }),
+        Shape::AspectLine { from, to, color, width, .. } => Some(ShapePath {
+            commands: polyline(&[*from, *to], false),
+            fill: None,
+            stroke: Some(*color),
+            stroke_width: *width,
+        }),
+        Shape::Path { points, closed, fill, stroke } => (points.len() > 1).then(|| ShapePath {
+            commands: polyline(points, *closed),
+            fill: *fill,
+            stroke: stroke.as_ref().map(|s| s.color),
+            stroke_width: stroke.as_ref().map_or(0.0, |s| s.width),
+        }),
+        Shape::Text { .. } | Shape::PlanetGlyph { .. } => None,
+    }
+}
+
+/// Slint color for a ChartSpec color
+pub fn to_slint_color(color: Color) -> slint::Color {
+    slint::Color::from_argb_u8(color.a, color.r, color.g, color.b)
+}
+
+fn at(center: Point, angle: f32, radius: f32) -> Point {
+    let radians = angle.to_radians();
+    Point {
+        x: center.x + radius * radians.cos(),
+        y: center.y + radius * radians.sin(),
+    }
+}
+
+fn circle(center: Point, radius: f32) -> String {
+    let (left, right) = (center.x - radius, center.x + radius);
+    format!(
+        "M {right:.2} {y:.2} A {radius:.2} {radius:.2} 0 1 1 {left:.2} {y:.2} A {radius:.2} {radius:.2} 0 1 1 {right:.2} {y:.2} Z",
+        y = center.y
+    )
+}
+
+/// Annular sector from canvas angle `start` through `sweep` degrees clockwise; each edge is
+/// drawn as two half arcs so full circles survive
+fn sector(center: Point, start: f32, sweep: f32, radius_inner: f32, radius_outer: f32) -> String {
+    let angles = [start, start + sweep / 2.0, start + sweep];
+    let mut commands = String::new();
+
+    let first = at(center, start, radius_outer);
+    let _ = write!(commands, "M {:.2} {:.2}", first.x, first.y);
+    for angle in &angles[1..] {
+        let point = at(center, *angle, radius_outer);
+        let _ = write!(commands, " A {r:.2} {r:.2} 0 0 1 {:.2} {:.2}", point.x, point.y, r = radius_outer);
+    }
+
+    if radius_inner > 0.0 {
+        let corner = at(center, start + sweep, radius_inner);
+        let _ = write!(commands, " L {:.2} {:.2}", corner.x, corner.y);
+        for angle in angles[..2].iter().rev() {
+            let point = at(center, *angle, radius_inner);
+            let _ = write!(commands, " A {r:.2} {r:.2} 0 0 0 {:.2} {:.2}", point.x, point.y, r = radius_inner);
         }
+    } else {
+        let _ = write!(commands, " L {:.2} {:.2}", center.x, center.y);
+    }
+    commands.push_str(" Z");
+    commands
+}
+
+fn polyline(points: &[Point], closed: bool) -> String {
+    let mut commands = String::new();
+    for (index, point) in points.iter().enumerate() {
+        let command = if index == 0 { "M" } else { " L" };
+        let _ = write!(commands, "{} {:.2} {:.2}", command, point.x, point.y);
+    }
+    if closed {
+        commands.push_str(" Z");
     }
+    commands
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aphrodite_core::rendering::Stroke;
+
+    const CENTER: Point = Point { x: 100.0, y: 100.0 };
+
+    #[test]
+    fn test_sign_segment_path() {
+        // Generator angles 90°..120° are canvas angles 0°..-30°, drawn clockwise from -30°
+        let shape = Shape::SignSegment {
+            center: CENTER,
+            start_angle: 90.0,
+            end_angle: 120.0,
+            radius_inner: 50.0,
+            radius_outer: 80.0,
+            sign_index: 0,
+            fill: Color::WHITE,
+            stroke: Some(Stroke { color: Color::BLACK, width: 1.0, dash_array: None }),
+        };
+        let path = shape_path(&shape).unwrap();
+        assert!(path.commands.starts_with("M 169.28 60.00"), "{}", path.commands);
+        assert!(path.commands.contains(" L 150.00 100.00"), "{}", path.commands);
+        assert!(path.commands.ends_with("Z"));
+        assert_eq!(path.fill.map(to_slint_color), Some(slint::Color::from_rgb_u8(255, 255, 255)));
+        assert_eq!(path.stroke_width, 1.0);
+    }
+
+    #[test]
+    fn test_glyphs_have_no_path() {
+        let glyph = Shape::PlanetGlyph {
+            center: CENTER,
+            planet_id: "sun".to_string(),
+            size: 12.0,
+            color: Color::WHITE,
+            retrograde: false,
+        };
+        assert!(shape_path(&glyph).is_none());
+        assert_eq!(to_slint_color(Color::WHITE), slint::Color::from_rgb_u8(255, 255, 255));
+    }
+}
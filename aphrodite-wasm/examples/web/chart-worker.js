@@ -0,0 +1,21 @@
+// Calculates and draws the chart on the OffscreenCanvas transferred by worker.html
+import init, { ChartRenderer } from './pkg/aphrodite_wasm.js';
+
+let renderer = null;
+let ctx = null;
+
+onmessage = async ({ data }) => {
+    try {
+        if (data.type === 'init') {
+            await init();
+            renderer = ChartRenderer.fromRequest(data.request, 800, 800);
+            renderer.fit_offscreen_canvas(data.canvas, data.pixelRatio);
+            ctx = data.canvas.getContext('2d');
+        } else if (data.type === 'ring' && renderer) {
+            renderer.set_ring_visible(data.slug, data.visible);
+        }
+        if (renderer) renderer.render_to_canvas(ctx);
+    } catch (error) {
+        postMessage({ error: String(error) });
+    }
+};
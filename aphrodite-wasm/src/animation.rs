@@ -0,0 +1,242 @@
+//! Interpolation between two ChartSpecs for time-scrubbing animations.
+//!
+//! Planet glyphs are matched by layer and planet id and move along the wheel in polar
+//! coordinates around the chart center, taking the shorter way round. Aspect line ends
+//! turn with their planets. Every other shape is taken from the starting spec until the
+//! animation completes, when the target spec replaces it.
+
+use aphrodite_core::rendering::{ChartShape, ChartSpec, Point, Shape};
+use std::collections::HashMap;
+use std::f32::consts::{PI, TAU};
+
+/// (layer id, planet id)
+type PlanetKey = (String, String);
+
+#[derive(Debug, Clone, Copy)]
+struct Polar {
+    angle: f32,
+    radius: f32,
+}
+
+impl Polar {
+    fn of(point: Point, center: Point) -> Self {
+        Self {
+            angle: (point.y - center.y).atan2(point.x - center.x),
+            radius: (point.x - center.x).hypot(point.y - center.y),
+        }
+    }
+
+    fn point(self, center: Point) -> Point {
+        Point {
+            x: center.x + self.radius * self.angle.cos(),
+            y: center.y + self.radius * self.angle.sin(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Track {
+    from: Polar,
+    to: Polar,
+}
+
+impl Track {
+    /// Signed angle travelled by progress `t`, the shorter way round
+    fn turn(&self, t: f32) -> f32 {
+        let delta = (self.to.angle - self.from.angle + PI).rem_euclid(TAU) - PI;
+        delta * t
+    }
+
+    fn at(&self, t: f32) -> Polar {
+        Polar {
+            angle: self.from.angle + self.turn(t),
+            radius: self.from.radius + (self.to.radius - self.from.radius) * t,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Animation {
+    from: ChartSpec,
+    to: ChartSpec,
+    tracks: HashMap<PlanetKey, Track>,
+    /// Indices of shapes in `from` that move while animating
+    moving: Vec<usize>,
+}
+
+impl Animation {
+    pub fn new(from: ChartSpec, to: ChartSpec) -> Self {
+        let from_glyphs = glyph_positions(&from);
+        let to_glyphs = glyph_positions(&to);
+        let tracks: HashMap<PlanetKey, Track> = from_glyphs
+            .into_iter()
+            .filter_map(|(key, start)| {
+                let end = to_glyphs.get(&key)?;
+                let track = Track {
+                    from: Polar::of(start, from.center),
+                    to: Polar::of(*end, to.center),
+                };
+                Some((key, track))
+            })
+            .collect();
+
+        let moving = from
+            .shapes
+            .iter()
+            .enumerate()
+            .filter(|(_, shape)| matches!(shape.shape, Shape::PlanetGlyph { .. } | Shape::AspectLine { .. }))
+            .map(|(index, _)| index)
+            .collect();
+
+        Self { from, to, tracks, moving }
+    }
+
+    pub fn moving(&self) -> &[usize] {
+        &self.moving
+    }
+
+    /// The chart at progress `t`, clamped to 0..=1
+    pub fn frame(&self, t: f32) -> ChartSpec {
+        let t = t.clamp(0.0, 1.0);
+        if t >= 1.0 {
+            return self.to.clone();
+        }
+
+        let center = self.from.center;
+        let mut frame = self.from.clone();
+        for &index in &self.moving {
+            let chart_shape = &mut frame.shapes[index];
+            let layer_id = chart_shape.layer_ids.first().cloned().unwrap_or_default();
+            match &mut chart_shape.shape {
+                Shape::PlanetGlyph { center: glyph, planet_id, .. } => {
+                    if let Some(track) = self.tracks.get(&(layer_id, planet_id.clone())) {
+                        *glyph = track.at(t).point(center);
+                    }
+                }
+                Shape::AspectLine { from, to, from_object, to_object, .. } => {
+                    for (end, object) in [(from, from_object), (to, to_object)] {
+                        let Some(object) = object.as_ref().filter(|object| object.object_type == "planet") else {
+                            continue;
+                        };
+                        if let Some(track) = self.tracks.get(&(object.layer_id.clone(), object.object_id.clone())) {
+                            let polar = Polar::of(*end, center);
+                            *end = Polar { angle: polar.angle + track.turn(t), ..polar }.point(center);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        frame
+    }
+}
+
+fn glyph_positions(spec: &ChartSpec) -> HashMap<PlanetKey, Point> {
+    spec.shapes
+        .iter()
+        .filter_map(|ChartShape { shape, layer_ids, .. }| match shape {
+            Shape::PlanetGlyph { center, planet_id, .. } => {
+                let layer_id = layer_ids.first().cloned().unwrap_or_default();
+                Some(((layer_id, planet_id.clone()), *center))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aphrodite_core::aspects::AspectObjectRef;
+    use crate::test_support::tagged;
+    use aphrodite_core::rendering::{Color, LineStyle};
+
+    const CENTER: Point = Point { x: 400.0, y: 400.0 };
+
+    fn at(degrees: f32, radius: f32) -> Point {
+        Polar { angle: degrees.to_radians(), radius }.point(CENTER)
+    }
+
+    fn glyph(planet_id: &str, position: Point) -> ChartShape {
+        let glyph = Shape::PlanetGlyph {
+            center: position,
+            planet_id: planet_id.to_string(),
+            size: 12.0,
+            color: Color::WHITE,
+            retrograde: false,
+        };
+        tagged(glyph, "ring_planets", &["natal"])
+    }
+
+    fn planet(id: &str) -> Option<AspectObjectRef> {
+        Some(AspectObjectRef {
+            layer_id: "natal".to_string(),
+            object_type: "planet".to_string(),
+            object_id: id.to_string(),
+        })
+    }
+
+    fn spec(sun: f32, moon: f32) -> ChartSpec {
+        let mut spec = ChartSpec::new(800.0, 800.0);
+        spec.shapes = vec![
+            glyph("sun", at(sun, 200.0)),
+            glyph("moon", at(moon, 200.0)),
+            tagged(
+                Shape::AspectLine {
+                    from: at(sun, 150.0),
+                    to: at(moon, 150.0),
+                    aspect_type: "square".to_string(),
+                    color: Color::WHITE,
+                    width: 1.0,
+                    style: LineStyle::Solid,
+                    from_object: planet("sun"),
+                    to_object: planet("moon"),
+                },
+                "aspects",
+                &["natal"],
+            ),
+        ];
+        spec
+    }
+
+    fn assert_near(actual: Point, expected: Point) {
+        assert!(
+            (actual.x - expected.x).abs() < 1e-3 && (actual.y - expected.y).abs() < 1e-3,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    fn glyph_center(spec: &ChartSpec, index: usize) -> Point {
+        match spec.shapes[index].shape {
+            Shape::PlanetGlyph { center, .. } => center,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_glyphs_move_along_the_wheel() {
+        // The moon crosses the 180° seam the short way, from 170° to -170°
+        let animation = Animation::new(spec(10.0, 170.0), spec(30.0, 190.0));
+        assert_eq!(animation.moving(), &[0, 1, 2]);
+
+        let frame = animation.frame(0.5);
+        assert_near(glyph_center(&frame, 0), at(20.0, 200.0));
+        assert_near(glyph_center(&frame, 1), at(180.0, 200.0));
+        let Shape::AspectLine { from, to, .. } = frame.shapes[2].shape else { unreachable!() };
+        assert_near(from, at(20.0, 150.0));
+        assert_near(to, at(180.0, 150.0));
+
+        assert_near(glyph_center(&animation.frame(-1.0), 0), at(10.0, 200.0));
+        assert_near(glyph_center(&animation.frame(2.0), 0), at(30.0, 200.0));
+    }
+
+    #[test]
+    fn test_unmatched_glyphs_stay_put() {
+        let mut to = spec(30.0, 190.0);
+        to.shapes.remove(1);
+        let frame = Animation::new(spec(10.0, 170.0), to).frame(0.5);
+        assert_near(glyph_center(&frame, 1), at(170.0, 200.0));
+    }
+}
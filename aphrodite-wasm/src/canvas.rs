@@ -1,8 +1,136 @@
-use aphrodite_core::rendering::{Color, Shape, Stroke};
-use web_sys::CanvasRenderingContext2d;
+use aphrodite_core::rendering::{Color, LineStyle, Point, Shape, TextAnchor, TextBaseline};
+use wasm_bindgen::JsValue;
+use web_sys::{CanvasRenderingContext2d, OffscreenCanvas, OffscreenCanvasRenderingContext2d, TextMetrics};
 
-/// Render a shape to HTML5 Canvas
-pub fn render_shape(ctx: &CanvasRenderingContext2d, shape: &Shape) -> Result<(), wasm_bindgen::JsValue> {
+/// 2D drawing calls shared by page canvases and OffscreenCanvas, so shapes can be drawn
+/// on the main thread or in a worker
+pub trait Context2d {
+    fn begin_path(&self);
+    fn close_path(&self);
+    fn move_to(&self, x: f64, y: f64);
+    fn line_to(&self, x: f64, y: f64);
+    fn arc(&self, x: f64, y: f64, radius: f64, start: f64, end: f64) -> Result<(), JsValue>;
+    fn arc_with_anticlockwise(&self, x: f64, y: f64, radius: f64, start: f64, end: f64, anticlockwise: bool) -> Result<(), JsValue>;
+    fn fill(&self);
+    fn stroke(&self);
+    fn set_fill_style_str(&self, value: &str);
+    fn set_stroke_style_str(&self, value: &str);
+    fn set_line_width(&self, value: f64);
+    /// Dash and gap lengths for following strokes; empty draws solid lines
+    fn set_line_dash(&self, segments: &[f64]) -> Result<(), JsValue>;
+    fn set_font(&self, value: &str);
+    fn set_text_align(&self, value: &str);
+    fn set_text_baseline(&self, value: &str);
+    fn measure_text(&self, text: &str) -> Result<TextMetrics, JsValue>;
+    fn fill_text(&self, text: &str, x: f64, y: f64) -> Result<(), JsValue>;
+    fn save(&self);
+    fn restore(&self);
+    fn translate(&self, x: f64, y: f64) -> Result<(), JsValue>;
+    fn rotate(&self, angle: f64) -> Result<(), JsValue>;
+    fn set_transform(&self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Result<(), JsValue>;
+    fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64);
+    fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64);
+    /// Backing store size of the context's canvas
+    fn canvas_size(&self) -> Option<(u32, u32)>;
+    /// Copy a cached canvas onto this one at the origin
+    fn draw_canvas(&self, canvas: &OffscreenCanvas) -> Result<(), JsValue>;
+}
+
+macro_rules! impl_context_2d {
+    ($ty:ty, |$ctx:ident| $canvas_size:expr) => {
+        impl Context2d for $ty {
+            fn begin_path(&self) {
+                <$ty>::begin_path(self)
+            }
+            fn close_path(&self) {
+                <$ty>::close_path(self)
+            }
+            fn move_to(&self, x: f64, y: f64) {
+                <$ty>::move_to(self, x, y)
+            }
+            fn line_to(&self, x: f64, y: f64) {
+                <$ty>::line_to(self, x, y)
+            }
+            fn arc(&self, x: f64, y: f64, radius: f64, start: f64, end: f64) -> Result<(), JsValue> {
+                <$ty>::arc(self, x, y, radius, start, end)
+            }
+            fn arc_with_anticlockwise(&self, x: f64, y: f64, radius: f64, start: f64, end: f64, anticlockwise: bool) -> Result<(), JsValue> {
+                <$ty>::arc_with_anticlockwise(self, x, y, radius, start, end, anticlockwise)
+            }
+            fn fill(&self) {
+                <$ty>::fill(self)
+            }
+            fn stroke(&self) {
+                <$ty>::stroke(self)
+            }
+            fn set_fill_style_str(&self, value: &str) {
+                <$ty>::set_fill_style_str(self, value)
+            }
+            fn set_stroke_style_str(&self, value: &str) {
+                <$ty>::set_stroke_style_str(self, value)
+            }
+            fn set_line_width(&self, value: f64) {
+                <$ty>::set_line_width(self, value)
+            }
+            fn set_line_dash(&self, segments: &[f64]) -> Result<(), JsValue> {
+                let segments: js_sys::Array = segments.iter().map(|&length| JsValue::from_f64(length)).collect();
+                <$ty>::set_line_dash(self, &segments)
+            }
+            fn set_font(&self, value: &str) {
+                <$ty>::set_font(self, value)
+            }
+            fn set_text_align(&self, value: &str) {
+                <$ty>::set_text_align(self, value)
+            }
+            fn set_text_baseline(&self, value: &str) {
+                <$ty>::set_text_baseline(self, value)
+            }
+            fn measure_text(&self, text: &str) -> Result<TextMetrics, JsValue> {
+                <$ty>::measure_text(self, text)
+            }
+            fn fill_text(&self, text: &str, x: f64, y: f64) -> Result<(), JsValue> {
+                <$ty>::fill_text(self, text, x, y)
+            }
+            fn save(&self) {
+                <$ty>::save(self)
+            }
+            fn restore(&self) {
+                <$ty>::restore(self)
+            }
+            fn translate(&self, x: f64, y: f64) -> Result<(), JsValue> {
+                <$ty>::translate(self, x, y)
+            }
+            fn rotate(&self, angle: f64) -> Result<(), JsValue> {
+                <$ty>::rotate(self, angle)
+            }
+            fn set_transform(&self, a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Result<(), JsValue> {
+                <$ty>::set_transform(self, a, b, c, d, e, f)
+            }
+            fn clear_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+                <$ty>::clear_rect(self, x, y, width, height)
+            }
+            fn fill_rect(&self, x: f64, y: f64, width: f64, height: f64) {
+                <$ty>::fill_rect(self, x, y, width, height)
+            }
+            fn canvas_size(&self) -> Option<(u32, u32)> {
+                let $ctx = self;
+                $canvas_size
+            }
+            fn draw_canvas(&self, canvas: &OffscreenCanvas) -> Result<(), JsValue> {
+                <$ty>::draw_image_with_offscreen_canvas(self, canvas, 0.0, 0.0)
+            }
+        }
+    };
+}
+
+impl_context_2d!(CanvasRenderingContext2d, |ctx| ctx.canvas().map(|canvas| (canvas.width(), canvas.height())));
+impl_context_2d!(OffscreenCanvasRenderingContext2d, |ctx| {
+    let canvas = ctx.canvas();
+    Some((canvas.width(), canvas.height()))
+});
+
+/// Render a shape to a 2D canvas context
+pub fn render_shape<C: Context2d>(ctx: &C, shape: &Shape) -> Result<(), JsValue> {
     match shape {
         Shape::Circle { center, radius, fill, stroke } => {
             ctx.begin_path();
@@ -15,14 +143,14 @@ pub fn render_shape(ctx: &CanvasRenderingContext2d, shape: &Shape) -> Result<(),
             )?;
             
             if let Some(fill_color) = fill {
-                ctx.set_fill_style(&color_to_css(fill_color));
-                ctx.fill()?;
+                ctx.set_fill_style_str(&color_to_css(fill_color));
+                ctx.fill();
             }
             
             if let Some(stroke_style) = stroke {
-                ctx.set_stroke_style(&color_to_css(&stroke_style.color));
+                ctx.set_stroke_style_str(&color_to_css(&stroke_style.color));
                 ctx.set_line_width(stroke_style.width as f64);
-                ctx.stroke()?;
+                ctx.stroke();
             }
         }
         Shape::Arc { center, radius_inner, radius_outer, start_angle, end_angle, fill, stroke } => {
@@ -43,7 +171,7 @@ pub fn render_shape(ctx: &CanvasRenderingContext2d, shape: &Shape) -> Result<(),
             // Line to inner arc
             let inner_end_x = center.x as f64 + *radius_inner as f64 * end_rad.cos();
             let inner_end_y = center.y as f64 + *radius_inner as f64 * end_rad.sin();
-            ctx.line_to(inner_end_x, inner_end_y)?;
+            ctx.line_to(inner_end_x, inner_end_y);
             
             // Inner arc (reverse direction)
             ctx.arc(
@@ -57,86 +185,68 @@ pub fn render_shape(ctx: &CanvasRenderingContext2d, shape: &Shape) -> Result<(),
             ctx.close_path();
             
             if let Some(fill_color) = fill {
-                ctx.set_fill_style(&color_to_css(fill_color));
-                ctx.fill()?;
+                ctx.set_fill_style_str(&color_to_css(fill_color));
+                ctx.fill();
             }
             
             if let Some(stroke_style) = stroke {
-                ctx.set_stroke_style(&color_to_css(&stroke_style.color));
+                ctx.set_stroke_style_str(&color_to_css(&stroke_style.color));
                 ctx.set_line_width(stroke_style.width as f64);
-                ctx.stroke()?;
+                ctx.stroke();
             }
         }
         Shape::Line { from, to, stroke } => {
             ctx.begin_path();
             ctx.move_to(from.x as f64, from.y as f64);
             ctx.line_to(to.x as f64, to.y as f64);
-            ctx.set_stroke_style(&color_to_css(&stroke.color));
+            ctx.set_stroke_style_str(&color_to_css(&stroke.color));
             ctx.set_line_width(stroke.width as f64);
-            ctx.stroke()?;
+            ctx.stroke();
         }
-        Shape::Text { position, content, size, color, .. } => {
-            ctx.set_fill_style(&color_to_css(color));
+        Shape::Text { position, content, size, color, anchor, baseline, rotation } => {
+            ctx.set_fill_style_str(&color_to_css(color));
             ctx.set_font(&format!("{}px sans-serif", size));
-            ctx.fill_text(content, position.x as f64, position.y as f64)?;
+            draw_text(ctx, content, *position, *anchor, *baseline, *rotation)?;
         }
         Shape::PlanetGlyph { center, planet_id, size, color, .. } => {
             // Render planet glyph as text (using Unicode glyphs)
-            ctx.set_fill_style(&color_to_css(color));
+            ctx.set_fill_style_str(&color_to_css(color));
             ctx.set_font(&format!("{}px sans-serif", size));
             // For now, just render the planet ID - full implementation would use glyph fonts
-            ctx.fill_text(planet_id, center.x as f64, center.y as f64)?;
+            draw_text(ctx, planet_id, *center, TextAnchor::Middle, TextBaseline::Middle, None)?;
         }
-        Shape::AspectLine { from, to, aspect_type: _, color, width, .. } => {
+        Shape::AspectLine { from, to, aspect_type: _, color, width, style, .. } => {
             ctx.begin_path();
             ctx.move_to(from.x as f64, from.y as f64);
             ctx.line_to(to.x as f64, to.y as f64);
-            ctx.set_stroke_style(&color_to_css(color));
+            ctx.set_stroke_style_str(&color_to_css(color));
             ctx.set_line_width(*width as f64);
-            ctx.stroke()?;
+            ctx.set_line_dash(aspect_dashes(*style))?;
+            ctx.stroke();
+            ctx.set_line_dash(&[])?;
         }
         Shape::HouseSegment { center, house_num: _, start_angle, end_angle, radius_inner, radius_outer, fill, stroke } => {
-            // Similar to Arc rendering
-            ctx.begin_path();
-            let start_rad = start_angle.to_radians() as f64;
-            let end_rad = end_angle.to_radians() as f64;
-            
-            ctx.arc(center.x as f64, center.y as f64, *radius_outer as f64, start_rad, end_rad)?;
-            let inner_end_x = center.x as f64 + *radius_inner as f64 * end_rad.cos();
-            let inner_end_y = center.y as f64 + *radius_inner as f64 * end_rad.sin();
-            ctx.line_to(inner_end_x, inner_end_y)?;
-            ctx.arc(center.x as f64, center.y as f64, *radius_inner as f64, end_rad, start_rad)?;
-            ctx.close_path();
+            segment_path(ctx, *center, *start_angle, *end_angle, *radius_inner, *radius_outer)?;
             
-            ctx.set_fill_style(&color_to_css(fill));
-            ctx.fill()?;
+            ctx.set_fill_style_str(&color_to_css(fill));
+            ctx.fill();
             
             if let Some(stroke_style) = stroke {
-                ctx.set_stroke_style(&color_to_css(&stroke_style.color));
+                ctx.set_stroke_style_str(&color_to_css(&stroke_style.color));
                 ctx.set_line_width(stroke_style.width as f64);
-                ctx.stroke()?;
+                ctx.stroke();
             }
         }
         Shape::SignSegment { center, sign_index: _, start_angle, end_angle, radius_inner, radius_outer, fill, stroke } => {
-            // Same as HouseSegment
-            ctx.begin_path();
-            let start_rad = start_angle.to_radians() as f64;
-            let end_rad = end_angle.to_radians() as f64;
-            
-            ctx.arc(center.x as f64, center.y as f64, *radius_outer as f64, start_rad, end_rad)?;
-            let inner_end_x = center.x as f64 + *radius_inner as f64 * end_rad.cos();
-            let inner_end_y = center.y as f64 + *radius_inner as f64 * end_rad.sin();
-            ctx.line_to(inner_end_x, inner_end_y)?;
-            ctx.arc(center.x as f64, center.y as f64, *radius_inner as f64, end_rad, start_rad)?;
-            ctx.close_path();
+            segment_path(ctx, *center, *start_angle, *end_angle, *radius_inner, *radius_outer)?;
             
-            ctx.set_fill_style(&color_to_css(fill));
-            ctx.fill()?;
+            ctx.set_fill_style_str(&color_to_css(fill));
+            ctx.fill();
             
             if let Some(stroke_style) = stroke {
-                ctx.set_stroke_style(&color_to_css(&stroke_style.color));
+                ctx.set_stroke_style_str(&color_to_css(&stroke_style.color));
                 ctx.set_line_width(stroke_style.width as f64);
-                ctx.stroke()?;
+                ctx.stroke();
             }
         }
         Shape::Path { points, closed, fill, stroke } => {
@@ -154,14 +264,14 @@ pub fn render_shape(ctx: &CanvasRenderingContext2d, shape: &Shape) -> Result<(),
             }
             
             if let Some(fill_color) = fill {
-                ctx.set_fill_style(&color_to_css(fill_color));
-                ctx.fill()?;
+                ctx.set_fill_style_str(&color_to_css(fill_color));
+                ctx.fill();
             }
             
             if let Some(stroke_style) = stroke {
-                ctx.set_stroke_style(&color_to_css(&stroke_style.color));
+                ctx.set_stroke_style_str(&color_to_css(&stroke_style.color));
                 ctx.set_line_width(stroke_style.width as f64);
-                ctx.stroke()?;
+                ctx.stroke();
             }
         }
     }
@@ -169,6 +279,87 @@ pub fn render_shape(ctx: &CanvasRenderingContext2d, shape: &Shape) -> Result<(),
     Ok(())
 }
 
+/// Draw text aligned on `position` from its measured extent, turned by `rotation` degrees
+/// clockwise around that point
+fn draw_text<C: Context2d>(
+    ctx: &C,
+    content: &str,
+    position: Point,
+    anchor: TextAnchor,
+    baseline: TextBaseline,
+    rotation: Option<f32>,
+) -> Result<(), JsValue> {
+    ctx.set_text_align("left");
+    ctx.set_text_baseline("alphabetic");
+    let metrics = ctx.measure_text(content)?;
+    let (dx, dy) = text_offset(
+        anchor,
+        baseline,
+        metrics.width(),
+        metrics.actual_bounding_box_ascent(),
+        metrics.actual_bounding_box_descent(),
+    );
+
+    ctx.save();
+    ctx.translate(position.x as f64, position.y as f64)?;
+    if let Some(rotation) = rotation {
+        ctx.rotate((rotation as f64).to_radians())?;
+    }
+    let result = ctx.fill_text(content, dx, dy);
+    ctx.restore();
+    result
+}
+
+/// Offset from the anchor point to the alphabetic baseline start of a text run
+fn text_offset(anchor: TextAnchor, baseline: TextBaseline, width: f64, ascent: f64, descent: f64) -> (f64, f64) {
+    let dx = match anchor {
+        TextAnchor::Start => 0.0,
+        TextAnchor::Middle => -width / 2.0,
+        TextAnchor::End => -width,
+    };
+    let dy = match baseline {
+        TextBaseline::Top => ascent,
+        TextBaseline::Middle => (ascent - descent) / 2.0,
+        TextBaseline::Alphabetic => 0.0,
+        TextBaseline::Bottom => -descent,
+    };
+    (dx, dy)
+}
+
+/// Trace a house or sign segment, sweeping the short way between its angles.
+///
+/// Segment angles follow the ChartSpecGenerator placement, which is the canvas angle
+/// `90° - a`.
+fn segment_path<C: Context2d>(
+    ctx: &C,
+    center: Point,
+    start_angle: f32,
+    end_angle: f32,
+    radius_inner: f32,
+    radius_outer: f32,
+) -> Result<(), JsValue> {
+    let start_rad = (90.0 - start_angle as f64).to_radians();
+    let end_rad = (90.0 - end_angle as f64).to_radians();
+    let anticlockwise = (end_angle - start_angle).rem_euclid(360.0) < 180.0;
+    let (cx, cy) = (center.x as f64, center.y as f64);
+
+    ctx.begin_path();
+    ctx.arc_with_anticlockwise(cx, cy, radius_outer as f64, start_rad, end_rad, anticlockwise)?;
+    ctx.line_to(cx + radius_inner as f64 * end_rad.cos(), cy + radius_inner as f64 * end_rad.sin());
+    ctx.arc_with_anticlockwise(cx, cy, radius_inner as f64, end_rad, start_rad, !anticlockwise)?;
+    ctx.close_path();
+    Ok(())
+}
+
+/// Dash and gap lengths of an aspect line style
+pub(crate) fn aspect_dashes(style: LineStyle) -> &'static [f64] {
+    match style {
+        LineStyle::Solid => &[],
+        LineStyle::Dashed => &[6.0, 4.0],
+        LineStyle::Dotted => &[1.0, 3.0],
+    }
+}
+
 /// Convert Color to CSS string
 fn color_to_css(color: &Color) -> String {
     if color.a == 255 {
@@ -184,3 +375,17 @@ fn color_to_css(color: &Color) -> String {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_offset() {
+        // 40px wide, 10px above and 2px below the baseline
+        let offset = |anchor, baseline| text_offset(anchor, baseline, 40.0, 10.0, 2.0);
+        assert_eq!(offset(TextAnchor::Start, TextBaseline::Alphabetic), (0.0, 0.0));
+        assert_eq!(offset(TextAnchor::Middle, TextBaseline::Middle), (-20.0, 4.0));
+        assert_eq!(offset(TextAnchor::End, TextBaseline::Top), (-40.0, 10.0));
+        assert_eq!(offset(TextAnchor::Start, TextBaseline::Bottom), (0.0, -2.0));
+    }
+}
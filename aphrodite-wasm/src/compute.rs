@@ -0,0 +1,285 @@
+//! Client-side chart calculation from a RenderRequest, using the analytic ephemeris.
+//!
+//! Accepts the same JSON as the API's `/api/v1/render` (subjects, settings and
+//! `layer_config` with natal and transit layers); fields the browser cannot honour, such
+//! as Vedic options, are ignored.
+
+use aphrodite_core::aspects::{AspectCalculator, AspectSet, AspectSettings, DEFAULT_ORBS};
+use aphrodite_core::ephemeris::{
+    AnalyticEphemeris, EphemerisSettings, GeoLocation, LayerPositions, LilithType, MoonPosition, NodeType, PolarFallback,
+    DEFAULT_OBJECTS,
+};
+use aphrodite_core::layout::{load_wheel_definition_from_json, WheelAssembler, DEFAULT_WHEEL_JSON};
+use aphrodite_core::rendering::{ChartSpec, ChartSpecGenerator};
+use aphrodite_core::{Error, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Location {
+    pub lat: f64,
+    pub lon: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Subject {
+    pub id: String,
+    #[serde(rename = "birthDateTime")]
+    pub birth_date_time: Option<String>,
+    pub location: Option<Location>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChartSettings {
+    #[serde(rename = "zodiacType", default = "default_zodiac_type")]
+    pub zodiac_type: String,
+    pub ayanamsa: Option<String>,
+    #[serde(rename = "customAyanamsa")]
+    pub custom_ayanamsa: Option<f64>,
+    #[serde(rename = "houseSystem", default = "default_house_system")]
+    pub house_system: String,
+    /// Orb per aspect type; missing types keep the default orb
+    #[serde(rename = "orbSettings", default)]
+    pub orb_settings: HashMap<String, f64>,
+    /// Empty selects the default objects
+    #[serde(rename = "includeObjects", default)]
+    pub include_objects: Vec<String>,
+    /// Drop aspects whose planets' signs don't match the aspect
+    #[serde(rename = "excludeOutOfSign", default)]
+    pub exclude_out_of_sign: bool,
+    /// Orb per object, averaged over each pair
+    #[serde(rename = "objectOrbs", default)]
+    pub object_orbs: HashMap<String, f64>,
+    #[serde(rename = "nodeType", default)]
+    pub node_type: NodeType,
+    #[serde(rename = "lilithType", default)]
+    pub lilith_type: LilithType,
+    #[serde(rename = "moonPosition", default)]
+    pub moon_position: MoonPosition,
+    #[serde(rename = "polarFallback", default)]
+    pub polar_fallback: PolarFallback,
+}
+
+fn default_zodiac_type() -> String {
+    "tropical".to_string()
+}
+
+fn default_house_system() -> String {
+    "placidus".to_string()
+}
+
+impl Default for ChartSettings {
+    fn default() -> Self {
+        Self {
+            zodiac_type: default_zodiac_type(),
+            ayanamsa: None,
+            custom_ayanamsa: None,
+            house_system: default_house_system(),
+            orb_settings: HashMap::new(),
+            include_objects: Vec::new(),
+            exclude_out_of_sign: false,
+            object_orbs: HashMap::new(),
+            node_type: NodeType::default(),
+            lilith_type: LilithType::default(),
+            moon_position: MoonPosition::default(),
+            polar_fallback: PolarFallback::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct LayerConfig {
+    /// "natal" or "transit"
+    pub kind: String,
+    #[serde(rename = "subjectId")]
+    pub subject_id: Option<String>,
+    #[serde(rename = "explicitDateTime")]
+    pub explicit_date_time: Option<String>,
+    pub location: Option<Location>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RenderRequest {
+    pub subjects: Vec<Subject>,
+    #[serde(default)]
+    pub settings: ChartSettings,
+    pub layer_config: HashMap<String, LayerConfig>,
+}
+
+/// Positions and aspects computed for a request
+#[derive(Debug, Clone, Serialize)]
+pub struct Calculation {
+    pub positions: HashMap<String, LayerPositions>,
+    pub aspects: HashMap<String, AspectSet>,
+}
+
+impl RenderRequest {
+    pub fn from_json(json: &str) -> Result<Self> {
+        serde_json::from_str(json).map_err(|e| Error::InvalidInput(format!("Failed to parse RenderRequest: {}", e)))
+    }
+
+    /// Positions for every layer and the aspects within and between them
+    pub fn calculate(&self) -> Result<Calculation> {
+        let settings = EphemerisSettings {
+            zodiac_type: self.settings.zodiac_type.clone(),
+            ayanamsa: self.settings.ayanamsa.clone(),
+            custom_ayanamsa: self.settings.custom_ayanamsa,
+            house_system: self.settings.house_system.clone(),
+            include_objects: if self.settings.include_objects.is_empty() {
+                DEFAULT_OBJECTS.iter().map(|id| id.to_string()).collect()
+            } else {
+                self.settings.include_objects.clone()
+            },
+            node_type: self.settings.node_type,
+            lilith_type: self.settings.lilith_type,
+            moon_position: self.settings.moon_position,
+            polar_fallback: self.settings.polar_fallback,
+        };
+
+        let ephemeris = AnalyticEphemeris::new();
+        let mut positions = HashMap::new();
+        for (layer_id, config) in &self.layer_config {
+            let (datetime, location) = self.resolve_layer(layer_id, config)?;
+            let layer = ephemeris.calc_positions(datetime, location, &settings)?;
+            positions.insert(layer_id.clone(), layer);
+        }
+
+        let mut orb_settings: HashMap<String, f64> =
+            DEFAULT_ORBS.iter().map(|(name, orb)| (name.to_string(), *orb)).collect();
+        orb_settings.extend(self.settings.orb_settings.clone());
+        let aspect_settings = AspectSettings {
+            orb_settings,
+            include_objects: self.settings.include_objects.clone(),
+            only_major: None,
+            exclude_out_of_sign: self.settings.exclude_out_of_sign,
+            object_orbs: self.settings.object_orbs.clone(),
+            filter: Default::default(),
+        };
+        let aspects = AspectCalculator::new().compute_all_aspect_sets(&positions, &aspect_settings);
+
+        Ok(Calculation { positions, aspects })
+    }
+
+    /// Moment and location of a layer, following the API's rules
+    fn resolve_layer(&self, layer_id: &str, config: &LayerConfig) -> Result<(DateTime<Utc>, Option<GeoLocation>)> {
+        let subject = config
+            .subject_id
+            .as_ref()
+            .map(|id| {
+                self.subjects
+                    .iter()
+                    .find(|s| s.id == *id)
+                    .ok_or_else(|| Error::InvalidInput(format!("Layer '{}': subjectId '{}' not found", layer_id, id)))
+            })
+            .transpose()?;
+
+        let datetime = match config.kind.as_str() {
+            "natal" => {
+                let subject = subject.ok_or_else(|| {
+                    Error::InvalidInput(format!("Layer '{}': natal layer must specify a 'subjectId'", layer_id))
+                })?;
+                let birth = subject.birth_date_time.as_ref().ok_or_else(|| {
+                    Error::InvalidInput(format!("Layer '{}': subject '{}' missing 'birthDateTime'", layer_id, subject.id))
+                })?;
+                parse_datetime(birth)?
+            }
+            "transit" => {
+                let explicit = config
+                    .explicit_date_time
+                    .as_ref()
+                    .ok_or_else(|| {
+                        Error::InvalidInput(format!("Layer '{}': transit layer must specify 'explicitDateTime'", layer_id))
+                    })?;
+                parse_datetime(explicit)?
+            }
+            kind => return Err(Error::InvalidInput(format!("Layer '{}': unsupported layer kind '{}'", layer_id, kind))),
+        };
+
+        let location = config
+            .location
+            .as_ref()
+            .or_else(|| subject.and_then(|s| s.location.as_ref()))
+            .map(|loc| GeoLocation { lat: loc.lat, lon: loc.lon });
+
+        Ok((datetime, location))
+    }
+}
+
+impl Calculation {
+    /// ChartSpec drawn with `wheel_json` or the standard natal wheel
+    pub fn chart_spec(
+        &self,
+        wheel_json: Option<&str>,
+        include_objects: &[String],
+        width: f32,
+        height: f32,
+    ) -> Result<ChartSpec> {
+        let wheel = load_wheel_definition_from_json(wheel_json.unwrap_or(DEFAULT_WHEEL_JSON))?;
+        let include_objects = (!include_objects.is_empty()).then_some(include_objects);
+        let assembled = WheelAssembler::build_wheel(&wheel.wheel, &self.positions, &self.aspects, include_objects);
+        Ok(ChartSpecGenerator::new().generate(&assembled, &self.aspects, width, height))
+    }
+}
+
+fn parse_datetime(value: &str) -> Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|_| value.parse::<DateTime<Utc>>())
+        .map_err(|e| Error::InvalidInput(format!("Failed to parse datetime '{}': {}", value, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REQUEST: &str = r#"{
+        "subjects": [{
+            "id": "person",
+            "label": "Person",
+            "birthDateTime": "1990-01-01T12:00:00Z",
+            "location": { "lat": 40.7128, "lon": -74.0060 }
+        }],
+        "settings": { "zodiacType": "tropical", "houseSystem": "placidus", "orbSettings": { "trine": 5.0 } },
+        "layer_config": {
+            "natal": { "kind": "natal", "subjectId": "person" },
+            "transit": { "kind": "transit", "explicitDateTime": "2024-06-01T00:00:00Z" }
+        }
+    }"#;
+
+    #[test]
+    fn test_calculate_layers_and_aspects() {
+        let calculation = RenderRequest::from_json(REQUEST).unwrap().calculate().unwrap();
+
+        let natal = &calculation.positions["natal"];
+        assert_eq!(natal.planets.len(), DEFAULT_OBJECTS.len());
+        assert!((natal.planets["sun"].lon - 280.81).abs() < 0.05);
+        assert!(natal.houses.is_some());
+        // Transit layers without a location have no houses
+        assert!(calculation.positions["transit"].houses.is_none());
+        assert!(calculation.aspects.contains_key("natal"));
+        assert!(calculation.aspects.values().any(|set| set.kind == "inter_layer"));
+    }
+
+    #[test]
+    fn test_chart_spec_from_request() {
+        let calculation = RenderRequest::from_json(REQUEST).unwrap().calculate().unwrap();
+        let spec = calculation.chart_spec(None, &[], 600.0, 600.0).unwrap();
+        assert_eq!(spec.width, 600.0);
+        assert!(!spec.shapes.is_empty());
+        assert!(calculation.chart_spec(Some("[]"), &[], 600.0, 600.0).is_err());
+    }
+
+    #[test]
+    fn test_invalid_requests() {
+        let request = |layer: &str| RenderRequest::from_json(&REQUEST.replace(r#""kind": "natal", "subjectId": "person""#, layer));
+
+        let error = request(r#""kind": "natal", "subjectId": "nobody""#).unwrap().calculate().unwrap_err();
+        assert!(error.to_string().contains("subjectId 'nobody' not found"));
+        let error = request(r#""kind": "progressed", "subjectId": "person""#).unwrap().calculate().unwrap_err();
+        assert!(error.to_string().contains("unsupported layer kind"));
+        let error = RenderRequest::from_json(&REQUEST.replace("placidus", "topocentric")).unwrap().calculate().unwrap_err();
+        assert_eq!(error.code(), "INVALID_HOUSE_SYSTEM");
+        assert!(RenderRequest::from_json("{}").is_err());
+    }
+}
@@ -0,0 +1,130 @@
+//! Contiguous runs of shapes on the same ring, each cached in its own offscreen canvas so
+//! toggling or updating one ring or layer only redraws the groups it touches.
+
+use aphrodite_core::rendering::ChartShape;
+use std::collections::HashSet;
+use std::ops::Range;
+
+#[derive(Debug, Clone)]
+pub struct ShapeGroup {
+    pub ring: Option<String>,
+    /// Indices into the spec's shapes
+    pub range: Range<usize>,
+    pub layer_ids: HashSet<String>,
+    /// Whether the cached drawing is stale
+    pub dirty: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ShapeGroups {
+    groups: Vec<ShapeGroup>,
+}
+
+impl ShapeGroups {
+    /// Split shapes into runs by ring; runs keep drawing order, so a ring whose shapes are
+    /// interleaved with others' spans several groups
+    pub fn new(shapes: &[ChartShape]) -> Self {
+        let mut groups: Vec<ShapeGroup> = Vec::new();
+        for (index, shape) in shapes.iter().enumerate() {
+            match groups.last_mut() {
+                Some(group) if group.ring == shape.ring => {
+                    group.range.end = index + 1;
+                    group.layer_ids.extend(shape.layer_ids.iter().cloned());
+                }
+                _ => groups.push(ShapeGroup {
+                    ring: shape.ring.clone(),
+                    range: index..index + 1,
+                    layer_ids: shape.layer_ids.iter().cloned().collect(),
+                    dirty: true,
+                }),
+            }
+        }
+        Self { groups }
+    }
+
+    pub fn groups(&self) -> &[ShapeGroup] {
+        &self.groups
+    }
+
+    pub fn groups_mut(&mut self) -> &mut [ShapeGroup] {
+        &mut self.groups
+    }
+
+    pub fn invalidate_all(&mut self) {
+        self.groups.iter_mut().for_each(|group| group.dirty = true);
+    }
+
+    pub fn invalidate_ring(&mut self, slug: &str) {
+        self.groups
+            .iter_mut()
+            .filter(|group| group.ring.as_deref() == Some(slug))
+            .for_each(|group| group.dirty = true);
+    }
+
+    /// Mark the group drawing the shape at `index`
+    pub fn invalidate_shape(&mut self, index: usize) {
+        if let Some(group) = self.groups.iter_mut().find(|group| group.range.contains(&index)) {
+            group.dirty = true;
+        }
+    }
+
+    pub fn invalidate_layer(&mut self, layer_id: &str) {
+        self.groups
+            .iter_mut()
+            .filter(|group| group.layer_ids.contains(layer_id))
+            .for_each(|group| group.dirty = true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::shape;
+
+    fn dirty(groups: &ShapeGroups) -> Vec<bool> {
+        groups.groups().iter().map(|group| group.dirty).collect()
+    }
+
+    #[test]
+    fn test_groups_follow_drawing_order() {
+        let shapes = vec![
+            shape("signs", &[]),
+            shape("signs", &[]),
+            shape("natal", &["natal"]),
+            shape("transit", &["transit"]),
+            shape("aspects", &["natal", "transit"]),
+            shape("signs", &[]),
+        ];
+        let groups = ShapeGroups::new(&shapes);
+        let ranges: Vec<Range<usize>> = groups.groups().iter().map(|group| group.range.clone()).collect();
+        assert_eq!(ranges, vec![0..2, 2..3, 3..4, 4..5, 5..6]);
+        assert!(ShapeGroups::new(&[]).groups().is_empty());
+    }
+
+    #[test]
+    fn test_invalidate_ring_and_layer() {
+        let shapes = vec![
+            shape("signs", &[]),
+            shape("natal", &["natal"]),
+            shape("transit", &["transit"]),
+            shape("aspects", &["natal", "transit"]),
+        ];
+        let mut groups = ShapeGroups::new(&shapes);
+        assert_eq!(dirty(&groups), vec![true; 4]);
+        groups.groups_mut().iter_mut().for_each(|group| group.dirty = false);
+
+        groups.invalidate_layer("transit");
+        assert_eq!(dirty(&groups), vec![false, false, true, true]);
+
+        groups.groups_mut().iter_mut().for_each(|group| group.dirty = false);
+        groups.invalidate_ring("aspects");
+        assert_eq!(dirty(&groups), vec![false, false, false, true]);
+
+        groups.groups_mut().iter_mut().for_each(|group| group.dirty = false);
+        groups.invalidate_shape(1);
+        assert_eq!(dirty(&groups), vec![false, true, false, false]);
+
+        groups.invalidate_all();
+        assert_eq!(dirty(&groups), vec![true; 4]);
+    }
+}
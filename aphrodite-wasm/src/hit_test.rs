@@ -0,0 +1,228 @@
+//! Hit-testing ChartSpec shapes at canvas coordinates.
+//!
+//! Segment angles follow the placement used by `ChartSpecGenerator`: a point at angle `a`
+//! and radius `r` lies at `center + r * (cos(90° - a), sin(90° - a))`.
+
+use aphrodite_core::aspects::AspectObjectRef;
+use crate::visibility::Visibility;
+use aphrodite_core::rendering::{ChartShape, Point, Shape};
+use serde::Serialize;
+
+/// Pixels accepted around a planet glyph beyond half its size
+const GLYPH_SLACK: f32 = 2.0;
+/// Minimum distance in pixels at which a thin aspect line still counts as hit
+const LINE_TOLERANCE: f32 = 4.0;
+
+/// What lies under a canvas coordinate
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Hit {
+    #[serde(rename_all = "camelCase")]
+    Planet { planet_id: String, retrograde: bool },
+    House { house: u8 },
+    #[serde(rename_all = "camelCase")]
+    Sign { sign_index: u8 },
+    #[serde(rename_all = "camelCase")]
+    Aspect {
+        aspect_type: String,
+        from: Option<AspectObjectRef>,
+        to: Option<AspectObjectRef>,
+    },
+}
+
+/// A hit and the id of the shape it landed on; serializes flat as `{"shapeId", "kind", ...}`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShapeHit {
+    pub shape_id: String,
+    #[serde(flatten)]
+    pub hit: Hit,
+}
+
+/// Topmost visible shape under `point`; later shapes are drawn over earlier ones
+pub fn hit_test(shapes: &[ChartShape], visibility: &Visibility, point: Point) -> Option<ShapeHit> {
+    shapes
+        .iter()
+        .rev()
+        .filter(|shape| visibility.is_visible(shape))
+        .find_map(|shape| {
+            hit_shape(&shape.shape, point).map(|hit| ShapeHit {
+                shape_id: shape.id.clone(),
+                hit,
+            })
+        })
+}
+
+fn hit_shape(shape: &Shape, point: Point) -> Option<Hit> {
+    match shape {
+        Shape::PlanetGlyph { center, planet_id, size, retrograde, .. } => {
+            (distance(*center, point) <= size / 2.0 + GLYPH_SLACK).then(|| Hit::Planet {
+                planet_id: planet_id.clone(),
+                retrograde: *retrograde,
+            })
+        }
+        Shape::AspectLine { from, to, aspect_type, width, from_object, to_object, .. } => {
+            (distance_to_segment(point, *from, *to) <= (width / 2.0).max(LINE_TOLERANCE)).then(|| Hit::Aspect {
+                aspect_type: aspect_type.clone(),
+                from: from_object.clone(),
+                to: to_object.clone(),
+            })
+        }
+        Shape::HouseSegment { center, house_num, start_angle, end_angle, radius_inner, radius_outer, .. } => {
+            in_segment(point, *center, *start_angle, *end_angle, *radius_inner, *radius_outer)
+                .then_some(Hit::House { house: *house_num })
+        }
+        Shape::SignSegment { center, sign_index, start_angle, end_angle, radius_inner, radius_outer, .. } => {
+            in_segment(point, *center, *start_angle, *end_angle, *radius_inner, *radius_outer)
+                .then_some(Hit::Sign { sign_index: *sign_index })
+        }
+        _ => None,
+    }
+}
+
+fn distance(a: Point, b: Point) -> f32 {
+    (a.x - b.x).hypot(a.y - b.y)
+}
+
+fn distance_to_segment(point: Point, from: Point, to: Point) -> f32 {
+    let (dx, dy) = (to.x - from.x, to.y - from.y);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return distance(point, from);
+    }
+    let t = (((point.x - from.x) * dx + (point.y - from.y) * dy) / length_sq).clamp(0.0, 1.0);
+    distance(point, Point { x: from.x + t * dx, y: from.y + t * dy })
+}
+
+/// Whether `point` lies in the annular sector spanning the short way from `start_angle` to
+/// `end_angle`
+fn in_segment(point: Point, center: Point, start_angle: f32, end_angle: f32, radius_inner: f32, radius_outer: f32) -> bool {
+    let radius = distance(center, point);
+    if radius < radius_inner || radius > radius_outer {
+        return false;
+    }
+    let angle = (90.0 - (point.y - center.y).atan2(point.x - center.x).to_degrees()).rem_euclid(360.0);
+    let span = (end_angle - start_angle).rem_euclid(360.0);
+    if span <= 180.0 {
+        (angle - start_angle).rem_euclid(360.0) <= span
+    } else {
+        (start_angle - angle).rem_euclid(360.0) <= 360.0 - span
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::RenderRequest;
+
+    fn hit(shapes: &[ChartShape], visibility: &Visibility, point: Point) -> Option<Hit> {
+        hit_test(shapes, visibility, point).map(|shape_hit| shape_hit.hit)
+    }
+
+    fn spec() -> aphrodite_core::rendering::ChartSpec {
+        let request = RenderRequest::from_json(
+            r#"{
+                "subjects": [{
+                    "id": "person",
+                    "label": "Person",
+                    "birthDateTime": "1990-01-01T12:00:00Z",
+                    "location": { "lat": 40.7128, "lon": -74.0060 }
+                }],
+                "settings": { "zodiacType": "tropical", "houseSystem": "placidus" },
+                "layer_config": { "natal": { "kind": "natal", "subjectId": "person" } }
+            }"#,
+        )
+        .unwrap();
+        request.calculate().unwrap().chart_spec(None, &request.settings.include_objects, 800.0, 800.0).unwrap()
+    }
+
+    #[test]
+    fn test_hit_planet_glyphs() {
+        let spec = spec();
+        for shape in &spec.shapes {
+            if let Shape::PlanetGlyph { center, planet_id, .. } = &shape.shape {
+                match hit(&spec.shapes, &Visibility::default(), *center) {
+                    // Glyphs of close planets may overlap; the later one wins
+                    Some(Hit::Planet { planet_id: hit, .. }) if &hit != planet_id => {
+                        let other = spec
+                            .shapes
+                            .iter()
+                            .find_map(|s| match &s.shape {
+                                Shape::PlanetGlyph { planet_id, center, .. } if *planet_id == hit => Some(*center),
+                                _ => None,
+                            })
+                            .unwrap();
+                        assert!(distance(*center, other) <= 16.0);
+                    }
+                    Some(Hit::Planet { .. }) => {}
+                    other => panic!("expected {} at its glyph, got {:?}", planet_id, other),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_hit_segments_and_background() {
+        let spec = spec();
+        let center = spec.center;
+        // Points halfway through the sign ring (0.85-1.0 of a 380px radius) and the house ring
+        let at = |angle: f32, radius: f32| {
+            let math = (90.0 - angle).to_radians();
+            Point { x: center.x + radius * math.cos(), y: center.y + radius * math.sin() }
+        };
+
+        let mut signs = Vec::new();
+        let mut houses = Vec::new();
+        for step in 0..72 {
+            let angle = step as f32 * 5.0 + 2.5;
+            match hit(&spec.shapes, &Visibility::default(), at(angle, 0.925 * 380.0)) {
+                Some(Hit::Sign { sign_index }) => signs.push(sign_index),
+                other => panic!("expected a sign at {}, got {:?}", angle, other),
+            }
+            match hit(&spec.shapes, &Visibility::default(), at(angle, 0.8 * 380.0)) {
+                Some(Hit::House { house }) => houses.push(house),
+                other => panic!("expected a house at {}, got {:?}", angle, other),
+            }
+        }
+        signs.sort();
+        signs.dedup();
+        houses.sort();
+        houses.dedup();
+        assert_eq!(signs, (0..12).collect::<Vec<u8>>());
+        assert_eq!(houses, (1..=12).collect::<Vec<u8>>());
+
+        assert!(hit(&spec.shapes, &Visibility::default(), Point { x: 2.0, y: 2.0 }).is_none());
+    }
+
+    #[test]
+    fn test_hit_aspect_line() {
+        let spec = spec();
+        let (from, to) = spec
+            .shapes
+            .iter()
+            .find_map(|shape| match shape.shape {
+                Shape::AspectLine { from, to, .. } => Some((from, to)),
+                _ => None,
+            })
+            .expect("aspect line");
+        let midpoint = Point { x: (from.x + to.x) / 2.0, y: (from.y + to.y) / 2.0 };
+
+        let shape_hit = hit_test(&spec.shapes, &Visibility::default(), midpoint).expect("hit");
+        assert!(shape_hit.shape_id.starts_with("aspects/natal."), "{}", shape_hit.shape_id);
+        let Hit::Aspect { from, to, .. } = &shape_hit.hit else {
+            panic!("expected an aspect, got {:?}", shape_hit.hit)
+        };
+        assert_eq!(from.as_ref().unwrap().object_type, "planet");
+        assert_eq!(to.as_ref().unwrap().layer_id, "natal");
+
+        let json: serde_json::Value = serde_json::to_value(&shape_hit).unwrap();
+        assert_eq!(json["kind"], "aspect");
+        assert_eq!(json["shapeId"], shape_hit.shape_id.as_str());
+        assert!(json["aspectType"].is_string());
+
+        // Hidden aspect lines are not hit
+        let mut visibility = Visibility::default();
+        visibility.set_ring_visible(aphrodite_core::rendering::ASPECT_LINES_RING, false);
+        assert!(!matches!(hit(&spec.shapes, &visibility, midpoint), Some(Hit::Aspect { .. })));
+    }
+}
@@ -1,7 +1,16 @@
 use wasm_bindgen::prelude::*;
 
+mod animation;
 mod canvas;
+mod compute;
+mod groups;
+mod hit_test;
+mod raster;
 mod renderer;
+#[cfg(test)]
+mod test_support;
+mod transform;
+mod visibility;
 
 pub use renderer::ChartRenderer;
 
@@ -11,6 +20,17 @@ pub fn init() {
     console_error_panic_hook::set_once();
 }
 
+/// Write an ecliptic longitude as `zodiacal` ("15°32′ Taurus", the default), `absolute`
+/// ("45°32′") or `nakshatraPada` ("Rohini pada 2"), the same way chart labels do
+#[wasm_bindgen(js_name = formatLongitude)]
+pub fn format_longitude(lon: f64, format: Option<String>) -> Result<String, JsValue> {
+    let format = match format {
+        Some(format) => format.parse().map_err(|e: String| JsValue::from_str(&e))?,
+        None => aphrodite_core::format::DegreeFormat::default(),
+    };
+    Ok(aphrodite_core::format::format_longitude(lon, format))
+}
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
@@ -0,0 +1,229 @@
+//! Pure-Rust PNG rasterizer for ChartSpec shapes, used where OffscreenCanvas is unavailable.
+//!
+//! There are no fonts to draw with, so text is skipped and planet glyphs are drawn as
+//! discs in the planet's color.
+
+use crate::canvas::aspect_dashes;
+use aphrodite_core::rendering::{Color, Point, Shape, Stroke};
+use tiny_skia::{FillRule, IntSize, Paint, PathBuilder, Pixmap, StrokeDash, Transform};
+
+/// Degrees per straight segment when flattening arcs
+const ARC_STEP: f32 = 1.0;
+
+/// Draw `shapes` into a `width` x `height` PNG, mapping spec coordinates through
+/// `scale` and `offset`
+pub fn render_png<'a>(
+    shapes: impl Iterator<Item = &'a Shape>,
+    background: Color,
+    width: u32,
+    height: u32,
+    scale: f32,
+    offset: Point,
+) -> Result<Vec<u8>, String> {
+    let mut pixmap = Pixmap::new(width, height).ok_or_else(|| format!("Invalid image size {}x{}", width, height))?;
+    pixmap.fill(to_skia(background));
+    let transform = Transform::from_row(scale, 0.0, 0.0, scale, offset.x, offset.y);
+
+    for shape in shapes {
+        draw_shape(&mut pixmap, shape, transform);
+    }
+    pixmap.encode_png().map_err(|e| format!("Failed to encode PNG: {}", e))
+}
+
+/// Encode canvas pixels (`ImageData` RGBA, not premultiplied) as a PNG
+pub fn encode_rgba(width: u32, height: u32, mut rgba: Vec<u8>) -> Result<Vec<u8>, String> {
+    let size = IntSize::from_wh(width, height).ok_or_else(|| format!("Invalid image size {}x{}", width, height))?;
+    // Pixmaps hold premultiplied color
+    for pixel in rgba.chunks_exact_mut(4) {
+        let alpha = pixel[3] as u16;
+        for channel in &mut pixel[..3] {
+            *channel = ((*channel as u16 * alpha + 127) / 255) as u8;
+        }
+    }
+    let pixmap = Pixmap::from_vec(rgba, size).ok_or_else(|| "Pixel data does not match the image size".to_string())?;
+    pixmap.encode_png().map_err(|e| format!("Failed to encode PNG: {}", e))
+}
+
+fn draw_shape(pixmap: &mut Pixmap, shape: &Shape, transform: Transform) {
+    match shape {
+        Shape::Circle { center, radius, fill, stroke } => {
+            let Some(path) = PathBuilder::from_circle(center.x, center.y, *radius) else {
+                return;
+            };
+            if let Some(fill) = fill {
+                pixmap.fill_path(&path, &paint(*fill), FillRule::Winding, transform, None);
+            }
+            if let Some(stroke) = stroke {
+                stroke_path(pixmap, &path, stroke, transform);
+            }
+        }
+        Shape::Arc { center, radius_inner, radius_outer, start_angle, end_angle, fill, stroke } => {
+            // Canvas angles, swept clockwise as the canvas renderer draws them
+            let sweep = (end_angle - start_angle).rem_euclid(360.0);
+            let points = sector(*center, *start_angle, sweep, *radius_inner, *radius_outer);
+            fill_and_stroke(pixmap, &points, *fill, stroke.as_ref(), transform);
+        }
+        Shape::HouseSegment { center, start_angle, end_angle, radius_inner, radius_outer, fill, stroke, .. }
+        | Shape::SignSegment { center, start_angle, end_angle, radius_inner, radius_outer, fill, stroke, .. } => {
+            // Segment angles are the canvas angle 90° - a, swept the short way
+            let (start, end) = (90.0 - start_angle, 90.0 - end_angle);
+            let sweep = (end - start).rem_euclid(360.0);
+            let points = if sweep <= 180.0 {
+                sector(*center, start, sweep, *radius_inner, *radius_outer)
+            } else {
+                sector(*center, end, 360.0 - sweep, *radius_inner, *radius_outer)
+            };
+            fill_and_stroke(pixmap, &points, Some(*fill), stroke.as_ref(), transform);
+        }
+        Shape::Line { from, to, stroke } => {
+            if let Some(path) = polyline(&[*from, *to], false) {
+                stroke_path(pixmap, &path, stroke, transform);
+            }
+        }
+        Shape::AspectLine { from, to, color, width, style, .. } => {
+            let dashes = aspect_dashes(*style);
+            let stroke = Stroke {
+                color: *color,
+                width: *width,
+                dash_array: (!dashes.is_empty()).then(|| dashes.iter().map(|&length| length as f32).collect()),
+            };
+            if let Some(path) = polyline(&[*from, *to], false) {
+                stroke_path(pixmap, &path, &stroke, transform);
+            }
+        }
+        Shape::Path { points, closed, fill, stroke } => {
+            let Some(path) = polyline(points, *closed) else {
+                return;
+            };
+            if let Some(fill) = fill {
+                pixmap.fill_path(&path, &paint(*fill), FillRule::Winding, transform, None);
+            }
+            if let Some(stroke) = stroke {
+                stroke_path(pixmap, &path, stroke, transform);
+            }
+        }
+        Shape::PlanetGlyph { center, size, color, .. } => {
+            if let Some(path) = PathBuilder::from_circle(center.x, center.y, size / 4.0) {
+                pixmap.fill_path(&path, &paint(*color), FillRule::Winding, transform, None);
+            }
+        }
+        Shape::Text { .. } => {}
+    }
+}
+
+/// Outline of an annular sector from canvas angle `start` through `sweep` degrees clockwise
+fn sector(center: Point, start: f32, sweep: f32, radius_inner: f32, radius_outer: f32) -> Vec<Point> {
+    let steps = (sweep / ARC_STEP).ceil().max(1.0) as usize;
+    let at = |angle: f32, radius: f32| {
+        let radians = angle.to_radians();
+        Point {
+            x: center.x + radius * radians.cos(),
+            y: center.y + radius * radians.sin(),
+        }
+    };
+    let angles: Vec<f32> = (0..=steps).map(|step| start + sweep * step as f32 / steps as f32).collect();
+
+    let mut points: Vec<Point> = angles.iter().map(|angle| at(*angle, radius_outer)).collect();
+    points.extend(angles.iter().rev().map(|angle| at(*angle, radius_inner)));
+    points
+}
+
+fn fill_and_stroke(pixmap: &mut Pixmap, points: &[Point], fill: Option<Color>, stroke: Option<&Stroke>, transform: Transform) {
+    let Some(path) = polyline(points, true) else {
+        return;
+    };
+    if let Some(fill) = fill {
+        pixmap.fill_path(&path, &paint(fill), FillRule::Winding, transform, None);
+    }
+    if let Some(stroke) = stroke {
+        stroke_path(pixmap, &path, stroke, transform);
+    }
+}
+
+fn polyline(points: &[Point], closed: bool) -> Option<tiny_skia::Path> {
+    let (first, rest) = points.split_first()?;
+    let mut builder = PathBuilder::new();
+    builder.move_to(first.x, first.y);
+    for point in rest {
+        builder.line_to(point.x, point.y);
+    }
+    if closed {
+        builder.close();
+    }
+    builder.finish()
+}
+
+fn stroke_path(pixmap: &mut Pixmap, path: &tiny_skia::Path, stroke: &Stroke, transform: Transform) {
+    let style = tiny_skia::Stroke {
+        width: stroke.width,
+        dash: stroke
+            .dash_array
+            .as_ref()
+            .and_then(|dashes| StrokeDash::new(dashes.clone(), 0.0)),
+        ..Default::default()
+    };
+    pixmap.stroke_path(path, &paint(stroke.color), &style, transform, None);
+}
+
+fn paint(color: Color) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color(to_skia(color));
+    paint.anti_alias = true;
+    paint
+}
+
+fn to_skia(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(color.r, color.g, color.b, color.a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compute::RenderRequest;
+
+    #[test]
+    fn test_render_chart_png() {
+        let request = RenderRequest::from_json(
+            r#"{
+                "subjects": [{
+                    "id": "person",
+                    "label": "Person",
+                    "birthDateTime": "1990-01-01T12:00:00Z",
+                    "location": { "lat": 40.7128, "lon": -74.0060 }
+                }],
+                "settings": { "zodiacType": "tropical", "houseSystem": "placidus" },
+                "layer_config": { "natal": { "kind": "natal", "subjectId": "person" } }
+            }"#,
+        )
+        .unwrap();
+        let spec = request.calculate().unwrap().chart_spec(None, &[], 400.0, 400.0).unwrap();
+        let shapes = spec.shapes.iter().map(|shape| &shape.shape);
+        let png = render_png(shapes, spec.background_color, 200, 200, 0.5, Point { x: 0.0, y: 0.0 }).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let pixmap = Pixmap::decode_png(&png).unwrap();
+        assert_eq!((pixmap.width(), pixmap.height()), (200, 200));
+        // The corner keeps the background; Aries, just below the right of the sign ring, is filled
+        let corner = pixmap.pixel(0, 0).unwrap();
+        assert_eq!((corner.red(), corner.green(), corner.blue()), (0, 0, 0));
+        let aries = pixmap.pixel(180, 121).unwrap();
+        assert_ne!((aries.red(), aries.green(), aries.blue()), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_invalid_size() {
+        assert!(render_png(std::iter::empty(), Color::BLACK, 0, 10, 1.0, Point { x: 0.0, y: 0.0 }).is_err());
+        assert!(encode_rgba(2, 2, vec![0; 12]).is_err());
+    }
+
+    #[test]
+    fn test_encode_canvas_pixels() {
+        // An opaque red pixel and a half-transparent white one
+        let png = encode_rgba(2, 1, vec![255, 0, 0, 255, 255, 255, 255, 128]).unwrap();
+        let pixmap = Pixmap::decode_png(&png).unwrap();
+        let red = pixmap.pixel(0, 0).unwrap().demultiply();
+        assert_eq!((red.red(), red.green(), red.blue(), red.alpha()), (255, 0, 0, 255));
+        let white = pixmap.pixel(1, 0).unwrap().demultiply();
+        assert_eq!((white.red(), white.green(), white.blue(), white.alpha()), (255, 255, 255, 128));
+    }
+}
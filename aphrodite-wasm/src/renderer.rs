@@ -1,13 +1,34 @@
-use crate::canvas::render_shape;
-use aphrodite_core::rendering::{ChartSpec, Shape};
-use serde_json;
+use crate::animation::Animation;
+use crate::canvas::{aspect_dashes, render_shape, Context2d};
+use crate::compute::{Calculation, RenderRequest};
+use crate::groups::ShapeGroups;
+use crate::hit_test::hit_test;
+use crate::raster::{encode_rgba, render_png};
+use crate::transform::{validate_factor, ViewTransform};
+use crate::visibility::Visibility;
+use aphrodite_core::rendering::{
+    ChartSpec, Color, GlyphConfig, Point, Shape, Stroke, TextAnchor, TextBaseline,
+};
+use std::ops::Range;
 use wasm_bindgen::prelude::*;
-use web_sys::CanvasRenderingContext2d;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, OffscreenCanvas, OffscreenCanvasRenderingContext2d};
 
 /// Chart renderer for WASM
 #[wasm_bindgen]
 pub struct ChartRenderer {
     spec: ChartSpec,
+    /// Positions and aspects when the chart was calculated in the browser
+    calculation: Option<Calculation>,
+    transform: ViewTransform,
+    visibility: Visibility,
+    groups: ShapeGroups,
+    /// Offscreen canvas per shape group, created on first render
+    group_canvases: Vec<OffscreenCanvas>,
+    cache_groups: bool,
+    animation: Option<Animation>,
+    /// Whether `spec` is the animation's target rather than an interpolated frame
+    showing_target: bool,
 }
 
 #[wasm_bindgen]
@@ -17,29 +38,204 @@ impl ChartRenderer {
     pub fn new(spec_json: &str) -> Result<ChartRenderer, JsValue> {
         let spec: ChartSpec = serde_json::from_str(spec_json)
             .map_err(|e| JsValue::from_str(&format!("Failed to parse ChartSpec: {}", e)))?;
-        Ok(ChartRenderer { spec })
+        Ok(ChartRenderer::with_spec(spec, None))
     }
 
-    /// Render the chart to an HTML5 Canvas
+    /// Calculate a chart from a RenderRequest JSON entirely client-side and prepare it for
+    /// drawing, with `wheel_json` or the standard natal wheel
+    #[wasm_bindgen(js_name = fromRequest)]
+    pub fn from_request(
+        request_json: &str,
+        width: f32,
+        height: f32,
+        wheel_json: Option<String>,
+    ) -> Result<ChartRenderer, JsValue> {
+        let request = RenderRequest::from_json(request_json).map_err(core_error)?;
+        let calculation = request.calculate().map_err(core_error)?;
+        let spec = calculation
+            .chart_spec(wheel_json.as_deref(), &request.settings.include_objects, width, height)
+            .map_err(core_error)?;
+        Ok(ChartRenderer::with_spec(spec, Some(calculation)))
+    }
+
+    /// Positions and aspects keyed by layer and aspect set id as JSON, or undefined when the
+    /// renderer was created from a ChartSpec
     #[wasm_bindgen]
-    pub fn render_to_canvas(&self, ctx: &CanvasRenderingContext2d) -> Result<(), JsValue> {
-        // Clear canvas
-        ctx.clear_rect(0.0, 0.0, self.spec.width as f64, self.spec.height as f64);
+    pub fn calculation_json(&self) -> Option<String> {
+        self.calculation
+            .as_ref()
+            .and_then(|calculation| serde_json::to_string(calculation).ok())
+    }
 
-        // Set background color
-        let bg_color = &self.spec.background_color;
-        ctx.set_fill_style(&format!("rgba({}, {}, {}, {})", 
-            bg_color.r, bg_color.g, bg_color.b, bg_color.a as f32 / 255.0));
-        ctx.fill_rect(0.0, 0.0, self.spec.width as f64, self.spec.height as f64);
+    /// The ChartSpec currently shown as JSON
+    #[wasm_bindgen]
+    pub fn spec_json(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.spec).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Zoom and pan: spec coordinates are scaled by `scale` and then shifted by the offset,
+    /// in CSS pixels
+    #[wasm_bindgen]
+    pub fn set_transform(&mut self, scale: f64, offset_x: f64, offset_y: f64) -> Result<(), JsValue> {
+        if !offset_x.is_finite() || !offset_y.is_finite() {
+            return Err(JsValue::from_str("offset must be finite"));
+        }
+        self.transform.scale = validate_factor("scale", scale).map_err(|e| JsValue::from_str(&e))?;
+        self.transform.offset_x = offset_x;
+        self.transform.offset_y = offset_y;
+        self.groups.invalidate_all();
+        Ok(())
+    }
+
+    /// Device pixels per CSS pixel used when drawing (`window.devicePixelRatio`)
+    #[wasm_bindgen]
+    pub fn set_pixel_ratio(&mut self, ratio: f64) -> Result<(), JsValue> {
+        self.transform.pixel_ratio = validate_factor("pixel ratio", ratio).map_err(|e| JsValue::from_str(&e))?;
+        self.groups.invalidate_all();
+        Ok(())
+    }
+
+    /// Size the canvas to the chart for the window's devicePixelRatio: the backing store
+    /// gets one pixel per device pixel while the CSS size stays at the chart size
+    #[wasm_bindgen]
+    pub fn fit_canvas(&mut self, canvas: &HtmlCanvasElement) -> Result<(), JsValue> {
+        if let Some(window) = web_sys::window() {
+            self.set_pixel_ratio(window.device_pixel_ratio())?;
+        }
+        let ratio = self.transform.pixel_ratio;
+        canvas.set_width((self.spec.width as f64 * ratio).round() as u32);
+        canvas.set_height((self.spec.height as f64 * ratio).round() as u32);
+        let style = canvas.style();
+        style.set_property("width", &format!("{}px", self.spec.width))?;
+        style.set_property("height", &format!("{}px", self.spec.height))?;
+        Ok(())
+    }
+
+    /// Size a canvas transferred to a worker (`transferControlToOffscreen`) to the chart.
+    /// Workers have no `window`, so the page passes its `devicePixelRatio` in
+    #[wasm_bindgen]
+    pub fn fit_offscreen_canvas(&mut self, canvas: &OffscreenCanvas, pixel_ratio: f64) -> Result<(), JsValue> {
+        self.set_pixel_ratio(pixel_ratio)?;
+        canvas.set_width((self.spec.width as f64 * pixel_ratio).round() as u32);
+        canvas.set_height((self.spec.height as f64 * pixel_ratio).round() as u32);
+        Ok(())
+    }
+
+    /// Show or hide every shape of a layer (e.g. "transit"), including aspect lines to it
+    #[wasm_bindgen]
+    pub fn set_layer_visible(&mut self, layer_id: &str, visible: bool) {
+        self.visibility.set_layer_visible(layer_id, visible);
+        self.groups.invalidate_layer(layer_id);
+    }
+
+    /// Show or hide a ring by its wheel definition slug; "aspects" toggles aspect lines
+    #[wasm_bindgen]
+    pub fn set_ring_visible(&mut self, slug: &str, visible: bool) {
+        self.visibility.set_ring_visible(slug, visible);
+        self.groups.invalidate_ring(slug);
+    }
 
-        // Render each shape
-        for shape in &self.spec.shapes {
-            render_shape(ctx, shape)?;
+    /// Cache each ring's shapes in an offscreen canvas (the default) so redraws only repaint
+    /// rings whose visibility changed; turn off to save memory on very large canvases
+    #[wasm_bindgen]
+    pub fn set_group_cache(&mut self, enabled: bool) {
+        self.cache_groups = enabled;
+        if !enabled {
+            self.group_canvases.clear();
         }
+        self.groups.invalidate_all();
+    }
+
+    /// Start animating from the chart currently shown to a ChartSpec JSON, e.g. the same wheel
+    /// a day later; drive it with `set_animation_progress`
+    #[wasm_bindgen]
+    pub fn animate_to(&mut self, spec_json: &str) -> Result<(), JsValue> {
+        let target: ChartSpec = serde_json::from_str(spec_json)
+            .map_err(|e| JsValue::from_str(&format!("Failed to parse ChartSpec: {}", e)))?;
+        self.animation = Some(Animation::new(self.spec.clone(), target));
+        self.showing_target = false;
+        Ok(())
+    }
 
+    /// Move planet glyphs and aspect lines to progress `t` (0 = start, 1 = target) of the
+    /// animation; the target chart is shown in full at 1
+    #[wasm_bindgen]
+    pub fn set_animation_progress(&mut self, t: f32) -> Result<(), JsValue> {
+        let animation = self
+            .animation
+            .as_ref()
+            .ok_or_else(|| JsValue::from_str("No animation; call animate_to first"))?;
+        let frame = animation.frame(t);
+        let at_target = t >= 1.0;
+
+        if at_target != self.showing_target {
+            // The target may have different shapes, so regroup them
+            self.groups = ShapeGroups::new(&frame.shapes);
+            self.showing_target = at_target;
+        } else {
+            for &index in animation.moving() {
+                self.groups.invalidate_shape(index);
+            }
+        }
+        self.spec = frame;
         Ok(())
     }
 
+    /// Metadata of the topmost visible shape at a canvas coordinate in CSS pixels (e.g. a pointer
+    /// event's offsetX/offsetY) as JSON, or undefined over empty space: the shape's `shapeId`
+    /// plus `{"kind": "planet", "planetId", "retrograde"}`, `{"kind": "house", "house"}`,
+    /// `{"kind": "sign", "signIndex"}` or `{"kind": "aspect", "aspectType", "from", "to"}`
+    #[wasm_bindgen]
+    pub fn hit_test(&self, x: f64, y: f64) -> Option<String> {
+        hit_test(&self.spec.shapes, &self.visibility, self.transform.spec_point(x, y)).and_then(|hit| serde_json::to_string(&hit).ok())
+    }
+
+    /// Render the chart to a 2D context: a page canvas's `CanvasRenderingContext2D`, or an
+    /// `OffscreenCanvasRenderingContext2D` when drawing from a worker
+    #[wasm_bindgen]
+    pub fn render_to_canvas(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "CanvasRenderingContext2D | OffscreenCanvasRenderingContext2D")]
+        ctx: &JsValue,
+    ) -> Result<(), JsValue> {
+        if let Some(ctx) = ctx.dyn_ref::<CanvasRenderingContext2d>() {
+            self.render_cached(ctx)
+        } else if let Some(ctx) = ctx.dyn_ref::<OffscreenCanvasRenderingContext2d>() {
+            self.render_cached(ctx)
+        } else {
+            Err(JsValue::from_str(
+                "Expected a CanvasRenderingContext2D or OffscreenCanvasRenderingContext2D",
+            ))
+        }
+    }
+
+    /// Encode the visible chart as a `width` x `height` PNG, scaled to fit and centered,
+    /// for downloads. Draws through an OffscreenCanvas, in pages and workers alike; where
+    /// OffscreenCanvas is unavailable it falls back to a built-in rasterizer that cannot
+    /// draw text, so glyphs appear as colored discs.
+    #[wasm_bindgen]
+    pub fn to_png_bytes(&self, width: u32, height: u32) -> Result<Vec<u8>, JsValue> {
+        let (scale, offset) = self.export_fit(width, height)?;
+        let Ok(canvas) = OffscreenCanvas::new(width, height) else {
+            return render_png(self.visible_shapes(), self.spec.background_color, width, height, scale, offset)
+                .map_err(|e| JsValue::from_str(&e));
+        };
+
+        let ctx = self.draw_export(&canvas, scale, offset)?;
+        // getImageData is synchronous, unlike convertToBlob
+        let image = ctx.get_image_data(0.0, 0.0, width as f64, height as f64)?;
+        encode_rgba(width, height, image.data().0).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Like `to_png_bytes`, but resolves to a PNG `Blob` encoded by the browser
+    #[wasm_bindgen]
+    pub fn to_png_blob(&self, width: u32, height: u32) -> Result<js_sys::Promise, JsValue> {
+        let (scale, offset) = self.export_fit(width, height)?;
+        let canvas = OffscreenCanvas::new(width, height)?;
+        self.draw_export(&canvas, scale, offset)?;
+        canvas.convert_to_blob()
+    }
+
     /// Convert ChartSpec to SVG string
     #[wasm_bindgen]
     pub fn to_svg(&self) -> String {
@@ -55,8 +251,8 @@ impl ChartRenderer {
             bg.r, bg.g, bg.b, bg.a as f32 / 255.0
         ));
 
-        // Render shapes as SVG elements
-        for shape in &self.spec.shapes {
+        // Render visible shapes as SVG elements
+        for shape in self.visible_shapes() {
             svg.push_str(&shape_to_svg(shape));
         }
 
@@ -65,6 +261,151 @@ impl ChartRenderer {
     }
 }
 
+impl ChartRenderer {
+    fn with_spec(spec: ChartSpec, calculation: Option<Calculation>) -> Self {
+        let groups = ShapeGroups::new(&spec.shapes);
+        Self {
+            spec,
+            calculation,
+            transform: ViewTransform::default(),
+            visibility: Visibility::default(),
+            groups,
+            group_canvases: Vec::new(),
+            cache_groups: true,
+            animation: None,
+            showing_target: false,
+        }
+    }
+
+    /// Scale and offset that fit the chart, centered, into a `width` x `height` export
+    fn export_fit(&self, width: u32, height: u32) -> Result<(f32, Point), JsValue> {
+        if width == 0 || height == 0 {
+            return Err(JsValue::from_str("width and height must be positive"));
+        }
+        let scale = (width as f32 / self.spec.width).min(height as f32 / self.spec.height);
+        let offset = Point {
+            x: (width as f32 - self.spec.width * scale) / 2.0,
+            y: (height as f32 - self.spec.height * scale) / 2.0,
+        };
+        Ok((scale, offset))
+    }
+
+    /// Draw the background and visible shapes into an export canvas
+    fn draw_export(
+        &self,
+        canvas: &OffscreenCanvas,
+        scale: f32,
+        offset: Point,
+    ) -> Result<OffscreenCanvasRenderingContext2d, JsValue> {
+        let ctx = context_2d(canvas)?;
+        let bg = &self.spec.background_color;
+        ctx.set_fill_style_str(&format!("rgba({}, {}, {}, {})", bg.r, bg.g, bg.b, bg.a as f32 / 255.0));
+        ctx.fill_rect(0.0, 0.0, canvas.width() as f64, canvas.height() as f64);
+        ctx.set_transform(scale as f64, 0.0, 0.0, scale as f64, offset.x as f64, offset.y as f64)?;
+        for shape in self.visible_shapes() {
+            render_shape(&ctx, shape)?;
+        }
+        Ok(ctx)
+    }
+
+    fn visible_shapes(&self) -> impl Iterator<Item = &Shape> {
+        self.spec
+            .shapes
+            .iter()
+            .filter(|shape| self.visibility.is_visible(shape))
+            .map(|shape| &shape.shape)
+    }
+
+    /// Redraw dirty shape groups into their cached canvases and composite them onto `ctx`
+    fn render_cached<C: Context2d>(&mut self, ctx: &C) -> Result<(), JsValue> {
+        let (width, height) = self.clear(ctx)?;
+
+        // Without a canvas to size the groups from, draw directly
+        if !self.cache_groups || ctx.canvas_size().is_none() {
+            return self.draw_shapes(ctx, 0..self.spec.shapes.len());
+        }
+
+        self.prepare_group_canvases(width, height)?;
+        for (index, group_canvas) in self.group_canvases.iter().enumerate() {
+            let group = &self.groups.groups()[index];
+            if group.dirty {
+                let group_ctx = context_2d(group_canvas)?;
+                group_ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)?;
+                group_ctx.clear_rect(0.0, 0.0, width as f64, height as f64);
+                self.draw_shapes(&group_ctx, group.range.clone())?;
+            }
+        }
+        self.groups.groups_mut().iter_mut().for_each(|group| group.dirty = false);
+
+        for group_canvas in &self.group_canvases {
+            ctx.draw_canvas(group_canvas)?;
+        }
+        Ok(())
+    }
+
+    /// Clear and fill the whole backing store, returning its size
+    fn clear<C: Context2d>(&self, ctx: &C) -> Result<(u32, u32), JsValue> {
+        ctx.set_transform(1.0, 0.0, 0.0, 1.0, 0.0, 0.0)?;
+        let (width, height) = ctx
+            .canvas_size()
+            .unwrap_or((self.spec.width.round() as u32, self.spec.height.round() as u32));
+        ctx.clear_rect(0.0, 0.0, width as f64, height as f64);
+
+        let bg_color = &self.spec.background_color;
+        ctx.set_fill_style_str(&format!("rgba({}, {}, {}, {})", 
+            bg_color.r, bg_color.g, bg_color.b, bg_color.a as f32 / 255.0));
+        ctx.fill_rect(0.0, 0.0, width as f64, height as f64);
+        Ok((width, height))
+    }
+
+    /// Draw the visible shapes in `range` through the view transform
+    fn draw_shapes<C: Context2d>(&self, ctx: &C, range: Range<usize>) -> Result<(), JsValue> {
+        let [a, b, c, d, e, f] = self.transform.canvas_matrix();
+        ctx.set_transform(a, b, c, d, e, f)?;
+        for shape in &self.spec.shapes[range] {
+            if self.visibility.is_visible(shape) {
+                render_shape(ctx, &shape.shape)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Create one offscreen canvas per group, matching the target canvas size
+    fn prepare_group_canvases(&mut self, width: u32, height: u32) -> Result<(), JsValue> {
+        let resized = self
+            .group_canvases
+            .first()
+            .is_some_and(|canvas| canvas.width() != width || canvas.height() != height);
+        if self.group_canvases.len() == self.groups.groups().len() && !resized {
+            return Ok(());
+        }
+
+        // OffscreenCanvas needs no document, so workers cache groups too
+        self.group_canvases.clear();
+        for _ in self.groups.groups() {
+            self.group_canvases.push(OffscreenCanvas::new(width, height)?);
+        }
+        self.groups.invalidate_all();
+        Ok(())
+    }
+}
+
+fn context_2d(canvas: &OffscreenCanvas) -> Result<OffscreenCanvasRenderingContext2d, JsValue> {
+    canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("Canvas has no 2D context"))?
+        .dyn_into()
+        .map_err(JsValue::from)
+}
+
+/// A JS `Error` carrying the engine's error code as `code`, e.g. `INVALID_HOUSE_SYSTEM`
+fn core_error(error: aphrodite_core::Error) -> JsValue {
+    let js_error = js_sys::Error::new(&error.to_string());
+    // Setting a property on a fresh Error object cannot fail
+    let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str("code"), &JsValue::from_str(error.code()));
+    js_error.into()
+}
+
 /// Convert a shape to SVG string
 fn shape_to_svg(shape: &Shape) -> String {
     match shape {
@@ -72,7 +413,7 @@ fn shape_to_svg(shape: &Shape) -> String {
             let fill_attr = fill.map(|c| format!("fill=\"rgba({}, {}, {}, {})\"", 
                 c.r, c.g, c.b, c.a as f32 / 255.0)).unwrap_or_else(|| "fill=\"none\"".to_string());
             let stroke_attr = stroke.as_ref().map(|s| format!("stroke=\"rgba({}, {}, {}, {})\" stroke-width=\"{}\"", 
-                s.color.r, s.color.g, s.color.b, s.color.a as f32 / 255.0, s.width)).unwrap_or_else(|| String::new());
+                s.color.r, s.color.g, s.color.b, s.color.a as f32 / 255.0, s.width)).unwrap_or_default();
             format!(r#"<circle cx="{}" cy="{}" r="{}" {} {} />"#, 
                 center.x, center.y, radius, fill_attr, stroke_attr)
         }
@@ -93,7 +434,7 @@ fn shape_to_svg(shape: &Shape) -> String {
             let fill_attr = fill.map(|c| format!("fill=\"rgba({}, {}, {}, {})\"", 
                 c.r, c.g, c.b, c.a as f32 / 255.0)).unwrap_or_else(|| "fill=\"none\"".to_string());
             let stroke_attr = stroke.as_ref().map(|s| format!("stroke=\"rgba({}, {}, {}, {})\" stroke-width=\"{}\"", 
-                s.color.r, s.color.g, s.color.b, s.color.a as f32 / 255.0, s.width)).unwrap_or_else(|| String::new());
+                s.color.r, s.color.g, s.color.b, s.color.a as f32 / 255.0, s.width)).unwrap_or_default();
             
             format!(r#"<path d="M {} {} A {} {} 0 {} 1 {} {} L {} {} A {} {} 0 {} 0 {} {} Z" {} {} />"#,
                 x1, y1, radius_outer, radius_outer, large_arc, x2, y2,
@@ -106,13 +447,100 @@ fn shape_to_svg(shape: &Shape) -> String {
                 stroke.color.r, stroke.color.g, stroke.color.b, stroke.color.a as f32 / 255.0,
                 stroke.width)
         }
-        Shape::Text { position, content, size, color, .. } => {
-            format!(r#"<text x="{}" y="{}" font-size="{}" fill="rgba({}, {}, {}, {})">{}</text>"#,
+        Shape::Text { position, content, size, color, anchor, baseline, rotation } => {
+            let text_anchor = match anchor {
+                TextAnchor::Start => "start",
+                TextAnchor::Middle => "middle",
+                TextAnchor::End => "end",
+            };
+            let dominant_baseline = match baseline {
+                TextBaseline::Top => "text-before-edge",
+                TextBaseline::Middle => "central",
+                TextBaseline::Alphabetic => "alphabetic",
+                TextBaseline::Bottom => "text-after-edge",
+            };
+            let transform = rotation
+                .map(|degrees| format!(r#" transform="rotate({} {} {})""#, degrees, position.x, position.y))
+                .unwrap_or_default();
+            format!(r#"<text x="{}" y="{}" font-size="{}" fill="rgba({}, {}, {}, {})" text-anchor="{}" dominant-baseline="{}"{}>{}</text>"#,
                 position.x, position.y, size,
                 color.r, color.g, color.b, color.a as f32 / 255.0,
-                content)
+                text_anchor, dominant_baseline, transform,
+                escape_xml(content))
+        }
+        Shape::Path { points, closed, fill, stroke } => {
+            let Some((first, rest)) = points.split_first() else {
+                return String::new();
+            };
+            let mut d = format!("M {} {}", first.x, first.y);
+            for point in rest {
+                d.push_str(&format!(" L {} {}", point.x, point.y));
+            }
+            if *closed {
+                d.push_str(" Z");
+            }
+            format!(r#"<path d="{}" fill="{}"{} />"#, d, svg_color(*fill), svg_stroke(stroke.as_ref()))
+        }
+        Shape::PlanetGlyph { center, planet_id, size, color, .. } => {
+            let glyph = GlyphConfig::default().planet_glyphs.get(planet_id).cloned().unwrap_or_else(|| planet_id.clone());
+            format!(r#"<text x="{}" y="{}" font-size="{}" fill="{}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                center.x, center.y, size, svg_color(Some(*color)), escape_xml(&glyph))
+        }
+        Shape::AspectLine { from, to, color, width, style, .. } => {
+            let dashes = aspect_dashes(*style);
+            let dash_array = if dashes.is_empty() {
+                String::new()
+            } else {
+                let dashes: Vec<String> = dashes.iter().map(f64::to_string).collect();
+                format!(r#" stroke-dasharray="{}""#, dashes.join(" "))
+            };
+            format!(r#"<line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"{} />"#,
+                from.x, from.y, to.x, to.y, svg_color(Some(*color)), width, dash_array)
+        }
+        Shape::HouseSegment { center, start_angle, end_angle, radius_inner, radius_outer, fill, stroke, .. }
+        | Shape::SignSegment { center, start_angle, end_angle, radius_inner, radius_outer, fill, stroke, .. } => {
+            // Segment angles are the canvas angle 90° - a, swept the short way
+            let (start, end) = (90.0 - start_angle, 90.0 - end_angle);
+            let sweep = (end - start).rem_euclid(360.0);
+            let (start, sweep) = if sweep <= 180.0 { (start, sweep) } else { (end, 360.0 - sweep) };
+            let at = |angle: f32, radius: f32| {
+                let radians = angle.to_radians();
+                (center.x + radius * radians.cos(), center.y + radius * radians.sin())
+            };
+            let (x1, y1) = at(start, *radius_outer);
+            let (x2, y2) = at(start + sweep, *radius_outer);
+            let (x3, y3) = at(start + sweep, *radius_inner);
+            let (x4, y4) = at(start, *radius_inner);
+            format!(r#"<path d="M {} {} A {} {} 0 0 1 {} {} L {} {} A {} {} 0 0 0 {} {} Z" fill="{}"{} />"#,
+                x1, y1, radius_outer, radius_outer, x2, y2,
+                x3, y3, radius_inner, radius_inner, x4, y4,
+                svg_color(Some(*fill)), svg_stroke(stroke.as_ref()))
         }
-        _ => String::new(), // Placeholder for other shapes
     }
 }
 
+fn svg_color(color: Option<Color>) -> String {
+    match color {
+        Some(c) => format!("rgba({}, {}, {}, {})", c.r, c.g, c.b, c.a as f32 / 255.0),
+        None => "none".to_string(),
+    }
+}
+
+fn svg_stroke(stroke: Option<&Stroke>) -> String {
+    let Some(stroke) = stroke else {
+        return String::new();
+    };
+    let dash_array = stroke
+        .dash_array
+        .as_ref()
+        .map(|dashes| {
+            let dashes: Vec<String> = dashes.iter().map(f32::to_string).collect();
+            format!(r#" stroke-dasharray="{}""#, dashes.join(" "))
+        })
+        .unwrap_or_default();
+    format!(r#" stroke="{}" stroke-width="{}"{}"#, svg_color(Some(stroke.color)), stroke.width, dash_array)
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -0,0 +1,25 @@
+//! Fixtures shared by the renderer's unit tests.
+
+use aphrodite_core::rendering::{ChartShape, Point, Shape};
+
+/// `shape` drawn in `ring` for the layers in `layer_ids`
+pub(crate) fn tagged(shape: Shape, ring: &str, layer_ids: &[&str]) -> ChartShape {
+    ChartShape {
+        shape,
+        id: String::new(),
+        meta: Default::default(),
+        ring: Some(ring.to_string()),
+        layer_ids: layer_ids.iter().map(|id| id.to_string()).collect(),
+    }
+}
+
+/// A unit circle tagged with `ring` and `layer_ids`
+pub(crate) fn shape(ring: &str, layer_ids: &[&str]) -> ChartShape {
+    let circle = Shape::Circle {
+        center: Point { x: 0.0, y: 0.0 },
+        radius: 1.0,
+        fill: None,
+        stroke: None,
+    };
+    tagged(circle, ring, layer_ids)
+}
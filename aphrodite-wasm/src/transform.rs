@@ -0,0 +1,96 @@
+//! Viewport transform between ChartSpec coordinates and the canvas.
+//!
+//! The view scale and offset are in CSS pixels; the device pixel ratio only affects the
+//! canvas backing store, so pointer coordinates (`offsetX`/`offsetY`) map back to the spec
+//! without it.
+
+use aphrodite_core::rendering::Point;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewTransform {
+    pub scale: f64,
+    pub offset_x: f64,
+    pub offset_y: f64,
+    /// Device pixels per CSS pixel
+    pub pixel_ratio: f64,
+}
+
+impl Default for ViewTransform {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            offset_x: 0.0,
+            offset_y: 0.0,
+            pixel_ratio: 1.0,
+        }
+    }
+}
+
+impl ViewTransform {
+    /// Canvas `setTransform(a, b, c, d, e, f)` arguments from spec to device pixels
+    pub fn canvas_matrix(&self) -> [f64; 6] {
+        let ratio = self.pixel_ratio;
+        [
+            self.scale * ratio,
+            0.0,
+            0.0,
+            self.scale * ratio,
+            self.offset_x * ratio,
+            self.offset_y * ratio,
+        ]
+    }
+
+    /// Spec coordinates under a point given in CSS pixels from the canvas origin
+    pub fn spec_point(&self, x: f64, y: f64) -> Point {
+        Point {
+            x: ((x - self.offset_x) / self.scale) as f32,
+            y: ((y - self.offset_y) / self.scale) as f32,
+        }
+    }
+}
+
+/// Reject ratios that would collapse or flip the canvas
+pub fn validate_factor(name: &str, value: f64) -> Result<f64, String> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("{} must be a positive number, got {}", name, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canvas_matrix_includes_pixel_ratio() {
+        let transform = ViewTransform {
+            scale: 2.0,
+            offset_x: -100.0,
+            offset_y: 50.0,
+            pixel_ratio: 1.5,
+        };
+        assert_eq!(transform.canvas_matrix(), [3.0, 0.0, 0.0, 3.0, -150.0, 75.0]);
+        assert_eq!(ViewTransform::default().canvas_matrix(), [1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_spec_point_inverts_view() {
+        let transform = ViewTransform {
+            scale: 2.0,
+            offset_x: -100.0,
+            offset_y: 50.0,
+            pixel_ratio: 3.0,
+        };
+        // Spec (400, 400) is drawn at CSS (700, 850) regardless of the pixel ratio
+        let point = transform.spec_point(700.0, 850.0);
+        assert_eq!((point.x, point.y), (400.0, 400.0));
+    }
+
+    #[test]
+    fn test_validate_factor() {
+        assert_eq!(validate_factor("scale", 2.0), Ok(2.0));
+        assert!(validate_factor("scale", 0.0).is_err());
+        assert!(validate_factor("scale", f64::NAN).is_err());
+    }
+}
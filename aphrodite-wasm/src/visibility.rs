@@ -0,0 +1,63 @@
+//! Layers and rings hidden in the renderer.
+
+use aphrodite_core::rendering::ChartShape;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Default)]
+pub struct Visibility {
+    hidden_layers: HashSet<String>,
+    hidden_rings: HashSet<String>,
+}
+
+impl Visibility {
+    pub fn set_layer_visible(&mut self, layer_id: &str, visible: bool) {
+        set_visible(&mut self.hidden_layers, layer_id, visible);
+    }
+
+    pub fn set_ring_visible(&mut self, slug: &str, visible: bool) {
+        set_visible(&mut self.hidden_rings, slug, visible);
+    }
+
+    /// A shape is drawn unless its ring or any of its layers is hidden
+    pub fn is_visible(&self, shape: &ChartShape) -> bool {
+        let ring_hidden = shape.ring.as_ref().is_some_and(|ring| self.hidden_rings.contains(ring));
+        !ring_hidden && !shape.layer_ids.iter().any(|layer| self.hidden_layers.contains(layer))
+    }
+}
+
+fn set_visible(hidden: &mut HashSet<String>, id: &str, visible: bool) {
+    if visible {
+        hidden.remove(id);
+    } else {
+        hidden.insert(id.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::shape;
+
+    #[test]
+    fn test_hidden_layer_hides_inter_layer_aspects() {
+        let mut visibility = Visibility::default();
+        let natal = shape("ring_natal", &["natal"]);
+        let aspect = shape("aspects", &["natal", "transit"]);
+        assert!(visibility.is_visible(&natal) && visibility.is_visible(&aspect));
+
+        visibility.set_layer_visible("transit", false);
+        assert!(visibility.is_visible(&natal));
+        assert!(!visibility.is_visible(&aspect));
+
+        visibility.set_layer_visible("transit", true);
+        assert!(visibility.is_visible(&aspect));
+    }
+
+    #[test]
+    fn test_hidden_ring() {
+        let mut visibility = Visibility::default();
+        visibility.set_ring_visible("aspects", false);
+        assert!(!visibility.is_visible(&shape("aspects", &["natal"])));
+        assert!(visibility.is_visible(&shape("ring_signs", &[])));
+    }
+}
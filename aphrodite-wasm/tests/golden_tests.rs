@@ -0,0 +1,222 @@
+//! Golden ChartSpec and SVG output for a few canonical charts.
+//!
+//! Each chart is calculated with the analytic ephemeris, so the fixtures in `tests/golden`
+//! only change when the calculation or the shape model does. Numbers are compared with a
+//! small tolerance to ride out floating point differences between platforms; everything
+//! else must match exactly. After an intended change, regenerate the fixtures with
+//! `UPDATE_GOLDEN=1 cargo test -p aphrodite-wasm --test golden_tests` and review the diff.
+
+use aphrodite_core::ephemeris::LayerPositions;
+use aphrodite_core::rendering::{DialChartGenerator, DialSize};
+use aphrodite_wasm::ChartRenderer;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const TOLERANCE: f64 = 1e-3;
+
+const NATAL_REQUEST: &str = r#"{
+    "subjects": [{
+        "id": "person",
+        "birthDateTime": "1990-01-01T12:00:00Z",
+        "location": { "lat": 40.7128, "lon": -74.0060 }
+    }],
+    "settings": { "zodiacType": "tropical", "houseSystem": "placidus" },
+    "layer_config": {
+        "natal": { "kind": "natal", "subjectId": "person" }
+    }
+}"#;
+
+const BIWHEEL_REQUEST: &str = r#"{
+    "subjects": [{
+        "id": "person",
+        "birthDateTime": "1990-01-01T12:00:00Z",
+        "location": { "lat": 40.7128, "lon": -74.0060 }
+    }],
+    "settings": { "zodiacType": "tropical", "houseSystem": "whole_sign" },
+    "layer_config": {
+        "natal": { "kind": "natal", "subjectId": "person" },
+        "transit": { "kind": "transit", "explicitDateTime": "2024-06-01T00:00:00Z" }
+    }
+}"#;
+
+/// Natal planets inside, transits on an outer ring
+const BIWHEEL_WHEEL: &str = r#"{
+    "name": "Natal and Transits",
+    "rings": [
+        { "slug": "ring_transits", "type": "planets", "label": "Transits", "orderIndex": 0,
+          "radiusInner": 0.88, "radiusOuter": 1.0,
+          "dataSource": { "kind": "layer_planets", "layerId": "transit" } },
+        { "slug": "ring_signs", "type": "signs", "label": "Zodiac Signs", "orderIndex": 1,
+          "radiusInner": 0.76, "radiusOuter": 0.88,
+          "dataSource": { "kind": "static_zodiac" } },
+        { "slug": "ring_houses", "type": "houses", "label": "Houses", "orderIndex": 2,
+          "radiusInner": 0.68, "radiusOuter": 0.76,
+          "dataSource": { "kind": "layer_houses", "layerId": "natal" } },
+        { "slug": "ring_planets", "type": "planets", "label": "Natal Planets", "orderIndex": 3,
+          "radiusInner": 0.5, "radiusOuter": 0.68,
+          "dataSource": { "kind": "layer_planets", "layerId": "natal" } }
+    ]
+}"#;
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden").join(name)
+}
+
+/// Compare `actual` with the fixture `name`, or rewrite the fixture when UPDATE_GOLDEN is set
+fn check_golden(name: &str, actual: &str, compare: fn(&str, &str) -> Result<(), String>) {
+    let path = golden_path(name);
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, actual).unwrap();
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Missing fixture {} ({}); run with UPDATE_GOLDEN=1", path.display(), e));
+    if let Err(difference) = compare(&expected, actual) {
+        panic!("{} differs from the fixture: {}\nRun with UPDATE_GOLDEN=1 if the change is intended", name, difference);
+    }
+}
+
+fn numbers_match(expected: f64, actual: f64) -> bool {
+    (expected - actual).abs() <= TOLERANCE * expected.abs().max(1.0)
+}
+
+fn compare_json(expected: &str, actual: &str) -> Result<(), String> {
+    let expected: Value = serde_json::from_str(expected).map_err(|e| format!("fixture is not JSON: {}", e))?;
+    let actual: Value = serde_json::from_str(actual).map_err(|e| format!("output is not JSON: {}", e))?;
+    compare_values("$", &expected, &actual)
+}
+
+fn compare_values(path: &str, expected: &Value, actual: &Value) -> Result<(), String> {
+    match (expected, actual) {
+        (Value::Number(e), Value::Number(a)) => {
+            if numbers_match(e.as_f64().unwrap(), a.as_f64().unwrap()) {
+                Ok(())
+            } else {
+                Err(format!("{}: expected {}, got {}", path, e, a))
+            }
+        }
+        (Value::Array(e), Value::Array(a)) => {
+            if e.len() != a.len() {
+                return Err(format!("{}: expected {} items, got {}", path, e.len(), a.len()));
+            }
+            e.iter()
+                .zip(a)
+                .enumerate()
+                .try_for_each(|(i, (e, a))| compare_values(&format!("{}[{}]", path, i), e, a))
+        }
+        (Value::Object(e), Value::Object(a)) => {
+            if let Some(key) = e.keys().find(|key| !a.contains_key(*key)) {
+                return Err(format!("{}.{}: missing from output", path, key));
+            }
+            if let Some(key) = a.keys().find(|key| !e.contains_key(*key)) {
+                return Err(format!("{}.{}: not in fixture", path, key));
+            }
+            e.iter().try_for_each(|(key, e)| compare_values(&format!("{}.{}", path, key), e, &a[key]))
+        }
+        _ if expected == actual => Ok(()),
+        _ => Err(format!("{}: expected {}, got {}", path, expected, actual)),
+    }
+}
+
+/// Text and numbers of an SVG fragment, in order
+fn svg_tokens(element: &str) -> Vec<Result<f64, String>> {
+    let mut tokens = Vec::new();
+    let mut text = String::new();
+    let mut chars = element.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let starts_number = c.is_ascii_digit()
+            || (c == '-' && chars.peek().is_some_and(|(_, next)| next.is_ascii_digit() || *next == '.'));
+        if !starts_number {
+            text.push(c);
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        while let Some((i, next)) = chars.peek().copied() {
+            if !(next.is_ascii_digit() || next == '.') {
+                break;
+            }
+            end = i + 1;
+            chars.next();
+        }
+        if !text.is_empty() {
+            tokens.push(Err(std::mem::take(&mut text)));
+        }
+        tokens.push(element[start..end].parse().map_err(|_| element[start..end].to_string()));
+    }
+    if !text.is_empty() {
+        tokens.push(Err(text));
+    }
+    tokens
+}
+
+fn compare_svg(expected: &str, actual: &str) -> Result<(), String> {
+    let expected: Vec<&str> = expected.split_inclusive('>').collect();
+    let actual: Vec<&str> = actual.split_inclusive('>').collect();
+    for (i, (e, a)) in expected.iter().zip(&actual).enumerate() {
+        let (e_tokens, a_tokens) = (svg_tokens(e), svg_tokens(a));
+        let same = e_tokens.len() == a_tokens.len()
+            && e_tokens.iter().zip(&a_tokens).all(|pair| match pair {
+                (Ok(e), Ok(a)) => numbers_match(*e, *a),
+                (e, a) => e == a,
+            });
+        if !same {
+            return Err(format!("element {}:\n  expected {}\n  got      {}", i, e, a));
+        }
+    }
+    if expected.len() != actual.len() {
+        return Err(format!("expected {} elements, got {}", expected.len(), actual.len()));
+    }
+    Ok(())
+}
+
+fn check_renderer(name: &str, renderer: &ChartRenderer) {
+    let spec: Value = serde_json::from_str(&renderer.spec_json().unwrap()).unwrap();
+    check_golden(&format!("{}.json", name), &serde_json::to_string_pretty(&spec).unwrap(), compare_json);
+    check_golden(&format!("{}.svg", name), &renderer.to_svg(), compare_svg);
+}
+
+#[test]
+fn test_natal_chart_golden() {
+    let renderer = ChartRenderer::from_request(NATAL_REQUEST, 800.0, 800.0, None).unwrap();
+    check_renderer("natal", &renderer);
+}
+
+#[test]
+fn test_biwheel_chart_golden() {
+    let renderer =
+        ChartRenderer::from_request(BIWHEEL_REQUEST, 800.0, 800.0, Some(BIWHEEL_WHEEL.to_string())).unwrap();
+    check_renderer("biwheel", &renderer);
+}
+
+#[test]
+fn test_dial_chart_golden() {
+    let calculation: Value = serde_json::from_str(
+        &ChartRenderer::from_request(NATAL_REQUEST, 800.0, 800.0, None)
+            .unwrap()
+            .calculation_json()
+            .unwrap(),
+    )
+    .unwrap();
+    let positions: HashMap<String, LayerPositions> =
+        serde_json::from_value(calculation["positions"].clone()).unwrap();
+    let spec = DialChartGenerator::new(DialSize::Ninety)
+        .with_pointer(positions["natal"].planets["sun"].lon)
+        .generate("natal", &positions["natal"], None, 600.0, 600.0);
+    let renderer = ChartRenderer::new(&serde_json::to_string(&spec).unwrap()).unwrap();
+    check_renderer("dial", &renderer);
+}
+
+#[test]
+fn test_comparison_tolerates_rounding_only() {
+    assert!(compare_json(r#"{"a": [1.0, "x"]}"#, r#"{"a": [1.0000001, "x"]}"#).is_ok());
+    let error = compare_json(r#"{"a": [1.0, "x"]}"#, r#"{"a": [1.5, "x"]}"#).unwrap_err();
+    assert!(error.starts_with("$.a[0]"), "{}", error);
+    assert!(compare_json(r#"{"a": 1}"#, r#"{"a": 1, "b": 2}"#).is_err());
+
+    assert!(compare_svg(r#"<line x1="10.5" y1="-3"/>"#, r#"<line x1="10.500001" y1="-3.0000002"/>"#).is_ok());
+    assert!(compare_svg(r#"<line x1="10.5"/>"#, r#"<line x1="12.5"/>"#).is_err());
+    assert!(compare_svg(r#"<text>♂</text>"#, r#"<text>♀</text>"#).is_err());
+    assert!(compare_svg("<g></g>", "<g></g><g></g>").is_err());
+}